@@ -0,0 +1,6 @@
+fn main() {
+    // Declares the custom cfgs so newer compilers do not warn about them. The `lints` table of the
+    // manifest is not used as it requires a newer cargo than the minimum supported version.
+    println!("cargo:rustc-check-cfg=cfg(documenting)");
+    println!("cargo:rerun-if-changed=build.rs");
+}
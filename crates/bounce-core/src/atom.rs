@@ -0,0 +1,71 @@
+use std::any::{Any, TypeId};
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+
+/// A simple reference counted state.
+///
+/// This trait is implemented by deriving `Atom` with Bounce, see
+/// [`Atom`](https://docs.rs/bounce/latest/bounce/derive.Atom.html) for more information.
+pub trait Atom: PartialEq + Default {
+    /// Applies a notion.
+    ///
+    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
+    /// atom using [`PartialEq`].
+    #[allow(unused_variables)]
+    fn apply(self: Rc<Self>, notion: Rc<dyn Any>) -> Rc<Self> {
+        self
+    }
+
+    /// Returns `true` if the atom accepts the notion in its current state.
+    #[allow(unused_variables)]
+    fn accepts(&self, notion: &dyn Any) -> bool {
+        true
+    }
+
+    /// Returns a list of notion ids that this atom accepts.
+    fn notion_ids(&self) -> Vec<TypeId>;
+
+    /// Notifies an atom that its value has changed.
+    fn changed(self: Rc<Self>) {}
+
+    /// Notifies an atom that it has been created.
+    fn init(self: Rc<Self>) {}
+
+    /// Notifies an atom that its root is destroyed.
+    fn teardown(self: Rc<Self>) {}
+
+    /// Returns `true` if the atom should be notified that its value has changed from `prev`.
+    #[allow(unused_variables)]
+    fn should_observe(&self, prev: &Self) -> bool {
+        true
+    }
+
+    /// Creates a new atom with its initial value.
+    fn create(init_states: &mut AnyMap) -> Self
+    where
+        Self: 'static + Sized,
+    {
+        Self::create_provided(init_states).unwrap_or_default()
+    }
+
+    /// Creates a new atom with the initial value provided to the root (if any).
+    fn create_provided(init_states: &mut AnyMap) -> Option<Self>
+    where
+        Self: 'static + Sized,
+    {
+        init_states.remove()
+    }
+
+    /// Creates a new atom with its initial value derived from other states.
+    ///
+    /// Returns `None` if the atom is not created from states. `states` are the `BounceStates` of
+    /// the root that creates the atom.
+    #[allow(unused_variables)]
+    fn create_from_states(states: &dyn Any) -> Option<Self>
+    where
+        Self: 'static + Sized,
+    {
+        None
+    }
+}
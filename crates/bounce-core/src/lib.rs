@@ -0,0 +1,82 @@
+//! The parts of Bounce that do not depend on Yew or WebAssembly.
+//!
+//! Shared logic crates can depend on this crate to implement the rules of states (e.g.: how a
+//! state applies a notion) and test them natively, without compiling Yew. All items are
+//! re-exported by [Bounce](https://docs.rs/bounce/), which provides the derive macros, the
+//! `<BounceRoot />` and the hooks.
+//!
+//! # Scope
+//!
+//! This crate contains the [`Atom`] and [`Slice`] traits, [`WithNotion`], [`SelectorResult`] and
+//! the [`StateDescriptor`] of registered states. A type that implements [`Slice`] in a shared
+//! logic crate can be used with the hooks of Bounce directly, while the derive macros of Bounce
+//! implement these traits on top of `yew::Reducible`.
+//!
+//! The `Selector` traits and the containers that hold states stay in Bounce: selectors read
+//! states through the `BounceStates` of a root, which notifies Yew components. Shared logic
+//! crates keep the rules of selectors on plain types, and the states of the application delegate
+//! to them as shown below.
+//!
+//! # Example
+//!
+//! ```
+//! use std::rc::Rc;
+//!
+//! use bounce_core::WithNotion;
+//!
+//! pub struct Reset;
+//!
+//! #[derive(Debug, PartialEq, Default)]
+//! pub struct Cart {
+//!     pub items: Vec<u64>,
+//! }
+//!
+//! impl WithNotion<Reset> for Cart {
+//!     fn apply(self: Rc<Self>, _notion: Rc<Reset>) -> Rc<Self> {
+//!         Self::default().into()
+//!     }
+//! }
+//!
+//! // The rules can be tested natively.
+//! let cart = Rc::new(Cart { items: vec![1, 2] });
+//! assert_eq!(*cart.apply(Rc::new(Reset)), Cart::default());
+//! ```
+//!
+//! A state in an application that depends on Bounce can then delegate to the shared rules:
+//!
+//! ```ignore
+//! #[derive(Atom, PartialEq, Default)]
+//! #[bounce(with_notion(Reset))]
+//! struct CartAtom {
+//!     inner: Rc<Cart>,
+//! }
+//!
+//! impl WithNotion<Reset> for CartAtom {
+//!     fn apply(self: Rc<Self>, notion: Rc<Reset>) -> Rc<Self> {
+//!         Self {
+//!             inner: self.inner.clone().apply(notion),
+//!         }
+//!         .into()
+//!     }
+//! }
+//! ```
+
+#![deny(clippy::all)]
+#![deny(missing_debug_implementations)]
+#![deny(unsafe_code)]
+#![deny(non_snake_case)]
+#![deny(clippy::cognitive_complexity)]
+#![deny(missing_docs)]
+#![cfg_attr(documenting, feature(doc_cfg))]
+
+mod atom;
+mod notion;
+mod registry;
+mod selector;
+mod slice;
+
+pub use atom::Atom;
+pub use notion::WithNotion;
+pub use registry::{StateDescriptor, StateKind};
+pub use selector::SelectorResult;
+pub use slice::Slice;
@@ -0,0 +1,76 @@
+use std::rc::Rc;
+
+/// A trait to apply a notion on a state.
+///
+/// A state must be registered for the notion with `#[bounce(with_notion(Notion))]`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce_core::WithNotion;
+/// #
+/// pub struct Reset;
+///
+/// #[derive(PartialEq, Default)]
+/// struct Username {
+///     inner: String,
+/// }
+///
+/// impl WithNotion<Reset> for Username {
+///     fn apply(self: Rc<Self>, _notion: Rc<Reset>) -> Rc<Self> {
+///         Self::default().into()
+///     }
+/// }
+/// ```
+///
+/// See: [`use_notion_applier`](https://docs.rs/bounce/latest/bounce/fn.use_notion_applier.html)
+pub trait WithNotion<T: 'static> {
+    /// Applies a notion on current state.
+    ///
+    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
+    /// state using [`PartialEq`].
+    fn apply(self: Rc<Self>, notion: Rc<T>) -> Rc<Self>;
+
+    /// Returns `true` if the notion should be applied on the current state.
+    ///
+    /// Notions that are not accepted are not applied and the state is left unchanged. This can
+    /// be used to ignore notions that are broadcast to many states but are only applicable to
+    /// some of their values without handling this in [`apply`](Self::apply).
+    ///
+    /// Defaults to accepting all notions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use bounce_core::WithNotion;
+    /// pub struct Cancel;
+    ///
+    /// #[derive(PartialEq, Default)]
+    /// enum Upload {
+    ///     #[default]
+    ///     Idle,
+    ///     Uploading,
+    ///     Cancelled,
+    /// }
+    ///
+    /// impl WithNotion<Cancel> for Upload {
+    ///     fn accepts(&self, _notion: &Cancel) -> bool {
+    ///         // Only an upload in progress can be cancelled.
+    ///         matches!(self, Self::Uploading)
+    ///     }
+    ///
+    ///     fn apply(self: Rc<Self>, _notion: Rc<Cancel>) -> Rc<Self> {
+    ///         Self::Cancelled.into()
+    ///     }
+    /// }
+    ///
+    /// assert!(!Upload::Idle.accepts(&Cancel));
+    /// assert!(Upload::Uploading.accepts(&Cancel));
+    /// ```
+    #[allow(unused_variables)]
+    fn accepts(&self, notion: &T) -> bool {
+        true
+    }
+}
@@ -0,0 +1,58 @@
+use std::any::{type_name, TypeId};
+
+/// The kind of a registered state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StateKind {
+    /// A [`Slice`](crate::Slice).
+    Slice,
+    /// An [`Atom`](crate::Atom).
+    Atom,
+    /// A [`Selector`](https://docs.rs/bounce/latest/bounce/trait.Selector.html).
+    Selector,
+    /// An [`InputSelector`](https://docs.rs/bounce/latest/bounce/trait.InputSelector.html).
+    InputSelector,
+    /// The artifacts of an [`Artifact`](https://docs.rs/bounce/latest/bounce/struct.Artifact.html)
+    /// type.
+    Artifact,
+    /// A [`Query`](https://docs.rs/bounce/latest/bounce/query/trait.Query.html).
+    #[cfg(feature = "query")]
+    #[cfg_attr(documenting, doc(cfg(feature = "query")))]
+    Query,
+}
+
+/// A description of a state that is registered under a bounce root.
+///
+/// See: [`use_registered_states`](https://docs.rs/bounce/latest/bounce/fn.use_registered_states.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDescriptor {
+    /// The type id of the state.
+    ///
+    /// For atoms, selectors, artifacts and queries, this is the type id of the type that
+    /// implements the trait, not the type id of the internal state that stores it.
+    pub type_id: TypeId,
+    /// The type name of the state, as returned by [`std::any::type_name`].
+    pub type_name: &'static str,
+    /// The kind of the state.
+    pub kind: StateKind,
+    /// The number of listeners currently subscribed to the state.
+    ///
+    /// Listeners include components that use the state and selectors that read it.
+    /// For input selectors and queries, this is the total across all inputs.
+    pub listener_count: usize,
+}
+
+impl StateDescriptor {
+    /// Creates a description of the state `T`.
+    pub fn new<T>(kind: StateKind, listener_count: usize) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            kind,
+            listener_count,
+        }
+    }
+}
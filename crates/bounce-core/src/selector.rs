@@ -0,0 +1,61 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// The result of a
+/// [`TrySelector`](https://docs.rs/bounce/latest/bounce/trait.TrySelector.html).
+pub enum SelectorResult<T> {
+    /// The value is ready.
+    Ready(Rc<T>),
+    /// The value is not ready yet, e.g.: a state it depends on is initialised asynchronously.
+    NotReady,
+}
+
+impl<T> SelectorResult<T> {
+    /// Returns `true` if the value is ready.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Ready(_))
+    }
+}
+
+impl<T> From<Option<Rc<T>>> for SelectorResult<T> {
+    fn from(m: Option<Rc<T>>) -> Self {
+        match m {
+            Some(m) => Self::Ready(m),
+            None => Self::NotReady,
+        }
+    }
+}
+
+impl<T> Clone for SelectorResult<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Ready(m) => Self::Ready(m.clone()),
+            Self::NotReady => Self::NotReady,
+        }
+    }
+}
+
+impl<T> PartialEq for SelectorResult<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Ready(lhs), Self::Ready(rhs)) => lhs == rhs,
+            (Self::NotReady, Self::NotReady) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SelectorResult<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ready(m) => f.debug_tuple("Ready").field(m).finish(),
+            Self::NotReady => write!(f, "NotReady"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::any::{Any, TypeId};
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+
+use crate::registry::{StateDescriptor, StateKind};
+
+/// A reducer-based state that is Copy-on-Write.
+///
+/// This trait is implemented by deriving `Slice` with Bounce, see
+/// [`Slice`](https://docs.rs/bounce/latest/bounce/derive.Slice.html) for more information.
+///
+/// A shared logic crate can also implement it without depending on Bounce, the slice can then be
+/// used with the hooks of Bounce.
+///
+/// # Example
+///
+/// ```
+/// use std::any::TypeId;
+/// use std::rc::Rc;
+///
+/// use bounce_core::Slice;
+///
+/// #[derive(Debug, PartialEq, Default)]
+/// pub struct Counter(u64);
+///
+/// impl Slice for Counter {
+///     type Action = u64;
+///
+///     fn reduce(self: Rc<Self>, action: u64) -> Rc<Self> {
+///         Self(self.0 + action).into()
+///     }
+///
+///     fn notion_ids(&self) -> Vec<TypeId> {
+///         Vec::new()
+///     }
+/// }
+///
+/// // The reducer can be tested natively.
+/// let counter = Rc::new(Counter::default()).reduce(2);
+/// assert_eq!(*counter, Counter(2));
+/// ```
+pub trait Slice: PartialEq + Default {
+    /// The action to reduce the slice with.
+    type Action;
+
+    /// Performs a reduce action.
+    ///
+    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
+    /// slice using [`PartialEq`].
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self>;
+
+    /// Applies a notion.
+    ///
+    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
+    /// slice using [`PartialEq`].
+    #[allow(unused_variables)]
+    fn apply(self: Rc<Self>, notion: Rc<dyn Any>) -> Rc<Self> {
+        self
+    }
+
+    /// Returns `true` if the slice accepts the notion in its current state.
+    #[allow(unused_variables)]
+    fn accepts(&self, notion: &dyn Any) -> bool {
+        true
+    }
+
+    /// Returns a list of notion ids that this Slice accepts.
+    fn notion_ids(&self) -> Vec<TypeId>;
+
+    /// Notifies a slice that it has changed.
+    fn changed(self: Rc<Self>) {}
+
+    /// Notifies a slice that it has been created.
+    fn init(self: Rc<Self>) {}
+
+    /// Notifies a slice that its root is destroyed.
+    fn teardown(self: Rc<Self>) {}
+
+    /// Returns `true` if the slice should be notified that it has changed from `prev`.
+    #[allow(unused_variables)]
+    fn should_observe(&self, prev: &Self) -> bool {
+        true
+    }
+
+    /// Creates a new slice with its initial value.
+    fn create(init_states: &mut AnyMap) -> Self
+    where
+        Self: 'static + Sized,
+    {
+        Self::create_provided(init_states).unwrap_or_default()
+    }
+
+    /// Creates a new slice with the initial value provided to the root (if any).
+    fn create_provided(init_states: &mut AnyMap) -> Option<Self>
+    where
+        Self: 'static + Sized,
+    {
+        init_states.remove()
+    }
+
+    /// Describes the slice for `use_registered_states`.
+    fn describe_state(listener_count: usize) -> StateDescriptor
+    where
+        Self: 'static + Sized,
+    {
+        StateDescriptor::new::<Self>(StateKind::Slice, listener_count)
+    }
+
+    /// Creates a new slice with its initial value derived from other states.
+    ///
+    /// Returns `None` if the slice is not created from states. `states` are the `BounceStates` of
+    /// the root that creates the slice.
+    #[allow(unused_variables)]
+    fn create_from_states(states: &dyn Any) -> Option<Self>
+    where
+        Self: 'static + Sized,
+    {
+        None
+    }
+}
@@ -0,0 +1,47 @@
+//! Helpers to test the procedural macros of [Bounce](https://docs.rs/bounce/).
+//!
+//! The error messages and the accepted syntaxes of the macros are tested with
+//! [trybuild](https://docs.rs/trybuild/): test files that are expected to compile and test files
+//! that are expected to fail with the error messages in the `.stderr` file next to them.
+//!
+//! Crates that build on the macros of Bounce (e.g.: with macros that expand to
+//! `#[derive(Atom)]`) can use the same layout for their own tests.
+
+#![deny(clippy::all)]
+#![deny(missing_debug_implementations)]
+#![deny(unsafe_code)]
+#![deny(non_snake_case)]
+#![deny(missing_docs)]
+
+use std::path::Path;
+
+/// Runs the UI tests in a directory.
+///
+/// - Each file in `dir/pass` must compile.
+/// - Each file in `dir/fail` must fail to compile with the errors in the `.stderr` file with
+///   the same name.
+///
+/// Expected errors can be updated by running the tests with `TRYBUILD=overwrite`.
+///
+/// # Panics
+///
+/// Panics if any test does not have the expected result.
+///
+/// # Example
+///
+/// ```no_run
+/// // tests/ui.rs
+/// #[test]
+/// fn ui() {
+///     bounce_macros_testing::run_ui_tests("tests/ui");
+/// }
+/// ```
+pub fn run_ui_tests(dir: impl AsRef<Path>) {
+    let dir = dir.as_ref();
+    let cases = trybuild::TestCases::new();
+
+    cases.pass(dir.join("pass/*.rs"));
+    cases.compile_fail(dir.join("fail/*.rs"));
+
+    // Tests are run when cases are dropped.
+}
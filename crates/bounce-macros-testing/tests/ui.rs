@@ -0,0 +1,11 @@
+#[test]
+fn ui() {
+    bounce_macros_testing::run_ui_tests("tests/ui");
+}
+
+// The writer of the helmet bridge is only required in server-side rendering builds.
+#[cfg(feature = "ssr")]
+#[test]
+fn ui_ssr() {
+    bounce_macros_testing::run_ui_tests("tests/ui/ssr");
+}
@@ -0,0 +1,26 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+use yew::prelude::*;
+
+pub enum CounterAction {
+    Increment,
+}
+
+#[derive(Slice, PartialEq, Default)]
+#[bounce(child)]
+pub struct Counter(u64);
+
+impl Reducible for Counter {
+    type Action = CounterAction;
+
+    fn reduce(self: Rc<Self>, _action: Self::Action) -> Rc<Self> {
+        Self(self.0 + 1).into()
+    }
+}
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(child, from_states)]
+pub struct EditorSettings(u32);
+
+fn main() {}
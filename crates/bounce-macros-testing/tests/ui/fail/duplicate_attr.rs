@@ -0,0 +1,13 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(observed, observed)]
+pub struct Theme(String);
+
+impl Observed for Theme {
+    fn changed(self: Rc<Self>) {}
+}
+
+fn main() {}
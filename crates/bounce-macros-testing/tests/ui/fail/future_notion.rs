@@ -0,0 +1,26 @@
+use bounce::prelude::*;
+
+#[future_notion]
+fn NotAsync(input: &u64) -> u64 {
+    *input
+}
+
+#[future_notion]
+async fn TooManyArgs(_states: &BounceStates, input: &u64, _other: &u64) -> u64 {
+    *input
+}
+
+#[future_notion]
+async fn NotReference(input: u64) -> u64 {
+    input
+}
+
+#[future_notion]
+async fn NoArgs() {}
+
+#[future_notion(FetchUser, FetchProject)]
+async fn fetch(input: &u64) -> u64 {
+    *input
+}
+
+fn main() {}
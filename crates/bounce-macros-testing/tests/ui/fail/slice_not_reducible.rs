@@ -0,0 +1,6 @@
+use bounce::prelude::*;
+
+#[derive(Slice, PartialEq, Default)]
+pub struct Counter(u64);
+
+fn main() {}
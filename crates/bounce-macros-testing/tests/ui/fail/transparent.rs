@@ -0,0 +1,41 @@
+use bounce::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Position {
+    x: u32,
+    y: u32,
+}
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Empty;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub enum Status {
+    #[default]
+    Idle,
+}
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent(inner))]
+pub struct Username(String);
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent, transparent)]
+pub struct Email(String);
+
+#[derive(Slice, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Counter(u64);
+
+impl Reducible for Counter {
+    type Action = ();
+
+    fn reduce(self: std::rc::Rc<Self>, _action: ()) -> std::rc::Rc<Self> {
+        self
+    }
+}
+
+fn main() {}
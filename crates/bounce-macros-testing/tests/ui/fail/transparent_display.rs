@@ -0,0 +1,10 @@
+use bounce::prelude::*;
+
+// `Display` is only implemented if the inner type implements `Display`.
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Tags(Vec<String>);
+
+fn main() {
+    let _ = Tags::default().to_string();
+}
@@ -0,0 +1,28 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+use yew::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(obsrved)]
+pub struct Theme(String);
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(persist)]
+pub struct Locale(String);
+
+#[derive(Slice, PartialEq, Default)]
+#[bounce(with_notoin(Reset))]
+pub struct Counter(u64);
+
+pub struct Reset;
+
+impl Reducible for Counter {
+    type Action = ();
+
+    fn reduce(self: Rc<Self>, _action: Self::Action) -> Rc<Self> {
+        self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use bounce::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(variants)]
+pub struct Username(String);
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(variants(Idle))]
+pub enum Status {
+    #[default]
+    Idle,
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+use yew::prelude::*;
+
+#[derive(Atom, Default)]
+#[bounce(eq = "Document::same_revision")]
+pub struct Document {
+    pub revision: u64,
+    pub nodes: Rc<Vec<String>>,
+}
+
+impl Document {
+    fn same_revision(&self, other: &Self) -> bool {
+        self.revision == other.revision
+    }
+}
+
+pub enum GraphAction {
+    Touch,
+}
+
+#[derive(Slice, Default)]
+#[bounce(eq = "graph_eq")]
+pub struct Graph {
+    pub edges: Rc<Vec<(u64, u64)>>,
+}
+
+fn graph_eq(lhs: &Graph, rhs: &Graph) -> bool {
+    Rc::ptr_eq(&lhs.edges, &rhs.edges)
+}
+
+impl Reducible for Graph {
+    type Action = GraphAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            GraphAction::Touch => self,
+        }
+    }
+}
+
+fn main() {}
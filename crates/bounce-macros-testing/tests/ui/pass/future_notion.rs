@@ -0,0 +1,25 @@
+use bounce::prelude::*;
+
+// With states and input.
+#[future_notion]
+async fn FetchUser(_states: &BounceStates, input: &u64) -> String {
+    format!("User {input}")
+}
+
+// With input only.
+#[future_notion]
+async fn Double(input: &u64) -> u64 {
+    *input * 2
+}
+
+// Without output.
+#[future_notion]
+async fn Ping(_input: &()) {}
+
+// With the name of the notion in the attribute.
+#[future_notion(FetchProject)]
+async fn fetch_project(_states: &BounceStates, input: &u64) -> String {
+    format!("Project {input}")
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+use yew::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(observed)]
+pub struct Theme(String);
+
+impl Observed for Theme {
+    fn changed(self: Rc<Self>) {}
+}
+
+pub enum DraftAction {
+    Save,
+}
+
+#[derive(Slice, PartialEq, Default)]
+#[bounce(observed(if = "Self::should_persist"))]
+pub struct Draft {
+    saved: bool,
+}
+
+impl Draft {
+    fn should_persist(prev: &Self, next: &Self) -> bool {
+        !prev.saved && next.saved
+    }
+}
+
+impl Reducible for Draft {
+    type Action = DraftAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            DraftAction::Save => Self { saved: true }.into(),
+        }
+    }
+}
+
+impl Observed for Draft {
+    fn changed(self: Rc<Self>) {}
+}
+
+fn main() {}
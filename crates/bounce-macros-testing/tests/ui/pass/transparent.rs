@@ -0,0 +1,53 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Username(String);
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct FontSize {
+    inner: u32,
+}
+
+// The inner type does not need to implement `Display`.
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Tags(Vec<String>);
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Wrapper<T>(T)
+where
+    T: PartialEq + Default + 'static;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent, observed)]
+pub struct Theme(String);
+
+impl Observed for Theme {
+    fn changed(self: Rc<Self>) {}
+}
+
+fn main() {
+    let username = Username::from("Jane Doe".to_string());
+    assert_eq!(&*username, "Jane Doe");
+    assert_eq!(username.to_string(), "Jane Doe");
+    assert_eq!(username.inner(), "Jane Doe");
+    assert_eq!(username.into_inner(), "Jane Doe");
+
+    let font_size = FontSize::from(12);
+    assert_eq!(*font_size, 12);
+    assert_eq!(format!("{font_size}px"), "12px");
+
+    let tags = Tags::from(vec!["a".to_string()]);
+    assert_eq!(tags.len(), 1);
+
+    let wrapper = Wrapper::from(1u64);
+    assert_eq!(wrapper.to_string(), "1");
+    assert_eq!(wrapper.into_inner(), 1);
+
+    assert_eq!(Theme::from("dark".to_string()).to_string(), "dark");
+}
@@ -0,0 +1,31 @@
+use bounce::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(transparent)]
+pub struct Username(String);
+
+#[derive(Atom, PartialEq, Default)]
+pub struct GlobalSettings {
+    font_size: u32,
+}
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(child)]
+pub struct EditorSettings {
+    font_size: u32,
+}
+
+impl ChildAtom for EditorSettings {
+    type Parent = GlobalSettings;
+
+    fn derive_from(parent: &GlobalSettings) -> Self {
+        Self {
+            font_size: parent.font_size,
+        }
+    }
+}
+
+fn main() {
+    let username = Username::from("Jane Doe".to_string());
+    assert_eq!(&*username, "Jane Doe");
+}
@@ -0,0 +1,33 @@
+use bounce::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(variants)]
+pub enum Status {
+    #[default]
+    Idle,
+    Loading {
+        progress: u32,
+    },
+    Failed(String),
+}
+
+#[derive(Atom, PartialEq, Default)]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+}
+
+fn main() {
+    assert_eq!(Status::Idle.variant(), StatusVariant::Idle);
+    assert_eq!(
+        Status::Loading { progress: 50 }.variant(),
+        StatusVariant::Loading
+    );
+    assert_eq!(
+        Status::Failed("timed out".into()).variant(),
+        StatusVariant::Failed
+    );
+
+    let _theme = Theme::Dark;
+}
@@ -0,0 +1,42 @@
+use std::rc::Rc;
+
+use bounce::prelude::*;
+
+pub struct Reset;
+pub struct Increment(u64);
+
+// Notions can be listed in 1 attribute or in separate attributes.
+#[derive(Atom, PartialEq, Default)]
+#[bounce(with_notion(Reset, Increment))]
+pub struct Counter(u64);
+
+#[derive(Atom, PartialEq, Default)]
+#[bounce(with_notion(Reset))]
+#[bounce(with_notion(Increment))]
+pub struct OtherCounter(u64);
+
+impl WithNotion<Reset> for Counter {
+    fn apply(self: Rc<Self>, _notion: Rc<Reset>) -> Rc<Self> {
+        Self::default().into()
+    }
+}
+
+impl WithNotion<Increment> for Counter {
+    fn apply(self: Rc<Self>, notion: Rc<Increment>) -> Rc<Self> {
+        Self(self.0 + notion.0).into()
+    }
+}
+
+impl WithNotion<Reset> for OtherCounter {
+    fn apply(self: Rc<Self>, _notion: Rc<Reset>) -> Rc<Self> {
+        Self::default().into()
+    }
+}
+
+impl WithNotion<Increment> for OtherCounter {
+    fn apply(self: Rc<Self>, notion: Rc<Increment>) -> Rc<Self> {
+        Self(self.0 + notion.0).into()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use bounce::helmet::HelmetBridge;
+use yew::prelude::*;
+
+#[function_component(App)]
+fn app() -> Html {
+    html! {
+        <HelmetBridge default_title="My Site" />
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use bounce::helmet::{CsrOnly, HelmetBridge, StaticWriter};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+struct ServerAppProps {
+    writer: StaticWriter,
+}
+
+#[function_component(ServerApp)]
+fn server_app(props: &ServerAppProps) -> Html {
+    html! {
+        <HelmetBridge default_title="My Site" writer={props.writer.clone()} />
+    }
+}
+
+#[function_component(ClientApp)]
+fn client_app() -> Html {
+    html! {
+        <HelmetBridge default_title="My Site" writer={CsrOnly} />
+    }
+}
+
+fn main() {}
@@ -0,0 +1,97 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Attribute, DeriveInput, Ident, Path, Token};
+
+pub(crate) struct ApplyAttr {
+    ident: Ident,
+    path: Path,
+}
+
+impl Parse for ApplyAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+
+        if ident != "apply" {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("unknown attribute `{ident}`, expected `apply`"),
+            ));
+        }
+
+        if !input.peek(Token![=]) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`apply` expects a function, e.g.: `apply = apply_title`",
+            ));
+        }
+
+        input.parse::<Token![=]>()?;
+        let path = input.parse::<Path>()?;
+
+        Ok(Self { ident, path })
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ArtifactAttrs {
+    apply: Option<ApplyAttr>,
+}
+
+impl ArtifactAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut this = Self::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("bounce") {
+                continue;
+            }
+
+            let parsed = attr.parse_args_with(Punctuated::<ApplyAttr, Comma>::parse_terminated)?;
+
+            for m in parsed {
+                if this.apply.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        m.ident,
+                        "you can only have 1 apply attribute",
+                    ));
+                }
+
+                this.apply = Some(m);
+            }
+        }
+
+        Ok(this)
+    }
+}
+
+pub(crate) fn macro_fn(input: DeriveInput) -> TokenStream {
+    let artifact_attrs = match ArtifactAttrs::parse(&input.attrs) {
+        Ok(m) => m,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let impl_apply = artifact_attrs.apply.map(|ApplyAttr { path, .. }| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::bounce::ApplyArtifacts for #ident #ty_generics #where_clause {
+                fn apply(values: &[::std::rc::Rc<Self>]) {
+                    #path(values)
+                }
+            }
+        }
+    });
+
+    // Errors of unsatisfied bounds (e.g.: missing `PartialEq`) point to the type.
+    quote_spanned! { ident.span() =>
+        #[automatically_derived]
+        impl #impl_generics ::bounce::ArtifactValue for #ident #ty_generics #where_clause {}
+
+        #impl_apply
+    }
+}
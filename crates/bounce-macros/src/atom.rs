@@ -1,8 +1,8 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::{DeriveInput, Ident};
+use quote::{quote, quote_spanned};
+use syn::{Data, DataEnum, DeriveInput, Fields, Generics, Ident, Index, Member, Visibility};
 
-use super::slice::BounceAttrs;
+use super::slice::{BounceAttrs, TransparentAttr};
 
 pub(crate) fn macro_fn(input: DeriveInput) -> TokenStream {
     let bounce_attrs = match BounceAttrs::parse(&input.attrs) {
@@ -12,34 +12,219 @@ pub(crate) fn macro_fn(input: DeriveInput) -> TokenStream {
 
     let notion_ident = Ident::new("notion", Span::mixed_site());
     let notion_apply_impls = bounce_attrs.create_notion_apply_impls(&notion_ident);
-    let notion_ids_impls = bounce_attrs.create_notion_id_impls();
+    let notion_accepts_impls = bounce_attrs.create_notion_accepts_impls(&notion_ident);
+    let mut notion_ids_impls = bounce_attrs.create_notion_id_impls();
+
+    // Child atoms accept a notion to derive their value from their parent again.
+    let (resync_apply_impl, resync_accepts_impl) = match bounce_attrs.child {
+        Some(_) => {
+            notion_ids_impls
+                .push(quote! { ::std::any::TypeId::of::<::bounce::ResyncNotion<Self>>() });
+
+            (
+                Some(quote! {
+                    let #notion_ident = match <::std::rc::Rc::<dyn ::std::any::Any>>::downcast::<::bounce::ResyncNotion<Self>>(#notion_ident) {
+                        ::std::result::Result::Ok(m) => return ::std::rc::Rc::new(<Self as ::bounce::ChildAtom>::derive_from(m.parent())),
+                        ::std::result::Result::Err(e) => e,
+                    };
+                }),
+                Some(quote! {
+                    if <dyn ::std::any::Any>::is::<::bounce::ResyncNotion<Self>>(#notion_ident) {
+                        return true;
+                    }
+                }),
+            )
+        }
+        None => (None, None),
+    };
 
     let ident = input.ident;
 
+    let impl_enum_atom = match (&bounce_attrs.variants, &input.data) {
+        (Some(_), Data::Enum(ref m)) => Some(create_enum_atom_impl(
+            &ident,
+            &input.vis,
+            &input.generics,
+            m,
+        )),
+        (Some(m), _) => {
+            return syn::Error::new_spanned(&m.ident, "`variants` can only be used on enums")
+                .into_compile_error()
+        }
+        (None, _) => None,
+    };
+
+    let impl_transparent = match bounce_attrs.transparent {
+        Some(ref m) => match create_transparent_impl(&ident, &input.generics, &input.data, m) {
+            Ok(m) => Some(m),
+            Err(e) => return e.into_compile_error(),
+        },
+        None => None,
+    };
+
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let impl_observed = bounce_attrs.observed.is_some().then(|| {
-        quote! {
-            fn changed(self: ::std::rc::Rc<Self>) {
-                ::bounce::Observed::changed(self);
-            }
-        }
-    });
+    let impl_from_states = bounce_attrs.create_from_states_impl();
 
-    quote! {
+    let impl_observed = bounce_attrs.create_observed_impl();
+
+    let impl_eq = bounce_attrs.create_eq_impl(&ident, &input.generics);
+
+    // Errors of unsatisfied bounds (e.g.: missing `PartialEq` or `Default`) point to the type.
+    quote_spanned! { ident.span() =>
         #[automatically_derived]
         impl #impl_generics ::bounce::Atom for #ident #ty_generics #where_clause {
             fn apply(self: ::std::rc::Rc<Self>, #notion_ident: ::std::rc::Rc<dyn ::std::any::Any>) -> ::std::rc::Rc<Self> {
+                #resync_apply_impl
+
                 #(#notion_apply_impls)*
 
                 self
             }
 
+            fn accepts(&self, #notion_ident: &dyn ::std::any::Any) -> bool {
+                #resync_accepts_impl
+
+                #(#notion_accepts_impls)*
+
+                false
+            }
+
             fn notion_ids(&self) -> ::std::vec::Vec<::std::any::TypeId> {
                 ::std::vec![#(#notion_ids_impls,)*]
             }
 
             #impl_observed
+
+            #impl_from_states
+        }
+
+        #impl_enum_atom
+
+        #impl_transparent
+
+        #impl_eq
+    }
+}
+
+/// Implements `From`, `Deref`, `Display` and `TransparentAtom` for a newtype atom.
+fn create_transparent_impl(
+    ident: &Ident,
+    generics: &Generics,
+    data: &Data,
+    attr: &TransparentAttr,
+) -> syn::Result<TokenStream> {
+    let field = match data {
+        Data::Struct(m) if m.fields.len() == 1 => m.fields.iter().next(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            &attr.ident,
+            "`transparent` can only be used on structs with exactly 1 field",
+        )
+    })?;
+
+    let inner_ty = &field.ty;
+    let member = match field.ident {
+        Some(ref m) => Member::Named(m.clone()),
+        None => Member::Unnamed(Index::from(0)),
+    };
+    let construct = match data {
+        Data::Struct(m) if matches!(m.fields, Fields::Named(_)) => {
+            quote! { Self { #member: inner } }
+        }
+        _ => quote! { Self(inner) },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The higher-ranked bound defers the check of the bound to where the implementation is used,
+    // so atoms with an inner type that does not implement `Display` can still be transparent.
+    let mut display_generics = generics.clone();
+    display_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { for<'__bounce> #inner_ty: ::std::fmt::Display });
+    let display_where_clause = &display_generics.where_clause;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::std::convert::From<#inner_ty> for #ident #ty_generics #where_clause {
+            fn from(inner: #inner_ty) -> Self {
+                #construct
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::std::ops::Deref for #ident #ty_generics #where_clause {
+            type Target = #inner_ty;
+
+            fn deref(&self) -> &Self::Target {
+                &self.#member
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::std::fmt::Display for #ident #ty_generics #display_where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.#member, f)
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::bounce::TransparentAtom for #ident #ty_generics #where_clause {
+            type Inner = #inner_ty;
+
+            fn inner(&self) -> &Self::Inner {
+                &self.#member
+            }
+
+            fn into_inner(self) -> Self::Inner {
+                self.#member
+            }
+        }
+    })
+}
+
+/// Creates an enum that contains the variants of an enum atom without their fields and
+/// implements `EnumAtom` for the atom.
+fn create_enum_atom_impl(
+    ident: &Ident,
+    vis: &Visibility,
+    generics: &Generics,
+    data: &DataEnum,
+) -> TokenStream {
+    let variant_enum_ident = Ident::new(&format!("{ident}Variant"), ident.span());
+    let variant_idents = data.variants.iter().map(|m| &m.ident).collect::<Vec<_>>();
+
+    let enum_doc = format!("The variants of [`{ident}`].");
+    let variant_docs = variant_idents
+        .iter()
+        .map(|m| format!("The variant `{ident}::{m}`."))
+        .collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[doc = #enum_doc]
+        #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy, ::std::cmp::PartialEq, ::std::cmp::Eq, ::std::hash::Hash)]
+        #vis enum #variant_enum_ident {
+            #(
+                #[doc = #variant_docs]
+                #variant_idents,
+            )*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::bounce::EnumAtom for #ident #ty_generics #where_clause {
+            type Variant = #variant_enum_ident;
+
+            fn variant(&self) -> Self::Variant {
+                match self {
+                    #(Self::#variant_idents { .. } => #variant_enum_ident::#variant_idents,)*
+                }
+            }
         }
     }
 }
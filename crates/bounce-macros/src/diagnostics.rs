@@ -0,0 +1,73 @@
+//! Helpers to produce actionable error messages.
+
+use proc_macro2::Span;
+use syn::Ident;
+
+/// Attributes accepted by `#[bounce(...)]`.
+pub(crate) const BOUNCE_ATTRS: &[&str] = &[
+    "with_notion",
+    "observed",
+    "from_states",
+    "transparent",
+    "variants",
+    "child",
+    "eq",
+];
+
+/// Returns the Levenshtein distance between 2 strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev_row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            row[j + 1] = (prev_row[j] + cost)
+                .min(prev_row[j + 1] + 1)
+                .min(row[j] + 1);
+        }
+
+        prev_row = row;
+    }
+
+    prev_row[b.len()]
+}
+
+/// Returns the candidate that is the most similar to `name`, if any candidate is similar enough.
+fn find_similar<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (name.len() / 3).max(1);
+
+    candidates
+        .iter()
+        .map(|m| (edit_distance(name, m), *m))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, m)| m)
+}
+
+/// Creates an error for an unknown attribute inside `#[bounce(...)]`.
+pub(crate) fn unknown_attr(ident: &Ident) -> syn::Error {
+    let name = ident.to_string();
+    let expected = BOUNCE_ATTRS
+        .iter()
+        .map(|m| format!("`{m}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let message = match find_similar(&name, BOUNCE_ATTRS) {
+        Some(m) => format!("unknown attribute `{name}`, did you mean `{m}`?"),
+        None => format!("unknown attribute `{name}`, expected one of: {expected}"),
+    };
+
+    syn::Error::new(ident.span(), message)
+}
+
+/// Creates an error for a token that is not an attribute inside `#[bounce(...)]`.
+pub(crate) fn expected_attr(span: Span) -> syn::Error {
+    syn::Error::new(
+        span,
+        "expected an attribute, e.g.: `with_notion(Notion)` or `observed`",
+    )
+}
@@ -12,9 +12,15 @@ pub struct FutureNotionAttr {
 
 impl Parse for FutureNotionAttr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        Ok(Self {
-            name: input.parse()?,
-        })
+        let name = input.parse()?;
+
+        if !input.is_empty() {
+            return Err(
+                input.error("expected the name of the notion, e.g.: `#[future_notion(FetchUser)]`")
+            );
+        }
+
+        Ok(Self { name })
     }
 }
 
@@ -35,7 +41,7 @@ impl AsyncFnProps {
 
         if item.sig.asyncness.is_none() {
             return Err(syn::Error::new_spanned(
-                name,
+                item.sig.fn_token,
                 "future notions must be async functions",
             ));
         }
@@ -50,21 +56,40 @@ impl AsyncFnProps {
 
         let mut fn_args = item.sig.inputs.iter();
 
+        if let Some(m) = item.sig.inputs.iter().nth(2) {
+            return Err(syn::Error::new_spanned(
+                m,
+                "future notions accept at most 2 arguments: `&BounceStates` and the input",
+            ));
+        }
+
         let (input_arg, with_state) = match (fn_args.next(), fn_args.next()) {
-            (Some(_), Some(n)) => (n.clone(), true),
+            (Some(m), Some(n)) => {
+                match m {
+                    FnArg::Typed(m) if matches!(*m.ty, Type::Reference(_)) => {}
+                    m => {
+                        return Err(syn::Error::new_spanned(
+                            m,
+                            "the first argument of future notions with 2 arguments must be `&BounceStates`",
+                        ))
+                    }
+                }
+
+                (n.clone(), true)
+            }
             (Some(m), None) => (m.clone(), false),
             _ => {
                 return Err(syn::Error::new_spanned(
-                    item.sig.inputs.clone(),
+                    &item.sig,
                     "future notions must accept at least 1 argument",
                 ))
             }
         };
 
         let input_type = match input_arg {
-            FnArg::Receiver(_) => {
+            FnArg::Receiver(m) => {
                 return Err(syn::Error::new_spanned(
-                    item.sig.inputs.clone(),
+                    m,
                     "future notions do not accept self argument",
                 ))
             }
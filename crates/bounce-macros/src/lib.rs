@@ -2,8 +2,11 @@ use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
 use syn::{parse_macro_input, DeriveInput, ItemFn};
 
+mod artifact;
 mod atom;
+mod diagnostics;
 mod future_notion;
+mod settings;
 mod slice;
 
 #[proc_macro_derive(Atom, attributes(bounce))]
@@ -20,6 +23,20 @@ pub fn slice(input: TokenStream) -> TokenStream {
     slice::macro_fn(input).into()
 }
 
+#[proc_macro_derive(Artifact, attributes(bounce))]
+#[proc_macro_error]
+pub fn artifact(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    artifact::macro_fn(input).into()
+}
+
+#[proc_macro_derive(Settings, attributes(bounce))]
+#[proc_macro_error]
+pub fn settings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    settings::macro_fn(input).into()
+}
+
 #[proc_macro_attribute]
 pub fn future_notion(attr: TokenStream, item: TokenStream) -> TokenStream {
     let item = parse_macro_input!(item as ItemFn);
@@ -0,0 +1,222 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, LitInt, LitStr, Meta, Path,
+    Token, Type,
+};
+
+pub(crate) enum SettingsAttr {
+    Key(Ident, LitStr),
+    Version(Ident, LitInt),
+    Migrate(Ident, Path),
+}
+
+impl SettingsAttr {
+    fn ident(&self) -> &Ident {
+        match self {
+            Self::Key(m, _) | Self::Version(m, _) | Self::Migrate(m, _) => m,
+        }
+    }
+}
+
+impl Parse for SettingsAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+
+        let example = match ident.to_string().as_str() {
+            "key" => "`key = \"settings\"`",
+            "version" => "`version = 2`",
+            "migrate" => "`migrate = migrate_settings`",
+            _ => {
+                let message = format!(
+                    "unknown attribute `{ident}`, expected one of: `key`, `version`, `migrate`"
+                );
+                return Err(syn::Error::new(ident.span(), message));
+            }
+        };
+
+        if !input.peek(Token![=]) {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("`{ident}` expects a value, e.g.: {example}"),
+            ));
+        }
+
+        input.parse::<Token![=]>()?;
+
+        match ident.to_string().as_str() {
+            "key" => Ok(Self::Key(ident, input.parse()?)),
+            "version" => Ok(Self::Version(ident, input.parse()?)),
+            _ => Ok(Self::Migrate(ident, input.parse()?)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SettingsAttrs {
+    key: Option<LitStr>,
+    version: Option<LitInt>,
+    migrate: Option<Path>,
+}
+
+impl SettingsAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut this = Self::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("bounce") {
+                continue;
+            }
+
+            let parsed =
+                attr.parse_args_with(Punctuated::<SettingsAttr, Comma>::parse_terminated)?;
+
+            for m in parsed {
+                let duplicated = match m {
+                    SettingsAttr::Key(_, ref key) => this.key.replace(key.clone()).is_some(),
+                    SettingsAttr::Version(_, ref version) => {
+                        version.base10_parse::<u32>()?;
+                        this.version.replace(version.clone()).is_some()
+                    }
+                    SettingsAttr::Migrate(_, ref path) => {
+                        this.migrate.replace(path.clone()).is_some()
+                    }
+                };
+
+                if duplicated {
+                    return Err(syn::Error::new_spanned(
+                        m.ident(),
+                        format!("you can only have 1 {} attribute", m.ident()),
+                    ));
+                }
+            }
+        }
+
+        Ok(this)
+    }
+}
+
+/// Returns the documentation of a field, with the lines joined by spaces.
+fn doc_of(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|m| m.path().is_ident("doc"))
+        .filter_map(|m| match m.meta {
+            Meta::NameValue(ref m) => match m.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(ref s),
+                    ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the type as it is written in the source, e.g.: `Option<String>`.
+fn type_name(ty: &Type) -> String {
+    let tokens = quote! { #ty }.to_string().chars().collect::<Vec<_>>();
+    let is_ident_char = |c: Option<&char>| c.map(|m| m.is_alphanumeric() || *m == '_');
+
+    let mut name = String::new();
+
+    for (index, c) in tokens.iter().enumerate() {
+        // Spaces are only kept between 2 identifiers (e.g.: `dyn Trait`).
+        if *c == ' '
+            && (is_ident_char(name.chars().last().as_ref()) != Some(true)
+                || is_ident_char(tokens.get(index + 1)) != Some(true))
+        {
+            continue;
+        }
+
+        name.push(*c);
+    }
+
+    name
+}
+
+pub(crate) fn macro_fn(input: DeriveInput) -> TokenStream {
+    let settings_attrs = match SettingsAttrs::parse(&input.attrs) {
+        Ok(m) => m,
+        Err(e) => return e.into_compile_error(),
+    };
+
+    let ident = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(ref m) if matches!(m.fields, Fields::Named(_)) => &m.fields,
+        _ => {
+            return syn::Error::new(
+                ident.span(),
+                "`Settings` can only be derived on structs with named fields",
+            )
+            .into_compile_error()
+        }
+    };
+
+    let key = match settings_attrs.key {
+        Some(m) => m,
+        None => {
+            return syn::Error::new(
+                ident.span(),
+                "`Settings` requires a storage key, e.g.: `#[bounce(key = \"settings\")]`",
+            )
+            .into_compile_error()
+        }
+    };
+
+    let impl_version = settings_attrs.version.map(|m| {
+        quote! {
+            const VERSION: u32 = #m;
+        }
+    });
+
+    let impl_migrate = settings_attrs.migrate.map(|m| {
+        quote! {
+            fn migrate(
+                version: u32,
+                value: ::bounce::__vendored::serde_json::Value,
+            ) -> ::std::option::Option<::bounce::__vendored::serde_json::Value> {
+                #m(version, value)
+            }
+        }
+    });
+
+    let field_impls = fields.iter().map(|m| {
+        let field_ident = m.ident.as_ref().expect("fields are named");
+        let name = field_ident.to_string();
+        let ty = type_name(&m.ty);
+        let description = doc_of(&m.attrs);
+
+        quote! {
+            ::bounce::settings::SettingsField::new(#name, #ty, &default.#field_ident, #description)
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Errors of unsatisfied bounds (e.g.: missing `Serialize`) point to the type.
+    quote_spanned! { ident.span() =>
+        #[automatically_derived]
+        impl #impl_generics ::bounce::settings::Settings for #ident #ty_generics #where_clause {
+            const KEY: &'static str = #key;
+
+            #impl_version
+
+            #impl_migrate
+
+            fn fields() -> ::std::vec::Vec<::bounce::settings::SettingsField> {
+                let default = <Self as ::std::default::Default>::default();
+
+                ::std::vec![#(#field_impls,)*]
+            }
+        }
+    }
+}
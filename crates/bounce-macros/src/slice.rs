@@ -1,10 +1,11 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::parse::discouraged::Speculative;
+use quote::{quote, quote_spanned};
 use syn::parse::{Parse, ParseBuffer, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::token::Comma;
-use syn::{parenthesized, Attribute, DeriveInput, Ident, Type};
+use syn::token::{Comma, Paren};
+use syn::{parenthesized, Attribute, DeriveInput, ExprPath, Generics, Ident, LitStr, Token, Type};
+
+use crate::diagnostics;
 
 pub(crate) struct WithNotionAttr {
     notion_idents: Vec<Type>,
@@ -19,59 +20,191 @@ impl WithNotionAttr {
         Ok(content)
     }
 
-    fn try_parse(input: ParseStream<'_>) -> syn::Result<Option<Self>> {
-        let ident = input.parse::<Ident>()?;
-
-        if ident != "with_notion" {
-            return Ok(None);
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if !input.peek(Paren) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`with_notion` expects a list of notions, e.g.: `with_notion(Reset)`",
+            ));
         }
 
         let content = Self::parse_parens_content(input)?;
 
         let idents = Punctuated::<Type, Comma>::parse_terminated(&content)?;
 
-        Ok(Some(Self {
+        if idents.is_empty() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`with_notion` expects at least 1 notion",
+            ));
+        }
+
+        Ok(Self {
             notion_idents: idents.into_iter().collect(),
-        }))
+        })
     }
 }
 
 pub(crate) struct ObservedAttr {
     ident: Ident,
+    filter: Option<ExprPath>,
 }
 
 impl ObservedAttr {
-    fn try_parse(input: ParseStream<'_>) -> syn::Result<Option<Self>> {
-        let ident = input.parse::<Ident>()?;
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if !input.peek(Paren) {
+            return Ok(Self {
+                ident,
+                filter: None,
+            });
+        }
+
+        let content;
+        parenthesized!(content in input);
+
+        if !content.peek(Token![if]) {
+            return Err(syn::Error::new(
+                content.span(),
+                "`observed` only accepts a filter, e.g.: `observed(if = \"Self::should_persist\")`",
+            ));
+        }
+
+        content.parse::<Token![if]>()?;
+        content.parse::<Token![=]>()?;
+        let filter = content.parse::<LitStr>()?.parse::<ExprPath>()?;
+
+        if !content.is_empty() {
+            return Err(syn::Error::new(
+                content.span(),
+                "`observed` only accepts 1 filter",
+            ));
+        }
+
+        Ok(Self {
+            ident,
+            filter: Some(filter),
+        })
+    }
+}
+
+pub(crate) struct FromStatesAttr {
+    ident: Ident,
+}
+
+impl FromStatesAttr {
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Paren) {
+            return Err(syn::Error::new(
+                input.span(),
+                "`from_states` does not accept any arguments",
+            ));
+        }
+
+        Ok(Self { ident })
+    }
+}
+
+pub(crate) struct TransparentAttr {
+    pub ident: Ident,
+}
+
+impl TransparentAttr {
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Paren) {
+            return Err(syn::Error::new(
+                input.span(),
+                "`transparent` does not accept any arguments",
+            ));
+        }
+
+        Ok(Self { ident })
+    }
+}
+
+pub(crate) struct VariantsAttr {
+    pub ident: Ident,
+}
+
+impl VariantsAttr {
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Paren) {
+            return Err(syn::Error::new(
+                input.span(),
+                "`variants` does not accept any arguments",
+            ));
+        }
+
+        Ok(Self { ident })
+    }
+}
+
+pub(crate) struct ChildAttr {
+    pub ident: Ident,
+}
 
-        if ident != "observed" {
-            return Ok(None);
+impl ChildAttr {
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Paren) {
+            return Err(syn::Error::new(
+                input.span(),
+                "`child` does not accept any arguments, the parent is declared with `ChildAtom`",
+            ));
         }
 
-        Ok(Some(Self { ident }))
+        Ok(Self { ident })
+    }
+}
+
+pub(crate) struct EqAttr {
+    ident: Ident,
+    path: ExprPath,
+}
+
+impl EqAttr {
+    fn parse_after_ident(ident: Ident, input: ParseStream<'_>) -> syn::Result<Self> {
+        if !input.peek(Token![=]) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "`eq` expects a comparison function, e.g.: `eq = \"Self::same_revision\"`",
+            ));
+        }
+
+        input.parse::<Token![=]>()?;
+        let path = input.parse::<LitStr>()?.parse::<ExprPath>()?;
+
+        Ok(Self { ident, path })
     }
 }
 
 pub(crate) enum BounceAttr {
     WithNotion(WithNotionAttr),
     Observed(ObservedAttr),
+    FromStates(FromStatesAttr),
+    Transparent(TransparentAttr),
+    Variants(VariantsAttr),
+    Child(ChildAttr),
+    Eq(EqAttr),
 }
 
 impl Parse for BounceAttr {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
-        let forked_input = input.fork();
-        if let Some(m) = ObservedAttr::try_parse(&forked_input)? {
-            input.advance_to(&forked_input);
-            return Ok(Self::Observed(m));
-        }
-
-        let forked_input = input.fork();
-        if let Some(m) = WithNotionAttr::try_parse(&forked_input)? {
-            input.advance_to(&forked_input);
-            return Ok(Self::WithNotion(m));
+        let ident = match input.parse::<Ident>() {
+            Ok(m) => m,
+            Err(_) => return Err(diagnostics::expected_attr(input.span())),
+        };
+
+        match ident.to_string().as_str() {
+            "with_notion" => WithNotionAttr::parse_after_ident(ident, input).map(Self::WithNotion),
+            "observed" => ObservedAttr::parse_after_ident(ident, input).map(Self::Observed),
+            "from_states" => FromStatesAttr::parse_after_ident(ident, input).map(Self::FromStates),
+            "transparent" => {
+                TransparentAttr::parse_after_ident(ident, input).map(Self::Transparent)
+            }
+            "variants" => VariantsAttr::parse_after_ident(ident, input).map(Self::Variants),
+            "child" => ChildAttr::parse_after_ident(ident, input).map(Self::Child),
+            "eq" => EqAttr::parse_after_ident(ident, input).map(Self::Eq),
+            _ => Err(diagnostics::unknown_attr(&ident)),
         }
-
-        Err(input.error("unknown attribute: expected either with_notion or observed"))
     }
 }
 
@@ -79,6 +212,11 @@ impl Parse for BounceAttr {
 pub(crate) struct BounceAttrs {
     pub notions: Vec<WithNotionAttr>,
     pub observed: Option<ObservedAttr>,
+    pub from_states: Option<FromStatesAttr>,
+    pub transparent: Option<TransparentAttr>,
+    pub variants: Option<VariantsAttr>,
+    pub child: Option<ChildAttr>,
+    pub eq: Option<EqAttr>,
 }
 
 impl Parse for BounceAttrs {
@@ -92,16 +230,12 @@ impl Parse for BounceAttrs {
                 BounceAttr::WithNotion(m) => {
                     this.notions.push(m);
                 }
-                BounceAttr::Observed(m) => {
-                    if this.observed.is_some() {
-                        return Err(syn::Error::new_spanned(
-                            m.ident,
-                            "you can only have 1 observed attribute",
-                        ));
-                    }
-
-                    this.observed = Some(m);
-                }
+                BounceAttr::Observed(m) => this.set_observed(m)?,
+                BounceAttr::FromStates(m) => this.set_from_states(m)?,
+                BounceAttr::Transparent(m) => this.set_transparent(m)?,
+                BounceAttr::Variants(m) => this.set_variants(m)?,
+                BounceAttr::Child(m) => this.set_child(m)?,
+                BounceAttr::Eq(m) => this.set_eq(m)?,
             }
         }
 
@@ -118,14 +252,27 @@ impl BounceAttrs {
         let other = attr.parse_args::<BounceAttrs>()?;
 
         if let Some(m) = other.observed {
-            if self.observed.is_some() {
-                return Err(syn::Error::new_spanned(
-                    m.ident,
-                    "you can only have 1 observed attribute",
-                ));
-            }
+            self.set_observed(m)?;
+        }
+
+        if let Some(m) = other.from_states {
+            self.set_from_states(m)?;
+        }
 
-            self.observed = Some(m);
+        if let Some(m) = other.transparent {
+            self.set_transparent(m)?;
+        }
+
+        if let Some(m) = other.variants {
+            self.set_variants(m)?;
+        }
+
+        if let Some(m) = other.child {
+            self.set_child(m)?;
+        }
+
+        if let Some(m) = other.eq {
+            self.set_eq(m)?;
         }
 
         self.notions.extend(other.notions);
@@ -133,6 +280,166 @@ impl BounceAttrs {
         Ok(())
     }
 
+    fn set_observed(&mut self, attr: ObservedAttr) -> syn::Result<()> {
+        if self.observed.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "you can only have 1 observed attribute",
+            ));
+        }
+
+        self.observed = Some(attr);
+
+        Ok(())
+    }
+
+    fn set_from_states(&mut self, attr: FromStatesAttr) -> syn::Result<()> {
+        if self.from_states.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "you can only have 1 from_states attribute",
+            ));
+        }
+
+        if let Some(ref m) = self.child {
+            return Err(syn::Error::new_spanned(
+                &m.ident,
+                "`child` and `from_states` cannot be used together",
+            ));
+        }
+
+        self.from_states = Some(attr);
+
+        Ok(())
+    }
+
+    fn set_transparent(&mut self, attr: TransparentAttr) -> syn::Result<()> {
+        if self.transparent.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "you can only have 1 transparent attribute",
+            ));
+        }
+
+        self.transparent = Some(attr);
+
+        Ok(())
+    }
+
+    fn set_variants(&mut self, attr: VariantsAttr) -> syn::Result<()> {
+        if self.variants.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "you can only have 1 variants attribute",
+            ));
+        }
+
+        self.variants = Some(attr);
+
+        Ok(())
+    }
+
+    fn set_child(&mut self, attr: ChildAttr) -> syn::Result<()> {
+        if self.child.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "you can only have 1 child attribute",
+            ));
+        }
+
+        if self.from_states.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "`child` and `from_states` cannot be used together",
+            ));
+        }
+
+        self.child = Some(attr);
+
+        Ok(())
+    }
+
+    fn set_eq(&mut self, attr: EqAttr) -> syn::Result<()> {
+        if self.eq.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr.ident,
+                "you can only have 1 eq attribute",
+            ));
+        }
+
+        self.eq = Some(attr);
+
+        Ok(())
+    }
+
+    /// Implements `PartialEq` with the comparison function of the `eq` attribute.
+    pub fn create_eq_impl(&self, ident: &Ident, generics: &Generics) -> Option<TokenStream> {
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        self.eq.as_ref().map(|m| {
+            let path = &m.path;
+
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics ::std::cmp::PartialEq for #ident #ty_generics #where_clause {
+                    fn eq(&self, other: &Self) -> bool {
+                        (#path)(self, other)
+                    }
+                }
+            }
+        })
+    }
+
+    pub fn create_observed_impl(&self) -> Option<TokenStream> {
+        self.observed.as_ref().map(|m| {
+            let filter_impl = m.filter.as_ref().map(|filter| {
+                quote! {
+                    fn should_observe(&self, prev: &Self) -> bool {
+                        (#filter)(prev, self)
+                    }
+                }
+            });
+
+            quote! {
+                fn changed(self: ::std::rc::Rc<Self>) {
+                    ::bounce::Observed::changed(self);
+                }
+
+                fn init(self: ::std::rc::Rc<Self>) {
+                    ::bounce::Observed::init(self);
+                }
+
+                fn teardown(self: ::std::rc::Rc<Self>) {
+                    ::bounce::Observed::teardown(self);
+                }
+
+                #filter_impl
+            }
+        })
+    }
+
+    pub fn create_from_states_impl(&self) -> Option<TokenStream> {
+        if self.child.is_some() {
+            return Some(quote! {
+                fn create_from_states(states: &dyn ::std::any::Any) -> ::std::option::Option<Self> {
+                    let states = states.downcast_ref::<::bounce::BounceStates>()?;
+                    let parent = states.get_atom_value::<<Self as ::bounce::ChildAtom>::Parent>();
+                    ::std::option::Option::Some(<Self as ::bounce::ChildAtom>::derive_from(&parent))
+                }
+            });
+        }
+
+        self.from_states.as_ref().map(|_| {
+            quote! {
+                fn create_from_states(states: &dyn ::std::any::Any) -> ::std::option::Option<Self> {
+                    states
+                        .downcast_ref::<::bounce::BounceStates>()
+                        .map(<Self as ::bounce::FromStates>::create_from)
+                }
+            }
+        })
+    }
+
     pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
         let mut this = Self::default();
 
@@ -168,6 +475,19 @@ impl BounceAttrs {
         notion_apply_impls
     }
 
+    pub fn create_notion_accepts_impls(&self, notion_ident: &Ident) -> Vec<TokenStream> {
+        self.notion_idents()
+            .iter()
+            .map(|ident| {
+                quote! {
+                    if let ::std::option::Option::Some(m) = <dyn ::std::any::Any>::downcast_ref::<#ident>(#notion_ident) {
+                        return ::bounce::WithNotion::<#ident>::accepts(self, m);
+                    }
+                }
+            })
+            .collect()
+    }
+
     pub fn create_notion_id_impls(&self) -> Vec<TokenStream> {
         self.notion_idents()
             .iter()
@@ -184,23 +504,56 @@ pub(crate) fn macro_fn(input: DeriveInput) -> TokenStream {
         Err(e) => return e.into_compile_error(),
     };
 
+    if let Some(ref m) = bounce_attrs.transparent {
+        return syn::Error::new_spanned(&m.ident, "`transparent` can only be used on atoms")
+            .into_compile_error();
+    }
+
+    if let Some(ref m) = bounce_attrs.variants {
+        return syn::Error::new_spanned(&m.ident, "`variants` can only be used on atoms")
+            .into_compile_error();
+    }
+
+    if let Some(ref m) = bounce_attrs.child {
+        return syn::Error::new_spanned(&m.ident, "`child` can only be used on atoms")
+            .into_compile_error();
+    }
+
     let notion_ident = Ident::new("notion", Span::mixed_site());
     let notion_apply_impls = bounce_attrs.create_notion_apply_impls(&notion_ident);
+    let notion_accepts_impls = bounce_attrs.create_notion_accepts_impls(&notion_ident);
     let notion_ids_impls = bounce_attrs.create_notion_id_impls();
 
     let type_ident = input.ident;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let impl_observed = bounce_attrs.observed.is_some().then(|| {
-        quote! {
-            fn changed(self: ::std::rc::Rc<Self>) {
-                ::bounce::Observed::changed(self);
+    let impl_from_states = bounce_attrs.create_from_states_impl();
+
+    let impl_observed = bounce_attrs.create_observed_impl();
+
+    let impl_eq = bounce_attrs.create_eq_impl(&type_ident, &input.generics);
+
+    // Slices must implement Reducible, this produces an error that points to the type if the
+    // implementation is missing.
+    let assert_reducible = quote_spanned! { type_ident.span() =>
+        const _: () = {
+            fn slice_requires_reducible<T>()
+            where
+                T: ?::std::marker::Sized + ::bounce::__vendored::yew::functional::Reducible,
+            {
             }
-        }
-    });
 
-    quote! {
+            #[allow(dead_code)]
+            fn assert_reducible #impl_generics () #where_clause {
+                slice_requires_reducible::<#type_ident #ty_generics>();
+            }
+        };
+    };
+
+    quote_spanned! { type_ident.span() =>
+        #assert_reducible
+
         #[automatically_derived]
         impl #impl_generics ::bounce::Slice for #type_ident #ty_generics #where_clause {
             type Action = <Self as ::bounce::__vendored::yew::functional::Reducible>::Action;
@@ -215,11 +568,21 @@ pub(crate) fn macro_fn(input: DeriveInput) -> TokenStream {
                 self
             }
 
+            fn accepts(&self, #notion_ident: &dyn ::std::any::Any) -> bool {
+                #(#notion_accepts_impls)*
+
+                false
+            }
+
             fn notion_ids(&self) -> ::std::vec::Vec<::std::any::TypeId> {
                 ::std::vec![#(#notion_ids_impls,)*]
             }
 
             #impl_observed
+
+            #impl_from_states
         }
+
+        #impl_eq
     }
 }
@@ -0,0 +1,92 @@
+//! Benchmarks of merging and rendering helmet tags with large tag sets.
+//!
+//! Tags are rendered on the server with a `StaticWriter`, so the benchmarks run natively with the
+//! public API.
+
+use bounce::helmet::{render_static, Helmet, HelmetBridge, HelmetWriter};
+use bounce::BounceRoot;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures::executor::block_on;
+use yew::html::IntoPropValue;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+struct PageProps {
+    index: usize,
+    tags: usize,
+}
+
+#[function_component(Page)]
+fn page(props: &PageProps) -> Html {
+    let index = props.index;
+    // Pages override the meta tags of previous pages with the same name.
+    let tags = (0..props.tags).map(|tag| {
+        html! { <meta name={format!("meta-{tag}")} content={index.to_string()} /> }
+    });
+
+    html! {
+        <Helmet>
+            <title>{format!("Page {index}")}</title>
+            <html class={format!("page-{index}")} />
+            {for tags}
+        </Helmet>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    writer: HelmetWriter,
+    pages: usize,
+    tags_per_page: usize,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    let pages = (0..props.pages).map(|index| html! { <Page {index} tags={props.tags_per_page} /> });
+
+    html! {
+        <BounceRoot>
+            <HelmetBridge writer={props.writer.clone()} />
+            {for pages}
+        </BounceRoot>
+    }
+}
+
+fn render(pages: usize, tags_per_page: usize) -> String {
+    block_on(async move {
+        let (renderer, writer) = render_static();
+
+        let _body = yew::LocalServerRenderer::<Root>::with_props(RootProps {
+            writer: writer.into_prop_value(),
+            pages,
+            tags_per_page,
+        })
+        .hydratable(false)
+        .render()
+        .await;
+
+        let mut head = String::new();
+        for tag in renderer.render().await {
+            tag.write_static(&mut head).unwrap();
+        }
+
+        head
+    })
+}
+
+fn helmet_render_static(c: &mut Criterion) {
+    let mut group = c.benchmark_group("helmet_render_static");
+
+    for (pages, tags_per_page) in [(1, 10), (10, 100), (100, 1_000)] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{pages}x{tags_per_page}")),
+            &(pages, tags_per_page),
+            |b, (pages, tags_per_page)| b.iter(|| render(*pages, *tags_per_page)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, helmet_render_static);
+criterion_main!(benches);
@@ -0,0 +1,90 @@
+//! Benchmarks of rendering components that subscribe to states.
+//!
+//! Components are rendered on the server, so the benchmarks run natively with the public API.
+
+use std::rc::Rc;
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures::executor::block_on;
+use yew::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+struct Value(u64);
+
+#[derive(PartialEq)]
+struct Doubled(u64);
+
+impl Selector for Doubled {
+    fn select(states: &BounceStates) -> Rc<Self> {
+        Self(states.get_atom_value::<Value>().0 * 2).into()
+    }
+}
+
+#[function_component(AtomReader)]
+fn atom_reader() -> Html {
+    let value = use_atom_value::<Value>();
+
+    html! { <div>{value.0}</div> }
+}
+
+#[function_component(SelectorReader)]
+fn selector_reader() -> Html {
+    let doubled = use_selector_value::<Doubled>();
+
+    html! { <div>{doubled.0}</div> }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    readers: usize,
+    selector: bool,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    let readers = (0..props.readers).map(|_| {
+        if props.selector {
+            html! { <SelectorReader /> }
+        } else {
+            html! { <AtomReader /> }
+        }
+    });
+
+    html! {
+        <BounceRoot>
+            {for readers}
+        </BounceRoot>
+    }
+}
+
+fn render(readers: usize, selector: bool) -> String {
+    block_on(
+        yew::LocalServerRenderer::<Root>::with_props(RootProps { readers, selector })
+            .hydratable(false)
+            .render(),
+    )
+}
+
+fn render_subscribers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render_subscribers");
+
+    for readers in [1, 100, 1_000] {
+        group.bench_with_input(BenchmarkId::new("atom", readers), &readers, |b, readers| {
+            b.iter(|| render(*readers, false))
+        });
+
+        // The selector is selected once and shared by all readers.
+        group.bench_with_input(
+            BenchmarkId::new("selector", readers),
+            &readers,
+            |b, readers| b.iter(|| render(*readers, true)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, render_subscribers);
+criterion_main!(benches);
@@ -0,0 +1,188 @@
+//! A token to cancel asynchronous work.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+use std::task::{Poll, Waker};
+#[cfg(feature = "query")]
+use std::time::Duration;
+
+use futures::future::poll_fn;
+#[cfg(feature = "query")]
+use futures::future::{self, Either, FutureExt};
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "query")]
+use crate::clock::Clock;
+
+#[derive(Default)]
+struct AbortTokenInner {
+    aborted: Cell<bool>,
+    wakers: RefCell<Vec<Waker>>,
+    // There is no AbortController on the server.
+    #[cfg(target_arch = "wasm32")]
+    controller: RefCell<Option<web_sys::AbortController>>,
+}
+
+/// A token that is aborted when the work it is passed to should be cancelled.
+///
+/// Queries and mutations obtain the token of their run with
+/// [`BounceStates::abort_token`](crate::BounceStates::abort_token) and wire it into their
+/// requests, so requests that are no longer needed do not keep consuming bandwidth. The token can
+/// be awaited with [`aborted`](Self::aborted) or passed to `fetch` (e.g.: with `gloo-net`) as an
+/// `AbortSignal` with [`signal`](Self::signal).
+///
+/// Clones of a token share the same state.
+///
+/// # Example
+///
+/// ```
+/// # use bounce::AbortToken;
+/// # futures::executor::block_on(async {
+/// let token = AbortToken::new();
+/// assert!(!token.is_aborted());
+///
+/// token.clone().abort();
+///
+/// assert!(token.is_aborted());
+/// // Resolves immediately as the token has been aborted.
+/// token.aborted().await;
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct AbortToken {
+    inner: Rc<AbortTokenInner>,
+}
+
+impl AbortToken {
+    /// Creates a token that has not been aborted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aborts the token.
+    ///
+    /// Futures returned by [`aborted`](Self::aborted) resolve and the `AbortSignal` returned by
+    /// [`signal`](Self::signal) (if any) is aborted. Aborting a token that has already been
+    /// aborted has no effect.
+    pub fn abort(&self) {
+        if self.inner.aborted.replace(true) {
+            return;
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        if let Some(m) = self.inner.controller.borrow().as_ref() {
+            m.abort();
+        }
+
+        let wakers = std::mem::take(&mut *self.inner.wakers.borrow_mut());
+        for m in wakers {
+            m.wake();
+        }
+    }
+
+    /// Returns `true` if the token has been aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.get()
+    }
+
+    /// Returns a future that resolves when the token is aborted.
+    ///
+    /// This can be raced against a request (e.g.: with `futures::select`) so that the request is
+    /// dropped when it is no longer needed.
+    pub fn aborted(&self) -> impl Future<Output = ()> + 'static {
+        let inner = self.inner.clone();
+
+        poll_fn(move |cx| {
+            if inner.aborted.get() {
+                return Poll::Ready(());
+            }
+
+            let mut wakers = inner.wakers.borrow_mut();
+            if !wakers.iter().any(|m| m.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+
+            Poll::Pending
+        })
+    }
+
+    /// Returns an `AbortSignal` that is aborted with the token.
+    ///
+    /// The signal can be cast into a `web_sys::AbortSignal` with `JsCast::unchecked_into` and
+    /// passed to `fetch`. Returns `None` if `AbortController` is not available (e.g.: during
+    /// server-side rendering).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let signal = states.abort_token().signal();
+    ///
+    /// let resp = gloo_net::http::Request::get(&url)
+    ///     .abort_signal(signal.as_ref().map(|m| m.unchecked_ref()))
+    ///     .send()
+    ///     .await;
+    /// ```
+    pub fn signal(&self) -> Option<JsValue> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mut controller = self.inner.controller.borrow_mut();
+
+            if controller.is_none() {
+                let m = web_sys::AbortController::new().ok()?;
+                if self.is_aborted() {
+                    m.abort();
+                }
+
+                *controller = Some(m);
+            }
+
+            controller.as_ref().map(|m| m.signal().into())
+        }
+
+        // There is no AbortController on the server.
+        #[cfg(not(target_arch = "wasm32"))]
+        None
+    }
+}
+
+impl fmt::Debug for AbortToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbortToken")
+            .field("aborted", &self.is_aborted())
+            .finish()
+    }
+}
+
+/// Runs `f` and aborts `token` if `f` has not completed after `timeout`.
+///
+/// `f` is not dropped when the token is aborted, so it can return an error.
+#[cfg(feature = "query")]
+pub(crate) async fn with_timeout<F>(
+    clock: &dyn Clock,
+    timeout: Option<Duration>,
+    token: &AbortToken,
+    f: F,
+) -> F::Output
+where
+    F: Future,
+{
+    let timeout = match timeout {
+        Some(m) => m,
+        None => return f.await,
+    };
+
+    let timer = {
+        let token = token.clone();
+        clock.sleep(timeout).then(move |_| {
+            token.abort();
+            future::pending::<()>()
+        })
+    };
+
+    match future::select(Box::pin(f), timer).await {
+        Either::Left((m, _)) => m,
+        Either::Right(_) => unreachable!("the timer never completes."),
+    }
+}
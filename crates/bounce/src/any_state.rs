@@ -3,18 +3,50 @@ use std::rc::Rc;
 
 use anymap2::AnyMap;
 
+use crate::root_state::BounceRootState;
+use crate::states::registry::StateDescriptor;
+
 /// A common trait for all states.
 pub(crate) trait AnyState {
     /// Applies a notion.
     fn apply(&self, notion: Rc<dyn Any>);
 
+    /// Returns `true` if the state accepts the notion in its current state.
+    ///
+    /// Notions that are not accepted are not applied.
+    #[allow(unused_variables)]
+    fn accepts(&self, notion: &dyn Any) -> bool {
+        true
+    }
+
     /// Returns a list of notion ids that this state accepts.
     fn notion_ids(&self) -> Vec<TypeId> {
         Vec::new()
     }
 
+    /// Notifies the state that it has been created and registered with the root.
+    fn init(&self) {}
+
+    /// Notifies the state that its root is destroyed.
+    fn teardown(&self) {}
+
     /// Creates a state from a possible initialise value.
     fn create(init_states: &mut AnyMap) -> Self
     where
         Self: Sized;
+
+    /// Creates a state that may read other states of the root.
+    fn create_from_root(root: &BounceRootState) -> Self
+    where
+        Self: Sized,
+    {
+        root.with_init_states(Self::create)
+    }
+
+    /// Describes the state for [`use_registered_states`](crate::use_registered_states).
+    ///
+    /// Returns `None` for internal states that are not listed.
+    fn describe(&self) -> Option<StateDescriptor> {
+        None
+    }
 }
@@ -0,0 +1,54 @@
+//! A module to manage authentication sessions.
+//!
+//! This module provides:
+//!
+//! - A session state that can be accessed with [`use_session`].
+//! - [`with_auth`], a helper to attach the access token to requests made in queries and
+//!   mutations.
+//! - Automatic token refresh when a request fails with an authentication error. Concurrent
+//!   requests that fail at the same time share a single refresh.
+//! - Logging out with [`UseSessionHandle::logout`], which clears the session with the [`Logout`]
+//!   notion, and all cached query results and the states of mutations with the
+//!   [`ClearQueryCache`](crate::query::ClearQueryCache) notion.
+//!
+//! Tokens are refreshed by a [`TokenRefresher`] that is provided to the `<BounceRoot />` as a
+//! service.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::rc::Rc;
+//! # use async_trait::async_trait;
+//! # use bounce::auth::{Session, TokenRefresher};
+//! # use bounce::{BounceRoot, Services};
+//! # use yew::prelude::*;
+//! #
+//! struct Refresher;
+//!
+//! #[async_trait(?Send)]
+//! impl TokenRefresher for Refresher {
+//!     async fn refresh(&self, session: Rc<Session>) -> Option<Session> {
+//!         // exchange the refresh token for a new access token.
+//!         # None
+//!     }
+//! }
+//!
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     let get_services = Callback::from(|_| {
+//!         Services::new().provide_rc::<dyn TokenRefresher>(Rc::new(Refresher))
+//!     });
+//!
+//!     html! {
+//!         <BounceRoot {get_services}>
+//!             // children...
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+
+mod session;
+mod with_auth;
+
+pub use session::{use_session, Logout, Session, UseSessionHandle};
+pub use with_auth::{with_auth, AuthError, TokenRefresher};
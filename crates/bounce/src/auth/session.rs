@@ -0,0 +1,146 @@
+use std::fmt;
+use std::rc::Rc;
+
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use crate::query::ClearQueryCache;
+use crate::root_state::BounceRootState;
+use crate::states::atom::{use_atom_value, AtomSlice};
+use crate::states::notion::{NotionBatch, WithNotion};
+use crate::states::slice::SliceState;
+use crate::Atom;
+
+/// An authentication session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// The access token attached to requests.
+    pub access_token: String,
+    /// The token used to obtain a new access token, if any.
+    pub refresh_token: Option<String>,
+}
+
+/// A notion to end the current session.
+///
+/// When this notion is applied, the session is cleared. [`UseSessionHandle::logout`] and failed
+/// token refreshes apply it together with [`ClearQueryCache`], so all cached query results are
+/// cleared and mutation handles are reset to idle as well.
+///
+/// States that hold information of the current user can also listen to this notion with
+/// `#[bounce(with_notion(Logout))]` to clear themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Logout;
+
+#[derive(Atom, Default, PartialEq)]
+#[bounce(with_notion(Logout))]
+pub(crate) struct SessionAtom {
+    pub inner: Option<Rc<Session>>,
+}
+
+impl WithNotion<Logout> for SessionAtom {
+    fn apply(self: Rc<Self>, _notion: Rc<Logout>) -> Rc<Self> {
+        Self::default().into()
+    }
+}
+
+/// Ends the current session, results of the previous user are cleared with it.
+pub(super) fn logout(root: &BounceRootState) {
+    root.apply_notions(NotionBatch::new().with(Logout).with(ClearQueryCache));
+}
+
+/// Returns the current session without subscribing to it.
+pub(super) fn current_session(root: &BounceRootState) -> Option<Rc<Session>> {
+    root.get_state::<SliceState<AtomSlice<SessionAtom>>>()
+        .get()
+        .inner
+        .inner
+        .clone()
+}
+
+/// Replaces the current session.
+pub(super) fn set_session(root: &BounceRootState, session: Rc<Session>) {
+    root.get_state::<SliceState<AtomSlice<SessionAtom>>>()
+        .dispatch(SessionAtom {
+            inner: Some(session),
+        });
+}
+
+/// A handle returned by [`use_session`].
+#[derive(Clone)]
+pub struct UseSessionHandle {
+    session: Option<Rc<Session>>,
+    root: BounceRootState,
+}
+
+impl UseSessionHandle {
+    /// Returns the current session.
+    ///
+    /// Returns `None` if the user is not logged in.
+    pub fn session(&self) -> Option<Rc<Session>> {
+        self.session.clone()
+    }
+
+    /// Returns `true` if there is an active session.
+    pub fn is_authenticated(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Starts a new session.
+    pub fn login(&self, session: Session) {
+        set_session(&self.root, session.into());
+    }
+
+    /// Ends the current session by applying the [`Logout`] and [`ClearQueryCache`] notions.
+    pub fn logout(&self) {
+        logout(&self.root);
+    }
+}
+
+impl fmt::Debug for UseSessionHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseSessionHandle")
+            .field("session", &self.session)
+            .finish()
+    }
+}
+
+/// A hook to connect to the current authentication session.
+///
+/// Returns a [`UseSessionHandle`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::auth::{use_session, Session};
+///
+/// #[function_component(LoginButton)]
+/// fn login_button() -> Html {
+///     let session = use_session();
+///
+///     if session.is_authenticated() {
+///         let onclick = Callback::from(move |_| session.logout());
+///
+///         return html! { <button {onclick}>{"Logout"}</button> };
+///     }
+///
+///     let onclick = Callback::from(move |_| {
+///         session.login(Session {
+///             access_token: "access-token".into(),
+///             refresh_token: Some("refresh-token".into()),
+///         })
+///     });
+///
+///     html! { <button {onclick}>{"Login"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_session() -> UseSessionHandle {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let session = use_atom_value::<SessionAtom>();
+
+    UseSessionHandle {
+        session: session.inner.clone(),
+        root,
+    }
+}
@@ -0,0 +1,197 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use async_trait::async_trait;
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+
+use super::session::{current_session, logout, set_session, Session};
+use crate::any_state::AnyState;
+use crate::root_state::{BounceRootState, BounceStates};
+
+/// A trait to be implemented on errors returned by requests wrapped with [`with_auth`].
+pub trait AuthError {
+    /// Returns `true` if the request failed because the access token is invalid or expired.
+    ///
+    /// Requests that fail with an authentication error are retried once after the session is
+    /// refreshed.
+    fn is_auth_error(&self) -> bool;
+
+    /// Creates an error that is returned when there is no active session.
+    fn unauthenticated() -> Self;
+}
+
+/// A trait to refresh the access token of a session.
+///
+/// The refresher should be provided to the `<BounceRoot />` as a service of type
+/// `dyn TokenRefresher`, see the [module documentation](crate::auth) for an example.
+///
+/// # Note
+///
+/// This trait is implemented with [async_trait](macro@async_trait), you should apply an `#[async_trait(?Send)]`
+/// attribute to your implementation of this trait.
+#[async_trait(?Send)]
+pub trait TokenRefresher {
+    /// Refreshes a session.
+    ///
+    /// Returns `None` if the session cannot be refreshed, in which case the user is logged out as
+    /// with [`UseSessionHandle::logout`](super::UseSessionHandle::logout).
+    async fn refresh(&self, session: Rc<Session>) -> Option<Session>;
+}
+
+type RefreshFuture = Shared<LocalBoxFuture<'static, Option<Rc<Session>>>>;
+
+/// The token refresh that is currently in flight.
+#[derive(Default, Clone)]
+struct RefreshState {
+    inner: Rc<RefCell<Option<RefreshFuture>>>,
+}
+
+impl AnyState for RefreshState {
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// Refreshes a session that has been rejected.
+///
+/// Only 1 refresh is run at a time, callers that request a refresh while another refresh is in
+/// flight wait for the result of that refresh.
+async fn refresh_session(root: &BounceRootState, rejected: Rc<Session>) -> Option<Rc<Session>> {
+    let refresh_state = root.get_state::<RefreshState>();
+
+    let refresh = {
+        let mut inflight = refresh_state.inner.borrow_mut();
+
+        match *inflight {
+            Some(ref m) => m.clone(),
+            None => {
+                // The session has already been refreshed by another request.
+                if let Some(m) = current_session(root) {
+                    if m != rejected {
+                        return Some(m);
+                    }
+                }
+
+                let root = root.clone();
+                let refresh = async move {
+                    let refresher = root.get_service::<dyn TokenRefresher>();
+                    let session = match refresher {
+                        Some(m) => m.refresh(rejected).await.map(Rc::new),
+                        None => None,
+                    };
+
+                    root.get_state::<RefreshState>().inner.borrow_mut().take();
+
+                    match session {
+                        Some(ref m) => set_session(&root, m.clone()),
+                        None => logout(&root),
+                    }
+
+                    session
+                }
+                .boxed_local()
+                .shared();
+
+                *inflight = Some(refresh.clone());
+
+                refresh
+            }
+        }
+    };
+
+    refresh.await
+}
+
+/// Runs a request with the current session.
+///
+/// If the request fails with an [authentication error](AuthError::is_auth_error), the session is
+/// refreshed with the [`TokenRefresher`] provided to the `<BounceRoot />` and the request is
+/// retried once. If the session cannot be refreshed, the user is logged out and the original
+/// error is returned.
+///
+/// If there is no active session, [`AuthError::unauthenticated`] is returned without running the
+/// request.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use async_trait::async_trait;
+/// # use bounce::prelude::*;
+/// # use bounce::auth::{with_auth, AuthError};
+/// # use bounce::query::{ErrorClass, Query, QueryResult};
+/// #
+/// #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+/// enum ApiError {
+///     #[error("unauthorized")]
+///     Unauthorized,
+///     #[error("request failed")]
+///     Other,
+/// }
+///
+/// impl AuthError for ApiError {
+///     fn is_auth_error(&self) -> bool {
+///         matches!(self, Self::Unauthorized)
+///     }
+///
+///     fn unauthenticated() -> Self {
+///         Self::Unauthorized
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct ProfileQuery {
+///     name: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for ProfileQuery {
+///     type Input = ();
+///     type Error = ApiError;
+///
+///     async fn query(states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+///         with_auth(states, |session| async move {
+///             // fetch the profile with session.access_token.
+///
+///             Ok(ProfileQuery { name: "John Smith".into() }.into())
+///         })
+///         .await
+///     }
+///
+///     fn classify_error(error: &ApiError) -> ErrorClass {
+///         match error {
+///             ApiError::Unauthorized => ErrorClass::Auth,
+///             ApiError::Other => ErrorClass::Unknown,
+///         }
+///     }
+/// }
+/// ```
+pub async fn with_auth<F, Fut, T, E>(states: &BounceStates, f: F) -> Result<T, E>
+where
+    F: Fn(Rc<Session>) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: AuthError,
+{
+    let root = states.root();
+
+    let session = match current_session(root) {
+        Some(m) => m,
+        None => return Err(E::unauthenticated()),
+    };
+
+    match f(session.clone()).await {
+        Err(e) if e.is_auth_error() => match refresh_session(root, session).await {
+            Some(m) => f(m).await,
+            None => Err(e),
+        },
+        m => m,
+    }
+}
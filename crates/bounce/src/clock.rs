@@ -0,0 +1,169 @@
+//! Clocks that time-based features read the time from.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+use futures::future::{self, FutureExt, LocalBoxFuture};
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use crate::root_state::BounceRootState;
+use crate::utils::now;
+
+/// A source of time.
+///
+/// All time-based features of bounce (e.g.: expiring atoms, cached values, scheduled refreshes
+/// and timeouts) read the time and sleep with the clock of the `<BounceRoot />`.
+///
+/// The clock defaults to [`SystemClock`]. A different clock can be provided to the root as a
+/// service, e.g.: a [`ManualClock`] in tests.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// use bounce::{BounceRoot, Clock, ManualClock, Services};
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     let get_services = Callback::from(|_| {
+///         let clock: Rc<dyn Clock> = Rc::new(ManualClock::new());
+///         Services::new().provide_rc(clock)
+///     });
+///
+///     html! {
+///         <BounceRoot {get_services}>
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+pub trait Clock {
+    /// Returns the current time as a duration since the Unix epoch.
+    fn now(&self) -> Duration;
+
+    /// Returns a future that resolves after `duration` has elapsed.
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()>;
+}
+
+/// A clock that reads the time of the system and sleeps with timers of the platform.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        now()
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        yew::platform::time::sleep(duration).boxed_local()
+    }
+}
+
+#[derive(Default)]
+struct ManualClockInner {
+    now: Cell<Duration>,
+    sleepers: RefCell<Vec<(Duration, oneshot::Sender<()>)>>,
+}
+
+/// A clock that only advances when it is told to.
+///
+/// This can be used to test time-based behaviour deterministically, without waiting for real
+/// timers. The clock starts at the Unix epoch.
+///
+/// Clones of a manual clock share the same time.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// use bounce::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let sleep = clock.sleep(Duration::from_secs(60));
+///
+/// // Resolves the sleep above without waiting.
+/// clock.advance(Duration::from_secs(60));
+///
+/// assert_eq!(clock.now(), Duration::from_secs(60));
+/// ```
+#[derive(Default, Clone)]
+pub struct ManualClock {
+    inner: Rc<ManualClockInner>,
+}
+
+impl ManualClock {
+    /// Creates a manual clock.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the clock by `duration`.
+    ///
+    /// Sleeps that have elapsed are resolved. Tasks waiting for them run the next time the
+    /// executor is polled.
+    pub fn advance(&self, duration: Duration) {
+        let now = self.inner.now.get() + duration;
+        self.inner.now.set(now);
+
+        let elapsed = {
+            let mut sleepers = self.inner.sleepers.borrow_mut();
+            let (elapsed, pending) = std::mem::take(&mut *sleepers)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(wake_at, _)| *wake_at <= now);
+            *sleepers = pending;
+
+            elapsed
+        };
+
+        for (_, sender) in elapsed {
+            let _ = sender.send(());
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.inner.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        if duration.is_zero() {
+            return future::ready(()).boxed_local();
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let wake_at = self.inner.now.get() + duration;
+        self.inner.sleepers.borrow_mut().push((wake_at, sender));
+
+        // If the clock is dropped, the sleep never resolves.
+        receiver
+            .then(|m| async move {
+                if m.is_err() {
+                    future::pending::<()>().await;
+                }
+            })
+            .boxed_local()
+    }
+}
+
+impl fmt::Debug for ManualClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManualClock")
+            .field("now", &self.inner.now.get())
+            .field("sleepers", &self.inner.sleepers.borrow().len())
+            .finish()
+    }
+}
+
+/// Returns the clock of the current bounce root.
+#[hook]
+pub(crate) fn use_clock() -> Rc<dyn Clock> {
+    use_context::<BounceRootState>()
+        .expect_throw("No bounce root found.")
+        .clock()
+}
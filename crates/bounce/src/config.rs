@@ -0,0 +1,196 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::query::{Query, QueryOptions};
+
+/// The configuration of a `<BounceRoot />`.
+///
+/// The configuration is provided to the root as a [service](crate::Services). It allows
+/// applications to override the defaults that are declared by the states they use, without
+/// changing where the states are used.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::{BounceConfig, BounceRoot, Services};
+/// # use bounce::query::{Query, QueryOptions, QueryResult};
+/// # use async_trait::async_trait;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # struct UserQuery {
+/// #     name: String,
+/// # }
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for UserQuery {
+/// #     type Input = u64;
+/// #     type Error = Infallible;
+/// #
+/// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+/// #         Ok(UserQuery { name: "John Smith".into() }.into())
+/// #     }
+/// # }
+/// #
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     let get_services = Callback::from(|_| {
+///         let config = BounceConfig::new()
+///             // Overrides the options declared by `UserQuery`.
+///             .with_query_options::<UserQuery>(|m| QueryOptions { retries: 3, ..m })
+///             // At most 6 queries are fetched at the same time.
+///             .with_fetch_pool_limit(6);
+///
+///         Services::new().provide(config)
+///     });
+///
+///     html! {
+///         <BounceRoot {get_services}>
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct BounceConfig {
+    query_options: HashMap<TypeId, QueryOptions>,
+    fetch_pool_limit: Option<usize>,
+    slow_query_threshold: Option<Duration>,
+    result_stability_check: bool,
+    offline_demo: bool,
+    prepared_transform: crate::query::PreparedTransformConfig,
+}
+
+impl BounceConfig {
+    /// Creates a configuration with the defaults of all states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the options of a query type.
+    ///
+    /// The closure receives the options declared by the query type.
+    pub fn with_query_options<T>(mut self, f: impl FnOnce(QueryOptions) -> QueryOptions) -> Self
+    where
+        T: Query + 'static,
+    {
+        self.query_options
+            .insert(TypeId::of::<T>(), f(QueryOptions::of::<T>()));
+        self
+    }
+
+    /// Limits the number of queries that are fetched concurrently.
+    ///
+    /// Fetches that exceed the limit wait in the order they are started until a running fetch
+    /// completes. This avoids overwhelming slow connections when a page mounts many queries at
+    /// once. Queries that are not [pooled](crate::query::Query::POOLED) are not limited.
+    ///
+    /// By default, the number of concurrent fetches is not limited.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`.
+    pub fn with_fetch_pool_limit(mut self, limit: usize) -> Self {
+        assert!(limit > 0, "the fetch pool limit must be at least 1");
+
+        self.fetch_pool_limit = Some(limit);
+        self
+    }
+
+    /// Logs a warning when a fetch of a query takes at least `threshold`.
+    ///
+    /// The warning includes the name of the query type and its input, as described by
+    /// [`Query::describe_input`](crate::query::Query::describe_input), to surface slow endpoints
+    /// during development. Warnings are only logged in debug builds.
+    ///
+    /// By default, slow queries are not reported.
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = Some(threshold);
+        self
+    }
+
+    /// Logs a warning when the result of a query compares unequal to the previous result on
+    /// several consecutive refetches.
+    ///
+    /// Such a result (e.g.: one that contains the time it was fetched) re-renders all of its
+    /// subscribers on every refetch, even if the data has not changed. Refreshes (e.g.: polling or
+    /// [`refresh`](crate::query::UseQueryValueHandle::refresh)) are expected to return new data
+    /// and are not checked. Warnings are only logged in debug builds.
+    ///
+    /// Disabled by default.
+    pub fn with_result_stability_check(mut self, enabled: bool) -> Self {
+        self.result_stability_check = enabled;
+        self
+    }
+
+    /// Enables the offline demo mode.
+    ///
+    /// In offline demo mode, queries, mutations and future notions (run with
+    /// [`use_future_notion_runner`](crate::use_future_notion_runner) or a
+    /// [`NotionScope`](crate::NotionScope)) never run their implementation. Those that are mocked
+    /// with [`MockQueries`](crate::query::MockQueries) resolve with their mocks. Others stay
+    /// pending, and queries with a cached result (e.g.: seeded or prepared results) keep serving
+    /// it.
+    ///
+    /// This allows demos and snapshot tests of an application to run without a network, without
+    /// intercepting requests at a lower layer.
+    ///
+    /// Disabled by default.
+    pub fn with_offline_demo(mut self, enabled: bool) -> Self {
+        self.offline_demo = enabled;
+        self
+    }
+
+    /// Returns `true` if the offline demo mode is enabled.
+    ///
+    /// Applications can use it to show that the data on the page is not real.
+    pub fn offline_demo(&self) -> bool {
+        self.offline_demo
+    }
+
+    /// Transforms the encoded results of prepared queries with `transform`.
+    ///
+    /// See [`PreparedTransform`](crate::query::PreparedTransform) for more information.
+    pub fn with_prepared_transform<T>(mut self, transform: T) -> Self
+    where
+        T: crate::query::PreparedTransform + 'static,
+    {
+        self.prepared_transform = crate::query::PreparedTransformConfig::new(Rc::new(transform));
+        self
+    }
+
+    /// Returns the overridden options of a query type, if any.
+    pub(crate) fn query_options<T>(&self) -> Option<QueryOptions>
+    where
+        T: Query + 'static,
+    {
+        self.query_options.get(&TypeId::of::<T>()).copied()
+    }
+
+    /// Returns the maximum number of queries that are fetched concurrently, if any.
+    pub(crate) fn fetch_pool_limit(&self) -> Option<usize> {
+        self.fetch_pool_limit
+    }
+
+    /// Returns the duration after which fetches are reported as slow, if any.
+    #[cfg(debug_assertions)]
+    pub(crate) fn slow_query_threshold(&self) -> Option<Duration> {
+        self.slow_query_threshold
+    }
+
+    /// Returns `true` if the results of refetched queries are checked for stability.
+    #[cfg(debug_assertions)]
+    pub(crate) fn result_stability_check(&self) -> bool {
+        self.result_stability_check
+    }
+
+    /// Returns the transform of prepared query results.
+    pub(crate) fn prepared_transform(&self) -> &crate::query::PreparedTransformConfig {
+        &self.prepared_transform
+    }
+}
@@ -0,0 +1,217 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use gloo::timers::callback::Timeout;
+use gloo::utils::{document, head};
+use wasm_bindgen::UnwrapThrowExt;
+use web_sys::Element;
+use yew::functional::{Hook, HookContext};
+use yew::prelude::*;
+
+use super::render_count::CountRenders;
+use super::trace::set_log_traces;
+use crate::root_state::BounceRootState;
+
+/// The class applied to highlighted elements.
+const HIGHLIGHT_CLASS: &str = "bounce-devtools-highlight";
+/// How long an element stays highlighted after being rendered, in milliseconds.
+const HIGHLIGHT_DURATION: u32 = 500;
+
+const HIGHLIGHT_STYLE: &str = r#".bounce-devtools-highlight {
+    outline: 2px solid rgba(255, 0, 128, 0.8) !important;
+    outline-offset: -2px;
+}"#;
+
+thread_local! {
+    static HIGHLIGHT_UPDATES: Cell<bool> = const { Cell::new(false) };
+    // The number of times the listeners of each component have been notified, by component key.
+    static COMPONENT_UPDATES: RefCell<HashMap<usize, u64>> = RefCell::default();
+}
+
+/// Returns a key of the current component that is unique among mounted components.
+pub(crate) fn use_component_key() -> impl Hook<Output = usize> {
+    struct UseComponentKey;
+
+    impl Hook for UseComponentKey {
+        type Output = usize;
+
+        fn run(self, ctx: &mut HookContext) -> Self::Output {
+            // The hook context is owned by the component and is not moved while the component is
+            // mounted.
+            ctx as *const HookContext as usize
+        }
+    }
+
+    UseComponentKey
+}
+
+/// Records that a listener registered by the component with the key has been notified of a
+/// state change.
+pub(crate) fn record_update(component: usize) {
+    COMPONENT_UPDATES.with(|m| *m.borrow_mut().entry(component).or_default() += 1);
+}
+
+fn current_update(component: usize) -> u64 {
+    COMPONENT_UPDATES.with(|m| m.borrow().get(&component).copied().unwrap_or_default())
+}
+
+fn set_highlight_updates(enabled: bool) {
+    HIGHLIGHT_UPDATES.with(|m| m.set(enabled));
+}
+
+/// Properties of the [`Devtools`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct DevtoolsProps {
+    /// Outlines components that use the [`use_render_highlight`] hook when they are
+    /// re-rendered after a state they are subscribed to has changed.
+    #[prop_or_default]
+    pub highlight_updates: bool,
+    /// Logs the chain of state changes to the browser console each time a query is marked
+    /// outdated.
+    ///
+    /// See: [`outdated_traces`](super::outdated_traces)
+    #[prop_or_default]
+    pub log_outdated: bool,
+    /// Aggregates the renders of components that use the
+    /// [`use_render_count`](super::use_render_count) hook.
+    ///
+    /// Renders of the children of this component are counted from their first render.
+    ///
+    /// See: [`render_counts`](super::render_counts)
+    #[prop_or_default]
+    pub count_renders: bool,
+    /// Children of the component.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A component to enable development tools.
+///
+/// You only need 1 `<Devtools />` per `<BounceRoot />`. Components can be rendered as children of
+/// `<Devtools />` so options that are read during a render apply to their first render.
+///
+/// See the [module documentation](crate::devtools) for an example.
+#[function_component(Devtools)]
+pub fn devtools(props: &DevtoolsProps) -> Html {
+    use_effect_with(props.highlight_updates, move |highlight_updates| {
+        set_highlight_updates(*highlight_updates);
+
+        let style = highlight_updates.then(|| {
+            let style = document()
+                .create_element("style")
+                .expect_throw("failed to create style element");
+            style.set_text_content(Some(HIGHLIGHT_STYLE));
+            head()
+                .append_child(&style)
+                .expect_throw("failed to append style element to head.");
+
+            style
+        });
+
+        move || {
+            set_highlight_updates(false);
+
+            if let Some(m) = style {
+                m.remove();
+            }
+        }
+    });
+
+    use_effect_with(props.log_outdated, |log_outdated| {
+        set_log_traces(*log_outdated);
+
+        || set_log_traces(false)
+    });
+
+    {
+        let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+        let count_renders = root.get_state::<CountRenders>();
+        // The option is set when this component renders, before its children are rendered.
+        count_renders.set(props.count_renders);
+
+        use_effect_with((), move |_| move || count_renders.set(false));
+    }
+
+    props.children.clone()
+}
+
+/// A hook to highlight the element of a component when it is re-rendered after a state change.
+///
+/// Only changes of states that the component is subscribed to with bounce hooks highlight the
+/// component, re-renders caused by its parent or its own yew states do not.
+///
+/// Returns a [`NodeRef`] that should be attached to the root element of the component.
+///
+/// This hook does nothing unless `highlight_updates` is enabled on the [`Devtools`] component.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::devtools::use_render_highlight;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Counter {
+///     inner: u64,
+/// }
+///
+/// #[function_component(CounterView)]
+/// fn counter_view() -> Html {
+///     let counter = use_atom_value::<Counter>();
+///     let node_ref = use_render_highlight();
+///
+///     html! { <div ref={node_ref}>{counter.inner}</div> }
+/// }
+/// ```
+///
+/// See the [module documentation](crate::devtools) for more information.
+#[hook]
+pub fn use_render_highlight() -> NodeRef {
+    let node_ref = use_node_ref();
+    let component = use_component_key();
+    let last_update = use_mut_ref(|| current_update(component));
+    let timeout = use_mut_ref(|| -> Option<Timeout> { None });
+
+    let update = current_update(component);
+    // A state this component is subscribed to has changed since the last time this component is
+    // rendered.
+    let updated = {
+        let mut last_update = last_update.borrow_mut();
+        let updated = *last_update != update;
+        *last_update = update;
+
+        updated
+    };
+
+    {
+        let node_ref = node_ref.clone();
+        use_effect(move || {
+            // The option is not a state of the root, so changing it does not re-render the
+            // component as if a state it is subscribed to has changed.
+            if HIGHLIGHT_UPDATES.with(|m| m.get()) && updated {
+                if let Some(el) = node_ref.cast::<Element>() {
+                    let class_list = el.class_list();
+                    let _ = class_list.add_1(HIGHLIGHT_CLASS);
+
+                    // Replacing the timeout cancels the previous one.
+                    *timeout.borrow_mut() = Some(Timeout::new(HIGHLIGHT_DURATION, move || {
+                        let _ = class_list.remove_1(HIGHLIGHT_CLASS);
+                    }));
+                }
+            }
+
+            || {}
+        });
+    }
+
+    // The key may be reused by another component after this component is unmounted.
+    use_effect_with(component, |component| {
+        let component = *component;
+        move || {
+            COMPONENT_UPDATES.with(|m| m.borrow_mut().remove(&component));
+        }
+    });
+
+    node_ref
+}
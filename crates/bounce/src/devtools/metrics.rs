@@ -0,0 +1,128 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::Duration;
+
+thread_local! {
+    // Incremented by the number of listeners each time a state notifies its listeners.
+    static NOTIFIED_CTR: Cell<u64> = const { Cell::new(0) };
+    static NOTION_METRICS: RefCell<HashMap<String, NotionMetrics>> = RefCell::default();
+}
+
+/// The aggregated cost of applying a notion type.
+///
+/// See the [module documentation](crate::devtools) for more information.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NotionMetrics {
+    /// The notion.
+    ///
+    /// Notions applied in a batch are recorded as `<batched notion>`.
+    pub notion: String,
+    /// The number of times the notion has been applied.
+    pub applications: u64,
+    /// The total number of states that have accepted the notion.
+    pub accepted: u64,
+    /// The total time spent applying the notion to states.
+    pub apply_duration: Duration,
+    /// The total number of listeners (components and derived states) notified by states that
+    /// have changed when the notion was applied.
+    pub notified_listeners: u64,
+}
+
+/// Returns the metrics of each notion type that has been applied, the most expensive first.
+///
+/// Notions are ordered by their total apply duration.
+///
+/// See the [module documentation](crate::devtools) for more information.
+///
+/// # Example
+///
+/// ```
+/// use bounce::devtools::notion_metrics;
+///
+/// for m in notion_metrics().into_iter().take(5) {
+///     let _message = format!(
+///         "{}: applied {} times to {} states in {:?}, notified {} listeners",
+///         m.notion, m.applications, m.accepted, m.apply_duration, m.notified_listeners
+///     );
+///     // log the message...
+/// }
+/// ```
+pub fn notion_metrics() -> Vec<NotionMetrics> {
+    let mut metrics =
+        NOTION_METRICS.with(|m| m.borrow().values().cloned().collect::<Vec<NotionMetrics>>());
+    metrics.sort_by(|lhs, rhs| {
+        rhs.apply_duration
+            .cmp(&lhs.apply_duration)
+            .then_with(|| lhs.notion.cmp(&rhs.notion))
+    });
+
+    metrics
+}
+
+/// Removes all recorded notion metrics.
+pub fn clear_notion_metrics() {
+    NOTION_METRICS.with(|m| m.borrow_mut().clear());
+}
+
+/// Records that a state has notified `count` listeners.
+pub(crate) fn record_notified(count: usize) {
+    NOTIFIED_CTR.with(|m| m.set(m.get() + count as u64));
+}
+
+/// Returns a monotonic time with sub-millisecond precision if available.
+#[cfg(target_arch = "wasm32")]
+fn precise_now() -> Duration {
+    let ms = web_sys::window()
+        .and_then(|m| m.performance())
+        .map(|m| m.now())
+        .unwrap_or_else(js_sys::Date::now);
+
+    Duration::from_secs_f64(ms / 1000.0)
+}
+
+/// Returns a monotonic time with sub-millisecond precision if available.
+#[cfg(not(target_arch = "wasm32"))]
+fn precise_now() -> Duration {
+    use std::time::Instant;
+
+    thread_local! {
+        static START: Instant = Instant::now();
+    }
+
+    START.with(|m| m.elapsed())
+}
+
+/// Measures the application of a notion.
+pub(crate) struct NotionMeasurement {
+    started_at: Duration,
+    notified: u64,
+}
+
+impl NotionMeasurement {
+    pub fn start() -> Self {
+        Self {
+            started_at: precise_now(),
+            notified: NOTIFIED_CTR.with(|m| m.get()),
+        }
+    }
+
+    pub fn finish(self, notion: &str, accepted: usize) {
+        let apply_duration = precise_now().saturating_sub(self.started_at);
+        let notified_listeners = NOTIFIED_CTR.with(|m| m.get()) - self.notified;
+
+        NOTION_METRICS.with(|m| {
+            let mut metrics = m.borrow_mut();
+            let entry = metrics
+                .entry(notion.to_string())
+                .or_insert_with(|| NotionMetrics {
+                    notion: notion.to_string(),
+                    ..Default::default()
+                });
+
+            entry.applications += 1;
+            entry.accepted += accepted as u64;
+            entry.apply_duration += apply_duration;
+            entry.notified_listeners += notified_listeners;
+        });
+    }
+}
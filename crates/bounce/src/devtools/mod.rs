@@ -0,0 +1,122 @@
+//! A module to provide development tools to inspect bounce states.
+//!
+//! Development tools are enabled by registering a `<Devtools />` component under the
+//! `<BounceRoot />`.
+//!
+//! # Highlighting Updates
+//!
+//! When `highlight_updates` is enabled, components that use the [`use_render_highlight`] hook
+//! will be outlined briefly each time they are re-rendered after a state change. This can be used
+//! to verify that a state change only re-renders components that are subscribed to it.
+//!
+//! # Counting Renders
+//!
+//! Components can count their renders with the [`use_render_count`] hook. When `count_renders`
+//! is enabled, the renders of all components are aggregated by the tag passed to the hook and
+//! can be retrieved with [`render_counts`], which reports how many components with each tag have
+//! been mounted and re-rendered.
+//!
+//! The option is read when a component renders. To count the first render of components, render
+//! them as children of the `<Devtools />` component:
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use bounce::BounceRoot;
+//! # use bounce::devtools::Devtools;
+//! #
+//! # #[function_component(Content)]
+//! # fn content() -> Html { Html::default() }
+//! #
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     html! {
+//!         <BounceRoot>
+//!             <Devtools count_renders={true}>
+//!                 <Content />
+//!             </Devtools>
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+//!
+//! # Tracing Outdated Queries
+//!
+//! Queries are fetched again when a state used during their last run has changed. Each time this
+//! happens, the chain that has led to the refetch is recorded: the state that has been updated,
+//! the selectors derived from it and the query that has been marked outdated, e.g.:
+//! `AtomSlice<Filter> -> UnitSelector<VisibleIds> -> RunQuery<ItemsQuery>`.
+//!
+//! The most recent traces can be retrieved with [`outdated_traces`]. When `log_outdated` is
+//! enabled, each trace is also logged to the browser console.
+//!
+//! # Tracing Notions
+//!
+//! Each time a notion is applied, the states that have accepted and ignored the notion are
+//! recorded (see: [`WithNotion::accepts`](crate::WithNotion::accepts)). The most recent traces
+//! can be retrieved with [`notion_traces`].
+//!
+//! # Notion Metrics
+//!
+//! For each notion type, the number of times it has been applied, the number of states that
+//! have accepted it, the time spent applying it and the number of listeners notified as a result
+//! are aggregated. The metrics can be retrieved with [`notion_metrics`], which orders notions by
+//! the time spent applying them, so expensive notions that are broadcast to many states can be
+//! identified.
+//!
+//! # Reads of Unfetched Queries
+//!
+//! Selectors can read queries with [`BounceStates::read_query`](crate::BounceStates::read_query).
+//! Reads of queries that have not been fetched yet are recorded and can be retrieved with
+//! [`unfetched_query_reads`], which helps to find selectors that read a query no component
+//! fetches.
+//!
+//! # Example
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use bounce::prelude::*;
+//! # use bounce::BounceRoot;
+//! use bounce::devtools::{use_render_highlight, Devtools};
+//!
+//! #[derive(PartialEq, Default, Atom)]
+//! struct Username {
+//!     inner: String,
+//! }
+//!
+//! #[function_component(Reader)]
+//! fn reader() -> Html {
+//!     let node_ref = use_render_highlight();
+//!     let username = use_atom_value::<Username>();
+//!
+//!     html! { <div ref={node_ref}>{"Hello, "}{&username.inner}</div> }
+//! }
+//!
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     html! {
+//!         <BounceRoot>
+//!             <Devtools highlight_updates={true} log_outdated={true} />
+//!             <Reader />
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+
+mod highlight;
+mod metrics;
+mod render_count;
+mod trace;
+
+pub use highlight::{use_render_highlight, Devtools, DevtoolsProps};
+pub use metrics::{clear_notion_metrics, notion_metrics, NotionMetrics};
+pub use render_count::{clear_render_counts, render_counts, use_render_count, RenderCount};
+pub use trace::{
+    clear_notion_traces, clear_outdated_traces, clear_unfetched_query_reads, notion_traces,
+    outdated_traces, unfetched_query_reads, NotionTrace, OutdatedTrace,
+};
+
+pub(crate) use highlight::{record_update, use_component_key};
+pub(crate) use metrics::record_notified;
+#[cfg(feature = "query")]
+pub(crate) use trace::record_unfetched_read;
+pub(crate) use trace::{enter_change, preserve_change_chain, record_outdated, NotionTraceRecorder};
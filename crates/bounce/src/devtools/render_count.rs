@@ -0,0 +1,167 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use yew::prelude::*;
+
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+
+thread_local! {
+    static RENDER_COUNTS: RefCell<HashMap<&'static str, RenderCount>> = RefCell::default();
+}
+
+/// The number of times components with the same tag have been rendered.
+///
+/// See the [module documentation](crate::devtools) for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderCount {
+    /// The tag passed to [`use_render_count`].
+    pub tag: &'static str,
+    /// The number of components with this tag that have been rendered for the first time.
+    pub mounts: u64,
+    /// The total number of times components with this tag have been rendered, including the
+    /// first render of each component.
+    pub renders: u64,
+}
+
+impl RenderCount {
+    /// Returns the number of times components with this tag have been re-rendered.
+    pub fn rerenders(&self) -> u64 {
+        self.renders - self.mounts
+    }
+}
+
+/// Whether the renders of components under a root are counted.
+///
+/// The option is set by the [`Devtools`](super::Devtools) component when it renders and read by
+/// [`use_render_count`] when a component renders, so it applies to the first render of the
+/// children of the `Devtools` component.
+#[derive(Default, Clone)]
+pub(super) struct CountRenders {
+    inner: Rc<Cell<bool>>,
+}
+
+impl CountRenders {
+    pub fn set(&self, enabled: bool) {
+        self.inner.set(enabled);
+    }
+
+    fn get(&self) -> bool {
+        self.inner.get()
+    }
+}
+
+impl AnyState for CountRenders {
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// Returns the number of renders of each tag, the most rendered first.
+///
+/// Renders are only counted while `count_renders` is enabled on the
+/// [`Devtools`](super::Devtools) component. Renders of components that are rendered before the
+/// `Devtools` component are not counted, components that are children of the `Devtools`
+/// component are counted from their first render.
+///
+/// See the [module documentation](crate::devtools) for more information.
+///
+/// # Example
+///
+/// ```
+/// use bounce::devtools::render_counts;
+///
+/// for m in render_counts() {
+///     let _message = format!("{}: re-rendered {} times", m.tag, m.rerenders());
+///     // log the message...
+/// }
+/// ```
+pub fn render_counts() -> Vec<RenderCount> {
+    let mut counts =
+        RENDER_COUNTS.with(|m| m.borrow().values().cloned().collect::<Vec<RenderCount>>());
+    counts.sort_by(|lhs, rhs| {
+        rhs.renders
+            .cmp(&lhs.renders)
+            .then_with(|| lhs.tag.cmp(rhs.tag))
+    });
+
+    counts
+}
+
+/// Removes all recorded render counts.
+pub fn clear_render_counts() {
+    RENDER_COUNTS.with(|m| m.borrow_mut().clear());
+}
+
+/// A hook to count the renders of a component.
+///
+/// Returns the number of times the component has been rendered, including the current render.
+///
+/// When `count_renders` is enabled on the [`Devtools`](super::Devtools) component of the root,
+/// renders are also aggregated by `tag` and can be retrieved with [`render_counts`]. This can be
+/// used to verify that a state change only re-renders the components that are subscribed to it.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::devtools::use_render_count;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Counter {
+///     inner: u64,
+/// }
+///
+/// #[function_component(CounterView)]
+/// fn counter_view() -> Html {
+///     let counter = use_atom_value::<Counter>();
+///     let renders = use_render_count("CounterView");
+///
+///     html! { <div>{format!("{} (rendered {} times)", counter.inner, renders)}</div> }
+/// }
+/// ```
+#[hook]
+pub fn use_render_count(tag: &'static str) -> u64 {
+    let root = use_context::<BounceRootState>();
+    let ctr = use_mut_ref(|| 0);
+
+    let renders = {
+        let mut ctr = ctr.borrow_mut();
+        *ctr += 1;
+
+        *ctr
+    };
+
+    // The option is read during the render, so renders that happen before an effect would have
+    // run are counted.
+    let count_renders = root
+        .map(|m| m.get_state::<CountRenders>().get())
+        .unwrap_or(false);
+
+    if count_renders {
+        RENDER_COUNTS.with(|m| {
+            let mut counts = m.borrow_mut();
+            let entry = counts.entry(tag).or_insert_with(|| RenderCount {
+                tag,
+                mounts: 0,
+                renders: 0,
+            });
+
+            if renders == 1 {
+                entry.mounts += 1;
+            }
+            entry.renders += 1;
+        });
+    }
+
+    renders
+}
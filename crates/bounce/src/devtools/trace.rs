@@ -0,0 +1,279 @@
+use std::any::type_name;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+
+use super::metrics::NotionMeasurement;
+use crate::any_state::AnyState;
+
+/// The maximum number of traces that are kept.
+const MAX_TRACES: usize = 100;
+
+thread_local! {
+    // The states that have changed and led to the notification that is currently being sent.
+    static CHANGE_CHAIN: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static TRACES: RefCell<VecDeque<OutdatedTrace>> = RefCell::default();
+    static NOTION_TRACES: RefCell<VecDeque<NotionTrace>> = RefCell::default();
+    static UNFETCHED_READS: RefCell<VecDeque<String>> = RefCell::default();
+    static LOG_TRACES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A record of a future notion (e.g.: a query) that has been marked outdated.
+///
+/// Future notions are marked outdated when a state used during their run has changed. Queries
+/// that are outdated are fetched again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutdatedTrace {
+    /// The states that have changed, in order.
+    ///
+    /// The first state is the state that has been updated, each following state is a selector
+    /// that is derived from the previous state.
+    pub chain: Vec<String>,
+    /// The future notion that has been marked outdated.
+    ///
+    /// For queries, this is `RunQuery<T>`, where `T` is the query.
+    pub notion: String,
+}
+
+impl fmt::Display for OutdatedTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for state in self.chain.iter() {
+            write!(f, "{state} -> ")?;
+        }
+
+        write!(f, "{}", self.notion)
+    }
+}
+
+/// A record of a notion that has been applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotionTrace {
+    /// The notion that has been applied.
+    ///
+    /// Notions applied in a batch are recorded as `<batched notion>`.
+    pub notion: String,
+    /// The states that have accepted the notion, in the order the notion was applied.
+    pub accepted: Vec<String>,
+    /// The states that have ignored the notion.
+    pub ignored: Vec<String>,
+}
+
+impl fmt::Display for NotionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (accepted: [{}], ignored: [{}])",
+            self.notion,
+            self.accepted.join(", "),
+            self.ignored.join(", ")
+        )
+    }
+}
+
+/// Returns the most recent notion traces, oldest first.
+///
+/// Only the last 100 traces are kept.
+///
+/// See the [module documentation](crate::devtools) for more information.
+pub fn notion_traces() -> Vec<NotionTrace> {
+    NOTION_TRACES.with(|m| m.borrow().iter().cloned().collect())
+}
+
+/// Removes all recorded notion traces.
+pub fn clear_notion_traces() {
+    NOTION_TRACES.with(|m| m.borrow_mut().clear());
+}
+
+/// Returns the queries that have been read before they are fetched, oldest first.
+///
+/// A query is recorded each time it is read with
+/// [`BounceStates::read_query`](crate::BounceStates::read_query) before it has been fetched
+/// with the same input. Only the last 100 reads are kept.
+///
+/// See the [module documentation](crate::devtools) for more information.
+pub fn unfetched_query_reads() -> Vec<String> {
+    UNFETCHED_READS.with(|m| m.borrow().iter().cloned().collect())
+}
+
+/// Removes all recorded reads of unfetched queries.
+pub fn clear_unfetched_query_reads() {
+    UNFETCHED_READS.with(|m| m.borrow_mut().clear());
+}
+
+/// Records that query `T` has been read before it is fetched.
+#[cfg(feature = "query")]
+pub(crate) fn record_unfetched_read<T>()
+where
+    T: ?Sized,
+{
+    let query = short_type_name(type_name::<T>());
+
+    UNFETCHED_READS.with(|m| {
+        let mut reads = m.borrow_mut();
+
+        if reads.len() >= MAX_TRACES {
+            reads.pop_front();
+        }
+
+        reads.push_back(query);
+    });
+}
+
+/// Returns the most recent outdated traces, oldest first.
+///
+/// Only the last 100 traces are kept.
+///
+/// See the [module documentation](crate::devtools) for more information.
+pub fn outdated_traces() -> Vec<OutdatedTrace> {
+    TRACES.with(|m| m.borrow().iter().cloned().collect())
+}
+
+/// Removes all recorded outdated traces.
+pub fn clear_outdated_traces() {
+    TRACES.with(|m| m.borrow_mut().clear());
+}
+
+pub(super) fn set_log_traces(log_traces: bool) {
+    LOG_TRACES.with(|m| m.set(log_traces));
+}
+
+/// Removes the module path of each type in a type name.
+fn short_type_name(name: &'static str) -> String {
+    let mut short_name = String::with_capacity(name.len());
+    let mut segment_start = 0;
+
+    for (i, c) in name.char_indices() {
+        if matches!(c, '<' | '>' | ',' | ' ' | '(' | ')' | '[' | ']' | '&') {
+            short_name.push_str(
+                name[segment_start..i]
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or_default(),
+            );
+            short_name.push(c);
+            segment_start = i + c.len_utf8();
+        }
+    }
+
+    short_name.push_str(
+        name[segment_start..]
+            .rsplit("::")
+            .next()
+            .unwrap_or_default(),
+    );
+
+    short_name
+}
+
+/// Removes the state from the change chain when the notification has been sent.
+pub(crate) struct ChangeGuard {
+    _private: (),
+}
+
+impl Drop for ChangeGuard {
+    fn drop(&mut self) {
+        CHANGE_CHAIN.with(|m| m.borrow_mut().pop());
+    }
+}
+
+/// Records that state `T` has changed and is notifying its listeners.
+pub(crate) fn enter_change<T>() -> ChangeGuard
+where
+    T: ?Sized,
+{
+    CHANGE_CHAIN.with(|m| m.borrow_mut().push(type_name::<T>()));
+
+    ChangeGuard { _private: () }
+}
+
+/// Preserves the current change chain for a notification that is sent later.
+pub(crate) fn preserve_change_chain(notify: Box<dyn FnOnce()>) -> Box<dyn FnOnce()> {
+    let chain = CHANGE_CHAIN.with(|m| m.borrow().clone());
+
+    Box::new(move || {
+        let last_chain = CHANGE_CHAIN.with(|m| m.replace(chain));
+        notify();
+        CHANGE_CHAIN.with(|m| m.replace(last_chain));
+    })
+}
+
+/// Records that future notion `T` has been marked outdated by the current change chain.
+pub(crate) fn record_outdated<T>()
+where
+    T: ?Sized,
+{
+    let mut chain = CHANGE_CHAIN.with(|m| {
+        m.borrow()
+            .iter()
+            .map(|m| short_type_name(m))
+            .collect::<Vec<_>>()
+    });
+    // A state may appear more than once if it is notified again during the same change.
+    chain.dedup();
+
+    let trace = OutdatedTrace {
+        chain,
+        notion: short_type_name(type_name::<T>()),
+    };
+
+    if LOG_TRACES.with(|m| m.get()) {
+        gloo::console::debug!(format!("bounce: outdated {trace}"));
+    }
+
+    TRACES.with(|m| {
+        let mut traces = m.borrow_mut();
+
+        if traces.len() >= MAX_TRACES {
+            traces.pop_front();
+        }
+
+        traces.push_back(trace);
+    });
+}
+
+/// Records the states that have accepted or ignored a notion and the cost of applying it.
+pub(crate) struct NotionTraceRecorder {
+    trace: NotionTrace,
+    measurement: NotionMeasurement,
+}
+
+impl NotionTraceRecorder {
+    pub fn new(notion: &'static str) -> Self {
+        Self {
+            trace: NotionTrace {
+                notion: short_type_name(notion),
+                accepted: Vec::new(),
+                ignored: Vec::new(),
+            },
+            measurement: NotionMeasurement::start(),
+        }
+    }
+
+    pub fn record(&mut self, state: &dyn AnyState, accepted: bool) {
+        let name = state
+            .describe()
+            .map(|m| short_type_name(m.type_name))
+            .unwrap_or_else(|| "<internal state>".to_string());
+
+        if accepted {
+            self.trace.accepted.push(name);
+        } else {
+            self.trace.ignored.push(name);
+        }
+    }
+
+    pub fn finish(self) {
+        self.measurement
+            .finish(&self.trace.notion, self.trace.accepted.len());
+
+        NOTION_TRACES.with(|m| {
+            let mut traces = m.borrow_mut();
+
+            if traces.len() >= MAX_TRACES {
+                traces.pop_front();
+            }
+
+            traces.push_back(self.trace);
+        });
+    }
+}
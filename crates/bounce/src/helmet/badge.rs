@@ -0,0 +1,226 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use gloo::utils::document;
+use yew::prelude::*;
+
+use super::state::HelmetTag;
+use crate::states::artifact::Artifact;
+use crate::states::selector::{use_selector_value, Selector};
+
+/// A badge registered by a [`TitleBadge`].
+#[derive(Debug, PartialEq)]
+pub(crate) struct BadgeState {
+    badge: Option<AttrValue>,
+    favicon: Option<AttrValue>,
+}
+
+fn is_favicon(tag: &HelmetTag) -> bool {
+    match tag {
+        HelmetTag::Link { attrs } => attrs
+            .get("rel")
+            .map(|m| {
+                m.split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("icon"))
+            })
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Applies the latest registered badge to merged helmet tags.
+///
+/// The badge is prepended to the title and the `href` of all favicon links is replaced with the
+/// favicon of the badge. If no favicon link is registered, one is added.
+///
+/// If no title is registered, the title of the document is badged instead. It is kept in
+/// `document_title` while the badge is shown and restored once the badge is removed.
+pub(crate) fn apply_badge(
+    tags: &mut BTreeSet<Arc<HelmetTag>>,
+    badges: &[Rc<BadgeState>],
+    document_title: &mut Option<Arc<str>>,
+) {
+    let has_title = tags.iter().any(|m| matches!(**m, HelmetTag::Title(_)));
+    let has_badge = badges.last().map(|m| m.badge.is_some()).unwrap_or(false);
+
+    if has_title {
+        // A registered title replaces the title of the document.
+        *document_title = None;
+    } else if has_badge {
+        let title = document_title
+            .get_or_insert_with(|| document().title().into())
+            .clone();
+        tags.insert(HelmetTag::Title(title).into());
+    } else if let Some(m) = document_title.take() {
+        tags.insert(HelmetTag::Title(m).into());
+    }
+
+    let badge = match badges.last() {
+        Some(m) => m,
+        None => return,
+    };
+
+    if let Some(ref badge) = badge.badge {
+        let title = tags
+            .iter()
+            .find(|m| matches!(***m, HelmetTag::Title(_)))
+            .cloned();
+
+        if let Some(m) = title {
+            if let HelmetTag::Title(ref title) = *m {
+                let badged = HelmetTag::Title(format!("({badge}) {title}").into());
+
+                tags.remove(&m);
+                tags.insert(badged.into());
+            }
+        }
+    }
+
+    if let Some(ref favicon) = badge.favicon {
+        let favicons = tags
+            .iter()
+            .filter(|m| is_favicon(m))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if favicons.is_empty() {
+            let mut attrs = BTreeMap::new();
+            attrs.insert("rel".into(), "icon".into());
+            attrs.insert("href".into(), favicon.as_str().into());
+
+            tags.insert(HelmetTag::Link { attrs }.into());
+        }
+
+        for m in favicons {
+            if let HelmetTag::Link { ref attrs } = *m {
+                let mut attrs = attrs.clone();
+                attrs.insert("href".into(), favicon.as_str().into());
+
+                tags.remove(&m);
+                tags.insert(HelmetTag::Link { attrs }.into());
+            }
+        }
+    }
+}
+
+/// A value that can be shown as a badge of the document.
+///
+/// This is implemented by the value of a selector rendered with a [`SelectorBadge`].
+pub trait HelmetBadge {
+    /// Returns the badge to prepend to the title, e.g.: `"3"` shows `(3) Inbox`.
+    ///
+    /// Returns `None` if the title should not have a badge.
+    fn badge(&self) -> Option<AttrValue>;
+
+    /// Returns the `href` of the favicon to show instead of the favicon of the page.
+    ///
+    /// Returns `None` if the favicon should not be replaced. The default implementation does not
+    /// replace the favicon.
+    fn favicon(&self) -> Option<AttrValue> {
+        None
+    }
+}
+
+/// Properties of the [`TitleBadge`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct TitleBadgeProps {
+    /// The badge to prepend to the title.
+    #[prop_or_default]
+    pub badge: Option<AttrValue>,
+    /// The `href` of the favicon to show instead of the favicon of the page.
+    #[prop_or_default]
+    pub favicon: Option<AttrValue>,
+}
+
+/// A component that shows a badge in the title and swaps the favicon.
+///
+/// The badge is prepended to the title after it is formatted by the `<HelmetBridge />`, so
+/// `badge="3"` shows the title `Inbox` as `(3) Inbox`. If no title is set with a `<Helmet />`,
+/// the title of the document is badged. When both properties are `None` or the component is
+/// unmounted, the title and the favicon are restored. If multiple badges are rendered, the one
+/// rendered last is applied.
+///
+/// Badges are only applied in the browser, they are not written by the
+/// [`StaticWriter`](super::StaticWriter).
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::helmet::TitleBadge;
+///
+/// #[derive(Properties, PartialEq)]
+/// struct InboxProps {
+///     unread: u64,
+/// }
+///
+/// #[function_component(Inbox)]
+/// fn inbox(props: &InboxProps) -> Html {
+///     let badge = (props.unread > 0).then(|| AttrValue::from(props.unread.to_string()));
+///     let favicon = (props.unread > 0).then(|| AttrValue::from("/favicon-unread.png"));
+///
+///     html! { <TitleBadge {badge} {favicon} /> }
+/// }
+/// ```
+#[function_component(TitleBadge)]
+pub fn title_badge(props: &TitleBadgeProps) -> Html {
+    let TitleBadgeProps { badge, favicon } = props.clone();
+
+    if badge.is_none() && favicon.is_none() {
+        return Html::default();
+    }
+
+    let value = Rc::new(BadgeState { badge, favicon });
+
+    html! { <Artifact<BadgeState> {value} /> }
+}
+
+/// A component that shows the value of a selector as a badge in the title and the favicon.
+///
+/// The component subscribes to the selector and renders a [`TitleBadge`] with the badge and the
+/// favicon of its value, so the title and the favicon are restored when the value returns `None`
+/// for both.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::helmet::{HelmetBadge, SelectorBadge};
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Messages {
+///     unread: Vec<u64>,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct UnreadCount(usize);
+///
+/// impl Selector for UnreadCount {
+///     fn select(states: &BounceStates) -> Rc<Self> {
+///         Self(states.get_atom_value::<Messages>().unread.len()).into()
+///     }
+/// }
+///
+/// impl HelmetBadge for UnreadCount {
+///     fn badge(&self) -> Option<AttrValue> {
+///         (self.0 > 0).then(|| self.0.to_string().into())
+///     }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! { <SelectorBadge<UnreadCount> /> }
+/// }
+/// ```
+#[function_component(SelectorBadge)]
+pub fn selector_badge<T>() -> Html
+where
+    T: Selector + HelmetBadge + 'static,
+{
+    let value = use_selector_value::<T>();
+
+    html! { <TitleBadge badge={value.badge()} favicon={value.favicon()} /> }
+}
@@ -6,13 +6,16 @@ use std::sync::Arc;
 use gloo::utils::head;
 use wasm_bindgen::UnwrapThrowExt;
 use web_sys::Element;
+use yew::html::IntoPropValue;
 use yew::prelude::*;
 use yew::virtual_dom::AttrValue;
 
+use super::badge::{apply_badge, BadgeState};
+use super::robots::override_robots;
 use super::state::{merge_helmet_states, HelmetState, HelmetTag};
-use super::FormatTitle;
 #[cfg(feature = "ssr")]
 use super::StaticWriter;
+use super::{FormatTitle, RobotsPolicy};
 use crate::states::artifact::use_artifacts;
 
 #[cfg(debug_assertions)]
@@ -75,6 +78,53 @@ mod guard {
     }
 }
 
+/// A marker passed as the `writer` of a [HelmetBridge] that does not write tags for server-side
+/// rendering.
+///
+/// With the `ssr` feature, the `writer` of a bridge is required, so a bridge that is rendered on
+/// the server without a [`StaticWriter`](super::StaticWriter) fails to compile instead of
+/// rendering an empty `<head />`. Bridges that are only rendered on the client (e.g.: the bridge
+/// of the application that is hydrated) pass this marker to omit the writer intentionally.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CsrOnly;
+
+/// The writer of a [HelmetBridge].
+///
+/// It is created from a [`StaticWriter`](super::StaticWriter) or [`CsrOnly`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(not(feature = "ssr"), derive(Default))]
+pub struct HelmetWriter {
+    #[cfg(feature = "ssr")]
+    inner: Option<StaticWriter>,
+}
+
+impl fmt::Debug for HelmetWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "ssr")]
+        if self.inner.is_some() {
+            return f.write_str("HelmetWriter::Static");
+        }
+
+        f.write_str("HelmetWriter::CsrOnly")
+    }
+}
+
+impl IntoPropValue<HelmetWriter> for CsrOnly {
+    fn into_prop_value(self) -> HelmetWriter {
+        HelmetWriter {
+            #[cfg(feature = "ssr")]
+            inner: None,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl IntoPropValue<HelmetWriter> for StaticWriter {
+    fn into_prop_value(self) -> HelmetWriter {
+        HelmetWriter { inner: Some(self) }
+    }
+}
+
 /// Properties of the [HelmetBridge].
 #[derive(Properties, PartialEq, Clone)]
 pub struct HelmetBridgeProps {
@@ -86,10 +136,35 @@ pub struct HelmetBridgeProps {
     #[prop_or_default]
     pub format_title: Option<FormatTitle>,
 
-    /// The StaticWriter to write to.
-    #[cfg(feature = "ssr")]
+    /// A template to format title, each `%s` in the template is replaced with the title, e.g.:
+    /// `"%s - My Site"`.
+    ///
+    /// Unlike `format_title`, a template is a plain value that is applied identically by the
+    /// `StaticWriter` during server-side rendering and by the bridge after hydration. It is
+    /// ignored if `format_title` is provided. The `default_title` is not formatted.
     #[prop_or_default]
-    pub writer: Option<StaticWriter>,
+    pub title_template: Option<AttrValue>,
+
+    /// A callback that is called with the rendered tags each time the tags are committed to the
+    /// document.
+    ///
+    /// This can be used to notify analytics of title changes or to signal a prerenderer that
+    /// the `<head />` has settled.
+    #[prop_or_default]
+    pub on_commit: Option<Callback<Vec<HelmetTag>>>,
+
+    /// The robots policy to apply regardless of the robots meta tags registered by pages.
+    ///
+    /// This can be used to prevent a staging environment from being indexed.
+    #[prop_or_default]
+    pub robots: Option<RobotsPolicy>,
+
+    /// The writer to write tags to during server-side rendering, a
+    /// [`StaticWriter`](super::StaticWriter) or [`CsrOnly`].
+    ///
+    /// This property is required with the `ssr` feature.
+    #[cfg_attr(not(feature = "ssr"), prop_or_default)]
+    pub writer: HelmetWriter,
 }
 
 impl fmt::Debug for HelmetBridgeProps {
@@ -104,6 +179,10 @@ impl fmt::Debug for HelmetBridgeProps {
                     &"None"
                 },
             )
+            .field("title_template", &self.title_template)
+            .field("on_commit", &self.on_commit)
+            .field("robots", &self.robots)
+            .field("writer", &self.writer)
             .finish()
     }
 }
@@ -187,6 +266,19 @@ fn render_tags(
 /// are registered and a `format_title` function which is used to format the title before it is
 /// passed to the document.
 ///
+/// A `title_template` (e.g.: `"%s - My Site"`) can be used instead of `format_title`. As it is
+/// not a callback, the same template is applied when the title is rendered by the
+/// `StaticWriter`, so the title after hydration matches the server-side rendered title.
+///
+/// Tags are reconciled with the tags that are already rendered. When only the title changes,
+/// only the title of the document is updated.
+///
+/// An `on_commit` callback can also be provided, which is called with the rendered tags after
+/// they are committed to the document.
+///
+/// A `robots` policy can be provided to replace the robots meta tags registered by all pages,
+/// e.g.: to prevent a staging environment from being indexed.
+///
 /// # Panics
 ///
 /// You can only register 1 `HelmetBridge` per `BounceRoot`. Registering multiple `HelmetBridge`s
@@ -198,13 +290,80 @@ fn render_tags(
 /// # use yew::prelude::*;
 /// # use bounce::prelude::*;
 /// # use bounce::BounceRoot;
-/// # use bounce::helmet::HelmetBridge;
+/// # use bounce::helmet::{CsrOnly, HelmetBridge};
 /// #
 /// # #[function_component(Comp)]
 /// # fn comp() -> Html {
 /// html! {
 ///     <BounceRoot>
-///         <HelmetBridge default_title="default title" />
+///         <HelmetBridge default_title="default title" writer={CsrOnly} />
+///         // other components.
+///     </BounceRoot>
+/// }
+/// # }
+/// ```
+///
+/// Formatting titles with a template:
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::BounceRoot;
+/// # use bounce::helmet::{CsrOnly, HelmetBridge};
+/// #
+/// # #[function_component(Comp)]
+/// # fn comp() -> Html {
+/// html! {
+///     <BounceRoot>
+///         // A page with the title "About" is rendered as "About - My Site".
+///         <HelmetBridge default_title="My Site" title_template="%s - My Site" writer={CsrOnly} />
+///         // other components.
+///     </BounceRoot>
+/// }
+/// # }
+/// ```
+///
+/// Reporting title changes:
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::BounceRoot;
+/// # use bounce::helmet::{CsrOnly, HelmetBridge, HelmetTag};
+/// #
+/// # #[function_component(Comp)]
+/// # fn comp() -> Html {
+/// let on_commit = Callback::from(|tags: Vec<HelmetTag>| {
+///     for tag in tags {
+///         if let HelmetTag::Title(title) = tag {
+///             // report page view with title.
+///         }
+///     }
+/// });
+///
+/// html! {
+///     <BounceRoot>
+///         <HelmetBridge {on_commit} writer={CsrOnly} />
+///         // other components.
+///     </BounceRoot>
+/// }
+/// # }
+/// ```
+///
+/// Preventing a staging environment from being indexed:
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::BounceRoot;
+/// # use bounce::helmet::{CsrOnly, HelmetBridge, RobotsPolicy};
+/// #
+/// # #[function_component(Comp)]
+/// # fn comp() -> Html {
+/// let is_staging = true;
+/// // Pages may still render <RobotsMeta /> or <NoIndex />, which are replaced on staging.
+/// let robots = is_staging.then_some(RobotsPolicy::NONE);
+///
+/// html! {
+///     <BounceRoot>
+///         <HelmetBridge {robots} writer={CsrOnly} />
 ///         // other components.
 ///     </BounceRoot>
 /// }
@@ -218,23 +377,30 @@ pub fn helmet_bridge(props: &HelmetBridgeProps) -> Html {
     }
 
     let helmet_states = use_artifacts::<HelmetState>();
+    let badges = use_artifacts::<BadgeState>();
 
     let rendered = use_mut_ref(|| -> Option<BTreeMap<Arc<HelmetTag>, Option<Element>>> { None });
+    // The title of the document while it is badged, if no title is set with helmet.
+    let document_title = use_mut_ref(|| -> Option<Arc<str>> { None });
 
     #[cfg(feature = "ssr")]
     {
         use super::ssr::StaticWriterState;
         use crate::use_atom_setter;
 
-        let writer = props.writer.clone();
+        let writer = props.writer.inner.clone();
         let format_title = props.format_title.clone();
+        let title_template = props.title_template.clone();
         let default_title = props.default_title.clone();
+        let robots = props.robots;
         let set_static_writer_state = use_atom_setter::<StaticWriterState>();
 
         use_state(move || {
             set_static_writer_state(StaticWriterState {
                 format_title,
+                title_template,
                 default_title,
+                robots,
                 writer,
             })
         });
@@ -255,19 +421,49 @@ pub fn helmet_bridge(props: &HelmetBridgeProps) -> Html {
         }
     });
 
+    // The latest callback is used so that a new callback does not cause tags to be rendered
+    // again.
+    let on_commit = use_mut_ref(|| None);
+    *on_commit.borrow_mut() = props.on_commit.clone();
+
     use_effect_with(
         (
             helmet_states,
             props.format_title.clone(),
+            props.title_template.clone(),
             props.default_title.clone(),
+            props.robots,
+            badges,
         ),
-        move |(helmet_states, format_title, default_title)| {
+        move |(helmet_states, format_title, title_template, default_title, robots, badges)| {
             // Calculate tags to render.
-            let to_render =
-                merge_helmet_states(helmet_states, format_title.as_ref(), default_title.clone());
-
-            let mut rendered = rendered.borrow_mut();
-            *rendered = Some(render_tags(to_render, rendered.take()));
+            let mut to_render = merge_helmet_states(
+                helmet_states,
+                format_title.as_ref(),
+                title_template.as_ref(),
+                default_title.clone(),
+            );
+            if let Some(m) = robots {
+                override_robots(&mut to_render, *m);
+            }
+            apply_badge(&mut to_render, badges, &mut document_title.borrow_mut());
+
+            let committed_tags = {
+                let mut rendered = rendered.borrow_mut();
+                let next_rendered = render_tags(to_render, rendered.take());
+                let committed_tags = next_rendered
+                    .keys()
+                    .map(|m| (**m).clone())
+                    .collect::<Vec<_>>();
+                *rendered = Some(next_rendered);
+
+                committed_tags
+            };
+
+            let on_commit = on_commit.borrow().clone();
+            if let Some(m) = on_commit {
+                m.emit(committed_tags);
+            }
 
             || {}
         },
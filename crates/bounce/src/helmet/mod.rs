@@ -20,7 +20,7 @@
 //! # use yew::prelude::*;
 //! # use bounce::BounceRoot;
 //! # use bounce::prelude::*;
-//! use bounce::helmet::{Helmet, HelmetBridge};
+//! use bounce::helmet::{CsrOnly, Helmet, HelmetBridge};
 //!
 //! #[function_component(PageA)]
 //! fn page_a() -> Html {
@@ -42,7 +42,8 @@
 //!             // A helmet bridge is required to apply helmet elements to the head element.
 //!             // You only need 1 helmet bridge per bounce root.
 //!             // The helmet bridge is intended to live as long as the BounceRoot.
-//!             <HelmetBridge default_title="default title" />
+//!             // This bridge is not rendered on the server, so it does not have a StaticWriter.
+//!             <HelmetBridge default_title="default title" writer={CsrOnly} />
 //!             <Helmet>
 //!                 // The title to apply.
 //!                 //
@@ -61,19 +62,25 @@
 
 use yew::prelude::*;
 
+mod badge;
 mod bridge;
 mod comp;
+mod robots;
 #[cfg(feature = "ssr")]
 mod ssr;
 mod state;
+mod theme;
 
-pub use bridge::{HelmetBridge, HelmetBridgeProps};
+pub use badge::{HelmetBadge, SelectorBadge, TitleBadge, TitleBadgeProps};
+pub use bridge::{CsrOnly, HelmetBridge, HelmetBridgeProps, HelmetWriter};
 pub use comp::{Helmet, HelmetProps};
+pub use robots::{NoIndex, RobotsMeta, RobotsMetaProps, RobotsPolicy};
 #[cfg(feature = "ssr")]
 pub(crate) use ssr::StaticWriterState;
 #[cfg(feature = "ssr")]
 #[cfg_attr(documenting, doc(cfg(feature = "ssr")))]
 pub use ssr::{render_static, StaticRenderer, StaticWriter};
 pub use state::HelmetTag;
+pub use theme::{use_theme, use_toggle_theme, ThemeAtom, ThemeAttr, ThemeHelmet, ThemeHelmetProps};
 
 type FormatTitle = Callback<AttrValue, AttrValue>;
@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+use yew::prelude::*;
+
+use super::state::HelmetTag;
+use super::Helmet;
+
+/// Names of meta tags that instruct crawlers how to index a page.
+const ROBOTS_META_NAMES: &[&str] = &["robots", "googlebot", "bingbot"];
+
+/// The indexing policy written to a `<meta name="robots" />` tag.
+///
+/// # Example
+///
+/// ```
+/// use bounce::helmet::RobotsPolicy;
+///
+/// assert_eq!(RobotsPolicy::ALL.content(), "index, follow");
+/// assert_eq!(RobotsPolicy::NONE.content(), "noindex, nofollow");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RobotsPolicy {
+    /// Whether crawlers may index the page.
+    pub index: bool,
+    /// Whether crawlers may follow the links on the page.
+    pub follow: bool,
+}
+
+impl RobotsPolicy {
+    /// Allows crawlers to index the page and follow its links.
+    pub const ALL: Self = Self {
+        index: true,
+        follow: true,
+    };
+
+    /// Forbids crawlers to index the page and follow its links.
+    pub const NONE: Self = Self {
+        index: false,
+        follow: false,
+    };
+
+    /// Forbids crawlers to index the page, but allows them to follow its links.
+    pub const NO_INDEX: Self = Self {
+        index: false,
+        follow: true,
+    };
+
+    /// Returns the value of the `content` attribute of the meta tag.
+    pub fn content(&self) -> &'static str {
+        match (self.index, self.follow) {
+            (true, true) => "index, follow",
+            (true, false) => "index, nofollow",
+            (false, true) => "noindex, follow",
+            (false, false) => "noindex, nofollow",
+        }
+    }
+
+    fn to_tag(self) -> Arc<HelmetTag> {
+        let mut attrs = BTreeMap::new();
+        attrs.insert("name".into(), "robots".into());
+        attrs.insert("content".into(), self.content().into());
+
+        HelmetTag::Meta { attrs }.into()
+    }
+}
+
+impl Default for RobotsPolicy {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl fmt::Display for RobotsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.content())
+    }
+}
+
+fn is_robots_meta(tag: &HelmetTag) -> bool {
+    match tag {
+        HelmetTag::Meta { attrs } => attrs
+            .get("name")
+            .map(|m| {
+                ROBOTS_META_NAMES
+                    .iter()
+                    .any(|name| m.eq_ignore_ascii_case(name))
+            })
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Replaces all robots meta tags of merged helmet tags with the tag of the policy.
+///
+/// Crawler specific tags (e.g.: `<meta name="googlebot" />`) are removed as well so they cannot
+/// contradict the policy.
+pub(crate) fn override_robots(tags: &mut BTreeSet<Arc<HelmetTag>>, policy: RobotsPolicy) {
+    tags.retain(|m| !is_robots_meta(m));
+    tags.insert(policy.to_tag());
+}
+
+/// Properties of the [`RobotsMeta`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct RobotsMetaProps {
+    /// The policy to apply.
+    pub policy: RobotsPolicy,
+}
+
+/// A component that registers a `<meta name="robots" />` tag with the content of a policy.
+///
+/// The policy can be overridden for all pages with the `robots` property of the
+/// [`HelmetBridge`](super::HelmetBridge).
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::helmet::{RobotsMeta, RobotsPolicy};
+///
+/// #[function_component(SearchPage)]
+/// fn search_page() -> Html {
+///     html! {
+///         <>
+///             // Renders <meta name="robots" content="noindex, follow">
+///             <RobotsMeta policy={RobotsPolicy::NO_INDEX} />
+///             <div>{"Search Results"}</div>
+///         </>
+///     }
+/// }
+/// ```
+#[function_component(RobotsMeta)]
+pub fn robots_meta(props: &RobotsMetaProps) -> Html {
+    html! {
+        <Helmet>
+            <meta name="robots" content={props.policy.content()} />
+        </Helmet>
+    }
+}
+
+/// A component that forbids crawlers to index the current page.
+///
+/// This is a shorthand of `<RobotsMeta policy={RobotsPolicy::NO_INDEX} />`.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::helmet::NoIndex;
+///
+/// #[function_component(AccountPage)]
+/// fn account_page() -> Html {
+///     html! {
+///         <>
+///             <NoIndex />
+///             <div>{"Account Settings"}</div>
+///         </>
+///     }
+/// }
+/// ```
+#[function_component(NoIndex)]
+pub fn no_index() -> Html {
+    html! { <RobotsMeta policy={RobotsPolicy::NO_INDEX} /> }
+}
@@ -11,8 +11,10 @@ use futures::channel::oneshot as sync_oneshot;
 use crate::root_state::BounceStates;
 use crate::Atom;
 
+use super::robots::override_robots;
 use super::state::{merge_helmet_states, HelmetState, HelmetTag};
 use super::FormatTitle;
+use super::RobotsPolicy;
 
 use yew::prelude::*;
 
@@ -23,6 +25,34 @@ pub struct StaticWriterInner {
 /// The writer of [StaticRenderer].
 ///
 /// This writer is passed to a `<HelmetBridge />` for tags to be rendered with it.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::BounceRoot;
+/// # use bounce::helmet::{render_static, HelmetBridge, StaticWriter};
+/// #
+/// #[derive(Properties, PartialEq, Eq)]
+/// pub struct ServerAppProps {
+///     pub helmet_writer: StaticWriter,
+/// }
+///
+/// #[function_component]
+/// pub fn ServerApp(props: &ServerAppProps) -> Html {
+///     html! {
+///         <BounceRoot>
+///             <HelmetBridge writer={props.helmet_writer.clone()} />
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+///
+/// let (_helmet_renderer, helmet_writer) = render_static();
+/// let _renderer = yew::ServerRenderer::<ServerApp>::with_props(move || ServerAppProps { helmet_writer });
+/// ```
+///
+/// See: [`render_static`]
 #[derive(Clone)]
 pub struct StaticWriter {
     inner: Arc<Mutex<Option<StaticWriterInner>>>,
@@ -47,7 +77,9 @@ impl StaticWriter {
         &self,
         states: BounceStates,
         format_title: Option<FormatTitle>,
+        title_template: Option<AttrValue>,
         default_title: Option<AttrValue>,
+        robots: Option<RobotsPolicy>,
     ) {
         let StaticWriterInner { tx } = match self.inner.lock().unwrap().take() {
             Some(m) => m,
@@ -55,7 +87,45 @@ impl StaticWriter {
         };
 
         let helmet_states = states.get_artifacts::<HelmetState>();
-        let tags = merge_helmet_states(&helmet_states, format_title.as_ref(), default_title);
+        let mut tags = merge_helmet_states(
+            &helmet_states,
+            format_title.as_ref(),
+            title_template.as_ref(),
+            default_title,
+        );
+        if let Some(m) = robots {
+            override_robots(&mut tags, m);
+        }
+
+        #[cfg(feature = "query")]
+        {
+            // Preloads resources of prepared queries executed during rendering, unless a link tag
+            // for the same resource is already rendered.
+            let rendered = tags
+                .iter()
+                .filter_map(|m| match **m {
+                    HelmetTag::Link { ref attrs } => attrs.get("href").cloned(),
+                    _ => None,
+                })
+                .collect::<std::collections::BTreeSet<_>>();
+
+            let hints = states.get_atom_value::<crate::query::PreloadHints>();
+            for hint in hints.inner.iter() {
+                if rendered.contains(hint.href.as_str()) {
+                    continue;
+                }
+
+                let mut attrs = BTreeMap::new();
+                attrs.insert("rel".into(), "preload".into());
+                attrs.insert("href".into(), hint.href.as_str().into());
+                attrs.insert("as".into(), hint.destination.as_str().into());
+                if hint.crossorigin {
+                    attrs.insert("crossorigin".into(), "anonymous".into());
+                }
+
+                tags.insert(HelmetTag::Link { attrs }.into());
+            }
+        }
 
         // We ignore cases where the StaticRenderer is dropped.
         let _ = tx.send(
@@ -70,6 +140,12 @@ impl StaticWriter {
 ///
 /// This renderer provides support to statically render helmet tags to string to be prefixed to a
 /// server-side rendered artifact.
+///
+/// With the `query` feature, the tags include a `<link rel="preload" ... />` tag for each
+/// [preload hint](crate::query::Query::preload_hints) of the prepared queries executed during
+/// rendering. These tags can also be sent as
+/// [early hints](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/103), see
+/// [`HelmetTag::to_link_header`].
 #[derive(Debug)]
 pub struct StaticRenderer {
     rx: sync_oneshot::Receiver<Vec<HelmetTag>>,
@@ -136,6 +212,43 @@ impl HelmetTag {
         }
     }
 
+    /// Returns the value of a `Link` header for a `<link ... />` tag.
+    ///
+    /// This can be used to send `103 Early Hints` responses or `Link` headers for the resources
+    /// of a page, before the server-side rendered artifact is sent. Returns `None` if the tag is
+    /// not a link tag or it does not have a `href` attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::{BTreeMap, BTreeSet};
+    /// # use bounce::helmet::HelmetTag;
+    /// let mut attrs = BTreeMap::new();
+    /// attrs.insert("rel".into(), "preload".into());
+    /// attrs.insert("href".into(), "/api/users/1".into());
+    /// attrs.insert("as".into(), "fetch".into());
+    ///
+    /// let tag = HelmetTag::Link { attrs };
+    ///
+    /// assert_eq!(
+    ///     tag.to_link_header().as_deref(),
+    ///     Some(r#"</api/users/1>; as="fetch"; rel="preload""#)
+    /// );
+    /// ```
+    pub fn to_link_header(&self) -> Option<String> {
+        let attrs = match self {
+            Self::Link { attrs } => attrs,
+            _ => return None,
+        };
+
+        let mut s = format!("<{}>", attrs.get("href")?);
+        for (name, value) in attrs.iter().filter(|(name, _)| &***name != "href") {
+            let _ = write!(s, r#"; {}="{}""#, name, value.replace('"', "%22"));
+        }
+
+        Some(s)
+    }
+
     /// Writes the content of a tag into a `std::fmt::Write`.
     ///
     /// `<html ...>` and `<body ...>` tags are not written.
@@ -183,6 +296,8 @@ pub(crate) struct StaticWriterState {
     pub writer: Option<StaticWriter>,
     pub default_title: Option<AttrValue>,
     pub format_title: Option<FormatTitle>,
+    pub title_template: Option<AttrValue>,
+    pub robots: Option<RobotsPolicy>,
 }
 
 /// Creates a new Static Renderer - Static Writer pair.
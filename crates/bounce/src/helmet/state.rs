@@ -358,10 +358,16 @@ fn merge_attrs(
     }
 }
 
+/// Replaces each `%s` in a title template with the title.
+fn apply_title_template(template: &str, title: &str) -> String {
+    template.replace("%s", title)
+}
+
 /// Merges helmet states into a set of tags to be rendered.
-pub(super) fn merge_helmet_states(
+pub(crate) fn merge_helmet_states(
     states: &[Rc<HelmetState>],
     format_title: Option<&FormatTitle>,
+    title_template: Option<&AttrValue>,
     default_title: Option<AttrValue>,
 ) -> BTreeSet<Arc<HelmetTag>> {
     let mut tags = BTreeSet::new();
@@ -427,12 +433,12 @@ pub(super) fn merge_helmet_states(
 
     // title.
     if let Some(m) = title
-        .map(|m| {
-            format_title
-                .map(|fmt_fn| {
-                    Arc::<str>::from(fmt_fn.emit(AttrValue::from(m.to_string())).to_string())
-                })
-                .unwrap_or(m)
+        .map(|m| match (format_title, title_template) {
+            (Some(fmt_fn), _) => {
+                Arc::<str>::from(fmt_fn.emit(AttrValue::from(m.to_string())).to_string())
+            }
+            (None, Some(template)) => apply_title_template(template, &m).into(),
+            (None, None) => m,
         })
         .or_else(|| default_title.map(|m| m.to_string().into()))
     {
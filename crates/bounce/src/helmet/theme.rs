@@ -0,0 +1,246 @@
+use std::fmt;
+use std::rc::Rc;
+
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use super::comp::Helmet;
+use crate::root_state::BounceRootState;
+use crate::states::atom::{use_atom, AtomSlice, UseAtomHandle};
+use crate::states::slice::SliceState;
+use crate::{Atom, Observed};
+
+// Storage is only available in the browser.
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    use gloo::storage::{LocalStorage, Storage};
+
+    use super::ThemeAtom;
+
+    /// The storage key used to persist the current theme.
+    const THEME_STORAGE_KEY: &str = "bounce-theme";
+
+    pub(super) fn load() -> Option<ThemeAtom> {
+        let theme = LocalStorage::get::<String>(THEME_STORAGE_KEY).ok()?;
+
+        match theme.as_str() {
+            "light" => Some(ThemeAtom::Light),
+            "dark" => Some(ThemeAtom::Dark),
+            _ => None,
+        }
+    }
+
+    pub(super) fn save(theme: ThemeAtom) {
+        // Failing to persist the theme should not break the application.
+        let _ = LocalStorage::set(THEME_STORAGE_KEY, theme.as_str());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use super::ThemeAtom;
+
+    pub(super) fn load() -> Option<ThemeAtom> {
+        None
+    }
+
+    pub(super) fn save(_theme: ThemeAtom) {}
+}
+
+/// A built-in atom that stores the colour theme of the application.
+///
+/// The value is persisted to the local storage and restored when a [`ThemeHelmet`] is mounted. The
+/// atom defaults to [`ThemeAtom::Light`] until then, so the server-side rendering and the hydration
+/// render the same theme.
+///
+/// To apply the theme to the `<html />` element, a [`ThemeHelmet`] needs to be rendered under the
+/// same `<BounceRoot />`.
+///
+/// See: [`use_theme`] and [`use_toggle_theme`]
+#[derive(Atom, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[bounce(observed)]
+pub enum ThemeAtom {
+    /// The light theme.
+    #[default]
+    Light,
+    /// The dark theme.
+    Dark,
+}
+
+impl ThemeAtom {
+    /// Returns the name of the theme.
+    ///
+    /// This is the value written to the `data-theme` attribute and the class name applied to the
+    /// `<html />` element.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+
+    /// Returns the opposite theme.
+    pub fn toggled(&self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::Light,
+        }
+    }
+}
+
+impl fmt::Display for ThemeAtom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Observed for ThemeAtom {
+    fn changed(self: Rc<Self>) {
+        storage::save(*self);
+    }
+}
+
+/// The attribute(s) of the `<html />` element that reflect the current theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeAttr {
+    /// Writes the theme to the `data-theme` attribute, e.g.: `<html data-theme="dark">`.
+    #[default]
+    DataTheme,
+    /// Adds the theme as a class, e.g.: `<html class="dark">`.
+    Class,
+    /// Writes both the `data-theme` attribute and the class.
+    Both,
+}
+
+/// Properties of the [`ThemeHelmet`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct ThemeHelmetProps {
+    /// The attribute(s) to write the theme to.
+    #[prop_or_default]
+    pub attr: ThemeAttr,
+}
+
+/// A component that applies the value of [`ThemeAtom`] to the `<html />` element.
+///
+/// The theme persisted to the local storage is restored after the component is mounted.
+///
+/// The attributes are applied with the Helmet API, so a `<HelmetBridge />` must also be
+/// registered under the same `<BounceRoot />`.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::BounceRoot;
+/// use bounce::helmet::{CsrOnly, HelmetBridge, ThemeAttr, ThemeHelmet};
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <HelmetBridge writer={CsrOnly} />
+///             // Renders <html data-theme="light" class="light">
+///             <ThemeHelmet attr={ThemeAttr::Both} />
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(ThemeHelmet)]
+pub fn theme_helmet(props: &ThemeHelmetProps) -> Html {
+    let theme = use_atom::<ThemeAtom>();
+
+    {
+        let theme = theme.clone();
+        // The storage is read in an effect, reading it while rendering would render a different
+        // theme than the server-side rendering.
+        use_effect_with((), move |_| {
+            if let Some(m) = storage::load() {
+                theme.set(m);
+            }
+        });
+    }
+
+    let theme = theme.as_str();
+
+    match props.attr {
+        ThemeAttr::DataTheme => html! {
+            <Helmet>
+                <html data-theme={theme} />
+            </Helmet>
+        },
+        ThemeAttr::Class => html! {
+            <Helmet>
+                <html class={theme} />
+            </Helmet>
+        },
+        ThemeAttr::Both => html! {
+            <Helmet>
+                <html data-theme={theme} class={theme} />
+            </Helmet>
+        },
+    }
+}
+
+/// A hook to connect to the [`ThemeAtom`].
+///
+/// Returns a [`UseAtomHandle<ThemeAtom>`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::helmet::{use_theme, ThemeAtom};
+///
+/// #[function_component(ThemeSelect)]
+/// fn theme_select() -> Html {
+///     let theme = use_theme();
+///
+///     let on_dark_click = {
+///         let theme = theme.clone();
+///         Callback::from(move |_| theme.set(ThemeAtom::Dark))
+///     };
+///
+///     html! {
+///         <div>
+///             <div>{"Current Theme: "}{*theme}</div>
+///             <button onclick={on_dark_click}>{"Dark"}</button>
+///         </div>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_theme() -> UseAtomHandle<ThemeAtom> {
+    use_atom::<ThemeAtom>()
+}
+
+/// A hook to produce a function that toggles the [`ThemeAtom`] between light and dark.
+///
+/// Returns a `Rc<dyn Fn()>`.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::helmet::use_toggle_theme;
+///
+/// #[function_component(ThemeToggle)]
+/// fn theme_toggle() -> Html {
+///     let toggle_theme = use_toggle_theme();
+///     let onclick = Callback::from(move |_| toggle_theme());
+///
+///     html! { <button {onclick}>{"Toggle Theme"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_toggle_theme() -> Rc<dyn Fn()> {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    // The current value is read when the function is called so that the component calling this
+    // hook does not need to subscribe to the theme.
+    Rc::new(move || {
+        let state = root.get_state::<SliceState<AtomSlice<ThemeAtom>>>();
+        let next_theme = state.get().inner.toggled();
+
+        state.dispatch(next_theme);
+    })
+}
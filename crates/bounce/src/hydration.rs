@@ -0,0 +1,264 @@
+//! Tracking of states that are hydrated asynchronously from storage.
+
+use std::fmt;
+use std::future::Future;
+
+use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use crate::root_state::BounceRootState;
+use crate::states::atom::{use_atom_value, AtomSlice};
+use crate::states::slice::SliceState;
+use crate::Atom;
+
+/// The progress of hydrating states from storage.
+///
+/// See: [`use_hydration_progress`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HydrationProgress {
+    /// The number of hydrations that have started.
+    pub started: usize,
+    /// The number of hydrations that have finished.
+    pub finished: usize,
+}
+
+impl HydrationProgress {
+    /// Returns `true` if all hydrations that have started have finished.
+    pub fn is_complete(&self) -> bool {
+        self.finished >= self.started
+    }
+
+    /// Returns the ratio of finished hydrations between `0.0` and `1.0`.
+    ///
+    /// Returns `1.0` if no hydration has started.
+    pub fn ratio(&self) -> f64 {
+        if self.started == 0 {
+            return 1.0;
+        }
+
+        self.finished as f64 / self.started as f64
+    }
+}
+
+#[derive(Atom, Default, PartialEq)]
+struct HydrationState {
+    inner: HydrationProgress,
+}
+
+fn update_progress<F>(root: &BounceRootState, f: F)
+where
+    F: FnOnce(&mut HydrationProgress),
+{
+    let state = root.get_state::<SliceState<AtomSlice<HydrationState>>>();
+    let mut inner = state.get().inner.inner;
+    f(&mut inner);
+
+    state.dispatch(HydrationState { inner });
+}
+
+/// A tracker of states that are hydrated asynchronously from storage.
+///
+/// Persistence backends with asynchronous storage (e.g.: IndexedDB) register each hydration
+/// with the tracker, so a [`PersistGate`] can delay rendering until all of them have finished.
+///
+/// Hydrations should be started when a component is mounted (e.g.: in `use_effect_with`), so
+/// they are registered in the same render pass as the `<PersistGate />` and are not started on
+/// the server.
+///
+/// See: [`use_hydration_tracker`]
+#[derive(Clone, PartialEq)]
+pub struct HydrationTracker {
+    root: BounceRootState,
+}
+
+impl HydrationTracker {
+    /// Starts a hydration.
+    ///
+    /// The hydration is finished when the returned guard is dropped.
+    pub fn start(&self) -> HydrationGuard {
+        update_progress(&self.root, |m| m.started += 1);
+
+        HydrationGuard {
+            root: self.root.clone(),
+        }
+    }
+
+    /// Spawns a future that hydrates states and tracks it until it completes.
+    pub fn hydrate<F>(&self, f: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        let guard = self.start();
+
+        spawn_local(async move {
+            f.await;
+            drop(guard);
+        });
+    }
+}
+
+impl fmt::Debug for HydrationTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HydrationTracker").finish_non_exhaustive()
+    }
+}
+
+/// A guard of a hydration started with [`HydrationTracker::start`].
+///
+/// The hydration is finished when the guard is dropped.
+#[must_use = "the hydration is finished when the guard is dropped."]
+pub struct HydrationGuard {
+    root: BounceRootState,
+}
+
+impl Drop for HydrationGuard {
+    fn drop(&mut self) {
+        update_progress(&self.root, |m| m.finished += 1);
+    }
+}
+
+impl fmt::Debug for HydrationGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HydrationGuard").finish_non_exhaustive()
+    }
+}
+
+/// A hook to obtain the [`HydrationTracker`] of the current bounce root.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::use_hydration_tracker;
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Drafts {
+///     inner: Vec<String>,
+/// }
+///
+/// # async fn read_drafts() -> Vec<String> { Vec::new() }
+/// #[function_component(DraftsHydrator)]
+/// fn drafts_hydrator() -> Html {
+///     let tracker = use_hydration_tracker();
+///     let set_drafts = use_atom_setter::<Drafts>();
+///
+///     // Hydration is started when the component is mounted.
+///     use_effect_with((), move |_| {
+///         tracker.hydrate(async move {
+///             // Reads drafts from IndexedDB.
+///             let inner = read_drafts().await;
+///             set_drafts(Drafts { inner });
+///         });
+///     });
+///
+///     Html::default()
+/// }
+/// ```
+#[hook]
+pub fn use_hydration_tracker() -> HydrationTracker {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    HydrationTracker { root }
+}
+
+/// A hook to subscribe to the [`HydrationProgress`] of the current bounce root.
+///
+/// This can be used to show the progress on a splash screen rendered by the `loading` property of
+/// a [`PersistGate`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::use_hydration_progress;
+///
+/// #[function_component(Splash)]
+/// fn splash() -> Html {
+///     let progress = use_hydration_progress();
+///
+///     html! { <progress value={progress.ratio().to_string()} /> }
+/// }
+/// ```
+#[hook]
+pub fn use_hydration_progress() -> HydrationProgress {
+    use_atom_value::<HydrationState>().inner
+}
+
+/// Properties of the [`PersistGate`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct PersistGateProps {
+    /// The content rendered until all hydrations have finished.
+    #[prop_or_default]
+    pub loading: Html,
+
+    /// The children of the gate.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A component that delays rendering its children until all states have been hydrated from
+/// storage.
+///
+/// Hydrations are registered with the [`HydrationTracker`]. Until all of them have finished, the
+/// `loading` property is rendered instead of the children. Once the children are rendered, they
+/// are not replaced by the `loading` property again when more hydrations are started.
+///
+/// The gate waits for hydrations started by all components rendered in the same render pass,
+/// including components rendered after it. The children are rendered after that render pass,
+/// even if no hydration is started, so the `loading` property is always rendered on the server.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::{BounceRoot, PersistGate};
+///
+/// # #[function_component(DraftsHydrator)]
+/// # fn drafts_hydrator() -> Html { Html::default() }
+/// # #[function_component(Editor)]
+/// # fn editor() -> Html { Html::default() }
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <DraftsHydrator />
+///             <PersistGate loading={html! {<div>{"Loading..."}</div>}}>
+///                 <Editor />
+///             </PersistGate>
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(PersistGate)]
+pub fn persist_gate(props: &PersistGateProps) -> Html {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let progress = use_hydration_progress();
+    let opened = use_state_eq(|| false);
+
+    {
+        let opened = opened.clone();
+        // The progress is read after the effects of the render pass have run, so hydrations
+        // started by components mounted after the gate are registered by then.
+        use_effect_with(progress, move |_| {
+            spawn_local(async move {
+                let progress = root
+                    .get_state::<SliceState<AtomSlice<HydrationState>>>()
+                    .get()
+                    .inner
+                    .inner;
+
+                if progress.is_complete() {
+                    opened.set(true);
+                }
+            });
+        });
+    }
+
+    if *opened {
+        props.children.clone()
+    } else {
+        props.loading.clone()
+    }
+}
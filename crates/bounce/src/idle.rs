@@ -0,0 +1,204 @@
+//! A scheduler that runs low-priority work when the browser is idle.
+
+use std::fmt;
+
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use crate::abort::AbortToken;
+use crate::root_state::BounceRootState;
+
+// There is no idle period on the server, so the queue is only available in the browser.
+#[cfg(target_arch = "wasm32")]
+mod queue {
+    use std::any::Any;
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    use anymap2::AnyMap;
+    use wasm_bindgen::prelude::*;
+    use web_sys::{IdleDeadline, IdleRequestOptions};
+
+    use crate::abort::AbortToken;
+    use crate::any_state::AnyState;
+
+    /// The maximum time in milliseconds a task waits for the browser to become idle.
+    ///
+    /// Tasks are run after this time has elapsed even if the browser has not become idle, so they
+    /// are not delayed indefinitely on busy pages.
+    const IDLE_TIMEOUT_MS: u32 = 2000;
+
+    /// Idle periods with less than this time in milliseconds remaining do not start another task.
+    const MIN_TIME_REMAINING_MS: f64 = 1.0;
+
+    pub(super) type IdleTask = Box<dyn FnOnce()>;
+
+    /// The tasks that are waiting for the browser to become idle.
+    #[derive(Default, Clone)]
+    pub(super) struct IdleTaskQueue {
+        tasks: Rc<RefCell<VecDeque<(AbortToken, IdleTask)>>>,
+        requested: Rc<Cell<bool>>,
+    }
+
+    impl IdleTaskQueue {
+        pub fn push(&self, token: AbortToken, task: IdleTask) {
+            self.tasks.borrow_mut().push_back((token, task));
+
+            if !self.requested.replace(true) {
+                self.request_idle();
+            }
+        }
+
+        fn request_idle(&self) {
+            let this = self.clone();
+            let callback = Closure::once_into_js(move |deadline: JsValue| {
+                this.run(deadline.dyn_into::<IdleDeadline>().ok());
+            });
+            let callback = callback.unchecked_ref::<js_sys::Function>();
+
+            let window = match web_sys::window() {
+                Some(m) => m,
+                None => return,
+            };
+
+            let options = IdleRequestOptions::new();
+            options.set_timeout(IDLE_TIMEOUT_MS);
+
+            // requestIdleCallback is not available in all browsers.
+            if window
+                .request_idle_callback_with_options(callback, &options)
+                .is_err()
+            {
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(callback, 1);
+            }
+        }
+
+        /// Runs tasks until the idle period ends.
+        ///
+        /// Without a deadline, only 1 task is run. Cancelled tasks are discarded without being
+        /// run.
+        fn run(&self, deadline: Option<IdleDeadline>) {
+            loop {
+                // The queue is not borrowed when running a task so tasks can schedule other tasks.
+                let (token, task) = match self.tasks.borrow_mut().pop_front() {
+                    Some(m) => m,
+                    None => break,
+                };
+
+                if token.is_aborted() {
+                    continue;
+                }
+
+                task();
+
+                let has_time_remaining = deadline
+                    .as_ref()
+                    .map(|m| !m.did_timeout() && m.time_remaining() > MIN_TIME_REMAINING_MS)
+                    .unwrap_or(false);
+
+                if !has_time_remaining {
+                    break;
+                }
+            }
+
+            if self.tasks.borrow().is_empty() {
+                self.requested.set(false);
+            } else {
+                self.request_idle();
+            }
+        }
+    }
+
+    impl AnyState for IdleTaskQueue {
+        fn apply(&self, _notion: Rc<dyn Any>) {}
+
+        fn create(_init_states: &mut AnyMap) -> Self
+        where
+            Self: Sized,
+        {
+            Self::default()
+        }
+    }
+}
+
+/// A scheduler that runs low-priority work when the browser is idle.
+///
+/// Tasks are run in the order they are scheduled with `requestIdleCallback`, so work like cache
+/// warming does not compete with user interactions. If the browser does not support
+/// `requestIdleCallback`, tasks are run with `setTimeout` instead.
+///
+/// Tasks are not run during server-side rendering.
+///
+/// See: [`use_idle_scheduler`]
+#[derive(Clone, PartialEq)]
+pub struct IdleScheduler {
+    root: BounceRootState,
+}
+
+impl IdleScheduler {
+    pub(crate) fn new(root: BounceRootState) -> Self {
+        Self { root }
+    }
+
+    /// Schedules a task to be run when the browser is idle.
+    ///
+    /// If the browser does not become idle, the task is run after 2 seconds.
+    ///
+    /// Returns an [`AbortToken`], the task is not run if the token is aborted before the task
+    /// starts.
+    pub fn schedule<F>(&self, task: F) -> AbortToken
+    where
+        F: FnOnce() + 'static,
+    {
+        let token = AbortToken::new();
+
+        #[cfg(target_arch = "wasm32")]
+        self.root
+            .get_state::<queue::IdleTaskQueue>()
+            .push(token.clone(), Box::new(task));
+
+        // There is no idle period on the server.
+        #[cfg(not(target_arch = "wasm32"))]
+        let _task = task;
+
+        token
+    }
+}
+
+impl fmt::Debug for IdleScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleScheduler").finish_non_exhaustive()
+    }
+}
+
+/// A hook to obtain the [`IdleScheduler`] of the current bounce root.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[function_component(Dashboard)]
+/// fn dashboard() -> Html {
+///     let scheduler = use_idle_scheduler();
+///
+///     use_effect_with((), move |_| {
+///         let token = scheduler.schedule(|| {
+///             // warm up caches for pages that the user is likely to visit next.
+///         });
+///
+///         // The work is no longer needed if the dashboard is closed before it starts.
+///         move || token.abort()
+///     });
+///
+///     Html::default()
+/// }
+/// ```
+#[hook]
+pub fn use_idle_scheduler() -> IdleScheduler {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    IdleScheduler::new(root)
+}
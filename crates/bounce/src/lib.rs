@@ -1,4 +1,31 @@
 //! The uncomplicated Yew State management library.
+//!
+//! # Features
+//!
+//! The core (atoms, slices, selectors, notions and artifacts) is always available. Other modules
+//! are enabled with the following features, so applications only compile what they use:
+//!
+//! - `query`: Queries and mutations.
+//! - `auth`: Session state and authenticated queries, enables `query`.
+//! - `prepared-codec`: Compact encoding of prepared query results, enables `query`.
+//! - `prepared-compression`: Compression of prepared query results, enables `prepared-codec`.
+//! - `query-seed`: Seeding the query cache from a static JSON document, enables `query`.
+//! - `query-export`: Exporting and importing the query cache as JSON, enables `query-seed`.
+//! - `router`: Queries with inputs derived from the route of `yew-router`, enables `query`.
+//! - `helmet`: The Helmet API to manipulate elements in the `<head />` element.
+//! - `devtools`: Development tools.
+//! - `web-atoms`: Atoms that reflect common browser states (e.g.: online status and window size).
+//! - `settings`: Settings that are persisted with a schema, enables `persist`.
+//! - `tab-sync`: Slices that are synchronised between tabs.
+//! - `persist`: Slices and atom families that are persisted to web storage or a storage of the
+//!   host (e.g.: Tauri).
+//! - `testing`: Helpers to wait for states in tests.
+//! - `toast`: A state of notifications (toasts) with automatic dismissal.
+//! - `ssr`: Server-side rendering support for the modules above, enables `ssr` of Yew.
+//!
+//! Items that do not depend on Yew (e.g.: the `Atom` and `Slice` traits, [`WithNotion`] and
+//! [`SelectorResult`]) are defined in the [`bounce-core`](https://docs.rs/bounce-core/) crate,
+//! which can be used by shared logic crates to implement and test the rules of states natively.
 
 #![deny(clippy::all)]
 #![deny(missing_debug_implementations)]
@@ -12,9 +39,18 @@
 
 extern crate self as bounce;
 
+mod abort;
 mod any_state;
+mod clock;
+#[cfg(feature = "query")]
+mod config;
+mod hydration;
+mod idle;
+mod notion_bus;
+mod overrides;
 mod provider;
 mod root_state;
+mod services;
 mod states;
 mod utils;
 
@@ -22,14 +58,78 @@ mod utils;
 #[cfg(feature = "query")]
 pub mod query;
 
+#[cfg_attr(documenting, doc(cfg(feature = "auth")))]
+#[cfg(feature = "auth")]
+pub mod auth;
+
 #[cfg_attr(documenting, doc(cfg(feature = "helmet")))]
 #[cfg(feature = "helmet")]
 pub mod helmet;
 
+#[cfg_attr(documenting, doc(cfg(feature = "devtools")))]
+#[cfg(feature = "devtools")]
+pub mod devtools;
+
+#[cfg_attr(documenting, doc(cfg(feature = "web-atoms")))]
+#[cfg(feature = "web-atoms")]
+pub mod web_atoms;
+
+#[cfg_attr(documenting, doc(cfg(feature = "settings")))]
+#[cfg(feature = "settings")]
+pub mod settings;
+
+#[cfg_attr(documenting, doc(cfg(feature = "tab-sync")))]
+#[cfg(feature = "tab-sync")]
+pub mod tab_sync;
+
+#[cfg_attr(documenting, doc(cfg(feature = "persist")))]
+#[cfg(feature = "persist")]
+pub mod persist;
+
+#[cfg_attr(documenting, doc(cfg(feature = "testing")))]
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg_attr(documenting, doc(cfg(feature = "toast")))]
+#[cfg(feature = "toast")]
+pub mod toast;
+
 /// A simple state that is Copy-on-Write and notifies registered hooks when `prev_value != next_value`.
 ///
 /// It can be derived for any state that implements [`PartialEq`] + [`Default`].
 ///
+/// Enum atoms can use `#[bounce(variants)]` to generate an enum named `{Atom}Variant` and
+/// implement [`EnumAtom`](crate::EnumAtom), see [`use_atom_variant`](crate::use_atom_variant).
+///
+/// Newtype atoms can use `#[bounce(transparent)]` to implement `From`, `Deref` and `Display`
+/// for the inner value, see [`TransparentAtom`](crate::TransparentAtom).
+///
+/// Atoms with `#[bounce(child)]` derive their initial value from a parent atom, see
+/// [`ChildAtom`](crate::ChildAtom).
+///
+/// Atoms whose `PartialEq` would be expensive (e.g.: large vectors or `Rc` graphs) can use
+/// `#[bounce(eq = "path::to::fn")]` instead of deriving `PartialEq`. `PartialEq` is then
+/// implemented with the function, which accepts `(&Self, &Self)` and returns `true` if the
+/// values are equal, and hooks are only notified when it returns `false`.
+///
+/// ```
+/// use std::rc::Rc;
+/// use bounce::prelude::*;
+///
+/// #[derive(Atom, Default)]
+/// #[bounce(eq = "Document::same_revision")]
+/// struct Document {
+///     revision: u64,
+///     nodes: Rc<Vec<String>>,
+/// }
+///
+/// impl Document {
+///     fn same_revision(&self, other: &Self) -> bool {
+///         self.revision == other.revision
+///     }
+/// }
+/// ```
+///
 /// # Example
 ///
 /// ```
@@ -57,6 +157,9 @@ pub use states::atom::Atom;
 ///
 /// It can be derived for any state that implements [`Reducible`](yew::functional::Reducible) + [`PartialEq`] + [`Default`].
 ///
+/// Like atoms, slices can use `#[bounce(eq = "path::to::fn")]` to implement [`PartialEq`] with a
+/// custom comparison function, see [`Atom`](crate::Atom).
+///
 /// # Example
 ///
 /// ```
@@ -120,41 +223,126 @@ pub use states::slice::Slice;
 /// See: [`use_future_notion_runner`](crate::use_future_notion_runner)
 pub use bounce_macros::future_notion;
 
+pub use abort::AbortToken;
+pub use clock::{Clock, ManualClock, SystemClock};
+#[cfg(feature = "query")]
+#[cfg_attr(documenting, doc(cfg(feature = "query")))]
+pub use config::BounceConfig;
+pub use hydration::{
+    use_hydration_progress, use_hydration_tracker, HydrationGuard, HydrationProgress,
+    HydrationTracker, PersistGate, PersistGateProps,
+};
+pub use idle::{use_idle_scheduler, IdleScheduler};
+pub use notion_bus::NotionBus;
+pub use overrides::StateOverrides;
 pub use provider::{BounceRoot, BounceRootProps};
 pub use root_state::BounceStates;
+pub use services::Services;
+pub use utils::{use_id, with_batched_updates};
 
-pub use states::artifact::{use_artifacts, Artifact, ArtifactProps};
-pub use states::atom::{use_atom, use_atom_setter, use_atom_value, CloneAtom, UseAtomHandle};
+pub use states::artifact::{
+    use_artifact_applier, use_artifacts, use_artifacts_scoped, ApplyArtifacts, Artifact,
+    ArtifactApplier, ArtifactProps, ArtifactScope, ArtifactScopeProps, ArtifactValue,
+};
+pub use states::atom::{
+    use_atom, use_atom_is_variant, use_atom_setter, use_atom_setter_if_changed, use_atom_value,
+    use_atom_value_inner, use_atom_variant, AtomSink, AtomStream, CloneAtom, EnumAtom,
+    TransparentAtom, UseAtomHandle,
+};
+pub use states::atom_expiry::{use_atom_with_expiry, UseAtomWithExpiryHandle};
+pub use states::atom_family::{
+    use_atom_family, use_atom_family_value, AtomFamily, UseAtomFamilyHandle,
+};
+pub use states::atom_list::{use_atom_list, use_atom_list_item, AtomListKey, UseAtomListHandle};
+pub use states::cached_value::use_cached_value;
+pub use states::child_atom::{use_atom_resync, ChildAtom, ResyncNotion};
+pub use states::derived::{
+    map_selector, use_derived_value, zip_selectors, DerivedSelector, MapFn, MapSelector,
+    ZipSelectors,
+};
+pub use states::external_store::{
+    use_external_store, ExternalSliceBridge, ExternalStore, ExternalStoreBridge,
+    ExternalStoreBridgeProps, SliceStore, StoreAtom,
+};
+pub use states::from_states::FromStates;
 pub use states::future_notion::{use_future_notion_runner, Deferred, FutureNotion};
-pub use states::input_selector::{use_input_selector_value, InputSelector};
-pub use states::notion::{use_notion_applier, WithNotion};
+pub use states::input_selector::{
+    use_input_selector_value, use_input_selector_value_fallible, InputSelector, SelectorPoisoned,
+};
+pub use states::lazy_subscriptions::{LazySubscriptions, LazySubscriptionsProps};
+pub use states::notion::{
+    use_batch_notion_applier, use_notion_applier, use_notion_applier_rate_limited,
+    use_notion_applier_rate_limited_with, NotionBatch, RateLimitStrategy, WithNotion,
+};
+pub use states::notion_scope::{use_notion_scope, NotionScope};
 pub use states::observer::Observed;
-pub use states::selector::{use_selector_value, Selector};
+pub use states::registry::{use_registered_states, StateDescriptor, StateKind};
+pub use states::selector::{
+    use_selector_value, use_selector_value_fallible, use_selector_value_or_suspend, Selector,
+    SelectorResult, TrySelector,
+};
 pub use states::slice::{
-    use_slice, use_slice_dispatch, use_slice_value, CloneSlice, UseSliceHandle,
+    use_slice, use_slice_dispatch, use_slice_dispatch_if_changed, use_slice_dispatch_with_output,
+    use_slice_value, CloneSlice, ReducibleWithOutput, SliceSink, SliceStream, UseSliceHandle,
 };
+pub use states::stream_atom::use_stream_atom;
+pub use states::transient::{TransientScope, TransientScopeProps, TransientStates};
 
 pub mod prelude {
     //! Default Bounce exports.
 
     pub use crate::future_notion;
+    pub use crate::use_cached_value;
+    pub use crate::use_stream_atom;
     pub use crate::BounceStates;
+    pub use crate::FromStates;
     pub use crate::Observed;
-    pub use crate::{use_artifacts, Artifact, ArtifactProps};
-    pub use crate::{use_atom, use_atom_setter, use_atom_value, Atom, CloneAtom, UseAtomHandle};
+    pub use crate::{map_selector, use_derived_value, zip_selectors, DerivedSelector};
+    pub use crate::{
+        use_artifact_applier, use_artifacts, use_artifacts_scoped, ApplyArtifacts, Artifact,
+        ArtifactApplier, ArtifactProps, ArtifactScope, ArtifactScopeProps, ArtifactValue,
+    };
+    pub use crate::{
+        use_atom, use_atom_is_variant, use_atom_setter, use_atom_setter_if_changed, use_atom_value,
+        use_atom_value_inner, use_atom_variant, Atom, CloneAtom, EnumAtom, TransparentAtom,
+        UseAtomHandle,
+    };
+    pub use crate::{use_atom_family, use_atom_family_value, AtomFamily, UseAtomFamilyHandle};
+    pub use crate::{use_atom_list, use_atom_list_item, AtomListKey, UseAtomListHandle};
+    pub use crate::{use_atom_resync, ChildAtom, ResyncNotion};
+    pub use crate::{use_atom_with_expiry, UseAtomWithExpiryHandle};
+    pub use crate::{
+        use_batch_notion_applier, use_notion_applier, use_notion_applier_rate_limited,
+        use_notion_applier_rate_limited_with, NotionBatch, WithNotion,
+    };
     pub use crate::{use_future_notion_runner, Deferred, FutureNotion};
-    pub use crate::{use_input_selector_value, InputSelector};
-    pub use crate::{use_notion_applier, WithNotion};
-    pub use crate::{use_selector_value, Selector};
+    pub use crate::{use_idle_scheduler, IdleScheduler};
     pub use crate::{
-        use_slice, use_slice_dispatch, use_slice_value, CloneSlice, Slice, UseSliceHandle,
+        use_input_selector_value, use_input_selector_value_fallible, InputSelector,
+        SelectorPoisoned,
     };
+    pub use crate::{use_notion_scope, NotionScope};
+    pub use crate::{
+        use_selector_value, use_selector_value_fallible, use_selector_value_or_suspend, Selector,
+        SelectorResult, TrySelector,
+    };
+    pub use crate::{
+        use_slice, use_slice_dispatch, use_slice_dispatch_if_changed, use_slice_value, CloneSlice,
+        Slice, UseSliceHandle,
+    };
+    pub use crate::{TransientScope, TransientScopeProps, TransientStates};
 }
 
 // vendored dependencies used by macros.
 #[doc(hidden)]
 pub mod __vendored {
     pub use futures;
-    pub use once_cell;
+    #[cfg(feature = "settings")]
+    pub use serde_json;
     pub use yew;
 }
+
+// Compiles the examples in the README.
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
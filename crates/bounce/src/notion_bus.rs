@@ -0,0 +1,133 @@
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::root_state::{AnyNotion, BounceRootState};
+
+#[derive(Default)]
+struct NotionBusInner {
+    shared: HashSet<TypeId>,
+    roots: RefCell<Vec<BounceRootState>>,
+}
+
+/// A bus that shares selected notions between multiple `<BounceRoot />`s.
+///
+/// Applications that embed several independent roots (e.g.: widgets on a page) can attach a bus
+/// to each root by providing it as a [service](crate::Services). When a notion of a shared type
+/// is applied to one of the roots, it is also applied to all other roots attached to the bus.
+/// States remain isolated in each root, only notions are shared.
+///
+/// Roots are detached from the bus when they are unmounted.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::{BounceRoot, NotionBus, Services};
+/// #
+/// pub struct Logout;
+///
+/// #[derive(Properties, PartialEq)]
+/// struct WidgetProps {
+///     bus: NotionBus,
+/// }
+///
+/// #[function_component(Widget)]
+/// fn widget(props: &WidgetProps) -> Html {
+///     let bus = props.bus.clone();
+///     let get_services = Callback::from(move |_| Services::new().provide(bus.clone()));
+///
+///     html! {
+///         <BounceRoot {get_services}>
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     // Logging out in one widget logs out all widgets.
+///     let bus = use_memo((), |_| NotionBus::new().share::<Logout>());
+///
+///     html! {
+///         <>
+///             <Widget bus={(*bus).clone()} />
+///             <Widget bus={(*bus).clone()} />
+///         </>
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct NotionBus {
+    inner: Rc<NotionBusInner>,
+}
+
+impl NotionBus {
+    /// Creates a bus that does not share any notion.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shares notions of type `T` between the roots attached to this bus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root is already attached to this bus.
+    pub fn share<T>(mut self) -> Self
+    where
+        T: 'static,
+    {
+        Rc::get_mut(&mut self.inner)
+            .expect("notions must be shared before the bus is attached to a root")
+            .shared
+            .insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Returns `true` if notions with the type id `id` are shared.
+    pub(crate) fn is_shared(&self, id: TypeId) -> bool {
+        self.inner.shared.contains(&id)
+    }
+
+    pub(crate) fn attach(&self, root: BounceRootState) {
+        self.inner.roots.borrow_mut().push(root);
+    }
+
+    pub(crate) fn detach(&self, root: &BounceRootState) {
+        self.inner.roots.borrow_mut().retain(|m| m != root);
+    }
+
+    /// Applies notions applied to `from` to all other roots attached to this bus.
+    pub(crate) fn forward(&self, from: &BounceRootState, notions: &[AnyNotion]) {
+        let roots = self
+            .inner
+            .roots
+            .borrow()
+            .iter()
+            .filter(|m| *m != from)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for root in roots {
+            root.apply_forwarded_notions(notions);
+        }
+    }
+}
+
+impl PartialEq for NotionBus {
+    fn eq(&self, rhs: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &rhs.inner)
+    }
+}
+
+impl fmt::Debug for NotionBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotionBus")
+            .field("shared", &self.inner.shared.len())
+            .field("roots", &self.inner.roots.borrow().len())
+            .finish()
+    }
+}
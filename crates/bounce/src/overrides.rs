@@ -0,0 +1,114 @@
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::states::atom::{Atom, AtomSlice};
+use crate::states::slice::Slice;
+
+/// A set of values that replace the initial values of states in a `<BounceRoot />`.
+///
+/// This can be used to run components against fake data in tests, without conditional
+/// compilation in the definitions of states.
+///
+/// Unlike initial states, overridden values take priority over values created from other states
+/// and are used each time the state is created (e.g.: when it is reset by a
+/// [`TransientScope`](crate::TransientScope)).
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::{BounceRoot, StateOverrides};
+/// #
+/// #[derive(Atom, PartialEq)]
+/// struct ApiBaseUrl {
+///     inner: String,
+/// }
+///
+/// impl Default for ApiBaseUrl {
+///     fn default() -> Self {
+///         Self {
+///             inner: "https://api.example.com".into(),
+///         }
+///     }
+/// }
+///
+/// #[function_component(TestRoot)]
+/// fn test_root() -> Html {
+///     let get_overrides = Callback::from(|_| {
+///         StateOverrides::new().set(ApiBaseUrl {
+///             inner: "http://localhost:8080".into(),
+///         })
+///     });
+///
+///     html! {
+///         <BounceRoot {get_overrides}>
+///             // components under test...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct StateOverrides {
+    inner: HashMap<TypeId, (&'static str, Rc<dyn Any>)>,
+}
+
+impl StateOverrides {
+    /// Creates an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the value of an [`Atom`](macro@crate::Atom).
+    pub fn set<T>(self, value: T) -> Self
+    where
+        T: Atom + 'static,
+    {
+        self.insert(
+            type_name::<T>(),
+            AtomSlice {
+                inner: value.into(),
+            },
+        )
+    }
+
+    /// Overrides the value of a [`Slice`](macro@crate::Slice).
+    pub fn set_slice<T>(self, value: T) -> Self
+    where
+        T: Slice + 'static,
+    {
+        self.insert(type_name::<T>(), value)
+    }
+
+    fn insert<T>(mut self, name: &'static str, value: T) -> Self
+    where
+        T: Slice + 'static,
+    {
+        self.inner.insert(
+            TypeId::of::<T>(),
+            (name, Rc::new(Rc::new(value)) as Rc<dyn Any>),
+        );
+        self
+    }
+
+    /// Returns the overridden value of a slice.
+    pub(crate) fn get<T>(&self) -> Option<Rc<T>>
+    where
+        T: Slice + 'static,
+    {
+        self.inner
+            .get(&TypeId::of::<T>())
+            .and_then(|(_, m)| m.downcast_ref::<Rc<T>>())
+            .cloned()
+    }
+}
+
+impl fmt::Debug for StateOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.inner.values().map(|(name, _)| name))
+            .finish()
+    }
+}
@@ -0,0 +1,669 @@
+//! Slices that are persisted to a key-value storage.
+//!
+//! A slice that implements [`Persisted`] is restored from storage when [`use_persist`] is
+//! mounted and written back each time it changes. Restoring is tracked by the
+//! [`HydrationTracker`](crate::HydrationTracker), so a [`PersistGate`](crate::PersistGate) can
+//! delay rendering until the stored value has been restored.
+//!
+//! An [atom family](crate::AtomFamily) that implements [`PersistedFamily`] is persisted with
+//! [`use_persist_family`]. Each key is stored in its own entry and restored when a component
+//! starts using it.
+//!
+//! The storage is asynchronous and implemented with [`PersistStorage`]. It defaults to the local
+//! storage of the browser ([`WebStorage`]). Desktop applications (e.g.: Tauri) can persist states
+//! to disk through their host shell with a [`HostStorage`], or any other implementation of
+//! [`PersistStorage`], provided to the `<BounceRoot />` as a service:
+//!
+//! ```
+//! # use std::rc::Rc;
+//! # use yew::prelude::*;
+//! use bounce::persist::{HostStorage, PersistError, PersistStorage};
+//! use bounce::{BounceRoot, Services};
+//!
+//! # async fn invoke_load(_key: String) -> Result<Option<String>, String> { Ok(None) }
+//! # async fn invoke_save(_key: String, _value: String) -> Result<(), String> { Ok(()) }
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     let get_services = Callback::from(|_| {
+//!         // The functions call the commands of the host shell.
+//!         let storage: Rc<dyn PersistStorage> = Rc::new(HostStorage::new(
+//!             |key| async move { invoke_load(key).await.map_err(PersistError::new) },
+//!             |key, value| async move { invoke_save(key, value).await.map_err(PersistError::new) },
+//!         ));
+//!
+//!         Services::new().provide_rc(storage)
+//!     });
+//!
+//!     html! {
+//!         <BounceRoot {get_services}>
+//!             // children...
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! # use std::rc::Rc;
+//! # use serde::{Deserialize, Serialize};
+//! # use yew::prelude::*;
+//! # use bounce::prelude::*;
+//! # use bounce::{BounceRoot, PersistGate};
+//! use bounce::persist::{use_persist, Persisted};
+//!
+//! #[derive(Slice, Serialize, Deserialize, PartialEq, Default)]
+//! struct Drafts {
+//!     items: Vec<String>,
+//! }
+//!
+//! # pub enum DraftsAction {
+//! #     Add(String),
+//! # }
+//! #
+//! # impl Reducible for Drafts {
+//! #     type Action = DraftsAction;
+//! #
+//! #     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+//! #         match action {
+//! #             DraftsAction::Add(m) => {
+//! #                 let mut items = self.items.clone();
+//! #                 items.push(m);
+//! #                 Self { items }.into()
+//! #             }
+//! #         }
+//! #     }
+//! # }
+//! #
+//! impl Persisted for Drafts {
+//!     const KEY: &'static str = "drafts";
+//! }
+//!
+//! #[function_component(DraftsPersist)]
+//! fn drafts_persist() -> Html {
+//!     use_persist::<Drafts>();
+//!
+//!     Html::default()
+//! }
+//!
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     html! {
+//!         <BounceRoot>
+//!             <DraftsPersist />
+//!             <PersistGate>
+//!                 // children...
+//!             </PersistGate>
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::rc::{Rc, Weak};
+
+use async_trait::async_trait;
+use futures::future::{FutureExt, LocalBoxFuture};
+#[cfg(target_arch = "wasm32")]
+use gloo::storage::{LocalStorage, Storage};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::UnwrapThrowExt;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use crate::hydration::{use_hydration_tracker, HydrationTracker};
+use crate::root_state::BounceRootState;
+use crate::states::atom_family::{
+    AtomFamily, AtomFamilyAction, AtomFamilySlice, AtomFamilySubscribers,
+};
+use crate::states::slice::{Slice, SliceState};
+use crate::utils::Listener;
+
+/// An error returned by a [`PersistStorage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistError {
+    message: String,
+}
+
+impl PersistError {
+    /// Creates an error with a message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+/// An asynchronous key-value storage that persisted slices are stored in.
+///
+/// Values are the JSON serialisation of the slices.
+///
+/// The storage is provided to the `<BounceRoot />` as an `Rc<dyn PersistStorage>` service, see
+/// [`bounce::persist`](crate::persist). Defaults to [`WebStorage`].
+#[async_trait(?Send)]
+pub trait PersistStorage {
+    /// Reads the value of a key.
+    ///
+    /// Returns `None` if nothing has been stored with the key.
+    async fn get_item(&self, key: &str) -> Result<Option<String>, PersistError>;
+
+    /// Writes the value of a key.
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), PersistError>;
+}
+
+/// A [`PersistStorage`] that stores values in the local storage of the browser.
+///
+/// Nothing is stored on the server.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WebStorage;
+
+// Storage is only available in the browser.
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+impl PersistStorage for WebStorage {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, PersistError> {
+        LocalStorage::raw()
+            .get_item(key)
+            .map_err(|e| PersistError::new(format!("{:?}", e)))
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), PersistError> {
+        LocalStorage::raw()
+            .set_item(key, value)
+            .map_err(|e| PersistError::new(format!("{:?}", e)))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait(?Send)]
+impl PersistStorage for WebStorage {
+    async fn get_item(&self, _key: &str) -> Result<Option<String>, PersistError> {
+        Ok(None)
+    }
+
+    async fn set_item(&self, _key: &str, _value: &str) -> Result<(), PersistError> {
+        Ok(())
+    }
+}
+
+type GetItemFn = dyn Fn(String) -> LocalBoxFuture<'static, Result<Option<String>, PersistError>>;
+type SetItemFn = dyn Fn(String, String) -> LocalBoxFuture<'static, Result<(), PersistError>>;
+
+/// A [`PersistStorage`] that stores values through a host bridge.
+///
+/// The storage is created from 2 async functions that read and write the value of a key, so
+/// values can be stored through the commands of a desktop host shell (e.g.: Tauri's `invoke`) or
+/// any other bridge.
+///
+/// # Example
+///
+/// ```
+/// use bounce::persist::{HostStorage, PersistError};
+///
+/// # async fn invoke_load(_key: String) -> Result<Option<String>, String> { Ok(None) }
+/// # async fn invoke_save(_key: String, _value: String) -> Result<(), String> { Ok(()) }
+/// let storage = HostStorage::new(
+///     |key| async move { invoke_load(key).await.map_err(PersistError::new) },
+///     |key, value| async move { invoke_save(key, value).await.map_err(PersistError::new) },
+/// );
+/// ```
+pub struct HostStorage {
+    get_item: Rc<GetItemFn>,
+    set_item: Rc<SetItemFn>,
+}
+
+impl HostStorage {
+    /// Creates a storage from a function that reads the value of a key and a function that
+    /// writes it.
+    pub fn new<G, GF, S, SF>(get_item: G, set_item: S) -> Self
+    where
+        G: Fn(String) -> GF + 'static,
+        GF: Future<Output = Result<Option<String>, PersistError>> + 'static,
+        S: Fn(String, String) -> SF + 'static,
+        SF: Future<Output = Result<(), PersistError>> + 'static,
+    {
+        Self {
+            get_item: Rc::new(move |key| get_item(key).boxed_local()),
+            set_item: Rc::new(move |key, value| set_item(key, value).boxed_local()),
+        }
+    }
+}
+
+impl fmt::Debug for HostStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostStorage").finish_non_exhaustive()
+    }
+}
+
+#[async_trait(?Send)]
+impl PersistStorage for HostStorage {
+    async fn get_item(&self, key: &str) -> Result<Option<String>, PersistError> {
+        (self.get_item)(key.to_string()).await
+    }
+
+    async fn set_item(&self, key: &str, value: &str) -> Result<(), PersistError> {
+        (self.set_item)(key.to_string(), value.to_string()).await
+    }
+}
+
+/// A trait to be implemented on slices that are persisted to storage.
+///
+/// See: [`bounce::persist`](crate::persist)
+pub trait Persisted: Slice + Serialize + DeserializeOwned + 'static {
+    /// The key of the slice in the storage.
+    ///
+    /// Each persisted slice of an application should have a unique key.
+    const KEY: &'static str;
+}
+
+/// Returns the storage of a bounce root, defaults to [`WebStorage`].
+pub(crate) fn get_storage(root: &BounceRootState) -> Rc<dyn PersistStorage> {
+    root.get_service::<dyn PersistStorage>()
+        .unwrap_or_else(|| Rc::new(WebStorage))
+}
+
+/// Deserialises a stored value, returns `None` if nothing is stored or it cannot be read.
+pub(crate) fn read_stored<T>(key: &str, stored: Result<Option<String>, PersistError>) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let stored = match stored {
+        Ok(Some(m)) => m,
+        Ok(None) => return None,
+        Err(e) => {
+            tracing::warn!("failed to restore {}: {}", key, e);
+            return None;
+        }
+    };
+
+    match serde_json::from_str::<T>(&stored) {
+        Ok(m) => Some(m),
+        Err(e) => {
+            tracing::warn!("failed to restore {}: {}", key, e);
+            None
+        }
+    }
+}
+
+/// Writes the values of a storage key.
+pub(crate) struct StorageWriter {
+    storage: Rc<dyn PersistStorage>,
+    key: String,
+    // The latest value that has not been written yet.
+    pending: RefCell<Option<String>>,
+    writing: Cell<bool>,
+}
+
+impl StorageWriter {
+    pub fn new(storage: Rc<dyn PersistStorage>, key: String) -> Rc<Self> {
+        Rc::new(Self {
+            storage,
+            key,
+            pending: RefCell::default(),
+            writing: Cell::new(false),
+        })
+    }
+
+    pub fn write<T>(this: &Rc<Self>, value: &T)
+    where
+        T: Serialize,
+    {
+        let value = match serde_json::to_string(value) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::error!("failed to serialise value of {}: {}", this.key, e);
+                return;
+            }
+        };
+
+        *this.pending.borrow_mut() = Some(value);
+
+        // Writes are made one at a time, so an older value never overwrites a newer one. Values
+        // that change while a write is in progress are coalesced into the next write.
+        if this.writing.replace(true) {
+            return;
+        }
+
+        let this = this.clone();
+        spawn_local(async move {
+            loop {
+                let value = match this.pending.borrow_mut().take() {
+                    Some(m) => m,
+                    None => break,
+                };
+
+                if let Err(e) = this.storage.set_item(&this.key, &value).await {
+                    tracing::warn!("failed to persist {}: {}", this.key, e);
+                }
+            }
+
+            this.writing.set(false);
+        });
+    }
+}
+
+/// A trait to be implemented on atom families that are persisted to storage.
+///
+/// Each key of the family is stored in its own entry of the storage, so a key is only
+/// deserialised when a component starts using it.
+///
+/// # Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// use bounce::AtomFamily;
+/// use bounce::persist::PersistedFamily;
+///
+/// #[derive(Serialize, Deserialize, PartialEq)]
+/// struct Draft {
+///     text: String,
+/// }
+///
+/// impl AtomFamily for Draft {
+///     type Key = u64;
+///
+///     fn create(_key: &u64) -> Self {
+///         Self { text: String::new() }
+///     }
+/// }
+///
+/// impl PersistedFamily for Draft {
+///     fn storage_key(key: &u64) -> String {
+///         format!("drafts/{}", key)
+///     }
+/// }
+/// ```
+pub trait PersistedFamily: AtomFamily + Serialize + DeserializeOwned {
+    /// Returns the key of the storage entry that the value of a key is stored in.
+    ///
+    /// Each key of each persisted family of an application should have a unique storage key.
+    fn storage_key(key: &Self::Key) -> String;
+}
+
+/// A persistence of a slice, stopped when dropped.
+struct PersistSession<T>
+where
+    T: Persisted,
+{
+    state: SliceState<T>,
+    writer: Rc<StorageWriter>,
+    listener: RefCell<Option<Listener>>,
+}
+
+impl<T> PersistSession<T>
+where
+    T: Persisted,
+{
+    fn start(root: &BounceRootState, tracker: &HydrationTracker) -> Rc<Self> {
+        let storage = get_storage(root);
+
+        let this = Rc::new(Self {
+            state: root.get_state::<SliceState<T>>(),
+            writer: StorageWriter::new(storage.clone(), T::KEY.to_string()),
+            listener: RefCell::default(),
+        });
+
+        let weak_this = Rc::downgrade(&this);
+        tracker.hydrate(async move {
+            let stored = storage.get_item(T::KEY).await;
+
+            if let Some(this) = weak_this.upgrade() {
+                let current = this.state.get();
+
+                // A value that has been set before the slice is restored is kept and written.
+                if *current != T::default() {
+                    StorageWriter::write(&this.writer, &*current);
+                } else if let Some(m) = read_stored::<T>(T::KEY, stored) {
+                    this.state.replace(m.into());
+                }
+
+                Self::listen(&this);
+            }
+        });
+
+        this
+    }
+
+    /// Writes the slice each time it changes after it has been restored.
+    fn listen(this: &Rc<Self>) {
+        let weak_this: Weak<Self> = Rc::downgrade(this);
+
+        *this.listener.borrow_mut() = Some(this.state.listen(Rc::new(Callback::from(
+            move |value: Rc<T>| {
+                if let Some(this) = weak_this.upgrade() {
+                    StorageWriter::write(&this.writer, &*value);
+                }
+            },
+        ))));
+    }
+}
+
+/// A persistence of an atom family, stopped when dropped.
+struct PersistFamilySession<T>
+where
+    T: PersistedFamily,
+{
+    state: SliceState<AtomFamilySlice<T>>,
+    storage: Rc<dyn PersistStorage>,
+    // The writers of the keys that are restored, `None` while a key is being restored.
+    writers: RefCell<HashMap<T::Key, Option<Rc<StorageWriter>>>>,
+    last: RefCell<Rc<AtomFamilySlice<T>>>,
+    listeners: RefCell<Vec<Listener>>,
+}
+
+impl<T> PersistFamilySession<T>
+where
+    T: PersistedFamily,
+{
+    fn start(root: &BounceRootState) -> Rc<Self> {
+        let state = root.get_state::<SliceState<AtomFamilySlice<T>>>();
+        let subscribers = root.get_state::<AtomFamilySubscribers<T>>();
+
+        let this = Rc::new(Self {
+            last: RefCell::new(state.get()),
+            state,
+            storage: get_storage(root),
+            writers: RefCell::default(),
+            listeners: RefCell::default(),
+        });
+
+        let listeners = {
+            let weak_this: Weak<Self> = Rc::downgrade(&this);
+            let on_mount =
+                subscribers.listen_mount(Rc::new(Callback::from(move |key: Rc<T::Key>| {
+                    if let Some(this) = weak_this.upgrade() {
+                        Self::restore(&this, (*key).clone());
+                    }
+                })));
+
+            // An evicted key is restored again when it is used next time.
+            let weak_this: Weak<Self> = Rc::downgrade(&this);
+            let on_evict =
+                subscribers.listen_evict(Rc::new(Callback::from(move |key: Rc<T::Key>| {
+                    if let Some(this) = weak_this.upgrade() {
+                        this.writers.borrow_mut().remove(&key);
+                    }
+                })));
+
+            let weak_this: Weak<Self> = Rc::downgrade(&this);
+            let on_change = this.state.listen(Rc::new(Callback::from(
+                move |value: Rc<AtomFamilySlice<T>>| {
+                    if let Some(this) = weak_this.upgrade() {
+                        Self::write_changed(&this, value);
+                    }
+                },
+            )));
+
+            vec![on_mount, on_evict, on_change]
+        };
+        *this.listeners.borrow_mut() = listeners;
+
+        // Keys that are used before the persistence is started.
+        for key in subscribers.keys() {
+            Self::restore(&this, key);
+        }
+
+        this
+    }
+
+    /// Restores a key, it is written each time it changes afterwards.
+    fn restore(this: &Rc<Self>, key: T::Key) {
+        if this.writers.borrow().contains_key(&key) {
+            return;
+        }
+        this.writers.borrow_mut().insert(key.clone(), None);
+
+        let storage_key = T::storage_key(&key);
+        let writer = StorageWriter::new(this.storage.clone(), storage_key.clone());
+
+        let weak_this = Rc::downgrade(this);
+        spawn_local(async move {
+            let stored = writer.storage.get_item(&storage_key).await;
+
+            let this = match weak_this.upgrade() {
+                Some(m) => m,
+                None => return,
+            };
+
+            // The key has been evicted while it was being restored.
+            if !matches!(this.writers.borrow().get(&key), Some(None)) {
+                return;
+            }
+
+            let current = this.state.get().get(&key);
+
+            // A value that has been set before the key is restored is kept and written.
+            if *current != T::create(&key) {
+                StorageWriter::write(&writer, &*current);
+            } else if let Some(m) = read_stored::<T>(&storage_key, stored) {
+                this.state.dispatch(AtomFamilyAction::Set {
+                    key: key.clone(),
+                    value: m.into(),
+                });
+            }
+
+            this.writers.borrow_mut().insert(key, Some(writer));
+        });
+    }
+
+    /// Writes the restored keys that have changed.
+    fn write_changed(this: &Rc<Self>, value: Rc<AtomFamilySlice<T>>) {
+        let prev = this.last.replace(value.clone());
+
+        for (key, writer) in this.writers.borrow().iter() {
+            let writer = match writer {
+                Some(m) => m,
+                None => continue,
+            };
+
+            let changed = match (prev.get_stored(key), value.get_stored(key)) {
+                (Some(prev), Some(next)) => !Rc::ptr_eq(prev, next),
+                (None, None) => false,
+                _ => true,
+            };
+
+            if changed {
+                StorageWriter::write(writer, &*value.get(key));
+            }
+        }
+    }
+}
+
+/// A hook to persist a slice while the component is mounted.
+///
+/// The slice is restored from storage when the component is mounted and written to storage each
+/// time it changes afterwards. If the slice is changed before it is restored, the value that has
+/// been set is kept and written. Components can be rendered under a
+/// [`PersistGate`](crate::PersistGate) so they are rendered with the restored value.
+///
+/// Each slice should be persisted by only 1 component of each root.
+///
+/// See: [`bounce::persist`](crate::persist)
+#[hook]
+pub fn use_persist<T>()
+where
+    T: Persisted,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let tracker = use_hydration_tracker();
+
+    use_effect_with((), move |_| {
+        let session = PersistSession::<T>::start(&root, &tracker);
+        move || drop(session)
+    });
+}
+
+/// A hook to persist an atom family while the component is mounted.
+///
+/// Each key is restored from its own storage entry when a component starts using it, so large
+/// families are not deserialised at once when the application is started. The value of a key is
+/// written to storage each time it changes after it has been restored. If the value of a key is
+/// set before it is restored, the value that has been set is kept.
+///
+/// Keys of families that [evict unused values](crate::AtomFamily::EVICT_UNUSED) are restored
+/// again when they are used after they have been evicted.
+///
+/// Each atom family should be persisted by only 1 component of each root.
+///
+/// # Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use yew::prelude::*;
+/// # use bounce::AtomFamily;
+/// use bounce::persist::{use_persist_family, PersistedFamily};
+///
+/// # #[derive(Serialize, Deserialize, PartialEq)]
+/// # struct Draft {
+/// #     text: String,
+/// # }
+/// #
+/// # impl AtomFamily for Draft {
+/// #     type Key = u64;
+/// #
+/// #     fn create(_key: &u64) -> Self {
+/// #         Self { text: String::new() }
+/// #     }
+/// # }
+/// #
+/// # impl PersistedFamily for Draft {
+/// #     fn storage_key(key: &u64) -> String {
+/// #         format!("drafts/{}", key)
+/// #     }
+/// # }
+/// #
+/// #[function_component(DraftsPersist)]
+/// fn drafts_persist() -> Html {
+///     use_persist_family::<Draft>();
+///
+///     Html::default()
+/// }
+/// ```
+///
+/// See: [`bounce::persist`](crate::persist)
+#[hook]
+pub fn use_persist_family<T>()
+where
+    T: PersistedFamily,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    // Keys that are used before the component is mounted are restored when the persistence is
+    // started, keys used afterwards are restored as components start using them.
+    use_effect_with((), move |_| {
+        let session = PersistFamilySession::<T>::start(&root);
+        move || drop(session)
+    });
+}
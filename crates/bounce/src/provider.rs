@@ -1,7 +1,9 @@
 use anymap2::AnyMap;
 use yew::prelude::*;
 
+use crate::overrides::StateOverrides;
 use crate::root_state::BounceRootState;
+use crate::services::Services;
 
 /// Properties for [`BounceRoot`].
 #[derive(Properties, Debug, PartialEq, Clone)]
@@ -17,6 +19,22 @@ pub struct BounceRootProps {
     /// This only affects [`Atom`](macro@crate::Atom) and [`Slice`](macro@crate::Slice).
     #[prop_or_default]
     pub get_init_states: Option<Callback<(), AnyMap>>,
+
+    /// A callback that retrieves the services provided to this root.
+    ///
+    /// Services can be accessed with [`BounceStates::get_service`](crate::BounceStates::get_service).
+    ///
+    /// See: [`Services`]
+    #[prop_or_default]
+    pub get_services: Option<Callback<(), Services>>,
+
+    /// A callback that retrieves the values that replace the initial values of states.
+    ///
+    /// This is intended for tests to run components against fake data.
+    ///
+    /// See: [`StateOverrides`]
+    #[prop_or_default]
+    pub get_overrides: Option<Callback<(), StateOverrides>>,
 }
 
 /// A `<BounceRoot />`.
@@ -45,11 +63,15 @@ pub fn bounce_root(props: &BounceRootProps) -> Html {
     let BounceRootProps {
         children,
         get_init_states,
+        get_services,
+        get_overrides,
     } = props.clone();
 
     let root_state = (*use_state(move || {
         let init_states = get_init_states.map(|m| m.emit(())).unwrap_or_default();
-        BounceRootState::new(init_states)
+        let services = get_services.map(|m| m.emit(())).unwrap_or_default();
+        let overrides = get_overrides.map(|m| m.emit(())).unwrap_or_default();
+        BounceRootState::new(init_states, services, overrides)
     }))
     .clone();
 
@@ -81,7 +103,9 @@ pub fn bounce_root(props: &BounceRootProps) -> Html {
                     w.send_helmet(
                         states,
                         writer_state.format_title.clone(),
+                        writer_state.title_template.clone(),
                         writer_state.default_title.clone(),
+                        writer_state.robots,
                     );
                 }
             }
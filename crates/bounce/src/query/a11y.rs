@@ -0,0 +1,279 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use super::traits::{Mutation, Query};
+use super::use_mutation::MutationState;
+use super::use_query::QueryState;
+use super::use_query_value::QueryValueState;
+use crate::states::artifact::{ArtifactAction, ArtifactSlice};
+use crate::states::slice::use_slice_dispatch;
+use crate::use_artifacts;
+use crate::utils::{use_root_scoped_id, Id};
+
+/// Hides live regions visually while keeping them available to screen readers.
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; \
+    padding: 0; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;";
+
+/// The status of a query or a mutation that is announced to screen readers.
+///
+/// It can be converted from the state of a query or a mutation. A query that is refreshing in the
+/// background keeps the status of its last result, so refreshes are not announced.
+///
+/// See: [`use_a11y_status_announcer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum A11yStatus {
+    /// The mutation has not started yet.
+    #[default]
+    Idle,
+    /// The query or the mutation is loading.
+    Loading,
+    /// The query or the mutation has completed successfully.
+    Success,
+    /// The query or the mutation has failed.
+    Error,
+}
+
+impl A11yStatus {
+    fn from_result<T, E>(result: &Result<T, E>) -> Self {
+        match result {
+            Ok(_) => Self::Success,
+            Err(_) => Self::Error,
+        }
+    }
+}
+
+impl<T> From<&QueryValueState<T>> for A11yStatus
+where
+    T: Query + 'static,
+{
+    fn from(state: &QueryValueState<T>) -> Self {
+        match state {
+            QueryValueState::Loading => Self::Loading,
+            QueryValueState::Completed { result }
+            | QueryValueState::Refreshing {
+                last_result: result,
+            } => Self::from_result(result),
+        }
+    }
+}
+
+impl<T> From<&QueryState<T>> for A11yStatus
+where
+    T: Query + 'static,
+{
+    fn from(state: &QueryState<T>) -> Self {
+        match state {
+            QueryState::Completed { result }
+            | QueryState::Refreshing {
+                last_result: result,
+            } => Self::from_result(result),
+        }
+    }
+}
+
+impl<T> From<&MutationState<T>> for A11yStatus
+where
+    T: Mutation + 'static,
+{
+    fn from(state: &MutationState<T>) -> Self {
+        match state {
+            MutationState::Idle => Self::Idle,
+            MutationState::Loading | MutationState::InProgress { .. } => Self::Loading,
+            MutationState::Completed { result }
+            | MutationState::Refreshing {
+                last_result: result,
+            } => Self::from_result(result),
+        }
+    }
+}
+
+/// The messages announced when the status of a query or a mutation changes.
+///
+/// Statuses without a message are not announced.
+///
+/// See: [`use_a11y_status_announcer`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct A11yMessages {
+    loading: Option<AttrValue>,
+    success: Option<AttrValue>,
+    error: Option<AttrValue>,
+}
+
+impl A11yMessages {
+    /// Creates a set of messages that announces nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the message announced when the query or the mutation starts loading.
+    pub fn loading(mut self, message: impl Into<AttrValue>) -> Self {
+        self.loading = Some(message.into());
+        self
+    }
+
+    /// Sets the message announced when the query or the mutation completes successfully.
+    pub fn success(mut self, message: impl Into<AttrValue>) -> Self {
+        self.success = Some(message.into());
+        self
+    }
+
+    /// Sets the message announced when the query or the mutation fails.
+    ///
+    /// Errors are announced assertively, interrupting the screen reader.
+    pub fn error(mut self, message: impl Into<AttrValue>) -> Self {
+        self.error = Some(message.into());
+        self
+    }
+
+    fn get(&self, status: A11yStatus) -> Option<&AttrValue> {
+        match status {
+            A11yStatus::Idle => None,
+            A11yStatus::Loading => self.loading.as_ref(),
+            A11yStatus::Success => self.success.as_ref(),
+            A11yStatus::Error => self.error.as_ref(),
+        }
+    }
+}
+
+/// An announcement registered as an artifact and rendered by the [`A11yAnnouncer`].
+#[derive(Debug, PartialEq)]
+pub(crate) struct A11yAnnouncement {
+    /// Increases with each announcement, so the latest announcement can be found.
+    seq: Id,
+    message: AttrValue,
+    assertive: bool,
+}
+
+/// A hook to announce the status transitions of a query or a mutation to screen readers.
+///
+/// Each time `status` changes, the message for the new status is rendered in the live regions
+/// of the [`A11yAnnouncer`] registered under the same `<BounceRoot />`, so applications do not
+/// need to wire an `aria-live` region to each query.
+///
+/// # Example
+///
+/// ```
+/// # use std::convert::Infallible;
+/// # use std::rc::Rc;
+/// # use async_trait::async_trait;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::BounceRoot;
+/// use bounce::query::{
+///     use_a11y_status_announcer, use_query_value, A11yAnnouncer, A11yMessages, Query,
+///     QueryResult,
+/// };
+///
+/// # #[derive(Debug, PartialEq)]
+/// # struct User;
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for User {
+/// #     type Input = u64;
+/// #     type Error = Infallible;
+/// #
+/// #     async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+/// #         Ok(User.into())
+/// #     }
+/// # }
+/// #
+/// #[function_component(UserProfile)]
+/// fn user_profile() -> Html {
+///     let user = use_query_value::<User>(0.into());
+///
+///     use_a11y_status_announcer(
+///         user.state().into(),
+///         A11yMessages::new()
+///             .loading("Loading profile")
+///             .success("Profile loaded")
+///             .error("Failed to load profile"),
+///     );
+///
+///     // render the profile...
+/// #   Html::default()
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <A11yAnnouncer />
+///             <UserProfile />
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_a11y_status_announcer(status: A11yStatus, messages: A11yMessages) {
+    let id = use_root_scoped_id();
+    let dispatch = use_slice_dispatch::<ArtifactSlice<A11yAnnouncement>>();
+    let last_status = use_memo((), |_| Cell::new(A11yStatus::Idle));
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with(status, move |status| {
+            if last_status.replace(*status) != *status {
+                if let Some(message) = messages.get(*status) {
+                    dispatch(ArtifactAction::Insert(
+                        id,
+                        None,
+                        Rc::new(A11yAnnouncement {
+                            seq: Id::new(),
+                            message: message.clone(),
+                            assertive: *status == A11yStatus::Error,
+                        }),
+                    ));
+                }
+            }
+
+            || {}
+        });
+    }
+
+    use_effect_with((), move |_| move || dispatch(ArtifactAction::Remove(id)));
+}
+
+/// Properties of [`A11yAnnouncer`].
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct A11yAnnouncerProps {
+    /// Classes of the element that contains the live regions.
+    ///
+    /// When classes are specified, the live regions are not hidden with an inline style.
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// A component that renders the announcements of [`use_a11y_status_announcer`] in `aria-live`
+/// regions.
+///
+/// The latest announcement is rendered in a polite `role="status"` region, or in an assertive
+/// `role="alert"` region for errors. The regions are hidden visually.
+///
+/// Only 1 announcer should be registered under each `<BounceRoot />`.
+///
+/// See: [`use_a11y_status_announcer`]
+#[function_component(A11yAnnouncer)]
+pub fn a11y_announcer(props: &A11yAnnouncerProps) -> Html {
+    let announcements = use_artifacts::<A11yAnnouncement>();
+
+    let latest = |assertive: bool| {
+        announcements
+            .iter()
+            .filter(|m| m.assertive == assertive)
+            .max_by_key(|m| m.seq)
+            // The message is keyed, so a message that is announced again replaces the text node.
+            .map(|m| html! { <span key={m.seq.value()}>{m.message.clone()}</span> })
+            .unwrap_or_default()
+    };
+
+    let style = props.class.is_empty().then_some(VISUALLY_HIDDEN);
+
+    html! {
+        <div class={props.class.clone()} {style}>
+            <div role="status" aria-live="polite" aria-atomic="true">{latest(false)}</div>
+            <div role="alert" aria-live="assertive" aria-atomic="true">{latest(true)}</div>
+        </div>
+    }
+}
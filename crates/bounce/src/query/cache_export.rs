@@ -0,0 +1,122 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::query_states::{resolve_run, QuerySlice, QuerySliceAction};
+use super::seed::{load_fn, LoadFn, SeedEntry};
+use super::traits::Query;
+use crate::root_state::BounceRootState;
+use crate::states::slice::SliceState;
+
+type ExportFn = Rc<dyn Fn(&BounceRootState) -> Result<Value, serde_json::Error>>;
+type ApplyFn = Rc<dyn Fn(&BounceRootState, Box<dyn Any>)>;
+
+struct ExportedQuery {
+    export: ExportFn,
+    load: LoadFn,
+    apply: ApplyFn,
+}
+
+/// The query types that are included in exported caches, with their keys.
+///
+/// See: [`QueryCacheSeed::with_exported_query`](super::QueryCacheSeed::with_exported_query)
+#[derive(Default)]
+pub(super) struct ExportedQueries {
+    inner: HashMap<&'static str, ExportedQuery>,
+}
+
+impl ExportedQueries {
+    pub fn insert<T>(&mut self, key: &'static str)
+    where
+        T: Query + Serialize + DeserializeOwned + 'static,
+        T::Input: Serialize + DeserializeOwned,
+    {
+        let export: ExportFn = Rc::new(|root| {
+            let entries = root
+                .get_state::<SliceState<QuerySlice<T>>>()
+                .get()
+                .successful_results()
+                .into_iter()
+                .map(|(input, value)| {
+                    serde_json::to_value(SeedEntry {
+                        input: &*input,
+                        value: &*value,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Value::Array(entries))
+        });
+
+        let apply: ApplyFn = Rc::new(|root, entries| {
+            let entries = match entries.downcast::<Vec<(Rc<T::Input>, Rc<T>)>>() {
+                Ok(m) => *m,
+                Err(_) => return,
+            };
+
+            // Runs in flight for the imported inputs resolve with the imported results, so they
+            // do not replace them when they complete.
+            for (input, value) in entries.iter() {
+                resolve_run::<T>(root, input, &Ok(value.clone()));
+            }
+
+            root.get_state::<SliceState<QuerySlice<T>>>()
+                .dispatch(QuerySliceAction::Import { entries });
+        });
+
+        self.inner.insert(
+            key,
+            ExportedQuery {
+                export,
+                load: load_fn::<T>(),
+                apply,
+            },
+        );
+    }
+
+    pub fn export(&self, root: &BounceRootState) -> Value {
+        let mut document = Map::new();
+
+        for (key, m) in self.inner.iter() {
+            match (m.export)(root) {
+                Ok(m) => {
+                    document.insert((*key).to_string(), m);
+                }
+                Err(e) => tracing::warn!("failed to export query cache for {}: {}", key, e),
+            }
+        }
+
+        Value::Object(document)
+    }
+
+    pub fn import(&self, root: &BounceRootState, value: Value) -> Result<(), serde_json::Error> {
+        let document = serde_json::from_value::<Map<String, Value>>(value)?;
+
+        // The whole document is deserialized before any result is loaded, so an invalid document
+        // leaves the cache unchanged.
+        let mut loaded = Vec::new();
+        for (key, value) in document {
+            match self.inner.get(key.as_str()) {
+                Some(m) => loaded.push((m.apply.clone(), (m.load)(value)?)),
+                None => tracing::warn!("query cache for {} is not registered, skipping", key),
+            }
+        }
+
+        for (apply, entries) in loaded {
+            apply(root, entries);
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ExportedQueries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.inner.keys()).finish()
+    }
+}
@@ -0,0 +1,280 @@
+use std::hash::Hash;
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::future::LocalBoxFuture;
+use futures::FutureExt;
+
+use super::error_class::ErrorClass;
+use super::options::SsrPolicy;
+use super::polling::HiddenPolling;
+use super::preload::PreloadHint;
+use super::query_client::QueryClient;
+use super::query_states::QuerySelector;
+use super::traits::{ProcessedQuery, Query, QueryResult};
+use crate::root_state::BounceStates;
+
+/// A set of queries that a [`DependentQuery`] depends on.
+///
+/// This trait is implemented for tuples of up to 4 queries, e.g.: `(UserQuery,)` or
+/// `(UserQuery, SettingsQuery)`.
+pub trait QueryDependencies: 'static {
+    /// The inputs of the queries, a tuple of `Rc<Input>` for each query.
+    type Inputs;
+
+    /// The results of the queries, a tuple of [`QueryResult`] for each query.
+    type Results;
+
+    /// Fetches the queries and waits for their results.
+    #[doc(hidden)]
+    fn resolve(states: &BounceStates, inputs: Self::Inputs) -> LocalBoxFuture<'_, Self::Results>;
+}
+
+macro_rules! impl_query_dependencies {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<$($ty),+> QueryDependencies for ($($ty,)+)
+        where
+            $($ty: Query + 'static,)+
+        {
+            type Inputs = ($(Rc<$ty::Input>,)+);
+            type Results = ($(QueryResult<$ty>,)+);
+
+            fn resolve(
+                states: &BounceStates,
+                inputs: Self::Inputs,
+            ) -> LocalBoxFuture<'_, Self::Results> {
+                let client = QueryClient::new(states.root().clone());
+                // All queries are started before waiting for any of them.
+                let fetches = ($(client.fetch_query::<$ty>(inputs.$idx.clone()),)+);
+
+                async move {
+                    let results = ($(fetches.$idx.await,)+);
+
+                    // Reading the queries after they are resolved makes the dependent query
+                    // outdated when any of them is refetched.
+                    $(states.get_input_selector_value::<QuerySelector<$ty>>(inputs.$idx);)+
+
+                    results
+                }
+                .boxed_local()
+            }
+        }
+    };
+}
+
+impl_query_dependencies!(A => 0);
+impl_query_dependencies!(A => 0, B => 1);
+impl_query_dependencies!(A => 0, B => 1, C => 2);
+impl_query_dependencies!(A => 0, B => 1, C => 2, D => 3);
+
+/// The inputs of the dependencies of a [`DependentQuery`].
+pub type DependencyInputs<T> = <<T as DependentQuery>::DependsOn as QueryDependencies>::Inputs;
+
+/// The results of the dependencies of a [`DependentQuery`].
+pub type DependencyResults<T> = <<T as DependentQuery>::DependsOn as QueryDependencies>::Results;
+
+/// A trait to be implemented on queries that depend on the results of other queries.
+///
+/// When a dependent query is fetched, the inputs of its dependencies are derived from its input
+/// with [`dependency_inputs`](Self::dependency_inputs) and the dependencies are fetched first,
+/// sharing requests and cached results with other components. The results of the dependencies are
+/// then passed to [`query`](Self::query).
+///
+/// The dependent query is loading until all of its dependencies have resolved and is marked
+/// outdated and fetched again each time one of its dependencies is refetched.
+///
+/// Types implementing this trait implement [`Query`] automatically.
+///
+/// # Note
+///
+/// This trait is implemented with [async_trait](macro@async_trait), you should apply an `#[async_trait(?Send)]`
+/// attribute to your implementation of this trait.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use std::convert::Infallible;
+/// use bounce::prelude::*;
+/// use bounce::query::{DependencyInputs, DependencyResults, DependentQuery, Query, QueryResult};
+/// use async_trait::async_trait;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct UserQuery {
+///     team_id: u64,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for UserQuery {
+///     type Input = u64;
+///     type Error = Infallible;
+///
+///     async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+///         // fetch user
+///
+///         Ok(UserQuery { team_id: 1 }.into())
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct TeamQuery {
+///     name: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl DependentQuery for TeamQuery {
+///     // The team of a user.
+///     type Input = u64;
+///     type Error = Infallible;
+///     type DependsOn = (UserQuery,);
+///
+///     fn dependency_inputs(input: &u64) -> DependencyInputs<Self> {
+///         (Rc::new(*input),)
+///     }
+///
+///     async fn query(
+///         _states: &BounceStates,
+///         _input: Rc<u64>,
+///         (user,): DependencyResults<Self>,
+///     ) -> Result<Self, Infallible> {
+///         let user = user?;
+///         // fetch team with user.team_id
+///
+///         Ok(TeamQuery { name: format!("Team {}", user.team_id) })
+///     }
+/// }
+/// ```
+#[async_trait(?Send)]
+pub trait DependentQuery: PartialEq {
+    /// The Input type of a query.
+    ///
+    /// The input type must implement Hash and Eq as it is used as the key of results in a
+    /// HashMap.
+    type Input: Hash + Eq + 'static;
+
+    /// The Error type of a query.
+    type Error: 'static + std::error::Error + PartialEq + Clone;
+
+    /// The queries this query depends on, as a tuple (e.g.: `(UserQuery,)`).
+    type DependsOn: QueryDependencies;
+
+    /// See: [`Query::STALE_TIME`]
+    const STALE_TIME: Option<Duration> = None;
+
+    /// See: [`Query::RETRIES`]
+    ///
+    /// Only this query is retried, its dependencies are retried as configured on their own.
+    const RETRIES: u32 = 0;
+
+    /// See: [`Query::KEEP_PREVIOUS_DATA`]
+    const KEEP_PREVIOUS_DATA: bool = false;
+
+    /// See: [`Query::POOLED`]
+    const POOLED: bool = true;
+
+    /// See: [`Query::FETCH_LIMIT`]
+    const FETCH_LIMIT: Option<usize> = None;
+
+    /// See: [`Query::SSR`]
+    const SSR: SsrPolicy = SsrPolicy::Block;
+
+    /// See: [`Query::POLL_INTERVAL`]
+    const POLL_INTERVAL: Option<Duration> = None;
+
+    /// See: [`Query::HIDDEN_POLLING`]
+    const HIDDEN_POLLING: HiddenPolling = HiddenPolling::Pause;
+
+    /// See: [`Query::TIMEOUT`]
+    const TIMEOUT: Option<Duration> = None;
+
+    /// See: [`Query::REFRESH_ON_RESTORE`]
+    const REFRESH_ON_RESTORE: bool = false;
+
+    /// Derives the inputs of the dependencies from the input of this query.
+    fn dependency_inputs(input: &Self::Input) -> DependencyInputs<Self>;
+
+    /// Runs a query with the results of its dependencies.
+    ///
+    /// This method will only be called when the result is not already cached.
+    ///
+    /// # Note
+    ///
+    /// When implementing this method with async_trait, you can use the following function
+    /// signature:
+    ///
+    /// ```ignore
+    /// async fn query(
+    ///     states: &BounceStates,
+    ///     input: Rc<Self::Input>,
+    ///     dependencies: DependencyResults<Self>,
+    /// ) -> Result<Self, Self::Error>
+    /// ```
+    async fn query(
+        states: &BounceStates,
+        input: Rc<Self::Input>,
+        dependencies: DependencyResults<Self>,
+    ) -> Result<Self, Self::Error>
+    where
+        Self: Sized;
+
+    /// Returns the resources the client will request for the result of this query.
+    ///
+    /// See: [`Query::preload_hints`]
+    fn preload_hints(_input: &Self::Input) -> Vec<PreloadHint> {
+        Vec::new()
+    }
+
+    /// See: [`Query::describe_input`]
+    fn describe_input(_input: &Self::Input) -> String {
+        std::any::type_name::<Self::Input>().to_string()
+    }
+
+    /// See: [`Query::classify_error`]
+    fn classify_error(_error: &Self::Error) -> ErrorClass {
+        ErrorClass::Unknown
+    }
+}
+
+#[async_trait(?Send)]
+impl<T> ProcessedQuery for T
+where
+    T: DependentQuery + 'static,
+{
+    type Input = T::Input;
+    type Error = T::Error;
+    type Raw = T;
+
+    const STALE_TIME: Option<Duration> = <T as DependentQuery>::STALE_TIME;
+    const RETRIES: u32 = <T as DependentQuery>::RETRIES;
+    const KEEP_PREVIOUS_DATA: bool = <T as DependentQuery>::KEEP_PREVIOUS_DATA;
+    const POOLED: bool = <T as DependentQuery>::POOLED;
+    const FETCH_LIMIT: Option<usize> = <T as DependentQuery>::FETCH_LIMIT;
+    const SSR: SsrPolicy = <T as DependentQuery>::SSR;
+    const POLL_INTERVAL: Option<Duration> = <T as DependentQuery>::POLL_INTERVAL;
+    const HIDDEN_POLLING: HiddenPolling = <T as DependentQuery>::HIDDEN_POLLING;
+    const TIMEOUT: Option<Duration> = <T as DependentQuery>::TIMEOUT;
+    const REFRESH_ON_RESTORE: bool = <T as DependentQuery>::REFRESH_ON_RESTORE;
+
+    async fn fetch(states: &BounceStates, input: Rc<Self::Input>) -> Result<T, Self::Error> {
+        let dependencies = T::DependsOn::resolve(states, T::dependency_inputs(&input)).await;
+
+        <T as DependentQuery>::query(states, input, dependencies).await
+    }
+
+    fn post_process(_input: &Self::Input, raw: T) -> Self {
+        raw
+    }
+
+    fn preload_hints(input: &Self::Input) -> Vec<PreloadHint> {
+        <T as DependentQuery>::preload_hints(input)
+    }
+
+    fn describe_input(input: &Self::Input) -> String {
+        <T as DependentQuery>::describe_input(input)
+    }
+
+    fn classify_error(error: &Self::Error) -> ErrorClass {
+        <T as DependentQuery>::classify_error(error)
+    }
+}
@@ -0,0 +1,117 @@
+/// The class of an error returned by a query or a mutation.
+///
+/// Errors are classified by [`Query::classify_error`](super::Query::classify_error) and
+/// [`Mutation::classify_error`](super::Mutation::classify_error).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorClass {
+    /// The request could not reach the server (e.g.: the device is offline).
+    Network,
+    /// The request did not complete in time.
+    Timeout,
+    /// The request is not authenticated or not authorised.
+    Auth,
+    /// The request is rejected because its input is invalid.
+    Validation,
+    /// The server has failed to handle the request.
+    Server,
+    /// The error does not belong to any other class.
+    Unknown,
+}
+
+/// A notion that is applied to the bounce root when a query fails.
+///
+/// Errors of mutations are applied as a [`MutationErrorNotion`].
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{ErrorClass, QueryErrorNotion};
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(with_notion(QueryErrorNotion))]
+/// struct OfflineBanner {
+///     visible: bool,
+/// }
+///
+/// impl WithNotion<QueryErrorNotion> for OfflineBanner {
+///     fn apply(self: Rc<Self>, notion: Rc<QueryErrorNotion>) -> Rc<Self> {
+///         match notion.class {
+///             ErrorClass::Network => Self { visible: true }.into(),
+///             _ => self,
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryErrorNotion {
+    /// The class of the error.
+    pub class: ErrorClass,
+    /// The type name of the query that has failed.
+    pub type_name: &'static str,
+    /// The message of the error.
+    pub message: String,
+}
+
+impl QueryErrorNotion {
+    pub(super) fn new<T, E>(class: ErrorClass, error: &E) -> Self
+    where
+        E: std::error::Error,
+    {
+        Self {
+            class,
+            type_name: std::any::type_name::<T>(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A notion that is applied to the bounce root when a mutation fails.
+///
+/// Unlike the errors of queries, which are often retried or refreshed in the background, the
+/// error of a mutation is the result of an action of the user and is usually reported to them
+/// (e.g.: with a toast).
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{ErrorClass, MutationErrorNotion};
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(with_notion(MutationErrorNotion))]
+/// struct UnsavedChanges {
+///     failed: bool,
+/// }
+///
+/// impl WithNotion<MutationErrorNotion> for UnsavedChanges {
+///     fn apply(self: Rc<Self>, _notion: Rc<MutationErrorNotion>) -> Rc<Self> {
+///         Self { failed: true }.into()
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationErrorNotion {
+    /// The class of the error.
+    pub class: ErrorClass,
+    /// The type name of the mutation that has failed.
+    pub type_name: &'static str,
+    /// The message of the error.
+    pub message: String,
+}
+
+impl MutationErrorNotion {
+    pub(super) fn new<T, E>(class: ErrorClass, error: &E) -> Self
+    where
+        E: std::error::Error,
+    {
+        Self {
+            class,
+            type_name: std::any::type_name::<T>(),
+            message: error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use yew::platform::pinned::oneshot;
+
+use crate::any_state::AnyState;
+
+#[derive(Default)]
+struct FetchPoolInner {
+    running: usize,
+    queue: VecDeque<oneshot::Sender<()>>,
+}
+
+/// A pool that limits the number of queries that are fetched concurrently in a root.
+///
+/// Fetches that exceed the limit wait in the order they are started until a running fetch
+/// completes.
+///
+/// The pool of the root is shared by all query types, each query type with a
+/// [fetch limit](super::Query::FETCH_LIMIT) also has a pool of its own (`FetchPool<T>`).
+///
+/// See: [`BounceConfig::with_fetch_pool_limit`](crate::BounceConfig::with_fetch_pool_limit)
+pub(super) struct FetchPool<T = ()>
+where
+    T: 'static,
+{
+    inner: Rc<RefCell<FetchPoolInner>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> FetchPool<T>
+where
+    T: 'static,
+{
+    /// Waits until a fetch can be started with at most `limit` fetches running.
+    ///
+    /// The fetch is running until the returned permit is dropped.
+    pub async fn acquire(&self, limit: usize) -> FetchPermit {
+        let receiver = {
+            let mut inner = self.inner.borrow_mut();
+
+            if inner.running < limit {
+                inner.running += 1;
+                None
+            } else {
+                let (sender, receiver) = oneshot::channel();
+                inner.queue.push_back(sender);
+                Some(receiver)
+            }
+        };
+
+        // The slot of a completed fetch is handed over to this fetch.
+        if let Some(m) = receiver {
+            let _ = m.await;
+        }
+
+        FetchPermit {
+            pool: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Default for FetchPool<T>
+where
+    T: 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for FetchPool<T>
+where
+    T: 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> AnyState for FetchPool<T>
+where
+    T: 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A running fetch of a [`FetchPool`], the fetch completes when the permit is dropped.
+pub(super) struct FetchPermit {
+    pool: Rc<RefCell<FetchPoolInner>>,
+}
+
+impl Drop for FetchPermit {
+    fn drop(&mut self) {
+        let mut inner = self.pool.borrow_mut();
+
+        // Waiters that are dropped before they are woken up (e.g.: the query is no longer
+        // current) are skipped.
+        while let Some(m) = inner.queue.pop_front() {
+            if m.send(()).is_ok() {
+                return;
+            }
+        }
+
+        inner.running -= 1;
+    }
+}
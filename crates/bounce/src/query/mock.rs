@@ -0,0 +1,426 @@
+use std::any::{type_name, Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anymap2::AnyMap;
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use super::traits::{Mutation, MutationResult, Query, QueryResult};
+use super::use_mutation::ProgressSink;
+use crate::any_state::AnyState;
+use crate::root_state::{BounceRootState, BounceStates};
+use crate::states::future_notion::FutureNotion;
+use crate::BounceConfig;
+
+type QueryResponder<T> = Rc<dyn Fn(&<T as Query>::Input) -> QueryResult<T>>;
+type MutationResponder<T> = Rc<dyn Fn(&<T as Mutation>::Input) -> MutationResult<T>>;
+type FutureNotionResponder<T> =
+    Rc<dyn Fn(&<T as FutureNotion>::Input) -> <T as FutureNotion>::Output>;
+
+/// A set of canned responses that replace the results of queries, mutations and future notions.
+///
+/// Queries, mutations and future notions that are not registered run as usual, unless the root
+/// is in [offline demo mode](crate::BounceConfig::with_offline_demo).
+///
+/// See: [`MockQueries`]
+///
+/// # Example
+///
+/// ```
+/// # use std::convert::Infallible;
+/// # use std::rc::Rc;
+/// # use std::time::Duration;
+/// # use async_trait::async_trait;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{Query, QueryMocks, QueryResult};
+/// #
+/// #[derive(Debug, PartialEq)]
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for User {
+///     type Input = u64;
+///     type Error = Infallible;
+///
+///     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+///         // fetch user from the server...
+/// #       todo!()
+///     }
+/// }
+///
+/// let mocks = QueryMocks::new()
+///     .with_latency(Duration::from_millis(500))
+///     .with_query::<User, _>(|id| {
+///         Ok(User {
+///             id: *id,
+///             name: "Jane Doe".into(),
+///         }
+///         .into())
+///     });
+/// ```
+#[derive(Default)]
+pub struct QueryMocks {
+    latency: Duration,
+    inner: HashMap<TypeId, (&'static str, Rc<dyn Any>)>,
+}
+
+impl QueryMocks {
+    /// Creates an empty set of mocks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the time that mocked queries and mutations take to resolve.
+    ///
+    /// Defaults to no latency. The latency is measured with the [`Clock`](crate::Clock) of the
+    /// root.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Replaces the result of a query with the result of `f`.
+    ///
+    /// `f` is called with the input of the query each time the query is fetched and can return
+    /// an error to show the error state of components.
+    pub fn with_query<T, F>(mut self, f: F) -> Self
+    where
+        T: Query + 'static,
+        F: 'static + Fn(&T::Input) -> QueryResult<T>,
+    {
+        let f: QueryResponder<T> = Rc::new(f);
+        self.inner
+            .insert(TypeId::of::<T>(), (type_name::<T>(), Rc::new(f)));
+        self
+    }
+
+    /// Replaces the result of a mutation with the result of `f`.
+    ///
+    /// `f` is called with the input of the mutation each time the mutation is run.
+    pub fn with_mutation<T, F>(mut self, f: F) -> Self
+    where
+        T: Mutation + 'static,
+        F: 'static + Fn(&T::Input) -> MutationResult<T>,
+    {
+        let f: MutationResponder<T> = Rc::new(f);
+        self.inner
+            .insert(TypeId::of::<T>(), (type_name::<T>(), Rc::new(f)));
+        self
+    }
+
+    /// Replaces the output of a future notion with the result of `f`.
+    ///
+    /// `f` is called with the input of the future notion each time the future notion is run with
+    /// [`use_future_notion_runner`](crate::use_future_notion_runner) or a
+    /// [`NotionScope`](crate::NotionScope).
+    pub fn with_future_notion<T, F>(mut self, f: F) -> Self
+    where
+        T: FutureNotion + 'static,
+        F: 'static + Fn(&T::Input) -> T::Output,
+    {
+        let f: FutureNotionResponder<T> = Rc::new(f);
+        self.inner
+            .insert(TypeId::of::<T>(), (type_name::<T>(), Rc::new(f)));
+        self
+    }
+
+    fn get<R>(&self, type_id: TypeId) -> Option<R>
+    where
+        R: Clone + 'static,
+    {
+        self.inner
+            .get(&type_id)
+            .and_then(|(_, m)| m.downcast_ref::<R>())
+            .cloned()
+    }
+}
+
+impl fmt::Debug for QueryMocks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryMocks")
+            .field("latency", &self.latency)
+            .field(
+                "mocks",
+                &self
+                    .inner
+                    .values()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// The mocks of the mounted [`MockQueries`] of a root.
+///
+/// Mocks are kept as a stack so a nested [`MockQueries`] overrides the mocks of its ancestors
+/// and the mocks of the ancestors apply again once it is unmounted.
+#[derive(Default, Clone)]
+struct ActiveMocks {
+    inner: Rc<RefCell<Vec<Rc<QueryMocks>>>>,
+}
+
+impl ActiveMocks {
+    /// Returns the innermost mocks that contain a mock of `type_id`.
+    fn find(&self, type_id: TypeId) -> Option<Rc<QueryMocks>> {
+        self.inner
+            .borrow()
+            .iter()
+            .rev()
+            .find(|m| m.inner.contains_key(&type_id))
+            .cloned()
+    }
+
+    fn push(&self, mocks: Rc<QueryMocks>) -> MockScope {
+        self.inner.borrow_mut().push(mocks.clone());
+
+        MockScope {
+            active: self.clone(),
+            mocks,
+        }
+    }
+}
+
+impl AnyState for ActiveMocks {
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// Removes the mocks of a [`MockQueries`] from the stack when dropped.
+struct MockScope {
+    active: ActiveMocks,
+    mocks: Rc<QueryMocks>,
+}
+
+impl Drop for MockScope {
+    fn drop(&mut self) {
+        self.active
+            .inner
+            .borrow_mut()
+            .retain(|m| !Rc::ptr_eq(m, &self.mocks));
+    }
+}
+
+/// Returns `true` if `T` is mocked by a mounted [`MockQueries`].
+pub(super) fn is_mocked<T>(root: &BounceRootState) -> bool
+where
+    T: 'static,
+{
+    root.get_state::<ActiveMocks>()
+        .find(TypeId::of::<T>())
+        .is_some()
+}
+
+/// Never resolves if the root is in offline demo mode and `T` is not mocked, so its
+/// implementation is not run.
+pub(super) async fn wait_if_offline<T>(root: &BounceRootState)
+where
+    T: 'static,
+{
+    let is_offline_demo = root
+        .get_service::<BounceConfig>()
+        .map(|m| m.offline_demo())
+        .unwrap_or(false);
+
+    if !is_offline_demo {
+        return;
+    }
+
+    if is_mocked::<T>(root) {
+        return;
+    }
+
+    #[cfg(debug_assertions)]
+    tracing::warn!(
+        "{} is not mocked, it is not run in offline demo mode",
+        type_name::<T>()
+    );
+
+    futures::future::pending::<()>().await;
+}
+
+/// Runs a future notion, or returns the mocked output if the future notion is mocked.
+pub(crate) async fn future_notion<T>(states: &BounceStates, input: &T::Input) -> T::Output
+where
+    T: FutureNotion + 'static,
+{
+    let root = states.root();
+    let mocked = root
+        .get_state::<ActiveMocks>()
+        .find(TypeId::of::<T>())
+        .and_then(|m| {
+            m.get::<FutureNotionResponder<T>>(TypeId::of::<T>())
+                .map(|f| (m.latency, f))
+        });
+
+    match mocked {
+        Some((latency, f)) => {
+            root.clock().sleep(latency).await;
+            f(input)
+        }
+        None => {
+            wait_if_offline::<T>(root).await;
+            T::run(states, input).await
+        }
+    }
+}
+
+/// Runs a query, or returns the mocked result if the query is mocked.
+pub(super) async fn query<T>(states: &BounceStates, input: Rc<T::Input>) -> QueryResult<T>
+where
+    T: Query + 'static,
+{
+    let root = states.root();
+    let mocked = root
+        .get_state::<ActiveMocks>()
+        .find(TypeId::of::<T>())
+        .and_then(|m| {
+            m.get::<QueryResponder<T>>(TypeId::of::<T>())
+                .map(|f| (m.latency, f))
+        });
+
+    match mocked {
+        Some((latency, f)) => {
+            root.clock().sleep(latency).await;
+            f(&input)
+        }
+        None => T::query(states, input).await,
+    }
+}
+
+/// Runs a mutation, or returns the mocked result if the mutation is mocked.
+pub(super) async fn run<T>(
+    states: &BounceStates,
+    input: Rc<T::Input>,
+    progress: ProgressSink,
+) -> MutationResult<T>
+where
+    T: Mutation + 'static,
+{
+    let root = states.root();
+    let mocked = root
+        .get_state::<ActiveMocks>()
+        .find(TypeId::of::<T>())
+        .and_then(|m| {
+            m.get::<MutationResponder<T>>(TypeId::of::<T>())
+                .map(|f| (m.latency, f))
+        });
+
+    match mocked {
+        Some((latency, f)) => {
+            root.clock().sleep(latency).await;
+            f(&input)
+        }
+        None => {
+            wait_if_offline::<T>(root).await;
+            T::run(states, input, progress).await
+        }
+    }
+}
+
+/// Properties of [`MockQueries`].
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct MockQueriesProps {
+    /// Children of the component.
+    #[prop_or_default]
+    pub children: Html,
+
+    /// A callback that returns the mocks.
+    ///
+    /// This function is called once when the component is mounted.
+    pub get_mocks: Callback<(), QueryMocks>,
+}
+
+/// A component that serves canned responses for queries and mutations.
+///
+/// Queries and mutations registered in [`QueryMocks`] resolve with the mocked result after the
+/// configured latency instead of running their implementation, so loading and error states can
+/// be shown in demos and visual tests without changing the definitions of queries.
+///
+/// Mocks apply to the whole `<BounceRoot />` while this component is mounted. Results that have
+/// already been cached are not replaced. If `<MockQueries />` are nested, the mocks of the
+/// innermost one that is mounted are used, and the mocks of its ancestors apply to the types it
+/// does not mock.
+///
+/// Mocked queries are not retried, so an error mock resolves after one latency.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::time::Duration;
+/// # use async_trait::async_trait;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::BounceRoot;
+/// # use bounce::query::{MockQueries, Query, QueryMocks, QueryResult};
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # struct User {
+/// #     id: u64,
+/// #     name: String,
+/// # }
+/// #
+/// # #[derive(Debug, PartialEq, Clone, thiserror::Error)]
+/// # #[error("user not found")]
+/// # struct NotFound;
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for User {
+/// #     type Input = u64;
+/// #     type Error = NotFound;
+/// #
+/// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+/// #         todo!()
+/// #     }
+/// # }
+/// #
+/// # #[function_component(UserProfile)]
+/// # fn user_profile() -> Html {
+/// #     Html::default()
+/// # }
+/// #
+/// #[function_component(UserProfileNotFound)]
+/// fn user_profile_not_found() -> Html {
+///     let get_mocks = Callback::from(|_| {
+///         QueryMocks::new()
+///             .with_latency(Duration::from_secs(1))
+///             .with_query::<User, _>(|_| Err(NotFound))
+///     });
+///
+///     html! {
+///         <BounceRoot>
+///             <MockQueries {get_mocks}>
+///                 <UserProfile />
+///             </MockQueries>
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(MockQueries)]
+pub fn mock_queries(props: &MockQueriesProps) -> Html {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    // Mocks are pushed before children are rendered so queries of children are mocked when they
+    // are first fetched. They are removed when the scope is dropped upon unmount.
+    let get_mocks = props.get_mocks.clone();
+    use_memo(root, move |root| {
+        root.get_state::<ActiveMocks>()
+            .push(get_mocks.emit(()).into())
+    });
+
+    props.children.clone()
+}
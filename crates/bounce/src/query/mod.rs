@@ -16,16 +16,73 @@
 //!
 //! If your backend is GraphQL, you can use graphql-client in conjunction with reqwest.
 
+mod a11y;
+#[cfg(feature = "query-export")]
+mod cache_export;
+mod dependent;
+mod error_class;
+mod fetch_pool;
+mod mock;
 mod mutation_states;
+mod options;
+mod polling;
+mod preload;
+mod prepared;
+mod query_client;
+mod query_read;
 mod query_states;
+mod query_view;
+mod restore;
+#[cfg(feature = "router")]
+mod route;
+#[cfg(feature = "query-seed")]
+mod seed;
+#[cfg(debug_assertions)]
+mod stability;
+mod subscriptions;
 mod traits;
 mod use_mutation;
 mod use_prepared_query;
 mod use_query;
 mod use_query_value;
 
-pub use traits::{Mutation, MutationResult, Query, QueryResult};
-pub use use_mutation::{use_mutation, MutationState, UseMutationHandle};
-pub use use_prepared_query::use_prepared_query;
-pub use use_query::{use_query, QueryState, UseQueryHandle};
-pub use use_query_value::{use_query_value, QueryValueState, UseQueryValueHandle};
+#[cfg(all(feature = "ssr", feature = "helmet"))]
+pub(crate) use preload::PreloadHints;
+
+pub(crate) use mock::future_notion as run_mocked_future_notion;
+
+pub(crate) use prepared::PreparedTransformConfig;
+
+#[cfg(feature = "testing")]
+pub(crate) use query_states::{QuerySlice, QuerySliceValue};
+
+pub use a11y::{
+    use_a11y_status_announcer, A11yAnnouncer, A11yAnnouncerProps, A11yMessages, A11yStatus,
+};
+pub use dependent::{DependencyInputs, DependencyResults, DependentQuery, QueryDependencies};
+pub use error_class::{ErrorClass, MutationErrorNotion, QueryErrorNotion};
+pub use mock::{MockQueries, MockQueriesProps, QueryMocks};
+pub use options::{QueryOptions, SsrPolicy};
+pub use polling::HiddenPolling;
+pub use preload::PreloadHint;
+pub use prepared::PreparedTransform;
+pub use query_client::{use_query_client, ClearQueryCache, QueryClient, QueryFetch};
+pub use query_read::QueryRead;
+pub use query_view::{use_query_view, QueryView};
+#[cfg(feature = "router")]
+#[cfg_attr(documenting, doc(cfg(feature = "router")))]
+pub use route::{use_route_query, FromRoute};
+#[cfg(feature = "query-seed")]
+#[cfg_attr(documenting, doc(cfg(feature = "query-seed")))]
+pub use seed::QueryCacheSeed;
+pub use subscriptions::{
+    QuerySubscription, QuerySubscriptionsOverlay, QuerySubscriptionsOverlayProps,
+};
+pub use traits::{Mutation, MutationResult, ProcessedQuery, Query, QueryResult};
+pub use use_mutation::{use_mutation, MutationState, ProgressSink, UseMutationHandle};
+pub use use_prepared_query::{use_prepared_query, use_prepared_query_value};
+pub use use_query::{use_query, use_query_with_timeout, QueryState, QueryTimedOut, UseQueryHandle};
+pub use use_query_value::{
+    use_query_value, use_query_value_with_policy, FetchPolicy, QueryMissing, QueryValueState,
+    UseQueryValueHandle,
+};
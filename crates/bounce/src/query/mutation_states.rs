@@ -1,12 +1,21 @@
 use std::cell::RefCell;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::marker::PhantomData;
 use std::rc::Rc;
+use std::time::Duration;
 
+use anymap2::AnyMap;
 use yew::platform::pinned::oneshot;
 use yew::prelude::*;
 
+use super::error_class::MutationErrorNotion;
+use super::mock;
+use super::query_client::ClearQueryCache;
 use super::traits::{Mutation, MutationResult};
+use super::use_mutation::ProgressSink;
+use crate::abort::{self, AbortToken};
+use crate::any_state::AnyState;
 use crate::future_notion;
 use crate::root_state::BounceStates;
 use crate::states::future_notion::Deferred;
@@ -17,7 +26,7 @@ use crate::utils::Id;
 
 // We create 2 ID types to better distinguish them in code.
 #[derive(Default, PartialEq, Debug, Clone, Eq, Hash, PartialOrd, Ord, Copy)]
-pub(super) struct HandleId(Id);
+pub(super) struct HandleId(pub Id);
 
 #[derive(Default, PartialEq, Debug, Clone, Eq, Hash, PartialOrd, Ord, Copy)]
 pub(super) struct MutationId(Id);
@@ -30,6 +39,7 @@ where
     Idle,
     Loading {
         id: MutationId,
+        progress: Option<f64>,
     },
     Completed {
         id: MutationId,
@@ -38,6 +48,7 @@ where
     Outdated {
         id: MutationId,
         result: MutationResult<T>,
+        progress: Option<f64>,
     },
 }
 
@@ -48,14 +59,22 @@ where
     fn clone(&self) -> Self {
         match self {
             Self::Idle => Self::Idle,
-            Self::Loading { id } => Self::Loading { id: *id },
+            Self::Loading { id, progress } => Self::Loading {
+                id: *id,
+                progress: *progress,
+            },
             Self::Completed { id, result } => Self::Completed {
                 id: *id,
                 result: result.clone(),
             },
-            Self::Outdated { id, result } => Self::Outdated {
+            Self::Outdated {
+                id,
+                result,
+                progress,
+            } => Self::Outdated {
                 id: *id,
                 result: result.clone(),
+                progress: *progress,
             },
         }
     }
@@ -69,6 +88,9 @@ where
     pub mutation_id: MutationId,
     pub input: Rc<T::Input>,
     pub sender: RefCell<Option<oneshot::Sender<MutationResult<T>>>>,
+    /// If `true`, the result is discarded if another run with this flag has been started by the
+    /// same handle.
+    pub latest_only: bool,
 }
 
 #[future_notion(RunMutation)]
@@ -79,7 +101,58 @@ pub(super) async fn run_mutation<T>(
 where
     T: Mutation + 'static,
 {
-    let result = T::run(states, input.input.clone()).await;
+    let progress = {
+        let root = states.root().clone();
+        let handle_id = input.handle_id;
+        let mutation_id = input.mutation_id;
+        let latest_only = input.latest_only;
+
+        ProgressSink::new(move |progress| {
+            root.apply_notion(Rc::new(MutationProgress::<T> {
+                handle_id,
+                mutation_id,
+                latest_only,
+                progress,
+                _marker: PhantomData,
+            }))
+        })
+    };
+
+    let token = AbortToken::new();
+    let states = states.with_abort_token(token.clone());
+    let runs = states.root().get_state::<MutationRuns<T>>();
+
+    let run = async {
+        match T::cache_key(&input.input) {
+            // Cached runs are shared by all handles, so they are not cancelled with a handle.
+            Some(key) => {
+                run_cached_mutation::<T>(&states, key, input.input.clone(), progress).await
+            }
+            None => {
+                runs.start(
+                    input.handle_id,
+                    input.mutation_id,
+                    input.latest_only,
+                    token.clone(),
+                );
+
+                mock::run::<T>(&states, input.input.clone(), progress).await
+            }
+        }
+    };
+
+    let result = abort::with_timeout(&*states.root().clock(), T::TIMEOUT, &token, run).await;
+
+    runs.finish(input.handle_id, input.mutation_id);
+
+    if let Err(ref e) = result {
+        states
+            .root()
+            .apply_notion(Rc::new(MutationErrorNotion::new::<T, _>(
+                T::classify_error(e),
+                e,
+            )));
+    }
 
     if let Some(m) = input.sender.borrow_mut().take() {
         let _result = m.send(result.clone());
@@ -88,6 +161,272 @@ where
     result
 }
 
+/// Runs an idempotent mutation, reusing the cached result or the run in flight with the same key.
+async fn run_cached_mutation<T>(
+    states: &BounceStates,
+    key: String,
+    input: Rc<T::Input>,
+    progress: ProgressSink,
+) -> MutationResult<T>
+where
+    T: Mutation + 'static,
+{
+    let cache = states.root().get_state::<MutationCache<T>>();
+    let clock = states.root().clock();
+
+    match cache.start(&key, clock.now()) {
+        CacheStart::Cached(m) => return Ok(m),
+        CacheStart::Running(rx) => {
+            if let Ok(m) = rx.await {
+                return m;
+            }
+        }
+        CacheStart::Started => {}
+    }
+
+    let result = mock::run::<T>(states, input, progress).await;
+    cache.complete(key, &result, clock.now() + T::CACHE_DURATION);
+
+    result
+}
+
+/// A run of a mutation that is in flight.
+struct MutationRun {
+    id: MutationId,
+    latest_only: bool,
+    token: AbortToken,
+}
+
+/// The runs of mutations that are in flight, by handle.
+pub(super) struct MutationRuns<T>
+where
+    T: Mutation + 'static,
+{
+    inner: Rc<RefCell<HashMap<HandleId, Vec<MutationRun>>>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> MutationRuns<T>
+where
+    T: Mutation + 'static,
+{
+    /// Registers a run, a latest-only run cancels the previous latest-only runs of the handle.
+    fn start(
+        &self,
+        handle_id: HandleId,
+        mutation_id: MutationId,
+        latest_only: bool,
+        token: AbortToken,
+    ) {
+        let mut inner = self.inner.borrow_mut();
+        let runs = inner.entry(handle_id).or_default();
+
+        if latest_only {
+            for m in runs.iter().filter(|m| m.latest_only) {
+                m.token.abort();
+            }
+            runs.retain(|m| !m.latest_only);
+        }
+
+        runs.push(MutationRun {
+            id: mutation_id,
+            latest_only,
+            token,
+        });
+    }
+
+    fn finish(&self, handle_id: HandleId, mutation_id: MutationId) {
+        let mut inner = self.inner.borrow_mut();
+
+        if let Entry::Occupied(mut m) = inner.entry(handle_id) {
+            m.get_mut().retain(|m| m.id != mutation_id);
+
+            if m.get().is_empty() {
+                m.remove();
+            }
+        }
+    }
+
+    /// Cancels all runs of a handle.
+    pub fn cancel(&self, handle_id: HandleId) {
+        let runs = self.inner.borrow_mut().remove(&handle_id);
+
+        for m in runs.into_iter().flatten() {
+            m.token.abort();
+        }
+    }
+}
+
+impl<T> Default for MutationRuns<T>
+where
+    T: Mutation + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for MutationRuns<T>
+where
+    T: Mutation + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> AnyState for MutationRuns<T>
+where
+    T: Mutation + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+enum CachedMutation<T>
+where
+    T: Mutation + 'static,
+{
+    Running(Vec<oneshot::Sender<MutationResult<T>>>),
+    Completed { value: Rc<T>, expires_at: Duration },
+}
+
+enum CacheStart<T>
+where
+    T: Mutation + 'static,
+{
+    /// An unexpired result is cached.
+    Cached(Rc<T>),
+    /// A mutation with the same key is in flight.
+    Running(oneshot::Receiver<MutationResult<T>>),
+    /// The mutation should be run by the caller.
+    Started,
+}
+
+/// Results of idempotent mutations, keyed by [`Mutation::cache_key`].
+pub(super) struct MutationCache<T>
+where
+    T: Mutation + 'static,
+{
+    inner: Rc<RefCell<HashMap<String, CachedMutation<T>>>>,
+}
+
+impl<T> MutationCache<T>
+where
+    T: Mutation + 'static,
+{
+    fn start(&self, key: &str, now: Duration) -> CacheStart<T> {
+        let mut inner = self.inner.borrow_mut();
+
+        match inner.get_mut(key) {
+            Some(CachedMutation::Completed { value, expires_at }) if now < *expires_at => {
+                CacheStart::Cached(value.clone())
+            }
+            Some(CachedMutation::Running(waiters)) => {
+                let (sender, receiver) = oneshot::channel();
+                waiters.push(sender);
+
+                CacheStart::Running(receiver)
+            }
+            _ => {
+                inner.insert(key.to_owned(), CachedMutation::Running(Vec::new()));
+
+                CacheStart::Started
+            }
+        }
+    }
+
+    fn complete(&self, key: String, result: &MutationResult<T>, expires_at: Duration) {
+        let mut inner = self.inner.borrow_mut();
+
+        // Only successful results are cached.
+        let waiters = match result {
+            Ok(m) => inner.insert(
+                key,
+                CachedMutation::Completed {
+                    value: m.clone(),
+                    expires_at,
+                },
+            ),
+            Err(_) => inner.remove(&key),
+        };
+
+        if let Some(CachedMutation::Running(waiters)) = waiters {
+            for m in waiters {
+                let _result = m.send(result.clone());
+            }
+        }
+    }
+}
+
+impl<T> Default for MutationCache<T>
+where
+    T: Mutation + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T> Clone for MutationCache<T>
+where
+    T: Mutation + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for MutationCache<T>
+where
+    T: Mutation + 'static,
+{
+    fn apply(&self, notion: Rc<dyn std::any::Any>) {
+        if notion.is::<ClearQueryCache>() {
+            self.inner.borrow_mut().clear();
+        }
+    }
+
+    fn notion_ids(&self) -> Vec<std::any::TypeId> {
+        vec![std::any::TypeId::of::<ClearQueryCache>()]
+    }
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A notion that is applied when a mutation reports its progress.
+pub(super) struct MutationProgress<T>
+where
+    T: Mutation,
+{
+    handle_id: HandleId,
+    mutation_id: MutationId,
+    latest_only: bool,
+    progress: f64,
+    _marker: PhantomData<T>,
+}
+
 pub(super) enum MutationSliceAction {
     /// Start tracking a handle.
     Create(HandleId),
@@ -96,13 +435,17 @@ pub(super) enum MutationSliceAction {
 }
 
 #[derive(Slice, Debug)]
-#[bounce(with_notion(Deferred<RunMutation<T>>))]
+#[bounce(with_notion(Deferred<RunMutation<T>>, MutationProgress<T>, ClearQueryCache))]
 pub(super) struct MutationSlice<T>
 where
     T: Mutation + 'static,
 {
     ctr: u64,
     mutations: HashMap<HandleId, MutationSliceValue<T>>,
+    /// The latest run of each handle that only accepts the result of its latest run.
+    latest_runs: HashMap<HandleId, MutationId>,
+    /// Runs started before the cache was last cleared, their results and progress are discarded.
+    cleared_at: Option<MutationId>,
 }
 
 impl<T> PartialEq for MutationSlice<T>
@@ -122,6 +465,8 @@ where
         Self {
             ctr: 0,
             mutations: HashMap::new(),
+            latest_runs: HashMap::new(),
+            cleared_at: None,
         }
     }
 }
@@ -134,6 +479,8 @@ where
         Self {
             ctr: self.ctr,
             mutations: self.mutations.clone(),
+            latest_runs: self.latest_runs.clone(),
+            cleared_at: self.cleared_at,
         }
     }
 }
@@ -156,6 +503,7 @@ where
 
                 Self::Action::Destroy(id) => {
                     this.mutations.remove(&id);
+                    this.latest_runs.remove(&id);
                 }
             }
         }
@@ -169,11 +517,24 @@ where
     T: Mutation + 'static,
 {
     fn apply(mut self: Rc<Self>, notion: Rc<Deferred<RunMutation<T>>>) -> Rc<Self> {
+        if let Deferred::Pending { input } | Deferred::Completed { input, .. } = notion.as_ref() {
+            if self.is_cleared(input.mutation_id) {
+                return self;
+            }
+        }
+
         match notion.as_ref() {
             Deferred::Completed {
                 ref input,
                 ref output,
             } => {
+                // A newer run has been started, so the result of this run is discarded.
+                if input.latest_only
+                    && self.latest_runs.get(&input.handle_id) != Some(&input.mutation_id)
+                {
+                    return self;
+                }
+
                 let this = Rc::make_mut(&mut self);
                 this.ctr += 1;
 
@@ -184,7 +545,7 @@ where
                     Entry::Occupied(mut m) => {
                         let m = m.get_mut();
                         match m {
-                            MutationSliceValue::Loading { id }
+                            MutationSliceValue::Loading { id, .. }
                             | MutationSliceValue::Completed { id, .. }
                             | MutationSliceValue::Outdated { id, .. } => {
                                 // only replace if new id is higher.
@@ -209,6 +570,10 @@ where
                 let this = Rc::make_mut(&mut self);
                 this.ctr += 1;
 
+                if input.latest_only && this.mutations.contains_key(&input.handle_id) {
+                    this.latest_runs.insert(input.handle_id, input.mutation_id);
+                }
+
                 match this.mutations.entry(input.handle_id) {
                     Entry::Vacant(_m) => {
                         return self; // The handle has been destroyed so there's no need to track it any more.
@@ -221,12 +586,14 @@ where
                                 *m = MutationSliceValue::Outdated {
                                     id: *id,
                                     result: result.clone(),
+                                    progress: None,
                                 };
                             }
                             MutationSliceValue::Outdated { .. } => {}
                             MutationSliceValue::Idle => {
                                 *m = MutationSliceValue::Loading {
                                     id: input.mutation_id,
+                                    progress: None,
                                 };
                             }
                         }
@@ -240,6 +607,81 @@ where
     }
 }
 
+impl<T> WithNotion<MutationProgress<T>> for MutationSlice<T>
+where
+    T: Mutation + 'static,
+{
+    fn apply(mut self: Rc<Self>, notion: Rc<MutationProgress<T>>) -> Rc<Self> {
+        if self.is_cleared(notion.mutation_id) {
+            return self;
+        }
+
+        // A newer run has been started, so the progress of this run is discarded.
+        if notion.latest_only
+            && self.latest_runs.get(&notion.handle_id) != Some(&notion.mutation_id)
+        {
+            return self;
+        }
+
+        let current_progress = match self.mutations.get(&notion.handle_id) {
+            Some(MutationSliceValue::Loading { id, progress }) if *id <= notion.mutation_id => {
+                *progress
+            }
+            Some(MutationSliceValue::Outdated { id, progress, .. }) if *id < notion.mutation_id => {
+                *progress
+            }
+            // The run has completed or the handle has been destroyed.
+            _ => return self,
+        };
+
+        if current_progress == Some(notion.progress) {
+            return self;
+        }
+
+        let this = Rc::make_mut(&mut self);
+        this.ctr += 1;
+
+        if let Some(MutationSliceValue::Loading { progress, .. })
+        | Some(MutationSliceValue::Outdated { progress, .. }) =
+            this.mutations.get_mut(&notion.handle_id)
+        {
+            *progress = Some(notion.progress);
+        }
+
+        self
+    }
+}
+
+impl<T> MutationSlice<T>
+where
+    T: Mutation + 'static,
+{
+    fn is_cleared(&self, mutation_id: MutationId) -> bool {
+        self.cleared_at.map(|m| mutation_id < m).unwrap_or(false)
+    }
+}
+
+impl<T> WithNotion<ClearQueryCache> for MutationSlice<T>
+where
+    T: Mutation + 'static,
+{
+    fn apply(self: Rc<Self>, _notion: Rc<ClearQueryCache>) -> Rc<Self> {
+        // Mounted handles are reset and the runs that are still in flight are discarded when
+        // they complete.
+        Self {
+            ctr: self.ctr + 1,
+            mutations: self
+                .mutations
+                .keys()
+                .map(|m| (*m, MutationSliceValue::Idle))
+                .collect(),
+            latest_runs: HashMap::new(),
+            cleared_at: Some(MutationId::default()),
+        }
+        .into()
+    }
+}
+
 #[derive(PartialEq)]
 pub(super) struct MutationSelector<T>
 where
@@ -262,7 +704,7 @@ where
             .cloned();
 
         let id = value.as_ref().and_then(|m| match m {
-            MutationSliceValue::Loading { id }
+            MutationSliceValue::Loading { id, .. }
             | MutationSliceValue::Completed { id, .. }
             | MutationSliceValue::Outdated { id, .. } => Some(*id),
             MutationSliceValue::Idle => None,
@@ -0,0 +1,307 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anymap2::AnyMap;
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use super::polling::HiddenPolling;
+use super::query_states::{QuerySlice, QuerySliceAction, QuerySliceValue, RunQuery, RunQueryInput};
+use super::traits::Query;
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+use crate::states::future_notion::use_internal_future_notion_runner;
+use crate::states::slice::{use_slice_dispatch, SliceState};
+use crate::utils::Id;
+use crate::BounceConfig;
+
+/// The options of a query type.
+///
+/// The defaults of a query are declared with the associated constants of [`Query`] and can be
+/// overridden per query type with [`BounceConfig::with_query_options`].
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use std::time::Duration;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{Query, QueryOptions, QueryResult, SsrPolicy};
+/// # use async_trait::async_trait;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// struct UserQuery {
+///     name: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for UserQuery {
+///     type Input = u64;
+///     type Error = Infallible;
+///
+///     const STALE_TIME: Option<Duration> = Some(Duration::from_secs(30));
+///     const RETRIES: u32 = 2;
+///
+///     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+///         Ok(UserQuery { name: "John Smith".into() }.into())
+///     }
+/// }
+///
+/// let options = QueryOptions::of::<UserQuery>();
+///
+/// assert_eq!(options.stale_time, Some(Duration::from_secs(30)));
+/// assert_eq!(options.retries, 2);
+/// assert!(!options.keep_previous_data);
+/// assert!(options.pooled);
+/// assert_eq!(options.fetch_limit, None);
+/// assert_eq!(options.ssr, SsrPolicy::Block);
+/// assert_eq!(options.poll_interval, None);
+/// assert_eq!(options.timeout, None);
+/// assert!(!options.refresh_on_restore);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// The duration after which a completed result is refreshed when a hook is mounted or its
+    /// input changes.
+    ///
+    /// See: [`Query::STALE_TIME`]
+    pub stale_time: Option<Duration>,
+    /// The number of times a failed query is run again before its error is returned.
+    ///
+    /// See: [`Query::RETRIES`]
+    pub retries: u32,
+    /// Whether hooks keep the result of the previous input while the result of a new input is
+    /// loading.
+    ///
+    /// See: [`Query::KEEP_PREVIOUS_DATA`]
+    pub keep_previous_data: bool,
+    /// Whether fetches wait for a slot of the fetch pool of the root.
+    ///
+    /// See: [`Query::POOLED`]
+    pub pooled: bool,
+    /// The maximum number of fetches of this query type that run at the same time.
+    ///
+    /// See: [`Query::FETCH_LIMIT`]
+    pub fetch_limit: Option<usize>,
+    /// Whether the server-side rendering waits for the result.
+    ///
+    /// See: [`Query::SSR`]
+    pub ssr: SsrPolicy,
+    /// The interval at which mounted queries are fetched again.
+    ///
+    /// See: [`Query::POLL_INTERVAL`]
+    pub poll_interval: Option<Duration>,
+    /// How queries are polled while the page is hidden.
+    ///
+    /// See: [`Query::HIDDEN_POLLING`]
+    pub hidden_polling: HiddenPolling,
+    /// The duration after which a run is cancelled.
+    ///
+    /// See: [`Query::TIMEOUT`]
+    pub timeout: Option<Duration>,
+    /// Whether mounted queries are fetched again when the page is restored from the
+    /// back/forward cache.
+    ///
+    /// See: [`Query::REFRESH_ON_RESTORE`]
+    pub refresh_on_restore: bool,
+}
+
+/// Whether the server-side rendering waits for the result of a query.
+///
+/// See: [`Query::SSR`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SsrPolicy {
+    /// The query is fetched during server-side rendering and the result is embedded in the
+    /// rendered html.
+    #[default]
+    Block,
+    /// The query is not fetched during server-side rendering.
+    ///
+    /// The server renders the query as loading and the query is fetched after the application
+    /// is hydrated.
+    Skip,
+}
+
+impl QueryOptions {
+    /// Returns the options declared by a query type.
+    pub fn of<T>() -> Self
+    where
+        T: Query,
+    {
+        Self {
+            stale_time: T::STALE_TIME,
+            retries: T::RETRIES,
+            keep_previous_data: T::KEEP_PREVIOUS_DATA,
+            pooled: T::POOLED,
+            fetch_limit: T::FETCH_LIMIT,
+            ssr: T::SSR,
+            poll_interval: T::POLL_INTERVAL,
+            hidden_polling: T::HIDDEN_POLLING,
+            timeout: T::TIMEOUT,
+            refresh_on_restore: T::REFRESH_ON_RESTORE,
+        }
+    }
+
+    /// Returns the options of a query type in a root, with the overrides of its [`BounceConfig`].
+    pub(super) fn resolve<T>(root: &BounceRootState) -> Self
+    where
+        T: Query + 'static,
+    {
+        root.get_service::<BounceConfig>()
+            .and_then(|m| m.query_options::<T>())
+            .unwrap_or_else(Self::of::<T>)
+    }
+}
+
+/// Returns the options of a query type in the current root.
+#[hook]
+pub(super) fn use_query_options<T>() -> QueryOptions
+where
+    T: Query + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    *use_memo((), move |_| QueryOptions::resolve::<T>(&root))
+}
+
+/// Refreshes the result of a query if it is stale when the hook is mounted or its input changes.
+///
+/// The cached result is served while it is refreshing in the background.
+#[hook]
+pub(super) fn use_refresh_stale<T>(options: QueryOptions, input: Option<Rc<T::Input>>)
+where
+    T: Query + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let dispatch_state = use_slice_dispatch::<QuerySlice<T>>();
+    let run_query = use_internal_future_notion_runner::<RunQuery<T>>();
+
+    use_effect_with(input, move |input| {
+        if let Some(input) = input {
+            // The state is read when the effect runs, so only the first of the hooks that are
+            // mounted at the same time refreshes the result, the others see that it is refreshing.
+            let is_completed = matches!(
+                root.get_state::<SliceState<QuerySlice<T>>>()
+                    .get()
+                    .get(input),
+                Some(QuerySliceValue::Completed { .. })
+            );
+
+            let is_stale = match options.stale_time {
+                Some(m) if is_completed => {
+                    root.get_state::<QueryTimestamps<T>>()
+                        .is_stale(input, root.clock().now(), m)
+                }
+                _ => false,
+            };
+
+            if is_stale {
+                let id = Id::new();
+                dispatch_state(QuerySliceAction::Refresh {
+                    id,
+                    input: input.clone(),
+                });
+
+                run_query(RunQueryInput {
+                    id,
+                    input: input.clone(),
+                    sender: Rc::default(),
+                    is_refresh: true,
+                });
+            }
+        }
+
+        || {}
+    });
+}
+
+/// The timing of the last fetch of a query.
+#[derive(Debug, Clone, Copy)]
+struct QueryTiming {
+    completed_at: Duration,
+    duration: Duration,
+}
+
+/// The timings of the last fetches of queries.
+///
+/// Results that are not fetched in this root (e.g.: prepared results) do not have a timing and
+/// never become stale.
+pub(super) struct QueryTimestamps<T>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<HashMap<Rc<T::Input>, QueryTiming>>>,
+}
+
+impl<T> QueryTimestamps<T>
+where
+    T: Query + 'static,
+{
+    /// Records that the fetch of a query that has started at `started_at` has completed at
+    /// `now`.
+    pub fn record(&self, input: Rc<T::Input>, started_at: Duration, now: Duration) {
+        self.inner.borrow_mut().insert(
+            input,
+            QueryTiming {
+                completed_at: now,
+                duration: now.saturating_sub(started_at),
+            },
+        );
+    }
+
+    /// Returns the time that the result of a query has completed, if any.
+    pub fn completed_at(&self, input: &T::Input) -> Option<Duration> {
+        self.inner.borrow().get(input).map(|m| m.completed_at)
+    }
+
+    /// Returns the duration of the last fetch of a query, if any.
+    pub fn duration(&self, input: &T::Input) -> Option<Duration> {
+        self.inner.borrow().get(input).map(|m| m.duration)
+    }
+
+    /// Returns `true` if the result of a query has completed for at least `stale_time`.
+    pub fn is_stale(&self, input: &T::Input, now: Duration, stale_time: Duration) -> bool {
+        self.completed_at(input)
+            .map(|m| now.saturating_sub(m) >= stale_time)
+            .unwrap_or(false)
+    }
+}
+
+impl<T> Default for QueryTimestamps<T>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T> Clone for QueryTimestamps<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for QueryTimestamps<T>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
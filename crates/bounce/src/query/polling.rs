@@ -0,0 +1,338 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anymap2::AnyMap;
+use futures::channel::mpsc;
+use futures::future::{select, Either};
+use futures::StreamExt;
+use gloo::events::EventListener;
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, VisibilityState};
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use super::options::QueryOptions;
+use super::query_states::{QuerySlice, QuerySliceAction, RunQuery, RunQueryInput};
+use super::traits::Query;
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+use crate::states::future_notion::run_future_notion;
+use crate::states::slice::SliceState;
+use crate::utils::Id;
+
+type PollerMap<T> = HashMap<Rc<<T as Query>::Input>, (usize, Poller)>;
+
+/// How a query is polled while the page is hidden (e.g.: in a background tab).
+///
+/// When the page becomes visible again, the query is fetched immediately and polled with its
+/// regular interval.
+///
+/// See: [`Query::POLL_INTERVAL`]
+///
+/// # Example
+///
+/// ```
+/// # use std::convert::Infallible;
+/// # use std::rc::Rc;
+/// # use std::time::Duration;
+/// # use async_trait::async_trait;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{HiddenPolling, Query, QueryResult};
+/// #
+/// #[derive(Debug, PartialEq)]
+/// struct Notifications {
+///     unread: u64,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for Notifications {
+///     type Input = ();
+///     type Error = Infallible;
+///
+///     const POLL_INTERVAL: Option<Duration> = Some(Duration::from_secs(30));
+///     // Polled at most every 10 minutes in background tabs.
+///     const HIDDEN_POLLING: HiddenPolling = HiddenPolling::Backoff {
+///         max: Duration::from_secs(600),
+///     };
+///
+///     async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+///         // fetch notifications...
+/// #       Ok(Notifications { unread: 0 }.into())
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HiddenPolling {
+    /// The query is not polled while the page is hidden.
+    #[default]
+    Pause,
+    /// The interval doubles after each poll while the page is hidden, up to `max`.
+    Backoff {
+        /// The maximum interval.
+        max: Duration,
+    },
+    /// The query is polled with its regular interval while the page is hidden.
+    Continue,
+}
+
+/// Returns the document, if the page has one.
+fn document() -> Option<Document> {
+    // There is no document on the server.
+    if cfg!(not(target_arch = "wasm32")) {
+        return None;
+    }
+
+    web_sys::window().and_then(|m| m.document())
+}
+
+fn is_visible(document: &Option<Document>) -> bool {
+    document
+        .as_ref()
+        .map(|m| m.visibility_state() != VisibilityState::Hidden)
+        .unwrap_or(true)
+}
+
+/// A running poll of a query, stopped when dropped.
+struct Poller {
+    cancelled: Rc<Cell<bool>>,
+    // Dropping the sender closes the channel of visibility changes, so a paused poll stops.
+    _visibility_changes: mpsc::UnboundedSender<()>,
+    _listener: Option<EventListener>,
+}
+
+impl Poller {
+    fn start<T>(
+        root: BounceRootState,
+        input: Rc<T::Input>,
+        interval: Duration,
+        hidden_polling: HiddenPolling,
+    ) -> Self
+    where
+        T: Query + 'static,
+    {
+        let cancelled = Rc::new(Cell::new(false));
+        let document = document();
+
+        // The listener sends a message each time the visibility changes.
+        let (tx, mut visibility_changes) = mpsc::unbounded::<()>();
+        let listener = document.as_ref().map(|m| {
+            let tx = tx.clone();
+            EventListener::new(m, "visibilitychange", move |_| {
+                let _ = tx.unbounded_send(());
+            })
+        });
+
+        {
+            let cancelled = cancelled.clone();
+            let mut hidden_interval = interval;
+
+            spawn_local(async move {
+                loop {
+                    let delay = match (is_visible(&document), hidden_polling) {
+                        (true, _) | (false, HiddenPolling::Continue) => Some(interval),
+                        (false, HiddenPolling::Pause) => None,
+                        (false, HiddenPolling::Backoff { max }) => {
+                            hidden_interval = (hidden_interval * 2).min(max);
+                            Some(hidden_interval)
+                        }
+                    };
+
+                    let visibility_changed = match delay {
+                        Some(m) => {
+                            match select(root.clock().sleep(m), visibility_changes.next()).await {
+                                Either::Left(_) => false,
+                                Either::Right((Some(_), _)) => true,
+                                Either::Right((None, _)) => return,
+                            }
+                        }
+                        None => match visibility_changes.next().await {
+                            Some(_) => true,
+                            None => return,
+                        },
+                    };
+
+                    if cancelled.get() {
+                        return;
+                    }
+
+                    if visibility_changed {
+                        // The page has become hidden, the delay is calculated again.
+                        if !is_visible(&document) {
+                            continue;
+                        }
+
+                        // The query is fetched immediately when the page becomes visible.
+                        hidden_interval = interval;
+                    }
+
+                    let id = Id::new();
+                    root.get_state::<SliceState<QuerySlice<T>>>().dispatch(
+                        QuerySliceAction::Refresh {
+                            id,
+                            input: input.clone(),
+                        },
+                    );
+
+                    run_future_notion::<RunQuery<T>>(
+                        root.clone(),
+                        RunQueryInput {
+                            id,
+                            input: input.clone(),
+                            sender: Rc::default(),
+                            is_refresh: true,
+                        }
+                        .into(),
+                    );
+                }
+            });
+        }
+
+        Self {
+            cancelled,
+            _visibility_changes: tx,
+            _listener: listener,
+        }
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.cancelled.set(true);
+    }
+}
+
+/// The pollers of a query type, by input.
+///
+/// Hooks that poll the same input share one poller, which is stopped when the last of them
+/// stops polling.
+struct Pollers<T>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<PollerMap<T>>>,
+}
+
+impl<T> Pollers<T>
+where
+    T: Query + 'static,
+{
+    /// Starts polling the input, unless it is already polled, and returns a handle that stops
+    /// polling when the last handle of the input is dropped.
+    fn subscribe(
+        &self,
+        root: &BounceRootState,
+        input: Rc<T::Input>,
+        interval: Duration,
+        hidden_polling: HiddenPolling,
+    ) -> PollerHandle<T> {
+        self.inner
+            .borrow_mut()
+            .entry(input.clone())
+            .and_modify(|(count, _)| *count += 1)
+            .or_insert_with(|| {
+                (
+                    1,
+                    Poller::start::<T>(root.clone(), input.clone(), interval, hidden_polling),
+                )
+            });
+
+        PollerHandle {
+            pollers: self.clone(),
+            input,
+        }
+    }
+}
+
+impl<T> Default for Pollers<T>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T> Clone for Pollers<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for Pollers<T>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A subscription of a hook to the poller of an input.
+struct PollerHandle<T>
+where
+    T: Query + 'static,
+{
+    pollers: Pollers<T>,
+    input: Rc<T::Input>,
+}
+
+impl<T> Drop for PollerHandle<T>
+where
+    T: Query + 'static,
+{
+    fn drop(&mut self) {
+        let poller = {
+            let mut inner = self.pollers.inner.borrow_mut();
+
+            match inner.get_mut(&self.input) {
+                Some((count, _)) if *count > 1 => {
+                    *count -= 1;
+                    None
+                }
+                Some(_) => inner.remove(&self.input),
+                None => None,
+            }
+        };
+
+        // The poller is dropped after the map is released.
+        drop(poller);
+    }
+}
+
+/// Polls a query with the interval of its options while the hook is mounted.
+///
+/// Hooks that poll the same input share a poller, which uses the options of the first hook.
+/// Polling restarts when the input changes.
+#[hook]
+pub(super) fn use_polling<T>(options: QueryOptions, input: Option<Rc<T::Input>>)
+where
+    T: Query + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    use_effect_with(
+        (input, options.poll_interval, options.hidden_polling),
+        move |(input, interval, hidden_polling)| {
+            let handle = input.clone().zip(*interval).map(|(input, m)| {
+                root.get_state::<Pollers<T>>()
+                    .subscribe(&root, input, m, *hidden_polling)
+            });
+
+            move || drop(handle)
+        },
+    );
+}
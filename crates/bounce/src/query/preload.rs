@@ -0,0 +1,77 @@
+#[cfg(feature = "ssr")]
+use std::collections::BTreeSet;
+#[cfg(feature = "ssr")]
+use std::rc::Rc;
+
+#[cfg(feature = "ssr")]
+use crate::root_state::BounceRootState;
+#[cfg(feature = "ssr")]
+use crate::states::atom::AtomSlice;
+#[cfg(feature = "ssr")]
+use crate::states::slice::SliceState;
+#[cfg(feature = "ssr")]
+use crate::Atom;
+
+/// A resource that the client will request for the result of a query.
+///
+/// Prepared queries executed during server-side rendering report their hints with
+/// [`Query::preload_hints`](super::Query::preload_hints). With the `helmet` feature, the hints
+/// are rendered by the `StaticRenderer` as `<link rel="preload" ... />` tags.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PreloadHint {
+    /// The URL of the resource.
+    pub href: String,
+    /// The type of the resource, written as the `as` attribute (e.g.: `fetch`, `image`).
+    pub destination: String,
+    /// Whether the resource is requested in CORS mode.
+    ///
+    /// This must match how the resource is requested, or the preloaded response is not reused.
+    pub crossorigin: bool,
+}
+
+impl PreloadHint {
+    /// Creates a hint for a resource of the type `destination`.
+    pub fn new(href: impl Into<String>, destination: impl Into<String>) -> Self {
+        Self {
+            href: href.into(),
+            destination: destination.into(),
+            crossorigin: false,
+        }
+    }
+
+    /// Creates a hint for a resource requested with `fetch()`.
+    ///
+    /// `fetch()` requests are sent in CORS mode, so the hint is marked as `crossorigin`.
+    pub fn fetch(href: impl Into<String>) -> Self {
+        Self {
+            crossorigin: true,
+            ..Self::new(href, "fetch")
+        }
+    }
+}
+
+/// The hints of prepared queries executed during server-side rendering.
+#[cfg(feature = "ssr")]
+#[derive(Atom, Default, PartialEq)]
+pub(crate) struct PreloadHints {
+    pub inner: Rc<BTreeSet<PreloadHint>>,
+}
+
+/// Records the hints of a query executed during server-side rendering.
+#[cfg(feature = "ssr")]
+pub(super) fn record_preload_hints(root: &BounceRootState, hints: Vec<PreloadHint>) {
+    if hints.is_empty() {
+        return;
+    }
+
+    let state = root.get_state::<SliceState<AtomSlice<PreloadHints>>>();
+    let mut inner = (*state.get().inner.inner).clone();
+    let len = inner.len();
+    inner.extend(hints);
+
+    if inner.len() != len {
+        state.dispatch(PreloadHints {
+            inner: inner.into(),
+        });
+    }
+}
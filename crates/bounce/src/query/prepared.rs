@@ -0,0 +1,295 @@
+//! The value of a prepared query that is transferred from the server to the client.
+//!
+//! By default, the result is serialised by the prepared state mechanism of Yew as is.
+//!
+//! With the `prepared-codec` feature or a [`PreparedTransform`] configured on the root, the
+//! result is encoded with postcard, a compact binary format, before it is passed to Yew. With the
+//! `prepared-compression` feature, large payloads are also compressed with deflate. The encoded
+//! payload is then transformed (e.g.: signed or encrypted) with the transform of the root.
+//!
+//! The payload contains the input of the query, so the client pairs results with queries by their
+//! inputs rather than by the order they are rendered in. The encoded payload also contains the
+//! type of the query, a payload of another query is discarded instead of failing to decode.
+
+use std::fmt;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use super::traits::Query;
+use crate::root_state::BounceRootState;
+use crate::BounceConfig;
+
+/// The input and the result of a query.
+type PreparedEntry<T> = (<T as Query>::Input, Result<T, <T as Query>::Error>);
+
+/// A transform applied to the encoded payloads of prepared queries.
+///
+/// The result of a prepared query is embedded into the server-side rendered html. A transform
+/// can sign or encrypt the payload (e.g.: with a key of the tenant of the request) so that the
+/// data embedded in the html cannot be read or altered by others.
+///
+/// The transform is configured on the root with
+/// [`BounceConfig::with_prepared_transform`](crate::BounceConfig::with_prepared_transform).
+/// [`encode`](Self::encode) is applied on the server after the payload is encoded (and
+/// compressed) and [`decode`](Self::decode) is applied on the client before it is decoded, so the
+/// server and the client must be configured with matching transforms. When a transform is
+/// configured, payloads that have not been transformed are discarded on the client.
+///
+/// Results are encoded with postcard when a transform is configured, the `prepared-codec`
+/// feature is not required.
+///
+/// # Example
+///
+/// ```
+/// use bounce::query::PreparedTransform;
+///
+/// /// Masks the payload with a key (use a real cipher in your application).
+/// struct XorTransform {
+///     key: u8,
+/// }
+///
+/// impl PreparedTransform for XorTransform {
+///     fn encode(&self, payload: Vec<u8>) -> Vec<u8> {
+///         payload.into_iter().map(|m| m ^ self.key).collect()
+///     }
+///
+///     fn decode(&self, payload: Vec<u8>) -> Option<Vec<u8>> {
+///         Some(self.encode(payload))
+///     }
+/// }
+///
+/// let transform = XorTransform { key: 0x5a };
+/// let payload = vec![1, 2, 3];
+///
+/// assert_eq!(transform.decode(transform.encode(payload.clone())), Some(payload));
+/// ```
+pub trait PreparedTransform {
+    /// Transforms an encoded payload on the server.
+    fn encode(&self, payload: Vec<u8>) -> Vec<u8>;
+
+    /// Restores a payload transformed by [`encode`](Self::encode) on the client.
+    ///
+    /// Returns `None` if the payload cannot be restored (e.g.: the signature does not match). The
+    /// prepared result is then discarded and the query is fetched on the client.
+    fn decode(&self, payload: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// The transform configured on a root.
+#[derive(Clone, Default)]
+pub(crate) struct PreparedTransformConfig {
+    inner: Option<Rc<dyn PreparedTransform>>,
+}
+
+impl PreparedTransformConfig {
+    pub fn new(transform: Rc<dyn PreparedTransform>) -> Self {
+        Self {
+            inner: Some(transform),
+        }
+    }
+}
+
+impl fmt::Debug for PreparedTransformConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreparedTransformConfig")
+            .field("configured", &self.inner.is_some())
+            .finish()
+    }
+}
+
+fn prepared_transform(root: &BounceRootState) -> Option<Rc<dyn PreparedTransform>> {
+    root.get_service::<BounceConfig>()
+        .and_then(|m| m.prepared_transform().inner.clone())
+}
+
+/// The result of a prepared query.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize, T::Input: Serialize, T::Error: Serialize",
+    deserialize = "T: Deserialize<'de>, T::Input: Deserialize<'de>, T::Error: Deserialize<'de>"
+))]
+pub(super) enum Prepared<T>
+where
+    T: Query,
+{
+    /// The input and the result of the query, serialised by Yew as is.
+    ///
+    /// This is `None` if no result is prepared.
+    Value(Option<PreparedEntry<T>>),
+    /// The input and the result of the query encoded with postcard, then compressed and
+    /// transformed.
+    Encoded(Vec<u8>),
+}
+
+impl<T> Prepared<T>
+where
+    T: Query + Clone + Serialize + for<'de> Deserialize<'de>,
+    T::Input: Clone + Serialize + for<'de> Deserialize<'de>,
+    T::Error: Clone + Serialize + for<'de> Deserialize<'de>,
+{
+    /// Prepares the input and the result of a query with the transform of the root.
+    ///
+    /// The result is encoded if the `prepared-codec` feature is enabled or the root has a
+    /// transform.
+    #[cfg(feature = "ssr")]
+    pub fn new(root: &BounceRootState, inner: Option<PreparedEntry<T>>) -> Self {
+        let transform = prepared_transform(root);
+
+        if cfg!(not(feature = "prepared-codec")) && transform.is_none() {
+            return Self::Value(inner);
+        }
+
+        let payload = match postcard::to_allocvec(&(std::any::type_name::<T>(), &inner)) {
+            Ok(m) => codec::compress(m),
+            // An empty payload fails to decode, the query is fetched on the client instead.
+            Err(e) => {
+                tracing::warn!(
+                    "failed to encode the prepared result of {}: {}",
+                    std::any::type_name::<T>(),
+                    e
+                );
+                Vec::new()
+            }
+        };
+
+        Self::Encoded(match transform {
+            Some(m) => m.encode(payload),
+            None => payload,
+        })
+    }
+
+    /// Returns the input and the result of the query, decoded with the transform of the root.
+    ///
+    /// This is `None` if no result is prepared or the payload cannot be decoded as a result of
+    /// this query.
+    pub fn decode(&self, root: &BounceRootState) -> Option<PreparedEntry<T>> {
+        let payload = match (self, prepared_transform(root)) {
+            (Self::Value(m), None) => return m.clone(),
+            (Self::Value(_), Some(_)) => {
+                tracing::warn!(
+                    "discarded a prepared payload of {} that has not been transformed",
+                    std::any::type_name::<T>()
+                );
+                return None;
+            }
+            (Self::Encoded(m), Some(transform)) => match transform.decode(m.clone()) {
+                Some(m) => m,
+                None => {
+                    tracing::warn!(
+                        "discarded a prepared payload of {} that cannot be restored by the \
+                         prepared transform",
+                        std::any::type_name::<T>()
+                    );
+                    return None;
+                }
+            },
+            (Self::Encoded(m), None) => m.clone(),
+        };
+
+        decode_payload::<T>(payload)
+    }
+}
+
+/// Decodes a payload encoded by [`Prepared::new`] after it has been restored by the transform.
+fn decode_payload<T>(payload: Vec<u8>) -> Option<PreparedEntry<T>>
+where
+    T: Query + for<'de> Deserialize<'de>,
+    T::Input: for<'de> Deserialize<'de>,
+    T::Error: for<'de> Deserialize<'de>,
+{
+    let bytes = match codec::decompress(payload) {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::warn!(
+                "discarded a prepared payload of {}: {}",
+                std::any::type_name::<T>(),
+                e
+            );
+            return None;
+        }
+    };
+
+    match postcard::from_bytes::<(String, _)>(&bytes) {
+        Ok((name, inner)) if name == std::any::type_name::<T>() => inner,
+        // The payload is prepared for another query, the query is fetched instead.
+        _ => {
+            tracing::warn!(
+                "discarded a prepared payload that is not a result of {}",
+                std::any::type_name::<T>()
+            );
+            None
+        }
+    }
+}
+
+impl<T> PartialEq for Prepared<T>
+where
+    T: Query,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Value(lhs), Self::Value(rhs)) => lhs == rhs,
+            (Self::Encoded(lhs), Self::Encoded(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+mod codec {
+    /// The payload is stored as is.
+    const RAW: u8 = 0;
+
+    /// The payload is compressed with deflate.
+    #[cfg(feature = "prepared-compression")]
+    const DEFLATE: u8 = 1;
+
+    /// Payloads smaller than this size are not compressed.
+    #[cfg(all(feature = "ssr", feature = "prepared-compression"))]
+    const COMPRESSION_THRESHOLD: usize = 512;
+
+    #[cfg(all(feature = "ssr", feature = "prepared-compression"))]
+    pub(super) fn compress(bytes: Vec<u8>) -> Vec<u8> {
+        use miniz_oxide::deflate::compress_to_vec;
+
+        if bytes.len() >= COMPRESSION_THRESHOLD {
+            let compressed = compress_to_vec(&bytes, 6);
+
+            if compressed.len() < bytes.len() {
+                let mut payload = Vec::with_capacity(compressed.len() + 1);
+                payload.push(DEFLATE);
+                payload.extend(compressed);
+
+                return payload;
+            }
+        }
+
+        let mut payload = Vec::with_capacity(bytes.len() + 1);
+        payload.push(RAW);
+        payload.extend(bytes);
+
+        payload
+    }
+
+    #[cfg(all(feature = "ssr", not(feature = "prepared-compression")))]
+    pub(super) fn compress(bytes: Vec<u8>) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(bytes.len() + 1);
+        payload.push(RAW);
+        payload.extend(bytes);
+
+        payload
+    }
+
+    pub(super) fn decompress(mut payload: Vec<u8>) -> Result<Vec<u8>, &'static str> {
+        if payload.is_empty() {
+            return Err("prepared payload is empty");
+        }
+
+        match payload.remove(0) {
+            RAW => Ok(payload),
+            #[cfg(feature = "prepared-compression")]
+            DEFLATE => miniz_oxide::inflate::decompress_to_vec(&payload)
+                .map_err(|_| "failed to decompress prepared payload"),
+            _ => Err("unsupported prepared payload encoding"),
+        }
+    }
+}
@@ -0,0 +1,487 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use super::options::QueryTimestamps;
+use super::query_states::{
+    QuerySlice, QuerySliceAction, QuerySliceValue, QueryWaiters, RunQuery, RunQueryInput,
+};
+use super::subscriptions::{QuerySubscription, QuerySubscriptionRegistry};
+use super::traits::{Query, QueryResult};
+#[cfg(feature = "query-export")]
+use super::QueryCacheSeed;
+use crate::idle::IdleScheduler;
+use crate::root_state::BounceRootState;
+use crate::states::future_notion::run_future_notion;
+use crate::states::slice::SliceState;
+use crate::utils::Id;
+
+/// A future returned by [`QueryClient::fetch_query`].
+///
+/// This future can be cloned, all clones resolve to the same result.
+pub struct QueryFetch<T>
+where
+    T: Query + 'static,
+{
+    inner: Shared<LocalBoxFuture<'static, QueryResult<T>>>,
+}
+
+impl<T> Future for QueryFetch<T>
+where
+    T: Query + 'static,
+{
+    type Output = QueryResult<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx)
+    }
+}
+
+impl<T> Clone for QueryFetch<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for QueryFetch<T>
+where
+    T: Query + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryFetch").finish_non_exhaustive()
+    }
+}
+
+/// A notion to clear all cached query results and reset mutation handles to idle.
+///
+/// Mounted queries are fetched again. Results and progress of mutations that are still in flight
+/// are discarded when they arrive.
+///
+/// This notion is applied by [`QueryClient::clear_cache`] and when the user logs out with the
+/// `auth` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearQueryCache;
+
+/// A client to access queries imperatively.
+///
+/// See: [`use_query_client`]
+#[derive(Clone, PartialEq)]
+pub struct QueryClient {
+    root: BounceRootState,
+}
+
+impl QueryClient {
+    pub(crate) fn new(root: BounceRootState) -> Self {
+        Self { root }
+    }
+
+    /// Fetches a query.
+    ///
+    /// - If a query with the same input is in flight (e.g.: started by [`use_query`](super::use_query())),
+    ///   the returned future resolves to the result of that query instead of fetching again.
+    /// - If a completed result is cached, the returned future resolves to the cached result.
+    /// - Otherwise, the query is fetched and the result is stored in the cache.
+    pub fn fetch_query<T>(&self, input: impl Into<Rc<T::Input>>) -> QueryFetch<T>
+    where
+        T: Query + 'static,
+    {
+        let input = input.into();
+        let waiters = self.root.get_state::<QueryWaiters<T>>();
+
+        if !waiters.is_in_flight(&input) {
+            let slice = self.root.get_state::<SliceState<QuerySlice<T>>>();
+            let value = slice.get().get(&input).cloned();
+
+            let (id, is_refresh) = match value {
+                Some(QuerySliceValue::Completed { result, .. }) => {
+                    return QueryFetch {
+                        inner: futures::future::ready(result).boxed_local().shared(),
+                    };
+                }
+                // The result is outdated, so the query is refreshed.
+                Some(_) => {
+                    let id = Id::new();
+                    slice.dispatch(QuerySliceAction::Refresh {
+                        id,
+                        input: input.clone(),
+                    });
+
+                    (id, true)
+                }
+                None => (Id::new(), false),
+            };
+
+            let receiver = waiters.wait(input.clone());
+
+            run_future_notion::<RunQuery<T>>(
+                self.root.clone(),
+                RunQueryInput {
+                    id,
+                    input,
+                    sender: Rc::default(),
+                    is_refresh,
+                }
+                .into(),
+            );
+
+            return QueryFetch {
+                inner: async move { receiver.await.expect_throw("query has been dropped.") }
+                    .boxed_local()
+                    .shared(),
+            };
+        }
+
+        let receiver = waiters.wait(input);
+
+        QueryFetch {
+            inner: async move { receiver.await.expect_throw("query has been dropped.") }
+                .boxed_local()
+                .shared(),
+        }
+    }
+
+    /// Prefetches a query when the browser is idle.
+    ///
+    /// The query is fetched with [`fetch_query`](Self::fetch_query) by the
+    /// [`IdleScheduler`](crate::IdleScheduler) of the bounce root, so a completed result that is
+    /// cached by then is not fetched again and an outdated result is refreshed.
+    pub fn prefetch_query_when_idle<T>(&self, input: impl Into<Rc<T::Input>>)
+    where
+        T: Query + 'static,
+    {
+        let input = input.into();
+        let this = self.clone();
+
+        IdleScheduler::new(self.root.clone()).schedule(move || {
+            // The query runs in the background, the result is stored in the cache.
+            this.fetch_query::<T>(input);
+        });
+    }
+
+    /// Returns the cached result of a query (if any).
+    ///
+    /// The result is read synchronously and the query is not fetched, so this can be used to
+    /// branch during rendering (e.g.: to skip a loading placeholder when the result is cached).
+    /// If the query is being refreshed, the result of the last completed run is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{use_query_client, Query, QueryResult};
+    /// # use yew::prelude::*;
+    /// # use async_trait::async_trait;
+    /// #
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct UserQuery {
+    /// #     name: String,
+    /// # }
+    /// #
+    /// # #[async_trait(?Send)]
+    /// # impl Query for UserQuery {
+    /// #     type Input = u64;
+    /// #     type Error = Infallible;
+    /// #
+    /// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+    /// #         Ok(UserQuery { name: "John Smith".into() }.into())
+    /// #     }
+    /// # }
+    /// #
+    /// #[function_component(UserName)]
+    /// fn user_name() -> Html {
+    ///     let client = use_query_client();
+    ///
+    ///     match client.read_cached::<UserQuery>(1) {
+    ///         Some(Ok(m)) => html! { <span>{&m.name}</span> },
+    ///         Some(Err(_)) | None => html! { <span>{"Unknown user"}</span> },
+    ///     }
+    /// }
+    /// ```
+    pub fn read_cached<T>(&self, input: impl Into<Rc<T::Input>>) -> Option<QueryResult<T>>
+    where
+        T: Query + 'static,
+    {
+        let input = input.into();
+
+        match self
+            .root
+            .get_state::<SliceState<QuerySlice<T>>>()
+            .get()
+            .get(&input)?
+        {
+            QuerySliceValue::Completed { result, .. }
+            | QuerySliceValue::Outdated { result, .. } => Some(result.clone()),
+            QuerySliceValue::Loading { .. } => None,
+        }
+    }
+
+    /// Returns the time that the cached result of a query has completed, measured by the
+    /// [`Clock`](crate::Clock) of the root.
+    ///
+    /// This is the time that [`Query::STALE_TIME`] is measured from. Results that are not fetched
+    /// in this root (e.g.: prepared results) do not have a completion time.
+    pub fn updated_at<T>(&self, input: impl Into<Rc<T::Input>>) -> Option<Duration>
+    where
+        T: Query + 'static,
+    {
+        self.root
+            .get_state::<QueryTimestamps<T>>()
+            .completed_at(&input.into())
+    }
+
+    /// Returns the duration of the last fetch of a query, measured by the
+    /// [`Clock`](crate::Clock) of the root.
+    ///
+    /// The duration includes retries, but not the time spent waiting for a slot of the
+    /// [fetch pool](crate::BounceConfig::with_fetch_pool_limit).
+    pub fn fetch_duration<T>(&self, input: impl Into<Rc<T::Input>>) -> Option<Duration>
+    where
+        T: Query + 'static,
+    {
+        self.root
+            .get_state::<QueryTimestamps<T>>()
+            .duration(&input.into())
+    }
+
+    /// Returns the queries subscribed to by mounted query hooks, ordered by query type and input.
+    ///
+    /// [`use_query`](super::use_query()), [`use_query_value`](super::use_query_value()) and their
+    /// prepared variants subscribe while they are mounted.
+    ///
+    /// Hooks with the same query and input share a subscription. This can be used to confirm
+    /// that requests are deduplicated and to find duplicate subscriptions with slightly different
+    /// inputs.
+    ///
+    /// See also: [`QuerySubscriptionsOverlay`](super::QuerySubscriptionsOverlay)
+    pub fn subscriptions(&self) -> Vec<QuerySubscription> {
+        self.root
+            .get_state::<QuerySubscriptionRegistry>()
+            .list(&self.root)
+    }
+
+    /// Returns the inputs of a query type that have a cached result, in no particular order.
+    ///
+    /// Queries that are loading for the first time do not have a cached result and are not
+    /// included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{use_query_client, Query, QueryResult};
+    /// # use yew::prelude::*;
+    /// # use async_trait::async_trait;
+    /// #
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct ProjectQuery {
+    /// #     name: String,
+    /// # }
+    /// #
+    /// # #[async_trait(?Send)]
+    /// # impl Query for ProjectQuery {
+    /// #     type Input = u64;
+    /// #     type Error = Infallible;
+    /// #
+    /// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+    /// #         Ok(ProjectQuery { name: format!("Project {}", input) }.into())
+    /// #     }
+    /// # }
+    /// #
+    /// #[function_component(ClearProjects)]
+    /// fn clear_projects() -> Html {
+    ///     let client = use_query_client();
+    ///
+    ///     let onclick = Callback::from(move |_| {
+    ///         for input in client.cached_inputs::<ProjectQuery>() {
+    ///             client.remove::<ProjectQuery>(input);
+    ///         }
+    ///     });
+    ///
+    ///     html! { <button {onclick}>{"Clear project data"}</button> }
+    /// }
+    /// ```
+    pub fn cached_inputs<T>(&self) -> Vec<Rc<T::Input>>
+    where
+        T: Query + 'static,
+    {
+        self.root
+            .get_state::<SliceState<QuerySlice<T>>>()
+            .get()
+            .cached_inputs()
+    }
+
+    /// Removes the cached result of a query.
+    ///
+    /// Components that are using the query with this input fetch it again, components that are
+    /// using [`use_query`](super::use_query()) suspend until it is fetched. If the query is in
+    /// flight, its result is stored in the cache when it completes.
+    pub fn remove<T>(&self, input: impl Into<Rc<T::Input>>)
+    where
+        T: Query + 'static,
+    {
+        self.root
+            .get_state::<SliceState<QuerySlice<T>>>()
+            .dispatch(QuerySliceAction::Remove {
+                input: input.into(),
+            });
+    }
+
+    /// Clears all cached query results and resets mutation handles to idle.
+    ///
+    /// See: [`ClearQueryCache`]
+    pub fn clear_cache(&self) {
+        self.root.apply_notion(Rc::new(ClearQueryCache));
+    }
+
+    /// Exports the successful results in the query cache as a JSON document.
+    ///
+    /// Only query types registered with
+    /// [`QueryCacheSeed::with_exported_query`](super::QueryCacheSeed::with_exported_query) are
+    /// exported. The document can be attached to bug reports and loaded with
+    /// [`import_cache`](Self::import_cache) to reproduce the state of the user interface. It has
+    /// the same format as the document of a
+    /// [`QueryCacheSeed`](crate::query::QueryCacheSeed), so it can also be used as a seed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{use_query_client, Query, QueryResult};
+    /// # use yew::prelude::*;
+    /// # use async_trait::async_trait;
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// # #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// # struct UserQuery {
+    /// #     name: String,
+    /// # }
+    /// #
+    /// # #[async_trait(?Send)]
+    /// # impl Query for UserQuery {
+    /// #     type Input = u64;
+    /// #     type Error = Infallible;
+    /// #
+    /// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+    /// #         Ok(UserQuery { name: "John Smith".into() }.into())
+    /// #     }
+    /// # }
+    /// #
+    /// // With `QueryCacheSeed::default().with_exported_query::<UserQuery>("user")` provided to the
+    /// // root.
+    /// #[function_component(ReportBug)]
+    /// fn report_bug() -> Html {
+    ///     let client = use_query_client();
+    ///
+    ///     let onclick = Callback::from(move |_| {
+    ///         let cache = client.export_cache();
+    ///         // attach `cache.to_string()` to the bug report...
+    ///     });
+    ///
+    ///     html! { <button {onclick}>{"Report a bug"}</button> }
+    /// }
+    /// ```
+    #[cfg(feature = "query-export")]
+    #[cfg_attr(documenting, doc(cfg(feature = "query-export")))]
+    pub fn export_cache(&self) -> serde_json::Value {
+        match self.root.get_service::<QueryCacheSeed>() {
+            Some(m) => m.exported().export(&self.root),
+            None => serde_json::Value::Object(serde_json::Map::new()),
+        }
+    }
+
+    /// Loads results exported with [`export_cache`](Self::export_cache) into the query cache.
+    ///
+    /// Imported results replace the cached results with the same input and are not fetched
+    /// until they are refreshed. Queries in flight with the same input resolve with the imported
+    /// results. Results of query types that are not registered with
+    /// [`QueryCacheSeed::with_exported_query`](super::QueryCacheSeed::with_exported_query) are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be deserialized. Nothing is imported if any
+    /// part of the document is invalid.
+    #[cfg(feature = "query-export")]
+    #[cfg_attr(documenting, doc(cfg(feature = "query-export")))]
+    pub fn import_cache(&self, value: serde_json::Value) -> Result<(), serde_json::Error> {
+        match self.root.get_service::<QueryCacheSeed>() {
+            Some(m) => m.exported().import(&self.root, value),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for QueryClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryClient").finish_non_exhaustive()
+    }
+}
+
+/// A hook to obtain a [`QueryClient`].
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{use_query_client, Query, QueryResult};
+/// # use yew::prelude::*;
+/// # use yew::platform::spawn_local;
+/// # use async_trait::async_trait;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # struct UserQuery {
+/// #     name: String,
+/// # }
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for UserQuery {
+/// #     type Input = u64;
+/// #     type Error = Infallible;
+/// #
+/// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+/// #         Ok(UserQuery { name: "John Smith".into() }.into())
+/// #     }
+/// # }
+/// #
+/// #[function_component(Prefetch)]
+/// fn prefetch() -> Html {
+///     let client = use_query_client();
+///
+///     let onmouseenter = Callback::from(move |_| {
+///         // Shares the request if the same user is already being fetched.
+///         let user = client.fetch_query::<UserQuery>(1);
+///
+///         spawn_local(async move {
+///             let _user = user.await;
+///         });
+///     });
+///
+///     html! { <a href="/users/1" {onmouseenter}>{"User 1"}</a> }
+/// }
+/// ```
+#[hook]
+pub fn use_query_client() -> QueryClient {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    QueryClient { root }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::rc::Rc;
+
+use super::query_states::{QuerySelector, QuerySliceValue};
+use super::traits::Query;
+use crate::root_state::BounceStates;
+
+/// The state of a query read with [`BounceStates::read_query`].
+pub enum QueryRead<T>
+where
+    T: Query + 'static,
+{
+    /// The query has not been fetched with this input.
+    NotStarted,
+    /// The query is loading for the first time.
+    Loading,
+    /// The query has completed successfully.
+    ///
+    /// If the query is being refreshed, this is the result of the last completed run.
+    Ready(Rc<T>),
+    /// The query has failed.
+    ///
+    /// If the query is being refreshed, this is the result of the last completed run.
+    Failed(T::Error),
+}
+
+impl<T> QueryRead<T>
+where
+    T: Query + 'static,
+{
+    /// Returns the value of the query if it has completed successfully.
+    pub fn ready(&self) -> Option<&Rc<T>> {
+        match self {
+            Self::Ready(m) => Some(m),
+            Self::NotStarted | Self::Loading | Self::Failed(_) => None,
+        }
+    }
+}
+
+impl<T> Clone for QueryRead<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::NotStarted => Self::NotStarted,
+            Self::Loading => Self::Loading,
+            Self::Ready(m) => Self::Ready(m.clone()),
+            Self::Failed(e) => Self::Failed(e.clone()),
+        }
+    }
+}
+
+impl<T> PartialEq for QueryRead<T>
+where
+    T: Query + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::NotStarted, Self::NotStarted) | (Self::Loading, Self::Loading) => true,
+            (Self::Ready(lhs), Self::Ready(rhs)) => lhs == rhs,
+            (Self::Failed(lhs), Self::Failed(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl<T> fmt::Debug for QueryRead<T>
+where
+    T: Query + fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotStarted => f.write_str("NotStarted"),
+            Self::Loading => f.write_str("Loading"),
+            Self::Ready(m) => f.debug_tuple("Ready").field(m).finish(),
+            Self::Failed(e) => f.debug_tuple("Failed").field(e).finish(),
+        }
+    }
+}
+
+impl BounceStates {
+    /// Reads the cached state of a query.
+    ///
+    /// This does not fetch the query. Selectors that read a query are notified when the state of
+    /// the query changes, so they can derive a value for each state of the query instead of
+    /// treating a query that has not been fetched as an empty value.
+    ///
+    /// With the `devtools` feature, reads of queries that have not been fetched are recorded, see
+    /// [`unfetched_query_reads`](crate::devtools::unfetched_query_reads).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{Query, QueryRead, QueryResult};
+    /// # use async_trait::async_trait;
+    /// #
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct CartQuery {
+    /// #     items: Vec<u64>,
+    /// # }
+    /// #
+    /// # #[async_trait(?Send)]
+    /// # impl Query for CartQuery {
+    /// #     type Input = ();
+    /// #     type Error = Infallible;
+    /// #
+    /// #     async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+    /// #         Ok(CartQuery { items: Vec::new() }.into())
+    /// #     }
+    /// # }
+    /// #
+    /// #[derive(PartialEq)]
+    /// enum CartBadge {
+    ///     Hidden,
+    ///     Count(usize),
+    /// }
+    ///
+    /// impl Selector for CartBadge {
+    ///     fn select(states: &BounceStates) -> Rc<Self> {
+    ///         match states.read_query::<CartQuery>(().into()) {
+    ///             QueryRead::Ready(m) => Self::Count(m.items.len()).into(),
+    ///             QueryRead::NotStarted | QueryRead::Loading | QueryRead::Failed(_) => {
+    ///                 Self::Hidden.into()
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn read_query<T>(&self, input: Rc<T::Input>) -> QueryRead<T>
+    where
+        T: Query + 'static,
+    {
+        let value = self.get_input_selector_value::<QuerySelector<T>>(input);
+
+        match value.value {
+            None => {
+                #[cfg(feature = "devtools")]
+                crate::devtools::record_unfetched_read::<T>();
+
+                QueryRead::NotStarted
+            }
+            Some(QuerySliceValue::Loading { .. }) => QueryRead::Loading,
+            Some(QuerySliceValue::Completed { ref result, .. })
+            | Some(QuerySliceValue::Outdated { ref result, .. }) => match result {
+                Ok(m) => QueryRead::Ready(m.clone()),
+                Err(e) => QueryRead::Failed(e.clone()),
+            },
+        }
+    }
+}
@@ -1,22 +1,36 @@
 use std::cell::RefCell;
-use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::rc::Rc;
 
+use anymap2::AnyMap;
+use futures::future::{AbortHandle, Abortable};
 use yew::platform::pinned::oneshot;
 use yew::prelude::*;
 
+use super::error_class::QueryErrorNotion;
+use super::fetch_pool::FetchPool;
+use super::mock;
+use super::options::{QueryOptions, QueryTimestamps};
+use super::query_client::ClearQueryCache;
+#[cfg(debug_assertions)]
+use super::stability::ResultStability;
 use super::traits::{Query, QueryResult};
+use crate::abort::{self, AbortToken};
+use crate::any_state::AnyState;
 use crate::future_notion;
-use crate::root_state::BounceStates;
+use crate::root_state::{BounceRootState, BounceStates};
 use crate::states::future_notion::Deferred;
 use crate::states::input_selector::InputSelector;
 use crate::states::notion::WithNotion;
-use crate::states::slice::Slice;
+use crate::states::registry::{StateDescriptor, StateKind};
+use crate::states::slice::{Slice, SliceState};
 use crate::utils::Id;
+use crate::BounceConfig;
 
 type RunQuerySender<T> = Rc<RefCell<Option<oneshot::Sender<QueryResult<T>>>>>;
+type QueryWaiterMap<T> = HashMap<Rc<<T as Query>::Input>, Vec<oneshot::Sender<QueryResult<T>>>>;
+type QueryRunMap<T> = HashMap<Rc<<T as Query>::Input>, QueryRun<T>>;
 
 pub(super) struct RunQueryInput<T>
 where
@@ -91,6 +105,287 @@ where
     }
 }
 
+/// Callers waiting for the result of queries that are currently in flight.
+pub(super) struct QueryWaiters<T>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<QueryWaiterMap<T>>>,
+}
+
+impl<T> QueryWaiters<T>
+where
+    T: Query + 'static,
+{
+    /// Marks a query as in flight.
+    pub fn start(&self, input: Rc<T::Input>) {
+        self.inner.borrow_mut().entry(input).or_default();
+    }
+
+    /// Returns `true` if a query with the same input is in flight.
+    pub fn is_in_flight(&self, input: &T::Input) -> bool {
+        self.inner.borrow().contains_key(input)
+    }
+
+    /// Waits for the result of the query with the same input.
+    ///
+    /// The query is marked as in flight if it is not already.
+    pub fn wait(&self, input: Rc<T::Input>) -> oneshot::Receiver<QueryResult<T>> {
+        let (sender, receiver) = oneshot::channel();
+        self.inner
+            .borrow_mut()
+            .entry(input)
+            .or_default()
+            .push(sender);
+
+        receiver
+    }
+
+    /// Sends the result to all callers waiting for the query with the same input.
+    pub fn complete(&self, input: &T::Input, result: &QueryResult<T>) {
+        let waiters = self.inner.borrow_mut().remove(input);
+
+        for m in waiters.into_iter().flatten() {
+            let _result = m.send(result.clone());
+        }
+    }
+
+    /// Returns `true` if a caller is waiting for the query with the same input.
+    fn has_waiters(&self, input: &T::Input) -> bool {
+        self.inner
+            .borrow()
+            .get(input)
+            .map_or(false, |m| !m.is_empty())
+    }
+
+    /// Marks a query as no longer in flight.
+    fn cancel(&self, input: &T::Input) {
+        self.inner.borrow_mut().remove(input);
+    }
+}
+
+impl<T> Default for QueryWaiters<T>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T> Clone for QueryWaiters<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for QueryWaiters<T>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A run of a query that is in flight.
+struct QueryRun<T>
+where
+    T: Query + 'static,
+{
+    id: Id,
+    token: AbortToken,
+    handle: AbortHandle,
+    /// Callers of this run and the runs it has superseded.
+    senders: Vec<RunQuerySender<T>>,
+}
+
+impl<T> QueryRun<T>
+where
+    T: Query + 'static,
+{
+    fn abort(&self) {
+        self.token.abort();
+        self.handle.abort();
+    }
+}
+
+/// The runs of a query that are in flight, by input.
+pub(super) struct QueryRuns<T>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<QueryRunMap<T>>>,
+}
+
+impl<T> QueryRuns<T>
+where
+    T: Query + 'static,
+{
+    /// Registers a run.
+    ///
+    /// A run in flight with the same input is superseded, its callers receive the result of the
+    /// new run.
+    fn start(&self, input: Rc<T::Input>, mut run: QueryRun<T>) {
+        let prev = self.inner.borrow_mut().remove(&input);
+
+        if let Some(m) = prev {
+            m.abort();
+            run.senders.extend(m.senders);
+        }
+
+        self.inner.borrow_mut().insert(input, run);
+    }
+
+    /// Unregisters a run and returns its callers.
+    ///
+    /// Returns `None` if the run has been superseded or cancelled.
+    fn finish(&self, id: Id, input: &T::Input) -> Option<Vec<RunQuerySender<T>>> {
+        let mut inner = self.inner.borrow_mut();
+
+        match inner.get(input) {
+            Some(m) if m.id == id => inner.remove(input).map(|m| m.senders),
+            _ => None,
+        }
+    }
+
+    /// Unregisters and aborts the run with the input.
+    ///
+    /// Returns the callers of the run.
+    #[cfg(feature = "query-export")]
+    fn abort(&self, input: &T::Input) -> Option<Vec<RunQuerySender<T>>> {
+        let run = self.inner.borrow_mut().remove(input)?;
+        run.abort();
+
+        Some(run.senders)
+    }
+
+    /// Cancels the run with the input if none of its callers is waiting for its result.
+    ///
+    /// Returns the id of the cancelled run.
+    fn cancel_unawaited(&self, input: &T::Input) -> Option<Id> {
+        let run = {
+            let mut inner = self.inner.borrow_mut();
+            let run = inner.get(input)?;
+
+            if run.senders.iter().any(|m| m.borrow().is_some()) {
+                return None;
+            }
+
+            inner.remove(input)?
+        };
+
+        run.abort();
+
+        Some(run.id)
+    }
+}
+
+impl<T> Default for QueryRuns<T>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T> Clone for QueryRuns<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for QueryRuns<T>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// Cancels the run of a query with the input, unless a caller is waiting for its result.
+///
+/// This is called when the last component subscribed to the input is unmounted.
+pub(super) fn cancel_unawaited_run<T>(root: &BounceRootState, input: &Rc<T::Input>)
+where
+    T: Query + 'static,
+{
+    let waiters = root.get_state::<QueryWaiters<T>>();
+
+    if waiters.has_waiters(input) {
+        return;
+    }
+
+    let id = match root.get_state::<QueryRuns<T>>().cancel_unawaited(input) {
+        Some(m) => m,
+        None => return,
+    };
+
+    waiters.cancel(input);
+    root.get_state::<SliceState<QuerySlice<T>>>()
+        .dispatch(QuerySliceAction::Cancel {
+            id,
+            input: input.clone(),
+        });
+}
+
+/// Aborts the run of a query with the input and resolves its callers with `result` instead.
+#[cfg(feature = "query-export")]
+pub(super) fn resolve_run<T>(root: &BounceRootState, input: &T::Input, result: &QueryResult<T>)
+where
+    T: Query + 'static,
+{
+    let senders = match root.get_state::<QueryRuns<T>>().abort(input) {
+        Some(m) => m,
+        None => return,
+    };
+
+    root.get_state::<QueryWaiters<T>>().complete(input, result);
+
+    for m in senders {
+        if let Some(m) = m.borrow_mut().take() {
+            let _result = m.send(result.clone());
+        }
+    }
+}
+
+/// Returns `true` if the query has a result or is being fetched for the input.
+pub(super) fn has_query_value<T>(root: &BounceRootState, input: &T::Input) -> bool
+where
+    T: Query + 'static,
+{
+    root.get_state::<SliceState<QuerySlice<T>>>()
+        .get()
+        .get(input)
+        .is_some()
+}
+
 #[future_notion]
 pub(super) async fn RunQuery<T>(
     states: &BounceStates,
@@ -116,10 +411,139 @@ where
         return None;
     }
 
-    let result = T::query(states, input.clone()).await;
+    // Callers of QueryClient::fetch_query will wait for this query instead of starting another one.
+    let waiters = states.root().get_state::<QueryWaiters<T>>();
+    waiters.start(input.clone());
+
+    let options = QueryOptions::resolve::<T>(states.root());
+
+    let root = states.root();
+    let token = AbortToken::new();
+    let (handle, registration) = AbortHandle::new_pair();
+
+    let runs = root.get_state::<QueryRuns<T>>();
+    runs.start(
+        input.clone(),
+        QueryRun {
+            id,
+            token: token.clone(),
+            handle,
+            senders: vec![sender],
+        },
+    );
+
+    let run = async {
+        // Queries are held before the fetch pool, so queries that are not run in offline demo
+        // mode do not hold its slots.
+        mock::wait_if_offline::<T>(root).await;
+
+        // Fetches wait for a slot of their query type first, so they do not hold a slot of the
+        // root while waiting.
+        let _query_permit = match options.fetch_limit {
+            Some(m) => Some(root.get_state::<FetchPool<T>>().acquire(m.max(1)).await),
+            None => None,
+        };
+
+        let fetch_pool_limit = root
+            .get_service::<BounceConfig>()
+            .and_then(|m| m.fetch_pool_limit())
+            .filter(|_| options.pooled);
+        let _permit = match fetch_pool_limit {
+            Some(m) => Some(root.get_state::<FetchPool>().acquire(m).await),
+            None => None,
+        };
+
+        let started_at = root.clock().now();
+
+        let states = states.with_abort_token(token.clone());
+        // Mocked results are not retried.
+        let retries = if mock::is_mocked::<T>(root) {
+            0
+        } else {
+            options.retries
+        };
+        let fetch = async {
+            let mut result = mock::query::<T>(&states, input.clone()).await;
+            for _ in 0..retries {
+                // A run that has timed out is not run again.
+                if result.is_ok() || token.is_aborted() {
+                    break;
+                }
+
+                result = mock::query::<T>(&states, input.clone()).await;
+            }
 
-    if let Some(m) = sender.borrow_mut().take() {
-        let _result = m.send(result.clone());
+            result
+        };
+
+        let result = abort::with_timeout(&*root.clock(), options.timeout, &token, fetch).await;
+
+        (started_at, result)
+    };
+
+    // The run is dropped when it is superseded or cancelled.
+    let (started_at, result) = Abortable::new(run, registration).await.ok()?;
+    let senders = runs.finish(id, &input)?;
+
+    let completed_at = root.clock().now();
+    root.get_state::<QueryTimestamps<T>>()
+        .record(input.clone(), started_at, completed_at);
+
+    #[cfg(debug_assertions)]
+    if let Some(threshold) = root
+        .get_service::<BounceConfig>()
+        .and_then(|m| m.slow_query_threshold())
+    {
+        let duration = completed_at.saturating_sub(started_at);
+
+        if duration >= threshold {
+            tracing::warn!(
+                "query {} with input {} took {:?}, which exceeds the slow query threshold of {:?}",
+                std::any::type_name::<T>(),
+                T::describe_input(&input),
+                duration,
+                threshold
+            );
+        }
+    }
+
+    // Refreshes (e.g.: polling) are expected to return new data, only refetches are checked.
+    #[cfg(debug_assertions)]
+    if !is_refresh
+        && root
+            .get_service::<BounceConfig>()
+            .map(|m| m.result_stability_check())
+            .unwrap_or(false)
+    {
+        // The result of this run has not been applied yet.
+        if let Some(
+            QuerySliceValue::Completed { result: prev, .. }
+            | QuerySliceValue::Outdated { result: prev, .. },
+        ) = root
+            .get_state::<SliceState<QuerySlice<T>>>()
+            .get()
+            .get(&input)
+        {
+            root.get_state::<ResultStability<T>>()
+                .record(&input, prev, &result);
+        }
+    }
+
+    if let Err(ref e) = result {
+        states
+            .root()
+            .apply_notion(Rc::new(QueryErrorNotion::new::<T, _>(
+                T::classify_error(e),
+                e,
+            )));
+    }
+
+    waiters.complete(&input, &result);
+
+    for m in senders {
+        if let Some(m) = m.borrow_mut().take() {
+            let _result = m.send(result.clone());
+        }
     }
 
     Some(result)
@@ -167,7 +591,7 @@ where
     }
 }
 
-pub(super) enum QuerySliceAction<T>
+pub(crate) enum QuerySliceAction<T>
 where
     T: Query + 'static,
 {
@@ -180,16 +604,65 @@ where
         input: Rc<T::Input>,
         result: QueryResult<T>,
     },
+    Remove {
+        input: Rc<T::Input>,
+    },
+    /// The run with the id has been cancelled.
+    Cancel {
+        id: Id,
+        input: Rc<T::Input>,
+    },
+    #[cfg(feature = "query-export")]
+    Import {
+        entries: Vec<(Rc<T::Input>, Rc<T>)>,
+    },
 }
 
 #[derive(Slice)]
-#[bounce(with_notion(Deferred<RunQuery<T>>))]
-pub(super) struct QuerySlice<T>
+#[bounce(with_notion(Deferred<RunQuery<T>>, ClearQueryCache))]
+#[cfg_attr(feature = "query-seed", bounce(from_states))]
+pub(crate) struct QuerySlice<T>
 where
     T: Query + 'static,
 {
     ctr: u64,
     queries: HashMap<Rc<T::Input>, QuerySliceValue<T>>,
+    // Runs that have read states which changed before the run completed.
+    outdated_runs: HashSet<Id>,
+}
+
+impl<T> QuerySlice<T>
+where
+    T: Query + 'static,
+{
+    /// Returns the cached value of a query.
+    pub fn get(&self, input: &T::Input) -> Option<&QuerySliceValue<T>> {
+        self.queries.get(input)
+    }
+
+    /// Returns the inputs of queries that have a cached result.
+    pub fn cached_inputs(&self) -> Vec<Rc<T::Input>> {
+        self.queries
+            .iter()
+            .filter(|(_, m)| !matches!(m, QuerySliceValue::Loading { .. }))
+            .map(|(input, _)| input.clone())
+            .collect()
+    }
+
+    /// Returns the cached results of queries that have completed successfully.
+    #[cfg(feature = "query-export")]
+    pub fn successful_results(&self) -> Vec<(Rc<T::Input>, Rc<T>)> {
+        self.queries
+            .iter()
+            .filter_map(|(input, m)| match m {
+                QuerySliceValue::Completed { result, .. }
+                | QuerySliceValue::Outdated { result, .. } => {
+                    result.as_ref().ok().map(|m| (input.clone(), m.clone()))
+                }
+                QuerySliceValue::Loading { .. } => None,
+            })
+            .collect()
+    }
 }
 
 impl<T> Reducible for QuerySlice<T>
@@ -213,15 +686,72 @@ where
             }
 
             Self::Action::LoadPrepared { id, input, result } => {
-                if self.queries.get(&input).is_none() {
+                // Prepared results are paired by input, a result may be loaded after the query
+                // with the same input has started fetching.
+                if matches!(
+                    self.queries.get(&input),
+                    None | Some(QuerySliceValue::Loading { .. })
+                ) {
+                    let this = Rc::make_mut(&mut self);
+                    this.ctr += 1;
+
+                    this.queries
+                        .insert(input, QuerySliceValue::Completed { id, result });
+                }
+            }
+
+            Self::Action::Remove { input } => {
+                if self.queries.contains_key(&input) {
                     let this = Rc::make_mut(&mut self);
                     this.ctr += 1;
 
-                    if let Entry::Vacant(m) = this.queries.entry(input) {
-                        m.insert(QuerySliceValue::Completed { id, result });
+                    if let Some(m) = this.queries.remove(&input) {
+                        this.outdated_runs.remove(&m.id());
                     }
                 }
             }
+
+            Self::Action::Cancel { id, input } => match self.queries.get(&input).cloned() {
+                // The query is loaded again when a component subscribes to it.
+                Some(QuerySliceValue::Loading { id: current_id }) if current_id == id => {
+                    let this = Rc::make_mut(&mut self);
+                    this.ctr += 1;
+
+                    this.queries.remove(&input);
+                    this.outdated_runs.remove(&id);
+                }
+                // The previous result is kept.
+                Some(QuerySliceValue::Outdated {
+                    id: current_id,
+                    result,
+                }) if current_id == id => {
+                    let this = Rc::make_mut(&mut self);
+                    this.ctr += 1;
+
+                    this.queries
+                        .insert(input, QuerySliceValue::Completed { id, result });
+                    this.outdated_runs.remove(&id);
+                }
+                _ => {}
+            },
+
+            #[cfg(feature = "query-export")]
+            Self::Action::Import { entries } => {
+                let this = Rc::make_mut(&mut self);
+                this.ctr += 1;
+
+                // Imported results replace cached results. Runs in flight for the same inputs
+                // have been aborted before the import, so they do not replace them.
+                for (input, value) in entries {
+                    this.queries.insert(
+                        input,
+                        QuerySliceValue::Completed {
+                            id: Id::new(),
+                            result: Ok(value),
+                        },
+                    );
+                }
+            }
         }
 
         self
@@ -236,6 +766,37 @@ where
         Self {
             ctr: 0,
             queries: HashMap::new(),
+            outdated_runs: HashSet::new(),
+        }
+    }
+}
+
+#[cfg(feature = "query-seed")]
+impl<T> crate::FromStates for QuerySlice<T>
+where
+    T: Query + 'static,
+{
+    fn create_from(states: &BounceStates) -> Self {
+        let queries = states
+            .get_service::<super::seed::QueryCacheSeed>()
+            .map(|m| m.load::<T>())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(input, value)| {
+                (
+                    input,
+                    QuerySliceValue::Completed {
+                        id: Id::new(),
+                        result: Ok(value),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            ctr: 0,
+            queries,
+            outdated_runs: HashSet::new(),
         }
     }
 }
@@ -257,6 +818,7 @@ where
         Self {
             ctr: self.ctr,
             queries: self.queries.clone(),
+            outdated_runs: self.outdated_runs.clone(),
         }
     }
 }
@@ -310,23 +872,27 @@ where
                     let this = Rc::make_mut(&mut self);
                     this.ctr += 1;
 
-                    this.queries.insert(
-                        input,
-                        QuerySliceValue::Completed {
-                            id,
-                            result: output.clone(),
-                        },
-                    );
+                    let result = output.clone();
+
+                    // The result is selected from states that have changed since they were read.
+                    let value = if this.outdated_runs.remove(&id) {
+                        QuerySliceValue::Outdated { id, result }
+                    } else {
+                        QuerySliceValue::Completed { id, result }
+                    };
+
+                    this.queries.insert(input, value);
+                } else if self.outdated_runs.contains(&id) {
+                    Rc::make_mut(&mut self).outdated_runs.remove(&id);
                 }
             }
             Deferred::Outdated { ref input } => {
                 let RunQueryInput { input, id, .. } = input.as_ref().clone();
-                if let Some(QuerySliceValue::Completed {
-                    id: current_id,
-                    result: current_result,
-                }) = self.queries.get(&input).cloned()
-                {
-                    if current_id == id {
+                match self.queries.get(&input).cloned() {
+                    Some(QuerySliceValue::Completed {
+                        id: current_id,
+                        result: current_result,
+                    }) if current_id == id => {
                         let this = Rc::make_mut(&mut self);
                         this.ctr += 1;
 
@@ -338,6 +904,15 @@ where
                             },
                         );
                     }
+                    // A state read by the query has changed while the query is running, the
+                    // result is marked as outdated when the run completes.
+                    Some(QuerySliceValue::Loading { id: current_id })
+                    | Some(QuerySliceValue::Outdated { id: current_id, .. })
+                        if current_id == id =>
+                    {
+                        Rc::make_mut(&mut self).outdated_runs.insert(id);
+                    }
+                    _ => {}
                 }
             }
         }
@@ -346,6 +921,21 @@ where
     }
 }
 
+impl<T> WithNotion<ClearQueryCache> for QuerySlice<T>
+where
+    T: Query + 'static,
+{
+    fn apply(self: Rc<Self>, _notion: Rc<ClearQueryCache>) -> Rc<Self> {
+        // Mounted queries will be fetched again.
+        Self {
+            ctr: self.ctr + 1,
+            queries: HashMap::new(),
+            outdated_runs: HashSet::new(),
+        }
+        .into()
+    }
+}
+
 #[derive(PartialEq)]
 pub(super) struct QuerySelector<T>
 where
@@ -369,4 +959,40 @@ where
 
         Self { value }.into()
     }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Query, listener_count)
+    }
+}
+
+/// Selects the value of a query for an input that may not be available yet.
+#[derive(PartialEq)]
+pub(super) struct MaybeQuerySelector<T>
+where
+    T: Query + 'static,
+{
+    pub value: Option<QuerySliceValue<T>>,
+}
+
+impl<T> InputSelector for MaybeQuerySelector<T>
+where
+    T: Query + 'static,
+{
+    type Input = Option<Rc<T::Input>>;
+
+    fn select(states: &BounceStates, input: Rc<Option<Rc<T::Input>>>) -> Rc<Self> {
+        let value = input.as_ref().as_ref().and_then(|m| {
+            states
+                .get_slice_value::<QuerySlice<T>>()
+                .queries
+                .get(m)
+                .cloned()
+        });
+
+        Self { value }.into()
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Query, listener_count)
+    }
 }
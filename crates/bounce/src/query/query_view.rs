@@ -0,0 +1,223 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use yew::prelude::*;
+
+use super::query_states::{QuerySlice, QuerySliceValue};
+use super::traits::Query;
+use crate::any_state::AnyState;
+use crate::root_state::BounceStates;
+use crate::states::input_selector::{use_input_selector_value, InputSelector};
+
+/// A view derived from the result of a query.
+///
+/// See: [`use_query_view`]
+pub trait QueryView<T>: PartialEq
+where
+    T: Query,
+{
+    /// Derives the view from a query value.
+    fn derive(input: &T::Input, value: &T) -> Self;
+}
+
+type QueryViewMap<T, V> = HashMap<Rc<<T as Query>::Input>, (Rc<T>, Rc<V>)>;
+
+/// Views that have been derived, with the query values they are derived from.
+struct QueryViewCache<T, V>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<QueryViewMap<T, V>>>,
+}
+
+impl<T, V> QueryViewCache<T, V>
+where
+    T: Query + 'static,
+    V: QueryView<T> + 'static,
+{
+    fn get(&self, input: &Rc<T::Input>, value: &Rc<T>) -> Rc<V> {
+        let mut inner = self.inner.borrow_mut();
+
+        match inner.get(input) {
+            // The view is only derived again if the query value has changed.
+            Some((last_value, view)) if Rc::ptr_eq(last_value, value) => view.clone(),
+            _ => {
+                let view = Rc::new(V::derive(input, value));
+                inner.insert(input.clone(), (value.clone(), view.clone()));
+                view
+            }
+        }
+    }
+
+    /// Removes the view derived for an input, so the query value it is derived from is released.
+    fn evict(&self, input: &T::Input) {
+        self.inner.borrow_mut().remove(input);
+    }
+}
+
+impl<T, V> Default for QueryViewCache<T, V>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T, V> Clone for QueryViewCache<T, V>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, V> AnyState for QueryViewCache<T, V>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+struct QueryViewSelector<T, V>
+where
+    T: Query + 'static,
+{
+    value: Option<Result<Rc<V>, T::Error>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, V> PartialEq for QueryViewSelector<T, V>
+where
+    T: Query + 'static,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T, V> InputSelector for QueryViewSelector<T, V>
+where
+    T: Query + 'static,
+    V: QueryView<T> + 'static,
+{
+    type Input = T::Input;
+
+    fn select(states: &BounceStates, input: Rc<T::Input>) -> Rc<Self> {
+        let cache = states.root().get_state::<QueryViewCache<T, V>>();
+
+        let value = match states.get_slice_value::<QuerySlice<T>>().get(&input) {
+            Some(QuerySliceValue::Completed {
+                result: Ok(value), ..
+            })
+            | Some(QuerySliceValue::Outdated {
+                result: Ok(value), ..
+            }) => Some(Ok(cache.get(&input, value))),
+            Some(QuerySliceValue::Completed { result: Err(e), .. })
+            | Some(QuerySliceValue::Outdated { result: Err(e), .. }) => {
+                cache.evict(&input);
+                Some(Err(e.clone()))
+            }
+            // The result has been removed or is being fetched without a previous value.
+            Some(QuerySliceValue::Loading { .. }) | None => {
+                cache.evict(&input);
+                None
+            }
+        };
+
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+        .into()
+    }
+}
+
+/// A hook to subscribe to a view derived from the result of a query.
+///
+/// The view is derived once each time the query value changes and is shared by all components
+/// that use the same view with the same input, so expensive work to derive the view is not
+/// repeated on every render.
+///
+/// A view is dropped when the result it is derived from fails or is removed from the cache
+/// (e.g.: with [`QueryClient::remove`](super::QueryClient::remove)).
+///
+/// This hook does not fetch the query. It returns `None` until the query is fetched with
+/// another hook (e.g.: [`use_query_value`](super::use_query_value())) and an error if the query
+/// has failed.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{use_query_view, Query, QueryResult, QueryView};
+/// # use yew::prelude::*;
+/// # use async_trait::async_trait;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// struct PostsQuery {
+///     posts: Vec<String>,
+/// }
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for PostsQuery {
+/// #     type Input = u64;
+/// #     type Error = Infallible;
+/// #
+/// #     async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+/// #         Ok(PostsQuery { posts: Vec::new() }.into())
+/// #     }
+/// # }
+///
+/// #[derive(PartialEq)]
+/// struct SortedTitles(Vec<String>);
+///
+/// impl QueryView<PostsQuery> for SortedTitles {
+///     fn derive(_input: &u64, value: &PostsQuery) -> Self {
+///         let mut titles = value.posts.clone();
+///         titles.sort();
+///
+///         Self(titles)
+///     }
+/// }
+///
+/// #[function_component(Titles)]
+/// fn titles() -> Html {
+///     let titles = use_query_view::<PostsQuery, SortedTitles>(1.into());
+///
+///     match titles {
+///         Some(Ok(m)) => m.0.iter().map(|m| html! { <div>{m}</div> }).collect(),
+///         Some(Err(_)) => html! { <div>{"Oops, something went wrong."}</div> },
+///         None => html! { <div>{"Loading..."}</div> },
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_query_view<T, V>(input: Rc<T::Input>) -> Option<Result<Rc<V>, T::Error>>
+where
+    T: Query + 'static,
+    V: QueryView<T> + 'static,
+{
+    use_input_selector_value::<QueryViewSelector<T, V>>(input)
+        .value
+        .clone()
+}
@@ -0,0 +1,205 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use anymap2::AnyMap;
+use gloo::events::EventListener;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{PageTransitionEvent, Window};
+use yew::prelude::*;
+
+use super::options::QueryOptions;
+use super::query_states::{QuerySlice, QuerySliceAction, RunQuery, RunQueryInput};
+use super::traits::Query;
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+use crate::states::future_notion::use_internal_future_notion_runner;
+use crate::states::slice::use_slice_dispatch;
+use crate::utils::Id;
+
+/// Returns the window, if the page has one.
+fn window() -> Option<Window> {
+    // There is no window on the server.
+    if cfg!(not(target_arch = "wasm32")) {
+        return None;
+    }
+
+    web_sys::window()
+}
+
+/// Listens to pages restored from the back/forward cache, the listener is removed when dropped.
+fn listen_restore(window: &Window, f: impl Fn() + 'static) -> EventListener {
+    // `pageshow` is also dispatched when the page is loaded, only restored pages are persisted.
+    EventListener::new(window, "pageshow", move |e| {
+        if e.dyn_ref::<PageTransitionEvent>()
+            .map(|m| m.persisted())
+            .unwrap_or(false)
+        {
+            f();
+        }
+    })
+}
+
+/// A query input that is refreshed when the page is restored.
+struct RestoreEntry {
+    id: Id,
+    query: TypeId,
+    input: Rc<dyn Any>,
+    same_input: fn(&dyn Any, &dyn Any) -> bool,
+    refresh: Rc<dyn Fn()>,
+}
+
+impl RestoreEntry {
+    fn is_same(&self, other: &Self) -> bool {
+        self.query == other.query && (self.same_input)(&*self.input, &*other.input)
+    }
+}
+
+fn same_input<T>(lhs: &dyn Any, rhs: &dyn Any) -> bool
+where
+    T: Query + 'static,
+{
+    lhs.downcast_ref::<T::Input>() == rhs.downcast_ref::<T::Input>()
+}
+
+#[derive(Default)]
+struct RestoreListenerInner {
+    listener: Option<EventListener>,
+    entries: Vec<RestoreEntry>,
+}
+
+/// The `pageshow` listener of a root, shared by all mounted queries that refresh on restore.
+///
+/// The listener is registered with the first subscription and removed with the last one.
+#[derive(Default, Clone)]
+pub(super) struct RestoreListener {
+    inner: Rc<RefCell<RestoreListenerInner>>,
+}
+
+impl RestoreListener {
+    fn subscribe<T>(&self, input: Rc<T::Input>, refresh: Rc<dyn Fn()>) -> RestoreSubscription
+    where
+        T: Query + 'static,
+    {
+        let id = Id::new();
+        let mut inner = self.inner.borrow_mut();
+
+        if inner.listener.is_none() {
+            let weak_inner = Rc::downgrade(&self.inner);
+            inner.listener =
+                window().map(|m| listen_restore(&m, move || Self::refresh(&weak_inner)));
+        }
+
+        inner.entries.push(RestoreEntry {
+            id,
+            query: TypeId::of::<T>(),
+            input,
+            same_input: same_input::<T>,
+            refresh,
+        });
+
+        RestoreSubscription {
+            id,
+            listener: self.clone(),
+        }
+    }
+
+    fn unsubscribe(&self, id: Id) {
+        let mut inner = self.inner.borrow_mut();
+        inner.entries.retain(|m| m.id != id);
+
+        if inner.entries.is_empty() {
+            inner.listener = None;
+        }
+    }
+
+    /// Refreshes each subscribed input once, no matter how many hooks are subscribed to it.
+    fn refresh(inner: &Weak<RefCell<RestoreListenerInner>>) {
+        let refreshes = match inner.upgrade() {
+            Some(m) => {
+                let inner = m.borrow();
+                inner
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, m)| !inner.entries[..*i].iter().any(|n| n.is_same(m)))
+                    .map(|(_, m)| m.refresh.clone())
+                    .collect::<Vec<_>>()
+            }
+            None => return,
+        };
+
+        for refresh in refreshes {
+            refresh();
+        }
+    }
+}
+
+impl AnyState for RestoreListener {
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// Removes the input from the [`RestoreListener`] when dropped.
+struct RestoreSubscription {
+    id: Id,
+    listener: RestoreListener,
+}
+
+impl Drop for RestoreSubscription {
+    fn drop(&mut self) {
+        self.listener.unsubscribe(self.id);
+    }
+}
+
+/// Refreshes the result of a query when the page is restored from the back/forward cache while
+/// the hook is mounted.
+///
+/// The result is marked as outdated and fetched again in the background, so the last result is
+/// shown while the query is fetched.
+#[hook]
+pub(super) fn use_refresh_on_restore<T>(options: QueryOptions, input: Option<Rc<T::Input>>)
+where
+    T: Query + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let dispatch_state = use_slice_dispatch::<QuerySlice<T>>();
+    let run_query = use_internal_future_notion_runner::<RunQuery<T>>();
+
+    use_effect_with(
+        (input, options.refresh_on_restore),
+        move |(input, refresh_on_restore)| {
+            let subscription = input.clone().filter(|_| *refresh_on_restore).map(|input| {
+                let refresh = {
+                    let input = input.clone();
+                    Rc::new(move || {
+                        let id = Id::new();
+                        dispatch_state(QuerySliceAction::Refresh {
+                            id,
+                            input: input.clone(),
+                        });
+
+                        run_query(RunQueryInput {
+                            id,
+                            input: input.clone(),
+                            sender: Rc::default(),
+                            is_refresh: true,
+                        });
+                    })
+                };
+
+                root.get_state::<RestoreListener>()
+                    .subscribe::<T>(input, refresh)
+            });
+
+            move || drop(subscription)
+        },
+    );
+}
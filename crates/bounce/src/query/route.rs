@@ -0,0 +1,125 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+use yew_router::prelude::{use_location, use_route, Location};
+use yew_router::Routable;
+
+use super::traits::Query;
+use super::use_query_value::{use_optional_query_value, FetchPolicy, UseQueryValueHandle};
+
+/// A trait to derive the input of a query from the current route.
+///
+/// See: [`use_route_query`]
+pub trait FromRoute: Sized {
+    /// The routes of the application.
+    type Route: Routable;
+
+    /// Derives the input from the current route and location.
+    ///
+    /// The query string can be read from the location (e.g.: with [`Location::query`]).
+    ///
+    /// Returns `None` if the route does not provide an input.
+    fn from_route(route: &Self::Route, location: &Location) -> Option<Self>;
+}
+
+/// A hook to run a query with an input derived from the current route.
+///
+/// The input is derived with [`FromRoute`] each time the route changes, and the query is run
+/// again when the derived input is different.
+///
+/// When the route does not provide an input (e.g.: while navigating away from the page), the last
+/// derived input is used. Returns `None` until the route has provided an input or when the hook
+/// is not used under a `<Router />`.
+///
+/// # Example
+///
+/// ```
+/// # use std::convert::Infallible;
+/// # use std::rc::Rc;
+/// # use async_trait::async_trait;
+/// # use yew::prelude::*;
+/// # use yew_router::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::query::{use_route_query, FromRoute, Query, QueryResult};
+///
+/// #[derive(Routable, Clone, PartialEq)]
+/// enum Route {
+///     #[at("/users/:id")]
+///     User { id: u64 },
+///     #[at("/")]
+///     Home,
+/// }
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// struct UserId(u64);
+///
+/// impl FromRoute for UserId {
+///     type Route = Route;
+///
+///     fn from_route(route: &Route, _location: &Location) -> Option<Self> {
+///         match route {
+///             Route::User { id } => Some(UserId(*id)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for User {
+///     type Input = UserId;
+///     type Error = Infallible;
+///
+///     async fn query(_states: &BounceStates, input: Rc<UserId>) -> QueryResult<Self> {
+///         // fetch user
+/// #       Ok(User { name: format!("User {}", input.0) }.into())
+///     }
+/// }
+///
+/// #[function_component(UserPage)]
+/// fn user_page() -> Html {
+///     let user = match use_route_query::<User>() {
+///         Some(m) => m,
+///         None => return html! {<div>{"no user selected"}</div>},
+///     };
+///
+///     match user.result() {
+///         None => html! {<div>{"loading..."}</div>},
+///         Some(Ok(m)) => html! {<div>{&m.name}</div>},
+///         Some(Err(_)) => html! {<div>{"failed to load user"}</div>},
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_route_query<T>() -> Option<UseQueryValueHandle<T>>
+where
+    T: Query + 'static,
+    T::Input: FromRoute,
+{
+    let route = use_route::<<T::Input as FromRoute>::Route>();
+    let location = use_location();
+    let last_input = use_mut_ref(|| None::<Rc<T::Input>>);
+
+    let input = {
+        let mut last_input = last_input.borrow_mut();
+
+        let next_input = route
+            .zip(location)
+            .and_then(|(route, location)| T::Input::from_route(&route, &location));
+
+        if let Some(m) = next_input {
+            // The input is kept when it is equal, so the query is not run again.
+            if last_input.as_deref() != Some(&m) {
+                *last_input = Some(Rc::new(m));
+            }
+        }
+
+        last_input.clone()
+    };
+
+    use_optional_query_value::<T>(input, FetchPolicy::CacheFirst)
+}
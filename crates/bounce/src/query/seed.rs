@@ -0,0 +1,228 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "query-export")]
+use super::cache_export::ExportedQueries;
+use super::traits::Query;
+
+pub(super) type LoadFn = Rc<dyn Fn(Value) -> Result<Box<dyn Any>, serde_json::Error>>;
+
+/// A result of a query in a seed document.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "I: DeserializeOwned, T: DeserializeOwned"))]
+pub(super) struct SeedEntry<I, T> {
+    pub input: I,
+    pub value: T,
+}
+
+/// Returns a function that deserializes the results of a query in a seed document into a
+/// `Vec<(Rc<T::Input>, Rc<T>)>`.
+pub(super) fn load_fn<T>() -> LoadFn
+where
+    T: Query + DeserializeOwned + 'static,
+    T::Input: DeserializeOwned,
+{
+    Rc::new(|value: Value| -> Result<Box<dyn Any>, serde_json::Error> {
+        let entries = serde_json::from_value::<Vec<SeedEntry<T::Input, T>>>(value)?
+            .into_iter()
+            .map(|m| (Rc::new(m.input), Rc::new(m.value)))
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(entries))
+    })
+}
+
+/// Results of queries that are loaded into the query cache from a static JSON document.
+///
+/// This can be used by mostly-static sites to show data instantly without server-side rendering,
+/// with a JSON document generated at build time and bundled with the application (e.g.: with
+/// `include_str!`).
+///
+/// The document is an object with a key for each query registered with
+/// [`with_query`](Self::with_query), and a list of inputs and values for each query:
+///
+/// ```json
+/// {
+///     "user": [
+///         { "input": 1, "value": { "id": 1, "name": "Jane Doe" } }
+///     ]
+/// }
+/// ```
+///
+/// The seed is provided to the `<BounceRoot />` as a [service](crate::Services). The document is
+/// parsed when a registered query is used for the first time and the results of that query are
+/// loaded into the cache as completed results. Queries with inputs that are not in the document
+/// are fetched as usual.
+///
+/// With the `query-export` feature, queries registered with
+/// [`with_exported_query`](Self::with_exported_query) can also be exported from and imported into
+/// the query cache with [`QueryClient`](super::QueryClient) in the same format.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::{BounceRoot, Services};
+/// # use bounce::query::{Query, QueryCacheSeed, QueryResult};
+/// # use async_trait::async_trait;
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct UserQuery {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for UserQuery {
+///     type Input = u64;
+///     type Error = Infallible;
+///
+///     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+///         // fetch user
+///
+///         Ok(UserQuery { id: *input, name: "John Smith".into() }.into())
+///     }
+/// }
+///
+/// // Usually: include_str!("../seed.json")
+/// const SEED: &str = r#"{ "user": [{ "input": 1, "value": { "id": 1, "name": "Jane Doe" } }] }"#;
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     let get_services = Callback::from(|_| {
+///         Services::new().provide(QueryCacheSeed::new(SEED).with_query::<UserQuery>("user"))
+///     });
+///
+///     html! {
+///         <BounceRoot {get_services}>
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+pub struct QueryCacheSeed {
+    json: &'static str,
+    document: RefCell<Option<Rc<HashMap<String, Value>>>>,
+    queries: HashMap<TypeId, (&'static str, LoadFn)>,
+    #[cfg(feature = "query-export")]
+    exported: ExportedQueries,
+}
+
+impl Default for QueryCacheSeed {
+    /// Creates a seed with an empty document.
+    fn default() -> Self {
+        Self::new("{}")
+    }
+}
+
+impl QueryCacheSeed {
+    /// Creates a seed from a JSON document.
+    ///
+    /// The document is not parsed until a registered query is used.
+    pub fn new(json: &'static str) -> Self {
+        Self {
+            json,
+            document: RefCell::default(),
+            queries: HashMap::new(),
+            #[cfg(feature = "query-export")]
+            exported: ExportedQueries::default(),
+        }
+    }
+
+    /// Registers a query with the key of its results in the document.
+    pub fn with_query<T>(mut self, key: &'static str) -> Self
+    where
+        T: Query + DeserializeOwned + 'static,
+        T::Input: DeserializeOwned,
+    {
+        self.queries
+            .insert(TypeId::of::<T>(), (key, load_fn::<T>()));
+        self
+    }
+
+    /// Registers a query with the key of its results in the document, and includes its results in
+    /// caches exported with [`QueryClient::export_cache`](super::QueryClient::export_cache).
+    ///
+    /// Results with the same key are loaded into the cache by
+    /// [`QueryClient::import_cache`](super::QueryClient::import_cache).
+    #[cfg(feature = "query-export")]
+    #[cfg_attr(documenting, doc(cfg(feature = "query-export")))]
+    pub fn with_exported_query<T>(mut self, key: &'static str) -> Self
+    where
+        T: Query + Serialize + DeserializeOwned + 'static,
+        T::Input: Serialize + DeserializeOwned,
+    {
+        self.exported.insert::<T>(key);
+        self.with_query::<T>(key)
+    }
+
+    /// Returns the query types that are included in exported caches.
+    #[cfg(feature = "query-export")]
+    pub(super) fn exported(&self) -> &ExportedQueries {
+        &self.exported
+    }
+
+    fn document(&self) -> Rc<HashMap<String, Value>> {
+        self.document
+            .borrow_mut()
+            .get_or_insert_with(|| match serde_json::from_str(self.json) {
+                Ok(m) => Rc::new(m),
+                Err(e) => {
+                    tracing::warn!("failed to parse query cache seed: {}", e);
+                    Rc::default()
+                }
+            })
+            .clone()
+    }
+
+    /// Returns the seeded results of a query.
+    pub(super) fn load<T>(&self) -> Vec<(Rc<T::Input>, Rc<T>)>
+    where
+        T: Query + 'static,
+    {
+        let (key, load) = match self.queries.get(&TypeId::of::<T>()) {
+            Some(m) => m,
+            None => return Vec::new(),
+        };
+
+        let value = match self.document().get(*key) {
+            Some(m) => m.clone(),
+            None => return Vec::new(),
+        };
+
+        match load(value).map(|m| m.downcast::<Vec<(Rc<T::Input>, Rc<T>)>>()) {
+            Ok(Ok(m)) => *m,
+            Ok(Err(_)) => Vec::new(),
+            Err(e) => {
+                tracing::warn!("failed to load query cache seed for {}: {}", key, e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl fmt::Debug for QueryCacheSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryCacheSeed")
+            .field(
+                "queries",
+                &self
+                    .queries
+                    .values()
+                    .map(|(key, _)| key)
+                    .collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
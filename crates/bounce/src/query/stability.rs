@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+
+use super::traits::{Query, QueryResult};
+use crate::any_state::AnyState;
+
+/// The number of consecutive refetches with unequal results after which a warning is emitted.
+const UNSTABLE_REFETCHES: u32 = 3;
+
+struct ResultStabilityInner<I> {
+    unequal_refetches: HashMap<Rc<I>, u32>,
+    warned: bool,
+}
+
+/// Detects queries with results that compare unequal on every refetch.
+///
+/// A result that is never equal to the previous result (e.g.: because it contains the time it
+/// was fetched) notifies all subscribers on every refetch, even if the data has not changed.
+///
+/// This is only checked in debug builds if it is enabled with
+/// [`BounceConfig::with_result_stability_check`](crate::BounceConfig::with_result_stability_check).
+pub(super) struct ResultStability<T>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<ResultStabilityInner<T::Input>>>,
+}
+
+impl<T> ResultStability<T>
+where
+    T: Query + 'static,
+{
+    /// Records that the result of a query has been refetched.
+    pub fn record(&self, input: &Rc<T::Input>, prev: &QueryResult<T>, next: &QueryResult<T>) {
+        // Errors are not cached as data, only successful results are checked.
+        let (prev, next) = match (prev, next) {
+            (Ok(prev), Ok(next)) => (prev, next),
+            _ => return,
+        };
+
+        let mut inner = self.inner.borrow_mut();
+
+        if prev == next {
+            inner.unequal_refetches.remove(input);
+            return;
+        }
+
+        let count = {
+            let count = inner.unequal_refetches.entry(input.clone()).or_default();
+            *count += 1;
+            *count
+        };
+
+        if count >= UNSTABLE_REFETCHES && !inner.warned {
+            inner.warned = true;
+
+            tracing::warn!(
+                "the result of query {} has compared unequal to the previous result on {} \
+                 consecutive refetches, which re-renders all of its subscribers on every refetch. \
+                 If the data has not changed, consider implementing `PartialEq` to ignore fields \
+                 that change on every fetch (e.g.: timestamps) or removing them in post-processing \
+                 (see `ProcessedQuery`).",
+                std::any::type_name::<T>(),
+                count
+            );
+        }
+    }
+}
+
+impl<T> Default for ResultStability<T>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(ResultStabilityInner {
+                unequal_refetches: HashMap::new(),
+                warned: false,
+            })),
+        }
+    }
+}
+
+impl<T> Clone for ResultStability<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for ResultStability<T>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
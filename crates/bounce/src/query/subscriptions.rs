@@ -0,0 +1,328 @@
+use std::any::{type_name, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anymap2::AnyMap;
+use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use super::options::QueryTimestamps;
+use super::query_states::cancel_unawaited_run;
+use super::traits::Query;
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+
+/// A query and input that is subscribed to by mounted components.
+///
+/// See: [`QueryClient::subscriptions`](super::QueryClient::subscriptions)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuerySubscription {
+    /// The name of the query type.
+    pub query: &'static str,
+    /// The input, as described by [`Query::describe_input`].
+    pub input: String,
+    /// The number of mounted query hooks (e.g.: [`use_query`](super::use_query()) and
+    /// [`use_query_value`](super::use_query_value())) that are subscribed to the query with this
+    /// input.
+    pub subscribers: usize,
+    /// The time that the last fetch of the query has completed, measured by the
+    /// [`Clock`](crate::Clock) of the root.
+    pub fetched_at: Option<Duration>,
+}
+
+/// The number of mounted hooks subscribed to each input of a query.
+pub(super) struct QuerySubscribers<T>
+where
+    T: Query + 'static,
+{
+    inner: Rc<RefCell<HashMap<Rc<T::Input>, usize>>>,
+}
+
+impl<T> QuerySubscribers<T>
+where
+    T: Query + 'static,
+{
+    fn subscribe(&self, input: Rc<T::Input>) {
+        *self.inner.borrow_mut().entry(input).or_default() += 1;
+    }
+
+    /// Returns `true` if the last subscriber of the input has unsubscribed.
+    fn unsubscribe(&self, input: &T::Input) -> bool {
+        let mut inner = self.inner.borrow_mut();
+
+        if let Some(m) = inner.get_mut(input) {
+            *m -= 1;
+
+            if *m == 0 {
+                inner.remove(input);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Returns the number of mounted hooks subscribed to an input.
+    pub fn count(&self, input: &T::Input) -> usize {
+        self.inner.borrow().get(input).copied().unwrap_or(0)
+    }
+}
+
+impl<T> Default for QuerySubscribers<T>
+where
+    T: Query + 'static,
+{
+    fn default() -> Self {
+        Self {
+            inner: Rc::default(),
+        }
+    }
+}
+
+impl<T> Clone for QuerySubscribers<T>
+where
+    T: Query + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AnyState for QuerySubscribers<T>
+where
+    T: Query + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+type ListSubscriptions = fn(&BounceRootState) -> Vec<QuerySubscription>;
+
+/// The query types that have been subscribed to in a root.
+#[derive(Default, Clone)]
+pub(super) struct QuerySubscriptionRegistry {
+    inner: Rc<RefCell<HashMap<TypeId, ListSubscriptions>>>,
+}
+
+impl QuerySubscriptionRegistry {
+    fn register<T>(&self)
+    where
+        T: Query + 'static,
+    {
+        self.inner
+            .borrow_mut()
+            .entry(TypeId::of::<T>())
+            .or_insert(list_subscriptions::<T>);
+    }
+
+    /// Returns the subscriptions of all query types, ordered by query type and input.
+    pub fn list(&self, root: &BounceRootState) -> Vec<QuerySubscription> {
+        let list_fns = self.inner.borrow().values().copied().collect::<Vec<_>>();
+
+        let mut subscriptions = list_fns
+            .into_iter()
+            .flat_map(|f| f(root))
+            .collect::<Vec<_>>();
+        // Subscriptions of the same query with slightly different inputs are listed together.
+        subscriptions.sort_by(|lhs, rhs| (lhs.query, &lhs.input).cmp(&(rhs.query, &rhs.input)));
+
+        subscriptions
+    }
+}
+
+impl AnyState for QuerySubscriptionRegistry {
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+fn list_subscriptions<T>(root: &BounceRootState) -> Vec<QuerySubscription>
+where
+    T: Query + 'static,
+{
+    let subscribers = root.get_state::<QuerySubscribers<T>>();
+    let timestamps = root.get_state::<QueryTimestamps<T>>();
+
+    let subscriptions = subscribers
+        .inner
+        .borrow()
+        .iter()
+        .map(|(input, count)| QuerySubscription {
+            query: type_name::<T>(),
+            input: T::describe_input(input),
+            subscribers: *count,
+            fetched_at: timestamps.completed_at(input),
+        })
+        .collect();
+
+    subscriptions
+}
+
+/// Counts the hook as a subscriber of the input while it is mounted.
+///
+/// When the last subscriber of an input is unmounted, the run of the input in flight is cancelled.
+#[hook]
+pub(super) fn use_query_subscription<T>(input: Option<Rc<T::Input>>) -> QuerySubscribers<T>
+where
+    T: Query + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let subscribers = root.get_state::<QuerySubscribers<T>>();
+
+    {
+        let subscribers = subscribers.clone();
+        use_effect_with(input, move |input| {
+            let input = input.clone();
+
+            if let Some(ref input) = input {
+                root.get_state::<QuerySubscriptionRegistry>()
+                    .register::<T>();
+                subscribers.subscribe(input.clone());
+            }
+
+            move || {
+                if let Some(input) = input {
+                    if subscribers.unsubscribe(&input) {
+                        cancel_unawaited_run::<T>(&root, &input);
+                    }
+                }
+            }
+        });
+    }
+
+    subscribers
+}
+
+/// Properties of the [`QuerySubscriptionsOverlay`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct QuerySubscriptionsOverlayProps {
+    /// Classes of the overlay.
+    ///
+    /// When classes are specified, the overlay is not positioned with an inline style.
+    #[prop_or_default]
+    pub class: Classes,
+
+    /// The interval that the overlay is updated at.
+    #[prop_or(Duration::from_secs(1))]
+    pub interval: Duration,
+}
+
+const OVERLAY_STYLE: &str = "position: fixed; right: 0; bottom: 0; z-index: 2147483647; \
+     max-height: 50vh; overflow: auto; padding: 4px 8px; font: 12px monospace; \
+     background: rgba(0, 0, 0, 0.8); color: #fff;";
+
+/// A debug overlay that lists the queries subscribed to by mounted components.
+///
+/// Each row shows the query type, the input (as described by [`Query::describe_input`]), the
+/// number of mounted [`use_query_value`](super::use_query_value()) hooks subscribed to it and the
+/// time since it has been fetched. Hooks with the same query and input share a single
+/// subscription, so rows of the same query with slightly different inputs point to accidental
+/// duplicate requests.
+///
+/// The overlay is meant for development, it can be rendered only in debug builds with
+/// `cfg!(debug_assertions)`.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::BounceRoot;
+/// use bounce::query::QuerySubscriptionsOverlay;
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             if cfg!(debug_assertions) {
+///                 <QuerySubscriptionsOverlay />
+///             }
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(QuerySubscriptionsOverlay)]
+pub fn query_subscriptions_overlay(props: &QuerySubscriptionsOverlayProps) -> Html {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let update = use_force_update();
+
+    {
+        let root = root.clone();
+        use_effect_with(props.interval, move |interval| {
+            let interval = *interval;
+            let cancelled = Rc::new(Cell::new(false));
+
+            {
+                let cancelled = cancelled.clone();
+                spawn_local(async move {
+                    loop {
+                        root.clock().sleep(interval).await;
+
+                        if cancelled.get() {
+                            return;
+                        }
+
+                        update.force_update();
+                    }
+                });
+            }
+
+            move || cancelled.set(true)
+        });
+    }
+
+    let now = root.clock().now();
+    let rows = root
+        .get_state::<QuerySubscriptionRegistry>()
+        .list(&root)
+        .into_iter()
+        .map(|m| {
+            let fetched = match m.fetched_at {
+                Some(fetched_at) => format!("{}s ago", now.saturating_sub(fetched_at).as_secs()),
+                None => "never".to_string(),
+            };
+
+            html! {
+                <tr>
+                    <td>{m.query}</td>
+                    <td>{m.input}</td>
+                    <td>{m.subscribers}</td>
+                    <td>{fetched}</td>
+                </tr>
+            }
+        });
+
+    let style = props.class.is_empty().then_some(OVERLAY_STYLE);
+
+    html! {
+        <div class={props.class.clone()} {style}>
+            <table>
+                <thead>
+                    <tr>
+                        <th>{"Query"}</th>
+                        <th>{"Input"}</th>
+                        <th>{"Subscribers"}</th>
+                        <th>{"Fetched"}</th>
+                    </tr>
+                </thead>
+                <tbody>{for rows}</tbody>
+            </table>
+        </div>
+    }
+}
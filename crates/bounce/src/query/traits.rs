@@ -1,7 +1,13 @@
 use async_trait::async_trait;
 use std::hash::Hash;
 use std::rc::Rc;
+use std::time::Duration;
 
+use super::error_class::ErrorClass;
+use super::options::SsrPolicy;
+use super::polling::HiddenPolling;
+use super::preload::PreloadHint;
+use super::use_mutation::ProgressSink;
 use crate::root_state::BounceStates;
 
 /// A Result returned by queries.
@@ -14,6 +20,10 @@ pub type QueryResult<T> = std::result::Result<Rc<T>, <T as Query>::Error>;
 /// This trait is implemented with [async_trait](macro@async_trait), you should apply an `#[async_trait(?Send)]`
 /// attribute to your implementation of this trait.
 ///
+/// Subscribers are only notified when a refetched result is not equal to the previous result.
+/// In debug builds, a warning is logged if the result of a query compares unequal on several
+/// consecutive refetches (e.g.: because it contains the time it was fetched).
+///
 /// # Example
 ///
 /// ```
@@ -60,10 +70,135 @@ pub trait Query: PartialEq {
     /// The Error type of a query.
     type Error: 'static + std::error::Error + PartialEq + Clone;
 
+    /// The duration after which a completed result is stale.
+    ///
+    /// A stale result is refreshed when a hook of this query is mounted or its input changes.
+    /// The stale result is shown while it is refreshing. Hooks that are mounted at the same time
+    /// share a single refresh.
+    ///
+    /// The time a result has completed is returned by
+    /// [`QueryClient::updated_at`](super::QueryClient::updated_at).
+    ///
+    /// Defaults to `None`, results never become stale.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const STALE_TIME: Option<Duration> = None;
+
+    /// The number of times a failed query is run again before its error is returned.
+    ///
+    /// Queries mocked with [`MockQueries`](super::MockQueries) are not retried.
+    ///
+    /// Defaults to `0`.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const RETRIES: u32 = 0;
+
+    /// Whether hooks keep the result of the previous input while the result of a new input is
+    /// loading.
+    ///
+    /// When enabled, [`use_query`](super::use_query()) and
+    /// [`use_query_value`](super::use_query_value()) return the previous result as refreshing
+    /// instead of suspending (or loading) when the input changes.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const KEEP_PREVIOUS_DATA: bool = false;
+
+    /// Whether fetches of this query wait for a slot of the fetch pool of the root.
+    ///
+    /// Queries that are not pooled are fetched immediately and do not count towards the limit
+    /// of the pool, e.g.: a query that the rest of the page depends on.
+    ///
+    /// Defaults to `true`. Fetches are only limited if a limit is set with
+    /// [`BounceConfig::with_fetch_pool_limit`](crate::BounceConfig::with_fetch_pool_limit).
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const POOLED: bool = true;
+
+    /// The maximum number of fetches of this query type that run at the same time.
+    ///
+    /// Fetches that exceed the limit wait in the order they are started until a running fetch
+    /// of this query type completes. This applies whether or not the query is
+    /// [pooled](Self::POOLED), fetches wait for a slot of this limit before a slot of the fetch
+    /// pool of the root. A limit of `0` is treated as `1`.
+    ///
+    /// Defaults to `None`, which does not limit fetches of this query type.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const FETCH_LIMIT: Option<usize> = None;
+
+    /// Whether the server-side rendering waits for the result of this query.
+    ///
+    /// Queries that are not critical to the page (e.g.: recommendations) can be
+    /// [skipped](SsrPolicy::Skip), so they do not delay the server-side rendering. They are
+    /// rendered as loading on the server and fetched on the client.
+    ///
+    /// This is respected by [`use_prepared_query_value`](super::use_prepared_query_value()).
+    /// [`use_prepared_query`](super::use_prepared_query()) suspends until the result is
+    /// available, so it waits for the result on the server but does not embed it in the html.
+    ///
+    /// Defaults to [`SsrPolicy::Block`].
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const SSR: SsrPolicy = SsrPolicy::Block;
+
+    /// The interval at which mounted queries are fetched again.
+    ///
+    /// Polling starts when a hook is mounted and restarts when its input changes. The result
+    /// is refreshed in the background, so the last result is shown while the query is fetched.
+    ///
+    /// Defaults to `None`, which does not poll.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const POLL_INTERVAL: Option<Duration> = None;
+
+    /// How this query is polled while the page is hidden (e.g.: in a background tab).
+    ///
+    /// Polling can be paused or slowed down while the page is hidden to save battery and API
+    /// quota. When the page becomes visible again, the query is fetched immediately.
+    ///
+    /// Defaults to [`HiddenPolling::Pause`].
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const HIDDEN_POLLING: HiddenPolling = HiddenPolling::Pause;
+
+    /// The duration after which a run of this query is cancelled.
+    ///
+    /// When a run exceeds the timeout, its [`AbortToken`](crate::AbortToken) is aborted. The run
+    /// is not dropped, so the implementation can return an error (e.g.: a timeout error) that is
+    /// cached as the result.
+    ///
+    /// Defaults to `None`, runs never time out.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const TIMEOUT: Option<Duration> = None;
+
+    /// Whether mounted queries are fetched again when the page is restored from the
+    /// back/forward cache.
+    ///
+    /// Browsers can keep a page in memory when the user navigates away and show it again as it
+    /// was when the user navigates back, without running the application again. The results
+    /// shown may be outdated by then. When enabled, the results of mounted hooks are refreshed
+    /// when a restored page is shown. The last result is shown while the query is fetched.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// This can be overridden at runtime with [`BounceConfig`](crate::BounceConfig).
+    const REFRESH_ON_RESTORE: bool = false;
+
     /// Runs a query.
     ///
     /// This method will only be called when the result is not already cached.
     ///
+    /// States read with `states` are tracked: when any of them changes, including while the
+    /// query is running, the cached result is marked as outdated and mounted queries are run
+    /// again. States that should be read as a snapshot can be read with
+    /// [`BounceStates::untracked`].
+    ///
+    /// Requests can be cancelled with the token returned by [`BounceStates::abort_token`], the
+    /// token of the run is carried by `states`.
+    ///
     /// # Note
     ///
     /// When implementing this method with async_trait, you can use the following function
@@ -73,6 +208,309 @@ pub trait Query: PartialEq {
     /// async fn query(states: &BounceStates, input: Rc<Self::Input>) -> QueryResult<Self>
     /// ```
     async fn query(states: &BounceStates, input: Rc<Self::Input>) -> QueryResult<Self>;
+
+    /// Returns the resources the client will request for the result of this query.
+    ///
+    /// When this query is run by [`use_prepared_query`](super::use_prepared_query()) during
+    /// server-side rendering, the hints are collected so they can be rendered as
+    /// `<link rel="preload" ... />` tags by the helmet `StaticRenderer`. This lets the client
+    /// start requesting these resources before the application is hydrated.
+    ///
+    /// Defaults to no hints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{PreloadHint, Query, QueryResult};
+    /// # use async_trait::async_trait;
+    /// #
+    /// #[derive(Debug, PartialEq)]
+    /// struct AvatarQuery {
+    ///     url: String,
+    /// }
+    ///
+    /// #[async_trait(?Send)]
+    /// impl Query for AvatarQuery {
+    ///     type Input = u64;
+    ///     type Error = Infallible;
+    ///
+    ///     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+    ///         // fetch the user at `/api/users/{input}`
+    ///
+    ///         Ok(AvatarQuery { url: format!("/avatars/{}.png", input) }.into())
+    ///     }
+    ///
+    ///     fn preload_hints(input: &u64) -> Vec<PreloadHint> {
+    ///         vec![
+    ///             PreloadHint::fetch(format!("/api/users/{}", input)),
+    ///             PreloadHint::new(format!("/avatars/{}.png", input), "image"),
+    ///         ]
+    ///     }
+    /// }
+    /// ```
+    fn preload_hints(_input: &Self::Input) -> Vec<PreloadHint> {
+        Vec::new()
+    }
+
+    /// Describes an input in diagnostics, e.g.: the warnings of
+    /// [slow queries](crate::BounceConfig::with_slow_query_threshold).
+    ///
+    /// Defaults to the name of the input type. Queries with an input that implements `Debug`
+    /// can return its debug output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{Query, QueryResult};
+    /// # use async_trait::async_trait;
+    /// #
+    /// #[derive(Debug, PartialEq)]
+    /// struct SearchQuery {
+    ///     results: Vec<String>,
+    /// }
+    ///
+    /// #[async_trait(?Send)]
+    /// impl Query for SearchQuery {
+    ///     type Input = String;
+    ///     type Error = Infallible;
+    ///
+    ///     async fn query(_states: &BounceStates, _input: Rc<String>) -> QueryResult<Self> {
+    ///         Ok(SearchQuery { results: Vec::new() }.into())
+    ///     }
+    ///
+    ///     fn describe_input(input: &String) -> String {
+    ///         format!("{:?}", input)
+    ///     }
+    /// }
+    /// ```
+    fn describe_input(_input: &Self::Input) -> String {
+        std::any::type_name::<Self::Input>().to_string()
+    }
+
+    /// Returns the class of an error returned by this query.
+    ///
+    /// When a query fails, a [`QueryErrorNotion`](super::QueryErrorNotion) with the class of the
+    /// error is applied to the bounce root, so global handlers (e.g.: toasts or session refresh)
+    /// can react to errors without every component handling them.
+    ///
+    /// Defaults to [`ErrorClass::Unknown`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{ErrorClass, Query, QueryResult};
+    /// # use async_trait::async_trait;
+    /// #
+    /// #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    /// enum ApiError {
+    ///     #[error("failed to connect")]
+    ///     Connect,
+    ///     #[error("server returned {0}")]
+    ///     Status(u16),
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct UserQuery {
+    ///     name: String,
+    /// }
+    ///
+    /// #[async_trait(?Send)]
+    /// impl Query for UserQuery {
+    ///     type Input = u64;
+    ///     type Error = ApiError;
+    ///
+    ///     async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+    ///         Err(ApiError::Connect)
+    ///     }
+    ///
+    ///     fn classify_error(error: &ApiError) -> ErrorClass {
+    ///         match error {
+    ///             ApiError::Connect => ErrorClass::Network,
+    ///             ApiError::Status(401 | 403) => ErrorClass::Auth,
+    ///             ApiError::Status(400 | 422) => ErrorClass::Validation,
+    ///             ApiError::Status(408) => ErrorClass::Timeout,
+    ///             ApiError::Status(500..=599) => ErrorClass::Server,
+    ///             ApiError::Status(_) => ErrorClass::Unknown,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn classify_error(_error: &Self::Error) -> ErrorClass {
+        ErrorClass::Unknown
+    }
+}
+
+/// A trait to be implemented on queries that process a raw response before it is cached.
+///
+/// A processed query fetches a raw value with [`fetch`](Self::fetch), which is passed to
+/// [`post_process`](Self::post_process). Only the processed value is cached and exposed to
+/// subscribers, the raw value is discarded. This means that expensive parsing and
+/// normalisation is done once per fetch instead of on every render.
+///
+/// Types implementing this trait implement [`Query`] automatically.
+///
+/// # Note
+///
+/// This trait is implemented with [async_trait](macro@async_trait), you should apply an `#[async_trait(?Send)]`
+/// attribute to your implementation of this trait.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use std::convert::Infallible;
+/// use bounce::prelude::*;
+/// use bounce::query::ProcessedQuery;
+/// use async_trait::async_trait;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct WordCountQuery {
+///     words: usize,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl ProcessedQuery for WordCountQuery {
+///     type Input = String;
+///     type Error = Infallible;
+///     type Raw = String;
+///
+///     async fn fetch(_states: &BounceStates, input: Rc<String>) -> Result<String, Infallible> {
+///         // fetch the document at the url.
+///
+///         Ok(format!("the document at {}", input))
+///     }
+///
+///     fn post_process(_input: &String, raw: String) -> Self {
+///         Self {
+///             words: raw.split_whitespace().count(),
+///         }
+///     }
+/// }
+/// ```
+#[async_trait(?Send)]
+pub trait ProcessedQuery: PartialEq {
+    /// The Input type of a query.
+    ///
+    /// The input type must implement Hash and Eq as it is used as the key of results in a
+    /// HashMap.
+    type Input: Hash + Eq + 'static;
+
+    /// The Error type of a query.
+    type Error: 'static + std::error::Error + PartialEq + Clone;
+
+    /// The raw value fetched by the query.
+    type Raw: 'static;
+
+    /// See: [`Query::STALE_TIME`]
+    const STALE_TIME: Option<Duration> = None;
+
+    /// See: [`Query::RETRIES`]
+    const RETRIES: u32 = 0;
+
+    /// See: [`Query::KEEP_PREVIOUS_DATA`]
+    const KEEP_PREVIOUS_DATA: bool = false;
+
+    /// See: [`Query::POOLED`]
+    const POOLED: bool = true;
+
+    /// See: [`Query::FETCH_LIMIT`]
+    const FETCH_LIMIT: Option<usize> = None;
+
+    /// See: [`Query::SSR`]
+    const SSR: SsrPolicy = SsrPolicy::Block;
+
+    /// See: [`Query::POLL_INTERVAL`]
+    const POLL_INTERVAL: Option<Duration> = None;
+
+    /// See: [`Query::HIDDEN_POLLING`]
+    const HIDDEN_POLLING: HiddenPolling = HiddenPolling::Pause;
+
+    /// See: [`Query::TIMEOUT`]
+    const TIMEOUT: Option<Duration> = None;
+
+    /// See: [`Query::REFRESH_ON_RESTORE`]
+    const REFRESH_ON_RESTORE: bool = false;
+
+    /// Fetches the raw value.
+    ///
+    /// This method will only be called when the result is not already cached.
+    ///
+    /// # Note
+    ///
+    /// When implementing this method with async_trait, you can use the following function
+    /// signature:
+    ///
+    /// ```ignore
+    /// async fn fetch(states: &BounceStates, input: Rc<Self::Input>) -> Result<Self::Raw, Self::Error>
+    /// ```
+    async fn fetch(states: &BounceStates, input: Rc<Self::Input>)
+        -> Result<Self::Raw, Self::Error>;
+
+    /// Processes the raw value into the value that is cached.
+    fn post_process(input: &Self::Input, raw: Self::Raw) -> Self;
+
+    /// Returns the resources the client will request for the result of this query.
+    ///
+    /// See: [`Query::preload_hints`]
+    fn preload_hints(_input: &Self::Input) -> Vec<PreloadHint> {
+        Vec::new()
+    }
+
+    /// See: [`Query::describe_input`]
+    fn describe_input(_input: &Self::Input) -> String {
+        std::any::type_name::<Self::Input>().to_string()
+    }
+
+    /// See: [`Query::classify_error`]
+    fn classify_error(_error: &Self::Error) -> ErrorClass {
+        ErrorClass::Unknown
+    }
+}
+
+#[async_trait(?Send)]
+impl<T> Query for T
+where
+    T: ProcessedQuery + 'static,
+{
+    type Input = T::Input;
+    type Error = T::Error;
+
+    const STALE_TIME: Option<Duration> = <T as ProcessedQuery>::STALE_TIME;
+    const RETRIES: u32 = <T as ProcessedQuery>::RETRIES;
+    const KEEP_PREVIOUS_DATA: bool = <T as ProcessedQuery>::KEEP_PREVIOUS_DATA;
+    const POOLED: bool = <T as ProcessedQuery>::POOLED;
+    const FETCH_LIMIT: Option<usize> = <T as ProcessedQuery>::FETCH_LIMIT;
+    const SSR: SsrPolicy = <T as ProcessedQuery>::SSR;
+    const POLL_INTERVAL: Option<Duration> = <T as ProcessedQuery>::POLL_INTERVAL;
+    const HIDDEN_POLLING: HiddenPolling = <T as ProcessedQuery>::HIDDEN_POLLING;
+    const TIMEOUT: Option<Duration> = <T as ProcessedQuery>::TIMEOUT;
+    const REFRESH_ON_RESTORE: bool = <T as ProcessedQuery>::REFRESH_ON_RESTORE;
+
+    async fn query(states: &BounceStates, input: Rc<Self::Input>) -> QueryResult<Self> {
+        let raw = T::fetch(states, input.clone()).await?;
+
+        Ok(T::post_process(&input, raw).into())
+    }
+
+    fn preload_hints(input: &Self::Input) -> Vec<PreloadHint> {
+        <T as ProcessedQuery>::preload_hints(input)
+    }
+
+    fn describe_input(input: &Self::Input) -> String {
+        <T as ProcessedQuery>::describe_input(input)
+    }
+
+    fn classify_error(error: &Self::Error) -> ErrorClass {
+        <T as ProcessedQuery>::classify_error(error)
+    }
 }
 
 /// A Result returned by mutations.
@@ -91,7 +529,7 @@ pub type MutationResult<T> = std::result::Result<Rc<T>, <T as Mutation>::Error>;
 /// use std::rc::Rc;
 /// use std::convert::Infallible;
 /// use bounce::prelude::*;
-/// use bounce::query::{Mutation, MutationResult};
+/// use bounce::query::{Mutation, MutationResult, ProgressSink};
 /// use yew::prelude::*;
 /// use async_trait::async_trait;
 ///
@@ -110,8 +548,13 @@ pub type MutationResult<T> = std::result::Result<Rc<T>, <T as Mutation>::Error>;
 ///     type Input = User;
 ///     type Error = Infallible;
 ///
-///     async fn run(_states: &BounceStates, _input: Rc<User>) -> MutationResult<Self> {
+///     async fn run(
+///         _states: &BounceStates,
+///         _input: Rc<User>,
+///         progress: ProgressSink,
+///     ) -> MutationResult<Self> {
 ///         // updates the user information.
+///         progress.report(0.5);
 ///
 ///         Ok(UpdateUserMutation {}.into())
 ///     }
@@ -129,13 +572,102 @@ pub trait Mutation: PartialEq {
 
     /// Runs a mutation.
     ///
+    /// The progress of the mutation (e.g.: the upload progress of a file) can be reported with
+    /// `progress`, it is available on the handle with
+    /// [`UseMutationHandle::progress`](super::UseMutationHandle::progress) until the mutation
+    /// completes.
+    ///
+    /// Requests can be cancelled with the token returned by [`BounceStates::abort_token`], the
+    /// token of the run is carried by `states`.
+    ///
     /// # Note
     ///
     /// When implementing this method with async_trait, you can use the following function
     /// signature:
     ///
     /// ```ignore
-    /// async fn run(states: &BounceStates, input: Rc<Self::Input>) -> MutationResult<Self>
+    /// async fn run(
+    ///     states: &BounceStates,
+    ///     input: Rc<Self::Input>,
+    ///     progress: ProgressSink,
+    /// ) -> MutationResult<Self>
     /// ```
-    async fn run(states: &BounceStates, input: Rc<Self::Input>) -> MutationResult<Self>;
+    async fn run(
+        states: &BounceStates,
+        input: Rc<Self::Input>,
+        progress: ProgressSink,
+    ) -> MutationResult<Self>;
+
+    /// How long the result of an idempotent mutation is cached for.
+    ///
+    /// Only used if [`cache_key`](Self::cache_key) returns a key. Defaults to 1 minute.
+    const CACHE_DURATION: Duration = Duration::from_secs(60);
+
+    /// The duration after which a run of this mutation is cancelled.
+    ///
+    /// When a run exceeds the timeout, its [`AbortToken`](crate::AbortToken) is aborted. The run
+    /// is not dropped, so the implementation can return an error.
+    ///
+    /// Defaults to `None`, runs never time out.
+    const TIMEOUT: Option<Duration> = None;
+
+    /// Returns the key that the result of the mutation with `input` is cached with.
+    ///
+    /// Mutations are not cached by default. If a key is returned, the mutation is treated as
+    /// idempotent (e.g.: "ensure a resource exists"): a run with the same key as a run in flight
+    /// waits for the result of that run, and a successful result is reused by runs with the same
+    /// key for [`CACHE_DURATION`](Self::CACHE_DURATION), across all components under the same
+    /// `<BounceRoot />`. Failed runs are not cached.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use std::time::Duration;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{Mutation, MutationResult, ProgressSink};
+    /// # use async_trait::async_trait;
+    /// #
+    /// #[derive(Debug, PartialEq)]
+    /// struct EnsureWorkspace {
+    ///     id: u64,
+    /// }
+    ///
+    /// #[async_trait(?Send)]
+    /// impl Mutation for EnsureWorkspace {
+    ///     type Input = String;
+    ///     type Error = Infallible;
+    ///
+    ///     const CACHE_DURATION: Duration = Duration::from_secs(300);
+    ///
+    ///     async fn run(
+    ///         _states: &BounceStates,
+    ///         _input: Rc<String>,
+    ///         _progress: ProgressSink,
+    ///     ) -> MutationResult<Self> {
+    ///         // creates the workspace if it does not exist.
+    ///
+    ///         Ok(EnsureWorkspace { id: 1 }.into())
+    ///     }
+    ///
+    ///     fn cache_key(input: &String) -> Option<String> {
+    ///         Some(input.clone())
+    ///     }
+    /// }
+    /// ```
+    #[allow(unused_variables)]
+    fn cache_key(input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    /// Returns the class of an error returned by this mutation.
+    ///
+    /// When a mutation fails, a [`MutationErrorNotion`](super::MutationErrorNotion) with the
+    /// class of the error is applied to the bounce root.
+    ///
+    /// See: [`Query::classify_error`]
+    fn classify_error(_error: &Self::Error) -> ErrorClass {
+        ErrorClass::Unknown
+    }
 }
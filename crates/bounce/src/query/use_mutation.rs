@@ -1,20 +1,68 @@
+use std::cell::Cell;
 use std::fmt;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::time::Duration;
 
+use wasm_bindgen::UnwrapThrowExt;
 use yew::platform::pinned::oneshot;
 use yew::prelude::*;
 
 use super::traits::{Mutation, MutationResult};
-use crate::states::future_notion::{use_future_notion_runner, FutureNotion};
+use crate::clock::{use_clock, Clock};
+use crate::root_state::BounceRootState;
+use crate::states::future_notion::{use_internal_future_notion_runner, FutureNotion};
 use crate::states::input_selector::use_input_selector_value;
 use crate::states::slice::use_slice_dispatch;
+use crate::utils::use_root_scoped_id;
 
 use super::mutation_states::{
-    HandleId, MutationId, MutationSelector, MutationSlice, MutationSliceAction, MutationSliceValue,
-    RunMutation, RunMutationInput,
+    HandleId, MutationId, MutationRuns, MutationSelector, MutationSlice, MutationSliceAction,
+    MutationSliceValue, RunMutation, RunMutationInput,
 };
 
+/// A sink to report the progress of a mutation.
+///
+/// See: [`Mutation::run`]
+#[derive(Clone)]
+pub struct ProgressSink {
+    inner: Rc<dyn Fn(f64)>,
+}
+
+impl ProgressSink {
+    pub(super) fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(f64),
+    {
+        Self { inner: Rc::new(f) }
+    }
+
+    /// Creates a sink that discards all reported progress.
+    ///
+    /// This is useful when a mutation is run outside of [`use_mutation`].
+    pub fn noop() -> Self {
+        Self::new(|_| {})
+    }
+
+    /// Reports the progress of the mutation.
+    ///
+    /// The progress is a fraction between `0.0` and `1.0`, values out of this range are clamped
+    /// and `NaN` is ignored.
+    pub fn report(&self, progress: f64) {
+        if progress.is_nan() {
+            return;
+        }
+
+        (self.inner)(progress.clamp(0.0, 1.0));
+    }
+}
+
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressSink").finish_non_exhaustive()
+    }
+}
+
 /// Mutation State
 #[derive(Debug, PartialEq)]
 pub enum MutationState<T>
@@ -25,6 +73,11 @@ where
     Idle,
     /// The mutation is loading.
     Loading,
+    /// The mutation is loading and has reported its progress.
+    InProgress {
+        /// The latest reported progress, between `0.0` and `1.0`.
+        progress: f64,
+    },
     /// The mutation has completed.
     Completed {
         /// Result of the completed mutation.
@@ -45,6 +98,9 @@ where
         match self {
             Self::Idle => Self::Idle,
             Self::Loading => Self::Loading,
+            Self::InProgress { progress } => Self::InProgress {
+                progress: *progress,
+            },
             Self::Completed { result } => Self::Completed {
                 result: result.clone(),
             },
@@ -80,7 +136,10 @@ where
 {
     id: HandleId,
     state: Rc<MutationState<T>>,
+    progress: Option<f64>,
     run_mutation: Rc<dyn Fn(<RunMutation<T> as FutureNotion>::Input)>,
+    latest_run: Rc<Cell<Option<MutationId>>>,
+    clock: Rc<dyn Clock>,
     _marker: PhantomData<T>,
 }
 
@@ -100,7 +159,7 @@ where
     /// - `Some(Err(e))` indicates that the last mutation has failed and the error is stored in `e`.
     pub fn result(&self) -> Option<&MutationResult<T>> {
         match self.state() {
-            MutationState::Idle | MutationState::Loading => None,
+            MutationState::Idle | MutationState::Loading | MutationState::InProgress { .. } => None,
             MutationState::Completed { result }
             | MutationState::Refreshing {
                 last_result: result,
@@ -108,7 +167,18 @@ where
         }
     }
 
+    /// Returns the latest progress reported by the loading mutation (if any).
+    ///
+    /// The progress is available while the mutation is loading or refreshing and is reset to
+    /// `None` when the mutation completes.
+    pub fn progress(&self) -> Option<f64> {
+        self.progress
+    }
+
     /// Runs a mutation with input.
+    ///
+    /// The [`AbortToken`](crate::AbortToken) of this mutation is aborted when the component of
+    /// this handle is unmounted.
     pub async fn run(&self, input: impl Into<Rc<T::Input>>) -> MutationResult<T> {
         let id = MutationId::default();
         let input = input.into();
@@ -119,10 +189,73 @@ where
             mutation_id: id,
             input,
             sender: Some(sender).into(),
+            latest_only: false,
         });
 
         receiver.await.unwrap()
     }
+
+    /// Runs a mutation with input and only keeps the result of the latest run.
+    ///
+    /// If another mutation is started with [`run_latest`](Self::run_latest) or
+    /// [`run_debounced`](Self::run_debounced) on this handle before this mutation completes,
+    /// the result of this mutation is discarded and `None` is returned.
+    ///
+    /// This is useful for forms that are saved automatically, where an earlier save should not
+    /// overwrite the result of a later save.
+    ///
+    /// The [`AbortToken`](crate::AbortToken) of this mutation is aborted when a newer run
+    /// supersedes it or when the component of this handle is unmounted.
+    pub async fn run_latest(&self, input: impl Into<Rc<T::Input>>) -> Option<MutationResult<T>> {
+        let id = MutationId::default();
+        self.latest_run.set(Some(id));
+
+        self.run_latest_with_id(id, input.into()).await
+    }
+
+    /// Runs a mutation with input after `delay`, only keeping the result of the latest run.
+    ///
+    /// If another mutation is started with [`run_latest`](Self::run_latest) or
+    /// [`run_debounced`](Self::run_debounced) on this handle before `delay` has elapsed, this
+    /// mutation is not run. Otherwise, this method behaves like [`run_latest`](Self::run_latest).
+    ///
+    /// Returns `None` if the mutation is not run or its result is discarded.
+    pub async fn run_debounced(
+        &self,
+        input: impl Into<Rc<T::Input>>,
+        delay: Duration,
+    ) -> Option<MutationResult<T>> {
+        let id = MutationId::default();
+        self.latest_run.set(Some(id));
+
+        self.clock.sleep(delay).await;
+
+        if self.latest_run.get() != Some(id) {
+            return None;
+        }
+
+        self.run_latest_with_id(id, input.into()).await
+    }
+
+    async fn run_latest_with_id(
+        &self,
+        id: MutationId,
+        input: Rc<T::Input>,
+    ) -> Option<MutationResult<T>> {
+        let (sender, receiver) = oneshot::channel();
+
+        (self.run_mutation)(RunMutationInput {
+            handle_id: self.id,
+            mutation_id: id,
+            input,
+            sender: Some(sender).into(),
+            latest_only: true,
+        });
+
+        let result = receiver.await.unwrap();
+
+        (self.latest_run.get() == Some(id)).then_some(result)
+    }
 }
 
 impl<T> fmt::Debug for UseMutationHandle<T>
@@ -144,7 +277,10 @@ where
         Self {
             id: self.id,
             state: self.state.clone(),
+            progress: self.progress,
             run_mutation: self.run_mutation.clone(),
+            latest_run: self.latest_run.clone(),
+            clock: self.clock.clone(),
             _marker: PhantomData,
         }
     }
@@ -161,7 +297,7 @@ where
 /// use std::rc::Rc;
 /// use std::convert::Infallible;
 /// use bounce::prelude::*;
-/// use bounce::query::{Mutation, MutationResult, use_mutation, MutationState};
+/// use bounce::query::{Mutation, MutationResult, use_mutation, MutationState, ProgressSink};
 /// use yew::prelude::*;
 /// use async_trait::async_trait;
 /// use yew::platform::spawn_local;
@@ -181,7 +317,11 @@ where
 ///     type Input = User;
 ///     type Error = Infallible;
 ///
-///     async fn run(_states: &BounceStates, _input: Rc<User>) -> MutationResult<Self> {
+///     async fn run(
+///         _states: &BounceStates,
+///         _input: Rc<User>,
+///         _progress: ProgressSink,
+///     ) -> MutationResult<Self> {
 ///         // updates the user information.
 ///
 ///         Ok(UpdateUserMutation {}.into())
@@ -225,25 +365,42 @@ pub fn use_mutation<T>() -> UseMutationHandle<T>
 where
     T: Mutation + 'static,
 {
-    let id = *use_memo((), |_| HandleId::default());
+    let id = HandleId(use_root_scoped_id());
     let dispatch_state = use_slice_dispatch::<MutationSlice<T>>();
-    let run_mutation = use_future_notion_runner::<RunMutation<T>>();
+    let run_mutation = use_internal_future_notion_runner::<RunMutation<T>>();
     let state = use_input_selector_value::<MutationSelector<T>>(id.into());
+    let latest_run = use_memo((), |_| Cell::new(None));
+    let clock = use_clock();
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
 
     {
-        use_effect_with(id, |id| {
+        use_effect_with(id, move |id| {
             let id = *id;
             dispatch_state(MutationSliceAction::Create(id));
 
             move || {
                 dispatch_state(MutationSliceAction::Destroy(id));
+                // The runs of the handle are no longer needed once the handle is destroyed.
+                root.get_state::<MutationRuns<T>>().cancel(id);
             }
         });
     }
 
+    let progress = match state.value.as_ref() {
+        Some(MutationSliceValue::Loading { progress, .. })
+        | Some(MutationSliceValue::Outdated { progress, .. }) => *progress,
+        Some(MutationSliceValue::Idle) | Some(MutationSliceValue::Completed { .. }) | None => None,
+    };
+
     let state = use_memo(state, |state| match state.value.as_ref() {
         Some(MutationSliceValue::Idle) | None => MutationState::Idle,
-        Some(MutationSliceValue::Loading { .. }) => MutationState::Loading,
+        Some(MutationSliceValue::Loading { progress: None, .. }) => MutationState::Loading,
+        Some(MutationSliceValue::Loading {
+            progress: Some(progress),
+            ..
+        }) => MutationState::InProgress {
+            progress: *progress,
+        },
         Some(MutationSliceValue::Completed { result, .. }) => MutationState::Completed {
             result: result.clone(),
         },
@@ -255,7 +412,10 @@ where
     UseMutationHandle {
         id,
         state,
+        progress,
         run_mutation,
+        latest_run,
+        clock,
         _marker: PhantomData,
     }
 }
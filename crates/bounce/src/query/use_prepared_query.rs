@@ -3,19 +3,25 @@ use std::rc::Rc;
 use serde::de::Deserialize;
 use serde::ser::Serialize;
 use wasm_bindgen::UnwrapThrowExt;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew::suspense::{Suspension, SuspensionResult};
 
+use super::options::{use_query_options, SsrPolicy};
+use super::prepared::Prepared;
 use super::query_states::{
-    QuerySelector, QuerySlice, QuerySliceAction, QuerySliceValue, RunQuery, RunQueryInput,
+    has_query_value, QuerySelector, QuerySlice, QuerySliceAction, QuerySliceValue, RunQuery,
+    RunQueryInput,
 };
-use super::traits::Query;
+use super::subscriptions::use_query_subscription;
+use super::traits::{Query, QueryResult};
 use super::use_query::{QueryState, UseQueryHandle};
+use super::use_query_value::{use_query_value, UseQueryValueHandle};
 use crate::root_state::BounceRootState;
-use crate::states::future_notion::use_future_notion_runner;
-use crate::states::input_selector::use_input_selector_value;
+use crate::states::future_notion::use_internal_future_notion_runner;
+use crate::states::input_selector::{use_input_selector_value, use_interned_input};
 use crate::states::slice::use_slice_dispatch;
-use crate::utils::Id;
+use crate::utils::{use_root_scoped_id, Id};
 
 /// A hook to run a query and subscribes to its result, suspending while fetching
 /// if server-side rendered values are not available.
@@ -28,6 +34,38 @@ use crate::utils::Id;
 ///
 /// If your endpoint modifies data, then you need to use a [mutation](super::use_mutation_value).
 ///
+/// # Encoding
+///
+/// The result of a prepared query is embedded into the server-side rendered html.
+/// To reduce the size of the html, the following features can be enabled:
+///
+/// - `prepared-codec`: encodes the result with a compact binary format ([postcard](https://docs.rs/postcard)).
+/// - `prepared-compression`: also compresses large results with deflate.
+///
+/// The server and the client must be built with the same features.
+///
+/// The result can also be signed or encrypted with a
+/// [`PreparedTransform`](super::PreparedTransform), it is then encoded with postcard without the
+/// `prepared-codec` feature.
+///
+/// Results are only prepared with the `ssr` feature, which enables server-side rendering of Yew.
+///
+/// # Pairing
+///
+/// Each prepared result is embedded with the input it was fetched with. When hydrating, a
+/// result is loaded into the cache for that input, so a result that is paired with another hook
+/// (e.g.: when nested suspense boundaries resolve in a different order than on the server) is
+/// still loaded for the query it belongs to. A hook whose input does not match the embedded input
+/// only fetches its own result if no other hook has loaded a result for its input by the end of
+/// the render pass.
+///
+/// # Server-side Rendering Policy
+///
+/// If the [`SsrPolicy`] of the query is [`Skip`](SsrPolicy::Skip), no result is embedded in the
+/// html. As this hook suspends until a result is available, the server still waits for the
+/// query to render the page, and the query is fetched again after hydration. Use
+/// [`use_prepared_query_value`] to render skipped queries as loading on the server.
+///
 /// # Example
 ///
 /// ```
@@ -86,75 +124,18 @@ where
     T::Input: Clone + Serialize + for<'de> Deserialize<'de>,
     T::Error: Clone + Serialize + for<'de> Deserialize<'de>,
 {
-    let id = *use_memo((), |_| Id::new());
+    let input = use_interned_input("use_prepared_query", input);
+    let id = use_root_scoped_id();
     let value_state = use_input_selector_value::<QuerySelector<T>>(input.clone());
     let dispatch_state = use_slice_dispatch::<QuerySlice<T>>();
-    let run_query = use_future_notion_runner::<RunQuery<T>>();
-
-    let prepared_value = {
-        let _run_query = run_query.clone();
-        let _root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
-
-        let prepared_value =
-            use_prepared_state!((*input).clone(), async move |input| -> std::result::Result<
-                T,
-                T::Error,
-            > {
-                use std::cell::RefCell;
-                use std::time::Duration;
-
-                use yew::platform::pinned::oneshot;
-                use yew::platform::time::sleep;
-
-                let (sender, receiver) = oneshot::channel();
-
-                _run_query(RunQueryInput {
-                    id,
-                    input: input.clone(),
-                    sender: Rc::new(RefCell::new(Some(sender))),
-                    is_refresh: false,
-                });
+    let run_query = use_internal_future_notion_runner::<RunQuery<T>>();
+    let options = use_query_options::<T>();
+    use_query_subscription::<T>(Some(input.clone()));
 
-                if let Ok(m) = receiver.await {
-                    return m.map(|m| (*m).clone());
-                }
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
 
-                loop {
-                    let states = _root.states();
-                    let value_state =
-                        states.get_input_selector_value::<QuerySelector<T>>(input.clone());
-
-                    match value_state.value {
-                        Some(QuerySliceValue::Completed { result: ref m, .. })
-                        | Some(QuerySliceValue::Outdated { result: ref m, .. }) => {
-                            return m.clone().map(|m| (*m).clone());
-                        }
-                        None | Some(QuerySliceValue::Loading { .. }) => {
-                            let (sender, receiver) = oneshot::channel::<()>();
-                            let sender = Rc::new(RefCell::new(Some(sender)));
-
-                            states.add_listener_callback(Rc::new(Callback::from(move |_| {
-                                if let Some(m) = sender.borrow_mut().take() {
-                                    let _ = m.send(());
-                                }
-                            })));
-                            // We subscribe to the selector again.
-                            states.get_input_selector_value::<QuerySelector<T>>(input.clone());
-
-                            // We yield to event loop so state updates can be applied.
-                            sleep(Duration::ZERO).await;
-
-                            receiver.await.unwrap();
-                        }
-                    }
-                }
-            })?;
-
-        (*use_memo(prepared_value, |p| {
-            p.clone().map(|m| (*m).clone().map(Rc::new))
-        }))
-        .clone()
-    };
+    let prepared_value =
+        use_prepared_result::<T>(input.clone(), id, options.ssr == SsrPolicy::Skip)?;
 
     let value = use_memo(value_state.clone(), |v| match v.value {
         Some(QuerySliceValue::Loading { .. }) | None => Err(Suspension::new()),
@@ -174,18 +155,39 @@ where
         let run_query = run_query.clone();
         let dispatch_state = dispatch_state.clone();
 
-        use_memo((), move |_| match prepared_value {
-            Some(m) => dispatch_state(QuerySliceAction::LoadPrepared {
-                id,
-                input,
-                result: m,
-            }),
-            None => run_query(RunQueryInput {
-                id,
-                input: input.clone(),
-                sender: Rc::default(),
-                is_refresh: false,
-            }),
+        use_memo((), move |_| {
+            let matches = match prepared_value {
+                Some((prepared_input, result)) => {
+                    let matches = prepared_input == input;
+
+                    // The result is loaded for the input it was prepared with, even if it was
+                    // prepared for another hook.
+                    dispatch_state(QuerySliceAction::LoadPrepared {
+                        id,
+                        input: prepared_input,
+                        result,
+                    });
+
+                    matches
+                }
+                None => false,
+            };
+
+            if !matches {
+                // The result of this input may be prepared for a hook that is rendered later
+                // in the same render pass, so the query is only fetched if no result has been
+                // loaded for this input once the render pass has finished.
+                spawn_local(async move {
+                    if !has_query_value::<T>(&root, &input) {
+                        run_query(RunQueryInput {
+                            id,
+                            input,
+                            sender: Rc::default(),
+                            is_refresh: false,
+                        });
+                    }
+                });
+            }
         });
     }
 
@@ -221,3 +223,234 @@ where
         Err((s, _)) => Err(s.clone()),
     }
 }
+
+/// The input that a result is prepared with and the result.
+type PreparedResult<T> = (Rc<<T as Query>::Input>, QueryResult<T>);
+
+/// Prepares the result of a query during server-side rendering and returns the prepared input
+/// and result on the client (if any).
+///
+/// If `skip` is `true`, the query is not fetched and no result is prepared.
+#[hook]
+fn use_prepared_result<T>(
+    input: Rc<T::Input>,
+    id: Id,
+    skip: bool,
+) -> SuspensionResult<Option<PreparedResult<T>>>
+where
+    T: Query + Clone + Serialize + for<'de> Deserialize<'de> + 'static,
+    T::Input: Clone + Serialize + for<'de> Deserialize<'de>,
+    T::Error: Clone + Serialize + for<'de> Deserialize<'de>,
+{
+    let _run_query = use_internal_future_notion_runner::<RunQuery<T>>();
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let _root = root.clone();
+    let _input = input;
+    let _id = id;
+    let _skip = skip;
+
+    // The value is paired by the input it contains instead of the dependencies of the
+    // prepared state, which are paired by the order that hooks are rendered in.
+    let prepared_value = use_prepared_state!((), async move |_deps| -> Prepared<T> {
+        #[cfg(feature = "ssr")]
+        return prepare_result(_root, _run_query, _input, _id, _skip).await;
+
+        // Results are only prepared with the `ssr` feature, the query is fetched on the client.
+        #[cfg(not(feature = "ssr"))]
+        return Prepared::Value(None);
+    })?;
+
+    Ok((*use_memo(prepared_value, move |p| {
+        p.as_ref()
+            .and_then(|m| m.decode(&root))
+            .map(|(input, result)| (Rc::new(input), result.map(Rc::new)))
+    }))
+    .clone())
+}
+
+/// Fetches the result of a query to prepare it during server-side rendering.
+#[cfg(feature = "ssr")]
+async fn prepare_result<T>(
+    root: BounceRootState,
+    run_query: Rc<dyn Fn(RunQueryInput<T>)>,
+    input: Rc<T::Input>,
+    id: Id,
+    skip: bool,
+) -> Prepared<T>
+where
+    T: Query + Clone + Serialize + for<'de> Deserialize<'de> + 'static,
+    T::Input: Clone + Serialize + for<'de> Deserialize<'de>,
+    T::Error: Clone + Serialize + for<'de> Deserialize<'de>,
+{
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    use yew::platform::pinned::oneshot;
+    use yew::platform::time::sleep;
+
+    if skip {
+        return Prepared::new(&root, None);
+    }
+
+    let prepared_input = (*input).clone();
+    let prepared_root = root.clone();
+    super::preload::record_preload_hints(&root, T::preload_hints(&input));
+
+    let result = async move {
+        let (sender, receiver) = oneshot::channel();
+
+        run_query(RunQueryInput {
+            id,
+            input: input.clone(),
+            sender: Rc::new(RefCell::new(Some(sender))),
+            is_refresh: false,
+        });
+
+        if let Ok(m) = receiver.await {
+            return m.map(|m| (*m).clone());
+        }
+
+        loop {
+            let states = root.states();
+            let value_state = states.get_input_selector_value::<QuerySelector<T>>(input.clone());
+
+            match value_state.value {
+                Some(QuerySliceValue::Completed { result: ref m, .. })
+                | Some(QuerySliceValue::Outdated { result: ref m, .. }) => {
+                    return m.clone().map(|m| (*m).clone());
+                }
+                None | Some(QuerySliceValue::Loading { .. }) => {
+                    let (sender, receiver) = oneshot::channel::<()>();
+                    let sender = Rc::new(RefCell::new(Some(sender)));
+
+                    states.add_listener_callback(Rc::new(Callback::from(move |_| {
+                        if let Some(m) = sender.borrow_mut().take() {
+                            let _ = m.send(());
+                        }
+                    })));
+                    // We subscribe to the selector again.
+                    states.get_input_selector_value::<QuerySelector<T>>(input.clone());
+
+                    // We yield to event loop so state updates can be applied.
+                    sleep(Duration::ZERO).await;
+
+                    receiver.await.unwrap();
+                }
+            }
+        }
+    }
+    .await;
+
+    Prepared::new(&prepared_root, Some((prepared_input, result)))
+}
+
+/// A hook to run a query and subscribe to its result, with the result prepared during
+/// server-side rendering according to the [`SsrPolicy`] of the query.
+///
+/// - [`SsrPolicy::Block`]: the server-side rendering suspends until the query has completed and
+///   the result is embedded in the rendered html, like [`use_prepared_query`].
+/// - [`SsrPolicy::Skip`]: the query is not fetched on the server and is rendered as
+///   [loading](QueryValueState::Loading). It is fetched after the application is hydrated, so
+///   widgets that are not critical to the page do not delay the server-side rendering.
+///
+/// Unlike [`use_prepared_query`], this hook only suspends during server-side rendering and
+/// returns a [`UseQueryValueHandle`] which can be loading on the client.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{
+/// #     use_prepared_query_value, Query, QueryResult, QueryValueState, SsrPolicy,
+/// # };
+/// # use yew::prelude::*;
+/// # use async_trait::async_trait;
+/// # use serde::{Serialize, Deserialize};
+/// #
+/// # #[derive(thiserror::Error, Debug, PartialEq, Serialize, Deserialize, Clone)]
+/// # #[error("Something that will never happen")]
+/// # struct Never {}
+/// #
+/// #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+/// struct RecommendationsQuery {
+///     titles: Vec<String>,
+/// }
+///
+/// #[async_trait(?Send)]
+/// impl Query for RecommendationsQuery {
+///     type Input = u64;
+///     type Error = Never;
+///
+///     // Recommendations are not critical, the page is rendered without them.
+///     const SSR: SsrPolicy = SsrPolicy::Skip;
+///
+///     async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+///         // fetch recommendations
+///
+///         Ok(RecommendationsQuery { titles: vec!["Bounce".into()] }.into())
+///     }
+/// }
+///
+/// #[function_component(Recommendations)]
+/// fn recommendations() -> HtmlResult {
+///     let recommendations = use_prepared_query_value::<RecommendationsQuery>(1.into())?;
+///
+///     Ok(match recommendations.state() {
+///         QueryValueState::Loading => html! { <div>{"Loading..."}</div> },
+///         QueryValueState::Completed { result: Ok(m) }
+///         | QueryValueState::Refreshing { last_result: Ok(m) } => {
+///             html! { <div>{m.titles.join(", ")}</div> }
+///         }
+///         QueryValueState::Completed { .. } | QueryValueState::Refreshing { .. } => {
+///             html! { <div>{"Oops, something went wrong."}</div> }
+///         }
+///     })
+/// }
+/// #
+/// # #[function_component(App)]
+/// # fn app() -> Html {
+/// #     html! {
+/// #         <bounce::BounceRoot>
+/// #             <Suspense>
+/// #                 <Recommendations />
+/// #             </Suspense>
+/// #         </bounce::BounceRoot>
+/// #     }
+/// # }
+/// #
+/// # let renderer = yew::ServerRenderer::<App>::new().hydratable(false);
+/// # let s = futures::executor::block_on(renderer.render());
+/// # // The query is not fetched while rendering on the server.
+/// # assert!(s.starts_with("<div>Loading...</div>"));
+/// ```
+#[hook]
+pub fn use_prepared_query_value<T>(input: Rc<T::Input>) -> SuspensionResult<UseQueryValueHandle<T>>
+where
+    T: Query + Clone + Serialize + for<'de> Deserialize<'de> + 'static,
+    T::Input: Clone + Serialize + for<'de> Deserialize<'de>,
+    T::Error: Clone + Serialize + for<'de> Deserialize<'de>,
+{
+    let input = use_interned_input("use_prepared_query_value", input);
+    let id = use_root_scoped_id();
+    let options = use_query_options::<T>();
+    let dispatch_state = use_slice_dispatch::<QuerySlice<T>>();
+
+    let prepared_value =
+        use_prepared_result::<T>(input.clone(), id, options.ssr == SsrPolicy::Skip)?;
+
+    use_memo((), move |_| {
+        // The result is loaded for the input it was prepared with. If it was prepared for
+        // another input, the query of this hook is fetched by `use_query_value` once the render
+        // pass has finished and no result has been loaded for its input.
+        if let Some((prepared_input, result)) = prepared_value {
+            dispatch_state(QuerySliceAction::LoadPrepared {
+                id,
+                input: prepared_input,
+                result,
+            });
+        }
+    });
+
+    Ok(use_query_value::<T>(input))
+}
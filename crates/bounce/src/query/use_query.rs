@@ -1,20 +1,28 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::error::Error;
 use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
+use std::time::Duration;
 
+use futures::future::{select, FutureExt};
 use yew::platform::pinned::oneshot;
 use yew::prelude::*;
 use yew::suspense::{Suspension, SuspensionResult};
 
+use super::options::{use_query_options, use_refresh_stale};
+use super::polling::use_polling;
 use super::query_states::{
     QuerySelector, QuerySlice, QuerySliceAction, QuerySliceValue, RunQuery, RunQueryInput,
 };
+use super::restore::use_refresh_on_restore;
+use super::subscriptions::use_query_subscription;
 use super::traits::{Query, QueryResult};
-use crate::states::future_notion::use_future_notion_runner;
-use crate::states::input_selector::use_input_selector_value;
+use crate::clock::use_clock;
+use crate::states::future_notion::use_internal_future_notion_runner;
+use crate::states::input_selector::{use_input_selector_value, use_interned_input};
 use crate::states::slice::use_slice_dispatch;
-use crate::utils::Id;
+use crate::utils::{use_root_scoped_id, Id};
 
 /// Query State
 #[derive(Debug, PartialEq)]
@@ -212,10 +220,15 @@ pub fn use_query<T>(input: Rc<T::Input>) -> SuspensionResult<UseQueryHandle<T>>
 where
     T: Query + 'static,
 {
-    let id = *use_memo((), |_| Id::new());
+    let input = use_interned_input("use_query", input);
+    let id = use_root_scoped_id();
     let value_state = use_input_selector_value::<QuerySelector<T>>(input.clone());
     let dispatch_state = use_slice_dispatch::<QuerySlice<T>>();
-    let run_query = use_future_notion_runner::<RunQuery<T>>();
+    let run_query = use_internal_future_notion_runner::<RunQuery<T>>();
+    let options = use_query_options::<T>();
+    let previous = use_mut_ref(|| None::<(Id, QueryResult<T>)>);
+    let started = use_memo((), |_| Cell::new(false));
+    use_query_subscription::<T>(Some(input.clone()));
 
     let value = use_memo(value_state.clone(), |v| match v.value {
         Some(QuerySliceValue::Loading { .. }) | None => Err(Suspension::new()),
@@ -231,16 +244,23 @@ where
     });
 
     {
-        let input = input.clone();
         let run_query = run_query.clone();
 
-        use_memo((), move |_| {
-            run_query(RunQueryInput {
-                id,
-                input: input.clone(),
-                sender: Rc::default(),
-                is_refresh: false,
-            });
+        // Effects are not run while the component is suspended, so the query is started during
+        // rendering. It is started again if its result is removed while the component is
+        // mounted.
+        let is_removed = value_state.value.is_none();
+
+        use_memo((input.clone(), is_removed), move |(input, is_removed)| {
+            // The memo is also recomputed when a restarted query starts loading.
+            if *is_removed || !started.replace(true) {
+                run_query(RunQueryInput {
+                    id,
+                    input: input.clone(),
+                    sender: Rc::default(),
+                    is_refresh: false,
+                });
+            }
         });
     }
 
@@ -265,16 +285,151 @@ where
         );
     }
 
-    value
-        .as_ref()
-        .as_ref()
-        .cloned()
-        .map(|(state_id, state)| UseQueryHandle {
-            state,
-            state_id,
-            input,
-            dispatch_state,
-            run_query,
+    use_refresh_stale::<T>(options, Some(input.clone()));
+
+    use_polling::<T>(options, Some(input.clone()));
+
+    use_refresh_on_restore::<T>(options, Some(input.clone()));
+
+    let value = match value.as_ref().as_ref().cloned() {
+        Ok((state_id, state)) => {
+            let result = match *state {
+                QueryState::Completed { ref result } => result.clone(),
+                QueryState::Refreshing { ref last_result } => last_result.clone(),
+            };
+            *previous.borrow_mut() = Some((state_id, result));
+
+            Ok((state_id, state))
+        }
+        Err((s, _)) => match *previous.borrow() {
+            // The result of the previous input is shown while the result of this input is loading.
+            Some((state_id, ref last_result)) if options.keep_previous_data => Ok((
+                state_id,
+                Rc::new(QueryState::Refreshing {
+                    last_result: last_result.clone(),
+                }),
+            )),
+            _ => Err(s.clone()),
+        },
+    };
+
+    value.map(|(state_id, state)| UseQueryHandle {
+        state,
+        state_id,
+        input,
+        dispatch_state,
+        run_query,
+    })
+}
+
+/// An error returned by [`use_query_with_timeout`] when a query has been loading for longer than
+/// the timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QueryTimedOut {
+    /// The timeout that has elapsed.
+    pub timeout: Duration,
+}
+
+impl fmt::Display for QueryTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "query has not completed within {:?}", self.timeout)
+    }
+}
+
+impl Error for QueryTimedOut {}
+
+/// A hook to run a query and subscribes to its result, suspending while fetching for at most
+/// `timeout`.
+///
+/// This hook behaves like [`use_query`], except that if the query is still loading after
+/// `timeout`, the component stops suspending and the hook returns a [`QueryTimedOut`] error.
+/// This allows the component to escalate (e.g.: render a retry button or a degraded view)
+/// instead of leaving the suspense fallback spinning forever.
+///
+/// The query is not cancelled when the timeout elapses. If it completes later, the component is
+/// re-rendered with the result. The timeout starts again each time the hook suspends for a new
+/// input.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use std::time::Duration;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{Query, QueryResult, use_query_with_timeout};
+/// # use yew::prelude::*;
+/// # use async_trait::async_trait;
+/// #
+/// # #[derive(Debug, PartialEq)]
+/// # struct UserQuery {
+/// #     name: String,
+/// # }
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for UserQuery {
+/// #     type Input = u64;
+/// #     type Error = Infallible;
+/// #
+/// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+/// #         Ok(UserQuery { name: "John Smith".into() }.into())
+/// #     }
+/// # }
+/// #
+/// #[function_component(Comp)]
+/// fn comp() -> HtmlResult {
+///     let user = use_query_with_timeout::<UserQuery>(0.into(), Duration::from_secs(10))?;
+///
+///     match user {
+///         Ok(m) => match m.as_ref() {
+///             Ok(m) => Ok(html! {<div>{"User's name is "}{m.name.to_string()}</div>}),
+///             Err(_e) => Ok(html! {<div>{"Oops, something went wrong."}</div>}),
+///         },
+///         // The query is still loading, it is rendered when it completes.
+///         Err(_timed_out) => Ok(html! {<div>{"This is taking longer than usual..."}</div>}),
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_query_with_timeout<T>(
+    input: Rc<T::Input>,
+    timeout: Duration,
+) -> SuspensionResult<Result<UseQueryHandle<T>, QueryTimedOut>>
+where
+    T: Query + 'static,
+{
+    let result = use_query::<T>(input.clone());
+    let clock = use_clock();
+
+    // A timer is started each time the query starts suspending for an input.
+    let timer = use_memo((input, result.is_err()), move |(_, suspended)| {
+        suspended.then(|| {
+            let timed_out = Rc::new(Cell::new(false));
+            let suspension = {
+                let timed_out = timed_out.clone();
+                let sleep = clock.sleep(timeout);
+                Suspension::from_future(async move {
+                    sleep.await;
+                    timed_out.set(true);
+                })
+            };
+
+            (timed_out, suspension)
         })
-        .map_err(|(s, _)| s.clone())
+    });
+
+    match result {
+        Ok(m) => Ok(Ok(m)),
+        Err(query_suspension) => match timer.as_ref() {
+            Some((timed_out, _)) if timed_out.get() => Ok(Err(QueryTimedOut { timeout })),
+            Some((_, timer_suspension)) => {
+                // Resumes when either the query completes or the timeout elapses.
+                let timer_suspension = timer_suspension.clone();
+                Err(Suspension::from_future(
+                    select(query_suspension, timer_suspension).map(|_| ()),
+                ))
+            }
+            None => Err(query_suspension),
+        },
+    }
 }
@@ -1,19 +1,28 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::error::Error;
 use std::fmt;
 use std::rc::Rc;
+use std::time::Duration;
 
+use wasm_bindgen::UnwrapThrowExt;
 use yew::platform::pinned::oneshot;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 
+use super::options::{use_query_options, use_refresh_stale, QueryOptions};
+use super::polling::use_polling;
 use super::query_states::{
-    QuerySelector, QuerySlice, QuerySliceAction, QuerySliceValue, RunQuery, RunQueryInput,
+    MaybeQuerySelector, QuerySlice, QuerySliceAction, QuerySliceValue, RunQuery, RunQueryInput,
 };
-
+use super::restore::use_refresh_on_restore;
+use super::subscriptions::{use_query_subscription, QuerySubscribers};
 use super::traits::{Query, QueryResult};
-use crate::states::future_notion::use_future_notion_runner;
-use crate::states::input_selector::use_input_selector_value;
-use crate::states::slice::use_slice_dispatch;
-use crate::utils::Id;
+use crate::clock::{use_clock, Clock};
+use crate::root_state::BounceRootState;
+use crate::states::future_notion::use_internal_future_notion_runner;
+use crate::states::input_selector::{use_input_selector_value, use_interned_input};
+use crate::states::slice::{use_slice_dispatch, SliceState};
+use crate::utils::{use_root_scoped_id, Id};
 
 /// Query Value State
 #[derive(Debug, PartialEq)]
@@ -70,6 +79,34 @@ where
     }
 }
 
+/// How a query hook obtains its result.
+///
+/// See [`use_query_value_with_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FetchPolicy {
+    /// Returns the cached result (if any) and runs the query if it is not cached or outdated.
+    #[default]
+    CacheFirst,
+    /// Only returns the cached result and never runs the query.
+    ///
+    /// The query is not run when the result is missing, outdated or stale and it is not polled.
+    /// If the query is completed by another hook or refreshed with
+    /// [`UseQueryValueHandle::refresh`], the hook is updated with the new result.
+    CacheOnly,
+}
+
+/// An error returned by [`UseQueryValueHandle::cached`] if the result of a query is not cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QueryMissing;
+
+impl fmt::Display for QueryMissing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "query result is not cached")
+    }
+}
+
+impl Error for QueryMissing {}
+
 /// A handle returned by [`use_query_value`].
 pub struct UseQueryValueHandle<T>
 where
@@ -77,8 +114,12 @@ where
 {
     input: Rc<T::Input>,
     state: Rc<QueryValueState<T>>,
+    is_cached: bool,
     run_query: Rc<dyn Fn(RunQueryInput<T>)>,
     dispatch_state: Rc<dyn Fn(QuerySliceAction<T>)>,
+    refresh_generation: Rc<Cell<u64>>,
+    clock: Rc<dyn Clock>,
+    subscribers: QuerySubscribers<T>,
 }
 
 impl<T> UseQueryValueHandle<T>
@@ -106,6 +147,18 @@ where
         }
     }
 
+    /// Returns the cached result of the input of this hook.
+    ///
+    /// Unlike [`result`](Self::result), this never returns the result of a previous input kept
+    /// with [`QueryOptions::keep_previous_data`](super::QueryOptions::keep_previous_data) and
+    /// returns a [`QueryMissing`] error instead of `None` if nothing is cached.
+    pub fn cached(&self) -> Result<&QueryResult<T>, QueryMissing> {
+        match self.result() {
+            Some(m) if self.is_cached => Ok(m),
+            _ => Err(QueryMissing),
+        }
+    }
+
     /// Refreshes the query.
     ///
     /// The query will be refreshed with the input provided to the hook.
@@ -127,6 +180,47 @@ where
 
         receiver.await.unwrap()
     }
+
+    /// Schedules a refresh of the query after `delay`.
+    ///
+    /// Only 1 refresh can be scheduled at a time, scheduling a refresh replaces the refresh that
+    /// is currently scheduled (if any).
+    ///
+    /// The scheduled refresh is cancelled if the input of the hook changes or the component is
+    /// unmounted before it is run.
+    pub fn schedule_refresh(&self, delay: Duration) {
+        let generation = self.refresh_generation.get() + 1;
+        self.refresh_generation.set(generation);
+
+        let this = self.clone();
+        let sleep = self.clock.sleep(delay);
+
+        spawn_local(async move {
+            sleep.await;
+
+            // The refresh has been cancelled or replaced.
+            if this.refresh_generation.get() != generation {
+                return;
+            }
+
+            let _ = this.refresh().await;
+        });
+    }
+
+    /// Returns the number of mounted hooks that are subscribed to the query with the input of
+    /// this hook, including this one.
+    ///
+    /// Hooks with the same query and input share a single request. A hook is counted once its
+    /// component is mounted, so this returns `0` during the first render.
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count(&self.input)
+    }
+
+    /// Cancels the refresh scheduled with [`schedule_refresh`](Self::schedule_refresh) (if any).
+    pub fn cancel_scheduled_refresh(&self) {
+        self.refresh_generation
+            .set(self.refresh_generation.get() + 1);
+    }
 }
 
 impl<T> Clone for UseQueryValueHandle<T>
@@ -137,8 +231,12 @@ where
         Self {
             input: self.input.clone(),
             state: self.state.clone(),
+            is_cached: self.is_cached,
             run_query: self.run_query.clone(),
             dispatch_state: self.dispatch_state.clone(),
+            refresh_generation: self.refresh_generation.clone(),
+            clock: self.clock.clone(),
+            subscribers: self.subscribers.clone(),
         }
     }
 }
@@ -162,6 +260,9 @@ where
 /// Queries are usually tied to idempotent methods like `GET`, which means that they should be side-effect
 /// free and can be cached.
 ///
+/// If a result is cached for the input, it is returned in the same render, including the first
+/// render and the render after the input changes, without going through a loading state.
+///
 /// If your endpoint modifies data, then you need to use a [mutation](super::use_mutation_value).
 ///
 /// # Example
@@ -216,18 +317,131 @@ pub fn use_query_value<T>(input: Rc<T::Input>) -> UseQueryValueHandle<T>
 where
     T: Query + 'static,
 {
-    let id = *use_memo((), |_| Id::new());
-    let value = use_input_selector_value::<QuerySelector<T>>(input.clone());
+    use_query_value_with_policy::<T>(input, FetchPolicy::CacheFirst)
+}
+
+/// A hook to subscribe to the result of a query with a [`FetchPolicy`].
+///
+/// With [`FetchPolicy::CacheFirst`], this hook behaves like [`use_query_value`].
+///
+/// With [`FetchPolicy::CacheOnly`], the query is never run by this hook and the cached result is
+/// returned as is. This can be used for screens that must work offline or to render a preview of
+/// data that has been fetched before. Use [`cached`](UseQueryValueHandle::cached) to tell a
+/// missing result apart from a loading one.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::convert::Infallible;
+/// # use bounce::prelude::*;
+/// # use bounce::query::{Query, QueryResult};
+/// # use yew::prelude::*;
+/// # use async_trait::async_trait;
+/// use bounce::query::{use_query_value_with_policy, FetchPolicy};
+///
+/// # #[derive(Debug, PartialEq)]
+/// # struct UserQuery {
+/// #     name: String,
+/// # }
+/// #
+/// # #[async_trait(?Send)]
+/// # impl Query for UserQuery {
+/// #     type Input = u64;
+/// #     type Error = Infallible;
+/// #
+/// #     async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+/// #         Ok(UserQuery { name: "John Smith".into() }.into())
+/// #     }
+/// # }
+/// #
+/// #[function_component(UserPreview)]
+/// fn user_preview() -> Html {
+///     let user = use_query_value_with_policy::<UserQuery>(0.into(), FetchPolicy::CacheOnly);
+///
+///     match user.cached() {
+///         Ok(Ok(m)) => html! {<div>{"User's name is "}{m.name.to_string()}</div>},
+///         Ok(Err(_)) => html! {<div>{"Oops, something went wrong."}</div>},
+///         // The user has not been fetched before.
+///         Err(_) => html! {<div>{"Not available offline."}</div>},
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_query_value_with_policy<T>(
+    input: Rc<T::Input>,
+    policy: FetchPolicy,
+) -> UseQueryValueHandle<T>
+where
+    T: Query + 'static,
+{
+    let input = use_interned_input("use_query_value", input);
+
+    use_optional_query_value::<T>(Some(input), policy).unwrap_throw()
+}
+
+/// Connects to the query of an input that may not be available yet (e.g.: an input derived from
+/// the current route).
+///
+/// Returns `None` while there is no input. Hooks are called in the same order either way.
+#[hook]
+pub(super) fn use_optional_query_value<T>(
+    input: Option<Rc<T::Input>>,
+    policy: FetchPolicy,
+) -> Option<UseQueryValueHandle<T>>
+where
+    T: Query + 'static,
+{
+    let cache_only = policy == FetchPolicy::CacheOnly;
+    let id = use_root_scoped_id();
+    // The same `Rc` is passed to the selector while the input is equal.
+    let selector_input = use_memo(input.clone(), |m| m.clone());
+    let value = use_input_selector_value::<MaybeQuerySelector<T>>(selector_input);
     let dispatch_state = use_slice_dispatch::<QuerySlice<T>>();
-    let run_query = use_future_notion_runner::<RunQuery<T>>();
+    let run_query = use_internal_future_notion_runner::<RunQuery<T>>();
+    let refresh_generation = use_memo((), |_| Cell::new(0));
+    let clock = use_clock();
+    let options = match use_query_options::<T>() {
+        // Stale results are not refreshed and the query is not polled.
+        m if cache_only => QueryOptions {
+            stale_time: None,
+            poll_interval: None,
+            refresh_on_restore: false,
+            ..m
+        },
+        m => m,
+    };
+    let previous = use_mut_ref(|| None::<QueryResult<T>>);
+    let subscribers = use_query_subscription::<T>(input.clone());
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    {
+        let refresh_generation = refresh_generation.clone();
+        // Scheduled refreshes are cancelled when the input changes or the component is unmounted.
+        use_effect_with(input.clone(), move |_| {
+            move || refresh_generation.set(refresh_generation.get() + 1)
+        });
+    }
 
     {
         let input = input.clone();
         let run_query = run_query.clone();
         use_effect_with(
-            (id, input, value.value.clone()),
-            move |(id, input, value)| {
-                if value.is_none() || matches!(value, Some(QuerySliceValue::Outdated { .. })) {
+            (id, input, value.value.clone(), cache_only),
+            move |(id, input, _value, cache_only)| {
+                let input = match input {
+                    Some(m) => m,
+                    None => return,
+                };
+
+                // The state is read when the effect runs, a result for this input may have been
+                // loaded by another hook (e.g.: a prepared result) since this hook was rendered.
+                let value = root.get_state::<SliceState<QuerySlice<T>>>().get();
+                let value = value.get(input);
+                let should_run =
+                    value.is_none() || matches!(value, Some(QuerySliceValue::Outdated { .. }));
+
+                if should_run && !*cache_only {
                     run_query(RunQueryInput {
                         id: *id,
                         input: input.clone(),
@@ -235,12 +449,16 @@ where
                         is_refresh: false,
                     });
                 }
-
-                || {}
             },
         );
     }
 
+    use_refresh_stale::<T>(options, input.clone());
+
+    use_polling::<T>(options, input.clone());
+
+    use_refresh_on_restore::<T>(options, input.clone());
+
     let state = use_memo(value, |value| match value.value {
         Some(QuerySliceValue::Completed { ref result, .. }) => QueryValueState::Completed {
             result: result.clone(),
@@ -251,10 +469,34 @@ where
         Some(QuerySliceValue::Loading { .. }) | None => QueryValueState::Loading,
     });
 
-    UseQueryValueHandle {
+    let is_cached = !matches!(*state, QueryValueState::Loading);
+
+    let state = match *state {
+        QueryValueState::Completed { ref result }
+        | QueryValueState::Refreshing {
+            last_result: ref result,
+        } => {
+            *previous.borrow_mut() = Some(result.clone());
+            state
+        }
+        // The result of the previous input is shown while the result of this input is loading.
+        QueryValueState::Loading if options.keep_previous_data => match *previous.borrow() {
+            Some(ref m) => Rc::new(QueryValueState::Refreshing {
+                last_result: m.clone(),
+            }),
+            None => state,
+        },
+        QueryValueState::Loading => state,
+    };
+
+    input.map(|input| UseQueryValueHandle {
         input,
         dispatch_state,
         run_query,
         state,
-    }
+        is_cached,
+        refresh_generation,
+        clock,
+        subscribers,
+    })
 }
@@ -1,7 +1,7 @@
-use std::any::{Any, TypeId};
-use std::cell::RefCell;
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::rc::Rc;
 
@@ -9,88 +9,424 @@ use anymap2::any::CloneAny;
 use anymap2::{AnyMap, Entry, Map};
 use yew::callback::Callback;
 
+use crate::abort::AbortToken;
 use crate::any_state::AnyState;
+use crate::clock::{Clock, SystemClock};
+use crate::notion_bus::NotionBus;
+use crate::overrides::StateOverrides;
+use crate::services::Services;
 use crate::states::artifact::ArtifactSlice;
 use crate::states::atom::{Atom, AtomSlice};
+use crate::states::derived::{DerivedSelector, DerivedValue};
 use crate::states::input_selector::{InputSelector, InputSelectorsState};
+use crate::states::notion::NotionBatch;
+use crate::states::registry::StateDescriptor;
 use crate::states::selector::{Selector, UnitSelector};
 use crate::states::slice::{Slice, SliceState};
-use crate::utils::Id;
 use crate::utils::Listener;
+use crate::utils::{batch_notifications, Id, IdGenerator};
 
 pub(crate) type StateMap = Map<dyn CloneAny>;
 type AnyStateMap = HashMap<TypeId, Vec<Rc<dyn AnyState>>>;
 
+/// The maximum length of a chain of notions, each applied while the previous one is applied or
+/// its listeners are notified.
+///
+/// Exceeding this length usually means that states are applying notions to each other in a cycle.
+const MAX_NOTION_DEPTH: usize = 100;
+
+/// A notion with its type erased.
+#[derive(Clone)]
+pub(crate) struct AnyNotion {
+    id: TypeId,
+    name: &'static str,
+    notion: Rc<dyn Any>,
+}
+
+impl AnyNotion {
+    pub fn new<T>(notion: Rc<T>) -> Self
+    where
+        T: 'static,
+    {
+        Self {
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+            notion,
+        }
+    }
+}
+
+struct QueuedNotion {
+    notion: AnyNotion,
+    /// The number of notions that caused this notion to be applied.
+    depth: usize,
+}
+
+/// Resets the flushing flag of the notion queue, even if a notion panics.
+struct FlushGuard<'a> {
+    flushing: &'a Cell<bool>,
+}
+
+impl Drop for FlushGuard<'_> {
+    fn drop(&mut self) {
+        self.flushing.set(false);
+    }
+}
+
+/// Removes a state from the creation stack once it has been created, even if creation panics.
+struct CreationGuard<'a> {
+    creating: &'a RefCell<Vec<(TypeId, &'static str)>>,
+}
+
+impl Drop for CreationGuard<'_> {
+    fn drop(&mut self) {
+        self.creating.borrow_mut().pop();
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct BounceRootState {
     id: Id,
     init_states: Rc<RefCell<AnyMap>>,
+    creating: Rc<RefCell<Vec<(TypeId, &'static str)>>>,
+    services: Rc<Services>,
+    overrides: Rc<StateOverrides>,
     states: Rc<RefCell<StateMap>>,
+    registered: Rc<RefCell<Vec<Rc<dyn AnyState>>>>,
     notion_states: Rc<RefCell<AnyStateMap>>,
+    notion_queue: Rc<RefCell<VecDeque<QueuedNotion>>>,
+    notion_flushing: Rc<Cell<bool>>,
+    notion_depth: Rc<Cell<usize>>,
+    ids: Rc<IdGenerator>,
+    clock: Rc<dyn Clock>,
 }
 
 impl Default for BounceRootState {
     fn default() -> Self {
-        Self::new(AnyMap::new())
+        Self::new(AnyMap::new(), Services::new(), StateOverrides::new())
     }
 }
 
 impl BounceRootState {
     #[inline]
-    pub fn new(init_states: AnyMap) -> Self {
-        Self {
+    pub fn new(init_states: AnyMap, services: Services, overrides: StateOverrides) -> Self {
+        let clock = services
+            .get::<dyn Clock>()
+            .unwrap_or_else(|| Rc::new(SystemClock));
+        let bus = services.get::<NotionBus>();
+
+        let this = Self {
             id: Id::new(),
             init_states: Rc::new(RefCell::new(init_states)),
+            creating: Rc::default(),
+            services: Rc::new(services),
+            overrides: Rc::new(overrides),
             states: Rc::default(),
+            registered: Rc::default(),
             notion_states: Rc::default(),
+            notion_queue: Rc::default(),
+            notion_flushing: Rc::default(),
+            notion_depth: Rc::default(),
+            ids: Rc::default(),
+            clock,
+        };
+
+        if let Some(m) = bus {
+            m.attach(this.clone());
         }
+
+        #[cfg(feature = "testing")]
+        crate::testing::register_root(&this);
+
+        this
     }
 
     pub fn get_state<T>(&self) -> T
     where
         T: AnyState + Clone + Default + 'static,
     {
-        let mut states = self.states.borrow_mut();
-
-        match states.entry::<T>() {
-            Entry::Occupied(m) => m.get().clone(),
-            Entry::Vacant(m) => {
-                let state = {
-                    let mut init_states = self.init_states.borrow_mut();
-                    T::create(&mut init_states)
-                };
-                m.insert(state.clone());
-
-                let mut notion_states = self.notion_states.borrow_mut();
-                for notion_id in state.notion_ids() {
-                    match notion_states.entry(notion_id) {
-                        hash_map::Entry::Occupied(mut m) => {
-                            m.get_mut().push(Rc::new(state.clone()) as Rc<dyn AnyState>);
-                        }
-                        hash_map::Entry::Vacant(m) => {
-                            m.insert(vec![Rc::new(state.clone()) as Rc<dyn AnyState>]);
+        if let Some(m) = self.states.borrow().get::<T>() {
+            return m.clone();
+        }
+
+        // States may read other states when they are created, so the state map is not borrowed
+        // during creation.
+        let state = match self.enter_creation::<T>() {
+            Some(_guard) => T::create_from_root(self),
+            None => self.with_init_states(T::create),
+        };
+
+        {
+            let mut states = self.states.borrow_mut();
+
+            match states.entry::<T>() {
+                Entry::Occupied(m) => return m.get().clone(),
+                Entry::Vacant(m) => {
+                    m.insert(state.clone());
+                    self.registered
+                        .borrow_mut()
+                        .push(Rc::new(state.clone()) as Rc<dyn AnyState>);
+
+                    let mut notion_states = self.notion_states.borrow_mut();
+                    for notion_id in state.notion_ids() {
+                        match notion_states.entry(notion_id) {
+                            hash_map::Entry::Occupied(mut m) => {
+                                m.get_mut().push(Rc::new(state.clone()) as Rc<dyn AnyState>);
+                            }
+                            hash_map::Entry::Vacant(m) => {
+                                m.insert(vec![Rc::new(state.clone()) as Rc<dyn AnyState>]);
+                            }
                         }
                     }
                 }
+            }
+        }
 
-                state
+        // The state map is not borrowed, so states can read other states when they are
+        // initialised.
+        state.init();
+
+        state
+    }
+
+    /// Marks a state as being created.
+    ///
+    /// Returns `None` if the state is already being created, which means that states depend on
+    /// each other in a cycle.
+    fn enter_creation<T>(&self) -> Option<CreationGuard<'_>>
+    where
+        T: 'static,
+    {
+        let id = TypeId::of::<T>();
+
+        let mut creating = self.creating.borrow_mut();
+
+        if let Some(pos) = creating.iter().position(|(m, _)| *m == id) {
+            let cycle = creating[pos..]
+                .iter()
+                .map(|(_, name)| *name)
+                .chain([type_name::<T>()])
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            drop(creating);
+
+            #[cfg(debug_assertions)]
+            panic!("states are created from each other in a cycle: {cycle}");
+
+            #[cfg(not(debug_assertions))]
+            {
+                tracing::error!(
+                    "states are created from each other in a cycle: {cycle}, \
+                     the last state is created with its default value."
+                );
+                return None;
             }
         }
+
+        creating.push((id, type_name::<T>()));
+
+        Some(CreationGuard {
+            creating: &self.creating,
+        })
+    }
+
+    /// Returns the value of a slice overridden by the root (if any).
+    pub fn get_override<T>(&self) -> Option<Rc<T>>
+    where
+        T: Slice + 'static,
+    {
+        self.overrides.get::<T>()
+    }
+
+    /// Runs a function with the initial values provided to the root.
+    pub fn with_init_states<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut AnyMap) -> R,
+    {
+        let mut init_states = self.init_states.borrow_mut();
+        f(&mut init_states)
     }
 
+    /// Applies a notion to all states that accept it.
+    ///
+    /// States receive a notion in the order they are registered. Notions applied while another
+    /// notion is being applied (e.g.: by a listener) are queued and applied in order after the
+    /// current notion has been delivered to all states.
+    ///
+    /// Listeners are notified after the notion has been applied to all states and each listener
+    /// is only notified once.
     pub fn apply_notion<T>(&self, notion: Rc<T>)
     where
         T: 'static,
     {
-        let notion_state = self.notion_states.borrow().get(&TypeId::of::<T>()).cloned();
+        self.apply_any_notions(vec![AnyNotion::new(notion)]);
+    }
+
+    /// Applies a batch of notions to all states that accept them.
+    ///
+    /// The notions are applied in order and listeners are notified once after the whole batch
+    /// has been applied, so observers never see a state where only some of the notions have been
+    /// applied.
+    pub fn apply_notions(&self, notions: NotionBatch) {
+        self.apply_any_notions(notions.into_inner());
+    }
+
+    fn apply_any_notions(&self, notions: Vec<AnyNotion>) {
+        self.forward_notions(&notions);
+
+        let depth = self.notion_depth.get();
+        self.notion_queue.borrow_mut().extend(
+            notions
+                .into_iter()
+                .map(|notion| QueuedNotion { notion, depth }),
+        );
+
+        self.drain_notion_queue();
+    }
+
+    /// Applies notions that are shared by a [`NotionBus`] to other roots attached to the bus.
+    fn forward_notions(&self, notions: &[AnyNotion]) {
+        let bus = match self.services.get::<NotionBus>() {
+            Some(m) => m,
+            None => return,
+        };
+
+        let shared = notions
+            .iter()
+            .filter(|m| bus.is_shared(m.id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !shared.is_empty() {
+            bus.forward(self, &shared);
+        }
+    }
+
+    /// Applies notions forwarded by a [`NotionBus`] from another root.
+    ///
+    /// Forwarded notions are not forwarded again.
+    pub(crate) fn apply_forwarded_notions(&self, notions: &[AnyNotion]) {
+        let depth = self.notion_depth.get();
+        self.notion_queue.borrow_mut().extend(
+            notions
+                .iter()
+                .cloned()
+                .map(|notion| QueuedNotion { notion, depth }),
+        );
+
+        self.drain_notion_queue();
+    }
+
+    fn drain_notion_queue(&self) {
+        // The notions will be applied by the call that is currently draining the queue.
+        if self.notion_flushing.replace(true) {
+            return;
+        }
+
+        let depth = self.notion_depth.get();
+
+        // Listeners are notified once after the notions are applied to all states.
+        // Notions applied by listeners start a new flush.
+        batch_notifications(|| {
+            let _guard = FlushGuard {
+                flushing: &self.notion_flushing,
+            };
 
-        let notion = notion as Rc<dyn Any>;
+            self.flush_notions();
+        });
 
-        if let Some(m) = notion_state {
-            for any_state in m.iter() {
-                any_state.apply(notion.clone());
+        self.notion_depth.set(depth);
+    }
+
+    fn flush_notions(&self) {
+        // The depth of notions applied by listeners of this flush.
+        let mut listener_depth = self.notion_depth.get();
+
+        loop {
+            let QueuedNotion {
+                notion: AnyNotion { id, name, notion },
+                depth,
+            } = match self.notion_queue.borrow_mut().pop_front() {
+                Some(m) => m,
+                None => break,
+            };
+
+            if depth >= MAX_NOTION_DEPTH {
+                #[cfg(debug_assertions)]
+                panic!(
+                    "notion {name} is applied by a chain of more than {MAX_NOTION_DEPTH} notions, \
+                     there may be a cycle involving this notion."
+                );
+
+                // Only the notion in the cycle is dropped, other queued notions are applied.
+                #[cfg(not(debug_assertions))]
+                {
+                    tracing::error!(
+                        "notion {name} is applied by a chain of more than {MAX_NOTION_DEPTH} \
+                         notions, there may be a cycle involving this notion."
+                    );
+                    continue;
+                }
+            }
+
+            // Notions applied while this notion is applied are caused by this notion.
+            self.notion_depth.set(depth + 1);
+            listener_depth = listener_depth.max(depth + 1);
+
+            let notion_state = self.notion_states.borrow().get(&id).cloned();
+
+            if let Some(m) = notion_state {
+                #[cfg(feature = "devtools")]
+                let mut trace = crate::devtools::NotionTraceRecorder::new(name);
+
+                for any_state in m.iter() {
+                    let accepted = any_state.accepts(&*notion);
+
+                    #[cfg(feature = "devtools")]
+                    trace.record(any_state.as_ref(), accepted);
+
+                    if accepted {
+                        any_state.apply(notion.clone());
+                    }
+                }
+
+                #[cfg(feature = "devtools")]
+                trace.finish();
             }
         }
+
+        // Listeners are notified after the flush, notions they apply are caused by the notions of
+        // this flush.
+        self.notion_depth.set(listener_depth);
+    }
+
+    pub fn get_service<T>(&self) -> Option<Rc<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        self.services.get::<T>()
+    }
+
+    /// Creates an id that is unique within this root.
+    ///
+    /// Ids created while rendering are the same during server-side rendering and hydration.
+    pub fn next_id(&self) -> Id {
+        self.ids.next()
+    }
+
+    /// Returns the clock of this root.
+    pub fn clock(&self) -> Rc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    /// Returns the descriptions of registered states in the order they are registered.
+    pub fn registered_states(&self) -> Vec<StateDescriptor> {
+        self.registered
+            .borrow()
+            .iter()
+            .filter_map(|m| m.describe())
+            .collect()
     }
 
     pub fn states(&self) -> BounceStates {
@@ -98,11 +434,30 @@ impl BounceRootState {
             inner: self.clone(),
             listeners: Rc::default(),
             listener_callbacks: Rc::default(),
+            snapshot: Rc::default(),
+            abort_token: AbortToken::default(),
         }
     }
 
     pub fn clear(&self) {
+        #[cfg(feature = "testing")]
+        crate::testing::unregister_root(self);
+
+        if let Some(m) = self.services.get::<NotionBus>() {
+            m.detach(self);
+        }
+
+        self.notion_queue.borrow_mut().clear();
+
+        // States are torn down in the reverse order they are created, so states are torn down
+        // before the states they are created from.
+        let registered = self.registered.take();
+        for state in registered.iter().rev() {
+            state.teardown();
+        }
+
         self.notion_states.borrow_mut().clear();
+        self.registered.borrow_mut().clear();
         self.states.borrow_mut().clear();
     }
 }
@@ -113,11 +468,39 @@ impl PartialEq for BounceRootState {
     }
 }
 
+/// Values of slices read during a snapshot, keyed by the type id of the slice.
+type SnapshotValues = HashMap<TypeId, Rc<dyn Any>>;
+
+/// Ends a snapshot when dropped.
+struct SnapshotGuard<'a> {
+    snapshot: &'a RefCell<Option<SnapshotValues>>,
+}
+
+impl Drop for SnapshotGuard<'_> {
+    fn drop(&mut self) {
+        self.snapshot.borrow_mut().take();
+    }
+}
+
 /// A type to access states under a bounce root.
+///
+/// # Consistency
+///
+/// The states passed to [`Selector::select`] and [`InputSelector::select`] are a snapshot of
+/// the root: each slice or atom read during 1 `select()` call has the same value however many
+/// times it is read, and listeners are not notified of updates until `select()` returns. A
+/// selector that reads multiple states never observes some of them before an update and others
+/// after it. If a state it has read is updated during `select()`, the selector is selected again
+/// afterwards.
+///
+/// Other code can read states from the same snapshot with
+/// [`with_snapshot`](Self::with_snapshot).
 pub struct BounceStates {
     inner: BounceRootState,
     listeners: Rc<RefCell<Vec<Listener>>>,
     listener_callbacks: Rc<RefCell<Vec<Rc<Callback<()>>>>>,
+    snapshot: Rc<RefCell<Option<SnapshotValues>>>,
+    abort_token: AbortToken,
 }
 
 impl BounceStates {
@@ -140,7 +523,16 @@ impl BounceStates {
 
         self.listeners.borrow_mut().extend(listeners);
 
-        state.get()
+        let mut snapshot = self.snapshot.borrow_mut();
+        match snapshot.as_mut() {
+            Some(values) => values
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| state.get() as Rc<dyn Any>)
+                .clone()
+                .downcast::<T>()
+                .expect("snapshot value has a different type."),
+            None => state.get(),
+        }
     }
 
     /// Returns the value of an `Atom`.
@@ -164,7 +556,7 @@ impl BounceStates {
         let mut listeners = Vec::new();
 
         for callback in listener_callbacks {
-            let listener = state.listen(Rc::new(Callback::from(move |_: Rc<T>| {
+            let listener = state.listen(Rc::new(Callback::from(move |_| {
                 callback.emit(());
             })));
 
@@ -186,7 +578,19 @@ impl BounceStates {
             .clone()
     }
 
-    /// Returns all values of an [`Artifact`](crate::Artifact).
+    /// Returns the value of a [`DerivedSelector`].
+    ///
+    /// The selector that reads the value is selected again when the derived value changes.
+    pub fn get_derived_value<D>(&self, derived: D) -> Rc<D::Output>
+    where
+        D: DerivedSelector,
+    {
+        self.get_input_selector_value::<DerivedValue<D>>(derived.into())
+            .inner
+            .clone()
+    }
+
+    /// Returns all values of an [`Artifact`](struct@crate::Artifact).
     pub fn get_artifacts<T>(&self) -> Vec<Rc<T>>
     where
         T: PartialEq + 'static,
@@ -194,6 +598,160 @@ impl BounceStates {
         self.get_slice_value::<ArtifactSlice<T>>().get()
     }
 
+    /// Returns a service provided to the `<BounceRoot />`.
+    ///
+    /// Returns `None` if the service is not provided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bounce::prelude::*;
+    /// struct ApiClient {
+    ///     base_url: String,
+    /// }
+    ///
+    /// #[future_notion(FetchUser)]
+    /// async fn fetch_user(states: &BounceStates, id: &u64) -> String {
+    ///     let client = states.get_service::<ApiClient>().expect("api client is not provided");
+    ///
+    ///     format!("{}/users/{}", client.base_url, id)
+    /// }
+    /// ```
+    ///
+    /// See: [`Services`]
+    pub fn get_service<T>(&self) -> Option<Rc<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        self.inner.get_service::<T>()
+    }
+
+    /// Reads states from a consistent snapshot of the root.
+    ///
+    /// Each slice or atom read within `f` has the same value however many times it is read, and
+    /// listeners are notified of updates made within `f` after it returns. If this is called
+    /// while a snapshot is already active (e.g.: during [`Selector::select`]), the active
+    /// snapshot is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bounce::prelude::*;
+    /// #[derive(PartialEq, Default, Atom)]
+    /// struct Subtotal(u64);
+    ///
+    /// #[derive(PartialEq, Default, Atom)]
+    /// struct Shipping(u64);
+    ///
+    /// #[future_notion(SubmitOrder)]
+    /// async fn submit_order(states: &BounceStates, _input: &()) -> u64 {
+    ///     // Both atoms are read from the same snapshot.
+    ///     states.with_snapshot(|states| {
+    ///         states.get_atom_value::<Subtotal>().0 + states.get_atom_value::<Shipping>().0
+    ///     })
+    /// }
+    /// ```
+    pub fn with_snapshot<R>(&self, f: impl FnOnce(&BounceStates) -> R) -> R {
+        let started = {
+            let mut snapshot = self.snapshot.borrow_mut();
+            let started = snapshot.is_none();
+            if started {
+                *snapshot = Some(HashMap::new());
+            }
+
+            started
+        };
+
+        let _guard = started.then(|| SnapshotGuard {
+            snapshot: &self.snapshot,
+        });
+
+        batch_notifications(|| f(self))
+    }
+
+    /// Returns the token that is aborted when the run of a query or a mutation that these states
+    /// are passed to is cancelled.
+    ///
+    /// Queries and mutations can wire the token into their requests (see [`AbortToken`]), so
+    /// requests that are no longer needed do not keep consuming bandwidth. Outside of queries and
+    /// mutations, the token is never aborted.
+    ///
+    /// A query is cancelled when a refresh of the same input supersedes it, when the last
+    /// component that subscribes to it is unmounted or when it exceeds its timeout. A mutation is
+    /// cancelled when a newer latest-only run of the same handle supersedes it, when the
+    /// component of its handle is unmounted or when it exceeds its timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bounce::BounceStates;
+    /// use futures::future::{select, Either};
+    ///
+    /// # async fn fetch_user(_id: u64) -> String { String::new() }
+    /// // Called by the query of a user.
+    /// async fn fetch_user_name(states: &BounceStates, id: u64) -> Option<String> {
+    ///     let fetch = Box::pin(fetch_user(id));
+    ///
+    ///     // The request is dropped when the query is cancelled.
+    ///     match select(fetch, Box::pin(states.abort_token().aborted())).await {
+    ///         Either::Left((m, _)) => Some(m),
+    ///         Either::Right(_) => None,
+    ///     }
+    /// }
+    /// ```
+    pub fn abort_token(&self) -> AbortToken {
+        self.abort_token.clone()
+    }
+
+    /// Returns states that share these states, with the abort token of a run.
+    #[cfg(feature = "query")]
+    pub(crate) fn with_abort_token(&self, abort_token: AbortToken) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            listeners: self.listeners.clone(),
+            listener_callbacks: self.listener_callbacks.clone(),
+            snapshot: self.snapshot.clone(),
+            abort_token,
+        }
+    }
+
+    /// Reads states without subscribing to them.
+    ///
+    /// States read within `f` are not tracked: a change of these states does not cause a selector
+    /// to be selected again, a future notion to be outdated or a query to be run again. This can
+    /// be used to read a value that is intentionally captured as a snapshot (e.g.: a locale used
+    /// to format a query result that should not be fetched again when the locale changes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bounce::prelude::*;
+    /// #[derive(PartialEq, Default, Atom)]
+    /// struct DraftId(u64);
+    ///
+    /// #[derive(PartialEq, Default, Atom)]
+    /// struct Locale(String);
+    ///
+    /// #[future_notion(SaveDraft)]
+    /// async fn save_draft(states: &BounceStates, _input: &()) -> String {
+    ///     // The notion is outdated when the draft changes.
+    ///     let id = states.get_atom_value::<DraftId>().0;
+    ///     // A change of locale does not affect this run.
+    ///     let locale = states.untracked(|states| states.get_atom_value::<Locale>().0.clone());
+    ///
+    ///     format!("{locale}: {id}")
+    /// }
+    /// ```
+    pub fn untracked<R>(&self, f: impl FnOnce(&BounceStates) -> R) -> R {
+        f(&self.derived_clone())
+    }
+
+    /// Returns the root state that these states belong to.
+    #[cfg(feature = "query")]
+    pub(crate) fn root(&self) -> &BounceRootState {
+        &self.inner
+    }
+
     pub(crate) fn add_listener_callback(&self, callback: Rc<Callback<()>>) {
         let mut listener_callbacks = self.listener_callbacks.borrow_mut();
         listener_callbacks.push(callback);
@@ -213,11 +771,16 @@ impl BounceStates {
     }
 
     /// Creates a sub-states, but with a separate listener holder.
+    ///
+    /// The sub-states share the snapshot of these states, so selectors selected for the first
+    /// time during a snapshot read from the same snapshot.
     fn derived_clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
             listeners: Rc::default(),
             listener_callbacks: Rc::default(),
+            snapshot: self.snapshot.clone(),
+            abort_token: self.abort_token.clone(),
         }
     }
 }
@@ -0,0 +1,85 @@
+use std::fmt;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+
+/// A collection of services that can be provided to a `<BounceRoot />`.
+///
+/// Services are arbitrary objects (e.g.: API clients, token providers) that can be accessed by
+/// queries, mutations and future notions with
+/// [`BounceStates::get_service`](crate::BounceStates::get_service).
+///
+/// Each type can only be provided once, providing the same type again replaces the previous
+/// value.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::{BounceRoot, Services};
+/// #
+/// struct ApiClient {
+///     base_url: String,
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     let get_services = Callback::from(|_| {
+///         Services::new().provide(ApiClient {
+///             base_url: "https://api.example.com".into(),
+///         })
+///     });
+///
+///     html! {
+///         <BounceRoot {get_services}>
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct Services {
+    inner: AnyMap,
+}
+
+impl Services {
+    /// Creates an empty collection of services.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Provides a service.
+    pub fn provide<T>(mut self, service: T) -> Self
+    where
+        T: 'static,
+    {
+        self.inner.insert(Rc::new(service));
+        self
+    }
+
+    /// Provides a service that is already wrapped in an `Rc`.
+    ///
+    /// This can be used to provide a trait object, e.g.: `Rc<dyn TokenProvider>`.
+    pub fn provide_rc<T>(mut self, service: Rc<T>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        self.inner.insert(service);
+        self
+    }
+
+    pub(crate) fn get<T>(&self) -> Option<Rc<T>>
+    where
+        T: ?Sized + 'static,
+    {
+        self.inner.get::<Rc<T>>().cloned()
+    }
+}
+
+impl fmt::Debug for Services {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Services")
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
@@ -0,0 +1,539 @@
+//! Persisted settings with a schema.
+//!
+//! An application declares its settings once as a struct and derives [`Settings`](macro@Settings)
+//! on it. The settings are stored in an atom that is persisted to the
+//! [`PersistStorage`](crate::persist::PersistStorage) of the root (the local storage by default)
+//! and restored when they are used for the first time. Stored settings of an older version are
+//! migrated with [`Settings::migrate`].
+//!
+//! Settings are read and updated with [`use_settings`], or field by field with [`use_setting`].
+//! [`Settings::fields`] describes the fields (names, types, defaults and documentation) so a
+//! preferences panel can be built from the struct.
+//!
+//! # Example
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! # use yew::prelude::*;
+//! use bounce::settings::{use_setting, Settings};
+//!
+//! #[derive(Settings, Serialize, Deserialize, Debug, Clone, PartialEq)]
+//! #[bounce(key = "app-settings", version = 2)]
+//! #[serde(default)]
+//! struct AppSettings {
+//!     /// Use the dark theme.
+//!     dark_mode: bool,
+//!     /// The size of the font in pixels.
+//!     font_size: u32,
+//! }
+//!
+//! impl Default for AppSettings {
+//!     fn default() -> Self {
+//!         Self {
+//!             dark_mode: false,
+//!             font_size: 16,
+//!         }
+//!     }
+//! }
+//!
+//! #[function_component(DarkModeToggle)]
+//! fn dark_mode_toggle() -> Html {
+//!     let dark_mode = use_setting::<AppSettings, _>(|m| &mut m.dark_mode);
+//!
+//!     let onclick = {
+//!         let dark_mode = dark_mode.clone();
+//!         Callback::from(move |_| dark_mode.set(!*dark_mode))
+//!     };
+//!
+//!     html! { <button {onclick}>{"Toggle dark mode"}</button> }
+//! }
+//!
+//! let fields = AppSettings::fields();
+//!
+//! assert_eq!(fields[1].name, "font_size");
+//! assert_eq!(fields[1].ty, "u32");
+//! assert_eq!(fields[1].default, 16);
+//! assert_eq!(fields[1].description, "The size of the font in pixels.");
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+
+use crate::any_state::AnyState;
+use crate::hydration::{use_hydration_tracker, HydrationTracker};
+use crate::persist::{get_storage, read_stored, StorageWriter};
+use crate::root_state::BounceRootState;
+use crate::states::atom::{use_atom, Atom, AtomSlice, UseAtomHandle};
+use crate::states::slice::SliceState;
+use crate::utils::Listener;
+
+/// Derives [`Settings`](trait@Settings) for a struct with named fields.
+///
+/// The key of the storage is set with `#[bounce(key = "...")]`. The version of the schema
+/// can be set with `#[bounce(version = 2)]` and a function that migrates older versions with
+/// `#[bounce(migrate = migrate_settings)]`, which has the signature of [`Settings::migrate`].
+///
+/// The documentation of each field is used as its description in [`Settings::fields`].
+///
+/// See: [`bounce::settings`](crate::settings)
+pub use bounce_macros::Settings;
+
+/// A struct of settings that is persisted to the storage of the root.
+///
+/// This trait should be implemented with the derive macro, see
+/// [`Settings`](macro@Settings).
+pub trait Settings: Default + Clone + PartialEq + Serialize + DeserializeOwned + 'static {
+    /// The key of the settings in the storage.
+    const KEY: &'static str;
+
+    /// The version of the schema of the settings.
+    ///
+    /// When the version of stored settings is different, the stored value is migrated with
+    /// [`Settings::migrate`].
+    const VERSION: u32 = 1;
+
+    /// Migrates stored settings of `version` to the current version.
+    ///
+    /// `version` can also be newer than the current version if the application has been
+    /// downgraded. When `None` is returned, the default settings are used.
+    ///
+    /// Defaults to discarding settings of other versions.
+    #[allow(unused_variables)]
+    fn migrate(version: u32, value: Value) -> Option<Value> {
+        None
+    }
+
+    /// Returns the fields of the settings.
+    fn fields() -> Vec<SettingsField>;
+}
+
+/// A field of [`Settings`], used to build preferences panels.
+///
+/// See: [`Settings::fields`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsField {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The type of the field as it is written in the struct, e.g.: `Option<String>`.
+    pub ty: &'static str,
+    /// The default value of the field.
+    pub default: Value,
+    /// The documentation of the field, or an empty string if the field is not documented.
+    pub description: &'static str,
+}
+
+impl SettingsField {
+    /// Creates a field.
+    ///
+    /// The default value is serialised to JSON, or [`Value::Null`] if it cannot be serialised.
+    pub fn new<V>(
+        name: &'static str,
+        ty: &'static str,
+        default: &V,
+        description: &'static str,
+    ) -> Self
+    where
+        V: Serialize + ?Sized,
+    {
+        Self {
+            name,
+            ty,
+            default: serde_json::to_value(default).unwrap_or_default(),
+            description,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredSettings {
+    version: u32,
+    value: Value,
+}
+
+fn load<T>(stored: StoredSettings) -> Option<T>
+where
+    T: Settings,
+{
+    let value = if stored.version == T::VERSION {
+        stored.value
+    } else {
+        T::migrate(stored.version, stored.value)?
+    };
+
+    match serde_json::from_value(value) {
+        Ok(m) => Some(m),
+        Err(e) => {
+            tracing::warn!("failed to restore settings {}: {}", T::KEY, e);
+            None
+        }
+    }
+}
+
+fn save<T>(writer: &Rc<StorageWriter>, settings: &T)
+where
+    T: Settings,
+{
+    // Failing to persist the settings should not break the application.
+    match serde_json::to_value(settings) {
+        Ok(value) => StorageWriter::write(
+            writer,
+            &StoredSettings {
+                version: T::VERSION,
+                value,
+            },
+        ),
+        Err(e) => tracing::warn!("failed to persist settings {}: {}", T::KEY, e),
+    }
+}
+
+/// The atom that stores settings.
+#[derive(PartialEq)]
+struct SettingsState<T> {
+    inner: Rc<T>,
+}
+
+impl<T> Default for SettingsState<T>
+where
+    T: Settings,
+{
+    fn default() -> Self {
+        Self {
+            inner: T::default().into(),
+        }
+    }
+}
+
+impl<T> Atom for SettingsState<T>
+where
+    T: Settings,
+{
+    fn notion_ids(&self) -> Vec<std::any::TypeId> {
+        Vec::new()
+    }
+}
+
+/// The persistence of settings in a root, started when the settings are used for the first time.
+struct SettingsPersistence<T>
+where
+    T: Settings,
+{
+    started: Rc<Cell<bool>>,
+    listener: Rc<RefCell<Option<Listener>>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Default for SettingsPersistence<T>
+where
+    T: Settings,
+{
+    fn default() -> Self {
+        Self {
+            started: Rc::default(),
+            listener: Rc::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for SettingsPersistence<T>
+where
+    T: Settings,
+{
+    fn clone(&self) -> Self {
+        Self {
+            started: self.started.clone(),
+            listener: self.listener.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> AnyState for SettingsPersistence<T>
+where
+    T: Settings,
+{
+    fn apply(&self, _notion: Rc<dyn std::any::Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+impl<T> SettingsPersistence<T>
+where
+    T: Settings,
+{
+    /// Restores the settings and writes them each time they change afterwards.
+    fn start(&self, root: &BounceRootState, tracker: &HydrationTracker) {
+        if self.started.replace(true) {
+            return;
+        }
+
+        let storage = get_storage(root);
+        let writer = StorageWriter::new(storage.clone(), T::KEY.to_string());
+        let state = root.get_state::<SliceState<AtomSlice<SettingsState<T>>>>();
+        let listener = self.listener.clone();
+
+        tracker.hydrate(async move {
+            let stored = storage.get_item(T::KEY).await;
+            let current = state.get().inner.inner.clone();
+
+            // Settings that have been changed before they are restored are kept and written.
+            if *current != T::default() {
+                save(&writer, &*current);
+            } else if let Some(m) =
+                read_stored::<StoredSettings>(T::KEY, stored).and_then(load::<T>)
+            {
+                state.dispatch(SettingsState { inner: m.into() });
+            }
+
+            *listener.borrow_mut() = Some(state.listen(Rc::new(Callback::from(
+                move |value: Rc<AtomSlice<SettingsState<T>>>| save(&writer, &*value.inner.inner),
+            ))));
+        });
+    }
+}
+
+/// A handle returned by [`use_settings`].
+pub struct UseSettingsHandle<T>
+where
+    T: Settings,
+{
+    inner: UseAtomHandle<SettingsState<T>>,
+}
+
+impl<T> UseSettingsHandle<T>
+where
+    T: Settings,
+{
+    /// Sets the settings.
+    pub fn set(&self, settings: T) {
+        self.inner.set(SettingsState {
+            inner: settings.into(),
+        });
+    }
+
+    /// Updates the settings with a function.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut settings = (*self.inner.inner).clone();
+        f(&mut settings);
+
+        self.set(settings);
+    }
+
+    /// Resets the settings to their default value.
+    pub fn reset(&self) {
+        self.set(T::default());
+    }
+
+    /// Returns the value of a field as JSON.
+    ///
+    /// Returns `None` if the settings do not have a field with the name.
+    pub fn get_field(&self, name: &str) -> Option<Value> {
+        match serde_json::to_value(&*self.inner.inner) {
+            Ok(Value::Object(mut m)) => m.remove(name),
+            _ => None,
+        }
+    }
+
+    /// Sets the value of a field from JSON.
+    ///
+    /// This can be used by preferences panels built from [`Settings::fields`].
+    pub fn set_field(&self, name: &str, value: Value) -> Result<(), serde_json::Error> {
+        let mut settings = match serde_json::to_value(&*self.inner.inner)? {
+            Value::Object(m) => m,
+            _ => return Err(serde_json::Error::custom("settings are not a struct")),
+        };
+
+        if !settings.contains_key(name) {
+            return Err(serde_json::Error::custom(format!("unknown field `{name}`")));
+        }
+
+        settings.insert(name.to_string(), value);
+        self.set(serde_json::from_value(Value::Object(settings))?);
+
+        Ok(())
+    }
+}
+
+impl<T> Deref for UseSettingsHandle<T>
+where
+    T: Settings,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner.inner
+    }
+}
+
+impl<T> Clone for UseSettingsHandle<T>
+where
+    T: Settings,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for UseSettingsHandle<T>
+where
+    T: Settings + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseSettingsHandle")
+            .field("value", &*self.inner.inner)
+            .finish()
+    }
+}
+
+/// A hook to read and update persisted settings.
+///
+/// Settings are restored from the storage when they are used for the first time and saved each
+/// time they change. The default settings are used until they are restored, components can be
+/// rendered under a [`PersistGate`](crate::PersistGate) so they are rendered with the restored
+/// settings.
+///
+/// # Example
+///
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// # use yew::prelude::*;
+/// use bounce::settings::{use_settings, Settings};
+///
+/// #[derive(Settings, Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+/// #[bounce(key = "app-settings")]
+/// struct AppSettings {
+///     dark_mode: bool,
+///     font_size: u32,
+/// }
+///
+/// #[function_component(ResetSettings)]
+/// fn reset_settings() -> Html {
+///     let settings = use_settings::<AppSettings>();
+///
+///     let onclick = {
+///         let settings = settings.clone();
+///         Callback::from(move |_| settings.reset())
+///     };
+///
+///     html! {
+///         <button {onclick} disabled={*settings == AppSettings::default()}>
+///             {"Reset"}
+///         </button>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_settings<T>() -> UseSettingsHandle<T>
+where
+    T: Settings,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let tracker = use_hydration_tracker();
+    let inner = use_atom::<SettingsState<T>>();
+
+    use_effect_with((), move |_| {
+        root.get_state::<SettingsPersistence<T>>()
+            .start(&root, &tracker);
+    });
+
+    UseSettingsHandle { inner }
+}
+
+/// A handle returned by [`use_setting`].
+pub struct UseSettingHandle<T, V>
+where
+    T: Settings,
+{
+    settings: UseSettingsHandle<T>,
+    field: fn(&mut T) -> &mut V,
+    value: V,
+}
+
+impl<T, V> UseSettingHandle<T, V>
+where
+    T: Settings,
+{
+    /// Sets the value of the field.
+    pub fn set(&self, value: V) {
+        let field = self.field;
+        self.settings.update(move |m| *field(m) = value);
+    }
+}
+
+impl<T, V> Deref for UseSettingHandle<T, V>
+where
+    T: Settings,
+{
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, V> Clone for UseSettingHandle<T, V>
+where
+    T: Settings,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            settings: self.settings.clone(),
+            field: self.field,
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<T, V> fmt::Debug for UseSettingHandle<T, V>
+where
+    T: Settings,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseSettingHandle")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// A hook to read and update a field of persisted settings.
+///
+/// The field is selected with a function that returns a mutable reference to it.
+///
+/// See: [`bounce::settings`](crate::settings)
+#[hook]
+pub fn use_setting<T, V>(field: fn(&mut T) -> &mut V) -> UseSettingHandle<T, V>
+where
+    T: Settings,
+    V: Clone + 'static,
+{
+    let settings = use_settings::<T>();
+    let value = field(&mut (*settings).clone()).clone();
+
+    UseSettingHandle {
+        settings,
+        field,
+        value,
+    }
+}
@@ -1,16 +1,45 @@
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
+/// Implements [`ArtifactValue`] for a type.
+///
+/// If an apply function is specified with `#[bounce(apply = ...)]`, [`ApplyArtifacts`] is also
+/// implemented and the artifacts can be applied with the [`ArtifactApplier`] component. The apply
+/// function receives `&[Rc<T>]` in rendering order.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Artifact)]
+/// #[bounce(apply = apply_title)]
+/// pub struct Title {
+///     inner: String,
+/// }
+///
+/// fn apply_title(titles: &[Rc<Title>]) {
+///     // apply the last title.
+/// }
+/// ```
+pub use bounce_macros::Artifact;
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
-use crate::root_state::BounceRootState;
+use crate::root_state::{BounceRootState, BounceStates};
+use crate::states::input_selector::{use_input_selector_value, InputSelector};
+use crate::states::registry::{StateDescriptor, StateKind};
 use crate::states::slice::{use_slice_dispatch, use_slice_value};
-use crate::utils::Id;
+use crate::utils::{use_root_scoped_id, Id};
 use crate::Slice;
 
+/// The id of an [`ArtifactScope`], provided as a context to its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct ArtifactScopeId(Id);
+
 pub(crate) enum ArtifactAction<T: PartialEq + 'static> {
-    Insert(Id, Rc<T>),
+    Insert(Id, Option<ArtifactScopeId>, Rc<T>),
     Remove(Id),
 }
 
@@ -19,7 +48,7 @@ pub(crate) struct ArtifactSlice<T>
 where
     T: PartialEq + 'static,
 {
-    inner: BTreeMap<Id, Rc<T>>,
+    inner: BTreeMap<Id, (Option<ArtifactScopeId>, Rc<T>)>,
 }
 
 impl<T> Default for ArtifactSlice<T>
@@ -51,7 +80,9 @@ where
         let mut self_ = (*self).clone();
 
         match action {
-            ArtifactAction::Insert(id, artifact) => self_.inner.insert(id, artifact),
+            ArtifactAction::Insert(id, scope, artifact) => {
+                self_.inner.insert(id, (scope, artifact))
+            }
             ArtifactAction::Remove(id) => self_.inner.remove(&id),
         };
 
@@ -64,7 +95,46 @@ where
     T: PartialEq + 'static,
 {
     pub(crate) fn get(&self) -> Vec<Rc<T>> {
-        self.inner.values().cloned().collect()
+        self.inner.values().map(|(_, m)| m.clone()).collect()
+    }
+
+    pub(crate) fn get_scoped(&self, scope: ArtifactScopeId) -> Vec<Rc<T>> {
+        self.inner
+            .values()
+            .filter(|(m, _)| *m == Some(scope))
+            .map(|(_, m)| m.clone())
+            .collect()
+    }
+}
+
+/// Artifacts collected by the nearest scope, or all artifacts if there is no scope.
+#[derive(PartialEq)]
+pub(crate) struct ScopedArtifacts<T>
+where
+    T: PartialEq + 'static,
+{
+    inner: Vec<Rc<T>>,
+}
+
+impl<T> InputSelector for ScopedArtifacts<T>
+where
+    T: PartialEq + 'static,
+{
+    type Input = Option<ArtifactScopeId>;
+
+    fn select(states: &BounceStates, input: Rc<Self::Input>) -> Rc<Self> {
+        let artifacts = states.get_slice_value::<ArtifactSlice<T>>();
+
+        let inner = match *input {
+            Some(m) => artifacts.get_scoped(m),
+            None => artifacts.get(),
+        };
+
+        Self { inner }.into()
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Artifact, listener_count)
     }
 }
 
@@ -118,7 +188,55 @@ where
     use_slice_value::<ArtifactSlice<T>>().get()
 }
 
-/// Properties of the [`Artifact`] Component.
+/// A hook to read artifacts of the current artifact type that are registered under the nearest
+/// [`ArtifactScope`].
+///
+/// Artifacts are collected in the rendering order. If the component is not rendered under any
+/// `<ArtifactScope />`, all artifacts of the current type are returned like [`use_artifacts`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use std::rc::Rc;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// pub struct Breadcrumb {
+///     inner: String,
+/// }
+///
+/// #[function_component(Breadcrumbs)]
+/// fn breadcrumbs() -> Html {
+///     // Only breadcrumbs registered in the same panel are returned.
+///     let breadcrumbs = use_artifacts_scoped::<Breadcrumb>();
+///
+///     breadcrumbs.iter().map(|m| html! { <span>{&m.inner}</span> }).collect()
+/// }
+///
+/// #[function_component(Panel)]
+/// fn panel() -> Html {
+///     html! {
+///         <ArtifactScope>
+///             <Breadcrumbs />
+///             <Artifact<Breadcrumb> value={Rc::new(Breadcrumb { inner: "Home".into() })} />
+///         </ArtifactScope>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_artifacts_scoped<T>() -> Vec<Rc<T>>
+where
+    T: PartialEq + 'static,
+{
+    let scope = use_context::<ArtifactScopeId>();
+
+    use_input_selector_value::<ScopedArtifacts<T>>(scope.into())
+        .inner
+        .clone()
+}
+
+/// Properties of the [`Artifact`](struct@Artifact) Component.
 #[derive(Debug, Properties, PartialEq, Eq)]
 pub struct ArtifactProps<T>
 where
@@ -159,20 +277,25 @@ pub fn artifact<T>(props: &ArtifactProps<T>) -> Html
 where
     T: PartialEq + 'static,
 {
-    let id = *use_state(Id::new);
+    let id = use_root_scoped_id();
 
     // we need to register root as a dependency of effects so that when the root changes the artifact can
     // be moved from 1 root to another.
     let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
 
+    let scope = use_context::<ArtifactScopeId>();
+
     let artifact_dispatch = use_slice_dispatch::<ArtifactSlice<T>>();
 
     {
         let artifact_dispatch = artifact_dispatch.clone();
-        use_effect_with((props.value.clone(), root.clone()), move |(val, _)| {
-            artifact_dispatch(ArtifactAction::Insert(id, val.clone()));
-            || {}
-        });
+        use_effect_with(
+            (props.value.clone(), scope, root.clone()),
+            move |(val, scope, _)| {
+                artifact_dispatch(ArtifactAction::Insert(id, *scope, val.clone()));
+                || {}
+            },
+        );
     }
 
     #[allow(clippy::unused_unit)]
@@ -180,7 +303,7 @@ where
         let _artifact_dispatch = artifact_dispatch.clone();
         let _val = props.value.clone();
         let _ = use_prepared_state!((), move |_| -> () {
-            _artifact_dispatch(ArtifactAction::Insert(id, _val));
+            _artifact_dispatch(ArtifactAction::Insert(id, scope, _val));
         });
     }
 
@@ -192,3 +315,218 @@ where
 
     Html::default()
 }
+
+/// Properties of the [`ArtifactScope`] Component.
+#[derive(Debug, Properties, PartialEq)]
+pub struct ArtifactScopeProps {
+    /// Children of the artifact scope.
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// A component to create an independent collector for artifacts.
+///
+/// Artifacts registered by the children of this component can be read separately from other
+/// artifacts of the same type with the [`use_artifacts_scoped`] hook.
+///
+/// Artifacts are always collected by the nearest scope and they are still returned by
+/// [`use_artifacts`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use std::rc::Rc;
+/// #
+/// #[derive(Debug, PartialEq)]
+/// pub struct Breadcrumb {
+///     inner: String,
+/// }
+///
+/// let rendered = html! {
+///     <>
+///         <ArtifactScope>
+///             <Artifact<Breadcrumb> value={Rc::new(Breadcrumb { inner: "Panel A".into() })} />
+///         </ArtifactScope>
+///         <ArtifactScope>
+///             <Artifact<Breadcrumb> value={Rc::new(Breadcrumb { inner: "Panel B".into() })} />
+///         </ArtifactScope>
+///     </>
+/// };
+/// ```
+#[function_component(ArtifactScope)]
+pub fn artifact_scope(props: &ArtifactScopeProps) -> Html {
+    let id = ArtifactScopeId(use_root_scoped_id());
+
+    html! {
+        <ContextProvider<ArtifactScopeId> context={id}>
+            {props.children.clone()}
+        </ContextProvider<ArtifactScopeId>>
+    }
+}
+
+/// A value that can be registered as an artifact.
+///
+/// This trait is implemented by `#[derive(Artifact)]`.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Artifact)]
+/// pub struct Breadcrumb {
+///     label: String,
+/// }
+///
+/// #[function_component(Settings)]
+/// fn settings() -> Html {
+///     html! {
+///         <>
+///             // Equivalent to <Artifact<Breadcrumb> value={...} />.
+///             {Breadcrumb { label: "Settings".into() }.into_artifact()}
+///             <div>{"Settings"}</div>
+///         </>
+///     }
+/// }
+/// ```
+pub trait ArtifactValue: PartialEq + 'static {
+    /// Renders an [`Artifact`](struct@Artifact) that registers this value.
+    fn into_artifact(self) -> Html
+    where
+        Self: Sized,
+    {
+        html! { <Artifact<Self> value={Rc::new(self)} /> }
+    }
+}
+
+/// An artifact that can apply the registered values to the document.
+///
+/// This trait is implemented by `#[derive(Artifact)]` when an apply function is specified with
+/// `#[bounce(apply = ...)]`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Artifact)]
+/// #[bounce(apply = apply_lang)]
+/// pub struct Lang {
+///     inner: String,
+/// }
+///
+/// fn apply_lang(langs: &[Rc<Lang>]) {
+///     // The value registered by the last rendered component wins.
+///     let _lang = langs.last().map(|m| m.inner.as_str()).unwrap_or("en");
+///     // gloo::utils::document_element().set_attribute("lang", _lang);
+/// }
+/// ```
+///
+/// See: [`ArtifactApplier`] and [`use_artifact_applier`]
+pub trait ApplyArtifacts: ArtifactValue {
+    /// Applies the registered values, in rendering order.
+    ///
+    /// This is called with an empty slice if no value is registered.
+    fn apply(values: &[Rc<Self>]);
+}
+
+/// A hook to apply artifacts of the current type each time they change.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Artifact)]
+/// #[bounce(apply = apply_title)]
+/// pub struct Title {
+///     inner: String,
+/// }
+///
+/// fn apply_title(titles: &[Rc<Title>]) {
+///     let _title = titles.last().map(|m| m.inner.as_str()).unwrap_or("My App");
+///     // gloo::utils::document().set_title(_title);
+/// }
+///
+/// #[function_component(Layout)]
+/// fn layout() -> Html {
+///     // Applies the title registered by the pages rendered in this layout.
+///     use_artifact_applier::<Title>();
+///
+///     html! {
+///         // pages...
+///     }
+/// }
+/// ```
+///
+/// See: [`ApplyArtifacts`]
+#[hook]
+pub fn use_artifact_applier<T>()
+where
+    T: ApplyArtifacts,
+{
+    let values = use_artifacts::<T>();
+
+    use_effect_with(values, |m| {
+        T::apply(m);
+
+        || {}
+    });
+}
+
+/// A component to apply artifacts of a type each time they change.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use std::rc::Rc;
+/// # use bounce::BounceRoot;
+/// #
+/// #[derive(PartialEq, Artifact)]
+/// #[bounce(apply = apply_title)]
+/// pub struct Title {
+///     inner: String,
+/// }
+///
+/// fn apply_title(titles: &[Rc<Title>]) {
+///     let _title = titles.last().map(|m| m.inner.as_str()).unwrap_or("My App");
+///     // gloo::utils::document().set_title(_title);
+/// }
+///
+/// #[function_component(Page)]
+/// fn page() -> Html {
+///     html! {
+///         <>
+///             {Title { inner: "My Page".into() }.into_artifact()}
+///             <div>{"This is my page."}</div>
+///         </>
+///     }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <ArtifactApplier<Title> />
+///             <Page />
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(ArtifactApplier)]
+pub fn artifact_applier<T>() -> Html
+where
+    T: ApplyArtifacts,
+{
+    use_artifact_applier::<T>();
+
+    Html::default()
+}
@@ -1,44 +1,138 @@
 use std::any::{Any, TypeId};
+use std::convert::Infallible;
 use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
 use std::ops::Deref;
+use std::pin::Pin;
 use std::rc::Rc;
+use std::task::{Context, Poll};
 
-use super::slice::{use_slice, use_slice_dispatch, use_slice_value, Slice, UseSliceHandle};
+use super::input_selector::{use_input_selector_value, InputSelector};
+use super::registry::{StateDescriptor, StateKind};
+use super::selector::{use_selector_value, Selector};
+use super::slice::{
+    use_slice, use_slice_dispatch, use_slice_value, Slice, SliceSink, SliceState, SliceStream,
+    UseSliceHandle,
+};
+use crate::root_state::{BounceRootState, BounceStates};
 
 use anymap2::AnyMap;
 pub use bounce_macros::Atom;
+use futures::{Sink, Stream};
+use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
-#[doc(hidden)]
-pub trait Atom: PartialEq + Default {
-    /// Applies a notion.
-    ///
-    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
-    /// atom using [`PartialEq`].
-    #[allow(unused_variables)]
-    fn apply(self: Rc<Self>, notion: Rc<dyn Any>) -> Rc<Self> {
-        self
-    }
+pub use bounce_core::Atom;
 
-    /// Returns a list of notion ids that this atom accepts.
-    fn notion_ids(&self) -> Vec<TypeId>;
+/// A trait implemented on atoms that are enums.
+///
+/// This trait is implemented when [`Atom`](macro@crate::Atom) is derived on an enum with the
+/// `#[bounce(variants)]` attribute. The derive macro also generates an enum named `{Atom}Variant`
+/// with the same visibility that contains the variants of the atom without their fields.
+///
+/// # Example
+///
+/// ```
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(variants)]
+/// enum Status {
+///     #[default]
+///     Idle,
+///     Failed(String),
+/// }
+///
+/// let status = Status::Failed("timed out".into());
+///
+/// assert_eq!(status.variant(), StatusVariant::Failed);
+/// ```
+///
+/// See: [`use_atom_variant`] and [`use_atom_is_variant`]
+pub trait EnumAtom: Atom {
+    /// The variants of the atom.
+    type Variant: fmt::Debug + Copy + Eq + Hash + 'static;
 
-    /// Notifies an atom that its value has changed.
-    fn changed(self: Rc<Self>) {}
+    /// Returns the variant of the atom.
+    fn variant(&self) -> Self::Variant;
+}
 
-    /// Creates a new atom with its initial value.
-    fn create(init_states: &mut AnyMap) -> Self
-    where
-        Self: 'static + Sized,
-    {
-        init_states.remove().unwrap_or_default()
-    }
+/// A trait implemented on atoms that wrap a single value.
+///
+/// This trait is implemented when [`Atom`](macro@crate::Atom) is derived on a struct with exactly 1
+/// field with the `#[bounce(transparent)]` attribute. The derive macro also implements
+/// [`From`] the inner value, [`Deref`] to the inner value and [`Display`](fmt::Display) if the
+/// inner value implements it.
+///
+/// # Example
+///
+/// ```
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(transparent)]
+/// struct Username(String);
+///
+/// let username = Username::from("Jane Doe".to_string());
+///
+/// assert_eq!(username.len(), 8);
+/// assert_eq!(username.to_string(), "Jane Doe");
+/// assert_eq!(username.into_inner(), "Jane Doe");
+///
+/// // The inner value does not need to implement `Display`.
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(transparent)]
+/// struct Tags {
+///     inner: Vec<String>,
+/// }
+///
+/// let tags = Tags::from(vec!["rust".to_string()]);
+///
+/// assert!(tags.contains(&"rust".to_string()));
+/// ```
+///
+/// See: [`use_atom_value_inner`]
+pub trait TransparentAtom: Atom {
+    /// The inner value of the atom.
+    type Inner;
+
+    /// Returns a reference to the inner value.
+    fn inner(&self) -> &Self::Inner;
+
+    /// Returns the inner value.
+    fn into_inner(self) -> Self::Inner;
 }
 
 /// A trait to provide cloning on atoms.
 ///
 /// This trait provides a `self.clone_atom()` method that can be used as an alias of `(*self).clone()`
 /// in apply functions to produce a owned clone of the atom.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// #
+/// pub struct Rename(String);
+///
+/// #[derive(Atom, PartialEq, Default, Clone)]
+/// #[bounce(with_notion(Rename))]
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// impl WithNotion<Rename> for User {
+///     fn apply(self: Rc<Self>, notion: Rc<Rename>) -> Rc<Self> {
+///         let mut next = self.clone_atom();
+///         next.name = notion.0.clone();
+///
+///         next.into()
+///     }
+/// }
+/// ```
 pub trait CloneAtom: Atom + Clone {
     /// Clones current atom.
     #[inline]
@@ -77,6 +171,10 @@ where
         .into()
     }
 
+    fn accepts(&self, notion: &dyn Any) -> bool {
+        self.inner.accepts(notion)
+    }
+
     fn notion_ids(&self) -> Vec<TypeId> {
         self.inner.notion_ids()
     }
@@ -85,6 +183,18 @@ where
         self.inner.clone().changed();
     }
 
+    fn init(self: Rc<Self>) {
+        self.inner.clone().init();
+    }
+
+    fn teardown(self: Rc<Self>) {
+        self.inner.clone().teardown();
+    }
+
+    fn should_observe(&self, prev: &Self) -> bool {
+        self.inner.should_observe(&prev.inner)
+    }
+
     fn create(init_states: &mut AnyMap) -> Self
     where
         Self: 'static + Sized,
@@ -93,6 +203,41 @@ where
             inner: T::create(init_states).into(),
         }
     }
+
+    fn create_provided(init_states: &mut AnyMap) -> Option<Self>
+    where
+        Self: 'static + Sized,
+    {
+        T::create_provided(init_states).map(|m| Self { inner: m.into() })
+    }
+
+    fn create_from_states(states: &dyn Any) -> Option<Self>
+    where
+        Self: 'static + Sized,
+    {
+        T::create_from_states(states).map(|m| Self { inner: m.into() })
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor
+    where
+        Self: 'static + Sized,
+    {
+        StateDescriptor::new::<T>(StateKind::Atom, listener_count)
+    }
+}
+
+impl<T> SliceState<AtomSlice<T>>
+where
+    T: Atom + 'static,
+{
+    fn set_if_changed(&self, val: T) -> bool {
+        if *self.get().inner == val {
+            return false;
+        }
+
+        self.dispatch(val);
+        true
+    }
 }
 
 /// A handle returned by [`use_atom`].
@@ -113,6 +258,147 @@ where
     pub fn set(&self, val: T) {
         self.inner.dispatch(val)
     }
+
+    /// Sets the value of current atom if it is not equal to the current value.
+    ///
+    /// Returns `true` if the value is set.
+    /// When the value is equal, nothing is dispatched and observers of the atom are not
+    /// notified.
+    pub fn set_if_changed(&self, val: T) -> bool {
+        self.inner.state().set_if_changed(val)
+    }
+
+    /// Returns a [`Stream`] that yields the value of the atom each time it changes.
+    ///
+    /// The stream does not yield the current value and it keeps yielding values after the
+    /// component is unmounted until it is dropped.
+    pub fn to_stream(&self) -> AtomStream<T> {
+        AtomStream {
+            inner: self.inner.to_stream(),
+        }
+    }
+
+    /// Returns a [`Sink`] that sets the atom to each value sent to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bounce::prelude::*;
+    /// # use futures::{stream, SinkExt, StreamExt};
+    /// # use yew::platform::spawn_local;
+    /// # use yew::prelude::*;
+    /// #
+    /// #[derive(PartialEq, Default, Atom)]
+    /// struct Progress(u8);
+    ///
+    /// #[function_component(Upload)]
+    /// fn upload() -> Html {
+    ///     let progress = use_atom::<Progress>();
+    ///
+    ///     use_effect_with((), move |_| {
+    ///         // e.g.: progress events reported by a worker.
+    ///         let events = stream::iter([25, 50, 100]).map(|m| Ok(Progress(m)));
+    ///
+    ///         spawn_local(async move {
+    ///             let _ = progress.to_sink().send_all(&mut events.boxed_local()).await;
+    ///         });
+    ///
+    ///         || {}
+    ///     });
+    ///
+    ///     Html::default()
+    /// }
+    /// ```
+    pub fn to_sink(&self) -> AtomSink<T> {
+        AtomSink {
+            inner: self.inner.to_sink(),
+        }
+    }
+}
+
+/// A [`Stream`] of the values of an [`Atom`](macro@crate::Atom).
+///
+/// See: [`UseAtomHandle::to_stream`]
+pub struct AtomStream<T>
+where
+    T: Atom,
+{
+    inner: SliceStream<AtomSlice<T>>,
+}
+
+impl<T> Stream for AtomStream<T>
+where
+    T: Atom,
+{
+    type Item = Rc<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|m| m.map(|m| m.inner.clone()))
+    }
+}
+
+impl<T> fmt::Debug for AtomStream<T>
+where
+    T: Atom,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomStream").finish_non_exhaustive()
+    }
+}
+
+/// A [`Sink`] that sets the value of an [`Atom`](macro@crate::Atom).
+///
+/// See: [`UseAtomHandle::to_sink`]
+pub struct AtomSink<T>
+where
+    T: Atom,
+{
+    inner: SliceSink<AtomSlice<T>>,
+}
+
+impl<T> Sink<T> for AtomSink<T>
+where
+    T: Atom + 'static,
+{
+    type Error = Infallible;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        Pin::new(&mut self.inner).start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+impl<T> Clone for AtomSink<T>
+where
+    T: Atom,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for AtomSink<T>
+where
+    T: Atom,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomSink").finish_non_exhaustive()
+    }
 }
 
 impl<T> Deref for UseAtomHandle<T>
@@ -268,6 +554,46 @@ where
     use_slice_dispatch::<AtomSlice<T>>()
 }
 
+/// A hook to produce a setter function for an [`Atom`](macro@crate::Atom) that skips values
+/// equal to the current value.
+///
+/// Returns a `Rc<dyn Fn(T) -> bool>`, which returns `true` if the value is set.
+///
+/// This is useful for atoms that are set from sources that repeat the same value (e.g.: a
+/// polled endpoint), so observers are only notified when the value actually changes.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// struct Online(bool);
+///
+/// #[function_component(Setter)]
+/// fn setter() -> Html {
+///     let set_online = use_atom_setter_if_changed::<Online>();
+///     // Online is already false, so nothing is dispatched.
+///     let changed = set_online(Online(false));
+///     # assert!(!changed);
+///
+///     Html::default()
+/// }
+/// ```
+#[hook]
+pub fn use_atom_setter_if_changed<T>() -> Rc<dyn Fn(T) -> bool>
+where
+    T: Atom + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move |val: T| {
+        root.get_state::<SliceState<AtomSlice<T>>>()
+            .set_if_changed(val)
+    })
+}
+
 /// A read-only hook to connect to the value of an [`Atom`](macro@crate::Atom).
 ///
 /// Returns `Rc<T>`.
@@ -311,3 +637,157 @@ where
 {
     use_slice_value::<AtomSlice<T>>().inner.clone()
 }
+
+/// A read-only hook to connect to the inner value of a transparent [`Atom`](macro@crate::Atom).
+///
+/// Returns a clone of the inner value, the component re-renders when the atom changes.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(transparent)]
+/// struct Username(String);
+///
+/// #[function_component(Reader)]
+/// fn reader() -> Html {
+///     let username: String = use_atom_value_inner::<Username>();
+///
+///     html! { <div>{"Hello, "}{username}</div> }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_value_inner<T>() -> T::Inner
+where
+    T: TransparentAtom + 'static,
+    T::Inner: Clone,
+{
+    use_atom_value::<T>().inner().clone()
+}
+
+#[derive(PartialEq)]
+struct AtomVariantSelector<T>
+where
+    T: EnumAtom + 'static,
+{
+    inner: T::Variant,
+}
+
+impl<T> Selector for AtomVariantSelector<T>
+where
+    T: EnumAtom + 'static,
+{
+    fn select(states: &BounceStates) -> Rc<Self> {
+        Self {
+            inner: states.get_atom_value::<T>().variant(),
+        }
+        .into()
+    }
+}
+
+#[derive(PartialEq)]
+struct IsAtomVariant<T>
+where
+    T: EnumAtom + 'static,
+{
+    _marker: PhantomData<T>,
+    inner: bool,
+}
+
+impl<T> InputSelector for IsAtomVariant<T>
+where
+    T: EnumAtom + 'static,
+{
+    type Input = T::Variant;
+
+    fn select(states: &BounceStates, input: Rc<T::Variant>) -> Rc<Self> {
+        Self {
+            _marker: PhantomData,
+            inner: states.get_atom_value::<T>().variant() == *input,
+        }
+        .into()
+    }
+}
+
+/// A read-only hook to connect to the variant of an enum [`Atom`](macro@crate::Atom).
+///
+/// This hook only re-renders when the atom changes to a different variant.
+///
+/// Returns the `{Atom}Variant` generated by the derive macro, which can be matched with
+/// `matches!`.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// #[bounce(variants)]
+/// enum Status {
+///     #[default]
+///     Idle,
+///     Loading,
+///     Failed(String),
+/// }
+///
+/// #[function_component(Spinner)]
+/// fn spinner() -> Html {
+///     // StatusVariant is generated by the derive macro.
+///     let variant = use_atom_variant::<Status>();
+///
+///     if matches!(variant, StatusVariant::Loading) {
+///         html! { <div>{"Loading..."}</div> }
+///     } else {
+///         Html::default()
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_variant<T>() -> T::Variant
+where
+    T: EnumAtom + 'static,
+{
+    use_selector_value::<AtomVariantSelector<T>>().inner
+}
+
+/// A read-only hook to check whether an enum [`Atom`](macro@crate::Atom) is a specific variant.
+///
+/// This hook only re-renders when the result changes.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// # #[derive(PartialEq, Default, Atom)]
+/// # #[bounce(variants)]
+/// # enum Status {
+/// #     #[default]
+/// #     Idle,
+/// #     Loading,
+/// #     Failed(String),
+/// # }
+/// #
+/// #[function_component(ErrorBanner)]
+/// fn error_banner() -> Html {
+///     let failed = use_atom_is_variant::<Status>(StatusVariant::Failed);
+///
+///     if failed {
+///         html! { <div>{"Something went wrong."}</div> }
+///     } else {
+///         Html::default()
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_is_variant<T>(variant: T::Variant) -> bool
+where
+    T: EnumAtom + 'static,
+{
+    use_input_selector_value::<IsAtomVariant<T>>(variant.into()).inner
+}
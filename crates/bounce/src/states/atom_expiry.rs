@@ -0,0 +1,238 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anymap2::AnyMap;
+use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use super::atom::{use_atom, Atom, AtomSlice, UseAtomHandle};
+use super::slice::SliceState;
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+use crate::utils::Listener;
+
+/// The expiry timer of an atom.
+///
+/// Each timer is tagged with a generation. The generation is incremented whenever the atom is set,
+/// with or without expiry, so previously scheduled timers no longer revert the atom.
+struct AtomExpiryState<T>
+where
+    T: Atom + 'static,
+{
+    generation: Rc<Cell<u64>>,
+    listener: Rc<RefCell<Option<Listener>>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> AtomExpiryState<T>
+where
+    T: Atom + 'static,
+{
+    fn schedule(&self, root: BounceRootState, ttl: Duration) {
+        self.cancel();
+        let generation = self.generation.get();
+
+        let state = root.get_state::<SliceState<AtomSlice<T>>>();
+        let scheduled_value = state.get();
+
+        // The timer is cancelled when the atom is set by other handles.
+        let listener = {
+            let current_generation = self.generation.clone();
+            state.listen(Rc::new(Callback::from(move |value| {
+                // Notifications may be batched, the value that has scheduled this timer is
+                // notified after the listener has been registered.
+                if !Rc::ptr_eq(&value, &scheduled_value) && current_generation.get() == generation {
+                    current_generation.set(generation + 1);
+                }
+            })))
+        };
+        *self.listener.borrow_mut() = Some(listener);
+
+        let current_generation = self.generation.clone();
+        let current_listener = self.listener.clone();
+
+        let sleep = root.clock().sleep(ttl);
+
+        spawn_local(async move {
+            sleep.await;
+
+            // The atom has been set again or the timer has been cancelled.
+            if current_generation.get() != generation {
+                return;
+            }
+
+            current_generation.set(generation + 1);
+            current_listener.borrow_mut().take();
+
+            state.dispatch(T::default());
+        });
+    }
+
+    fn cancel(&self) {
+        self.generation.set(self.generation.get() + 1);
+        self.listener.borrow_mut().take();
+    }
+}
+
+impl<T> Default for AtomExpiryState<T>
+where
+    T: Atom + 'static,
+{
+    fn default() -> Self {
+        Self {
+            generation: Rc::default(),
+            listener: Rc::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for AtomExpiryState<T>
+where
+    T: Atom + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            generation: self.generation.clone(),
+            listener: self.listener.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> AnyState for AtomExpiryState<T>
+where
+    T: Atom + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A handle returned by [`use_atom_with_expiry`].
+///
+/// This type dereferences to `T` and has a `set` method to set a value that reverts to the
+/// default value after the time-to-live of the handle has elapsed.
+pub struct UseAtomWithExpiryHandle<T>
+where
+    T: Atom + 'static,
+{
+    inner: UseAtomHandle<T>,
+    root: BounceRootState,
+    ttl: Duration,
+}
+
+impl<T> UseAtomWithExpiryHandle<T>
+where
+    T: Atom + 'static,
+{
+    /// Sets the value of current atom.
+    ///
+    /// The atom reverts to its default value after the time-to-live has elapsed, unless it is
+    /// set again before that.
+    pub fn set(&self, val: T) {
+        self.inner.set(val);
+
+        self.root
+            .get_state::<AtomExpiryState<T>>()
+            .schedule(self.root.clone(), self.ttl);
+    }
+
+    /// Reverts the atom to its default value immediately.
+    pub fn reset(&self) {
+        self.root.get_state::<AtomExpiryState<T>>().cancel();
+        self.inner.set(T::default());
+    }
+}
+
+impl<T> Deref for UseAtomWithExpiryHandle<T>
+where
+    T: Atom + 'static,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> Clone for UseAtomWithExpiryHandle<T>
+where
+    T: Atom + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            root: self.root.clone(),
+            ttl: self.ttl,
+        }
+    }
+}
+
+impl<T> fmt::Debug for UseAtomWithExpiryHandle<T>
+where
+    T: Atom + fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseAtomWithExpiryHandle")
+            .field("inner", &self.inner)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+/// A hook to connect to an [`Atom`](macro@crate::Atom) where values set with the returned
+/// handle revert to the default value after `ttl`.
+///
+/// The timer is managed by the bounce root instead of the component, so the value is reverted
+/// even if the component that has set the value is unmounted.
+///
+/// Returns a [`UseAtomWithExpiryHandle<T>`].
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// struct Toast {
+///     message: Option<String>,
+/// }
+///
+/// #[function_component(SaveButton)]
+/// fn save_button() -> Html {
+///     let toast = use_atom_with_expiry::<Toast>(Duration::from_secs(3));
+///
+///     let onclick = Callback::from(move |_| {
+///         // The toast is hidden after 3 seconds.
+///         toast.set(Toast {
+///             message: Some("Saved!".into()),
+///         });
+///     });
+///
+///     html! { <button {onclick}>{"Save"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_with_expiry<T>(ttl: Duration) -> UseAtomWithExpiryHandle<T>
+where
+    T: Atom + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let inner = use_atom::<T>();
+
+    UseAtomWithExpiryHandle { inner, root, ttl }
+}
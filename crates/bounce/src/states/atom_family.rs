@@ -0,0 +1,465 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use crate::any_state::AnyState;
+use crate::root_state::{BounceRootState, BounceStates};
+use crate::states::input_selector::{use_input_selector_value, InputSelector};
+use crate::states::registry::{StateDescriptor, StateKind};
+use crate::states::slice::{Slice, SliceState};
+#[cfg(feature = "persist")]
+use crate::utils::Listener;
+use crate::utils::{notify_listeners, ListenerVec};
+
+/// A family of atoms where each key has its own value.
+///
+/// A key has the value created with [`create`](AtomFamily::create) until a different value is
+/// set. Subscribers of a key are only notified when the value of the same key changes.
+///
+/// # Example
+///
+/// ```
+/// use bounce::AtomFamily;
+///
+/// #[derive(PartialEq)]
+/// struct Draft {
+///     text: String,
+/// }
+///
+/// impl AtomFamily for Draft {
+///     // The id of a document.
+///     type Key = u64;
+///
+///     fn create(_key: &u64) -> Self {
+///         Self { text: String::new() }
+///     }
+/// }
+/// ```
+pub trait AtomFamily: PartialEq + 'static {
+    /// The key of each atom in the family.
+    type Key: Eq + Hash + Clone + 'static;
+
+    /// Whether the value of a key is removed when the last component subscribed to the key is
+    /// unmounted.
+    ///
+    /// The key is created with [`create`](AtomFamily::create) again when it is used next time.
+    /// Values that are equal to the created value are never stored, regardless of this option.
+    ///
+    /// Defaults to `false`.
+    const EVICT_UNUSED: bool = false;
+
+    /// Creates the initial value of a key.
+    fn create(key: &Self::Key) -> Self;
+}
+
+pub(crate) enum AtomFamilyAction<T>
+where
+    T: AtomFamily,
+{
+    Set { key: T::Key, value: Rc<T> },
+    Evict { key: T::Key },
+}
+
+/// The values of an atom family that are different from their created values.
+#[derive(PartialEq)]
+pub(crate) struct AtomFamilySlice<T>
+where
+    T: AtomFamily,
+{
+    values: HashMap<T::Key, Rc<T>>,
+}
+
+impl<T> Default for AtomFamilySlice<T>
+where
+    T: AtomFamily,
+{
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<T> AtomFamilySlice<T>
+where
+    T: AtomFamily,
+{
+    /// Returns the value of a key, or its created value if it has not been set.
+    pub fn get(&self, key: &T::Key) -> Rc<T> {
+        self.values
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| T::create(key).into())
+    }
+
+    /// Returns the stored value of a key, if it has been set.
+    #[cfg(feature = "persist")]
+    pub fn get_stored(&self, key: &T::Key) -> Option<&Rc<T>> {
+        self.values.get(key)
+    }
+}
+
+impl<T> Slice for AtomFamilySlice<T>
+where
+    T: AtomFamily,
+{
+    type Action = AtomFamilyAction<T>;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut values = self.values.clone();
+
+        match action {
+            AtomFamilyAction::Set { key, value } => {
+                if *value == T::create(&key) {
+                    values.remove(&key);
+                } else {
+                    values.insert(key, value);
+                }
+            }
+            AtomFamilyAction::Evict { key } => {
+                values.remove(&key);
+            }
+        }
+
+        Self { values }.into()
+    }
+
+    fn notion_ids(&self) -> Vec<TypeId> {
+        Vec::new()
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Atom, listener_count)
+    }
+}
+
+/// Selects the value of a key.
+#[derive(PartialEq)]
+struct AtomFamilySelector<T>
+where
+    T: AtomFamily,
+{
+    value: Rc<T>,
+}
+
+impl<T> InputSelector for AtomFamilySelector<T>
+where
+    T: AtomFamily,
+{
+    type Input = T::Key;
+
+    fn select(states: &BounceStates, input: Rc<T::Key>) -> Rc<Self> {
+        let value = states.get_slice_value::<AtomFamilySlice<T>>().get(&input);
+
+        Self { value }.into()
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Atom, listener_count)
+    }
+}
+
+/// The number of mounted components subscribed to each key of an atom family.
+pub(crate) struct AtomFamilySubscribers<T>
+where
+    T: AtomFamily,
+{
+    counts: Rc<RefCell<HashMap<T::Key, usize>>>,
+    mount_listeners: Rc<RefCell<ListenerVec<T::Key>>>,
+    evict_listeners: Rc<RefCell<ListenerVec<T::Key>>>,
+}
+
+impl<T> AtomFamilySubscribers<T>
+where
+    T: AtomFamily,
+{
+    fn subscribe(&self, key: T::Key) {
+        let mounted = {
+            let mut counts = self.counts.borrow_mut();
+            let count = counts.entry(key.clone()).or_default();
+            *count += 1;
+
+            *count == 1
+        };
+
+        if mounted {
+            notify_listeners(self.mount_listeners.clone(), key.into());
+        }
+    }
+
+    fn unsubscribe(&self, root: &BounceRootState, key: &T::Key) {
+        {
+            let mut counts = self.counts.borrow_mut();
+
+            match counts.get_mut(key) {
+                Some(m) if *m > 1 => {
+                    *m -= 1;
+                    return;
+                }
+                Some(_) => {
+                    counts.remove(key);
+                }
+                None => return,
+            }
+        }
+
+        if T::EVICT_UNUSED {
+            notify_listeners(self.evict_listeners.clone(), key.clone().into());
+
+            root.get_state::<SliceState<AtomFamilySlice<T>>>()
+                .dispatch(AtomFamilyAction::Evict { key: key.clone() });
+        }
+    }
+
+    /// Returns the keys that components are subscribed to, in no particular order.
+    #[cfg(feature = "persist")]
+    pub fn keys(&self) -> Vec<T::Key> {
+        self.counts.borrow().keys().cloned().collect()
+    }
+
+    /// Listens to keys that a component subscribes to while no other component is subscribed.
+    #[cfg(feature = "persist")]
+    pub fn listen_mount(&self, callback: Rc<Callback<Rc<T::Key>>>) -> Listener {
+        self.mount_listeners
+            .borrow_mut()
+            .push(Rc::downgrade(&callback));
+
+        Listener::new(callback)
+    }
+
+    /// Listens to keys that are about to be evicted.
+    #[cfg(feature = "persist")]
+    pub fn listen_evict(&self, callback: Rc<Callback<Rc<T::Key>>>) -> Listener {
+        self.evict_listeners
+            .borrow_mut()
+            .push(Rc::downgrade(&callback));
+
+        Listener::new(callback)
+    }
+}
+
+impl<T> Clone for AtomFamilySubscribers<T>
+where
+    T: AtomFamily,
+{
+    fn clone(&self) -> Self {
+        Self {
+            counts: self.counts.clone(),
+            mount_listeners: self.mount_listeners.clone(),
+            evict_listeners: self.evict_listeners.clone(),
+        }
+    }
+}
+
+impl<T> Default for AtomFamilySubscribers<T>
+where
+    T: AtomFamily,
+{
+    fn default() -> Self {
+        Self {
+            counts: Rc::default(),
+            mount_listeners: Rc::default(),
+            evict_listeners: Rc::default(),
+        }
+    }
+}
+
+impl<T> AnyState for AtomFamilySubscribers<T>
+where
+    T: AtomFamily,
+{
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A handle returned by [`use_atom_family`].
+///
+/// This type dereferences to the value of the key.
+pub struct UseAtomFamilyHandle<T>
+where
+    T: AtomFamily,
+{
+    key: T::Key,
+    value: Rc<T>,
+    root: BounceRootState,
+}
+
+impl<T> UseAtomFamilyHandle<T>
+where
+    T: AtomFamily,
+{
+    /// Sets the value of the key.
+    ///
+    /// Only subscribers of the key are notified and only if the value has changed.
+    pub fn set(&self, value: T) {
+        self.root
+            .get_state::<SliceState<AtomFamilySlice<T>>>()
+            .dispatch(AtomFamilyAction::Set {
+                key: self.key.clone(),
+                value: value.into(),
+            });
+    }
+}
+
+impl<T> Deref for UseAtomFamilyHandle<T>
+where
+    T: AtomFamily,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> Clone for UseAtomFamilyHandle<T>
+where
+    T: AtomFamily,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            value: self.value.clone(),
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for UseAtomFamilyHandle<T>
+where
+    T: AtomFamily + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseAtomFamilyHandle")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+/// A hook to connect to the atom of a key in an atom family.
+///
+/// The component only re-renders when the value of the same key changes.
+///
+/// Returns a [`UseAtomFamilyHandle<T>`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq)]
+/// struct Draft {
+///     text: String,
+/// }
+///
+/// impl AtomFamily for Draft {
+///     type Key = u64;
+///
+///     fn create(_key: &u64) -> Self {
+///         Self { text: String::new() }
+///     }
+/// }
+///
+/// #[derive(Properties, PartialEq)]
+/// struct EditorProps {
+///     document_id: u64,
+/// }
+///
+/// #[function_component(Editor)]
+/// fn editor(props: &EditorProps) -> Html {
+///     let draft = use_atom_family::<Draft>(props.document_id);
+///
+///     let on_clear = {
+///         let draft = draft.clone();
+///         Callback::from(move |_| draft.set(Draft { text: String::new() }))
+///     };
+///
+///     html! {
+///         <div>
+///             <div>{&draft.text}</div>
+///             <button onclick={on_clear}>{"Clear"}</button>
+///         </div>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_family<T>(key: T::Key) -> UseAtomFamilyHandle<T>
+where
+    T: AtomFamily,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let value = use_atom_family_value::<T>(key.clone());
+
+    UseAtomFamilyHandle { key, value, root }
+}
+
+/// A read-only hook to connect to the value of a key in an atom family.
+///
+/// The component only re-renders when the value of the same key changes.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// # #[derive(PartialEq)]
+/// # struct Draft {
+/// #     text: String,
+/// # }
+/// #
+/// # impl AtomFamily for Draft {
+/// #     type Key = u64;
+/// #
+/// #     fn create(_key: &u64) -> Self {
+/// #         Self { text: String::new() }
+/// #     }
+/// # }
+/// #
+/// #[derive(Properties, PartialEq)]
+/// struct PreviewProps {
+///     document_id: u64,
+/// }
+///
+/// #[function_component(Preview)]
+/// fn preview(props: &PreviewProps) -> Html {
+///     let draft = use_atom_family_value::<Draft>(props.document_id);
+///
+///     html! { <div>{&draft.text}</div> }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_family_value<T>(key: T::Key) -> Rc<T>
+where
+    T: AtomFamily,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    // The same `Rc` is passed to the selector while the key is equal.
+    let key = use_memo(key, |m| Rc::new(m.clone()));
+    let value = use_input_selector_value::<AtomFamilySelector<T>>((*key).clone());
+
+    use_effect_with(key, move |key| {
+        let key = (**key).clone();
+        let subscribers = root.get_state::<AtomFamilySubscribers<T>>();
+        subscribers.subscribe((*key).clone());
+
+        move || subscribers.unsubscribe(&root, &key)
+    });
+
+    value.value.clone()
+}
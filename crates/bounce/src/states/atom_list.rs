@@ -0,0 +1,472 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+use crate::utils::{notify_listeners, Id, Listener, ListenerVec};
+
+/// A stable key of an item in an atom list.
+///
+/// Keys are assigned when items are added to the list and do not change when items are moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AtomListKey(Id);
+
+struct AtomListValue<T> {
+    keys: Rc<Vec<AtomListKey>>,
+    items: HashMap<AtomListKey, Rc<T>>,
+}
+
+impl<T> Default for AtomListValue<T> {
+    fn default() -> Self {
+        Self {
+            keys: Rc::default(),
+            items: HashMap::new(),
+        }
+    }
+}
+
+type ItemListeners<T> = HashMap<AtomListKey, Rc<RefCell<ListenerVec<Option<Rc<T>>>>>>;
+
+/// The state of an atom list.
+///
+/// Listeners of the keys are only notified when the structure of the list changes and listeners
+/// of an item are only notified when the item with the same key changes.
+pub(crate) struct AtomListState<T>
+where
+    T: PartialEq + 'static,
+{
+    value: Rc<RefCell<AtomListValue<T>>>,
+    keys_listeners: Rc<RefCell<ListenerVec<Vec<AtomListKey>>>>,
+    item_listeners: Rc<RefCell<ItemListeners<T>>>,
+}
+
+impl<T> Clone for AtomListState<T>
+where
+    T: PartialEq + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            keys_listeners: self.keys_listeners.clone(),
+            item_listeners: self.item_listeners.clone(),
+        }
+    }
+}
+
+impl<T> Default for AtomListState<T>
+where
+    T: PartialEq + 'static,
+{
+    fn default() -> Self {
+        Self {
+            value: Rc::default(),
+            keys_listeners: Rc::default(),
+            item_listeners: Rc::default(),
+        }
+    }
+}
+
+impl<T> AtomListState<T>
+where
+    T: PartialEq + 'static,
+{
+    pub fn keys(&self) -> Rc<Vec<AtomListKey>> {
+        self.value.borrow().keys.clone()
+    }
+
+    pub fn get(&self, key: AtomListKey) -> Option<Rc<T>> {
+        self.value.borrow().items.get(&key).cloned()
+    }
+
+    pub fn insert(&self, index: usize, item: T) -> AtomListKey {
+        let key = AtomListKey(Id::new());
+
+        let keys = {
+            let mut value = self.value.borrow_mut();
+            Rc::make_mut(&mut value.keys).insert(index, key);
+            value.items.insert(key, item.into());
+
+            value.keys.clone()
+        };
+
+        notify_listeners(self.keys_listeners.clone(), keys);
+
+        key
+    }
+
+    pub fn push(&self, item: T) -> AtomListKey {
+        let index = self.value.borrow().keys.len();
+        self.insert(index, item)
+    }
+
+    pub fn set(&self, key: AtomListKey, item: T) {
+        let item = {
+            let mut value = self.value.borrow_mut();
+
+            match value.items.get_mut(&key) {
+                Some(m) if **m != item => {
+                    *m = item.into();
+                    m.clone()
+                }
+                _ => return,
+            }
+        };
+
+        self.notify_item_listeners(key, Some(item));
+    }
+
+    pub fn remove(&self, key: AtomListKey) -> Option<Rc<T>> {
+        let (keys, item) = {
+            let mut value = self.value.borrow_mut();
+            let item = value.items.remove(&key)?;
+            Rc::make_mut(&mut value.keys).retain(|m| *m != key);
+
+            (value.keys.clone(), item)
+        };
+
+        notify_listeners(self.keys_listeners.clone(), keys);
+        self.notify_item_listeners(key, None);
+        self.item_listeners.borrow_mut().remove(&key);
+
+        Some(item)
+    }
+
+    pub fn move_item(&self, from: usize, to: usize) {
+        let keys = {
+            let mut value = self.value.borrow_mut();
+
+            if from == to || from >= value.keys.len() || to >= value.keys.len() {
+                return;
+            }
+
+            let keys = Rc::make_mut(&mut value.keys);
+            let key = keys.remove(from);
+            keys.insert(to, key);
+
+            value.keys.clone()
+        };
+
+        notify_listeners(self.keys_listeners.clone(), keys);
+    }
+
+    fn notify_item_listeners(&self, key: AtomListKey, item: Option<Rc<T>>) {
+        let listeners = self.item_listeners.borrow().get(&key).cloned();
+
+        if let Some(m) = listeners {
+            notify_listeners(m, item.into());
+        }
+    }
+
+    pub fn listen_keys(&self, callback: Rc<Callback<Rc<Vec<AtomListKey>>>>) -> Listener {
+        self.keys_listeners
+            .borrow_mut()
+            .push(Rc::downgrade(&callback));
+
+        Listener::new(callback)
+    }
+
+    pub fn listen_item(
+        &self,
+        key: AtomListKey,
+        callback: Rc<Callback<Rc<Option<Rc<T>>>>>,
+    ) -> Listener {
+        self.item_listeners
+            .borrow_mut()
+            .entry(key)
+            .or_default()
+            .borrow_mut()
+            .push(Rc::downgrade(&callback));
+
+        Listener::new(callback)
+    }
+}
+
+impl<T> AnyState for AtomListState<T>
+where
+    T: PartialEq + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A handle returned by [`use_atom_list`].
+///
+/// The handle holds the keys of the list in order and provides methods to modify the list.
+pub struct UseAtomListHandle<T>
+where
+    T: PartialEq + 'static,
+{
+    keys: Rc<Vec<AtomListKey>>,
+    root: BounceRootState,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> UseAtomListHandle<T>
+where
+    T: PartialEq + 'static,
+{
+    fn state(&self) -> AtomListState<T> {
+        self.root.get_state::<AtomListState<T>>()
+    }
+
+    /// Returns the keys of the items in the list in order.
+    pub fn keys(&self) -> &[AtomListKey] {
+        &self.keys
+    }
+
+    /// Returns the number of items in the list.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the list contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the current value of an item.
+    ///
+    /// This method does not subscribe to the item,
+    /// use [`use_atom_list_item`] to subscribe to the value of an item.
+    pub fn get(&self, key: AtomListKey) -> Option<Rc<T>> {
+        self.state().get(key)
+    }
+
+    /// Appends an item to the end of the list and returns its key.
+    pub fn push(&self, item: T) -> AtomListKey {
+        self.state().push(item)
+    }
+
+    /// Inserts an item at `index` and returns its key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&self, index: usize, item: T) -> AtomListKey {
+        self.state().insert(index, item)
+    }
+
+    /// Sets the value of an item.
+    ///
+    /// Only subscribers of the item are notified and only if the value has changed.
+    pub fn set(&self, key: AtomListKey, item: T) {
+        self.state().set(key, item)
+    }
+
+    /// Removes an item and returns its value if it exists.
+    pub fn remove(&self, key: AtomListKey) -> Option<Rc<T>> {
+        self.state().remove(key)
+    }
+
+    /// Moves the item at index `from` to index `to`.
+    ///
+    /// Nothing happens if any index is out of bounds.
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.state().move_item(from, to)
+    }
+}
+
+impl<T> Clone for UseAtomListHandle<T>
+where
+    T: PartialEq + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            root: self.root.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for UseAtomListHandle<T>
+where
+    T: PartialEq + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseAtomListHandle")
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+/// A hook to connect to an atom list with items of type `T`.
+///
+/// An atom list is an ordered list of items where each item can be subscribed to separately.
+/// This hook subscribes to the structure of the list and only re-renders when items are added,
+/// removed or moved. Use [`use_atom_list_item`] to subscribe to the value of an item.
+///
+/// Returns a [`UseAtomListHandle<T>`].
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq)]
+/// struct Todo {
+///     title: String,
+///     done: bool,
+/// }
+///
+/// #[derive(Properties, PartialEq)]
+/// struct RowProps {
+///     todo_key: AtomListKey,
+/// }
+///
+/// #[function_component(Row)]
+/// fn row(props: &RowProps) -> Html {
+///     // Only re-renders when this todo changes.
+///     match use_atom_list_item::<Todo>(props.todo_key) {
+///         Some(m) => html! { <li>{&m.title}</li> },
+///         None => Html::default(),
+///     }
+/// }
+///
+/// #[function_component(Todos)]
+/// fn todos() -> Html {
+///     let todos = use_atom_list::<Todo>();
+///
+///     let on_add = {
+///         let todos = todos.clone();
+///         Callback::from(move |_| {
+///             todos.push(Todo { title: "New Todo".into(), done: false });
+///         })
+///     };
+///
+///     html! {
+///         <>
+///             <ul>
+///                 {for todos.keys().iter().map(|m| html! { <Row key={format!("{m:?}")} todo_key={*m} /> })}
+///             </ul>
+///             <button onclick={on_add}>{"Add"}</button>
+///         </>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_list<T>() -> UseAtomListHandle<T>
+where
+    T: PartialEq + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    let keys = {
+        let root = root.clone();
+        use_state_eq(move || root.get_state::<AtomListState<T>>().keys())
+    };
+    #[cfg(feature = "devtools")]
+    let component = crate::devtools::use_component_key();
+
+    {
+        let keys = keys.clone();
+        use_memo(root.clone(), move |root| {
+            let state = root.get_state::<AtomListState<T>>();
+
+            // we need to set the value here again in case the value has changed between the
+            // initial render and the listener is registered.
+            keys.set(state.keys());
+
+            state.listen_keys(Rc::new(Callback::from(move |m| {
+                #[cfg(feature = "devtools")]
+                crate::devtools::record_update(component);
+
+                keys.set(m);
+            })))
+        });
+    }
+
+    UseAtomListHandle {
+        keys: (*keys).clone(),
+        root,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// A read-only hook to connect to the value of an item in an atom list.
+///
+/// This hook only re-renders when the item with the same key changes.
+///
+/// Returns `None` if the item does not exist (e.g.: it has been removed).
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq)]
+/// struct Todo {
+///     title: String,
+///     done: bool,
+/// }
+///
+/// #[derive(Properties, PartialEq)]
+/// struct TodoTitleProps {
+///     todo_key: AtomListKey,
+/// }
+///
+/// #[function_component(TodoTitle)]
+/// fn todo_title(props: &TodoTitleProps) -> Html {
+///     let todo = use_atom_list_item::<Todo>(props.todo_key);
+///
+///     match todo {
+///         Some(m) if m.done => html! { <s>{&m.title}</s> },
+///         Some(m) => html! { <span>{&m.title}</span> },
+///         None => Html::default(),
+///     }
+/// }
+/// ```
+///
+/// See [`use_atom_list`] for how items are added.
+#[hook]
+pub fn use_atom_list_item<T>(key: AtomListKey) -> Option<Rc<T>>
+where
+    T: PartialEq + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    let item = {
+        let root = root.clone();
+        use_state_eq(move || root.get_state::<AtomListState<T>>().get(key))
+    };
+    #[cfg(feature = "devtools")]
+    let component = crate::devtools::use_component_key();
+
+    {
+        let item = item.clone();
+        use_memo((root, key), move |(root, key)| {
+            let state = root.get_state::<AtomListState<T>>();
+
+            // we need to set the value here again in case the value has changed between the
+            // initial render and the listener is registered.
+            item.set(state.get(*key));
+
+            state.listen_item(
+                *key,
+                Rc::new(Callback::from(move |m: Rc<Option<Rc<T>>>| {
+                    #[cfg(feature = "devtools")]
+                    crate::devtools::record_update(component);
+
+                    item.set((*m).clone());
+                })),
+            )
+        });
+    }
+
+    (*item).clone()
+}
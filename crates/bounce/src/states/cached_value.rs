@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use crate::root_state::BounceRootState;
+use crate::states::slice::SliceState;
+use crate::Slice;
+
+struct CachedValue<V> {
+    value: Rc<V>,
+    expires_at: Duration,
+}
+
+impl<V> Clone for CachedValue<V> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+pub(crate) enum CachedValueAction<K, V> {
+    Insert {
+        key: Rc<K>,
+        value: Rc<V>,
+        now: Duration,
+        expires_at: Duration,
+    },
+}
+
+#[derive(Slice)]
+pub(crate) struct CachedValueSlice<K, V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+{
+    ctr: u64,
+    values: HashMap<Rc<K>, CachedValue<V>>,
+}
+
+impl<K, V> CachedValueSlice<K, V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+{
+    /// Returns the cached value if it has not expired at `now`.
+    pub(crate) fn get(&self, key: &K, now: Duration) -> Option<Rc<V>> {
+        self.values
+            .get(key)
+            .filter(|m| m.expires_at > now)
+            .map(|m| m.value.clone())
+    }
+}
+
+impl<K, V> Reducible for CachedValueSlice<K, V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+{
+    type Action = CachedValueAction<K, V>;
+
+    fn reduce(mut self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            Self::Action::Insert {
+                key,
+                value,
+                now,
+                expires_at,
+            } => {
+                let this = Rc::make_mut(&mut self);
+                this.ctr += 1;
+
+                // Evicts expired values.
+                this.values.retain(|_, m| m.expires_at > now);
+
+                this.values.insert(key, CachedValue { value, expires_at });
+            }
+        }
+
+        self
+    }
+}
+
+impl<K, V> Default for CachedValueSlice<K, V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+{
+    fn default() -> Self {
+        Self {
+            ctr: 0,
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl<K, V> PartialEq for CachedValueSlice<K, V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        self.ctr == rhs.ctr
+    }
+}
+
+impl<K, V> Clone for CachedValueSlice<K, V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ctr: self.ctr,
+            values: self.values.clone(),
+        }
+    }
+}
+
+/// A hook to compute a value once and cache it under the bounce root for a period of time.
+///
+/// The value is cached by its key and shared between all components under the same
+/// `<BounceRoot />`. If no value is cached for the key or the cached value is older than `ttl`,
+/// `init_fn` is called to create a new value. Expired values are evicted when a new value is
+/// cached.
+///
+/// This is useful for expensive computations (e.g.: parsing) that do not warrant a
+/// [Query](crate::query).
+///
+/// Returns `Rc<V>`.
+///
+/// # Note
+///
+/// Components are not re-rendered when a value expires. A new value is computed the next time a
+/// component using the key is rendered.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use std::time::Duration;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Properties, PartialEq)]
+/// struct Props {
+///     source: Rc<String>,
+/// }
+///
+/// #[function_component(WordCount)]
+/// fn word_count(props: &Props) -> Html {
+///     let count = use_cached_value(props.source.clone(), Duration::from_secs(60), |source| {
+///         source.split_whitespace().count()
+///     });
+///
+///     html! { <div>{"Words: "}{count}</div> }
+/// }
+/// ```
+#[hook]
+pub fn use_cached_value<K, V, F>(key: Rc<K>, ttl: Duration, init_fn: F) -> Rc<V>
+where
+    K: Eq + Hash + 'static,
+    V: 'static,
+    F: FnOnce(&K) -> V,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let state = root.get_state::<SliceState<CachedValueSlice<K, V>>>();
+    let now = root.clock().now();
+
+    // Values are read without subscribing, components do not need to be notified when other
+    // values are cached.
+    if let Some(m) = state.get().get(&key, now) {
+        return m;
+    }
+
+    let value = Rc::new(init_fn(&key));
+
+    state.dispatch(CachedValueAction::Insert {
+        key,
+        value: value.clone(),
+        now,
+        expires_at: now + ttl,
+    });
+
+    value
+}
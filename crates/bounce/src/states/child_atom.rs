@@ -0,0 +1,142 @@
+use std::fmt;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use super::atom::Atom;
+use crate::root_state::BounceRootState;
+
+/// A trait to be implemented on atoms with an initial value that is derived from a parent atom.
+///
+/// Atoms that implement this trait need to be denoted with the `#[bounce(child)]` attribute.
+/// When the child atom is created, its value is derived from the current value of the parent
+/// atom. Afterwards, the child atom is independent of its parent: a change of the parent does
+/// not change the child until it is re-synced with [`use_atom_resync`] or by applying a
+/// [`ResyncNotion`].
+///
+/// If an initial value is provided with `get_init_states` of the `<BounceRoot />`, that value is
+/// used instead.
+///
+/// # Example
+///
+/// ```
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct GlobalSettings {
+///     font_size: u32,
+/// }
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// #[bounce(child)]
+/// struct EditorSettings {
+///     font_size: u32,
+/// }
+///
+/// impl ChildAtom for EditorSettings {
+///     type Parent = GlobalSettings;
+///
+///     fn derive_from(parent: &GlobalSettings) -> Self {
+///         Self {
+///             font_size: parent.font_size,
+///         }
+///     }
+/// }
+/// ```
+pub trait ChildAtom: Atom {
+    /// The atom that the value of this atom is derived from.
+    type Parent: Atom + 'static;
+
+    /// Derives the value of this atom from the value of its parent.
+    fn derive_from(parent: &Self::Parent) -> Self;
+}
+
+/// A notion that derives the value of a [`ChildAtom`] from the value of its parent again.
+///
+/// This notion is accepted automatically by atoms with the `#[bounce(child)]` attribute.
+///
+/// See: [`use_atom_resync`]
+pub struct ResyncNotion<T>
+where
+    T: ChildAtom,
+{
+    parent: Rc<T::Parent>,
+}
+
+impl<T> ResyncNotion<T>
+where
+    T: ChildAtom,
+{
+    /// Creates a notion that derives the child atom from `parent`.
+    pub fn new(parent: Rc<T::Parent>) -> Self {
+        Self { parent }
+    }
+
+    /// Returns the value of the parent that the child atom is derived from.
+    pub fn parent(&self) -> &T::Parent {
+        &self.parent
+    }
+}
+
+impl<T> fmt::Debug for ResyncNotion<T>
+where
+    T: ChildAtom,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResyncNotion").finish_non_exhaustive()
+    }
+}
+
+/// A hook to derive the value of a [`ChildAtom`] from the current value of its parent again.
+///
+/// Returns a function that re-syncs the child atom when it is called. Changes made to the
+/// child atom since it was created or last re-synced are discarded.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// # #[derive(Atom, PartialEq, Default)]
+/// # struct GlobalSettings {
+/// #     font_size: u32,
+/// # }
+/// #
+/// # #[derive(Atom, PartialEq, Default)]
+/// # #[bounce(child)]
+/// # struct EditorSettings {
+/// #     font_size: u32,
+/// # }
+/// #
+/// # impl ChildAtom for EditorSettings {
+/// #     type Parent = GlobalSettings;
+/// #
+/// #     fn derive_from(parent: &GlobalSettings) -> Self {
+/// #         Self {
+/// #             font_size: parent.font_size,
+/// #         }
+/// #     }
+/// # }
+/// #
+/// #[function_component(ResetEditorSettings)]
+/// fn reset_editor_settings() -> Html {
+///     let resync = use_atom_resync::<EditorSettings>();
+///     let onclick = Callback::from(move |_| resync());
+///
+///     html! { <button {onclick}>{"Use Global Settings"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_atom_resync<T>() -> Rc<dyn Fn()>
+where
+    T: ChildAtom + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move || {
+        let parent = root.states().get_atom_value::<T::Parent>();
+        root.apply_notion(Rc::new(ResyncNotion::<T>::new(parent)));
+    })
+}
@@ -0,0 +1,317 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use super::input_selector::{use_selected_value, InputSelector};
+use super::selector::Selector;
+use crate::root_state::BounceStates;
+use crate::states::registry::{StateDescriptor, StateKind};
+
+/// A state derived from selectors without defining a new type.
+///
+/// Derived selectors are created with [`map_selector`] and [`zip_selectors`] and read with
+/// [`use_derived_value`] or [`BounceStates::get_derived_value`]. Derived selectors that are equal
+/// share the same value, which is selected again when a selector it is derived from changes.
+pub trait DerivedSelector: Eq + Hash + Clone + 'static {
+    /// The type of the derived value.
+    type Output: PartialEq + 'static;
+
+    /// Selects the derived value from existing bounce states.
+    fn select(&self, states: &BounceStates) -> Rc<Self::Output>;
+}
+
+/// A function that maps the value of selector `A`, see [`map_selector`].
+///
+/// This is implemented for all functions and closures that take `&A` and are `Copy`.
+pub trait MapFn<A>: Fn(&A) -> <Self as MapFn<A>>::Value + Copy + 'static {
+    /// The type of the mapped value.
+    type Value: PartialEq + 'static;
+}
+
+impl<A, F, R> MapFn<A> for F
+where
+    F: Fn(&A) -> R + Copy + 'static,
+    R: PartialEq + 'static,
+{
+    type Value = R;
+}
+
+/// A selector derived from selector `A` with a function, created with [`map_selector`].
+pub struct MapSelector<A, F> {
+    f: F,
+    _marker: PhantomData<A>,
+}
+
+impl<A, F> Clone for MapSelector<A, F>
+where
+    F: Copy,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, F> Copy for MapSelector<A, F> where F: Copy {}
+
+// Each function and closure has its own type, so selectors mapped with the same function are
+// equal.
+impl<A, F> PartialEq for MapSelector<A, F> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<A, F> Eq for MapSelector<A, F> {}
+
+impl<A, F> Hash for MapSelector<A, F> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl<A, F> fmt::Debug for MapSelector<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapSelector")
+            .field("selector", &std::any::type_name::<A>())
+            .field("f", &std::any::type_name::<F>())
+            .finish()
+    }
+}
+
+impl<A, F> DerivedSelector for MapSelector<A, F>
+where
+    A: Selector + 'static,
+    F: MapFn<A>,
+{
+    type Output = F::Value;
+
+    fn select(&self, states: &BounceStates) -> Rc<F::Value> {
+        (self.f)(&states.get_selector_value::<A>()).into()
+    }
+}
+
+/// Derives a state from selector `A` with a function.
+///
+/// The derived state is identified by the type of the function, so the function must not capture
+/// any variables. Each closure has its own type: the same closure shares its value across renders
+/// and components, while 2 closures with the same body are different states. The value is only
+/// selected again when `A` changes and components are only notified if the result of the function
+/// changes.
+///
+/// # Panics
+///
+/// Panics if the function captures variables or is a function pointer, which do not identify a
+/// function by their type.
+///
+/// ```should_panic
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// use bounce::map_selector;
+///
+/// # #[derive(PartialEq)]
+/// # struct Count(u64);
+/// #
+/// # impl Selector for Count {
+/// #     fn select(_states: &BounceStates) -> Rc<Self> {
+/// #         Self(0).into()
+/// #     }
+/// # }
+/// #
+/// let offset = 1;
+/// // The closure captures `offset`.
+/// let _selector = map_selector::<Count, _>(move |m| m.0 + offset);
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::{map_selector, use_derived_value};
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Cart {
+///     items: Vec<u64>,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct CartSelector(Rc<Cart>);
+///
+/// impl Selector for CartSelector {
+///     fn select(states: &BounceStates) -> Rc<Self> {
+///         Self(states.get_atom_value::<Cart>()).into()
+///     }
+/// }
+///
+/// #[function_component(CartBadge)]
+/// fn cart_badge() -> Html {
+///     // Only re-renders when the number of items changes.
+///     let len = use_derived_value(map_selector::<CartSelector, _>(|m| m.0.items.len()));
+///
+///     html! { <div>{*len}</div> }
+/// }
+/// ```
+pub fn map_selector<A, F>(f: F) -> MapSelector<A, F>
+where
+    A: Selector + 'static,
+    F: MapFn<A>,
+{
+    // Functions and closures without captures are zero-sized, a value of another size cannot be
+    // identified by its type.
+    assert!(
+        std::mem::size_of::<F>() == 0,
+        "the function passed to map_selector must not capture any variables"
+    );
+
+    MapSelector {
+        f,
+        _marker: PhantomData,
+    }
+}
+
+/// A selector that combines the values of selectors `A` and `B`, created with
+/// [`zip_selectors`].
+pub struct ZipSelectors<A, B> {
+    _marker: PhantomData<(A, B)>,
+}
+
+impl<A, B> Clone for ZipSelectors<A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> Copy for ZipSelectors<A, B> {}
+
+impl<A, B> PartialEq for ZipSelectors<A, B> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<A, B> Eq for ZipSelectors<A, B> {}
+
+impl<A, B> Hash for ZipSelectors<A, B> {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl<A, B> fmt::Debug for ZipSelectors<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipSelectors")
+            .field("selectors", &std::any::type_name::<(A, B)>())
+            .finish()
+    }
+}
+
+impl<A, B> DerivedSelector for ZipSelectors<A, B>
+where
+    A: Selector + 'static,
+    B: Selector + 'static,
+{
+    type Output = (Rc<A>, Rc<B>);
+
+    fn select(&self, states: &BounceStates) -> Rc<Self::Output> {
+        (
+            states.get_selector_value::<A>(),
+            states.get_selector_value::<B>(),
+        )
+            .into()
+    }
+}
+
+/// Derives a state that combines the values of selectors `A` and `B`.
+///
+/// The value is selected again when either selector changes.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::{use_derived_value, zip_selectors};
+///
+/// #[derive(PartialEq)]
+/// struct Subtotal(u64);
+///
+/// impl Selector for Subtotal {
+///     fn select(_states: &BounceStates) -> Rc<Self> {
+///         Self(100).into()
+///     }
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct Shipping(u64);
+///
+/// impl Selector for Shipping {
+///     fn select(_states: &BounceStates) -> Rc<Self> {
+///         Self(10).into()
+///     }
+/// }
+///
+/// #[function_component(Total)]
+/// fn total() -> Html {
+///     let value = use_derived_value(zip_selectors::<Subtotal, Shipping>());
+///     let (subtotal, shipping) = &*value;
+///
+///     html! { <div>{subtotal.0 + shipping.0}</div> }
+/// }
+/// ```
+pub fn zip_selectors<A, B>() -> ZipSelectors<A, B>
+where
+    A: Selector + 'static,
+    B: Selector + 'static,
+{
+    ZipSelectors {
+        _marker: PhantomData,
+    }
+}
+
+/// The value of a derived selector.
+#[derive(PartialEq)]
+pub(crate) struct DerivedValue<D>
+where
+    D: DerivedSelector,
+{
+    pub inner: Rc<D::Output>,
+}
+
+impl<D> InputSelector for DerivedValue<D>
+where
+    D: DerivedSelector,
+{
+    type Input = D;
+
+    fn select(states: &BounceStates, input: Rc<D>) -> Rc<Self> {
+        Self {
+            inner: input.select(states),
+        }
+        .into()
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<D>(StateKind::Selector, listener_count)
+    }
+}
+
+/// A hook to connect to a [`DerivedSelector`].
+///
+/// The component re-renders when the derived value changes.
+///
+/// See [`map_selector`] and [`zip_selectors`] for examples.
+#[hook]
+pub fn use_derived_value<D>(derived: D) -> Rc<D::Output>
+where
+    D: DerivedSelector,
+{
+    // The derived selector is created in each render, so it is memoised to avoid allocating a
+    // new input each render.
+    let derived = use_memo(derived, |m| m.clone());
+
+    use_selected_value::<DerivedValue<D>>("use_derived_value", derived)
+        .unwrap_or_else(|e| e.resume_unwind())
+        .inner
+        .clone()
+}
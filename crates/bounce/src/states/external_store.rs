@@ -0,0 +1,457 @@
+use std::any::Any;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use super::atom::{use_atom, AtomSlice, UseAtomHandle};
+use super::slice::{use_slice_value, Slice, SliceState};
+use crate::root_state::BounceRootState;
+use crate::Atom;
+
+/// A store of another state management library that is mirrored into a bounce atom or slice.
+///
+/// This allows a codebase to migrate to bounce incrementally: once an [`ExternalStoreBridge`] (or
+/// an [`ExternalSliceBridge`]) is rendered, components that are migrated read and write the store
+/// with bounce, while components that are not migrated keep using the store directly. Changes
+/// made on either side are visible to the other.
+///
+/// The implementation forwards to the API of the store. For a store of yewdux, `get` and `set`
+/// forward to the methods of its global `Dispatch` and `subscribe` returns the `Dispatch` created
+/// by `Dispatch::subscribe`, which ends the subscription when it is dropped.
+///
+/// For the other direction, see [`SliceStore`].
+///
+/// # Example
+///
+/// ```
+/// use std::any::Any;
+/// use std::cell::RefCell;
+/// use std::rc::{Rc, Weak};
+///
+/// use bounce::ExternalStore;
+/// use yew::prelude::*;
+///
+/// #[derive(PartialEq)]
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// // The store and the listeners of the other library.
+/// thread_local! {
+///     static VALUE: RefCell<Rc<Counter>> = RefCell::new(Counter { count: 0 }.into());
+///     static LISTENERS: RefCell<Vec<Weak<Callback<Rc<Counter>>>>> = RefCell::default();
+/// }
+///
+/// impl ExternalStore for Counter {
+///     fn get() -> Rc<Self> {
+///         VALUE.with(|m| m.borrow().clone())
+///     }
+///
+///     fn set(value: Rc<Self>) {
+///         VALUE.with(|m| *m.borrow_mut() = value.clone());
+///
+///         let listeners = LISTENERS.with(|m| m.borrow().clone());
+///         for listener in listeners.iter().filter_map(|m| m.upgrade()) {
+///             listener.emit(value.clone());
+///         }
+///     }
+///
+///     fn subscribe(callback: Callback<Rc<Self>>) -> Box<dyn Any> {
+///         let callback = Rc::new(callback);
+///         LISTENERS.with(|m| m.borrow_mut().push(Rc::downgrade(&callback)));
+///
+///         // The listener is removed when the guard is dropped.
+///         Box::new(callback)
+///     }
+/// }
+/// ```
+pub trait ExternalStore: PartialEq + 'static {
+    /// Returns the current value of the store.
+    fn get() -> Rc<Self>;
+
+    /// Replaces the value of the store.
+    fn set(value: Rc<Self>);
+
+    /// Subscribes to changes of the store.
+    ///
+    /// The subscription ends when the returned guard is dropped.
+    fn subscribe(callback: Callback<Rc<Self>>) -> Box<dyn Any>;
+}
+
+/// The atom that mirrors an [`ExternalStore`].
+///
+/// This type dereferences to the value of the store.
+#[derive(Atom)]
+pub struct StoreAtom<S>
+where
+    S: ExternalStore,
+{
+    inner: Rc<S>,
+}
+
+impl<S> StoreAtom<S>
+where
+    S: ExternalStore,
+{
+    /// Creates an atom with a value of the store.
+    pub fn new(value: Rc<S>) -> Self {
+        Self { inner: value }
+    }
+
+    /// Returns the value of the store.
+    pub fn value(&self) -> Rc<S> {
+        self.inner.clone()
+    }
+}
+
+impl<S> Default for StoreAtom<S>
+where
+    S: ExternalStore,
+{
+    fn default() -> Self {
+        Self::new(S::get())
+    }
+}
+
+impl<S> PartialEq for StoreAtom<S>
+where
+    S: ExternalStore,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<S> Deref for StoreAtom<S>
+where
+    S: ExternalStore,
+{
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<S> fmt::Debug for StoreAtom<S>
+where
+    S: ExternalStore + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoreAtom")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+/// Properties of the [`ExternalStoreBridge`] and [`ExternalSliceBridge`] components.
+#[derive(Properties, Debug)]
+pub struct ExternalStoreBridgeProps<S>
+where
+    S: ExternalStore,
+{
+    #[prop_or_default]
+    _marker: PhantomData<S>,
+}
+
+impl<S> PartialEq for ExternalStoreBridgeProps<S>
+where
+    S: ExternalStore,
+{
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// A component that keeps an [`ExternalStore`] and its [`StoreAtom`] in sync.
+///
+/// The atom is updated when the store notifies its subscribers and the store is set when the
+/// atom is set with bounce. Values that are equal are not written again, so an update is not
+/// echoed back to where it came from.
+///
+/// The bridge needs to be rendered under the `<BounceRoot />` for as long as the store is used
+/// with bounce. Only one bridge should be rendered for each store.
+///
+/// # Example
+///
+/// ```
+/// # use std::any::Any;
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::BounceRoot;
+/// use bounce::{ExternalStore, ExternalStoreBridge};
+///
+/// #[derive(PartialEq)]
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// impl ExternalStore for Counter {
+///     // ...
+/// #     fn get() -> Rc<Self> {
+/// #         Self { count: 0 }.into()
+/// #     }
+/// #
+/// #     fn set(_value: Rc<Self>) {}
+/// #
+/// #     fn subscribe(_callback: Callback<Rc<Self>>) -> Box<dyn Any> {
+/// #         Box::new(())
+/// #     }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <ExternalStoreBridge<Counter> />
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(ExternalStoreBridge)]
+pub fn external_store_bridge<S>(_props: &ExternalStoreBridgeProps<S>) -> Html
+where
+    S: ExternalStore,
+{
+    use_store_bridge::<S, AtomSlice<StoreAtom<S>>>(
+        |m| {
+            AtomSlice {
+                inner: StoreAtom::new(m).into(),
+            }
+            .into()
+        },
+        |m| m.inner.value(),
+    );
+
+    Html::default()
+}
+
+/// A component that keeps an [`ExternalStore`] and a [`Slice`](macro@crate::Slice) of the same
+/// type in sync.
+///
+/// This is the [`ExternalStoreBridge`] for stores that are also slices, so that migrated
+/// components can dispatch actions with [`use_slice`](crate::use_slice). The slice is set to the
+/// value of the store when the bridge is mounted and the store is set to the reduced value of the
+/// slice after each action.
+///
+/// Only one bridge should be rendered for each store.
+///
+/// # Example
+///
+/// ```
+/// # use std::any::Any;
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::BounceRoot;
+/// use bounce::{ExternalSliceBridge, ExternalStore};
+///
+/// enum CounterAction {
+///     Increment,
+/// }
+///
+/// #[derive(PartialEq, Default, Slice)]
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// impl Reducible for Counter {
+///     type Action = CounterAction;
+///
+///     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+///         match action {
+///             CounterAction::Increment => Self {
+///                 count: self.count + 1,
+///             }
+///             .into(),
+///         }
+///     }
+/// }
+///
+/// impl ExternalStore for Counter {
+///     // ...
+/// #     fn get() -> Rc<Self> {
+/// #         Self { count: 0 }.into()
+/// #     }
+/// #
+/// #     fn set(_value: Rc<Self>) {}
+/// #
+/// #     fn subscribe(_callback: Callback<Rc<Self>>) -> Box<dyn Any> {
+/// #         Box::new(())
+/// #     }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <ExternalSliceBridge<Counter> />
+///             // children...
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(ExternalSliceBridge)]
+pub fn external_slice_bridge<S>(_props: &ExternalStoreBridgeProps<S>) -> Html
+where
+    S: ExternalStore + Slice,
+{
+    use_store_bridge::<S, S>(|m| m, |m| m.clone());
+
+    Html::default()
+}
+
+/// Mirrors an [`ExternalStore`] into the slice `T`.
+///
+/// Values that are equal are not written again, so an update is not echoed back to where it
+/// came from.
+#[hook]
+fn use_store_bridge<S, T>(wrap: fn(Rc<S>) -> Rc<T>, unwrap: fn(&Rc<T>) -> Rc<S>)
+where
+    S: ExternalStore,
+    T: Slice + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let value = use_slice_value::<T>();
+
+    // Store -> Slice
+    use_effect_with(root, move |root| {
+        let state = root.get_state::<SliceState<T>>();
+
+        // The store may have changed before the subscription is registered.
+        state.replace(wrap(S::get()));
+
+        let subscription = S::subscribe(Callback::from(move |m: Rc<S>| {
+            state.replace(wrap(m));
+        }));
+
+        move || drop(subscription)
+    });
+
+    // Slice -> Store
+    use_effect_with(value, move |value| {
+        let value = unwrap(value);
+
+        if S::get() != value {
+            S::set(value);
+        }
+    });
+}
+
+/// A hook to connect to an [`ExternalStore`] through its [`StoreAtom`].
+///
+/// Setting the atom also sets the store, provided that an [`ExternalStoreBridge`] is rendered.
+///
+/// # Example
+///
+/// ```
+/// # use std::any::Any;
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// use bounce::{use_external_store, ExternalStore, StoreAtom};
+///
+/// #[derive(PartialEq)]
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// impl ExternalStore for Counter {
+///     // ...
+/// #     fn get() -> Rc<Self> {
+/// #         Self { count: 0 }.into()
+/// #     }
+/// #
+/// #     fn set(_value: Rc<Self>) {}
+/// #
+/// #     fn subscribe(_callback: Callback<Rc<Self>>) -> Box<dyn Any> {
+/// #         Box::new(())
+/// #     }
+/// }
+///
+/// #[function_component(CounterView)]
+/// fn counter_view() -> Html {
+///     let counter = use_external_store::<Counter>();
+///
+///     let onclick = {
+///         let counter = counter.clone();
+///         Callback::from(move |_| {
+///             counter.set(StoreAtom::new(Counter { count: counter.count + 1 }.into()))
+///         })
+///     };
+///
+///     html! { <button {onclick}>{counter.count}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_external_store<S>() -> UseAtomHandle<StoreAtom<S>>
+where
+    S: ExternalStore,
+{
+    use_atom::<StoreAtom<S>>()
+}
+
+/// A handle to read, update and subscribe to a [`Slice`](macro@crate::Slice) outside of
+/// components.
+///
+/// This is the other direction of [`ExternalStore`]: code that is not migrated yet, such as the
+/// listeners and reducers of another state management library, can keep a `SliceStore` to use a
+/// slice that has been migrated to bounce.
+///
+/// See: [`UseSliceHandle::to_store`](crate::UseSliceHandle::to_store)
+pub struct SliceStore<T>
+where
+    T: Slice,
+{
+    state: SliceState<T>,
+}
+
+impl<T> SliceStore<T>
+where
+    T: Slice + 'static,
+{
+    pub(crate) fn new(state: SliceState<T>) -> Self {
+        Self { state }
+    }
+
+    /// Returns the current value of the slice.
+    pub fn get(&self) -> Rc<T> {
+        self.state.get()
+    }
+
+    /// Dispatches `Action` to the slice.
+    pub fn dispatch(&self, action: T::Action) {
+        self.state.dispatch(action);
+    }
+
+    /// Subscribes to changes of the slice.
+    ///
+    /// The subscription ends when the returned guard is dropped.
+    pub fn subscribe(&self, callback: Callback<Rc<T>>) -> Box<dyn Any> {
+        Box::new(self.state.listen(Rc::new(callback)))
+    }
+}
+
+impl<T> Clone for SliceStore<T>
+where
+    T: Slice,
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SliceStore<T>
+where
+    T: Slice,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SliceStore").finish_non_exhaustive()
+    }
+}
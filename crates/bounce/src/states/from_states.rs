@@ -0,0 +1,48 @@
+use crate::root_state::BounceStates;
+
+/// A trait to create the initial value of a state from other states.
+///
+/// Currently, only Slices and Atoms can be created from states. States that are created from
+/// other states need to be denoted with the `from_states` attribute.
+///
+/// If an initial value is provided with `get_init_states` of the `<BounceRoot />`, that value is
+/// used instead.
+///
+/// # Panics
+///
+/// States that depend on each other in a cycle cannot be created. In debug builds, creating such
+/// a state panics. In release builds, the state that closes the cycle is created with its default
+/// value.
+///
+/// # Example
+///
+/// ```
+/// use bounce::prelude::*;
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Settings {
+///     page_size: usize,
+/// }
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// #[bounce(from_states)] // states created from other states need to be denoted with the from_states attribute.
+/// struct Pagination {
+///     page: usize,
+///     page_size: usize,
+/// }
+///
+/// impl FromStates for Pagination {
+///     fn create_from(states: &BounceStates) -> Self {
+///         let settings = states.get_atom_value::<Settings>();
+///
+///         Self {
+///             page: 0,
+///             page_size: settings.page_size,
+///         }
+///     }
+/// }
+/// ```
+pub trait FromStates {
+    /// Creates the initial value of the state.
+    fn create_from(states: &BounceStates) -> Self;
+}
@@ -1,8 +1,9 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use futures::future::LocalBoxFuture;
+use futures::future::{abortable, AbortHandle, LocalBoxFuture};
 use wasm_bindgen::prelude::*;
 use yew::platform::spawn_local;
 use yew::prelude::*;
@@ -200,49 +201,133 @@ where
     let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
 
     Rc::new(move |input: T::Input| {
+        spawn_local(future_notion_run::<T>(root.clone(), input.into(), true));
+    })
+}
+
+/// A hook to run the future notions of bounce (e.g.: queries), which serve mocks in their own
+/// bodies.
+#[cfg(feature = "query")]
+#[hook]
+pub(crate) fn use_internal_future_notion_runner<T>() -> Rc<dyn Fn(T::Input)>
+where
+    T: FutureNotion + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move |input: T::Input| {
+        run_future_notion::<T>(root.clone(), input.into());
+    })
+}
+
+/// Runs a future notion of bounce under a bounce root.
+///
+/// See: [`use_internal_future_notion_runner`]
+#[cfg(feature = "query")]
+pub(crate) fn run_future_notion<T>(root: BounceRootState, input: Rc<T::Input>)
+where
+    T: FutureNotion + 'static,
+{
+    spawn_local(future_notion_run::<T>(root, input, false));
+}
+
+/// Runs a future notion that can be aborted with the returned handle.
+///
+/// If the run is aborted after it has started, [`Deferred::Outdated`] is applied instead of
+/// [`Deferred::Completed`].
+pub(crate) fn run_future_notion_abortable<T>(
+    root: BounceRootState,
+    input: Rc<T::Input>,
+) -> (AbortHandle, impl Future<Output = ()>)
+where
+    T: FutureNotion + 'static,
+{
+    let started = Rc::new(Cell::new(false));
+    let (run, handle) = {
+        let started = started.clone();
         let root = root.clone();
-        let input = Rc::new(input);
+        let input = input.clone();
 
-        spawn_local(async move {
-            root.apply_notion(Rc::new(Deferred::<T>::Pending {
-                input: input.clone(),
-            }));
-
-            let states = root.states();
-
-            // send the listeners in to be destroyed.
-            let listeners = Rc::new(RefCell::new(None));
-            let listener_run = Rc::new(AtomicBool::new(false));
-
-            {
-                let listener_run = listener_run.clone();
-                let listeners = listeners.clone();
-                let root = root.clone();
-                let input = input.clone();
-                states.add_listener_callback(Rc::new(Callback::from(move |_| {
-                    // There's a chance that the listeners might be called during the time while the future
-                    // notion is running and there will be nothing to drop.
-                    let listeners = listeners.borrow_mut().take();
-                    let last_listener_run = listener_run.swap(true, Ordering::Relaxed);
-
-                    if !last_listener_run || listeners.is_some() {
-                        root.apply_notion(Rc::new(Deferred::<T>::Outdated {
-                            input: input.clone(),
-                        }));
-                    }
-                })))
-            }
+        abortable(async move {
+            started.set(true);
+            future_notion_run::<T>(root, input, true).await;
+        })
+    };
 
-            let output = T::run(&states, &input).await;
+    let run = async move {
+        if run.await.is_err() && started.get() {
+            root.apply_notion(Rc::new(Deferred::<T>::Outdated { input }));
+        }
+    };
 
-            if !listener_run.load(Ordering::Relaxed) {
-                let _result = listeners.borrow_mut().replace(states.take_listeners());
+    (handle, run)
+}
+
+/// Runs the body of a future notion, or serves its mock if it is `mockable`.
+#[cfg(feature = "query")]
+async fn run_body<T>(states: &BounceStates, input: &T::Input, mockable: bool) -> T::Output
+where
+    T: FutureNotion + 'static,
+{
+    if mockable {
+        crate::query::run_mocked_future_notion::<T>(states, input).await
+    } else {
+        T::run(states, input).await
+    }
+}
+
+#[cfg(not(feature = "query"))]
+async fn run_body<T>(states: &BounceStates, input: &T::Input, _mockable: bool) -> T::Output
+where
+    T: FutureNotion + 'static,
+{
+    T::run(states, input).await
+}
+
+async fn future_notion_run<T>(root: BounceRootState, input: Rc<T::Input>, mockable: bool)
+where
+    T: FutureNotion + 'static,
+{
+    root.apply_notion(Rc::new(Deferred::<T>::Pending {
+        input: input.clone(),
+    }));
+
+    let states = root.states();
+
+    // send the listeners in to be destroyed.
+    let listeners = Rc::new(RefCell::new(None));
+    let listener_run = Rc::new(AtomicBool::new(false));
+
+    {
+        let listener_run = listener_run.clone();
+        let listeners = listeners.clone();
+        let root = root.clone();
+        let input = input.clone();
+        states.add_listener_callback(Rc::new(Callback::from(move |_| {
+            // There's a chance that the listeners might be called during the time while the future
+            // notion is running and there will be nothing to drop.
+            let listeners = listeners.borrow_mut().take();
+            let last_listener_run = listener_run.swap(true, Ordering::Relaxed);
+
+            if !last_listener_run || listeners.is_some() {
+                #[cfg(feature = "devtools")]
+                crate::devtools::record_outdated::<T>();
+
+                root.apply_notion(Rc::new(Deferred::<T>::Outdated {
+                    input: input.clone(),
+                }));
             }
+        })))
+    }
 
-            root.apply_notion(Rc::new(Deferred::<T>::Completed {
-                input,
-                output: output.into(),
-            }));
-        });
-    })
+    let output = run_body::<T>(&states, &input, mockable).await;
+
+    if !listener_run.load(Ordering::Relaxed) {
+        let _result = listeners.borrow_mut().replace(states.take_listeners());
+    }
+
+    root.apply_notion(Rc::new(Deferred::<T>::Completed {
+        input,
+        output: output.into(),
+    }));
 }
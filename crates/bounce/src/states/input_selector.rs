@@ -1,28 +1,78 @@
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
 use std::hash::Hash;
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
 
 use anymap2::AnyMap;
 use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 
 use crate::any_state::AnyState;
 use crate::root_state::{BounceRootState, BounceStates};
-use crate::utils::{notify_listeners, Listener, ListenerVec};
+use crate::states::registry::{StateDescriptor, StateKind};
+use crate::utils::{batch_notifications, notify_listeners, Listener, ListenerVec};
 
 /// An auto-updating derived state, similar to [`Selector`](crate::Selector), but with an input.
 ///
 /// Each selector with a different input are treated as a different selector.
 ///
 /// It will automatically update when any selected state changes and only notifies registered
-/// hooks when `prev_value != next_value`.
+/// hooks when `prev_value != next_value`. In the browser, changes made within the same tick are
+/// batched, so the selector is selected once per tick.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Scores {
+///     inner: Vec<u64>,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct ScoreAt {
+///     inner: Option<u64>,
+/// }
+///
+/// impl InputSelector for ScoreAt {
+///     type Input = usize;
+///
+///     fn select(states: &BounceStates, input: Rc<usize>) -> Rc<Self> {
+///         let scores = states.get_atom_value::<Scores>();
+///
+///         Self {
+///             inner: scores.inner.get(*input).copied(),
+///         }
+///         .into()
+///     }
+/// }
+/// ```
+///
+/// See: [`use_input_selector_value`]
 pub trait InputSelector: PartialEq {
     /// The input type of the current input selector.
     type Input: 'static + Eq + Hash;
 
+    /// Whether the selector is selected lazily.
+    ///
+    /// By default, a selector is selected again each time a state it depends on changes, even if
+    /// no component is subscribed to it. When this is `true` and no component or state is
+    /// subscribed to the selector, a change only invalidates the current value and the selector
+    /// is selected again when it is read next time.
+    ///
+    /// This reduces recomputation for selectors whose consumers are often unmounted.
+    ///
+    /// Defaults to `false`.
+    const LAZY: bool = false;
+
     /// Selects `self` from existing bounce states with an input.
     ///
     /// # Panics
@@ -30,6 +80,114 @@ pub trait InputSelector: PartialEq {
     /// `states.get_selector_value::<T>()` will panic if you are trying to create a loop by selecting current selector
     /// again.
     fn select(states: &BounceStates, input: Rc<Self::Input>) -> Rc<Self>;
+
+    /// Describes the input selector for [`use_registered_states`](crate::use_registered_states).
+    #[doc(hidden)]
+    fn describe_state(listener_count: usize) -> StateDescriptor
+    where
+        Self: 'static + Sized,
+    {
+        StateDescriptor::new::<Self>(StateKind::InputSelector, listener_count)
+    }
+}
+
+/// An error returned when a selector has panicked while it was selected.
+///
+/// A selector that has panicked is poisoned: the error is returned to all hooks that read it
+/// instead of selecting it again on each render. The selector is selected again when any state
+/// that it has read before it panicked changes.
+///
+/// Panics can only be caught on targets that unwind (e.g.: server-side rendering on native
+/// targets). On `wasm32-unknown-unknown`, panics abort by default and cannot be recovered from.
+///
+/// See: [`use_selector_value_fallible`](crate::use_selector_value_fallible) and
+/// [`use_input_selector_value_fallible`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorPoisoned {
+    /// The type name of the selector.
+    pub selector: &'static str,
+    /// The message of the panic, if it is a string.
+    pub message: Option<String>,
+}
+
+impl SelectorPoisoned {
+    fn new<T>(payload: &(dyn Any + Send)) -> Self
+    where
+        T: InputSelector + 'static,
+    {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|m| m.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned());
+
+        Self {
+            selector: T::describe_state(0).type_name,
+            message,
+        }
+    }
+
+    /// Resumes the panic of the selector.
+    ///
+    /// Unlike `panic!`, this does not invoke the panic hook again, so the panic is only reported
+    /// once, when the selector has panicked.
+    pub(crate) fn resume_unwind(self) -> ! {
+        panic::resume_unwind(Box::new(self.to_string()))
+    }
+}
+
+impl fmt::Display for SelectorPoisoned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "selector {} has panicked", self.selector)?;
+
+        if let Some(ref m) = self.message {
+            write!(f, ": {m}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for SelectorPoisoned {}
+
+/// The value of a selector, or the error if the selector has panicked.
+pub(crate) type SelectedValue<T> = Result<Rc<T>, SelectorPoisoned>;
+
+thread_local! {
+    // Selectors whose dependencies have changed and are waiting to be selected again.
+    static SCHEDULED_SELECTS: RefCell<VecDeque<Box<dyn FnOnce()>>> = RefCell::default();
+}
+
+/// Schedules a selector to be selected again.
+///
+/// In the browser, scheduled selectors are selected in a microtask, so a selector whose
+/// dependencies change several times within the same tick is only selected once.
+fn schedule_select(select: Box<dyn FnOnce()>) {
+    #[cfg(feature = "devtools")]
+    let select = crate::devtools::preserve_change_chain(select);
+
+    let is_first = SCHEDULED_SELECTS.with(|m| {
+        let mut m = m.borrow_mut();
+        m.push_back(select);
+
+        m.len() == 1
+    });
+
+    // There is no microtask queue outside of the browser, selectors are selected immediately.
+    if cfg!(not(target_arch = "wasm32")) {
+        flush_scheduled_selects();
+    } else if is_first {
+        spawn_local(async { flush_scheduled_selects() });
+    }
+}
+
+/// Selects all scheduled selectors.
+///
+/// This is called before a selector is read, so reads made in the same tick as a change (e.g.:
+/// by selectors that are derived from scheduled selectors) do not return outdated values.
+fn flush_scheduled_selects() {
+    while let Some(m) = SCHEDULED_SELECTS.with(|m| m.borrow_mut().pop_front()) {
+        m();
+    }
 }
 
 #[derive(Debug)]
@@ -38,10 +196,11 @@ where
     T: InputSelector,
 {
     input: Rc<T::Input>,
-    value: Rc<RefCell<Option<Rc<T>>>>,
-    listeners: Rc<RefCell<ListenerVec<T>>>,
+    value: Rc<RefCell<Option<SelectedValue<T>>>>,
+    listeners: Rc<RefCell<ListenerVec<SelectedValue<T>>>>,
     state_listener_handles: Rc<RefCell<Vec<Listener>>>,
     states: Rc<RefCell<Option<Rc<BounceStates>>>>,
+    scheduled: Rc<Cell<bool>>,
 }
 
 impl<T> Clone for InputSelectorState<T>
@@ -55,6 +214,7 @@ where
             listeners: self.listeners.clone(),
             state_listener_handles: self.state_listener_handles.clone(),
             states: self.states.clone(),
+            scheduled: self.scheduled.clone(),
         }
     }
 }
@@ -70,16 +230,24 @@ where
             listeners: Rc::default(),
             state_listener_handles: Rc::default(),
             states: Rc::default(),
+            scheduled: Rc::default(),
         }
     }
 
-    pub fn select_value(&self, states: &BounceStates) -> Rc<T> {
+    pub fn select_value(&self, states: &BounceStates) -> SelectedValue<T> {
         let self_ = self.clone();
         states.add_listener_callback(Rc::new(Callback::from(move |_: ()| {
             self_.clone().refresh();
         })));
 
-        let next_value = T::select(states, self.input.clone());
+        // States read before the selector has panicked are still listened to, so the selector is
+        // selected again when any of them changes.
+        //
+        // This only catches panics on targets that unwind, on wasm32 the panic aborts.
+        let next_value = panic::catch_unwind(AssertUnwindSafe(|| {
+            states.with_snapshot(|states| T::select(states, self.input.clone()))
+        }))
+        .map_err(|e| SelectorPoisoned::new::<T>(e.as_ref()));
 
         let mut handles = self.state_listener_handles.borrow_mut();
         *handles = states.take_listeners();
@@ -87,46 +255,92 @@ where
         next_value
     }
 
+    /// Returns the value of the selector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is poisoned.
     pub fn get(&self, states: BounceStates) -> Rc<T> {
-        let mut value = self.value.borrow_mut();
+        self.try_get(states).unwrap_or_else(|e| e.resume_unwind())
+    }
+
+    pub fn try_get(&self, states: BounceStates) -> SelectedValue<T> {
+        flush_scheduled_selects();
+
+        // Notifications of updates made while selecting are sent after the value is stored.
+        batch_notifications(|| {
+            let mut value = self.value.borrow_mut();
 
-        match value.clone() {
-            Some(m) => m,
-            None => {
-                let next_value = self.select_value(&states);
-                let mut last_states = self.states.borrow_mut();
-                *last_states = Some(Rc::new(states));
-                *value = Some(next_value.clone());
+            match value.clone() {
+                Some(m) => m,
+                None => {
+                    let next_value = self.select_value(&states);
+                    let mut last_states = self.states.borrow_mut();
+                    *last_states = Some(Rc::new(states));
+                    *value = Some(next_value.clone());
 
-                next_value
+                    next_value
+                }
             }
-        }
+        })
     }
 
+    /// Schedules the selector to be selected again after a state it depends on has changed.
     pub fn refresh(&self) {
-        if let Some(states) = self.states.borrow().clone() {
-            let maybe_next_val = {
-                let mut value = self.value.borrow_mut();
-                let prev_val = value.clone();
-                let next_val = self.select_value(&states);
+        if T::LAZY && !self.has_listeners() {
+            // The value is selected again when it is read next time.
+            self.value.borrow_mut().take();
+            self.state_listener_handles.borrow_mut().clear();
 
-                let should_notify = prev_val.as_ref() != Some(&next_val);
-                *value = Some(next_val.clone());
+            return;
+        }
 
-                should_notify.then_some(next_val)
-            };
+        // The selector is already scheduled.
+        if self.scheduled.replace(true) {
+            return;
+        }
 
-            if let Some(next_val) = maybe_next_val {
-                self.notify_listeners(next_val);
-            }
+        let self_ = self.clone();
+        schedule_select(Box::new(move || self_.select_scheduled()));
+    }
+
+    fn select_scheduled(&self) {
+        if !self.scheduled.replace(false) {
+            return;
+        }
+
+        if let Some(states) = self.states.borrow().clone() {
+            batch_notifications(|| {
+                let maybe_next_val = {
+                    let mut value = self.value.borrow_mut();
+                    let prev_val = value.clone();
+                    let next_val = self.select_value(&states);
+
+                    let should_notify = prev_val.as_ref() != Some(&next_val);
+                    *value = Some(next_val.clone());
+
+                    should_notify.then_some(next_val)
+                };
+
+                if let Some(next_val) = maybe_next_val {
+                    self.notify_listeners(next_val.into());
+                }
+            });
         }
     }
 
-    pub fn notify_listeners(&self, val: Rc<T>) {
+    fn has_listeners(&self) -> bool {
+        self.listeners.borrow().iter().any(|m| m.strong_count() > 0)
+    }
+
+    pub fn notify_listeners(&self, val: Rc<SelectedValue<T>>) {
+        #[cfg(feature = "devtools")]
+        let _change = crate::devtools::enter_change::<T>();
+
         notify_listeners(self.listeners.clone(), val);
     }
 
-    pub fn listen(&self, callback: Rc<Callback<Rc<T>>>) -> Listener {
+    pub fn listen(&self, callback: Rc<Callback<Rc<SelectedValue<T>>>>) -> Listener {
         let mut callbacks_ref = self.listeners.borrow_mut();
         callbacks_ref.push(Rc::downgrade(&callback));
 
@@ -196,6 +410,102 @@ where
     {
         Self::default()
     }
+
+    fn describe(&self) -> Option<StateDescriptor> {
+        let listener_count = self
+            .selectors
+            .borrow()
+            .values()
+            .map(|m| {
+                m.listeners
+                    .borrow()
+                    .iter()
+                    .filter(|m| m.strong_count() > 0)
+                    .count()
+            })
+            .sum();
+
+        Some(T::describe_state(listener_count))
+    }
+}
+
+/// The number of consecutive renders with an equal but newly allocated input before a warning
+/// is emitted.
+#[cfg(debug_assertions)]
+const INPUT_CHURN_THRESHOLD: u32 = 10;
+
+struct InputTracker<T> {
+    last: Rc<T>,
+    #[cfg(debug_assertions)]
+    churn: u32,
+    #[cfg(debug_assertions)]
+    warned: bool,
+}
+
+/// Interns the input of a hook.
+///
+/// If the input is equal to the input of the last render, the input of the last render is
+/// returned. As `Rc<T: Eq>` compares pointers before values, this keeps comparisons of inputs in
+/// dependencies cheap even if the caller allocates a new input each render.
+///
+/// In debug builds, a warning is emitted if the hook receives an equal but newly allocated input
+/// on many consecutive renders.
+#[hook]
+pub(crate) fn use_interned_input<T>(hook_name: &'static str, input: Rc<T>) -> Rc<T>
+where
+    T: Eq + 'static,
+{
+    let tracker = {
+        let input = input.clone();
+        use_mut_ref(move || InputTracker {
+            last: input,
+            #[cfg(debug_assertions)]
+            churn: 0,
+            #[cfg(debug_assertions)]
+            warned: false,
+        })
+    };
+    let mut tracker = tracker.borrow_mut();
+
+    if Rc::ptr_eq(&tracker.last, &input) {
+        #[cfg(debug_assertions)]
+        {
+            tracker.churn = 0;
+        }
+
+        return input;
+    }
+
+    if tracker.last != input {
+        tracker.last = input.clone();
+
+        #[cfg(debug_assertions)]
+        {
+            tracker.churn = 0;
+        }
+
+        return input;
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        tracker.churn += 1;
+
+        if tracker.churn >= INPUT_CHURN_THRESHOLD && !tracker.warned {
+            tracker.warned = true;
+
+            tracing::warn!(
+                "{hook_name} has received an equal but newly allocated input of type {} on \
+                 {INPUT_CHURN_THRESHOLD} consecutive renders, consider memoising the input \
+                 (e.g.: with use_memo).",
+                std::any::type_name::<T>()
+            );
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    let _ = hook_name;
+
+    tracker.last.clone()
 }
 
 /// A hook to connect to an [`InputSelector`].
@@ -259,36 +569,109 @@ pub fn use_input_selector_value<T>(input: Rc<T::Input>) -> Rc<T>
 where
     T: InputSelector + 'static,
 {
-    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
-
-    let val = {
-        let input = input.clone();
-        let root = root.clone();
-        use_state_eq(move || {
-            let states = root.states();
+    use_selected_value::<T>("use_input_selector_value", input).unwrap_or_else(|e| e.resume_unwind())
+}
 
-            root.get_state::<InputSelectorsState<T>>()
-                .get_state(input)
-                .get(states)
-        })
-    };
+/// A hook to connect to an [`InputSelector`] that returns an error if the selector has panicked.
+///
+/// Unlike [`use_input_selector_value`], which panics when it reads a poisoned selector, this
+/// hook returns [`SelectorPoisoned`] so the component can render a fallback. The component
+/// re-renders with the value once the selector is selected successfully again.
+///
+/// # Note
+///
+/// Panics are only caught on targets that unwind (e.g.: server-side rendering on native
+/// targets). On `wasm32-unknown-unknown`, panics abort by default, so this hook does not return
+/// an error in the browser.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Scores {
+///     inner: Vec<u64>,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct ScoreAt {
+///     inner: u64,
+/// }
+///
+/// impl InputSelector for ScoreAt {
+///     type Input = usize;
+///
+///     fn select(states: &BounceStates, input: Rc<usize>) -> Rc<Self> {
+///         // Panics if the index is out of bounds.
+///         let inner = states.get_atom_value::<Scores>().inner[*input];
+///
+///         Self { inner }.into()
+///     }
+/// }
+///
+/// #[derive(Properties, PartialEq)]
+/// struct ScoreProps {
+///     index: usize,
+/// }
+///
+/// #[function_component(Score)]
+/// fn score(props: &ScoreProps) -> Html {
+///     match use_input_selector_value_fallible::<ScoreAt>(props.index.into()) {
+///         Ok(m) => html! { <div>{m.inner}</div> },
+///         Err(_) => html! { <div>{"Score is not available."}</div> },
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_input_selector_value_fallible<T>(input: Rc<T::Input>) -> Result<Rc<T>, SelectorPoisoned>
+where
+    T: InputSelector + 'static,
+{
+    use_selected_value::<T>("use_input_selector_value_fallible", input)
+}
 
-    {
-        let val = val.clone();
-        let root = root;
-        use_memo((root, input), move |(root, input)| {
-            let state = root
-                .get_state::<InputSelectorsState<T>>()
-                .get_state(input.clone());
-
-            // we need to set the value here again in case the value has changed between the
-            // initial render and the listener is registered.
-            val.set(state.get(root.states()));
-
-            state.listen(Rc::new(Callback::from(move |m| {
-                val.set(m);
+#[hook]
+pub(crate) fn use_selected_value<T>(
+    hook_name: &'static str,
+    input: Rc<T::Input>,
+) -> SelectedValue<T>
+where
+    T: InputSelector + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let input = use_interned_input(hook_name, input);
+
+    let force_update = use_force_update();
+    #[cfg(feature = "devtools")]
+    let component = crate::devtools::use_component_key();
+
+    // The value is read when the selector is subscribed, so a value that is already selected
+    // (e.g.: a cached query) is returned in the same render, including the first render and the
+    // render after the input changes.
+    let subscription = use_memo((root, input), move |(root, input)| {
+        let state = root
+            .get_state::<InputSelectorsState<T>>()
+            .get_state(input.clone());
+
+        let value = Rc::new(RefCell::new(state.try_get(root.states())));
+
+        let listener = {
+            let value = value.clone();
+            state.listen(Rc::new(Callback::from(move |m: Rc<SelectedValue<T>>| {
+                #[cfg(feature = "devtools")]
+                crate::devtools::record_update(component);
+
+                *value.borrow_mut() = (*m).clone();
+                force_update.force_update();
             })))
-        });
-    }
-    (*val).clone()
+        };
+
+        (value, listener)
+    });
+
+    let value = subscription.0.borrow().clone();
+    value
 }
@@ -0,0 +1,69 @@
+use yew::prelude::*;
+
+/// Provided by [`LazySubscriptions`] to the read-only hooks of its children.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LazySubscriptionsContext;
+
+/// Returns `true` if the component is rendered under a [`LazySubscriptions`] component.
+#[hook]
+pub(crate) fn use_lazy_subscriptions() -> bool {
+    use_context::<LazySubscriptionsContext>().is_some()
+}
+
+/// Properties of the [`LazySubscriptions`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct LazySubscriptionsProps {
+    /// The children of the component.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A component that defers the subscriptions of read-only hooks until the browser is idle.
+///
+/// Components under it that use [`use_slice_value`](crate::use_slice_value) or
+/// [`use_atom_value`](crate::use_atom_value) read the value of the state once when they are
+/// rendered and start listening to changes with the [`IdleScheduler`](crate::IdleScheduler) after
+/// the first paint. Values that change in between
+/// are picked up when the listener is attached.
+///
+/// This reduces the hydration cost of large, mostly static pages (e.g.: islands-style
+/// applications) where most states never change after the page is loaded. Hooks that return a
+/// handle that can update the state (e.g.: [`use_atom`](crate::use_atom)) always subscribe
+/// immediately.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::{BounceRoot, LazySubscriptions};
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct SiteName(String);
+///
+/// #[function_component(Footer)]
+/// fn footer() -> Html {
+///     let name = use_atom_value::<SiteName>();
+///
+///     html! { <footer>{&name.0}</footer> }
+/// }
+///
+/// #[function_component(App)]
+/// fn app() -> Html {
+///     html! {
+///         <BounceRoot>
+///             <LazySubscriptions>
+///                 <Footer />
+///             </LazySubscriptions>
+///         </BounceRoot>
+///     }
+/// }
+/// ```
+#[function_component(LazySubscriptions)]
+pub fn lazy_subscriptions(props: &LazySubscriptionsProps) -> Html {
+    html! {
+        <ContextProvider<LazySubscriptionsContext> context={LazySubscriptionsContext}>
+            {props.children.clone()}
+        </ContextProvider<LazySubscriptionsContext>>
+    }
+}
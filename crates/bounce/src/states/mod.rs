@@ -2,9 +2,22 @@
 
 pub(crate) mod artifact;
 pub(crate) mod atom;
+pub(crate) mod atom_expiry;
+pub(crate) mod atom_family;
+pub(crate) mod atom_list;
+pub(crate) mod cached_value;
+pub(crate) mod child_atom;
+pub(crate) mod derived;
+pub(crate) mod external_store;
+pub(crate) mod from_states;
 pub(crate) mod future_notion;
 pub(crate) mod input_selector;
+pub(crate) mod lazy_subscriptions;
 pub(crate) mod notion;
+pub(crate) mod notion_scope;
 pub(crate) mod observer;
+pub(crate) mod registry;
 pub(crate) mod selector;
 pub(crate) mod slice;
+pub(crate) mod stream_atom;
+pub(crate) mod transient;
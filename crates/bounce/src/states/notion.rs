@@ -1,19 +1,61 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
 use std::rc::Rc;
+use std::time::Duration;
 
 use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 
-use crate::root_state::BounceRootState;
+use crate::clock::Clock;
+use crate::root_state::{AnyNotion, BounceRootState};
 
-/// A trait to apply a notion on a state.
+pub use bounce_core::WithNotion;
+
+type BatchNotionApplier = Rc<dyn Fn(NotionBatch)>;
+
+/// A batch of notions that is applied atomically.
 ///
-/// See: [`use_notion_applier`](crate::use_notion_applier)
-pub trait WithNotion<T: 'static> {
-    /// Applies a notion on current state.
-    ///
-    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
-    /// state using [`PartialEq`].
-    fn apply(self: Rc<Self>, notion: Rc<T>) -> Rc<Self>;
+/// See: [`use_batch_notion_applier`]
+#[derive(Default, Clone)]
+pub struct NotionBatch {
+    notions: Vec<AnyNotion>,
+}
+
+impl NotionBatch {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a notion to the batch.
+    pub fn with<T>(self, notion: T) -> Self
+    where
+        T: 'static,
+    {
+        self.with_rc(Rc::new(notion))
+    }
+
+    /// Appends a notion that is already wrapped in an `Rc` to the batch.
+    pub fn with_rc<T>(mut self, notion: Rc<T>) -> Self
+    where
+        T: 'static,
+    {
+        self.notions.push(AnyNotion::new(notion));
+        self
+    }
+
+    pub(crate) fn into_inner(self) -> Vec<AnyNotion> {
+        self.notions
+    }
+}
+
+impl fmt::Debug for NotionBatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotionBatch")
+            .field("len", &self.notions.len())
+            .finish()
+    }
 }
 
 /// A hook to create a function that applies a `Notion`.
@@ -22,6 +64,10 @@ pub trait WithNotion<T: 'static> {
 ///
 /// Any type that is `'static` can be dispatched as a notion.
 ///
+/// States receive a notion in the order they are registered. If a notion is applied while
+/// another notion is being applied (e.g.: from a listener or an observer), it is queued and
+/// applied after the current notion has been delivered to all states.
+///
 /// Returns `Rc<dyn Fn(T)>`.
 ///
 /// # Note
@@ -91,3 +137,248 @@ where
         root.apply_notion(Rc::new(notion));
     })
 }
+
+/// A hook to create a function that applies a batch of notions atomically.
+///
+/// The notions are applied in order to all states that accept them. Listeners are notified once
+/// after the whole batch has been applied, so components and selectors never observe a state
+/// where only some of the notions have been applied.
+///
+/// Returns `Rc<dyn Fn(NotionBatch)>`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// pub struct LoggedOut;
+/// pub struct CacheCleared;
+///
+/// #[derive(PartialEq, Atom, Default)]
+/// #[bounce(with_notion(LoggedOut))]
+/// struct Session {
+///     token: Option<String>,
+/// }
+///
+/// impl WithNotion<LoggedOut> for Session {
+///     fn apply(self: Rc<Self>, _notion: Rc<LoggedOut>) -> Rc<Self> {
+///         Self::default().into()
+///     }
+/// }
+///
+/// #[derive(PartialEq, Atom, Default)]
+/// #[bounce(with_notion(CacheCleared))]
+/// struct Cache {
+///     entries: Vec<String>,
+/// }
+///
+/// impl WithNotion<CacheCleared> for Cache {
+///     fn apply(self: Rc<Self>, _notion: Rc<CacheCleared>) -> Rc<Self> {
+///         Self::default().into()
+///     }
+/// }
+///
+/// # #[function_component(Logout)]
+/// # fn logout() -> Html {
+/// let apply_notions = use_batch_notion_applier();
+/// // No component observes a logged out session with a filled cache.
+/// apply_notions(NotionBatch::new().with(LoggedOut).with(CacheCleared));
+/// # Html::default()
+/// # }
+/// ```
+#[hook]
+pub fn use_batch_notion_applier() -> BatchNotionApplier {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move |notions: NotionBatch| {
+        root.apply_notions(notions);
+    })
+}
+
+/// How a rate limited notion applier handles notions that exceed the rate.
+///
+/// See: [`use_notion_applier_rate_limited_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RateLimitStrategy {
+    /// Only the latest excess notion is kept and applied when the rate allows it, so the last
+    /// notion is never lost.
+    #[default]
+    Coalesce,
+    /// Excess notions are dropped.
+    Drop,
+}
+
+/// Applies notions of a component with a limited rate.
+struct RateLimiter<T> {
+    root: BounceRootState,
+    clock: Rc<dyn Clock>,
+    interval: Duration,
+    strategy: RateLimitStrategy,
+    last_applied: Cell<Option<Duration>>,
+    pending: RefCell<Option<T>>,
+}
+
+impl<T> RateLimiter<T>
+where
+    T: 'static,
+{
+    fn apply_now(&self, notion: T) {
+        self.last_applied.set(Some(self.clock.now()));
+        self.root.apply_notion(Rc::new(notion));
+    }
+
+    fn apply(self: &Rc<Self>, notion: T) {
+        let now = self.clock.now();
+        let wait = self
+            .last_applied
+            .get()
+            .and_then(|m| (m + self.interval).checked_sub(now))
+            .filter(|m| !m.is_zero());
+
+        let wait = match wait {
+            Some(m) => m,
+            None => {
+                self.apply_now(notion);
+                return;
+            }
+        };
+
+        if self.strategy == RateLimitStrategy::Drop {
+            return;
+        }
+
+        // A notion is already waiting to be applied, it is replaced by the latest notion.
+        if self.pending.replace(Some(notion)).is_some() {
+            return;
+        }
+
+        let this = Rc::downgrade(self);
+        let sleep = self.clock.sleep(wait);
+
+        spawn_local(async move {
+            sleep.await;
+
+            // The pending notion is discarded if the component has been unmounted.
+            if let Some(this) = this.upgrade() {
+                if let Some(m) = this.pending.take() {
+                    this.apply_now(m);
+                }
+            }
+        });
+    }
+}
+
+/// A hook to create a function that applies a `Notion` at most `max_per_second` times per
+/// second.
+///
+/// Notions that exceed the rate are coalesced: only the latest one is kept and it is applied
+/// when the rate allows it. This is useful when notions are applied from high-frequency sources
+/// like scroll or mousemove handlers.
+///
+/// See [`use_notion_applier_rate_limited_with`] to drop excess notions instead.
+///
+/// Returns `Rc<dyn Fn(T)>`.
+///
+/// # Panics
+///
+/// Panics if `max_per_second` is `0`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// pub struct Scrolled {
+///     offset: f64,
+/// }
+///
+/// #[derive(PartialEq, Atom, Default)]
+/// #[bounce(with_notion(Scrolled))]
+/// struct ScrollOffset(f64);
+///
+/// impl WithNotion<Scrolled> for ScrollOffset {
+///     fn apply(self: Rc<Self>, notion: Rc<Scrolled>) -> Rc<Self> {
+///         Self(notion.offset).into()
+///     }
+/// }
+///
+/// #[function_component(Page)]
+/// fn page() -> Html {
+///     // The offset is updated at most 10 times per second.
+///     let scrolled = use_notion_applier_rate_limited::<Scrolled>(10);
+///     let onscroll = Callback::from(move |_: Event| scrolled(Scrolled { offset: 0.0 }));
+///
+///     html! { <div {onscroll}>{"content..."}</div> }
+/// }
+/// ```
+#[hook]
+pub fn use_notion_applier_rate_limited<T>(max_per_second: u32) -> Rc<dyn Fn(T)>
+where
+    T: 'static,
+{
+    use_notion_applier_rate_limited_with::<T>(max_per_second, RateLimitStrategy::Coalesce)
+}
+
+/// A hook to create a function that applies a `Notion` at most `max_per_second` times per
+/// second, with a strategy for the notions that exceed the rate.
+///
+/// The rate is reset when `max_per_second` or `strategy` changes.
+///
+/// Returns `Rc<dyn Fn(T)>`.
+///
+/// # Panics
+///
+/// Panics if `max_per_second` is `0`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::RateLimitStrategy;
+/// pub struct PointerMoved {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// # #[function_component(Canvas)]
+/// # fn canvas() -> Html {
+/// // Positions that exceed 30 updates per second are dropped.
+/// let pointer_moved =
+///     use_notion_applier_rate_limited_with::<PointerMoved>(30, RateLimitStrategy::Drop);
+/// pointer_moved(PointerMoved { x: 0, y: 0 });
+/// # Html::default()
+/// # }
+/// ```
+#[hook]
+pub fn use_notion_applier_rate_limited_with<T>(
+    max_per_second: u32,
+    strategy: RateLimitStrategy,
+) -> Rc<dyn Fn(T)>
+where
+    T: 'static,
+{
+    assert!(max_per_second > 0, "the rate must be at least 1 per second");
+
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    let limiter = use_memo(
+        (root, max_per_second, strategy),
+        |(root, max_per_second, strategy)| {
+            Rc::new(RateLimiter {
+                root: root.clone(),
+                clock: root.clock(),
+                interval: Duration::from_secs(1) / *max_per_second,
+                strategy: *strategy,
+                last_applied: Cell::default(),
+                pending: RefCell::default(),
+            })
+        },
+    );
+
+    let limiter = (*limiter).clone();
+    Rc::new(move |notion: T| limiter.apply(notion))
+}
@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+
+use futures::future::{abortable, AbortHandle};
+use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use super::future_notion::{run_future_notion_abortable, FutureNotion};
+use crate::root_state::BounceRootState;
+use crate::utils::Id;
+
+/// A scope that aborts the tasks launched through it when the component unmounts.
+///
+/// See: [`use_notion_scope`]
+#[derive(Clone)]
+pub struct NotionScope {
+    root: BounceRootState,
+    tasks: Rc<RefCell<HashMap<Id, AbortHandle>>>,
+}
+
+impl NotionScope {
+    /// Runs a [`FutureNotion`].
+    ///
+    /// If the component unmounts before the notion completes, the run is aborted and
+    /// [`Deferred::Outdated`](crate::Deferred::Outdated) is applied instead of
+    /// [`Deferred::Completed`](crate::Deferred::Completed).
+    pub fn run_future_notion<T>(&self, input: T::Input)
+    where
+        T: FutureNotion + 'static,
+    {
+        let (handle, run) = run_future_notion_abortable::<T>(self.root.clone(), input.into());
+        self.spawn_task(handle, run);
+    }
+
+    /// Spawns a future.
+    ///
+    /// The future is dropped at its next await point if the component unmounts before it
+    /// completes, so it never writes to states after the component is gone.
+    ///
+    /// Queries fetched by the future are shared with other components and continue to
+    /// completion, only the future itself is aborted.
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        let (run, handle) = abortable(f);
+        self.spawn_task(handle, async move {
+            let _ = run.await;
+        });
+    }
+
+    fn spawn_task<F>(&self, handle: AbortHandle, run: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        let id = Id::new();
+        self.tasks.borrow_mut().insert(id, handle);
+
+        let tasks = self.tasks.clone();
+        spawn_local(async move {
+            run.await;
+            tasks.borrow_mut().remove(&id);
+        });
+    }
+
+    fn abort_all(&self) {
+        let tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+
+        for handle in tasks.into_values() {
+            handle.abort();
+        }
+    }
+}
+
+impl PartialEq for NotionScope {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && Rc::ptr_eq(&self.tasks, &other.tasks)
+    }
+}
+
+impl fmt::Debug for NotionScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotionScope")
+            .field("tasks", &self.tasks.borrow().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A hook to obtain a [`NotionScope`] that is tied to the lifecycle of the current component.
+///
+/// Future notions and futures launched through the scope are aborted when the component
+/// unmounts, preventing orphaned runs and late state writes from components that no longer
+/// exist.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// # #[derive(PartialEq)]
+/// # struct User {
+/// #     id: u64,
+/// # }
+/// #
+/// #[future_notion(FetchUser)]
+/// async fn fetch_user(id: &u64) -> User {
+///     // fetch user
+///
+///     User { id: *id }
+/// }
+///
+/// #[function_component(Profile)]
+/// fn profile() -> Html {
+///     let scope = use_notion_scope();
+///
+///     let onclick = Callback::from(move |_| {
+///         // The run is aborted if the profile is closed before the user is fetched.
+///         scope.run_future_notion::<FetchUser>(1);
+///     });
+///
+///     html! { <button {onclick}>{"Load"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_notion_scope() -> NotionScope {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let tasks = use_mut_ref(HashMap::new);
+    let scope = NotionScope { root, tasks };
+
+    {
+        let scope = scope.clone();
+        use_effect_with((), move |_| move || scope.abort_all());
+    }
+
+    scope
+}
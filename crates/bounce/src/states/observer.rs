@@ -23,7 +23,83 @@ use std::rc::Rc;
 ///     }
 /// }
 /// ```
+///
+/// # Filtering Changes
+///
+/// An observed state can provide a filter with `#[bounce(observed(if = "path"))]`. The filter is
+/// a function with the signature `fn(prev: &Self, next: &Self) -> bool` and the observer is only
+/// notified when it returns `true`. Listeners of the state are notified regardless of the filter.
+///
+/// This can be used to avoid writing to storage on every keystroke of a text-bound state.
+///
+/// ```
+/// use bounce::prelude::*;
+/// use std::rc::Rc;
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// #[bounce(observed(if = "Self::should_persist"))]
+/// struct Draft {
+///     text: String,
+///     saved: bool,
+/// }
+///
+/// impl Draft {
+///     fn should_persist(prev: &Self, next: &Self) -> bool {
+///         // only persist when the draft is saved.
+///         !prev.saved && next.saved
+///     }
+/// }
+///
+/// impl Observed for Draft {
+///     fn changed(self: Rc<Self>) {
+///         // write the draft to the storage.
+///     }
+/// }
+/// ```
+///
+/// # Lifecycle
+///
+/// Observers that hold external resources (e.g.: storage handles or event listeners) can set
+/// them up in [`init`](Self::init), which is called when the state is created in a root, and
+/// clean them up in [`teardown`](Self::teardown), which is called when the root is destroyed.
+///
+/// ```
+/// use bounce::prelude::*;
+/// use std::rc::Rc;
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// #[bounce(observed)]
+/// struct Connection {
+///     url: String,
+/// }
+///
+/// impl Observed for Connection {
+///     fn init(self: Rc<Self>) {
+///         // open the connection.
+///     }
+///
+///     fn changed(self: Rc<Self>) {
+///         // reconnect to the new url.
+///     }
+///
+///     fn teardown(self: Rc<Self>) {
+///         // close the connection.
+///     }
+/// }
+/// ```
 pub trait Observed {
     /// Notified when the state value has changed.
     fn changed(self: Rc<Self>);
+
+    /// Notified with the initial value when the state is created in a root.
+    ///
+    /// This is called once per root, after the state is registered, so the observer can set up
+    /// external resources (e.g.: storage handles or event listeners).
+    fn init(self: Rc<Self>) {}
+
+    /// Notified with the last value when the root of the state is destroyed.
+    ///
+    /// This is called once for each state that has been created, so the observer can clean up
+    /// the resources set up in [`init`](Self::init).
+    fn teardown(self: Rc<Self>) {}
 }
@@ -0,0 +1,41 @@
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use crate::root_state::BounceRootState;
+
+pub use bounce_core::{StateDescriptor, StateKind};
+
+/// A hook to list the states that are registered under the current bounce root.
+///
+/// States are listed in the order they are registered. A state is registered when it is first
+/// used by a component, a selector or a query.
+///
+/// The list is a snapshot taken when the component renders, the component is not re-rendered
+/// when states are registered or listeners change.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::use_registered_states;
+///
+/// #[function_component(StateOverlay)]
+/// fn state_overlay() -> Html {
+///     let states = use_registered_states();
+///
+///     html! {
+///         <ul>
+///             {for states.iter().map(|m| html! {
+///                 <li>{format!("{:?} {} ({} listeners)", m.kind, m.type_name, m.listener_count)}</li>
+///             })}
+///         </ul>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_registered_states() -> Vec<StateDescriptor> {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    root.registered_states()
+}
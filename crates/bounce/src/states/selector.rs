@@ -1,14 +1,50 @@
 use std::rc::Rc;
 
 use yew::prelude::*;
+use yew::suspense::{Suspension, SuspensionResult};
 
-use super::input_selector::{use_input_selector_value, InputSelector};
+use super::input_selector::{
+    use_input_selector_value, use_selected_value, InputSelector, SelectorPoisoned,
+};
 use crate::root_state::BounceStates;
+use crate::states::registry::{StateDescriptor, StateKind};
+
+pub use bounce_core::SelectorResult;
 
 /// An auto-updating derived state.
 ///
 /// It will automatically update when any selected state changes and only notifies registered
 /// hooks when `prev_value != next_value`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Username {
+///     inner: String,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct UsernameLength {
+///     inner: usize,
+/// }
+///
+/// impl Selector for UsernameLength {
+///     fn select(states: &BounceStates) -> Rc<Self> {
+///         let username = states.get_atom_value::<Username>();
+///
+///         Self {
+///             inner: username.inner.len(),
+///         }
+///         .into()
+///     }
+/// }
+/// ```
+///
+/// See: [`use_selector_value`]
 pub trait Selector: PartialEq {
     /// Selects `self` from existing bounce states.
     ///
@@ -39,6 +75,10 @@ where
         }
         .into()
     }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Selector, listener_count)
+    }
 }
 
 /// A hook to connect to a [`Selector`].
@@ -104,3 +144,201 @@ where
         .inner
         .clone()
 }
+
+/// A hook to connect to a [`Selector`] that returns an error if the selector has panicked.
+///
+/// Unlike [`use_selector_value`], which panics when it reads a poisoned selector, this hook
+/// returns [`SelectorPoisoned`] so the component can render a fallback. The selector is selected
+/// again when any state that it has read before it panicked changes.
+///
+/// # Note
+///
+/// Panics are only caught on targets that unwind (e.g.: server-side rendering on native
+/// targets). On `wasm32-unknown-unknown`, panics abort by default, so this hook does not return
+/// an error in the browser.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Config {
+///     raw: String,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct PageSize {
+///     inner: usize,
+/// }
+///
+/// impl Selector for PageSize {
+///     fn select(states: &BounceStates) -> Rc<Self> {
+///         let config = states.get_atom_value::<Config>();
+///
+///         Self {
+///             inner: config.raw.parse().expect("page size is not a number"),
+///         }
+///         .into()
+///     }
+/// }
+///
+/// #[function_component(Pagination)]
+/// fn pagination() -> Html {
+///     match use_selector_value_fallible::<PageSize>() {
+///         Ok(m) => html! { <div>{m.inner}{" per page"}</div> },
+///         Err(e) => html! { <div>{e.to_string()}</div> },
+///     }
+/// }
+/// #
+/// # #[function_component(App)]
+/// # fn app() -> Html {
+/// #     html! { <bounce::BounceRoot><Pagination /></bounce::BounceRoot> }
+/// # }
+/// #
+/// # let rendered = futures::executor::block_on(yew::LocalServerRenderer::<App>::new().render());
+/// # assert!(rendered.contains("has panicked: page size is not a number"));
+/// ```
+#[hook]
+pub fn use_selector_value_fallible<T>() -> Result<Rc<T>, SelectorPoisoned>
+where
+    T: Selector + 'static,
+{
+    use_selected_value::<UnitSelector<T>>("use_selector_value_fallible", ().into())
+        .map(|m| m.inner.clone())
+}
+
+/// A derived state that may not be ready to be selected.
+///
+/// # Example
+///
+/// ```
+/// # use bounce::prelude::*;
+/// # use bounce::{SelectorResult, TrySelector};
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Profile {
+///     // `None` until the profile is loaded.
+///     name: Option<String>,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct Greeting {
+///     inner: String,
+/// }
+///
+/// impl TrySelector for Greeting {
+///     fn try_select(states: &BounceStates) -> SelectorResult<Self> {
+///         let profile = states.get_atom_value::<Profile>();
+///
+///         match profile.name {
+///             Some(ref m) => SelectorResult::Ready(
+///                 Self {
+///                     inner: format!("Hello, {m}!"),
+///                 }
+///                 .into(),
+///             ),
+///             None => SelectorResult::NotReady,
+///         }
+///     }
+/// }
+/// ```
+///
+/// See: [`use_selector_value_or_suspend`]
+pub trait TrySelector: PartialEq {
+    /// Selects `self` from existing bounce states.
+    ///
+    /// Returns [`SelectorResult::NotReady`] if the states it depends on are not ready.
+    fn try_select(states: &BounceStates) -> SelectorResult<Self>
+    where
+        Self: Sized;
+}
+
+#[derive(PartialEq)]
+pub(crate) struct UnitTrySelector<T>
+where
+    T: TrySelector + 'static,
+{
+    pub inner: SelectorResult<T>,
+}
+
+impl<T> InputSelector for UnitTrySelector<T>
+where
+    T: TrySelector + 'static,
+{
+    type Input = ();
+
+    fn select(states: &BounceStates, _input: Rc<()>) -> Rc<Self> {
+        Self {
+            inner: T::try_select(states),
+        }
+        .into()
+    }
+
+    fn describe_state(listener_count: usize) -> StateDescriptor {
+        StateDescriptor::new::<T>(StateKind::Selector, listener_count)
+    }
+}
+
+/// A hook to connect to a [`TrySelector`], suspending while the selector is not ready.
+///
+/// This is useful when a selector depends on states that are initialised asynchronously and
+/// rendering a placeholder value would be incorrect.
+///
+/// Its value will be automatically re-calculated when any state used in the selector has
+/// changed. The component is resumed once the selector returns [`SelectorResult::Ready`].
+///
+/// Returns a [`SuspensionResult<Rc<T>>`].
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Config {
+///     // Loaded asynchronously after the application has started.
+///     api_endpoint: Option<String>,
+/// }
+///
+/// #[derive(PartialEq)]
+/// struct ApiEndpoint(String);
+///
+/// impl TrySelector for ApiEndpoint {
+///     fn try_select(states: &BounceStates) -> SelectorResult<Self> {
+///         let config = states.get_atom_value::<Config>();
+///
+///         match config.api_endpoint {
+///             Some(ref m) => SelectorResult::Ready(Self(m.clone()).into()),
+///             None => SelectorResult::NotReady,
+///         }
+///     }
+/// }
+///
+/// #[function_component(Endpoint)]
+/// fn endpoint() -> HtmlResult {
+///     // Suspends until the config is loaded.
+///     let endpoint = use_selector_value_or_suspend::<ApiEndpoint>()?;
+///
+///     Ok(html! { <div>{&endpoint.0}</div> })
+/// }
+/// ```
+#[hook]
+pub fn use_selector_value_or_suspend<T>() -> SuspensionResult<Rc<T>>
+where
+    T: TrySelector + 'static,
+{
+    let value = use_input_selector_value::<UnitTrySelector<T>>(().into());
+
+    // The suspension is resumed when the handle is dropped, which happens when the value changes.
+    let value = use_memo(value, |m| match m.inner {
+        SelectorResult::Ready(ref m) => Ok(m.clone()),
+        SelectorResult::NotReady => Err(Suspension::new()),
+    });
+
+    value.as_ref().as_ref().cloned().map_err(|(s, _)| s.clone())
+}
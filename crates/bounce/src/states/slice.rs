@@ -1,57 +1,126 @@
 use std::any::{Any, TypeId};
 use std::cell::RefCell;
+use std::convert::Infallible;
 use std::fmt;
 use std::ops::Deref;
+use std::pin::Pin;
 use std::rc::Rc;
+use std::task::{Context, Poll};
 
 use anymap2::AnyMap;
+use futures::channel::mpsc;
+use futures::{Sink, Stream};
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
 use crate::any_state::AnyState;
+use crate::idle::IdleScheduler;
 use crate::root_state::BounceRootState;
-use crate::utils::{notify_listeners, Listener, ListenerVec};
+use crate::states::external_store::SliceStore;
+use crate::states::lazy_subscriptions::use_lazy_subscriptions;
+use crate::states::registry::StateDescriptor;
+use crate::utils::{batch_notifications, notify_listeners, Listener, ListenerVec};
 
 pub use bounce_macros::Slice;
 
-#[doc(hidden)]
-pub trait Slice: PartialEq + Default {
-    type Action;
+pub use bounce_core::Slice;
 
-    /// Performs a reduce action.
-    ///
-    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
-    /// slice using [`PartialEq`].
-    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self>;
-
-    /// Applies a notion.
-    ///
-    /// This always yields a new instance of [`Rc<Self>`] so it can be compared with the previous
-    /// slice using [`PartialEq`].
-    #[allow(unused_variables)]
-    fn apply(self: Rc<Self>, notion: Rc<dyn Any>) -> Rc<Self> {
-        self
-    }
-
-    /// Returns a list of notion ids that this Slice accepts.
-    fn notion_ids(&self) -> Vec<TypeId>;
-
-    /// Notifies a slice that it has changed.
-    fn changed(self: Rc<Self>) {}
+/// A trait to be implemented on slices that produce an output when an action is dispatched.
+///
+/// The output is returned to the dispatcher by
+/// [`UseSliceHandle::dispatch_with_output`] and [`use_slice_dispatch_with_output`], e.g.: the
+/// id generated when an item is added.
+///
+/// The [`Reducible`](yew::functional::Reducible) implementation of the slice can be written in
+/// terms of this trait by discarding the output.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::ReducibleWithOutput;
+/// #
+/// enum TodoAction {
+///     Add(String),
+/// }
+///
+/// #[derive(PartialEq, Default, Clone, Slice)]
+/// struct Todos {
+///     next_id: u64,
+///     items: Vec<(u64, String)>,
+/// }
+///
+/// impl ReducibleWithOutput for Todos {
+///     type Output = u64;
+///
+///     fn reduce_with_output(self: Rc<Self>, action: TodoAction) -> (Rc<Self>, u64) {
+///         match action {
+///             TodoAction::Add(title) => {
+///                 let mut next = self.clone_slice();
+///                 let id = next.next_id;
+///                 next.next_id += 1;
+///                 next.items.push((id, title));
+///
+///                 (next.into(), id)
+///             }
+///         }
+///     }
+/// }
+///
+/// impl Reducible for Todos {
+///     type Action = TodoAction;
+///
+///     fn reduce(self: Rc<Self>, action: TodoAction) -> Rc<Self> {
+///         self.reduce_with_output(action).0
+///     }
+/// }
+/// ```
+pub trait ReducibleWithOutput: Slice {
+    /// The output produced when an action is dispatched.
+    type Output;
 
-    /// Creates a new slice with its initial value.
-    fn create(init_states: &mut AnyMap) -> Self
-    where
-        Self: 'static + Sized,
-    {
-        init_states.remove().unwrap_or_default()
-    }
+    /// Performs a reduce action and returns the next slice with the output of the action.
+    fn reduce_with_output(self: Rc<Self>, action: Self::Action) -> (Rc<Self>, Self::Output);
 }
 
 /// A trait to provide cloning on slices.
 ///
 /// This trait provides a `self.clone_slice()` method that can be used as an alias of `(*self).clone()`
 /// in reduce and apply functions to produce a owned clone of the slice.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// #
+/// enum CounterAction {
+///     Increment,
+/// }
+///
+/// #[derive(Slice, PartialEq, Default, Clone)]
+/// struct Counter {
+///     label: String,
+///     count: u64,
+/// }
+///
+/// impl Reducible for Counter {
+///     type Action = CounterAction;
+///
+///     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+///         let mut next = self.clone_slice();
+///
+///         match action {
+///             CounterAction::Increment => next.count += 1,
+///         }
+///
+///         next.into()
+///     }
+/// }
+/// ```
 pub trait CloneSlice: Slice + Clone {
     /// Clones current slice.
     #[inline]
@@ -88,24 +157,77 @@ where
     T: Slice + 'static,
 {
     pub fn dispatch(&self, action: T::Action) {
-        let maybe_next_val = {
+        self.reduce_by(|m| (m.reduce(action), ()))
+    }
+
+    pub fn dispatch_with_output(&self, action: T::Action) -> T::Output
+    where
+        T: ReducibleWithOutput,
+    {
+        self.reduce_by(|m| m.reduce_with_output(action))
+    }
+
+    /// Dispatches `Action` and keeps the current value if the reduced value is equal to it.
+    ///
+    /// Returns `true` if the value of the slice has changed.
+    pub fn dispatch_if_changed(&self, action: T::Action) -> bool {
+        let (prev_val, next_val) = {
             let mut value = self.value.borrow_mut();
             let prev_val: Rc<T> = value.clone();
             let next_val = prev_val.clone().reduce(action);
 
+            if prev_val == next_val {
+                return false;
+            }
+            *value = next_val.clone();
+
+            (prev_val, next_val)
+        };
+
+        batch_notifications(|| self.notify_listeners(&prev_val, next_val));
+
+        true
+    }
+
+    fn reduce_by<F, O>(&self, f: F) -> O
+    where
+        F: FnOnce(Rc<T>) -> (Rc<T>, O),
+    {
+        let (maybe_next_val, output) = {
+            let mut value = self.value.borrow_mut();
+            let prev_val: Rc<T> = value.clone();
+            let (next_val, output) = f(prev_val.clone());
+
             let should_notify = prev_val != next_val;
             *value = next_val.clone();
 
-            should_notify.then_some(next_val)
+            (should_notify.then_some((prev_val, next_val)), output)
         };
 
-        if let Some(next_val) = maybe_next_val {
-            self.notify_listeners(next_val);
+        if let Some((prev_val, next_val)) = maybe_next_val {
+            // Selectors derived from this slice are notified in the same batch.
+            batch_notifications(|| self.notify_listeners(&prev_val, next_val));
+        }
+
+        output
+    }
+
+    /// Replaces the value of the slice.
+    pub fn replace(&self, next_val: Rc<T>) {
+        let prev_val = std::mem::replace(&mut *self.value.borrow_mut(), next_val.clone());
+
+        if prev_val != next_val {
+            batch_notifications(|| self.notify_listeners(&prev_val, next_val));
         }
     }
 
-    pub fn notify_listeners(&self, val: Rc<T>) {
-        val.clone().changed();
+    pub fn notify_listeners(&self, prev_val: &T, val: Rc<T>) {
+        #[cfg(feature = "devtools")]
+        let _change = crate::devtools::enter_change::<T>();
+
+        if val.should_observe(prev_val) {
+            val.clone().changed();
+        }
         notify_listeners(self.listeners.clone(), val);
     }
 
@@ -135,18 +257,30 @@ where
             let should_notify = prev_val != next_val;
             *value = next_val.clone();
 
-            should_notify.then_some(next_val)
+            should_notify.then_some((prev_val, next_val))
         };
 
-        if let Some(next_val) = maybe_next_val {
-            self.notify_listeners(next_val);
+        if let Some((prev_val, next_val)) = maybe_next_val {
+            self.notify_listeners(&prev_val, next_val);
         }
     }
 
+    fn accepts(&self, notion: &dyn Any) -> bool {
+        self.value.borrow().accepts(notion)
+    }
+
     fn notion_ids(&self) -> Vec<TypeId> {
         self.value.borrow().notion_ids()
     }
 
+    fn init(&self) {
+        self.get().init();
+    }
+
+    fn teardown(&self) {
+        self.get().teardown();
+    }
+
     fn create(init_states: &mut AnyMap) -> Self
     where
         Self: Sized,
@@ -156,6 +290,36 @@ where
             listeners: Rc::default(),
         }
     }
+
+    fn create_from_root(root: &BounceRootState) -> Self
+    where
+        Self: Sized,
+    {
+        // Overridden values take priority over values provided to the root, which take priority
+        // over values created from states.
+        let value = root.get_override::<T>().unwrap_or_else(|| {
+            root.with_init_states(T::create_provided)
+                .or_else(|| T::create_from_states(&root.states()))
+                .unwrap_or_default()
+                .into()
+        });
+
+        Self {
+            value: Rc::new(RefCell::new(value)),
+            listeners: Rc::default(),
+        }
+    }
+
+    fn describe(&self) -> Option<StateDescriptor> {
+        let listener_count = self
+            .listeners
+            .borrow()
+            .iter()
+            .filter(|m| m.strong_count() > 0)
+            .count();
+
+        Some(T::describe_state(listener_count))
+    }
 }
 
 /// A handle returned by [`use_slice`].
@@ -177,6 +341,98 @@ where
     pub fn dispatch(&self, action: T::Action) {
         self.root.get_state::<SliceState<T>>().dispatch(action);
     }
+
+    /// Dispatches `Action` and returns the output of the action.
+    ///
+    /// See: [`ReducibleWithOutput`]
+    pub fn dispatch_with_output(&self, action: T::Action) -> T::Output
+    where
+        T: ReducibleWithOutput,
+    {
+        self.root
+            .get_state::<SliceState<T>>()
+            .dispatch_with_output(action)
+    }
+
+    /// Dispatches `Action` and only notifies subscribers if the value has changed.
+    ///
+    /// Returns `true` if the value of the slice has changed.
+    pub fn dispatch_if_changed(&self, action: T::Action) -> bool {
+        self.root
+            .get_state::<SliceState<T>>()
+            .dispatch_if_changed(action)
+    }
+
+    /// Returns a [`Stream`] that yields the value of the slice each time it changes.
+    ///
+    /// The stream does not yield the current value and it keeps yielding values after the
+    /// component is unmounted until it is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use bounce::prelude::*;
+    /// # use futures::StreamExt;
+    /// # use yew::platform::spawn_local;
+    /// # use yew::prelude::*;
+    /// #
+    /// # enum CounterAction {
+    /// #     Increment,
+    /// # }
+    /// #
+    /// #[derive(PartialEq, Default, Slice)]
+    /// struct Counter(u64);
+    /// #
+    /// # impl Reducible for Counter {
+    /// #     type Action = CounterAction;
+    /// #
+    /// #     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+    /// #         match action {
+    /// #             CounterAction::Increment => Self(self.0 + 1).into(),
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// #[function_component(CounterLogger)]
+    /// fn counter_logger() -> Html {
+    ///     let counter = use_slice::<Counter>();
+    ///
+    ///     use_effect_with((), move |_| {
+    ///         let mut changes = counter.to_stream();
+    ///
+    ///         spawn_local(async move {
+    ///             while let Some(m) = changes.next().await {
+    ///                 // sends the new value to a worker.
+    ///                 let _value = m.0;
+    ///             }
+    ///         });
+    ///
+    ///         || {}
+    ///     });
+    ///
+    ///     Html::default()
+    /// }
+    /// ```
+    pub fn to_stream(&self) -> SliceStream<T> {
+        SliceStream::new(&self.root)
+    }
+
+    pub(crate) fn state(&self) -> SliceState<T> {
+        self.root.get_state::<SliceState<T>>()
+    }
+
+    /// Returns a [`Sink`] that dispatches each action sent to it.
+    pub fn to_sink(&self) -> SliceSink<T> {
+        SliceSink {
+            state: self.root.get_state::<SliceState<T>>(),
+        }
+    }
+
+    /// Returns a [`SliceStore`] to read, update and subscribe to the slice outside of components.
+    pub fn to_store(&self) -> SliceStore<T> {
+        SliceStore::new(self.state())
+    }
 }
 
 impl<T> Deref for UseSliceHandle<T>
@@ -213,6 +469,112 @@ where
     }
 }
 
+/// A [`Stream`] of the values of a [`Slice`](macro@crate::Slice).
+///
+/// See: [`UseSliceHandle::to_stream`]
+pub struct SliceStream<T>
+where
+    T: Slice,
+{
+    rx: mpsc::UnboundedReceiver<Rc<T>>,
+    _listener: Listener,
+}
+
+impl<T> SliceStream<T>
+where
+    T: Slice + 'static,
+{
+    fn new(root: &BounceRootState) -> Self {
+        let (tx, rx) = mpsc::unbounded();
+
+        let listener = root
+            .get_state::<SliceState<T>>()
+            .listen(Rc::new(Callback::from(move |m| {
+                let _ = tx.unbounded_send(m);
+            })));
+
+        Self {
+            rx,
+            _listener: listener,
+        }
+    }
+}
+
+impl<T> Stream for SliceStream<T>
+where
+    T: Slice,
+{
+    type Item = Rc<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.rx).poll_next(cx)
+    }
+}
+
+impl<T> fmt::Debug for SliceStream<T>
+where
+    T: Slice,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SliceStream").finish_non_exhaustive()
+    }
+}
+
+/// A [`Sink`] that dispatches actions to a [`Slice`](macro@crate::Slice).
+///
+/// See: [`UseSliceHandle::to_sink`]
+pub struct SliceSink<T>
+where
+    T: Slice,
+{
+    state: SliceState<T>,
+}
+
+impl<T> Sink<T::Action> for SliceSink<T>
+where
+    T: Slice + 'static,
+{
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T::Action) -> Result<(), Self::Error> {
+        self.state.dispatch(item);
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Clone for SliceSink<T>
+where
+    T: Slice,
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SliceSink<T>
+where
+    T: Slice,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SliceSink").finish_non_exhaustive()
+    }
+}
+
 /// A hook to connect to a [`Slice`](macro@crate::Slice).
 ///
 /// Returns a [`UseSliceHandle<T>`].
@@ -271,31 +633,63 @@ where
     T: Slice + 'static,
 {
     let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let val = (*use_slice_subscription::<T>(root.clone(), false)).clone();
+
+    UseSliceHandle { inner: val, root }
+}
 
-    let val = {
-        let root = root.clone();
-        use_state_eq(move || root.get_state::<SliceState<T>>().get())
-    };
+/// Subscribes the current component to a slice and returns the current value of the slice.
+///
+/// If `lazy` is `true`, the listener is registered when the browser is idle.
+#[hook]
+fn use_slice_subscription<T>(root: BounceRootState, lazy: bool) -> UseStateHandle<Rc<T>>
+where
+    T: Slice + 'static,
+{
+    let val = use_state_eq(|| root.get_state::<SliceState<T>>().get());
+    #[cfg(feature = "devtools")]
+    let component = crate::devtools::use_component_key();
 
     {
         let val = val.clone();
-        let root = root.clone();
         use_memo(root, move |root| {
-            let state = root.get_state::<SliceState<T>>();
-
-            // we need to set the value here again in case the value has changed between the
-            // initial render and the listener is registered.
-            val.set(state.get());
-
-            state.listen(Rc::new(Callback::from(move |m| {
-                val.set(m);
-            })))
+            let listen = {
+                let root = root.clone();
+                move || {
+                    let state = root.get_state::<SliceState<T>>();
+
+                    // we need to set the value here again in case the value has changed between
+                    // the initial render and the listener is registered.
+                    val.set(state.get());
+
+                    state.listen(Rc::new(Callback::from(move |m| {
+                        #[cfg(feature = "devtools")]
+                        crate::devtools::record_update(component);
+
+                        val.set(m);
+                    })))
+                }
+            };
+
+            if !lazy {
+                return Rc::new(RefCell::new(Some(listen())));
+            }
+
+            let listener = Rc::new(RefCell::new(None));
+            let weak_listener = Rc::downgrade(&listener);
+
+            IdleScheduler::new(root.clone()).schedule(move || {
+                // The component may have been unmounted before the browser became idle.
+                if let Some(m) = weak_listener.upgrade() {
+                    *m.borrow_mut() = Some(listen());
+                }
+            });
+
+            listener
         });
     }
 
-    let val = (*val).clone();
-
-    UseSliceHandle { inner: val, root }
+    val
 }
 
 /// A hook to produce a dispatch function for a [`Slice`](macro@crate::Slice).
@@ -365,6 +759,140 @@ where
     })
 }
 
+/// A hook to produce a dispatch function that returns the output of the action for a
+/// [`Slice`](macro@crate::Slice) that implements [`ReducibleWithOutput`].
+///
+/// Returns a `Rc<dyn Fn(T::Action) -> T::Output>`.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::{use_slice_dispatch_with_output, ReducibleWithOutput};
+/// #
+/// # enum TodoAction {
+/// #     Add(String),
+/// # }
+/// #
+/// # #[derive(PartialEq, Default, Clone, Slice)]
+/// # struct Todos {
+/// #     next_id: u64,
+/// #     items: Vec<(u64, String)>,
+/// # }
+/// #
+/// # impl ReducibleWithOutput for Todos {
+/// #     type Output = u64;
+/// #
+/// #     fn reduce_with_output(self: Rc<Self>, action: TodoAction) -> (Rc<Self>, u64) {
+/// #         match action {
+/// #             TodoAction::Add(title) => {
+/// #                 let mut next = self.clone_slice();
+/// #                 let id = next.next_id;
+/// #                 next.next_id += 1;
+/// #                 next.items.push((id, title));
+/// #
+/// #                 (next.into(), id)
+/// #             }
+/// #         }
+/// #     }
+/// # }
+/// #
+/// # impl Reducible for Todos {
+/// #     type Action = TodoAction;
+/// #
+/// #     fn reduce(self: Rc<Self>, action: TodoAction) -> Rc<Self> {
+/// #         self.reduce_with_output(action).0
+/// #     }
+/// # }
+/// #
+/// #[function_component(AddTodo)]
+/// fn add_todo() -> Html {
+///     let add_todo = use_slice_dispatch_with_output::<Todos>();
+///     let editing = use_state(|| None);
+///
+///     let onclick = {
+///         let editing = editing.clone();
+///         Callback::from(move |_| {
+///             let id = add_todo(TodoAction::Add("New Todo".into()));
+///             // Focus the todo that was just added.
+///             editing.set(Some(id));
+///         })
+///     };
+///
+///     html! { <button {onclick}>{"Add"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_slice_dispatch_with_output<T>() -> Rc<dyn Fn(T::Action) -> T::Output>
+where
+    T: ReducibleWithOutput + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move |action: T::Action| {
+        root.get_state::<SliceState<T>>()
+            .dispatch_with_output(action)
+    })
+}
+
+/// A hook to produce a dispatch function for a [`Slice`](macro@crate::Slice) that skips
+/// actions which reduce to a value equal to the current value.
+///
+/// Returns a `Rc<dyn Fn(T::Action) -> bool>`, which returns `true` if the value has changed.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// # use bounce::use_slice_dispatch_if_changed;
+/// #
+/// # enum CounterAction {
+/// #     Set(u64),
+/// # }
+/// #
+/// # #[derive(PartialEq, Default, Slice)]
+/// # struct Counter(u64);
+/// #
+/// # impl Reducible for Counter {
+/// #     type Action = CounterAction;
+/// #
+/// #     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+/// #         match action {
+/// #             CounterAction::Set(m) => Self(m).into(),
+/// #         }
+/// #     }
+/// # }
+/// #
+/// #[function_component(ResetCounter)]
+/// fn reset_counter() -> Html {
+///     let dispatch_ctr = use_slice_dispatch_if_changed::<Counter>();
+///
+///     let onclick = Callback::from(move |_| {
+///         if !dispatch_ctr(CounterAction::Set(0)) {
+///             // The counter was already 0, no subscriber was notified.
+///         }
+///     });
+///
+///     html! { <button {onclick}>{"Reset"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_slice_dispatch_if_changed<T>() -> Rc<dyn Fn(T::Action) -> bool>
+where
+    T: Slice + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move |action: T::Action| {
+        root.get_state::<SliceState<T>>()
+            .dispatch_if_changed(action)
+    })
+}
+
 /// A read-only hook to connect to the value of a [`Slice`](macro@crate::Slice).
 ///
 /// Returns `Rc<T>`.
@@ -411,5 +939,8 @@ pub fn use_slice_value<T>() -> Rc<T>
 where
     T: Slice + 'static,
 {
-    use_slice::<T>().inner
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let lazy = use_lazy_subscriptions();
+
+    (*use_slice_subscription::<T>(root, lazy)).clone()
 }
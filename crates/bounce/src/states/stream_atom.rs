@@ -0,0 +1,171 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use anymap2::AnyMap;
+use futures::future::{abortable, AbortHandle};
+use futures::{FutureExt, Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use super::atom::{use_atom_value, Atom, AtomSlice};
+use super::slice::SliceState;
+use crate::any_state::AnyState;
+use crate::root_state::BounceRootState;
+
+/// The subscription of an atom to a stream.
+///
+/// The stream is shared by all components that are connected to the atom with
+/// [`use_stream_atom`]. It is started when the first component is mounted and dropped when the
+/// last component is unmounted.
+struct AtomStreamState<T>
+where
+    T: Atom + 'static,
+{
+    subscribers: Rc<Cell<usize>>,
+    abort_handle: Rc<RefCell<Option<AbortHandle>>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> AtomStreamState<T>
+where
+    T: Atom + 'static,
+{
+    fn subscribe<F, S>(&self, root: BounceRootState, stream_factory: F)
+    where
+        F: FnOnce() -> S,
+        S: Stream<Item = T> + 'static,
+    {
+        let subscribers = self.subscribers.get() + 1;
+        self.subscribers.set(subscribers);
+
+        if subscribers > 1 {
+            return;
+        }
+
+        let stream = stream_factory();
+
+        let (fut, abort_handle) = abortable(async move {
+            let mut stream = Box::pin(stream);
+
+            while let Some(m) = stream.next().await {
+                root.get_state::<SliceState<AtomSlice<T>>>().dispatch(m);
+            }
+        });
+
+        *self.abort_handle.borrow_mut() = Some(abort_handle);
+
+        spawn_local(fut.map(|_| ()));
+    }
+
+    fn unsubscribe(&self) {
+        let subscribers = self.subscribers.get().saturating_sub(1);
+        self.subscribers.set(subscribers);
+
+        if subscribers > 0 {
+            return;
+        }
+
+        if let Some(m) = self.abort_handle.borrow_mut().take() {
+            m.abort();
+        }
+    }
+}
+
+impl<T> Default for AtomStreamState<T>
+where
+    T: Atom + 'static,
+{
+    fn default() -> Self {
+        Self {
+            subscribers: Rc::default(),
+            abort_handle: Rc::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for AtomStreamState<T>
+where
+    T: Atom + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            subscribers: self.subscribers.clone(),
+            abort_handle: self.abort_handle.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> AnyState for AtomStreamState<T>
+where
+    T: Atom + 'static,
+{
+    fn apply(&self, _notion: Rc<dyn Any>) {}
+
+    fn create(_init_states: &mut AnyMap) -> Self
+    where
+        Self: Sized,
+    {
+        Self::default()
+    }
+}
+
+/// A hook to connect to an [`Atom`](macro@crate::Atom) that is updated with the items of a
+/// [`Stream`].
+///
+/// Each item yielded by the stream replaces the value of the atom. As the value is stored in an
+/// atom, it can be read by selectors and reset by notions like any other atom.
+///
+/// The stream is created with `stream_factory` when the first component connected to the atom
+/// with this hook is mounted and is shared by all components connected to the atom with this hook.
+/// The stream is dropped when the last of these components is unmounted.
+///
+/// Returns `Rc<T>`.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use futures::stream::{self, StreamExt};
+/// # use yew::prelude::*;
+/// # use yew::platform::time::sleep;
+/// # use bounce::prelude::*;
+/// #
+/// #[derive(PartialEq, Default, Atom)]
+/// struct Ticks(u64);
+///
+/// #[function_component(Clock)]
+/// fn clock() -> Html {
+///     let ticks = use_stream_atom::<Ticks, _, _>(|| {
+///         stream::iter(1..).then(|m| async move {
+///             sleep(Duration::from_secs(1)).await;
+///             Ticks(m)
+///         })
+///     });
+///
+///     html! { <div>{"Seconds elapsed: "}{ticks.0}</div> }
+/// }
+/// ```
+#[hook]
+pub fn use_stream_atom<T, F, S>(stream_factory: F) -> Rc<T>
+where
+    T: Atom + 'static,
+    F: FnOnce() -> S + 'static,
+    S: Stream<Item = T> + 'static,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+    let value = use_atom_value::<T>();
+
+    use_effect_with((), move |_| {
+        let state = root.get_state::<AtomStreamState<T>>();
+        state.subscribe(root, stream_factory);
+
+        move || state.unsubscribe()
+    });
+
+    value
+}
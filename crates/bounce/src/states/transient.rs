@@ -0,0 +1,175 @@
+use std::any::{type_name, TypeId};
+use std::fmt;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+
+use super::atom::{Atom, AtomSlice};
+use super::slice::{Slice, SliceState};
+use crate::root_state::BounceRootState;
+
+type ResetFn = Rc<dyn Fn(&BounceRootState)>;
+
+/// A set of state types that are reset by a [`TransientScope`].
+///
+/// # Example
+///
+/// ```
+/// # use bounce::prelude::*;
+/// # use bounce::TransientStates;
+/// #
+/// # #[derive(Atom, PartialEq, Default)]
+/// # struct FormValues;
+/// #
+/// # #[derive(Atom, PartialEq, Default)]
+/// # struct FormErrors;
+/// #
+/// let states = TransientStates::new()
+///     .with_atom::<FormValues>()
+///     .with_atom::<FormErrors>();
+/// ```
+#[derive(Clone, Default)]
+pub struct TransientStates {
+    inner: Vec<(TypeId, &'static str, ResetFn)>,
+}
+
+impl TransientStates {
+    /// Creates an empty set of transient states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a [`Slice`](macro@crate::Slice) to the set.
+    pub fn with_slice<T>(mut self) -> Self
+    where
+        T: Slice + 'static,
+    {
+        self.inner.push((
+            TypeId::of::<T>(),
+            type_name::<T>(),
+            Rc::new(|root: &BounceRootState| {
+                // The slice is created as if it has not been used before, except that
+                // values provided to the root are not used again.
+                let value = root.get_override::<T>().unwrap_or_else(|| {
+                    T::create_from_states(&root.states())
+                        .unwrap_or_default()
+                        .into()
+                });
+                root.get_state::<SliceState<T>>().replace(value);
+            }),
+        ));
+
+        self
+    }
+
+    /// Adds an [`Atom`](macro@crate::Atom) to the set.
+    pub fn with_atom<T>(self) -> Self
+    where
+        T: Atom + 'static,
+    {
+        self.with_slice::<AtomSlice<T>>()
+    }
+
+    fn reset(&self, root: &BounceRootState) {
+        for (_, _, reset) in self.inner.iter() {
+            reset(root);
+        }
+    }
+}
+
+impl PartialEq for TransientStates {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.len() == other.inner.len()
+            && self
+                .inner
+                .iter()
+                .zip(other.inner.iter())
+                .all(|(lhs, rhs)| lhs.0 == rhs.0)
+    }
+}
+
+impl fmt::Debug for TransientStates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.inner.iter().map(|(_, name, _)| name))
+            .finish()
+    }
+}
+
+/// Properties of the [`TransientScope`] component.
+#[derive(Properties, Debug, PartialEq, Clone)]
+pub struct TransientScopeProps {
+    /// The states that are reset when the scope is mounted and unmounted.
+    pub states: TransientStates,
+
+    /// The children of the scope.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A component that ties designated states to its lifecycle.
+///
+/// The states are reset to a fresh value before the children of the scope are rendered for the
+/// first time and reset again when the scope is unmounted, so values never outlive the scope
+/// even though the states are used with regular bounce hooks. Observers of the states are
+/// notified of each reset.
+///
+/// This is useful for modal dialogs, wizards and editors that should start from a clean state
+/// each time they are opened.
+///
+/// # Note
+///
+/// The states are still shared with the rest of the bounce root, components outside of the
+/// scope that use the same states will see the values reset as well.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::{TransientScope, TransientStates};
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Draft {
+///     content: String,
+/// }
+///
+/// #[function_component(Editor)]
+/// fn editor() -> Html {
+///     let draft = use_atom::<Draft>();
+///
+///     html! { <textarea value={draft.content.clone()} /> }
+/// }
+///
+/// #[function_component(EditorDialog)]
+/// fn editor_dialog() -> Html {
+///     // Each time the dialog is opened, the editor starts with an empty draft.
+///     html! {
+///         <TransientScope states={TransientStates::new().with_atom::<Draft>()}>
+///             <Editor />
+///         </TransientScope>
+///     }
+/// }
+/// ```
+#[function_component(TransientScope)]
+pub fn transient_scope(props: &TransientScopeProps) -> Html {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    {
+        let root = root.clone();
+        let states = props.states.clone();
+
+        // Effects run after children are rendered, so the states are reset during the first
+        // render of the scope.
+        use_memo((), move |_| states.reset(&root));
+    }
+
+    // The states of the latest render are reset when the scope is unmounted.
+    let states = use_mut_ref(TransientStates::default);
+    *states.borrow_mut() = props.states.clone();
+
+    use_effect_with((), move |_| move || states.borrow().reset(&root));
+
+    props.children.clone()
+}
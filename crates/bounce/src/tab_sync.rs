@@ -0,0 +1,397 @@
+//! Slices that are synchronised between the tabs of an application.
+//!
+//! A slice that implements [`TabSynced`] is synchronised with other tabs (and other roots on the
+//! same page) while [`use_tab_sync`] is mounted. Each update is broadcast with a
+//! [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel) and
+//! carries a version vector, so tabs can tell whether an update from another tab follows their
+//! current value or has been made concurrently with a local update.
+//!
+//! Updates that follow the current value replace it. Concurrent updates are resolved with the
+//! [`ConflictResolution`] of the slice, so concurrent edits in 2 tabs do not silently overwrite
+//! each other. All tabs converge to the same value after a conflict is resolved.
+//!
+//! Only updates are synchronised, a tab that is opened later starts with the initial value of
+//! the slice until the next update.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::rc::Rc;
+//! # use serde::{Deserialize, Serialize};
+//! # use yew::prelude::*;
+//! # use bounce::prelude::*;
+//! # use bounce::BounceRoot;
+//! use bounce::tab_sync::{use_tab_sync, ConflictResolution, TabSynced};
+//!
+//! #[derive(Slice, Serialize, Deserialize, PartialEq, Default, Clone)]
+//! struct Cart {
+//!     items: Vec<String>,
+//! }
+//!
+//! # pub enum CartAction {
+//! #     Add(String),
+//! # }
+//! #
+//! # impl Reducible for Cart {
+//! #     type Action = CartAction;
+//! #
+//! #     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+//! #         match action {
+//! #             CartAction::Add(m) => {
+//! #                 let mut items = self.items.clone();
+//! #                 items.push(m);
+//! #                 Self { items }.into()
+//! #             }
+//! #         }
+//! #     }
+//! # }
+//! #
+//! impl TabSynced for Cart {
+//!     const CHANNEL: &'static str = "cart";
+//!     // Items added in 2 tabs at the same time are kept.
+//!     const CONFLICT_RESOLUTION: ConflictResolution<Self> = ConflictResolution::Merge(merge_carts);
+//! }
+//!
+//! fn merge_carts(local: &Cart, remote: &Cart) -> Cart {
+//!     let mut items = local.items.clone();
+//!     items.extend(remote.items.iter().filter(|m| !local.items.contains(m)).cloned());
+//!
+//!     Cart { items }
+//! }
+//!
+//! #[function_component(CartSync)]
+//! fn cart_sync() -> Html {
+//!     use_tab_sync::<Cart>();
+//!
+//!     Html::default()
+//! }
+//!
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     html! {
+//!         <BounceRoot>
+//!             <CartSync />
+//!             // children...
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+use web_sys::{BroadcastChannel, MessageEvent};
+use yew::prelude::*;
+
+use crate::root_state::BounceRootState;
+use crate::states::slice::{Slice, SliceState};
+use crate::utils::Listener;
+
+/// How a slice resolves an update from another tab that has been made concurrently with a local
+/// update.
+///
+/// See: [`TabSynced::CONFLICT_RESOLUTION`]
+pub enum ConflictResolution<T> {
+    /// The update that has been made last is kept, the other update is discarded.
+    ///
+    /// Updates are compared with the clock of the tabs that made them.
+    LastWriterWins,
+    /// The local value and the value of the other tab are merged with a function.
+    ///
+    /// The function receives the local value and the value of the other tab. It is run in both
+    /// tabs, and the tabs converge to the result of 1 of them.
+    Merge(fn(&T, &T) -> T),
+}
+
+impl<T> Clone for ConflictResolution<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ConflictResolution<T> {}
+
+impl<T> fmt::Debug for ConflictResolution<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LastWriterWins => f.write_str("LastWriterWins"),
+            Self::Merge(_) => f.debug_tuple("Merge").finish(),
+        }
+    }
+}
+
+/// A trait to be implemented on slices that are synchronised between tabs.
+///
+/// See: [`bounce::tab_sync`](crate::tab_sync)
+pub trait TabSynced: Slice + Serialize + DeserializeOwned + 'static {
+    /// The name of the channel the slice is synchronised with.
+    ///
+    /// Each synchronised slice of an application should have a unique channel.
+    const CHANNEL: &'static str;
+
+    /// How updates that have been made concurrently in 2 tabs are resolved.
+    ///
+    /// Defaults to [`ConflictResolution::LastWriterWins`].
+    const CONFLICT_RESOLUTION: ConflictResolution<Self> = ConflictResolution::LastWriterWins;
+}
+
+/// The number of updates each tab has made to the value of a slice.
+type VersionVector = BTreeMap<u64, u64>;
+
+/// Compares 2 version vectors.
+///
+/// Returns `None` if the versions are concurrent.
+fn compare_versions(lhs: &VersionVector, rhs: &VersionVector) -> Option<Ordering> {
+    let mut ordering = Ordering::Equal;
+
+    for tab in lhs.keys().chain(rhs.keys()) {
+        let lhs_version = lhs.get(tab).copied().unwrap_or(0);
+        let rhs_version = rhs.get(tab).copied().unwrap_or(0);
+
+        match (ordering, lhs_version.cmp(&rhs_version)) {
+            (_, Ordering::Equal) => {}
+            (Ordering::Equal, m) => ordering = m,
+            (m, n) if m != n => return None,
+            _ => {}
+        }
+    }
+
+    Some(ordering)
+}
+
+fn merge_versions(lhs: &VersionVector, rhs: &VersionVector) -> VersionVector {
+    let mut merged = lhs.clone();
+
+    for (tab, version) in rhs.iter() {
+        let m = merged.entry(*tab).or_default();
+        *m = (*m).max(*version);
+    }
+
+    merged
+}
+
+type MessageListener = Closure<dyn Fn(MessageEvent)>;
+
+/// An update broadcast to other tabs.
+#[derive(Serialize, Deserialize)]
+struct TabSyncMessage {
+    tab: u64,
+    versions: VersionVector,
+    updated_at: f64,
+    value: Value,
+}
+
+/// A synchronisation of a slice with other tabs, stopped when dropped.
+struct TabSyncSession<T>
+where
+    T: TabSynced,
+{
+    tab: u64,
+    state: SliceState<T>,
+    channel: BroadcastChannel,
+    versions: RefCell<VersionVector>,
+    updated_at: Cell<f64>,
+    // The last value received from another tab, which is not broadcast again.
+    remote_value: RefCell<Option<Rc<T>>>,
+    listener: RefCell<Option<Listener>>,
+    onmessage: RefCell<Option<MessageListener>>,
+}
+
+impl<T> TabSyncSession<T>
+where
+    T: TabSynced,
+{
+    fn start(root: &BounceRootState) -> Option<Rc<Self>> {
+        // There is no other tab on the server.
+        if cfg!(not(target_arch = "wasm32")) {
+            return None;
+        }
+
+        let channel = match BroadcastChannel::new(T::CHANNEL) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!("failed to open channel {}: {:?}", T::CHANNEL, e);
+                return None;
+            }
+        };
+
+        let this = Rc::new(Self {
+            tab: (js_sys::Math::random() * (u64::MAX as f64)) as u64,
+            state: root.get_state::<SliceState<T>>(),
+            channel,
+            versions: RefCell::default(),
+            updated_at: Cell::new(0.0),
+            remote_value: RefCell::default(),
+            listener: RefCell::default(),
+            onmessage: RefCell::default(),
+        });
+
+        let weak_this = Rc::downgrade(&this);
+        *this.listener.borrow_mut() = Some(this.state.listen(Rc::new(Callback::from(
+            move |value: Rc<T>| {
+                if let Some(this) = weak_this.upgrade() {
+                    this.local_changed(value);
+                }
+            },
+        ))));
+
+        let weak_this: Weak<Self> = Rc::downgrade(&this);
+        let onmessage = MessageListener::new(move |event: MessageEvent| {
+            if let Some(this) = weak_this.upgrade() {
+                this.received(event.data());
+            }
+        });
+        this.channel
+            .set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        *this.onmessage.borrow_mut() = Some(onmessage);
+
+        Some(this)
+    }
+
+    fn local_changed(&self, value: Rc<T>) {
+        let is_remote = self
+            .remote_value
+            .borrow()
+            .as_ref()
+            .map(|m| Rc::ptr_eq(m, &value))
+            .unwrap_or(false);
+
+        if !is_remote {
+            self.updated_at.set(js_sys::Date::now());
+            self.publish(&value);
+        }
+    }
+
+    /// Broadcasts the local value as a new update of this tab.
+    fn publish(&self, value: &T) {
+        let message = {
+            let mut versions = self.versions.borrow_mut();
+            *versions.entry(self.tab).or_default() += 1;
+
+            let value = match serde_json::to_value(value) {
+                Ok(m) => m,
+                Err(e) => {
+                    tracing::error!("failed to serialise value of {}: {}", T::CHANNEL, e);
+                    return;
+                }
+            };
+
+            TabSyncMessage {
+                tab: self.tab,
+                versions: versions.clone(),
+                updated_at: self.updated_at.get(),
+                value,
+            }
+        };
+
+        match serde_json::to_string(&message) {
+            Ok(m) => {
+                if let Err(e) = self.channel.post_message(&m.into()) {
+                    tracing::warn!("failed to broadcast update of {}: {:?}", T::CHANNEL, e);
+                }
+            }
+            Err(e) => tracing::error!("failed to serialise update of {}: {}", T::CHANNEL, e),
+        }
+    }
+
+    fn received(&self, data: JsValue) {
+        let message = match data
+            .as_string()
+            .map(|m| serde_json::from_str::<TabSyncMessage>(&m))
+        {
+            Some(Ok(m)) if m.tab != self.tab => m,
+            _ => return,
+        };
+
+        let remote = match serde_json::from_value::<T>(message.value) {
+            Ok(m) => Rc::new(m),
+            Err(e) => {
+                tracing::warn!("failed to deserialise update of {}: {}", T::CHANNEL, e);
+                return;
+            }
+        };
+
+        let local_versions = self.versions.borrow().clone();
+
+        match compare_versions(&message.versions, &local_versions) {
+            // The update follows the local value.
+            Some(Ordering::Greater) => {
+                *self.versions.borrow_mut() = message.versions;
+                self.updated_at.set(message.updated_at);
+                self.replace(remote);
+            }
+            // The update has already been applied.
+            Some(Ordering::Less) | Some(Ordering::Equal) => {}
+            // The update has been made concurrently with a local update.
+            None => {
+                let local = self.state.get();
+
+                let resolved = match T::CONFLICT_RESOLUTION {
+                    ConflictResolution::LastWriterWins => {
+                        let remote_wins =
+                            (message.updated_at, message.tab) > (self.updated_at.get(), self.tab);
+
+                        if remote_wins {
+                            remote
+                        } else {
+                            local
+                        }
+                    }
+                    ConflictResolution::Merge(merge) => Rc::new(merge(&local, &remote)),
+                };
+
+                *self.versions.borrow_mut() = merge_versions(&local_versions, &message.versions);
+                self.updated_at
+                    .set(self.updated_at.get().max(message.updated_at));
+                self.replace(resolved.clone());
+
+                // Only 1 of the tabs broadcasts the resolved value, so the tabs converge to it
+                // instead of resolving each other's results again.
+                if self.tab > message.tab {
+                    self.publish(&resolved);
+                }
+            }
+        }
+    }
+
+    fn replace(&self, value: Rc<T>) {
+        *self.remote_value.borrow_mut() = Some(value.clone());
+        self.state.replace(value);
+    }
+}
+
+impl<T> Drop for TabSyncSession<T>
+where
+    T: TabSynced,
+{
+    fn drop(&mut self) {
+        self.channel.set_onmessage(None);
+        self.channel.close();
+    }
+}
+
+/// A hook to synchronise a slice with other tabs while the component is mounted.
+///
+/// Each slice should be synchronised by only 1 component of each root.
+///
+/// See: [`bounce::tab_sync`](crate::tab_sync)
+#[hook]
+pub fn use_tab_sync<T>()
+where
+    T: TabSynced,
+{
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    use_effect_with(root, |root| {
+        let session = TabSyncSession::<T>::start(root);
+
+        move || drop(session)
+    });
+}
@@ -0,0 +1,256 @@
+//! Utilities to test components that use bounce states.
+//!
+//! The helpers in this module wait until a state of the most recently mounted `<BounceRoot />`
+//! meets a condition. They subscribe to the state with the same listeners that are used by hooks,
+//! so a test resumes as soon as the state changes instead of polling with sleeps.
+//!
+//! If the condition is not met within the timeout, a [`WaitTimedOut`] error is returned. The
+//! timeout is measured with the [`Clock`](crate::Clock) of the root.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use bounce::prelude::*;
+//! # use bounce::testing::wait_for_state;
+//! #
+//! #[derive(Atom, PartialEq, Default)]
+//! struct Counter {
+//!     inner: u32,
+//! }
+//!
+//! # async fn test() {
+//! // render the application...
+//!
+//! let counter = wait_for_state::<Counter>(|m| m.inner == 3, Duration::from_secs(5))
+//!     .await
+//!     .expect("counter did not reach 3");
+//! # }
+//! ```
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::future::{select, Either};
+use yew::callback::Callback;
+use yew::platform::pinned::oneshot;
+
+use crate::root_state::{BounceRootState, BounceStates};
+use crate::states::atom::Atom;
+use crate::states::slice::Slice;
+
+thread_local! {
+    static MOUNTED_ROOTS: RefCell<Vec<BounceRootState>> = RefCell::default();
+}
+
+/// Records that a root has been created.
+pub(crate) fn register_root(root: &BounceRootState) {
+    MOUNTED_ROOTS.with(|m| m.borrow_mut().push(root.clone()));
+}
+
+/// Records that a root has been cleared.
+pub(crate) fn unregister_root(root: &BounceRootState) {
+    MOUNTED_ROOTS.with(|m| m.borrow_mut().retain(|m| m != root));
+}
+
+fn current_root() -> BounceRootState {
+    MOUNTED_ROOTS
+        .with(|m| m.borrow().last().cloned())
+        .expect("no bounce root is mounted.")
+}
+
+/// An error returned when a condition is not met within the timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WaitTimedOut {
+    /// The timeout that has elapsed.
+    pub timeout: Duration,
+}
+
+impl fmt::Display for WaitTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "condition is not met within {:?}", self.timeout)
+    }
+}
+
+impl Error for WaitTimedOut {}
+
+/// Reads states with `read` until it returns `Some`, reading again each time a state it has read
+/// changes.
+async fn wait_for<R>(
+    timeout: Duration,
+    read: impl Fn(&BounceStates) -> Option<R>,
+) -> Result<R, WaitTimedOut> {
+    let root = current_root();
+    let mut deadline = root.clock().sleep(timeout);
+
+    loop {
+        let states = root.states();
+
+        let (sender, receiver) = oneshot::channel::<()>();
+        let sender = RefCell::new(Some(sender));
+        states.add_listener_callback(Rc::new(Callback::from(move |_| {
+            if let Some(m) = sender.borrow_mut().take() {
+                let _ = m.send(());
+            }
+        })));
+
+        if let Some(m) = read(&states) {
+            return Ok(m);
+        }
+
+        match select(Box::pin(receiver), &mut deadline).await {
+            Either::Left(_) => continue,
+            Either::Right(_) => return Err(WaitTimedOut { timeout }),
+        }
+    }
+}
+
+/// Waits until the value of an [`Atom`](macro@crate::Atom) meets a condition.
+///
+/// Returns the value that has met the condition.
+///
+/// # Panics
+///
+/// Panics if no `<BounceRoot />` is mounted.
+pub async fn wait_for_state<T>(
+    cond: impl Fn(&T) -> bool,
+    timeout: Duration,
+) -> Result<Rc<T>, WaitTimedOut>
+where
+    T: Atom + 'static,
+{
+    wait_for(timeout, |states| {
+        let value = states.get_atom_value::<T>();
+        cond(&value).then_some(value)
+    })
+    .await
+}
+
+/// Waits until the value of a [`Slice`](macro@crate::Slice) meets a condition.
+///
+/// Returns the value that has met the condition.
+///
+/// # Panics
+///
+/// Panics if no `<BounceRoot />` is mounted.
+pub async fn wait_for_slice<T>(
+    cond: impl Fn(&T) -> bool,
+    timeout: Duration,
+) -> Result<Rc<T>, WaitTimedOut>
+where
+    T: Slice + 'static,
+{
+    wait_for(timeout, |states| {
+        let value = states.get_slice_value::<T>();
+        cond(&value).then_some(value)
+    })
+    .await
+}
+
+#[cfg(feature = "query")]
+mod query {
+    use std::time::Duration;
+
+    use super::{wait_for, WaitTimedOut};
+    use crate::query::{Query, QuerySlice, QuerySliceValue, QueryValueState};
+
+    /// The status of a query to wait for.
+    ///
+    /// See: [`wait_for_query`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum QueryStatus {
+        /// The query is loading or has not been started.
+        Loading,
+        /// The query has completed.
+        Completed,
+        /// A previous query has completed and a new query is currently loading.
+        Refreshing,
+    }
+
+    /// Waits until the query with `input` has a status.
+    ///
+    /// Returns the state of the query when it has the status.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no `<BounceRoot />` is mounted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use std::convert::Infallible;
+    /// # use std::time::Duration;
+    /// # use bounce::prelude::*;
+    /// # use bounce::query::{Query, QueryResult, QueryValueState};
+    /// # use bounce::testing::{wait_for_query, QueryStatus};
+    /// # use async_trait::async_trait;
+    /// #
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct UserQuery {
+    /// #     name: String,
+    /// # }
+    /// #
+    /// # #[async_trait(?Send)]
+    /// # impl Query for UserQuery {
+    /// #     type Input = u64;
+    /// #     type Error = Infallible;
+    /// #
+    /// #     async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+    /// #         Ok(UserQuery { name: "John Smith".into() }.into())
+    /// #     }
+    /// # }
+    /// #
+    /// # async fn test() {
+    /// // render the application...
+    ///
+    /// let state = wait_for_query::<UserQuery>(1, QueryStatus::Completed, Duration::from_secs(5))
+    ///     .await
+    ///     .expect("user did not load");
+    ///
+    /// if let QueryValueState::Completed { result: Ok(m) } = state {
+    ///     assert_eq!(m.name, "John Smith");
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_query<T>(
+        input: T::Input,
+        status: QueryStatus,
+        timeout: Duration,
+    ) -> Result<QueryValueState<T>, WaitTimedOut>
+    where
+        T: Query + 'static,
+    {
+        wait_for(timeout, |states| {
+            let value = states
+                .get_slice_value::<QuerySlice<T>>()
+                .get(&input)
+                .cloned();
+
+            let (current_status, state) = match value {
+                None | Some(QuerySliceValue::Loading { .. }) => {
+                    (QueryStatus::Loading, QueryValueState::Loading)
+                }
+                Some(QuerySliceValue::Completed { result, .. }) => (
+                    QueryStatus::Completed,
+                    QueryValueState::Completed { result },
+                ),
+                Some(QuerySliceValue::Outdated { result, .. }) => (
+                    QueryStatus::Refreshing,
+                    QueryValueState::Refreshing {
+                        last_result: result,
+                    },
+                ),
+            };
+
+            (current_status == status).then_some(state)
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "query")]
+pub use query::{wait_for_query, QueryStatus};
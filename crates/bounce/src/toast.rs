@@ -0,0 +1,454 @@
+//! A state of notifications (toasts) that are displayed to the user.
+//!
+//! Toasts are pushed with [`use_push_toast`] and read with [`use_toasts`]. This module does not
+//! render any element, applications render the toasts returned by [`use_toasts`] with their own
+//! components.
+//!
+//! A toast is dismissed automatically after its time-to-live has elapsed. The time-to-live is
+//! measured with the [`Clock`](crate::Clock) of the root.
+//!
+//! With the `query` feature, errors of queries and mutations can be pushed as toasts by
+//! registering a [`QueryErrorToasts`] component under the [`BounceRoot`](crate::BounceRoot).
+//!
+//! # Example
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use bounce::prelude::*;
+//! use bounce::toast::{use_push_toast, use_toasts, Toast};
+//!
+//! #[function_component(Toasts)]
+//! fn toasts() -> Html {
+//!     let toasts = use_toasts();
+//!
+//!     toasts
+//!         .iter()
+//!         .map(|m| {
+//!             let id = m.id();
+//!             let toasts = toasts.clone();
+//!             let onclick = Callback::from(move |_| toasts.dismiss(id));
+//!
+//!             html! { <div key={id.to_string()} {onclick}>{m.message.clone()}</div> }
+//!         })
+//!         .collect()
+//! }
+//!
+//! #[function_component(SaveButton)]
+//! fn save_button() -> Html {
+//!     let push_toast = use_push_toast();
+//!     let onclick = Callback::from(move |_| {
+//!         push_toast(Toast::success("Saved."));
+//!     });
+//!
+//!     html! { <button {onclick}>{"Save"}</button> }
+//! }
+//! ```
+
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen::prelude::*;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use crate::root_state::BounceRootState;
+use crate::states::slice::{use_slice, SliceState, UseSliceHandle};
+use crate::utils::Id;
+use crate::Slice;
+
+/// The time-to-live of a toast that is not set with [`Toast::with_ttl`].
+pub const DEFAULT_TTL: Duration = Duration::from_secs(5);
+
+/// The level of a toast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastLevel {
+    /// A neutral message.
+    Info,
+    /// An operation has succeeded.
+    Success,
+    /// An operation needs the attention of the user.
+    Warning,
+    /// An operation has failed.
+    Error,
+}
+
+/// A toast to be pushed with [`use_push_toast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    /// The level of the toast.
+    pub level: ToastLevel,
+    /// The message of the toast.
+    pub message: AttrValue,
+    /// The duration after which the toast is dismissed, if any.
+    pub ttl: Option<Duration>,
+}
+
+impl Toast {
+    /// Creates a toast with the [default time-to-live](DEFAULT_TTL).
+    pub fn new(level: ToastLevel, message: impl Into<AttrValue>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            ttl: Some(DEFAULT_TTL),
+        }
+    }
+
+    /// Creates a toast with the level [`ToastLevel::Info`].
+    pub fn info(message: impl Into<AttrValue>) -> Self {
+        Self::new(ToastLevel::Info, message)
+    }
+
+    /// Creates a toast with the level [`ToastLevel::Success`].
+    pub fn success(message: impl Into<AttrValue>) -> Self {
+        Self::new(ToastLevel::Success, message)
+    }
+
+    /// Creates a toast with the level [`ToastLevel::Warning`].
+    pub fn warning(message: impl Into<AttrValue>) -> Self {
+        Self::new(ToastLevel::Warning, message)
+    }
+
+    /// Creates a toast with the level [`ToastLevel::Error`].
+    pub fn error(message: impl Into<AttrValue>) -> Self {
+        Self::new(ToastLevel::Error, message)
+    }
+
+    /// Sets the duration after which the toast is dismissed.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Keeps the toast until it is dismissed with [`UseToastsHandle::dismiss`].
+    pub fn persistent(mut self) -> Self {
+        self.ttl = None;
+        self
+    }
+}
+
+/// The id of a toast that has been pushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ToastId(Id);
+
+impl fmt::Display for ToastId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.value())
+    }
+}
+
+/// A toast that has been pushed and is not dismissed yet.
+///
+/// This type dereferences to [`Toast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveToast {
+    id: ToastId,
+    inner: Rc<Toast>,
+}
+
+impl ActiveToast {
+    /// Returns the id of the toast.
+    pub fn id(&self) -> ToastId {
+        self.id
+    }
+}
+
+impl Deref for ActiveToast {
+    type Target = Toast;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+enum ToastsAction {
+    Push(ActiveToast),
+    Dismiss(ToastId),
+    Clear,
+}
+
+#[derive(Slice, PartialEq, Default)]
+struct Toasts {
+    inner: Vec<ActiveToast>,
+}
+
+impl Reducible for Toasts {
+    type Action = ToastsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            ToastsAction::Push(m) => {
+                let mut inner = self.inner.clone();
+                inner.push(m);
+
+                Self { inner }.into()
+            }
+            ToastsAction::Dismiss(id) => {
+                // The toast has already been dismissed.
+                if !self.inner.iter().any(|m| m.id == id) {
+                    return self;
+                }
+
+                Self {
+                    inner: self.inner.iter().filter(|m| m.id != id).cloned().collect(),
+                }
+                .into()
+            }
+            ToastsAction::Clear => {
+                if self.inner.is_empty() {
+                    return self;
+                }
+
+                Self::default().into()
+            }
+        }
+    }
+}
+
+/// A handle returned by [`use_toasts`].
+///
+/// This type dereferences to a slice of the toasts that are not dismissed, in the order they
+/// were pushed.
+pub struct UseToastsHandle {
+    inner: UseSliceHandle<Toasts>,
+}
+
+impl UseToastsHandle {
+    /// Dismisses a toast.
+    ///
+    /// Dismissing a toast that has already been dismissed has no effect.
+    pub fn dismiss(&self, id: ToastId) {
+        self.inner.dispatch(ToastsAction::Dismiss(id));
+    }
+
+    /// Dismisses all toasts.
+    pub fn clear(&self) {
+        self.inner.dispatch(ToastsAction::Clear);
+    }
+}
+
+impl Deref for UseToastsHandle {
+    type Target = [ActiveToast];
+
+    fn deref(&self) -> &Self::Target {
+        &(*self.inner).inner
+    }
+}
+
+impl Clone for UseToastsHandle {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for UseToastsHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UseToastsHandle")
+            .field("toasts", &(*self.inner).inner)
+            .finish()
+    }
+}
+
+/// Pushes a toast and schedules it to be dismissed after its time-to-live.
+fn push_toast(root: &BounceRootState, toast: Toast) -> ToastId {
+    let id = ToastId(Id::new());
+    let ttl = toast.ttl;
+
+    root.get_state::<SliceState<Toasts>>()
+        .dispatch(ToastsAction::Push(ActiveToast {
+            id,
+            inner: toast.into(),
+        }));
+
+    if let Some(m) = ttl {
+        let sleep = root.clock().sleep(m);
+        let root = root.clone();
+
+        spawn_local(async move {
+            sleep.await;
+
+            root.get_state::<SliceState<Toasts>>()
+                .dispatch(ToastsAction::Dismiss(id));
+        });
+    }
+
+    id
+}
+
+/// A hook to read the toasts that are not dismissed.
+///
+/// The component re-renders when a toast is pushed or dismissed.
+///
+/// See: [module documentation](self)
+#[hook]
+pub fn use_toasts() -> UseToastsHandle {
+    UseToastsHandle {
+        inner: use_slice::<Toasts>(),
+    }
+}
+
+/// A hook to push toasts.
+///
+/// Returns a function that pushes a toast and returns its id.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::toast::{use_push_toast, Toast};
+///
+/// #[function_component(Upload)]
+/// fn upload() -> Html {
+///     let push_toast = use_push_toast();
+///     let onclick = Callback::from(move |_| {
+///         push_toast(Toast::warning("The file is larger than 10MB.").with_ttl(Duration::from_secs(10)));
+///     });
+///
+///     html! { <button {onclick}>{"Upload"}</button> }
+/// }
+/// ```
+#[hook]
+pub fn use_push_toast() -> Rc<dyn Fn(Toast) -> ToastId> {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    Rc::new(move |toast| push_toast(&root, toast))
+}
+
+#[cfg(feature = "query")]
+mod feat_query {
+    use std::rc::Rc;
+
+    use yew::prelude::*;
+
+    use super::{use_push_toast, Toast};
+    use crate::query::{MutationErrorNotion, QueryErrorNotion};
+    use crate::states::atom::{use_atom_setter, use_atom_value};
+    use crate::{Atom, WithNotion};
+
+    #[derive(PartialEq, Clone)]
+    enum PendingError {
+        Query(Rc<QueryErrorNotion>),
+        Mutation(Rc<MutationErrorNotion>),
+    }
+
+    /// Errors that have not been pushed as toasts yet.
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(QueryErrorNotion, MutationErrorNotion))]
+    struct PendingErrors {
+        inner: Vec<PendingError>,
+    }
+
+    impl PendingErrors {
+        fn push(&self, error: PendingError) -> Rc<Self> {
+            let mut inner = self.inner.clone();
+            inner.push(error);
+
+            Self { inner }.into()
+        }
+    }
+
+    impl WithNotion<QueryErrorNotion> for PendingErrors {
+        fn apply(self: Rc<Self>, notion: Rc<QueryErrorNotion>) -> Rc<Self> {
+            self.push(PendingError::Query(notion))
+        }
+    }
+
+    impl WithNotion<MutationErrorNotion> for PendingErrors {
+        fn apply(self: Rc<Self>, notion: Rc<MutationErrorNotion>) -> Rc<Self> {
+            self.push(PendingError::Mutation(notion))
+        }
+    }
+
+    /// Properties of [`QueryErrorToasts`].
+    #[derive(Properties, PartialEq, Clone, Debug, Default)]
+    pub struct QueryErrorToastsProps {
+        /// Converts the error of a query into a toast, errors are not pushed if `None` is
+        /// returned.
+        ///
+        /// By default, each error is pushed as an error toast with the message of the error.
+        #[prop_or_default]
+        pub to_toast: Option<Callback<Rc<QueryErrorNotion>, Option<Toast>>>,
+
+        /// Converts the error of a mutation into a toast, errors are not pushed if `None` is
+        /// returned.
+        ///
+        /// By default, each error is pushed as an error toast with the message of the error.
+        #[prop_or_default]
+        pub mutation_to_toast: Option<Callback<Rc<MutationErrorNotion>, Option<Toast>>>,
+    }
+
+    /// A component that pushes the errors of queries and mutations as toasts.
+    ///
+    /// Errors are received as [`QueryErrorNotion`]s and [`MutationErrorNotion`]s, errors that
+    /// occur before this component is mounted are not pushed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # use yew::prelude::*;
+    /// # use bounce::BounceRoot;
+    /// # use bounce::query::{ErrorClass, MutationErrorNotion, QueryErrorNotion};
+    /// use bounce::toast::{QueryErrorToasts, Toast};
+    ///
+    /// #[function_component(App)]
+    /// fn app() -> Html {
+    ///     // Authentication errors are handled by redirecting to the login page instead.
+    ///     let to_toast = Callback::from(|error: Rc<QueryErrorNotion>| match error.class {
+    ///         ErrorClass::Auth => None,
+    ///         _ => Some(Toast::error(error.message.clone())),
+    ///     });
+    ///     let mutation_to_toast = Callback::from(|error: Rc<MutationErrorNotion>| {
+    ///         Some(Toast::error(format!("Failed to save: {}", error.message)))
+    ///     });
+    ///
+    ///     html! {
+    ///         <BounceRoot>
+    ///             <QueryErrorToasts {to_toast} {mutation_to_toast} />
+    ///             // children...
+    ///         </BounceRoot>
+    ///     }
+    /// }
+    /// ```
+    #[function_component(QueryErrorToasts)]
+    pub fn query_error_toasts(props: &QueryErrorToastsProps) -> Html {
+        let pending_errors = use_atom_value::<PendingErrors>();
+        let set_pending_errors = use_atom_setter::<PendingErrors>();
+        let push_toast = use_push_toast();
+
+        let to_toast = props.to_toast.clone();
+        let mutation_to_toast = props.mutation_to_toast.clone();
+        use_effect_with(pending_errors, move |pending_errors| {
+            if !pending_errors.inner.is_empty() {
+                for error in pending_errors.inner.iter() {
+                    let toast = match (error, &to_toast, &mutation_to_toast) {
+                        (PendingError::Query(m), Some(to_toast), _) => to_toast.emit(m.clone()),
+                        (PendingError::Query(m), None, _) => Some(Toast::error(m.message.clone())),
+                        (PendingError::Mutation(m), _, Some(to_toast)) => to_toast.emit(m.clone()),
+                        (PendingError::Mutation(m), _, None) => {
+                            Some(Toast::error(m.message.clone()))
+                        }
+                    };
+
+                    if let Some(m) = toast {
+                        push_toast(m);
+                    }
+                }
+
+                set_pending_errors(PendingErrors::default());
+            }
+
+            || {}
+        });
+
+        Html::default()
+    }
+}
+
+#[cfg(feature = "query")]
+pub use feat_query::{QueryErrorToasts, QueryErrorToastsProps};
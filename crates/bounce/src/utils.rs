@@ -1,21 +1,37 @@
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt;
 use std::rc::{Rc, Weak};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
-use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
 use yew::callback::Callback;
+use yew::prelude::*;
 
-#[derive(PartialEq, Debug, Clone, Eq, Hash, PartialOrd, Ord, Copy, Serialize, Deserialize)]
-pub struct Id(u64);
+use crate::root_state::BounceRootState;
+
+// Ids are only serialised by prepared queries.
+//
+// Ids created by a root are only unique within that root. They are numbered in the order they
+// are created, which is the same during server-side rendering and hydration as long as
+// components are rendered in the same order. Other ids are unique across all roots.
+#[derive(PartialEq, Debug, Clone, Eq, Hash, PartialOrd, Ord, Copy)]
+#[cfg_attr(feature = "query", derive(serde::Serialize, serde::Deserialize))]
+pub struct Id {
+    root_scoped: bool,
+    value: u64,
+}
 
 impl Default for Id {
     fn default() -> Self {
-        static CTR: Lazy<AtomicU64> = Lazy::new(AtomicU64::default);
+        static CTR: AtomicU64 = AtomicU64::new(0);
 
-        Self(CTR.fetch_add(1, Ordering::SeqCst))
+        Self {
+            root_scoped: false,
+            value: CTR.fetch_add(1, Ordering::SeqCst),
+        }
     }
 }
 
@@ -23,6 +39,88 @@ impl Id {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Returns the number of this id.
+    #[cfg(any(feature = "toast", feature = "query"))]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// A generator of ids that are scoped to a root.
+#[derive(Debug, Default)]
+pub(crate) struct IdGenerator {
+    ctr: Cell<u64>,
+}
+
+impl IdGenerator {
+    pub fn next(&self) -> Id {
+        let value = self.ctr.get();
+        self.ctr.set(value + 1);
+
+        Id {
+            root_scoped: true,
+            value,
+        }
+    }
+}
+
+/// A hook to create an id for the current component that is scoped to the bounce root.
+///
+/// Unlike [`Id::new`], the id is the same during server-side rendering and hydration.
+#[hook]
+pub(crate) fn use_root_scoped_id() -> Id {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    *use_memo((), move |_| root.next_id())
+}
+
+/// A hook to create an id for the current component that can be used in the rendered html (e.g.:
+/// to associate a label with an input).
+///
+/// Ids are numbered by the bounce root in the order they are created, so a component receives
+/// the same id during server-side rendering and hydration as long as components are rendered in
+/// the same order. Ids are only unique within a bounce root.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// use bounce::use_id;
+///
+/// #[function_component(NameInput)]
+/// fn name_input() -> Html {
+///     let id = use_id();
+///
+///     html! {
+///         <>
+///             <label for={id.clone()}>{"Name"}</label>
+///             <input {id} />
+///         </>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_id() -> String {
+    let id = use_root_scoped_id();
+
+    format!("bounce-{}", id.value)
+}
+
+/// Returns the current time as a duration since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now() -> Duration {
+    Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+}
+
+/// Returns the current time as a duration since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now() -> Duration {
+    use std::time::SystemTime;
+
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
 }
 
 pub(crate) struct Listener {
@@ -43,7 +141,7 @@ impl fmt::Debug for Listener {
 
 pub(crate) type ListenerVec<T> = Vec<Weak<Callback<Rc<T>>>>;
 
-pub(crate) fn notify_listeners<T>(listeners: Rc<RefCell<ListenerVec<T>>>, val: Rc<T>) {
+pub(crate) fn notify_listeners<T: 'static>(listeners: Rc<RefCell<ListenerVec<T>>>, val: Rc<T>) {
     let callables = {
         let mut callbacks_ref = listeners.borrow_mut();
 
@@ -65,7 +163,150 @@ pub(crate) fn notify_listeners<T>(listeners: Rc<RefCell<ListenerVec<T>>>, val: R
         callbacks
     };
 
-    for callback in callables {
-        callback.emit(val.clone())
+    if NOTIFICATION_BATCH.with(|m| m.borrow().depth == 0) {
+        #[cfg(feature = "devtools")]
+        crate::devtools::record_notified(callables.len());
+
+        for callback in callables {
+            callback.emit(val.clone())
+        }
+
+        return;
+    }
+
+    NOTIFICATION_BATCH.with(|m| {
+        let mut batch = m.borrow_mut();
+
+        for callback in callables {
+            let val = val.clone();
+            let key = Rc::as_ptr(&callback) as *const () as usize;
+            let notify: Box<dyn FnOnce()> = Box::new(move || callback.emit(val));
+
+            #[cfg(feature = "devtools")]
+            let notify = crate::devtools::preserve_change_chain(notify);
+
+            batch.push(key, notify);
+        }
+    });
+}
+
+/// Listener notifications that are deferred until the outermost batch completes.
+#[derive(Default)]
+struct NotificationBatch {
+    depth: usize,
+    positions: HashMap<usize, usize>,
+    pending: Vec<Box<dyn FnOnce()>>,
+}
+
+impl NotificationBatch {
+    /// Queues a notification.
+    ///
+    /// If the same listener has already been queued, the previous notification is replaced so
+    /// that each listener is only notified once with the latest value.
+    fn push(&mut self, key: usize, notify: Box<dyn FnOnce()>) {
+        match self.positions.get(&key) {
+            Some(m) => self.pending[*m] = notify,
+            None => {
+                // Only listeners that are notified are counted, not replaced notifications.
+                #[cfg(feature = "devtools")]
+                crate::devtools::record_notified(1);
+
+                self.positions.insert(key, self.pending.len());
+                self.pending.push(notify);
+            }
+        }
+    }
+
+    fn take(&mut self) -> Vec<Box<dyn FnOnce()>> {
+        self.positions.clear();
+        std::mem::take(&mut self.pending)
+    }
+}
+
+thread_local! {
+    static NOTIFICATION_BATCH: RefCell<NotificationBatch> = RefCell::default();
+}
+
+struct BatchGuard;
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        let depth = NOTIFICATION_BATCH.with(|m| {
+            let mut batch = m.borrow_mut();
+            batch.depth -= 1;
+            batch.depth
+        });
+
+        if depth > 0 || std::thread::panicking() {
+            return;
+        }
+
+        // Listeners may update other states, notifications of these updates are sent
+        // immediately as the batch has completed.
+        let pending = NOTIFICATION_BATCH.with(|m| m.borrow_mut().take());
+        for notify in pending {
+            notify();
+        }
     }
 }
+
+/// Runs a function with listener notifications batched.
+///
+/// Listeners are notified after the outermost batch completes, so that states are updated
+/// before any listener is notified and each listener is notified at most once.
+pub(crate) fn batch_notifications<R>(f: impl FnOnce() -> R) -> R {
+    NOTIFICATION_BATCH.with(|m| m.borrow_mut().depth += 1);
+    let _guard = BatchGuard;
+
+    f()
+}
+
+/// Runs a function with updates batched.
+///
+/// Listeners of atoms and slices written in `f`, including writes made by callbacks that `f`
+/// calls synchronously, are notified after `f` returns. Each listener is notified at most once
+/// with the latest value, so a DOM event that updates multiple atoms does not cause components
+/// and selectors to observe the states in between.
+///
+/// Calls can be nested, listeners are notified when the outermost call returns. Observers
+/// (see [`Observed`](crate::Observed)) are still notified when each state is written.
+///
+/// # Note
+///
+/// This is a free function rather than a method of the root: the state of a root is not
+/// exposed outside of bounce, and a batch applies to the writes made to all roots on the
+/// current thread, so it does not need a root to be passed in. Event handlers can call it
+/// without obtaining a handle to the root first.
+///
+/// # Example
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use bounce::prelude::*;
+/// use bounce::with_batched_updates;
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Query(String);
+///
+/// #[derive(Atom, PartialEq, Default)]
+/// struct Page(u64);
+///
+/// #[function_component(Search)]
+/// fn search() -> Html {
+///     let set_query = use_atom_setter::<Query>();
+///     let set_page = use_atom_setter::<Page>();
+///
+///     let onclick = Callback::from(move |_| {
+///         // Components never observe the new query with the page of the previous query.
+///         with_batched_updates(|| {
+///             set_query(Query("bounce".into()));
+///             set_page(Page(0));
+///         });
+///     });
+///
+///     html! { <button {onclick}>{"Search"}</button> }
+/// }
+/// ```
+pub fn with_batched_updates<R>(f: impl FnOnce() -> R) -> R {
+    batch_notifications(f)
+}
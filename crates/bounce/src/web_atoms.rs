@@ -0,0 +1,279 @@
+//! Atoms that reflect common browser states.
+//!
+//! This module provides the following atoms:
+//!
+//! - [`OnlineStatus`]: whether the browser is online.
+//! - [`WindowSize`]: the inner size of the window, with a [`Breakpoint`] selector derived from it.
+//! - [`ColorScheme`]: the colour scheme preferred by the user.
+//! - [`PageVisibility`]: whether the page is visible.
+//!
+//! Each atom is created with the current browser state and kept up to date by the event listeners
+//! of a [`WebAtoms`] component registered under the [`BounceRoot`](crate::BounceRoot). They can
+//! be used with the standard hooks like any other atom or selector.
+//!
+//! When the browser state is not available (e.g.: during server-side rendering), atoms are
+//! created with a default value.
+//!
+//! # Example
+//!
+//! ```
+//! # use yew::prelude::*;
+//! # use bounce::prelude::*;
+//! # use bounce::BounceRoot;
+//! use bounce::web_atoms::{Breakpoint, OnlineStatus, WebAtoms};
+//!
+//! #[function_component(Banner)]
+//! fn banner() -> Html {
+//!     let status = use_atom_value::<OnlineStatus>();
+//!     // Only re-rendered when the window crosses a breakpoint.
+//!     let breakpoint = use_selector_value::<Breakpoint>();
+//!
+//!     if status.online {
+//!         return Html::default();
+//!     }
+//!
+//!     match *breakpoint {
+//!         Breakpoint::Mobile => html! { <div>{"Offline"}</div> },
+//!         Breakpoint::Tablet | Breakpoint::Desktop => {
+//!             html! { <div>{"You are offline, changes will be saved when you reconnect."}</div> }
+//!         }
+//!     }
+//! }
+//!
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     html! {
+//!         <BounceRoot>
+//!             <WebAtoms />
+//!             <Banner />
+//!         </BounceRoot>
+//!     }
+//! }
+//! ```
+
+use std::rc::Rc;
+
+use gloo::events::EventListener;
+use wasm_bindgen::prelude::*;
+use web_sys::{MediaQueryList, VisibilityState, Window};
+use yew::prelude::*;
+
+use crate::root_state::{BounceRootState, BounceStates};
+use crate::states::atom::{Atom, AtomSlice};
+use crate::states::selector::Selector;
+use crate::states::slice::SliceState;
+
+const PREFERS_DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+
+fn window() -> Option<Window> {
+    web_sys::window()
+}
+
+/// Whether the browser is online.
+///
+/// Defaults to online when the browser state is not available.
+#[derive(Atom, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnlineStatus {
+    /// `true` if the browser is online.
+    pub online: bool,
+}
+
+impl Default for OnlineStatus {
+    fn default() -> Self {
+        let online = window().map(|m| m.navigator().on_line()).unwrap_or(true);
+
+        Self { online }
+    }
+}
+
+/// The inner size of the window in CSS pixels.
+///
+/// Defaults to `0 x 0` when the browser state is not available.
+#[derive(Atom, Debug, Clone, Copy, PartialEq)]
+pub struct WindowSize {
+    /// The inner width of the window.
+    pub width: f64,
+    /// The inner height of the window.
+    pub height: f64,
+}
+
+impl Default for WindowSize {
+    fn default() -> Self {
+        let size = |m: Result<JsValue, JsValue>| m.ok().and_then(|m| m.as_f64()).unwrap_or(0.0);
+
+        match window() {
+            Some(m) => Self {
+                width: size(m.inner_width()),
+                height: size(m.inner_height()),
+            },
+            None => Self {
+                width: 0.0,
+                height: 0.0,
+            },
+        }
+    }
+}
+
+/// The breakpoint of the current window width.
+///
+/// | Breakpoint | Window Width       |
+/// | ---------- | ------------------ |
+/// | `Mobile`   | less than 768px    |
+/// | `Tablet`   | 768px to 1023px    |
+/// | `Desktop`  | 1024px and above   |
+///
+/// This is a selector derived from [`WindowSize`], components that use it are only re-rendered
+/// when the window crosses a breakpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Breakpoint {
+    /// The window is less than 768px wide.
+    Mobile,
+    /// The window is between 768px and 1023px wide.
+    Tablet,
+    /// The window is at least 1024px wide.
+    Desktop,
+}
+
+impl Breakpoint {
+    /// Returns the breakpoint of a window width.
+    pub fn from_width(width: f64) -> Self {
+        if width >= 1024.0 {
+            Self::Desktop
+        } else if width >= 768.0 {
+            Self::Tablet
+        } else {
+            Self::Mobile
+        }
+    }
+}
+
+impl Selector for Breakpoint {
+    fn select(states: &BounceStates) -> Rc<Self> {
+        let size = states.get_atom_value::<WindowSize>();
+
+        Self::from_width(size.width).into()
+    }
+}
+
+/// The colour scheme preferred by the user.
+///
+/// Defaults to [`Light`](Self::Light) when the browser state is not available.
+#[derive(Atom, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    /// The user prefers a light colour scheme or has not expressed a preference.
+    Light,
+    /// The user prefers a dark colour scheme.
+    Dark,
+}
+
+impl ColorScheme {
+    fn media_query() -> Option<MediaQueryList> {
+        window().and_then(|m| m.match_media(PREFERS_DARK_QUERY).ok().flatten())
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        match Self::media_query() {
+            Some(m) if m.matches() => Self::Dark,
+            _ => Self::Light,
+        }
+    }
+}
+
+/// Whether the page is visible.
+///
+/// A page is hidden when it is in a background tab or the window is minimised.
+///
+/// Defaults to visible when the browser state is not available.
+#[derive(Atom, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageVisibility {
+    /// `true` if the page is visible.
+    pub visible: bool,
+}
+
+impl Default for PageVisibility {
+    fn default() -> Self {
+        let visible = window()
+            .and_then(|m| m.document())
+            .map(|m| m.visibility_state() != VisibilityState::Hidden)
+            .unwrap_or(true);
+
+        Self { visible }
+    }
+}
+
+/// Sets the value of an atom to the current browser state.
+fn sync<T>(root: &BounceRootState, value: T)
+where
+    T: Atom + 'static,
+{
+    root.get_state::<SliceState<AtomSlice<T>>>().dispatch(value);
+}
+
+/// Creates an event listener that synchronises the browser state to the atoms.
+fn listen<F>(target: &web_sys::EventTarget, event_type: &'static str, f: F) -> EventListener
+where
+    F: 'static + Fn(),
+{
+    EventListener::new(target, event_type, move |_| f())
+}
+
+fn sync_all(root: &BounceRootState) {
+    sync(root, OnlineStatus::default());
+    sync(root, WindowSize::default());
+    sync(root, ColorScheme::default());
+    sync(root, PageVisibility::default());
+}
+
+/// A component that keeps the atoms of this module up to date.
+///
+/// The event listeners are registered when the component is mounted and removed when it is
+/// unmounted. Only 1 `WebAtoms` should be registered per [`BounceRoot`](crate::BounceRoot).
+///
+/// See the [module documentation](crate::web_atoms) for an example.
+#[function_component(WebAtoms)]
+pub fn web_atoms() -> Html {
+    let root = use_context::<BounceRootState>().expect_throw("No bounce root found.");
+
+    use_effect_with(root, |root| {
+        let mut listeners = Vec::new();
+
+        if let Some(window) = window() {
+            // The browser state may have changed before the component is mounted.
+            sync_all(root);
+
+            for event_type in ["online", "offline"] {
+                let root = root.clone();
+                listeners.push(listen(&window, event_type, move || {
+                    sync(&root, OnlineStatus::default())
+                }));
+            }
+
+            {
+                let root = root.clone();
+                listeners.push(listen(&window, "resize", move || {
+                    sync(&root, WindowSize::default())
+                }));
+            }
+
+            if let Some(m) = ColorScheme::media_query() {
+                let root = root.clone();
+                listeners.push(listen(&m, "change", move || {
+                    sync(&root, ColorScheme::default())
+                }));
+            }
+
+            if let Some(m) = window.document() {
+                let root = root.clone();
+                listeners.push(listen(&m, "visibilitychange", move || {
+                    sync(&root, PageVisibility::default())
+                }));
+            }
+        }
+
+        move || drop(listeners)
+    });
+
+    Html::default()
+}
@@ -0,0 +1,107 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_artifact_scoped() {
+    #[derive(PartialEq)]
+    struct Crumb(&'static str);
+
+    #[derive(Properties, PartialEq)]
+    struct CrumbsProps {
+        id: AttrValue,
+    }
+
+    #[function_component(Crumbs)]
+    fn crumbs(props: &CrumbsProps) -> Html {
+        let crumbs = use_artifacts_scoped::<Crumb>();
+        let crumbs = crumbs.iter().map(|m| m.0).collect::<Vec<_>>().join(",");
+
+        html! { <div id={props.id.clone()}>{crumbs}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <ArtifactScope>
+                    <Crumbs id="a" />
+                    <Artifact<Crumb> value={Rc::new(Crumb("a1"))} />
+                    <Artifact<Crumb> value={Rc::new(Crumb("a2"))} />
+                </ArtifactScope>
+                <ArtifactScope>
+                    <Crumbs id="b" />
+                    <Artifact<Crumb> value={Rc::new(Crumb("b1"))} />
+                </ArtifactScope>
+                <Crumbs id="all" />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "a1,a2");
+
+    let s = get_text_content("#b").await;
+    assert_eq!(s, "b1");
+
+    let s = get_text_content("#all").await;
+    assert_eq!(s, "a1,a2,b1");
+}
+
+#[test]
+async fn test_artifact_derive_apply() {
+    #[derive(PartialEq, Artifact)]
+    #[bounce(apply = apply_heading)]
+    struct Heading(&'static str);
+
+    fn apply_heading(values: &[Rc<Heading>]) {
+        let heading = values.iter().map(|m| m.0).collect::<Vec<_>>().join(",");
+
+        document()
+            .query_selector("#heading")
+            .unwrap()
+            .unwrap()
+            .set_text_content(Some(&heading));
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <div id="heading"></div>
+                <ArtifactApplier<Heading> />
+                {Heading("a").into_artifact()}
+                {Heading("b").into_artifact()}
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#heading").await;
+    assert_eq!(s, "a,b");
+}
@@ -0,0 +1,111 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{BounceRoot, Clock, ManualClock, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[derive(Atom, PartialEq, Default)]
+struct State {
+    inner: u32,
+}
+
+type StateSetter = Rc<dyn Fn(State)>;
+
+thread_local! {
+    static CLOCK: ManualClock = ManualClock::new();
+    static EXPIRY: RefCell<Option<UseAtomWithExpiryHandle<State>>> = RefCell::default();
+    static SETTER: RefCell<Option<StateSetter>> = RefCell::default();
+}
+
+fn advance(duration: Duration) {
+    CLOCK.with(|m| m.advance(duration));
+}
+
+fn set_with_expiry(value: u32) {
+    EXPIRY
+        .with(|m| m.borrow().clone())
+        .unwrap()
+        .set(State { inner: value });
+}
+
+fn set(value: u32) {
+    SETTER.with(|m| m.borrow().clone()).unwrap()(State { inner: value });
+}
+
+#[function_component(Comp)]
+fn comp() -> Html {
+    let state = use_atom_with_expiry::<State>(Duration::from_secs(60));
+    let setter = use_atom_setter::<State>();
+
+    EXPIRY.with(|m| *m.borrow_mut() = Some(state.clone()));
+    SETTER.with(|m| *m.borrow_mut() = Some(setter));
+
+    html! { <div id="state">{state.inner}</div> }
+}
+
+#[function_component(Root)]
+fn root() -> Html {
+    let get_services = Callback::from(|_| {
+        let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+        Services::new().provide_rc(clock)
+    });
+
+    html! {
+        <BounceRoot {get_services}>
+            <Comp />
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_atom_expiry_timers() {
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+
+    // Setting the atom with expiry again restarts the timer.
+    set_with_expiry(1);
+    advance(Duration::from_secs(30));
+    set_with_expiry(2);
+    advance(Duration::from_secs(30));
+    assert_eq!(get_text_content("#state").await, "2");
+
+    advance(Duration::from_secs(30));
+    assert_eq!(get_text_content("#state").await, "0");
+
+    // Setting the atom with another handle cancels the timer.
+    set_with_expiry(3);
+    advance(Duration::from_secs(30));
+    set(4);
+    advance(Duration::from_secs(60));
+    assert_eq!(get_text_content("#state").await, "4");
+
+    // Resetting the atom cancels the timer.
+    set_with_expiry(5);
+    EXPIRY.with(|m| m.borrow().clone()).unwrap().reset();
+    assert_eq!(get_text_content("#state").await, "0");
+
+    set(6);
+    advance(Duration::from_secs(60));
+    assert_eq!(get_text_content("#state").await, "6");
+}
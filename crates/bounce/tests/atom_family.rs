@@ -0,0 +1,215 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+async fn click<S: AsRef<str>>(selector: S) {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+#[derive(PartialEq)]
+struct Draft {
+    text: String,
+}
+
+impl AtomFamily for Draft {
+    type Key = u64;
+
+    fn create(key: &u64) -> Self {
+        Self {
+            text: format!("empty {}", key),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+struct EvictedDraft {
+    text: String,
+}
+
+impl AtomFamily for EvictedDraft {
+    type Key = u64;
+
+    const EVICT_UNUSED: bool = true;
+
+    fn create(key: &u64) -> Self {
+        Self {
+            text: format!("empty {}", key),
+        }
+    }
+}
+
+#[test]
+async fn test_atom_family() {
+    thread_local! {
+        static RENDERS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct EditorProps {
+        id: u64,
+    }
+
+    #[function_component(Editor)]
+    fn editor(props: &EditorProps) -> Html {
+        let draft = use_atom_family::<Draft>(props.id);
+
+        let onclick = {
+            let draft = draft.clone();
+            let id = props.id;
+            Callback::from(move |_| {
+                draft.set(Draft {
+                    text: format!("edited {}", id),
+                })
+            })
+        };
+
+        html! {
+            <div>
+                <div id={format!("draft-{}", props.id)}>{&draft.text}</div>
+                <button id={format!("edit-{}", props.id)} {onclick}>{"Edit"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Preview)]
+    fn preview() -> Html {
+        RENDERS.with(|m| m.set(m.get() + 1));
+        let draft = use_atom_family_value::<Draft>(2);
+
+        html! { <div id="preview">{&draft.text}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Editor id={1} />
+                <Editor id={2} />
+                <Preview />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#draft-1").await;
+    assert_eq!(s, "empty 1");
+    let s = get_text_content("#preview").await;
+    assert_eq!(s, "empty 2");
+    let renders = RENDERS.with(|m| m.get());
+
+    // Subscribers of other keys are not re-rendered.
+    click("#edit-1").await;
+    let s = get_text_content("#draft-1").await;
+    assert_eq!(s, "edited 1");
+    assert_eq!(RENDERS.with(|m| m.get()), renders);
+
+    click("#edit-2").await;
+    let s = get_text_content("#preview").await;
+    assert_eq!(s, "edited 2");
+}
+
+#[test]
+async fn test_atom_family_evict_unused() {
+    #[derive(Properties, PartialEq)]
+    struct EditorProps {
+        id: u64,
+    }
+
+    #[function_component(Editor)]
+    fn editor(props: &EditorProps) -> Html {
+        let draft = use_atom_family::<Draft>(props.id);
+        let evicted = use_atom_family::<EvictedDraft>(props.id);
+
+        let onclick = {
+            let draft = draft.clone();
+            let evicted = evicted.clone();
+            Callback::from(move |_| {
+                draft.set(Draft {
+                    text: "edited".to_string(),
+                });
+                evicted.set(EvictedDraft {
+                    text: "edited".to_string(),
+                });
+            })
+        };
+
+        html! {
+            <div>
+                <div id="draft">{format!("{} {}", draft.text, evicted.text)}</div>
+                <button id="edit" {onclick}>{"Edit"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let id = use_state(|| 1);
+
+        let onclick = {
+            let id = id.clone();
+            Callback::from(move |_| id.set(if *id == 1 { 2 } else { 1 }))
+        };
+
+        html! {
+            <div>
+                <Editor key={*id} id={*id} />
+                <button id="switch" {onclick}>{"Switch"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <App />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    click("#edit").await;
+    let s = get_text_content("#draft").await;
+    assert_eq!(s, "edited edited");
+
+    click("#switch").await;
+    let s = get_text_content("#draft").await;
+    assert_eq!(s, "empty 2 empty 2");
+
+    // The value of the family that evicts unused keys is created again.
+    click("#switch").await;
+    let s = get_text_content("#draft").await;
+    assert_eq!(s, "edited empty 1");
+}
@@ -0,0 +1,124 @@
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_atom_list() {
+    #[derive(Debug, PartialEq)]
+    struct Todo(&'static str);
+
+    thread_local! {
+        static TODOS: RefCell<Option<UseAtomListHandle<Todo>>> = RefCell::default();
+        static LIST_RENDERS: Cell<u32> = const { Cell::new(0) };
+        static ROW_RENDERS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn todos() -> UseAtomListHandle<Todo> {
+        TODOS.with(|m| m.borrow().clone()).unwrap()
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct RowProps {
+        todo_key: AtomListKey,
+    }
+
+    #[function_component(Row)]
+    fn row(props: &RowProps) -> Html {
+        ROW_RENDERS.with(|m| m.set(m.get() + 1));
+
+        match use_atom_list_item::<Todo>(props.todo_key) {
+            Some(m) => html! { <li>{m.0}</li> },
+            None => Html::default(),
+        }
+    }
+
+    #[function_component(List)]
+    fn list() -> Html {
+        LIST_RENDERS.with(|m| m.set(m.get() + 1));
+
+        let todos = use_atom_list::<Todo>();
+        TODOS.with(|m| *m.borrow_mut() = Some(todos.clone()));
+
+        html! {
+            <ul id="list">
+                {for todos.keys().iter().map(|m| html! { <Row key={format!("{m:?}")} todo_key={*m} /> })}
+            </ul>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <List />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#list").await;
+    assert_eq!(s, "");
+
+    let a = todos().push(Todo("a"));
+    let b = todos().push(Todo("b"));
+    todos().insert(0, Todo("c"));
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#list").await;
+    assert_eq!(s, "cab");
+    assert_eq!(todos().len(), 3);
+
+    // Setting an item only re-renders the row of the item.
+    let list_renders = LIST_RENDERS.with(|m| m.get());
+    let row_renders = ROW_RENDERS.with(|m| m.get());
+    todos().set(a, Todo("A"));
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#list").await;
+    assert_eq!(s, "cAb");
+    assert_eq!(LIST_RENDERS.with(|m| m.get()), list_renders);
+    assert_eq!(ROW_RENDERS.with(|m| m.get()), row_renders + 1);
+
+    // Setting an equal value does not re-render.
+    todos().set(a, Todo("A"));
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(ROW_RENDERS.with(|m| m.get()), row_renders + 1);
+
+    // Keys do not change when items are moved.
+    todos().move_item(2, 0);
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#list").await;
+    assert_eq!(s, "bcA");
+    assert_eq!(todos().keys()[0], b);
+
+    let removed = todos().remove(b);
+    assert_eq!(removed.as_deref(), Some(&Todo("b")));
+    assert!(todos().get(b).is_none());
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#list").await;
+    assert_eq!(s, "cA");
+    assert_eq!(todos().get(a).as_deref(), Some(&Todo("A")));
+}
@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_atom_transparent() {
+    #[derive(PartialEq, Default, Atom)]
+    #[bounce(transparent)]
+    struct Username(String);
+
+    #[derive(PartialEq, Default, Atom)]
+    #[bounce(transparent)]
+    struct FontSize {
+        inner: u32,
+    }
+
+    type Setter<T> = Rc<dyn Fn(T)>;
+
+    thread_local! {
+        static SET_USERNAME: RefCell<Option<Setter<Username>>> = RefCell::default();
+    }
+
+    #[function_component(Controls)]
+    fn controls() -> Html {
+        let set_username = use_atom_setter::<Username>();
+        SET_USERNAME.with(|m| *m.borrow_mut() = Some(set_username));
+
+        Html::default()
+    }
+
+    #[function_component(Reader)]
+    fn reader() -> Html {
+        let username = use_atom_value::<Username>();
+        let inner: String = use_atom_value_inner::<Username>();
+        let font_size: u32 = use_atom_value_inner::<FontSize>();
+
+        html! {
+            <>
+                // Display is forwarded to the inner value.
+                <div id="display">{username.to_string()}</div>
+                // Deref is forwarded to the inner value.
+                <div id="len">{username.len()}</div>
+                <div id="inner">{inner}</div>
+                <div id="font-size">{font_size}</div>
+            </>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Controls />
+                <Reader />
+            </BounceRoot>
+        }
+    }
+
+    assert_eq!(FontSize::from(12).into_inner(), 12);
+    assert_eq!(*FontSize::from(12).inner(), 12);
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    assert_eq!(get_text_content("#display").await, "");
+    assert_eq!(get_text_content("#len").await, "0");
+    assert_eq!(get_text_content("#font-size").await, "0");
+
+    SET_USERNAME.with(|m| m.borrow().clone()).unwrap()(Username::from("Jane".to_string()));
+
+    assert_eq!(get_text_content("#display").await, "Jane");
+    assert_eq!(get_text_content("#len").await, "4");
+    assert_eq!(get_text_content("#inner").await, "Jane");
+}
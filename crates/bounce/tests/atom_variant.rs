@@ -0,0 +1,110 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_atom_variant() {
+    #[derive(PartialEq, Default, Atom)]
+    #[bounce(variants)]
+    enum Status {
+        #[default]
+        Idle,
+        Loading(u32),
+        Failed,
+    }
+
+    type StatusSetter = Rc<dyn Fn(Status)>;
+
+    thread_local! {
+        static SET_STATUS: RefCell<Option<StatusSetter>> = RefCell::default();
+        static VARIANT_RENDERS: Cell<u32> = const { Cell::new(0) };
+        static FAILED_RENDERS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn set_status(status: Status) {
+        SET_STATUS.with(|m| m.borrow().clone()).unwrap()(status);
+    }
+
+    #[function_component(Setter)]
+    fn setter() -> Html {
+        let set_status = use_atom_setter::<Status>();
+        SET_STATUS.with(|m| *m.borrow_mut() = Some(set_status));
+
+        Html::default()
+    }
+
+    #[function_component(VariantView)]
+    fn variant_view() -> Html {
+        VARIANT_RENDERS.with(|m| m.set(m.get() + 1));
+
+        let variant = use_atom_variant::<Status>();
+
+        html! { <div id="variant">{format!("{variant:?}")}</div> }
+    }
+
+    #[function_component(FailedView)]
+    fn failed_view() -> Html {
+        FAILED_RENDERS.with(|m| m.set(m.get() + 1));
+
+        let failed = use_atom_is_variant::<Status>(StatusVariant::Failed);
+
+        html! { <div id="failed">{failed.to_string()}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Setter />
+                <VariantView />
+                <FailedView />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    assert_eq!(get_text_content("#variant").await, "Idle");
+    assert_eq!(get_text_content("#failed").await, "false");
+    assert_eq!(VARIANT_RENDERS.with(|m| m.get()), 1);
+    assert_eq!(FAILED_RENDERS.with(|m| m.get()), 1);
+
+    set_status(Status::Loading(10));
+    assert_eq!(get_text_content("#variant").await, "Loading");
+    assert_eq!(VARIANT_RENDERS.with(|m| m.get()), 2);
+    assert_eq!(FAILED_RENDERS.with(|m| m.get()), 1);
+
+    // The variant does not change, so neither component is rendered again.
+    set_status(Status::Loading(50));
+    assert_eq!(get_text_content("#variant").await, "Loading");
+    assert_eq!(VARIANT_RENDERS.with(|m| m.get()), 2);
+    assert_eq!(FAILED_RENDERS.with(|m| m.get()), 1);
+
+    set_status(Status::Failed);
+    assert_eq!(get_text_content("#variant").await, "Failed");
+    assert_eq!(get_text_content("#failed").await, "true");
+    assert_eq!(VARIANT_RENDERS.with(|m| m.get()), 3);
+    assert_eq!(FAILED_RENDERS.with(|m| m.get()), 2);
+}
@@ -0,0 +1,324 @@
+#![cfg(feature = "auth")]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::auth::{use_session, with_auth, AuthError, Session, TokenRefresher, UseSessionHandle};
+use bounce::prelude::*;
+use bounce::query::{
+    use_mutation, use_query, use_query_value, Mutation, MutationResult, ProgressSink, Query,
+    QueryResult,
+};
+use bounce::{BounceRoot, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+enum ApiError {
+    #[error("unauthorized")]
+    Unauthorized,
+}
+
+impl AuthError for ApiError {
+    fn is_auth_error(&self) -> bool {
+        matches!(self, Self::Unauthorized)
+    }
+
+    fn unauthenticated() -> Self {
+        Self::Unauthorized
+    }
+}
+
+/// A query that returns the access token it has been run with.
+///
+/// Requests made with the token `"expired"` fail with an authentication error.
+#[derive(Debug, PartialEq)]
+struct TokenQuery {
+    token: String,
+}
+
+#[async_trait(?Send)]
+impl Query for TokenQuery {
+    type Input = u64;
+    type Error = ApiError;
+
+    async fn query(states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+        with_auth(states, |session| async move {
+            sleep(Duration::from_millis(10)).await;
+
+            if session.access_token == "expired" {
+                return Err(ApiError::Unauthorized);
+            }
+
+            Ok(TokenQuery {
+                token: session.access_token.clone(),
+            }
+            .into())
+        })
+        .await
+    }
+}
+
+fn session(access_token: &str) -> Session {
+    Session {
+        access_token: access_token.into(),
+        refresh_token: Some("refresh-token".into()),
+    }
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<UseSessionHandle>> = RefCell::default();
+}
+
+fn session_handle() -> UseSessionHandle {
+    SESSION.with(|m| m.borrow().clone()).unwrap()
+}
+
+#[function_component(SessionView)]
+fn session_view() -> Html {
+    let session = use_session();
+    SESSION.with(|m| *m.borrow_mut() = Some(session.clone()));
+
+    let token = session
+        .session()
+        .map(|m| m.access_token.clone())
+        .unwrap_or_else(|| "logged out".into());
+
+    html! { <div id="session">{token}</div> }
+}
+
+#[derive(Properties, PartialEq)]
+struct AfterLoginProps {
+    children: Children,
+}
+
+/// Renders its children once a session has been started, they stay mounted after logout.
+#[function_component(AfterLogin)]
+fn after_login(props: &AfterLoginProps) -> Html {
+    let session = use_session();
+    let logged_in = use_mut_ref(|| false);
+
+    if session.is_authenticated() {
+        *logged_in.borrow_mut() = true;
+    }
+
+    if !*logged_in.borrow() {
+        return Html::default();
+    }
+
+    html! { <>{props.children.clone()}</> }
+}
+
+#[derive(Properties, PartialEq)]
+struct TokenViewProps {
+    input: u64,
+}
+
+#[function_component(TokenView)]
+fn token_view(props: &TokenViewProps) -> Html {
+    let token = use_query_value::<TokenQuery>(props.input.into());
+
+    let content = match token.result() {
+        None => "loading".to_string(),
+        Some(Ok(m)) => m.token.clone(),
+        Some(Err(_)) => "unauthorized".to_string(),
+    };
+
+    html! { <div id={format!("token-{}", props.input)}>{content}</div> }
+}
+
+#[test]
+async fn test_logout_clears_states() {
+    #[derive(Debug, PartialEq)]
+    struct SaveMutation;
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveMutation {
+        type Input = ();
+        type Error = ApiError;
+
+        async fn run(
+            states: &BounceStates,
+            _input: Rc<()>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            with_auth(states, |_session| async { Ok(SaveMutation.into()) }).await
+        }
+    }
+
+    #[function_component(SuspendedTokenView)]
+    fn suspended_token_view() -> HtmlResult {
+        let token = use_query::<TokenQuery>(0.into())?;
+
+        let content = match token.as_ref() {
+            Ok(m) => m.token.clone(),
+            Err(_) => "unauthorized".to_string(),
+        };
+
+        Ok(html! { <div id="token">{content}</div> })
+    }
+
+    #[function_component(SaveView)]
+    fn save_view() -> Html {
+        let save = use_mutation::<SaveMutation>();
+
+        {
+            let save = save.clone();
+            use_effect_with((), move |_| {
+                yew::platform::spawn_local(async move {
+                    let _result = save.run(()).await;
+                });
+
+                || {}
+            });
+        }
+
+        let content = match save.result() {
+            None => "idle",
+            Some(Ok(_)) => "saved",
+            Some(Err(_)) => "unauthorized",
+        };
+
+        html! { <div id="save">{content}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let fallback = html! { <div id="token">{"loading"}</div> };
+
+        html! {
+            <BounceRoot>
+                <SessionView />
+                <AfterLogin>
+                    <SaveView />
+                    <Suspense {fallback}>
+                        <SuspendedTokenView />
+                    </Suspense>
+                </AfterLogin>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+    session_handle().login(session("token"));
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#session").await, "token");
+    assert_eq!(get_text_content("#token").await, "token");
+    assert_eq!(get_text_content("#save").await, "saved");
+
+    session_handle().logout();
+
+    // The suspended query is started again without a session.
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#session").await, "logged out");
+    assert_eq!(get_text_content("#token").await, "unauthorized");
+    assert_eq!(get_text_content("#save").await, "idle");
+}
+
+#[test]
+async fn test_concurrent_requests_share_refresh() {
+    thread_local! {
+        static REFRESHES: Cell<u32> = const { Cell::new(0) };
+    }
+
+    struct Refresher;
+
+    #[async_trait(?Send)]
+    impl TokenRefresher for Refresher {
+        async fn refresh(&self, _session: Rc<Session>) -> Option<Session> {
+            REFRESHES.with(|m| m.set(m.get() + 1));
+            sleep(Duration::from_millis(10)).await;
+
+            Some(session("fresh"))
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide_rc::<dyn TokenRefresher>(Rc::new(Refresher))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <SessionView />
+                <AfterLogin>
+                    <TokenView input={1} />
+                    <TokenView input={2} />
+                </AfterLogin>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+    session_handle().login(session("expired"));
+
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(get_text_content("#token-1").await, "fresh");
+    assert_eq!(get_text_content("#token-2").await, "fresh");
+    assert_eq!(get_text_content("#session").await, "fresh");
+    assert_eq!(REFRESHES.with(|m| m.get()), 1);
+}
+
+#[test]
+async fn test_failed_refresh_logs_out() {
+    struct Refresher;
+
+    #[async_trait(?Send)]
+    impl TokenRefresher for Refresher {
+        async fn refresh(&self, _session: Rc<Session>) -> Option<Session> {
+            None
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide_rc::<dyn TokenRefresher>(Rc::new(Refresher))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <SessionView />
+                <AfterLogin>
+                    <TokenView input={1} />
+                </AfterLogin>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+    session_handle().login(session("expired"));
+
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(get_text_content("#session").await, "logged out");
+    assert_eq!(get_text_content("#token-1").await, "unauthorized");
+}
@@ -0,0 +1,120 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{BounceRoot, Clock, ManualClock, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static CLOCK: ManualClock = ManualClock::new();
+    static INITS: Cell<u32> = const { Cell::new(0) };
+}
+
+#[derive(Properties, PartialEq)]
+struct Props {
+    key_: Rc<String>,
+    // Changes to this property render the components without changing the key.
+    renders: u32,
+}
+
+#[derive(Properties, PartialEq)]
+struct CompProps {
+    id: &'static str,
+    key_: Rc<String>,
+    renders: u32,
+}
+
+#[function_component(Comp)]
+fn comp(props: &CompProps) -> Html {
+    let value = use_cached_value(props.key_.clone(), Duration::from_secs(60), |key| {
+        INITS.with(|m| m.set(m.get() + 1));
+        format!("{}-{}", key, INITS.with(|m| m.get()))
+    });
+
+    html! { <div id={props.id}>{value.as_str()}{" "}{props.renders}</div> }
+}
+
+#[function_component(Root)]
+fn root(props: &Props) -> Html {
+    let get_services = Callback::from(|_| {
+        let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+        Services::new().provide_rc(clock)
+    });
+
+    html! {
+        <BounceRoot {get_services}>
+            <Comp id="a" key_={props.key_.clone()} renders={props.renders} />
+            <Comp id="b" key_={props.key_.clone()} renders={props.renders} />
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_cached_value() {
+    fn inits() -> u32 {
+        INITS.with(|m| m.get())
+    }
+
+    let mut handle = yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        Props {
+            key_: Rc::new("x".into()),
+            renders: 0,
+        },
+    )
+    .render();
+
+    // The value is computed once and shared between components.
+    assert_eq!(get_text_content("#a").await, "x-1 0");
+    assert_eq!(get_text_content("#b").await, "x-1 0");
+    assert_eq!(inits(), 1);
+
+    // A value is computed for a key that is not cached.
+    handle.update(Props {
+        key_: Rc::new("y".into()),
+        renders: 1,
+    });
+    assert_eq!(get_text_content("#a").await, "y-2 1");
+    assert_eq!(get_text_content("#b").await, "y-2 1");
+    assert_eq!(inits(), 2);
+
+    // The cached value is returned for a key that is cached.
+    CLOCK.with(|m| m.advance(Duration::from_secs(59)));
+    handle.update(Props {
+        key_: Rc::new("x".into()),
+        renders: 2,
+    });
+    assert_eq!(get_text_content("#a").await, "x-1 2");
+    assert_eq!(get_text_content("#b").await, "x-1 2");
+    assert_eq!(inits(), 2);
+
+    // The value is computed again once the cached value expires.
+    CLOCK.with(|m| m.advance(Duration::from_secs(1)));
+    handle.update(Props {
+        key_: Rc::new("x".into()),
+        renders: 3,
+    });
+    assert_eq!(get_text_content("#a").await, "x-3 3");
+    assert_eq!(get_text_content("#b").await, "x-3 3");
+    assert_eq!(inits(), 3);
+
+    handle.destroy();
+}
@@ -0,0 +1,82 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{BounceRoot, Clock, ManualClock, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static CLOCK: ManualClock = ManualClock::new();
+}
+
+#[test]
+async fn test_atom_expiry_with_manual_clock() {
+    #[derive(Atom, PartialEq, Default)]
+    struct State {
+        inner: u32,
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let a = use_atom_with_expiry::<State>(Duration::from_secs(60));
+
+        {
+            let a = a.clone();
+            use_effect_with((), move |_| {
+                a.set(State { inner: 1 });
+                || {}
+            });
+        }
+
+        html! {
+            <div>
+                <div id="a">{a.inner}</div>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new().provide_rc(clock)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "1");
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(59)));
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "1");
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(1)));
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "0");
+}
@@ -0,0 +1,347 @@
+#![cfg(feature = "devtools")]
+
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::devtools::{use_render_highlight, Devtools};
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+fn click<S: AsRef<str>>(selector: S) {
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+fn is_highlighted<S: AsRef<str>>(selector: S) -> bool {
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .class_list()
+        .contains("bounce-devtools-highlight")
+}
+
+#[test]
+async fn test_render_highlight_subscribed_states() {
+    #[derive(Atom, PartialEq, Default)]
+    struct Counter(u64);
+
+    #[function_component(CounterView)]
+    fn counter_view() -> Html {
+        let counter = use_atom::<Counter>();
+        let node_ref = use_render_highlight();
+
+        let onclick = {
+            let counter = counter.clone();
+            Callback::from(move |_| counter.set(Counter(counter.0 + 1)))
+        };
+
+        html! {
+            <div id="counter" ref={node_ref}>
+                <button id="inc" {onclick}>{counter.0}</button>
+            </div>
+        }
+    }
+
+    #[function_component(LocalView)]
+    fn local_view() -> Html {
+        let local = use_state(|| 0);
+        let node_ref = use_render_highlight();
+
+        let onclick = {
+            let local = local.clone();
+            Callback::from(move |_| local.set(*local + 1))
+        };
+
+        html! {
+            <div id="local" ref={node_ref}>
+                <button id="local-inc" {onclick}>{*local}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Devtools highlight_updates={true} />
+                <CounterView />
+                <LocalView />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    assert!(!is_highlighted("#counter"));
+    assert!(!is_highlighted("#local"));
+
+    // The component subscribed to the atom is highlighted.
+    click("#inc");
+    sleep(Duration::from_millis(10)).await;
+    assert!(is_highlighted("#counter"));
+    assert!(!is_highlighted("#local"));
+
+    // A re-render caused by a yew state is not highlighted, even if a bounce state has changed
+    // since the last render of the component.
+    click("#local-inc");
+    sleep(Duration::from_millis(10)).await;
+    assert!(!is_highlighted("#local"));
+}
+
+#[test]
+async fn test_notion_metrics_count_deduplicated_listeners() {
+    use std::rc::Rc;
+
+    use bounce::devtools::{clear_notion_metrics, notion_metrics};
+    use bounce::NotionBatch;
+
+    pub struct Increment;
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Increment))]
+    struct Counter(u64);
+
+    impl WithNotion<Increment> for Counter {
+        fn apply(self: Rc<Self>, _notion: Rc<Increment>) -> Rc<Self> {
+            Self(self.0 + 1).into()
+        }
+    }
+
+    #[function_component(CounterView)]
+    fn counter_view() -> Html {
+        let counter = use_atom_value::<Counter>();
+        let apply_notions = use_batch_notion_applier();
+
+        let onclick = Callback::from(move |_| {
+            apply_notions(NotionBatch::new().with(Increment).with(Increment))
+        });
+
+        html! {
+            <>
+                <div id="metrics-counter">{counter.0}</div>
+                <button id="metrics-increment" {onclick}>{"Increment"}</button>
+            </>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <CounterView />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+    clear_notion_metrics();
+
+    click("#metrics-increment");
+    sleep(Duration::ZERO).await;
+
+    let counter = document()
+        .query_selector("#metrics-counter")
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap();
+    assert_eq!(counter, "2");
+
+    // The component is notified once for both notions of the batch.
+    let metrics = notion_metrics()
+        .into_iter()
+        .find(|m| m.notion.ends_with("Increment"))
+        .unwrap();
+    assert_eq!(metrics.applications, 2);
+    assert_eq!(metrics.accepted, 2);
+    assert_eq!(metrics.notified_listeners, 1);
+}
+
+#[test]
+async fn test_render_counts_from_first_render() {
+    use bounce::devtools::{clear_render_counts, render_counts, use_render_count};
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Counter(u64);
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Other(u64);
+
+    #[function_component(CounterView)]
+    fn counter_view() -> Html {
+        let counter = use_atom::<Counter>();
+        let renders = use_render_count("CounterView");
+
+        let onclick = {
+            let counter = counter.clone();
+            Callback::from(move |_| counter.set(Counter(counter.0 + 1)))
+        };
+
+        html! {
+            <button id="count-inc" {onclick}>{format!("{} {}", counter.0, renders)}</button>
+        }
+    }
+
+    #[function_component(OtherView)]
+    fn other_view() -> Html {
+        let other = use_atom_value::<Other>();
+        let _renders = use_render_count("OtherView");
+
+        html! { <div>{other.0}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Devtools count_renders={true}>
+                    <CounterView />
+                    <CounterView />
+                    <OtherView />
+                </Devtools>
+            </BounceRoot>
+        }
+    }
+
+    clear_render_counts();
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+
+    // The first renders of the children of the devtools are counted.
+    let counts = render_counts();
+    let counter = counts.iter().find(|m| m.tag == "CounterView").unwrap();
+    assert_eq!((counter.mounts, counter.renders), (2, 2));
+    let other = counts.iter().find(|m| m.tag == "OtherView").unwrap();
+    assert_eq!((other.mounts, other.renders), (1, 1));
+
+    click("#count-inc");
+    sleep(Duration::from_millis(10)).await;
+
+    // Only the components subscribed to the counter are re-rendered.
+    let counts = render_counts();
+    let counter = counts.iter().find(|m| m.tag == "CounterView").unwrap();
+    assert_eq!(counter.rerenders(), 2);
+    let other = counts.iter().find(|m| m.tag == "OtherView").unwrap();
+    assert_eq!(other.rerenders(), 0);
+}
+
+#[cfg(feature = "query")]
+#[test]
+async fn test_outdated_traces_record_refetch_chain() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use async_trait::async_trait;
+    use bounce::devtools::{clear_outdated_traces, outdated_traces, OutdatedTrace};
+    use bounce::query::{use_query_value, Query, QueryResult};
+
+    thread_local! {
+        static RUNS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Filter(u64);
+
+    #[derive(PartialEq)]
+    struct Doubled(u64);
+
+    impl Selector for Doubled {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            Self(states.get_atom_value::<Filter>().0 * 2).into()
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct ItemsQuery(u64);
+
+    #[async_trait(?Send)]
+    impl Query for ItemsQuery {
+        type Input = ();
+        type Error = std::convert::Infallible;
+
+        async fn query(states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            RUNS.with(|m| m.set(m.get() + 1));
+
+            Ok(Self(states.get_selector_value::<Doubled>().0).into())
+        }
+    }
+
+    #[function_component(Items)]
+    fn items() -> Html {
+        let items = use_query_value::<ItemsQuery>(().into());
+        let set_filter = use_atom_setter::<Filter>();
+        let onclick = Callback::from(move |_| set_filter(Filter(1)));
+
+        let value = items
+            .result()
+            .and_then(|m| m.as_ref().ok())
+            .map(|m| m.0.to_string())
+            .unwrap_or_default();
+
+        html! {
+            <>
+                <div id="trace-items">{value}</div>
+                <button id="trace-filter" {onclick}>{"Filter"}</button>
+            </>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Items />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(RUNS.with(|m| m.get()), 1);
+    clear_outdated_traces();
+
+    click("#trace-filter");
+    sleep(Duration::from_millis(10)).await;
+
+    let value = document()
+        .query_selector("#trace-items")
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap();
+    assert_eq!(value, "2");
+    assert_eq!(RUNS.with(|m| m.get()), 2);
+
+    // The refetch is traced from the updated atom through the selector to the query.
+    let trace = OutdatedTrace {
+        chain: vec!["AtomSlice<Filter>".into(), "UnitSelector<Doubled>".into()],
+        notion: "RunQuery<ItemsQuery>".into(),
+    };
+    assert_eq!(outdated_traces(), vec![trace.clone()]);
+    assert_eq!(
+        trace.to_string(),
+        "AtomSlice<Filter> -> UnitSelector<Doubled> -> RunQuery<ItemsQuery>"
+    );
+}
@@ -0,0 +1,318 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{
+    use_external_store, BounceRoot, ExternalSliceBridge, ExternalStore, ExternalStoreBridge,
+    SliceStore, StoreAtom,
+};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_external_store_bridge() {
+    // A store with its own listener mechanism.
+    #[derive(PartialEq, Debug)]
+    struct Counter {
+        count: u32,
+    }
+
+    thread_local! {
+        static VALUE: RefCell<Rc<Counter>> = RefCell::new(Counter { count: 1 }.into());
+        static LISTENERS: RefCell<Vec<Weak<Callback<Rc<Counter>>>>> = RefCell::default();
+    }
+
+    fn set_counter(value: Rc<Counter>) {
+        VALUE.with(|m| *m.borrow_mut() = value.clone());
+
+        let listeners = LISTENERS.with(|m| m.borrow().clone());
+        for listener in listeners.iter().filter_map(|m| m.upgrade()) {
+            listener.emit(value.clone());
+        }
+    }
+
+    impl ExternalStore for Counter {
+        fn get() -> Rc<Self> {
+            VALUE.with(|m| m.borrow().clone())
+        }
+
+        fn set(value: Rc<Self>) {
+            set_counter(value);
+        }
+
+        fn subscribe(callback: Callback<Rc<Self>>) -> Box<dyn Any> {
+            let callback = Rc::new(callback);
+            LISTENERS.with(|m| m.borrow_mut().push(Rc::downgrade(&callback)));
+
+            Box::new(callback)
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let counter = use_external_store::<Counter>();
+
+        let onclick = {
+            let counter = counter.clone();
+            Callback::from(move |_| {
+                counter.set(StoreAtom::new(
+                    Counter {
+                        count: counter.count + 1,
+                    }
+                    .into(),
+                ))
+            })
+        };
+
+        html! {
+            <div>
+                <div id="count">{counter.count}</div>
+                <button id="inc" {onclick}>{"Increment"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <ExternalStoreBridge<Counter> />
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "1");
+
+    // Updates of the store are mirrored to bounce.
+    set_counter(Counter { count: 5 }.into());
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "5");
+
+    // Updates made with bounce are written to the store.
+    document()
+        .query_selector("#inc")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "6");
+    assert_eq!(Counter::get().count, 6);
+}
+
+#[test]
+async fn test_external_slice_bridge() {
+    enum CounterAction {
+        Increment,
+    }
+
+    #[derive(PartialEq, Default, Debug, Slice)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl Reducible for Counter {
+        type Action = CounterAction;
+
+        fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+            match action {
+                CounterAction::Increment => Self {
+                    count: self.count + 1,
+                }
+                .into(),
+            }
+        }
+    }
+
+    thread_local! {
+        static VALUE: RefCell<Rc<Counter>> = RefCell::new(Counter { count: 1 }.into());
+        static LISTENERS: RefCell<Vec<Weak<Callback<Rc<Counter>>>>> = RefCell::default();
+    }
+
+    fn set_counter(value: Rc<Counter>) {
+        VALUE.with(|m| *m.borrow_mut() = value.clone());
+
+        let listeners = LISTENERS.with(|m| m.borrow().clone());
+        for listener in listeners.iter().filter_map(|m| m.upgrade()) {
+            listener.emit(value.clone());
+        }
+    }
+
+    impl ExternalStore for Counter {
+        fn get() -> Rc<Self> {
+            VALUE.with(|m| m.borrow().clone())
+        }
+
+        fn set(value: Rc<Self>) {
+            set_counter(value);
+        }
+
+        fn subscribe(callback: Callback<Rc<Self>>) -> Box<dyn Any> {
+            let callback = Rc::new(callback);
+            LISTENERS.with(|m| m.borrow_mut().push(Rc::downgrade(&callback)));
+
+            Box::new(callback)
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let counter = use_slice::<Counter>();
+
+        let onclick = {
+            let counter = counter.clone();
+            Callback::from(move |_| counter.dispatch(CounterAction::Increment))
+        };
+
+        html! {
+            <div>
+                <div id="count">{counter.count}</div>
+                <button id="inc" {onclick}>{"Increment"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <ExternalSliceBridge<Counter> />
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // The slice is set to the value of the store.
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "1");
+
+    // Updates of the store are mirrored to bounce.
+    set_counter(Counter { count: 5 }.into());
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "5");
+
+    // Actions dispatched with bounce are written to the store.
+    document()
+        .query_selector("#inc")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "6");
+    assert_eq!(Counter::get().count, 6);
+}
+
+#[test]
+async fn test_slice_store() {
+    enum CounterAction {
+        Increment,
+    }
+
+    #[derive(PartialEq, Default, Debug, Slice)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl Reducible for Counter {
+        type Action = CounterAction;
+
+        fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+            match action {
+                CounterAction::Increment => Self {
+                    count: self.count + 1,
+                }
+                .into(),
+            }
+        }
+    }
+
+    thread_local! {
+        static STORE: RefCell<Option<SliceStore<Counter>>> = RefCell::default();
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let counter = use_slice::<Counter>();
+
+        {
+            let counter = counter.clone();
+            use_effect_with((), move |_| {
+                STORE.with(|m| *m.borrow_mut() = Some(counter.to_store()));
+            });
+        }
+
+        html! { <div id="count">{counter.count}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "0");
+
+    let store = STORE.with(|m| m.borrow().clone()).unwrap();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let subscription = {
+        let seen = seen.clone();
+        store.subscribe(Callback::from(move |m: Rc<Counter>| {
+            seen.borrow_mut().push(m.count)
+        }))
+    };
+
+    // Actions dispatched outside of components are rendered and seen by subscribers.
+    store.dispatch(CounterAction::Increment);
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "1");
+    assert_eq!(store.get().count, 1);
+    assert_eq!(*seen.borrow(), vec![1]);
+
+    // Subscribers are not notified once the guard is dropped.
+    drop(subscription);
+    store.dispatch(CounterAction::Increment);
+
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "2");
+    assert_eq!(*seen.borrow(), vec![1]);
+}
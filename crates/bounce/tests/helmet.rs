@@ -0,0 +1,533 @@
+#![cfg(feature = "helmet")]
+
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::helmet::{
+    use_theme, use_toggle_theme, CsrOnly, Helmet, HelmetBridge, HelmetWriter, ThemeHelmet,
+};
+use bounce::BounceRoot;
+use gloo::storage::{LocalStorage, Storage};
+use gloo::timers::future::sleep;
+use gloo::utils::{document, document_element};
+use wasm_bindgen::JsCast;
+use yew::html::IntoPropValue;
+use yew::prelude::*;
+use yew::virtual_dom::AttrValue;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+fn click<S: AsRef<str>>(selector: S) {
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+#[function_component(ThemeView)]
+fn theme_view() -> Html {
+    let theme = use_theme();
+    let toggle_theme = use_toggle_theme();
+    let onclick = Callback::from(move |_| toggle_theme());
+
+    html! {
+        <div>
+            <div id="theme">{theme.as_str()}</div>
+            <button id="toggle" {onclick}>{"Toggle"}</button>
+        </div>
+    }
+}
+
+#[test]
+async fn test_theme_restored_after_mount() {
+    LocalStorage::set("bounce-theme", "dark").unwrap();
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <HelmetBridge writer={CsrOnly} />
+                <ThemeHelmet />
+                <ThemeView />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#theme").await;
+    assert_eq!(s, "dark");
+    assert_eq!(
+        document_element().get_attribute("data-theme").as_deref(),
+        Some("dark")
+    );
+
+    click("#toggle");
+
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#theme").await;
+    assert_eq!(s, "light");
+    assert_eq!(
+        document_element().get_attribute("data-theme").as_deref(),
+        Some("light")
+    );
+    assert_eq!(
+        LocalStorage::get::<String>("bounce-theme").unwrap(),
+        "light"
+    );
+}
+
+#[test]
+async fn test_theme_not_read_during_render() {
+    LocalStorage::set("bounce-theme", "dark").unwrap();
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <ThemeHelmet />
+                <ThemeView />
+            </BounceRoot>
+        }
+    }
+
+    // The server-side rendering and the hydration render the default theme.
+    let s = yew::LocalServerRenderer::<Root>::new()
+        .hydratable(false)
+        .render()
+        .await;
+    assert!(s.contains(r#"<div id="theme">light</div>"#));
+}
+
+#[derive(Properties, PartialEq)]
+struct TitleRootProps {
+    writer: HelmetWriter,
+    title: Option<AttrValue>,
+}
+
+#[function_component(TitleRoot)]
+fn title_root(props: &TitleRootProps) -> Html {
+    html! {
+        <BounceRoot>
+            <HelmetBridge
+                default_title="My Site"
+                title_template="%s - My Site"
+                writer={props.writer.clone()}
+            />
+            <Helmet>
+                <meta name="description" content="A site" />
+            </Helmet>
+            if let Some(ref m) = props.title {
+                <Helmet>
+                    <title>{m.clone()}</title>
+                </Helmet>
+            }
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_title_template() {
+    fn description() -> web_sys::Element {
+        document()
+            .head()
+            .unwrap()
+            .query_selector(r#"meta[name="description"]"#)
+            .unwrap()
+            .unwrap()
+    }
+
+    let mut handle = yew::Renderer::<TitleRoot>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        TitleRootProps {
+            writer: CsrOnly.into_prop_value(),
+            title: None,
+        },
+    )
+    .render();
+    sleep(Duration::ZERO).await;
+
+    // The default title is not formatted.
+    assert_eq!(document().title(), "My Site");
+    let meta = description();
+
+    handle.update(TitleRootProps {
+        writer: CsrOnly.into_prop_value(),
+        title: Some("About".into()),
+    });
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "About - My Site");
+
+    handle.update(TitleRootProps {
+        writer: CsrOnly.into_prop_value(),
+        title: Some("Contact".into()),
+    });
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "Contact - My Site");
+
+    // Only the title is updated, other tags are not rendered again.
+    assert!(description().is_same_node(Some(&meta)));
+
+    handle.destroy();
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+async fn test_title_template_static() {
+    use bounce::helmet::render_static;
+
+    let (renderer, writer) = render_static();
+    let _s = yew::LocalServerRenderer::<TitleRoot>::with_props(TitleRootProps {
+        writer: writer.into_prop_value(),
+        title: Some("About".into()),
+    })
+    .render()
+    .await;
+
+    let mut head = String::new();
+    for tag in renderer.render().await {
+        tag.write_static(&mut head).unwrap();
+    }
+
+    // The static writer applies the same template as the bridge on the client.
+    assert!(head.contains("<title>About - My Site</title>"));
+}
+
+#[derive(Properties, PartialEq)]
+struct BadgeRootProps {
+    badge: Option<AttrValue>,
+    favicon: Option<AttrValue>,
+}
+
+#[function_component(BadgeRoot)]
+fn badge_root(props: &BadgeRootProps) -> Html {
+    use bounce::helmet::TitleBadge;
+
+    html! {
+        <BounceRoot>
+            <HelmetBridge writer={CsrOnly} />
+            <Helmet>
+                <title>{"Inbox"}</title>
+                <link rel="icon" href="/favicon.png" />
+            </Helmet>
+            <TitleBadge badge={props.badge.clone()} favicon={props.favicon.clone()} />
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_title_badge() {
+    fn favicon() -> String {
+        document()
+            .head()
+            .unwrap()
+            .query_selector(r#"link[rel="icon"]"#)
+            .unwrap()
+            .unwrap()
+            .get_attribute("href")
+            .unwrap()
+    }
+
+    let mut handle = yew::Renderer::<BadgeRoot>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        BadgeRootProps {
+            badge: None,
+            favicon: None,
+        },
+    )
+    .render();
+    sleep(Duration::ZERO).await;
+
+    assert_eq!(document().title(), "Inbox");
+    assert_eq!(favicon(), "/favicon.png");
+
+    // The badge is applied.
+    handle.update(BadgeRootProps {
+        badge: Some("3".into()),
+        favicon: Some("/favicon-unread.png".into()),
+    });
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "(3) Inbox");
+    assert_eq!(favicon(), "/favicon-unread.png");
+
+    // The badge is swapped.
+    handle.update(BadgeRootProps {
+        badge: Some("5".into()),
+        favicon: Some("/favicon-unread.png".into()),
+    });
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "(5) Inbox");
+
+    // The title and the favicon are restored.
+    handle.update(BadgeRootProps {
+        badge: None,
+        favicon: None,
+    });
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "Inbox");
+    assert_eq!(favicon(), "/favicon.png");
+
+    handle.destroy();
+}
+
+#[test]
+async fn test_selector_badge_without_title() {
+    use std::rc::Rc;
+
+    use bounce::helmet::{HelmetBadge, SelectorBadge};
+    use bounce::prelude::*;
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Unread(u64);
+
+    #[derive(PartialEq)]
+    struct UnreadBadge(u64);
+
+    impl Selector for UnreadBadge {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            Self(states.get_atom_value::<Unread>().0).into()
+        }
+    }
+
+    impl HelmetBadge for UnreadBadge {
+        fn badge(&self) -> Option<AttrValue> {
+            (self.0 > 0).then(|| self.0.to_string().into())
+        }
+    }
+
+    #[function_component(Setter)]
+    fn setter() -> Html {
+        let unread = use_atom::<Unread>();
+
+        let inc = {
+            let unread = unread.clone();
+            Callback::from(move |_| unread.set(Unread(unread.0 + 1)))
+        };
+        let clear = Callback::from(move |_| unread.set(Unread(0)));
+
+        html! {
+            <>
+                <button id="badge-inc" onclick={inc}>{"Receive"}</button>
+                <button id="badge-clear" onclick={clear}>{"Read All"}</button>
+            </>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <HelmetBridge writer={CsrOnly} />
+                <SelectorBadge<UnreadBadge> />
+                <Setter />
+            </BounceRoot>
+        }
+    }
+
+    // The title is not set with helmet.
+    document().set_title("Mail");
+
+    let handle =
+        yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+            .render();
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "Mail");
+
+    // The title of the document is badged.
+    click("#badge-inc");
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "(1) Mail");
+
+    click("#badge-inc");
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "(2) Mail");
+
+    // The title of the document is restored.
+    click("#badge-clear");
+    sleep(Duration::ZERO).await;
+    assert_eq!(document().title(), "Mail");
+
+    handle.destroy();
+}
+
+#[test]
+async fn test_on_commit() {
+    use std::cell::RefCell;
+
+    use bounce::helmet::HelmetTag;
+
+    thread_local! {
+        static TITLES: RefCell<Vec<Option<String>>> = const { RefCell::new(Vec::new()) };
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct RootProps {
+        title: AttrValue,
+        // Changes to this property render the root without changing any tag.
+        renders: u32,
+    }
+
+    #[function_component(Root)]
+    fn root(props: &RootProps) -> Html {
+        // A new callback is created for each render.
+        let on_commit = Callback::from(|tags: Vec<HelmetTag>| {
+            let title = tags.into_iter().find_map(|m| match m {
+                HelmetTag::Title(m) => Some(m.to_string()),
+                _ => None,
+            });
+            TITLES.with(|m| m.borrow_mut().push(title));
+        });
+
+        html! {
+            <BounceRoot>
+                <HelmetBridge {on_commit} writer={CsrOnly} />
+                <Helmet>
+                    <title>{props.title.clone()}</title>
+                </Helmet>
+                <div id="renders">{props.renders}</div>
+            </BounceRoot>
+        }
+    }
+
+    fn titles() -> Vec<Option<String>> {
+        TITLES.with(|m| m.borrow().clone())
+    }
+
+    let mut handle = yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps {
+            title: "Home".into(),
+            renders: 0,
+        },
+    )
+    .render();
+    sleep(Duration::ZERO).await;
+
+    // The callback is called with the committed tags.
+    assert_eq!(titles().last(), Some(&Some("Home".to_string())));
+    let commits = titles().len();
+
+    // The callback is called once for each commit.
+    handle.update(RootProps {
+        title: "About".into(),
+        renders: 0,
+    });
+    sleep(Duration::ZERO).await;
+    assert_eq!(titles().len(), commits + 1);
+    assert_eq!(titles().last(), Some(&Some("About".to_string())));
+    assert_eq!(document().title(), "About");
+
+    // The callback is not called when the root is rendered without changing any tag.
+    handle.update(RootProps {
+        title: "About".into(),
+        renders: 1,
+    });
+    assert_eq!(get_text_content("#renders").await, "1");
+    assert_eq!(titles().len(), commits + 1);
+
+    handle.destroy();
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+async fn test_robots_meta_static() {
+    use bounce::helmet::{render_static, HelmetTag, NoIndex, RobotsMeta, RobotsPolicy};
+
+    #[derive(Properties, PartialEq)]
+    struct RootProps {
+        writer: HelmetWriter,
+        robots: Option<RobotsPolicy>,
+        children: Html,
+    }
+
+    #[function_component(Root)]
+    fn root(props: &RootProps) -> Html {
+        html! {
+            <BounceRoot>
+                <HelmetBridge writer={props.writer.clone()} robots={props.robots} />
+                {props.children.clone()}
+            </BounceRoot>
+        }
+    }
+
+    /// Renders the root on the server and returns the rendered robots meta tags.
+    async fn render_robots(robots: Option<RobotsPolicy>, children: Html) -> Vec<String> {
+        let (renderer, writer) = render_static();
+        let _s = yew::LocalServerRenderer::<Root>::with_props(RootProps {
+            writer: writer.into_prop_value(),
+            robots,
+            children,
+        })
+        .render()
+        .await;
+
+        renderer
+            .render()
+            .await
+            .into_iter()
+            .filter(|m| matches!(m, HelmetTag::Meta { attrs } if attrs.contains_key("name")))
+            .map(|m| {
+                let mut s = String::new();
+                m.write_static(&mut s).unwrap();
+                s
+            })
+            .collect()
+    }
+
+    let tags = render_robots(None, html! { <RobotsMeta policy={RobotsPolicy::NONE} /> }).await;
+    assert_eq!(tags.len(), 1);
+    assert!(tags[0].contains(r#"name="robots""#));
+    assert!(tags[0].contains(r#"content="noindex, nofollow""#));
+
+    let tags = render_robots(None, html! { <NoIndex /> }).await;
+    assert_eq!(tags.len(), 1);
+    assert!(tags[0].contains(r#"name="robots""#));
+    assert!(tags[0].contains(r#"content="noindex, follow""#));
+
+    // A tag rendered later replaces the tag of an outer component.
+    let tags = render_robots(
+        None,
+        html! {
+            <>
+                <RobotsMeta policy={RobotsPolicy::ALL} />
+                <NoIndex />
+            </>
+        },
+    )
+    .await;
+    assert_eq!(tags.len(), 1);
+    assert!(tags[0].contains(r#"content="noindex, follow""#));
+
+    // The policy of the bridge replaces the robots meta tags of all pages.
+    let tags = render_robots(
+        Some(RobotsPolicy::NONE),
+        html! {
+            <>
+                <RobotsMeta policy={RobotsPolicy::ALL} />
+                <Helmet>
+                    <meta name="googlebot" content="index, follow" />
+                </Helmet>
+            </>
+        },
+    )
+    .await;
+    assert_eq!(tags.len(), 1);
+    assert!(tags[0].contains(r#"name="robots""#));
+    assert!(tags[0].contains(r#"content="noindex, nofollow""#));
+}
@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{use_hydration_progress, use_hydration_tracker, BounceRoot, PersistGate};
+use futures::channel::oneshot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static HYDRATED: RefCell<Option<oneshot::Sender<u32>>> = RefCell::default();
+}
+
+#[test]
+async fn test_persist_gate() {
+    #[derive(Atom, PartialEq, Default)]
+    struct State {
+        inner: u32,
+    }
+
+    #[function_component(Hydrator)]
+    fn hydrator() -> Html {
+        let tracker = use_hydration_tracker();
+        let set_state = use_atom_setter::<State>();
+
+        use_memo((), move |_| {
+            let (tx, rx) = oneshot::channel();
+            HYDRATED.with(|m| *m.borrow_mut() = Some(tx));
+
+            tracker.hydrate(async move {
+                let inner = rx.await.unwrap();
+                set_state(State { inner });
+            });
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Loading)]
+    fn loading() -> Html {
+        let progress = use_hydration_progress();
+
+        html! { <div id="loading">{progress.finished}{"/"}{progress.started}</div> }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let a = use_atom_value::<State>();
+
+        html! { <div id="a">{a.inner}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Hydrator />
+                <div id="gate">
+                    <PersistGate loading={html! {<Loading />}}>
+                        <Comp />
+                    </PersistGate>
+                </div>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#gate").await;
+    assert_eq!(s, "0/1");
+
+    HYDRATED.with(|m| m.borrow_mut().take().unwrap().send(1).unwrap());
+
+    let s = get_text_content("#gate").await;
+    assert_eq!(s, "1");
+}
+
+#[test]
+async fn test_persist_gate_hydrator_after_gate() {
+    thread_local! {
+        static LATE_HYDRATED: RefCell<Option<oneshot::Sender<()>>> = RefCell::default();
+    }
+
+    #[function_component(Hydrator)]
+    fn hydrator() -> Html {
+        let tracker = use_hydration_tracker();
+
+        use_memo((), move |_| {
+            let (tx, rx) = oneshot::channel();
+            LATE_HYDRATED.with(|m| *m.borrow_mut() = Some(tx));
+
+            tracker.hydrate(async move {
+                rx.await.unwrap();
+            });
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <div id="gate">
+                    <PersistGate loading={html! {"loading"}}>
+                        {"content"}
+                    </PersistGate>
+                </div>
+                // The hydration is started after the gate is rendered.
+                <Hydrator />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#gate").await;
+    assert_eq!(s, "loading");
+
+    LATE_HYDRATED.with(|m| m.borrow_mut().take().unwrap().send(()).unwrap());
+
+    let s = get_text_content("#gate").await;
+    assert_eq!(s, "content");
+}
@@ -0,0 +1,43 @@
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::{use_id, BounceRoot};
+use yew::prelude::*;
+
+#[function_component(Field)]
+fn field() -> Html {
+    let id = use_id();
+
+    html! { <input {id} /> }
+}
+
+#[function_component(Root)]
+fn root() -> Html {
+    html! {
+        <BounceRoot>
+            <Field />
+            <Field />
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_ids_match_between_renders() {
+    let first = yew::LocalServerRenderer::<Root>::new()
+        .hydratable(false)
+        .render()
+        .await;
+
+    // Ids created by another root do not change the ids of the next render.
+    let _other = yew::LocalServerRenderer::<Root>::new().render().await;
+
+    let second = yew::LocalServerRenderer::<Root>::new()
+        .hydratable(false)
+        .render()
+        .await;
+
+    assert_eq!(first, second);
+    assert!(first.contains(r#"<input id="bounce-0">"#));
+    assert!(first.contains(r#"<input id="bounce-1">"#));
+}
@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+#[test]
+async fn test_idle_scheduler() {
+    thread_local! {
+        static RAN: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn push(task: &'static str) -> impl FnOnce() {
+        move || RAN.with(|m| m.borrow_mut().push(task))
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let scheduler = use_idle_scheduler();
+
+        use_effect_with((), move |_| {
+            scheduler.schedule(push("first"));
+            let token = scheduler.schedule(push("cancelled"));
+            scheduler.schedule(push("last"));
+
+            token.abort();
+
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // Tasks are not run during rendering.
+    assert!(RAN.with(|m| m.borrow().is_empty()));
+
+    // Tasks are run within the idle timeout even if the browser does not become idle.
+    for _ in 0..30 {
+        if RAN.with(|m| m.borrow().len()) >= 2 {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    sleep(Duration::from_millis(100)).await;
+
+    // Queued tasks are run in order, the cancelled task is not run.
+    assert_eq!(RAN.with(|m| m.borrow().clone()), vec!["first", "last"]);
+}
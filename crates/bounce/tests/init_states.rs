@@ -6,9 +6,10 @@ use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
 wasm_bindgen_test_configure!(run_in_browser);
 
 use bounce::prelude::*;
-use bounce::BounceRoot;
+use bounce::{BounceRoot, StateOverrides};
 use gloo::timers::future::sleep;
 use gloo::utils::document;
+use wasm_bindgen::JsCast;
 use yew::prelude::*;
 
 async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
@@ -80,3 +81,163 @@ async fn test_with_init_states() {
     let s = get_text_content("#a").await;
     assert_eq!(s, "1");
 }
+
+#[test]
+async fn test_from_states() {
+    #[derive(Slice, PartialEq, Default)]
+    #[bounce(from_states)]
+    struct Doubled {
+        inner: u32,
+    }
+
+    impl Reducible for Doubled {
+        type Action = u32;
+
+        fn reduce(self: std::rc::Rc<Self>, action: Self::Action) -> std::rc::Rc<Self> {
+            Self { inner: action }.into()
+        }
+    }
+
+    impl FromStates for Doubled {
+        fn create_from(states: &BounceStates) -> Self {
+            Self {
+                inner: states.get_atom_value::<State>().inner * 2,
+            }
+        }
+    }
+
+    #[function_component(DoubledComp)]
+    fn doubled_comp() -> Html {
+        let doubled = use_slice_value::<Doubled>();
+
+        html! { <div id="doubled">{doubled.inner}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        fn get_init_states(_: ()) -> AnyMap {
+            let mut map = AnyMap::new();
+            map.insert(State { inner: 2 });
+
+            map
+        }
+
+        html! {
+            <BounceRoot {get_init_states}>
+                <DoubledComp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#doubled").await;
+    assert_eq!(s, "4");
+}
+
+#[test]
+async fn test_child_atom() {
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(child)]
+    struct Child {
+        inner: u32,
+    }
+
+    impl ChildAtom for Child {
+        type Parent = State;
+
+        fn derive_from(parent: &State) -> Self {
+            Self {
+                inner: parent.inner,
+            }
+        }
+    }
+
+    #[function_component(ChildComp)]
+    fn child_comp() -> Html {
+        let child = use_atom_value::<Child>();
+        let set_state = use_atom_setter::<State>();
+        let resync = use_atom_resync::<Child>();
+
+        let set_parent = Callback::from(move |_| set_state(State { inner: 5 }));
+        let resync = Callback::from(move |_| resync());
+
+        html! {
+            <>
+                <div id="child">{child.inner}</div>
+                <button id="set-parent" onclick={set_parent}>{"Set Parent"}</button>
+                <button id="resync" onclick={resync}>{"Resync"}</button>
+            </>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        fn get_init_states(_: ()) -> AnyMap {
+            let mut map = AnyMap::new();
+            map.insert(State { inner: 2 });
+
+            map
+        }
+
+        html! {
+            <BounceRoot {get_init_states}>
+                <ChildComp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#child").await;
+    assert_eq!(s, "2");
+
+    let click = |selector: &str| {
+        document()
+            .query_selector(selector)
+            .unwrap()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+    };
+
+    // The child is not changed by its parent until it is re-synced.
+    click("#set-parent");
+    let s = get_text_content("#child").await;
+    assert_eq!(s, "2");
+
+    click("#resync");
+    let s = get_text_content("#child").await;
+    assert_eq!(s, "5");
+}
+
+#[test]
+async fn test_with_overrides() {
+    #[function_component(Root)]
+    fn root() -> Html {
+        fn get_init_states(_: ()) -> AnyMap {
+            let mut map = AnyMap::new();
+            map.insert(State { inner: 1 });
+
+            map
+        }
+
+        fn get_overrides(_: ()) -> StateOverrides {
+            StateOverrides::new().set(State { inner: 3 })
+        }
+
+        html! {
+            <BounceRoot {get_init_states} {get_overrides}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "3");
+}
@@ -0,0 +1,457 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static SELECTS: Cell<u32> = const { Cell::new(0) };
+}
+
+#[test]
+async fn test_lazy_input_selector() {
+    #[derive(Atom, PartialEq, Default)]
+    struct State {
+        inner: u32,
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Mounted {
+        inner: bool,
+    }
+
+    #[derive(PartialEq)]
+    struct Sum {
+        inner: u32,
+    }
+
+    impl InputSelector for Sum {
+        type Input = u32;
+
+        const LAZY: bool = true;
+
+        fn select(states: &BounceStates, input: Rc<u32>) -> Rc<Self> {
+            SELECTS.with(|m| m.set(m.get() + 1));
+
+            Self {
+                inner: states.get_atom_value::<State>().inner + *input,
+            }
+            .into()
+        }
+    }
+
+    #[function_component(Reader)]
+    fn reader() -> Html {
+        let sum = use_input_selector_value::<Sum>(1.into());
+
+        html! { <div id="sum">{sum.inner}</div> }
+    }
+
+    #[function_component(Setter)]
+    fn setter() -> Html {
+        let state = use_atom::<State>();
+        let mounted = use_atom::<Mounted>();
+
+        let increment = {
+            let state = state.clone();
+            Callback::from(move |_| {
+                state.set(State {
+                    inner: state.inner + 1,
+                })
+            })
+        };
+        let toggle = {
+            let mounted = mounted.clone();
+            Callback::from(move |_| {
+                mounted.set(Mounted {
+                    inner: !mounted.inner,
+                })
+            })
+        };
+
+        html! {
+            <div>
+                <button id="increment" onclick={increment}>{"Increment"}</button>
+                <button id="toggle" onclick={toggle}>{"Toggle"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let mounted = use_atom_value::<Mounted>();
+
+        html! {
+            <div>
+                <Setter />
+                if mounted.inner {
+                    <Reader />
+                } else {
+                    <div id="sum">{"unmounted"}</div>
+                }
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    let click = |selector: &str| {
+        let el: web_sys::HtmlElement = wasm_bindgen::JsCast::unchecked_into(
+            document().query_selector(selector).unwrap().unwrap(),
+        );
+        el.click();
+    };
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    click("#toggle");
+    let s = get_text_content("#sum").await;
+    assert_eq!(s, "1");
+    assert_eq!(SELECTS.with(|m| m.get()), 1);
+
+    // Subscribed selectors are selected on each change.
+    click("#increment");
+    let s = get_text_content("#sum").await;
+    assert_eq!(s, "2");
+    assert_eq!(SELECTS.with(|m| m.get()), 2);
+
+    click("#toggle");
+    let s = get_text_content("#sum").await;
+    assert_eq!(s, "unmounted");
+
+    // Selectors without subscribers are only invalidated.
+    click("#increment");
+    sleep(Duration::ZERO).await;
+    click("#increment");
+    sleep(Duration::ZERO).await;
+    assert_eq!(SELECTS.with(|m| m.get()), 2);
+
+    click("#toggle");
+    let s = get_text_content("#sum").await;
+    assert_eq!(s, "4");
+    assert_eq!(SELECTS.with(|m| m.get()), 3);
+}
+
+#[test]
+async fn test_input_selector_batched_selects() {
+    thread_local! {
+        static BATCHED_SELECTS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Width {
+        inner: u32,
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Height {
+        inner: u32,
+    }
+
+    #[derive(PartialEq)]
+    struct Area {
+        inner: u32,
+    }
+
+    impl InputSelector for Area {
+        type Input = u32;
+
+        fn select(states: &BounceStates, input: Rc<u32>) -> Rc<Self> {
+            BATCHED_SELECTS.with(|m| m.set(m.get() + 1));
+
+            let width = states.get_atom_value::<Width>().inner;
+            let height = states.get_atom_value::<Height>().inner;
+
+            Self {
+                inner: width * height * *input,
+            }
+            .into()
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let area = use_input_selector_value::<Area>(2.into());
+        let set_width = use_atom_setter::<Width>();
+        let set_height = use_atom_setter::<Height>();
+
+        // Both dependencies change within the same tick.
+        let resize = Callback::from(move |_| {
+            set_width(Width { inner: 2 });
+            set_height(Height { inner: 3 });
+        });
+
+        html! {
+            <div>
+                <div id="area">{area.inner}</div>
+                <button id="resize" onclick={resize}>{"Resize"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#area").await;
+    assert_eq!(s, "0");
+    assert_eq!(BATCHED_SELECTS.with(|m| m.get()), 1);
+
+    let el: web_sys::HtmlElement = wasm_bindgen::JsCast::unchecked_into(
+        document().query_selector("#resize").unwrap().unwrap(),
+    );
+    el.click();
+
+    // The selector is selected once for both changes.
+    let s = get_text_content("#area").await;
+    assert_eq!(s, "12");
+    assert_eq!(BATCHED_SELECTS.with(|m| m.get()), 2);
+}
+
+#[test]
+async fn test_derived_selectors() {
+    thread_local! {
+        static PARITY_RENDERS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Count {
+        inner: u32,
+    }
+
+    #[derive(PartialEq)]
+    struct CountSelector {
+        inner: u32,
+    }
+
+    impl Selector for CountSelector {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            Self {
+                inner: states.get_atom_value::<Count>().inner,
+            }
+            .into()
+        }
+    }
+
+    #[derive(PartialEq)]
+    struct Label {
+        inner: &'static str,
+    }
+
+    impl Selector for Label {
+        fn select(_states: &BounceStates) -> Rc<Self> {
+            Self { inner: "count" }.into()
+        }
+    }
+
+    #[derive(PartialEq)]
+    struct IsEven {
+        inner: bool,
+    }
+
+    impl Selector for IsEven {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            // A derived value can be read by another selector.
+            let count = states.get_derived_value(map_selector::<CountSelector, _>(|m| m.inner));
+
+            Self {
+                inner: *count % 2 == 0,
+            }
+            .into()
+        }
+    }
+
+    #[function_component(Parity)]
+    fn parity() -> Html {
+        let is_even = use_derived_value(map_selector::<IsEven, _>(|m| m.inner));
+        PARITY_RENDERS.with(|m| m.set(m.get() + 1));
+
+        html! { <div id="parity">{if *is_even { "even" } else { "odd" }}</div> }
+    }
+
+    #[function_component(Offsets)]
+    fn offsets() -> Html {
+        // Closures with the same signature are different derived states.
+        let plus_one = use_derived_value(map_selector::<CountSelector, _>(|m| m.inner + 1));
+        let plus_two = use_derived_value(map_selector::<CountSelector, _>(|m| m.inner + 2));
+
+        html! { <div id="offsets">{format!("{} {}", plus_one, plus_two)}</div> }
+    }
+
+    #[function_component(Labelled)]
+    fn labelled() -> Html {
+        let value = use_derived_value(zip_selectors::<Label, CountSelector>());
+        let (label, count) = &*value;
+
+        html! { <div id="labelled">{format!("{}: {}", label.inner, count.inner)}</div> }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let set_count = use_atom_setter::<Count>();
+        let add_two = {
+            let set_count = set_count.clone();
+            Callback::from(move |_| set_count(Count { inner: 2 }))
+        };
+        let add_one = Callback::from(move |_| set_count(Count { inner: 3 }));
+
+        html! {
+            <div>
+                <Parity />
+                <Offsets />
+                <Labelled />
+                <button id="add-two" onclick={add_two}>{"Add Two"}</button>
+                <button id="add-one" onclick={add_one}>{"Add One"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#parity").await;
+    assert_eq!(s, "even");
+    let s = get_text_content("#labelled").await;
+    assert_eq!(s, "count: 0");
+    let s = get_text_content("#offsets").await;
+    assert_eq!(s, "1 2");
+    assert_eq!(PARITY_RENDERS.with(|m| m.get()), 1);
+
+    let click = |id: &str| {
+        let el: web_sys::HtmlElement =
+            wasm_bindgen::JsCast::unchecked_into(document().query_selector(id).unwrap().unwrap());
+        el.click();
+    };
+
+    click("#add-two");
+
+    // The parity has not changed, so the component is not rendered again.
+    let s = get_text_content("#labelled").await;
+    assert_eq!(s, "count: 2");
+    let s = get_text_content("#parity").await;
+    assert_eq!(s, "even");
+    assert_eq!(PARITY_RENDERS.with(|m| m.get()), 1);
+
+    click("#add-one");
+
+    let s = get_text_content("#labelled").await;
+    assert_eq!(s, "count: 3");
+    let s = get_text_content("#parity").await;
+    assert_eq!(s, "odd");
+    assert_eq!(PARITY_RENDERS.with(|m| m.get()), 2);
+}
+
+#[test]
+async fn test_input_selector_interns_equal_inputs() {
+    thread_local! {
+        static INTERNED_SELECTS: Cell<u32> = const { Cell::new(0) };
+        static FIRST_INPUT: Cell<usize> = const { Cell::new(0) };
+        static SELECTED_INPUT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(PartialEq)]
+    struct Doubled {
+        inner: u32,
+    }
+
+    impl InputSelector for Doubled {
+        type Input = u32;
+
+        fn select(_states: &BounceStates, input: Rc<u32>) -> Rc<Self> {
+            INTERNED_SELECTS.with(|m| m.set(m.get() + 1));
+            SELECTED_INPUT.with(|m| m.set(Rc::as_ptr(&input) as usize));
+
+            Self { inner: *input * 2 }.into()
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let renders = use_state(|| 1);
+
+        // An equal input is allocated on every render.
+        let input = Rc::new(1);
+        if *renders == 1 {
+            FIRST_INPUT.with(|m| m.set(Rc::as_ptr(&input) as usize));
+        }
+        let doubled = use_input_selector_value::<Doubled>(input);
+
+        {
+            let renders = renders.clone();
+            use_effect_with(*renders, move |m| {
+                if *m < 5 {
+                    renders.set(*m + 1);
+                }
+            });
+        }
+
+        html! { <div id="doubled">{format!("{} {}", doubled.inner, *renders)}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#doubled").await;
+    assert_eq!(s, "2 5");
+
+    // The input of the first render is kept, so the selector is not selected again.
+    assert_eq!(INTERNED_SELECTS.with(|m| m.get()), 1);
+    assert_eq!(
+        SELECTED_INPUT.with(|m| m.get()),
+        FIRST_INPUT.with(|m| m.get())
+    );
+}
@@ -6,9 +6,10 @@ use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
 wasm_bindgen_test_configure!(run_in_browser);
 
 use bounce::prelude::*;
-use bounce::BounceRoot;
+use bounce::{with_batched_updates, BounceRoot, Clock, ManualClock, RateLimitStrategy, Services};
 use gloo::timers::future::sleep;
 use gloo::utils::document;
+use wasm_bindgen::JsCast;
 use yew::prelude::*;
 
 async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
@@ -84,3 +85,727 @@ async fn test_notion_generic() {
     let s = get_text_content("#b").await;
     assert_eq!(s, "2");
 }
+
+#[test]
+async fn test_notion_batched_notification() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static SELECT_CTR: Cell<u32> = const { Cell::new(0) };
+    }
+
+    struct Reset;
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Reset))]
+    struct A(u32);
+
+    impl WithNotion<Reset> for A {
+        fn apply(self: Rc<Self>, _notion: Rc<Reset>) -> Rc<Self> {
+            Self::default().into()
+        }
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Reset))]
+    struct B(u32);
+
+    impl WithNotion<Reset> for B {
+        fn apply(self: Rc<Self>, _notion: Rc<Reset>) -> Rc<Self> {
+            Self::default().into()
+        }
+    }
+
+    #[derive(PartialEq)]
+    struct Sum(u32);
+
+    impl Selector for Sum {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            SELECT_CTR.with(|m| m.set(m.get() + 1));
+
+            Self(states.get_atom_value::<A>().0 + states.get_atom_value::<B>().0).into()
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let sum = use_selector_value::<Sum>();
+        let set_a = use_atom_setter::<A>();
+        let set_b = use_atom_setter::<B>();
+        let reset = use_notion_applier::<Reset>();
+
+        use_effect_with((), move |_| {
+            set_a(A(1));
+            set_b(B(2));
+
+            || {}
+        });
+
+        let onclick = Callback::from(move |_| reset(Reset));
+
+        html! {
+            <div>
+                <div id="sum">{sum.0}</div>
+                <button id="reset" {onclick}>{"Reset"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#sum").await;
+    assert_eq!(s, "3");
+
+    let ctr_before_reset = SELECT_CTR.with(|m| m.get());
+
+    document()
+        .query_selector("#reset")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#sum").await;
+    assert_eq!(s, "0");
+
+    // Both atoms are reset by the same notion, so the selector is only computed once.
+    assert_eq!(SELECT_CTR.with(|m| m.get()) - ctr_before_reset, 1);
+}
+
+#[test]
+async fn test_batch_notion_applier() {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static OBSERVED: RefCell<Vec<(bool, u32)>> = RefCell::default();
+    }
+
+    struct LoggedOut;
+    struct CacheCleared;
+
+    #[derive(Atom, PartialEq)]
+    #[bounce(with_notion(LoggedOut))]
+    struct Session {
+        logged_in: bool,
+    }
+
+    impl Default for Session {
+        fn default() -> Self {
+            Self { logged_in: true }
+        }
+    }
+
+    impl WithNotion<LoggedOut> for Session {
+        fn apply(self: Rc<Self>, _notion: Rc<LoggedOut>) -> Rc<Self> {
+            Self { logged_in: false }.into()
+        }
+    }
+
+    #[derive(Atom, PartialEq)]
+    #[bounce(with_notion(CacheCleared))]
+    struct Cache {
+        entries: u32,
+    }
+
+    impl Default for Cache {
+        fn default() -> Self {
+            Self { entries: 3 }
+        }
+    }
+
+    impl WithNotion<CacheCleared> for Cache {
+        fn apply(self: Rc<Self>, _notion: Rc<CacheCleared>) -> Rc<Self> {
+            Self { entries: 0 }.into()
+        }
+    }
+
+    #[derive(PartialEq)]
+    struct Snapshot;
+
+    impl Selector for Snapshot {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            let logged_in = states.get_atom_value::<Session>().logged_in;
+            let entries = states.get_atom_value::<Cache>().entries;
+            OBSERVED.with(|m| m.borrow_mut().push((logged_in, entries)));
+
+            Self.into()
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let _snapshot = use_selector_value::<Snapshot>();
+        let session = use_atom_value::<Session>();
+        let apply_notions = use_batch_notion_applier();
+
+        let onclick = Callback::from(move |_| {
+            apply_notions(NotionBatch::new().with(LoggedOut).with(CacheCleared))
+        });
+
+        html! {
+            <div>
+                <div id="session">{session.logged_in.to_string()}</div>
+                <button id="logout" {onclick}>{"Logout"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#session").await;
+    assert_eq!(s, "true");
+
+    document()
+        .query_selector("#logout")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#session").await;
+    assert_eq!(s, "false");
+
+    // The selector never observes a logged out session with a filled cache.
+    let observed = OBSERVED.with(|m| m.borrow().clone());
+    assert_eq!(observed, vec![(true, 3), (false, 0)]);
+}
+
+#[test]
+async fn test_with_batched_updates() {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static RENDERED: RefCell<Vec<(u32, u32)>> = RefCell::default();
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct A(u32);
+
+    #[derive(Atom, PartialEq, Default)]
+    struct B(u32);
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let a = use_atom_value::<A>();
+        let b = use_atom_value::<B>();
+        let set_a = use_atom_setter::<A>();
+        let set_b = use_atom_setter::<B>();
+
+        RENDERED.with(|m| m.borrow_mut().push((a.0, b.0)));
+
+        let set_b = Rc::new(move |m| set_b(B(m)));
+        let onclick = Callback::from(move |_| {
+            let set_b = set_b.clone();
+            with_batched_updates(|| {
+                set_a(A(1));
+                // Nested batches are flushed with the outermost batch.
+                with_batched_updates(|| set_b(2));
+            });
+        });
+
+        html! {
+            <div>
+                <div id="a-b">{format!("{} {}", a.0, b.0)}</div>
+                <button id="update" {onclick}>{"Update"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#a-b").await;
+    assert_eq!(s, "0 0");
+
+    document()
+        .query_selector("#update")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#a-b").await;
+    assert_eq!(s, "1 2");
+
+    // The component is never rendered with only one of the atoms updated.
+    RENDERED.with(|m| assert_eq!(*m.borrow(), vec![(0, 0), (1, 2)]));
+}
+
+#[test]
+async fn test_notion_scope_aborts_on_unmount() {
+    #[derive(PartialEq)]
+    struct Loaded(u64);
+
+    #[future_notion(Load)]
+    async fn load(input: &u64) -> Loaded {
+        sleep(Duration::from_millis(100)).await;
+
+        Loaded(*input)
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Deferred<Load>))]
+    struct Status(&'static str);
+
+    impl WithNotion<Deferred<Load>> for Status {
+        fn apply(self: Rc<Self>, notion: Rc<Deferred<Load>>) -> Rc<Self> {
+            match *notion {
+                Deferred::Pending { .. } => Self("pending"),
+                Deferred::Completed { .. } => Self("completed"),
+                Deferred::Outdated { .. } => Self("outdated"),
+            }
+            .into()
+        }
+    }
+
+    #[function_component(Loader)]
+    fn loader() -> Html {
+        let scope = use_notion_scope();
+
+        use_effect_with((), move |_| {
+            scope.run_future_notion::<Load>(1);
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let mounted = use_state(|| true);
+        let status = use_atom_value::<Status>();
+
+        let onclick = {
+            let mounted = mounted.clone();
+            Callback::from(move |_| mounted.set(false))
+        };
+
+        html! {
+            <div>
+                <div id="status">{status.0}</div>
+                <button id="unmount" {onclick}>{"Unmount"}</button>
+                if *mounted {
+                    <Loader />
+                }
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+
+    let s = get_text_content("#status").await;
+    assert_eq!(s, "pending");
+
+    document()
+        .query_selector("#unmount")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    sleep(Duration::from_millis(150)).await;
+
+    // The run is aborted, so the notion never completes.
+    let s = get_text_content("#status").await;
+    assert_eq!(s, "outdated");
+}
+
+#[test]
+async fn test_notion_accepts() {
+    pub struct Increment;
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Increment))]
+    struct Counter {
+        inner: u32,
+    }
+
+    impl WithNotion<Increment> for Counter {
+        fn accepts(&self, _notion: &Increment) -> bool {
+            self.inner < 2
+        }
+
+        fn apply(self: Rc<Self>, _notion: Rc<Increment>) -> Rc<Self> {
+            Self {
+                inner: self.inner + 1,
+            }
+            .into()
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let counter = use_atom_value::<Counter>();
+        let increment = use_notion_applier::<Increment>();
+
+        use_effect_with((), move |_| {
+            for _ in 0..5 {
+                increment(Increment);
+            }
+
+            || {}
+        });
+
+        html! { <div id="counter">{counter.inner}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // Notions are not applied once the counter stops accepting them.
+    let s = get_text_content("#counter").await;
+    assert_eq!(s, "2");
+}
+
+#[test]
+async fn test_notion_bus() {
+    use bounce::{NotionBus, Services};
+
+    struct Logout;
+    struct Increment;
+
+    #[derive(Atom, PartialEq)]
+    #[bounce(with_notion(Logout, Increment))]
+    struct Session {
+        logged_in: bool,
+        ctr: u32,
+    }
+
+    impl Default for Session {
+        fn default() -> Self {
+            Self {
+                logged_in: true,
+                ctr: 0,
+            }
+        }
+    }
+
+    impl WithNotion<Logout> for Session {
+        fn apply(self: Rc<Self>, _notion: Rc<Logout>) -> Rc<Self> {
+            Self {
+                logged_in: false,
+                ctr: self.ctr,
+            }
+            .into()
+        }
+    }
+
+    impl WithNotion<Increment> for Session {
+        fn apply(self: Rc<Self>, _notion: Rc<Increment>) -> Rc<Self> {
+            Self {
+                logged_in: self.logged_in,
+                ctr: self.ctr + 1,
+            }
+            .into()
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct WidgetProps {
+        name: &'static str,
+        bus: NotionBus,
+    }
+
+    #[function_component(Inner)]
+    fn inner(props: &WidgetProps) -> Html {
+        let session = use_atom_value::<Session>();
+        let logout = use_notion_applier::<Logout>();
+        let increment = use_notion_applier::<Increment>();
+
+        let onlogout = Callback::from(move |_| logout(Logout));
+        let onincrement = Callback::from(move |_| increment(Increment));
+
+        html! {
+            <div>
+                <div id={format!("{}-session", props.name)}>
+                    {format!("{} {}", session.logged_in, session.ctr)}
+                </div>
+                <button id={format!("{}-logout", props.name)} onclick={onlogout}>{"Logout"}</button>
+                <button id={format!("{}-increment", props.name)} onclick={onincrement}>{"+"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Widget)]
+    fn widget(props: &WidgetProps) -> Html {
+        let bus = props.bus.clone();
+        let get_services = Callback::from(move |_| Services::new().provide(bus.clone()));
+
+        html! {
+            <BounceRoot {get_services}>
+                <Inner name={props.name} bus={props.bus.clone()} />
+            </BounceRoot>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let bus = use_memo((), |_| NotionBus::new().share::<Logout>());
+
+        html! {
+            <>
+                <Widget name="a" bus={(*bus).clone()} />
+                <Widget name="b" bus={(*bus).clone()} />
+            </>
+        }
+    }
+
+    let click = |selector: &str| {
+        document()
+            .query_selector(selector)
+            .unwrap()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+    };
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#b-session").await;
+    assert_eq!(s, "true 0");
+
+    // Notions that are not shared only reach their own root.
+    click("#a-increment");
+    let s = get_text_content("#a-session").await;
+    assert_eq!(s, "true 1");
+    let s = get_text_content("#b-session").await;
+    assert_eq!(s, "true 0");
+
+    click("#a-logout");
+    let s = get_text_content("#a-session").await;
+    assert_eq!(s, "false 1");
+    let s = get_text_content("#b-session").await;
+    assert_eq!(s, "false 0");
+}
+
+#[test]
+async fn test_notion_applier_rate_limited() {
+    thread_local! {
+        static CLOCK: ManualClock = ManualClock::new();
+    }
+
+    pub struct Moved(u64);
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Moved))]
+    struct Coalesced(u64);
+
+    impl WithNotion<Moved> for Coalesced {
+        fn apply(self: Rc<Self>, notion: Rc<Moved>) -> Rc<Self> {
+            Self(notion.0).into()
+        }
+    }
+
+    pub struct Scrolled(u64);
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Scrolled))]
+    struct Dropped(u64);
+
+    impl WithNotion<Scrolled> for Dropped {
+        fn apply(self: Rc<Self>, notion: Rc<Scrolled>) -> Rc<Self> {
+            Self(notion.0).into()
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let coalesced = use_atom_value::<Coalesced>();
+        let dropped = use_atom_value::<Dropped>();
+
+        let moved = use_notion_applier_rate_limited::<Moved>(2);
+        let scrolled = use_notion_applier_rate_limited_with::<Scrolled>(2, RateLimitStrategy::Drop);
+
+        use_effect_with((), move |_| {
+            for i in 1..=3 {
+                moved(Moved(i));
+                scrolled(Scrolled(i));
+            }
+            || {}
+        });
+
+        html! { <div id="rate-limited">{format!("{} {}", coalesced.0, dropped.0)}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new().provide_rc(clock)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // The first notion is applied immediately.
+    let s = get_text_content("#rate-limited").await;
+    assert_eq!(s, "1 1");
+
+    // The latest excess notion is applied after the interval, excess notions are dropped.
+    CLOCK.with(|m| m.advance(Duration::from_millis(500)));
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#rate-limited").await;
+    assert_eq!(s, "3 1");
+}
+
+#[test]
+async fn test_notion_large_batch_and_chain() {
+    use std::cell::RefCell;
+
+    type StepApplier = Rc<dyn Fn(Step)>;
+
+    thread_local! {
+        static APPLY_STEP: RefCell<Option<StepApplier>> = RefCell::default();
+    }
+
+    struct Add;
+    struct Step;
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Add))]
+    struct Total(u32);
+
+    impl WithNotion<Add> for Total {
+        fn apply(self: Rc<Self>, _notion: Rc<Add>) -> Rc<Self> {
+            Self(self.0 + 1).into()
+        }
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Step), observed)]
+    struct Chain(u32);
+
+    impl WithNotion<Step> for Chain {
+        fn apply(self: Rc<Self>, _notion: Rc<Step>) -> Rc<Self> {
+            Self(self.0 + 1).into()
+        }
+    }
+
+    // Each step applies the next step while it is applied, until the chain has 50 steps.
+    impl Observed for Chain {
+        fn changed(self: Rc<Self>) {
+            if self.0 < 50 {
+                let apply_step = APPLY_STEP.with(|m| m.borrow().clone()).unwrap();
+                apply_step(Step);
+            }
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let total = use_atom_value::<Total>();
+        let chain = use_atom_value::<Chain>();
+        let apply_notions = use_batch_notion_applier();
+        let apply_step = use_notion_applier::<Step>();
+
+        APPLY_STEP.with(|m| *m.borrow_mut() = Some(apply_step.clone()));
+
+        let on_add_click = Callback::from(move |_| {
+            apply_notions((0..2000).fold(NotionBatch::new(), |m, _| m.with(Add)))
+        });
+        let on_step_click = Callback::from(move |_| apply_step(Step));
+
+        html! {
+            <div>
+                <div id="total">{total.0}</div>
+                <div id="chain">{chain.0}</div>
+                <button id="add" onclick={on_add_click}>{"Add"}</button>
+                <button id="step" onclick={on_step_click}>{"Step"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#total").await;
+    assert_eq!(s, "0");
+
+    // A large batch is not a cycle.
+    document()
+        .query_selector("#add")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#total").await;
+    assert_eq!(s, "2000");
+
+    // A chain that ends is not a cycle.
+    document()
+        .query_selector("#step")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+
+    let s = get_text_content("#chain").await;
+    assert_eq!(s, "50");
+}
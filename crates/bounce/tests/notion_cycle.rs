@@ -0,0 +1,98 @@
+// Cycles are only reported with a panic in debug builds.
+#![cfg(debug_assertions)]
+
+// This test is in its own file as the panic leaves the root in an unusable state.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+#[test]
+// Notions applied in a batch are reported with their type name.
+#[should_panic(expected = "Ping is applied by a chain of more than 100 notions")]
+async fn test_notion_cycle() {
+    type PingApplier = Rc<dyn Fn(NotionBatch)>;
+    type PongApplier = Rc<dyn Fn(Pong)>;
+
+    thread_local! {
+        static APPLY_PING: RefCell<Option<PingApplier>> = RefCell::default();
+        static APPLY_PONG: RefCell<Option<PongApplier>> = RefCell::default();
+    }
+
+    struct Ping;
+    struct Pong;
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Ping), observed)]
+    struct Pings(u64);
+
+    impl WithNotion<Ping> for Pings {
+        fn apply(self: Rc<Self>, _notion: Rc<Ping>) -> Rc<Self> {
+            Self(self.0 + 1).into()
+        }
+    }
+
+    impl Observed for Pings {
+        fn changed(self: Rc<Self>) {
+            let apply_pong = APPLY_PONG.with(|m| m.borrow().clone()).unwrap();
+            apply_pong(Pong);
+        }
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(Pong), observed)]
+    struct Pongs(u64);
+
+    impl WithNotion<Pong> for Pongs {
+        fn apply(self: Rc<Self>, _notion: Rc<Pong>) -> Rc<Self> {
+            Self(self.0 + 1).into()
+        }
+    }
+
+    impl Observed for Pongs {
+        fn changed(self: Rc<Self>) {
+            let apply_ping = APPLY_PING.with(|m| m.borrow().clone()).unwrap();
+            apply_ping(NotionBatch::new().with(Ping));
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let _pings = use_atom_value::<Pings>();
+        let _pongs = use_atom_value::<Pongs>();
+        let apply_ping = use_batch_notion_applier();
+        let apply_pong = use_notion_applier::<Pong>();
+
+        APPLY_PING.with(|m| *m.borrow_mut() = Some(apply_ping));
+        APPLY_PONG.with(|m| *m.borrow_mut() = Some(apply_pong));
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+
+    let apply_ping = APPLY_PING.with(|m| m.borrow().clone()).unwrap();
+    apply_ping(NotionBatch::new().with(Ping));
+}
@@ -0,0 +1,358 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static PERSISTED: RefCell<Vec<String>> = RefCell::default();
+}
+
+#[test]
+async fn test_observed_filter() {
+    enum DraftAction {
+        Type(char),
+        Save,
+    }
+
+    #[derive(Slice, PartialEq, Default)]
+    #[bounce(observed(if = "Self::should_persist"))]
+    struct Draft {
+        text: String,
+        saved: bool,
+    }
+
+    impl Draft {
+        fn should_persist(prev: &Self, next: &Self) -> bool {
+            !prev.saved && next.saved
+        }
+    }
+
+    impl Reducible for Draft {
+        type Action = DraftAction;
+
+        fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+            match action {
+                DraftAction::Type(c) => Self {
+                    text: format!("{}{}", self.text, c),
+                    saved: false,
+                },
+                DraftAction::Save => Self {
+                    text: self.text.clone(),
+                    saved: true,
+                },
+            }
+            .into()
+        }
+    }
+
+    impl Observed for Draft {
+        fn changed(self: Rc<Self>) {
+            PERSISTED.with(|m| m.borrow_mut().push(self.text.clone()));
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let draft = use_slice::<Draft>();
+
+        let type_a = {
+            let draft = draft.clone();
+            Callback::from(move |_| draft.dispatch(DraftAction::Type('a')))
+        };
+        let save = {
+            let draft = draft.clone();
+            Callback::from(move |_| draft.dispatch(DraftAction::Save))
+        };
+
+        html! {
+            <div>
+                <div id="text">{draft.text.clone()}</div>
+                <button id="type" onclick={type_a}>{"Type"}</button>
+                <button id="save" onclick={save}>{"Save"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    let click = |selector: &str| {
+        let el: web_sys::HtmlElement = wasm_bindgen::JsCast::unchecked_into(
+            document().query_selector(selector).unwrap().unwrap(),
+        );
+        el.click();
+    };
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    click("#type");
+    click("#type");
+    let s = get_text_content("#text").await;
+    assert_eq!(s, "aa");
+
+    // Listeners are notified, but the observer is not.
+    assert!(PERSISTED.with(|m| m.borrow().is_empty()));
+
+    click("#save");
+    click("#type");
+    let s = get_text_content("#text").await;
+    assert_eq!(s, "aaa");
+
+    assert_eq!(
+        PERSISTED.with(|m| m.borrow().clone()),
+        vec!["aa".to_string()]
+    );
+}
+
+thread_local! {
+    static THEMES: RefCell<Vec<String>> = RefCell::default();
+}
+
+#[test]
+async fn test_set_if_changed() {
+    #[derive(Atom, PartialEq)]
+    #[bounce(observed)]
+    struct Theme(String);
+
+    impl Default for Theme {
+        fn default() -> Self {
+            Self("light".into())
+        }
+    }
+
+    impl Observed for Theme {
+        fn changed(self: Rc<Self>) {
+            THEMES.with(|m| m.borrow_mut().push(self.0.clone()));
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let theme = use_atom::<Theme>();
+        let set_theme = use_atom_setter_if_changed::<Theme>();
+
+        use_effect_with((), move |_| {
+            assert!(!set_theme(Theme("light".into())));
+            assert!(set_theme(Theme("dark".into())));
+            assert!(!set_theme(Theme("dark".into())));
+            || {}
+        });
+
+        let set_light = {
+            let theme = theme.clone();
+            Callback::from(move |_| {
+                assert!(theme.set_if_changed(Theme("light".into())));
+            })
+        };
+
+        html! {
+            <div>
+                <div id="theme">{theme.0.clone()}</div>
+                <button id="light" onclick={set_light}>{"Light"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#theme").await;
+    assert_eq!(s, "dark");
+    assert_eq!(
+        THEMES.with(|m| m.borrow().clone()),
+        vec!["dark".to_string()]
+    );
+
+    let el: web_sys::HtmlElement =
+        wasm_bindgen::JsCast::unchecked_into(document().query_selector("#light").unwrap().unwrap());
+    el.click();
+
+    let s = get_text_content("#theme").await;
+    assert_eq!(s, "light");
+    assert_eq!(
+        THEMES.with(|m| m.borrow().clone()),
+        vec!["dark".to_string(), "light".to_string()]
+    );
+}
+
+thread_local! {
+    static VOLUMES: RefCell<Vec<u8>> = RefCell::default();
+}
+
+#[test]
+async fn test_slice_dispatch_if_changed() {
+    enum VolumeAction {
+        Set(u8),
+    }
+
+    #[derive(Slice, PartialEq, Default)]
+    #[bounce(observed)]
+    struct Volume(u8);
+
+    impl Reducible for Volume {
+        type Action = VolumeAction;
+
+        fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+            match action {
+                VolumeAction::Set(m) => Self(m).into(),
+            }
+        }
+    }
+
+    impl Observed for Volume {
+        fn changed(self: Rc<Self>) {
+            VOLUMES.with(|m| m.borrow_mut().push(self.0));
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let volume = use_slice::<Volume>();
+        let set_volume = use_slice_dispatch_if_changed::<Volume>();
+
+        use_effect_with((), move |_| {
+            assert!(!set_volume(VolumeAction::Set(0)));
+            assert!(set_volume(VolumeAction::Set(5)));
+            assert!(!set_volume(VolumeAction::Set(5)));
+            || {}
+        });
+
+        let mute = {
+            let volume = volume.clone();
+            Callback::from(move |_| {
+                assert!(volume.dispatch_if_changed(VolumeAction::Set(0)));
+            })
+        };
+
+        html! {
+            <div>
+                <div id="volume">{volume.0}</div>
+                <button id="mute" onclick={mute}>{"Mute"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#volume").await;
+    assert_eq!(s, "5");
+    assert_eq!(VOLUMES.with(|m| m.borrow().clone()), vec![5]);
+
+    let el: web_sys::HtmlElement =
+        wasm_bindgen::JsCast::unchecked_into(document().query_selector("#mute").unwrap().unwrap());
+    el.click();
+
+    let s = get_text_content("#volume").await;
+    assert_eq!(s, "0");
+    assert_eq!(VOLUMES.with(|m| m.borrow().clone()), vec![5, 0]);
+}
+
+thread_local! {
+    static LIFECYCLE: RefCell<Vec<String>> = RefCell::default();
+}
+
+#[test]
+async fn test_observed_lifecycle() {
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(observed)]
+    struct Connection {
+        value: u64,
+    }
+
+    impl Observed for Connection {
+        fn init(self: Rc<Self>) {
+            LIFECYCLE.with(|m| m.borrow_mut().push(format!("init {}", self.value)));
+        }
+
+        fn changed(self: Rc<Self>) {
+            LIFECYCLE.with(|m| m.borrow_mut().push(format!("changed {}", self.value)));
+        }
+
+        fn teardown(self: Rc<Self>) {
+            LIFECYCLE.with(|m| m.borrow_mut().push(format!("teardown {}", self.value)));
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let conn = use_atom::<Connection>();
+
+        {
+            let conn = conn.clone();
+            use_effect_with((), move |_| {
+                conn.set(Connection { value: 1 });
+                || {}
+            });
+        }
+
+        html! { <div id="conn-value">{conn.value}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    let handle =
+        yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+            .render();
+
+    let s = get_text_content("#conn-value").await;
+    assert_eq!(s, "1");
+
+    handle.destroy();
+    sleep(Duration::ZERO).await;
+
+    let events = LIFECYCLE.with(|m| m.borrow().clone());
+    assert_eq!(events, vec!["init 0", "changed 1", "teardown 1"]);
+}
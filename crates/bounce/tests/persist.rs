@@ -0,0 +1,338 @@
+#![cfg(feature = "persist")]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::persist::{
+    use_persist, use_persist_family, HostStorage, PersistStorage, Persisted, PersistedFamily,
+};
+use bounce::prelude::*;
+use bounce::{BounceRoot, PersistGate, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_persist_host_storage() {
+    thread_local! {
+        static STORE: RefCell<HashMap<String, String>> = RefCell::default();
+    }
+
+    pub enum NotesAction {
+        Add(&'static str),
+    }
+
+    #[derive(Slice, Serialize, Deserialize, PartialEq, Default)]
+    struct Notes {
+        items: Vec<String>,
+    }
+
+    impl Reducible for Notes {
+        type Action = NotesAction;
+
+        fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+            match action {
+                NotesAction::Add(m) => {
+                    let mut items = self.items.clone();
+                    items.push(m.to_string());
+                    Self { items }.into()
+                }
+            }
+        }
+    }
+
+    impl Persisted for Notes {
+        const KEY: &'static str = "notes";
+    }
+
+    #[function_component(NotesPersist)]
+    fn notes_persist() -> Html {
+        use_persist::<Notes>();
+
+        Html::default()
+    }
+
+    #[function_component(Editor)]
+    fn editor() -> Html {
+        let notes = use_slice::<Notes>();
+
+        {
+            let notes = notes.clone();
+            use_effect_with((), move |_| {
+                notes.dispatch(NotesAction::Add("new"));
+                || {}
+            });
+        }
+
+        html! { <div id="notes">{notes.items.join(",")}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            // A host bridge that answers asynchronously.
+            let storage: Rc<dyn PersistStorage> = Rc::new(HostStorage::new(
+                |key| async move {
+                    sleep(Duration::from_millis(10)).await;
+                    Ok(STORE.with(|m| m.borrow().get(&key).cloned()))
+                },
+                |key, value| async move {
+                    sleep(Duration::from_millis(10)).await;
+                    STORE.with(|m| m.borrow_mut().insert(key, value));
+                    Ok(())
+                },
+            ));
+
+            Services::new().provide_rc(storage)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <NotesPersist />
+                <PersistGate loading={html! {<div id="notes">{"Loading..."}</div>}}>
+                    <Editor />
+                </PersistGate>
+            </BounceRoot>
+        }
+    }
+
+    STORE.with(|m| {
+        m.borrow_mut()
+            .insert("notes".to_string(), r#"{"items":["saved"]}"#.to_string())
+    });
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#notes").await;
+    assert_eq!(s, "Loading...");
+
+    // The stored value is restored before the editor is rendered.
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#notes").await;
+    assert_eq!(s, "saved,new");
+
+    // Changes are written back to the storage.
+    sleep(Duration::from_millis(50)).await;
+    let stored = STORE.with(|m| m.borrow().get("notes").cloned());
+    assert_eq!(stored.as_deref(), Some(r#"{"items":["saved","new"]}"#));
+}
+
+#[test]
+async fn test_persist_family() {
+    thread_local! {
+        static STORE: RefCell<HashMap<String, String>> = RefCell::default();
+        static READS: RefCell<Vec<String>> = RefCell::default();
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq)]
+    struct Draft {
+        text: String,
+    }
+
+    impl AtomFamily for Draft {
+        type Key = u64;
+
+        fn create(_key: &u64) -> Self {
+            Self {
+                text: "empty".to_string(),
+            }
+        }
+    }
+
+    impl PersistedFamily for Draft {
+        fn storage_key(key: &u64) -> String {
+            format!("drafts/{}", key)
+        }
+    }
+
+    #[function_component(DraftsPersist)]
+    fn drafts_persist() -> Html {
+        use_persist_family::<Draft>();
+
+        Html::default()
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct EditorProps {
+        id: u64,
+    }
+
+    #[function_component(Editor)]
+    fn editor(props: &EditorProps) -> Html {
+        let draft = use_atom_family::<Draft>(props.id);
+
+        {
+            let draft = draft.clone();
+            use_effect_with(props.id, move |id| {
+                // The second draft is edited before it is restored.
+                if *id == 2 {
+                    draft.set(Draft {
+                        text: "edited".to_string(),
+                    });
+                }
+                || {}
+            });
+        }
+
+        html! { <div id={format!("draft-{}", props.id)}>{&draft.text}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let storage: Rc<dyn PersistStorage> = Rc::new(HostStorage::new(
+                |key| async move {
+                    sleep(Duration::from_millis(10)).await;
+                    READS.with(|m| m.borrow_mut().push(key.clone()));
+                    Ok(STORE.with(|m| m.borrow().get(&key).cloned()))
+                },
+                |key, value| async move {
+                    STORE.with(|m| m.borrow_mut().insert(key, value));
+                    Ok(())
+                },
+            ));
+
+            Services::new().provide_rc(storage)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <DraftsPersist />
+                <Editor id={1} />
+                <Editor id={2} />
+            </BounceRoot>
+        }
+    }
+
+    STORE.with(|m| {
+        let mut m = m.borrow_mut();
+        m.insert("drafts/1".to_string(), r#"{"text":"saved 1"}"#.to_string());
+        m.insert("drafts/2".to_string(), r#"{"text":"saved 2"}"#.to_string());
+        m.insert("drafts/3".to_string(), r#"{"text":"saved 3"}"#.to_string());
+    });
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#draft-1").await;
+    assert_eq!(s, "saved 1");
+    // The value that has been set before the key is restored is kept and written.
+    let s = get_text_content("#draft-2").await;
+    assert_eq!(s, "edited");
+    let stored = STORE.with(|m| m.borrow().get("drafts/2").cloned());
+    assert_eq!(stored.as_deref(), Some(r#"{"text":"edited"}"#));
+
+    // Keys that are not accessed are not read.
+    let mut reads = READS.with(|m| m.borrow().clone());
+    reads.sort();
+    assert_eq!(reads, vec!["drafts/1".to_string(), "drafts/2".to_string()]);
+}
+
+#[test]
+async fn test_persist_changed_before_restore() {
+    thread_local! {
+        static STORE: RefCell<HashMap<String, String>> = RefCell::default();
+    }
+
+    #[derive(Slice, Serialize, Deserialize, PartialEq, Default)]
+    struct Counter {
+        inner: u32,
+    }
+
+    impl Reducible for Counter {
+        type Action = u32;
+
+        fn reduce(self: Rc<Self>, action: u32) -> Rc<Self> {
+            Self { inner: action }.into()
+        }
+    }
+
+    impl Persisted for Counter {
+        const KEY: &'static str = "counter";
+    }
+
+    #[function_component(CounterPersist)]
+    fn counter_persist() -> Html {
+        use_persist::<Counter>();
+
+        Html::default()
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let counter = use_slice::<Counter>();
+
+        {
+            let counter = counter.clone();
+            // The counter is changed before it is restored.
+            use_effect_with((), move |_| {
+                counter.dispatch(2);
+                || {}
+            });
+        }
+
+        html! { <div id="counter">{counter.inner}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let storage: Rc<dyn PersistStorage> = Rc::new(HostStorage::new(
+                |key| async move {
+                    sleep(Duration::from_millis(10)).await;
+                    Ok(STORE.with(|m| m.borrow().get(&key).cloned()))
+                },
+                |key, value| async move {
+                    STORE.with(|m| m.borrow_mut().insert(key, value));
+                    Ok(())
+                },
+            ));
+
+            Services::new().provide_rc(storage)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <CounterPersist />
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    STORE.with(|m| {
+        m.borrow_mut()
+            .insert("counter".to_string(), r#"{"inner":1}"#.to_string())
+    });
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // The value that has been set before the slice is restored is kept and written.
+    let s = get_text_content("#counter").await;
+    assert_eq!(s, "2");
+    let stored = STORE.with(|m| m.borrow().get("counter").cloned());
+    assert_eq!(stored.as_deref(), Some(r#"{"inner":2}"#));
+}
@@ -0,0 +1,166 @@
+#![cfg(all(feature = "query", feature = "ssr", feature = "prepared-compression"))]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::prelude::*;
+use bounce::query::{use_prepared_query, PreparedTransform, Query, QueryResult};
+use bounce::{BounceConfig, BounceRoot, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+thread_local! {
+    static RUNS: Cell<u32> = const { Cell::new(0) };
+    static ENCODED: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[error("Something that will never happen")]
+struct Never {}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct ReportQuery {
+    rows: Vec<u64>,
+}
+
+/// The number of rows in a report, large enough for the payload to be compressed.
+const ROWS: usize = 1000;
+
+#[async_trait(?Send)]
+impl Query for ReportQuery {
+    type Input = u64;
+    type Error = Never;
+
+    async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+        RUNS.with(|m| m.set(m.get() + 1));
+        sleep(Duration::from_millis(10)).await;
+
+        Ok(ReportQuery {
+            rows: vec![*input; ROWS],
+        }
+        .into())
+    }
+}
+
+/// How the payload is restored on the client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Restore {
+    AsIs,
+    /// The payload is replaced with bytes that are not a payload.
+    Garbage,
+    /// The payload is cut in half.
+    Truncated,
+}
+
+/// Records the encoded payload and restores it as configured.
+struct Recorder {
+    restore: Restore,
+}
+
+impl PreparedTransform for Recorder {
+    fn encode(&self, payload: Vec<u8>) -> Vec<u8> {
+        ENCODED.with(|m| *m.borrow_mut() = payload.clone());
+        payload
+    }
+
+    fn decode(&self, mut payload: Vec<u8>) -> Option<Vec<u8>> {
+        match self.restore {
+            Restore::AsIs => {}
+            Restore::Garbage => payload = vec![0xff; 16],
+            Restore::Truncated => payload.truncate(payload.len() / 2),
+        }
+
+        Some(payload)
+    }
+}
+
+#[function_component(Report)]
+fn report() -> HtmlResult {
+    let report = use_prepared_query::<ReportQuery>(7.into())?;
+    let rows = report.as_ref().map(|m| m.rows.clone()).unwrap_or_default();
+    let content = format!("{} {}", rows.len(), rows.iter().sum::<u64>());
+
+    Ok(html! { <div>{content}</div> })
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    restore: Restore,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    let get_services = {
+        let restore = props.restore;
+        Callback::from(move |_| {
+            Services::new()
+                .provide(BounceConfig::new().with_prepared_transform(Recorder { restore }))
+        })
+    };
+
+    html! {
+        <BounceRoot {get_services}>
+            <Suspense>
+                <Report />
+            </Suspense>
+        </BounceRoot>
+    }
+}
+
+/// Renders the root on the server, then hydrates it with the payload restored as configured.
+async fn render_and_hydrate(restore: Restore) -> String {
+    let s = yew::LocalServerRenderer::<Root>::with_props(RootProps {
+        restore: Restore::AsIs,
+    })
+    .render()
+    .await;
+
+    let output = document().query_selector("#output").unwrap().unwrap();
+    output.set_inner_html(&s);
+
+    let handle =
+        yew::Renderer::<Root>::with_root_and_props(output.clone(), RootProps { restore }).hydrate();
+
+    sleep(Duration::from_millis(50)).await;
+    let content = output.text_content().unwrap();
+    handle.destroy();
+
+    content
+}
+
+#[test]
+async fn test_prepared_codec_round_trip() {
+    RUNS.with(|m| m.set(0));
+
+    let content = render_and_hydrate(Restore::AsIs).await;
+
+    // The result is compressed on the server, postcard alone encodes each row with one byte.
+    let encoded = ENCODED.with(|m| m.borrow().clone());
+    assert_eq!(encoded.first(), Some(&1));
+    assert!(encoded.len() < ROWS);
+
+    // The decoded result equals the result of the server, so it is not fetched again.
+    assert_eq!(content, format!("{} {}", ROWS, ROWS as u64 * 7));
+    assert_eq!(RUNS.with(|m| m.get()), 1);
+}
+
+#[test]
+async fn test_prepared_codec_rejects_corrupt_payloads() {
+    for restore in [Restore::Garbage, Restore::Truncated] {
+        RUNS.with(|m| m.set(0));
+
+        let content = render_and_hydrate(restore).await;
+
+        // The payload is discarded without panicking and the query is fetched on the client.
+        assert_eq!(content, format!("{} {}", ROWS, ROWS as u64 * 7));
+        assert_eq!(RUNS.with(|m| m.get()), 2, "{restore:?}");
+    }
+}
@@ -0,0 +1,333 @@
+#![cfg(all(feature = "query", feature = "ssr"))]
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::prelude::*;
+use bounce::query::{
+    use_prepared_query, use_prepared_query_value, PreparedTransform, Query, QueryResult,
+    QueryValueState, SsrPolicy,
+};
+use bounce::{BounceConfig, BounceRoot, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+thread_local! {
+    static RUNS: Cell<u32> = const { Cell::new(0) };
+    static SKIPPED_RUNS: Cell<u32> = const { Cell::new(0) };
+    static SIGNED_RUNS: Cell<u32> = const { Cell::new(0) };
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[error("Something that will never happen")]
+struct Never {}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct UserQuery {
+    name: String,
+}
+
+#[async_trait(?Send)]
+impl Query for UserQuery {
+    type Input = u64;
+    type Error = Never;
+
+    async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+        RUNS.with(|m| m.set(m.get() + 1));
+        sleep(Duration::from_millis(10)).await;
+
+        Ok(UserQuery {
+            name: format!("user-{input}"),
+        }
+        .into())
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct UserProps {
+    id: u64,
+}
+
+#[function_component(User)]
+fn user(props: &UserProps) -> HtmlResult {
+    let user = use_prepared_query::<UserQuery>(props.id.into())?;
+    let name = user.as_ref().map(|m| m.name.clone()).unwrap_or_default();
+
+    Ok(html! { <div>{name}</div> })
+}
+
+#[function_component(UserValue)]
+fn user_value(props: &UserProps) -> HtmlResult {
+    let user = use_prepared_query_value::<UserQuery>(props.id.into())?;
+    let name = match user.state() {
+        QueryValueState::Completed { result: Ok(m) }
+        | QueryValueState::Refreshing { last_result: Ok(m) } => m.name.clone(),
+        QueryValueState::Loading => "loading".to_string(),
+        _ => unreachable!(),
+    };
+
+    Ok(html! { <div>{name}</div> })
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    reversed: bool,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    let (first, second) = if props.reversed { (2, 1) } else { (1, 2) };
+
+    html! {
+        <BounceRoot>
+            <Suspense>
+                <User id={first} />
+                <User id={second} />
+                <UserValue id={first + 2} />
+                <UserValue id={second + 2} />
+            </Suspense>
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_prepared_results_paired_by_input() {
+    let s = yew::LocalServerRenderer::<Root>::with_props(RootProps { reversed: false })
+        .render()
+        .await;
+    assert_eq!(RUNS.with(|m| m.get()), 4);
+
+    let output = document().query_selector("#output").unwrap().unwrap();
+    output.set_inner_html(&s);
+
+    // The client renders the components in a different order than the server, so each hook
+    // is paired with the prepared result of another input.
+    let _handle =
+        yew::Renderer::<Root>::with_root_and_props(output.clone(), RootProps { reversed: true })
+            .hydrate();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // The results are loaded for the inputs they were prepared with and none are fetched again.
+    assert_eq!(output.text_content().unwrap(), "user-2user-1user-4user-3");
+    assert_eq!(RUNS.with(|m| m.get()), 4);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct SkippedQuery {
+    name: String,
+}
+
+#[async_trait(?Send)]
+impl Query for SkippedQuery {
+    type Input = u64;
+    type Error = Never;
+
+    const SSR: SsrPolicy = SsrPolicy::Skip;
+
+    async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+        SKIPPED_RUNS.with(|m| m.set(m.get() + 1));
+        sleep(Duration::from_millis(10)).await;
+
+        Ok(SkippedQuery {
+            name: format!("skipped-{input}"),
+        }
+        .into())
+    }
+}
+
+#[function_component(Skipped)]
+fn skipped(props: &UserProps) -> HtmlResult {
+    let skipped = use_prepared_query::<SkippedQuery>(props.id.into())?;
+    let name = skipped.as_ref().map(|m| m.name.clone()).unwrap_or_default();
+
+    Ok(html! { <div>{name}</div> })
+}
+
+#[function_component(SkippedValue)]
+fn skipped_value(props: &UserProps) -> HtmlResult {
+    let skipped = use_prepared_query_value::<SkippedQuery>(props.id.into())?;
+    let name = match skipped.state() {
+        QueryValueState::Completed { result: Ok(m) }
+        | QueryValueState::Refreshing { last_result: Ok(m) } => m.name.clone(),
+        QueryValueState::Loading => "loading".to_string(),
+        _ => unreachable!(),
+    };
+
+    Ok(html! { <div>{name}</div> })
+}
+
+#[function_component(SkippedRoot)]
+fn skipped_root() -> Html {
+    html! {
+        <BounceRoot>
+            <Suspense>
+                <SkippedValue id={1} />
+                <Skipped id={2} />
+            </Suspense>
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_prepared_skipped_queries() {
+    let s = yew::LocalServerRenderer::<SkippedRoot>::new()
+        .render()
+        .await;
+
+    let output = document().query_selector("#output").unwrap().unwrap();
+    output.set_inner_html(&s);
+
+    // The value hook renders as loading on the server without fetching the query. The suspending
+    // hook still waits for its result.
+    assert_eq!(output.text_content().unwrap(), "loadingskipped-2");
+    assert_eq!(SKIPPED_RUNS.with(|m| m.get()), 1);
+
+    let _handle = yew::Renderer::<SkippedRoot>::with_root(output.clone()).hydrate();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // No result is embedded, both queries are fetched on the client.
+    assert_eq!(output.text_content().unwrap(), "skipped-1skipped-2");
+    assert_eq!(SKIPPED_RUNS.with(|m| m.get()), 3);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+struct SignedQuery {
+    name: String,
+}
+
+#[async_trait(?Send)]
+impl Query for SignedQuery {
+    type Input = u64;
+    type Error = Never;
+
+    async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+        SIGNED_RUNS.with(|m| m.set(m.get() + 1));
+        sleep(Duration::from_millis(10)).await;
+
+        Ok(SignedQuery {
+            name: format!("signed-{input}"),
+        }
+        .into())
+    }
+}
+
+/// Appends a checksum of the payload and a key to the payload.
+struct SignTransform {
+    key: u8,
+}
+
+impl SignTransform {
+    fn sign(&self, payload: &[u8]) -> u8 {
+        payload
+            .iter()
+            .fold(self.key, |sum, m| sum.wrapping_mul(31).wrapping_add(*m))
+    }
+}
+
+impl PreparedTransform for SignTransform {
+    fn encode(&self, mut payload: Vec<u8>) -> Vec<u8> {
+        payload.push(self.sign(&payload));
+        payload
+    }
+
+    fn decode(&self, mut payload: Vec<u8>) -> Option<Vec<u8>> {
+        let signature = payload.pop()?;
+        (signature == self.sign(&payload)).then_some(payload)
+    }
+}
+
+#[function_component(Signed)]
+fn signed(props: &UserProps) -> HtmlResult {
+    let signed = use_prepared_query::<SignedQuery>(props.id.into())?;
+    let name = signed.as_ref().map(|m| m.name.clone()).unwrap_or_default();
+
+    Ok(html! { <div>{name}</div> })
+}
+
+#[derive(Properties, PartialEq)]
+struct SignedRootProps {
+    key: u8,
+}
+
+#[function_component(SignedRoot)]
+fn signed_root(props: &SignedRootProps) -> Html {
+    let get_services = {
+        let key = props.key;
+        Callback::from(move |_| {
+            Services::new()
+                .provide(BounceConfig::new().with_prepared_transform(SignTransform { key }))
+        })
+    };
+
+    html! {
+        <BounceRoot {get_services}>
+            <Suspense>
+                <Signed id={1} />
+                <Signed id={2} />
+            </Suspense>
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_prepared_transform() {
+    let s = yew::LocalServerRenderer::<SignedRoot>::with_props(SignedRootProps { key: 1 })
+        .render()
+        .await;
+    assert_eq!(SIGNED_RUNS.with(|m| m.get()), 2);
+
+    let output = document().query_selector("#output").unwrap().unwrap();
+    output.set_inner_html(&s);
+
+    // The client restores the payloads with the same transform, no query is fetched again.
+    let handle = yew::Renderer::<SignedRoot>::with_root_and_props(
+        output.clone(),
+        SignedRootProps { key: 1 },
+    )
+    .hydrate();
+
+    sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(output.text_content().unwrap(), "signed-1signed-2");
+    assert_eq!(SIGNED_RUNS.with(|m| m.get()), 2);
+
+    handle.destroy();
+}
+
+#[test]
+async fn test_prepared_transform_rejects_tampered_payloads() {
+    SIGNED_RUNS.with(|m| m.set(0));
+
+    let s = yew::LocalServerRenderer::<SignedRoot>::with_props(SignedRootProps { key: 1 })
+        .render()
+        .await;
+    assert_eq!(SIGNED_RUNS.with(|m| m.get()), 2);
+
+    let output = document().query_selector("#output").unwrap().unwrap();
+    output.set_inner_html(&s);
+
+    // The signatures do not match the key of the client, so the payloads are discarded and the
+    // queries are fetched on the client.
+    let handle = yew::Renderer::<SignedRoot>::with_root_and_props(
+        output.clone(),
+        SignedRootProps { key: 2 },
+    )
+    .hydrate();
+
+    sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(output.text_content().unwrap(), "signed-1signed-2");
+    assert_eq!(SIGNED_RUNS.with(|m| m.get()), 4);
+
+    handle.destroy();
+}
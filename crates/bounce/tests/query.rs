@@ -1,7 +1,9 @@
 #![cfg(feature = "query")]
 
+use std::cell::RefCell;
 use std::convert::Infallible;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
@@ -10,8 +12,15 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 use async_trait::async_trait;
 use bounce::prelude::*;
-use bounce::query::{use_query_value, Query, QueryResult};
-use bounce::BounceRoot;
+use bounce::query::{
+    use_a11y_status_announcer, use_mutation, use_query, use_query_client, use_query_value,
+    use_query_value_with_policy, use_query_with_timeout, A11yAnnouncer, A11yMessages,
+    DependencyInputs, DependencyResults, DependentQuery, ErrorClass, FetchPolicy, MockQueries,
+    Mutation, MutationResult, MutationState, ProgressSink, Query, QueryClient, QueryErrorNotion,
+    QueryMissing, QueryMocks, QueryOptions, QueryRead, QueryResult, QuerySubscriptionsOverlay,
+    UseQueryValueHandle,
+};
+use bounce::{AbortToken, BounceConfig, BounceRoot, Clock, ManualClock, Services};
 use gloo::timers::future::sleep;
 use gloo::utils::document;
 use yew::platform::spawn_local;
@@ -103,3 +112,3299 @@ async fn test_query_requery_upon_state_change() {
     let s = get_text_content("#content").await;
     assert_eq!(s, "value: 1");
 }
+
+#[test]
+async fn test_mutation_run_latest() {
+    thread_local! {
+        // The input of each save and whether it has returned a result.
+        static RESULTS: RefCell<Vec<(u64, bool)>> = RefCell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct SaveMutation {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveMutation {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn run(
+            _states: &BounceStates,
+            input: Rc<u64>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            // Earlier saves take longer to complete.
+            sleep(Duration::from_millis(100 / *input)).await;
+
+            Ok(SaveMutation { inner: *input }.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let save = use_mutation::<SaveMutation>();
+
+        {
+            let save = save.clone();
+            use_effect_with((), move |_| {
+                for i in 1..=2 {
+                    let save = save.clone();
+                    spawn_local(async move {
+                        let result = save.run_latest(i).await;
+                        RESULTS.with(|m| m.borrow_mut().push((i, result.is_some())));
+                    });
+                }
+
+                || {}
+            });
+        }
+
+        match save.result() {
+            None => {
+                html! { <div id="content">{"Saving..."}</div> }
+            }
+            Some(Ok(m)) => {
+                html! { <div id="content">{format!("saved: {}", m.inner)}</div> }
+            }
+            Some(Err(_)) => unreachable!(),
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(75)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "saved: 2");
+
+    sleep(Duration::from_millis(75)).await;
+
+    // The result of the earlier save is discarded.
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "saved: 2");
+
+    // Only the result of the latest save is returned.
+    let mut results = RESULTS.with(|m| m.borrow().clone());
+    results.sort_unstable();
+    assert_eq!(results, [(1, false), (2, true)]);
+}
+
+#[test]
+async fn test_mutation_progress() {
+    #[derive(PartialEq, Eq)]
+    pub struct UploadMutation;
+
+    #[async_trait(?Send)]
+    impl Mutation for UploadMutation {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn run(
+            _states: &BounceStates,
+            _input: Rc<()>,
+            progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            sleep(Duration::from_millis(50)).await;
+            progress.report(0.5);
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(UploadMutation.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let upload = use_mutation::<UploadMutation>();
+
+        {
+            let upload = upload.clone();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    let _result = upload.run(()).await;
+                });
+
+                || {}
+            });
+        }
+
+        let content = match upload.state() {
+            MutationState::Idle | MutationState::Loading => "loading".to_string(),
+            MutationState::InProgress { progress } => format!("progress: {}", progress),
+            MutationState::Completed { .. } => {
+                format!("completed, progress: {:?}", upload.progress())
+            }
+            MutationState::Refreshing { .. } => unreachable!(),
+        };
+
+        html! { <div id="content">{content}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(25)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "loading");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "progress: 0.5");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "completed, progress: None");
+}
+
+#[test]
+async fn test_dependent_query() {
+    #[derive(PartialEq, Eq)]
+    pub struct UserQuery {
+        team_id: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(UserQuery {
+                team_id: *input * 10,
+            }
+            .into())
+        }
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct TeamQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl DependentQuery for TeamQuery {
+        type Input = u64;
+        type Error = Infallible;
+        type DependsOn = (UserQuery,);
+
+        fn dependency_inputs(input: &u64) -> DependencyInputs<Self> {
+            (Rc::new(*input),)
+        }
+
+        async fn query(
+            _states: &BounceStates,
+            _input: Rc<u64>,
+            (user,): DependencyResults<Self>,
+        ) -> Result<Self, Infallible> {
+            let user = user?;
+
+            Ok(TeamQuery {
+                name: format!("team {}", user.team_id),
+            })
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let team = use_query_value::<TeamQuery>(1.into());
+
+        match team.result() {
+            None => html! { <div id="content">{"loading"}</div> },
+            Some(Ok(m)) => html! { <div id="content">{&m.name}</div> },
+            Some(Err(_)) => unreachable!(),
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(25)).await;
+
+    // The dependent query is loading until its dependency has resolved.
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "loading");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "team 10");
+}
+
+#[test]
+async fn test_dependent_query_refresh_and_errors() {
+    thread_local! {
+        static USER_RUNS: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+        static TEAM_ATTEMPTS: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+        static USER: RefCell<Option<UseQueryValueHandle<UserQuery>>> = RefCell::default();
+    }
+
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    pub enum ApiError {
+        #[error("user {0} not found")]
+        NotFound(u64),
+        #[error("team server is unavailable")]
+        Unavailable,
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct UserQuery {
+        team_id: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = u64;
+        type Error = ApiError;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(10)).await;
+
+            if *input == 2 {
+                return Err(ApiError::NotFound(2));
+            }
+
+            let runs = USER_RUNS.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            Ok(UserQuery { team_id: runs * 10 }.into())
+        }
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct TeamQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl DependentQuery for TeamQuery {
+        type Input = u64;
+        type Error = ApiError;
+        type DependsOn = (UserQuery,);
+
+        // The first attempt fails, so the team is only resolved if this is mirrored to `Query`.
+        const RETRIES: u32 = 1;
+
+        fn dependency_inputs(input: &u64) -> DependencyInputs<Self> {
+            (Rc::new(*input),)
+        }
+
+        async fn query(
+            _states: &BounceStates,
+            _input: Rc<u64>,
+            (user,): DependencyResults<Self>,
+        ) -> Result<Self, ApiError> {
+            let user = user?;
+
+            let attempts = TEAM_ATTEMPTS.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            if attempts == 1 {
+                return Err(ApiError::Unavailable);
+            }
+
+            Ok(TeamQuery {
+                name: format!("team {}", user.team_id),
+            })
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct CompProps {
+        input: u64,
+    }
+
+    #[function_component(Comp)]
+    fn comp(props: &CompProps) -> Html {
+        let user = use_query_value::<UserQuery>(props.input.into());
+        let team = use_query_value::<TeamQuery>(props.input.into());
+
+        if props.input == 1 {
+            USER.with(|m| *m.borrow_mut() = Some(user));
+        }
+
+        let content = match team.result() {
+            None => "loading".to_string(),
+            Some(Ok(m)) => m.name.clone(),
+            Some(Err(e)) => e.to_string(),
+        };
+
+        html! { <div id={format!("team-{}", props.input)}>{content}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp input={1} />
+                <Comp input={2} />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(get_text_content("#team-1").await, "team 10");
+    // The error of the dependency is returned by the dependent query.
+    assert_eq!(get_text_content("#team-2").await, "user 2 not found");
+    assert_eq!(TEAM_ATTEMPTS.with(|m| m.get()), 2);
+
+    // Refreshing the dependency fetches the dependent query again.
+    let user = USER.with(|m| m.borrow().clone()).unwrap();
+    spawn_local(async move {
+        let _result = user.refresh().await;
+    });
+
+    sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(get_text_content("#team-1").await, "team 20");
+    assert_eq!(USER_RUNS.with(|m| m.get()), 2);
+}
+
+#[test]
+async fn test_read_query() {
+    #[derive(PartialEq, Eq)]
+    pub struct CountQuery {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for CountQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(CountQuery { inner: 3 }.into())
+        }
+    }
+
+    #[derive(PartialEq)]
+    struct CountLabel(String);
+
+    impl Selector for CountLabel {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            let label = match states.read_query::<CountQuery>(().into()) {
+                QueryRead::NotStarted => "not started".to_string(),
+                QueryRead::Loading => "loading".to_string(),
+                QueryRead::Ready(m) => format!("count: {}", m.inner),
+                QueryRead::Failed(_) => unreachable!(),
+            };
+
+            Self(label).into()
+        }
+    }
+
+    #[function_component(Label)]
+    fn label() -> Html {
+        let label = use_selector_value::<CountLabel>();
+
+        html! { <div id="content">{&label.0}</div> }
+    }
+
+    #[function_component(Fetcher)]
+    fn fetcher() -> Html {
+        let _count = use_query_value::<CountQuery>(().into());
+
+        Html::default()
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let fetching = use_state(|| false);
+
+        {
+            let fetching = fetching.clone();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(50)).await;
+                    fetching.set(true);
+                });
+
+                || {}
+            });
+        }
+
+        html! {
+            <BounceRoot>
+                <Label />
+                if *fetching {
+                    <Fetcher />
+                }
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(25)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "not started");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "loading");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "count: 3");
+}
+
+#[test]
+async fn test_query_suspension_timeout() {
+    #[derive(PartialEq, Eq)]
+    pub struct SlowQuery {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for SlowQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(200)).await;
+
+            Ok(SlowQuery { inner: *input }.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> HtmlResult {
+        let slow = use_query_with_timeout::<SlowQuery>(1.into(), Duration::from_millis(50))?;
+
+        Ok(match slow {
+            Ok(m) => {
+                html! { <div id="content">{format!("value: {}", m.as_ref().unwrap().inner)}</div> }
+            }
+            Err(_) => html! { <div id="content">{"Timed out."}</div> },
+        })
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let fallback = html! { <div id="content">{"Loading..."}</div> };
+
+        html! {
+            <BounceRoot>
+                <Suspense {fallback}>
+                    <Comp />
+                </Suspense>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Loading...");
+
+    sleep(Duration::from_millis(100)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Timed out.");
+
+    sleep(Duration::from_millis(200)).await;
+
+    // The result is rendered once the query completes.
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "value: 1");
+}
+
+#[test]
+async fn test_query_client_remove() {
+    thread_local! {
+        static FETCHES: std::cell::Cell<u64> = std::cell::Cell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct ProjectQuery {
+        fetch: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            let fetch = FETCHES.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            Ok(ProjectQuery { fetch }.into())
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ProjectProps {
+        id: u64,
+    }
+
+    #[function_component(Project)]
+    fn project(props: &ProjectProps) -> Html {
+        let project = use_query_value::<ProjectQuery>(props.id.into());
+        let fetch = project
+            .result()
+            .map(|m| m.as_ref().unwrap().fetch.to_string())
+            .unwrap_or_default();
+
+        html! { <div id={format!("project-{}", props.id)}>{fetch}</div> }
+    }
+
+    #[function_component(Inputs)]
+    fn inputs() -> Html {
+        let client = use_query_client();
+        let inputs = use_state(String::new);
+
+        {
+            let inputs = inputs.clone();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(50)).await;
+
+                    let mut cached = client
+                        .cached_inputs::<ProjectQuery>()
+                        .into_iter()
+                        .map(|m| *m)
+                        .collect::<Vec<_>>();
+                    cached.sort_unstable();
+                    inputs.set(format!("{cached:?}"));
+
+                    client.remove::<ProjectQuery>(1);
+                });
+
+                || {}
+            });
+        }
+
+        html! { <div id="inputs">{(*inputs).clone()}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Project id={1} />
+                <Project id={2} />
+                <Inputs />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(25)).await;
+
+    let mut fetches = [
+        get_text_content("#project-1").await,
+        get_text_content("#project-2").await,
+    ];
+    fetches.sort_unstable();
+    assert_eq!(fetches, ["1", "2"]);
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#inputs").await;
+    assert_eq!(s, "[1, 2]");
+
+    // Only the removed query is fetched again.
+    let s = get_text_content("#project-1").await;
+    assert_eq!(s, "3");
+}
+
+#[test]
+async fn test_query_client_remove_suspended_query() {
+    thread_local! {
+        static FETCHES: std::cell::Cell<u64> = std::cell::Cell::default();
+        static RENDERS: std::cell::Cell<u64> = std::cell::Cell::default();
+        static CLIENT: RefCell<Option<QueryClient>> = RefCell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct ProjectQuery {
+        fetch: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            let fetch = FETCHES.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+            sleep(Duration::from_millis(10)).await;
+
+            Ok(ProjectQuery { fetch }.into())
+        }
+    }
+
+    #[function_component(Project)]
+    fn project() -> HtmlResult {
+        let project = use_query::<ProjectQuery>(1.into())?;
+        let fetch = project.as_ref().unwrap().fetch;
+        RENDERS.with(|m| m.set(m.get() + 1));
+
+        Ok(html! { <div id="project">{fetch}</div> })
+    }
+
+    #[function_component(Client)]
+    fn client() -> Html {
+        let client = use_query_client();
+
+        use_effect_with((), move |_| {
+            CLIENT.with(|m| *m.borrow_mut() = Some(client));
+
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let fallback = html! { <div id="project">{"loading"}</div> };
+
+        html! {
+            <BounceRoot>
+                <Client />
+                <Suspense {fallback}>
+                    <Project />
+                </Suspense>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#project").await, "1");
+    let renders = RENDERS.with(|m| m.get());
+
+    CLIENT
+        .with(|m| m.borrow().clone())
+        .unwrap()
+        .remove::<ProjectQuery>(1);
+
+    // The component suspends while the removed query is fetched again.
+    sleep(Duration::from_millis(5)).await;
+    assert_eq!(get_text_content("#project").await, "loading");
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#project").await, "2");
+    assert_eq!(FETCHES.with(|m| m.get()), 2);
+    assert!(RENDERS.with(|m| m.get()) > renders);
+}
+
+#[test]
+async fn test_query_client_clear_cache() {
+    thread_local! {
+        static FETCHES: std::cell::Cell<u64> = std::cell::Cell::default();
+        static CLIENT: RefCell<Option<QueryClient>> = RefCell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct ProjectQuery {
+        fetch: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            let fetch = FETCHES.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            Ok(ProjectQuery { fetch }.into())
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct SaveMutation;
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveMutation {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn run(
+            _states: &BounceStates,
+            _input: Rc<()>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            Ok(SaveMutation.into())
+        }
+    }
+
+    #[function_component(Project)]
+    fn project() -> HtmlResult {
+        let project = use_query::<ProjectQuery>(1.into())?;
+        let fetch = project.as_ref().unwrap().fetch;
+
+        Ok(html! { <div id="project">{fetch}</div> })
+    }
+
+    #[function_component(Save)]
+    fn save() -> Html {
+        let save = use_mutation::<SaveMutation>();
+        let client = use_query_client();
+
+        {
+            let save = save.clone();
+            use_effect_with((), move |_| {
+                CLIENT.with(|m| *m.borrow_mut() = Some(client));
+                spawn_local(async move {
+                    let _result = save.run(()).await;
+                });
+
+                || {}
+            });
+        }
+
+        let content = match save.result() {
+            None => "idle",
+            Some(_) => "saved",
+        };
+
+        html! { <div id="save">{content}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let fallback = html! { <div id="project">{"loading"}</div> };
+
+        html! {
+            <BounceRoot>
+                <Save />
+                <Suspense {fallback}>
+                    <Project />
+                </Suspense>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#project").await, "1");
+    assert_eq!(get_text_content("#save").await, "saved");
+
+    CLIENT.with(|m| m.borrow().clone()).unwrap().clear_cache();
+
+    // The suspended query is fetched again and the mutation handle is reset.
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#project").await, "2");
+    assert_eq!(get_text_content("#save").await, "idle");
+    assert_eq!(FETCHES.with(|m| m.get()), 2);
+}
+
+#[test]
+async fn test_query_value_cached_first_render() {
+    thread_local! {
+        static LOADED: std::cell::RefCell<Vec<u64>> = std::cell::RefCell::default();
+        static RELOADING_RENDERS: std::cell::Cell<u64> = std::cell::Cell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct ProjectQuery {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(10)).await;
+
+            Ok(ProjectQuery { inner: *input }.into())
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ProjectProps {
+        id: u64,
+    }
+
+    #[function_component(Project)]
+    fn project(props: &ProjectProps) -> Html {
+        let client = use_query_client();
+        let project = use_query_value::<ProjectQuery>(props.id.into());
+
+        // Both read the cache in the same render.
+        assert_eq!(
+            project.result().is_some(),
+            client.read_cached::<ProjectQuery>(props.id).is_some()
+        );
+
+        match project.result() {
+            Some(m) => {
+                LOADED.with(|m| m.borrow_mut().push(props.id));
+                html! { <div id="content">{m.as_ref().unwrap().inner}</div> }
+            }
+            None => {
+                if LOADED.with(|m| m.borrow().contains(&props.id)) {
+                    RELOADING_RENDERS.with(|m| m.set(m.get() + 1));
+                }
+                html! { <div id="content">{"Loading..."}</div> }
+            }
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let id = use_state(|| 1);
+
+        {
+            let id = id.setter();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(50)).await;
+                    id.set(2);
+                    sleep(Duration::from_millis(50)).await;
+                    id.set(1);
+                });
+
+                || {}
+            });
+        }
+
+        html! {
+            <BounceRoot>
+                <Project id={*id} />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(25)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "1");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "2");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "1");
+
+    // The cached result is rendered immediately when the input changes back.
+    assert_eq!(RELOADING_RENDERS.with(|m| m.get()), 0);
+}
+
+#[test]
+async fn test_query_error_notion() {
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    #[error("failed to connect")]
+    pub struct ConnectError;
+
+    #[derive(PartialEq, Eq)]
+    pub struct FailingQuery;
+
+    #[async_trait(?Send)]
+    impl Query for FailingQuery {
+        type Input = ();
+        type Error = ConnectError;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            Err(ConnectError)
+        }
+
+        fn classify_error(_error: &ConnectError) -> ErrorClass {
+            ErrorClass::Network
+        }
+    }
+
+    #[derive(PartialEq, Default, Atom)]
+    #[bounce(with_notion(QueryErrorNotion))]
+    struct LastError {
+        inner: Option<(ErrorClass, String)>,
+    }
+
+    impl WithNotion<QueryErrorNotion> for LastError {
+        fn apply(self: Rc<Self>, notion: Rc<QueryErrorNotion>) -> Rc<Self> {
+            Self {
+                inner: Some((notion.class, notion.message.clone())),
+            }
+            .into()
+        }
+    }
+
+    #[function_component(Fetcher)]
+    fn fetcher() -> Html {
+        let _failing = use_query_value::<FailingQuery>(().into());
+
+        Html::default()
+    }
+
+    #[function_component(Banner)]
+    fn banner() -> Html {
+        let last_error = use_atom_value::<LastError>();
+
+        match last_error.inner {
+            Some((class, ref message)) => {
+                html! { <div id="content">{format!("{class:?}: {message}")}</div> }
+            }
+            None => html! { <div id="content">{"No error."}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Banner />
+                <Fetcher />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Network: failed to connect");
+}
+
+#[test]
+async fn test_query_tracks_states_changed_while_running() {
+    #[derive(PartialEq, Eq, Default, Atom)]
+    pub struct Filter {
+        inner: usize,
+    }
+
+    #[derive(PartialEq, Eq, Default, Atom)]
+    pub struct Label {
+        inner: &'static str,
+    }
+
+    #[derive(PartialEq, Eq, Default)]
+    pub struct FilteredQuery {
+        inner: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for FilteredQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let filter = states.get_atom_value::<Filter>().inner;
+            let label = states.untracked(|states| states.get_atom_value::<Label>().inner);
+
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(FilteredQuery {
+                inner: format!("{label} {filter}"),
+            }
+            .into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let query = use_query_value::<FilteredQuery>(().into());
+        let set_filter = use_atom_setter();
+        let set_label = use_atom_setter();
+
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                // Changes while the query is running.
+                sleep(Duration::from_millis(10)).await;
+                set_filter(Filter { inner: 1 });
+
+                sleep(Duration::from_millis(200)).await;
+                set_label(Label { inner: "label" });
+            });
+
+            || {}
+        });
+
+        match query.result() {
+            None => {
+                html! { <div id="content">{"Loading..."}</div> }
+            }
+            Some(Ok(m)) => {
+                html! { <div id="content">{format!("value:{}", m.inner)}</div> }
+            }
+            Some(Err(_)) => unreachable!(),
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Loading...");
+
+    // The first result is outdated when it completes and the query runs again.
+    sleep(Duration::from_millis(150)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "value: 1");
+
+    // Untracked states do not cause the query to run again.
+    sleep(Duration::from_millis(200)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "value: 1");
+}
+
+#[test]
+async fn test_mutation_cache_key() {
+    thread_local! {
+        static RUNS: std::cell::Cell<u64> = std::cell::Cell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct EnsureMutation {
+        run: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Mutation for EnsureMutation {
+        type Input = &'static str;
+        type Error = Infallible;
+
+        async fn run(
+            _states: &BounceStates,
+            _input: Rc<&'static str>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            let run = RUNS.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(EnsureMutation { run }.into())
+        }
+
+        fn cache_key(input: &&'static str) -> Option<String> {
+            Some(input.to_string())
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct CompProps {
+        id: &'static str,
+        input: &'static str,
+    }
+
+    #[function_component(Comp)]
+    fn comp(props: &CompProps) -> Html {
+        let ensure = use_mutation::<EnsureMutation>();
+
+        {
+            let ensure = ensure.clone();
+            use_effect_with(props.input, move |input| {
+                let input = *input;
+                spawn_local(async move {
+                    let _result = ensure.run(input).await;
+                });
+
+                || {}
+            });
+        }
+
+        match ensure.result() {
+            None => html! { <div id={props.id}>{"Running..."}</div> },
+            Some(Ok(m)) => html! { <div id={props.id}>{format!("run: {}", m.run)}</div> },
+            Some(Err(_)) => unreachable!(),
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp id="a" input="workspace" />
+                <Comp id="b" input="workspace" />
+                <Comp id="c" input="other" />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(100)).await;
+
+    // Runs with the same key share the result of the run in flight.
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "run: 1");
+    let s = get_text_content("#b").await;
+    assert_eq!(s, "run: 1");
+    let s = get_text_content("#c").await;
+    assert_eq!(s, "run: 2");
+    assert_eq!(RUNS.with(|m| m.get()), 2);
+}
+
+#[test]
+async fn test_query_retries_overridden_by_config() {
+    thread_local! {
+        static ATTEMPTS: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+    }
+
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    #[error("failed to connect")]
+    pub struct ConnectError;
+
+    #[derive(PartialEq, Eq)]
+    pub struct FlakyQuery {
+        attempts: u32,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for FlakyQuery {
+        type Input = ();
+        type Error = ConnectError;
+
+        // Not enough to succeed without the override.
+        const RETRIES: u32 = 1;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let attempts = ATTEMPTS.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            if attempts < 3 {
+                return Err(ConnectError);
+            }
+
+            Ok(Self { attempts }.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let flaky = use_query_value::<FlakyQuery>(().into());
+
+        match flaky.result() {
+            None => html! { <div id="content">{"Loading..."}</div> },
+            Some(Ok(m)) => html! { <div id="content">{format!("attempts: {}", m.attempts)}</div> },
+            Some(Err(e)) => html! { <div id="content">{e.to_string()}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide(
+                BounceConfig::new()
+                    .with_query_options::<FlakyQuery>(|m| QueryOptions { retries: 2, ..m }),
+            )
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "attempts: 3");
+}
+
+#[test]
+async fn test_query_fetch_pool_limit() {
+    thread_local! {
+        static RUNNING: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+        static MAX_RUNNING: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct SlowQuery {
+        value: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for SlowQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            let running = RUNNING.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+            MAX_RUNNING.with(|m| m.set(m.get().max(running)));
+
+            sleep(Duration::from_millis(20)).await;
+
+            RUNNING.with(|m| m.set(m.get() - 1));
+
+            Ok(Self { value: *input }.into())
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ItemProps {
+        id: u64,
+    }
+
+    #[function_component(Item)]
+    fn item(props: &ItemProps) -> Html {
+        let slow = use_query_value::<SlowQuery>(props.id.into());
+
+        match slow.result() {
+            Some(Ok(m)) => html! { <span>{m.value}</span> },
+            _ => Html::default(),
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide(BounceConfig::new().with_fetch_pool_limit(2))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <div id="content">
+                    {for (1..=5).map(|id| html! { <Item {id} /> })}
+                </div>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(200)).await;
+
+    // All queries complete, but at most 2 are fetched at the same time.
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "12345");
+    assert_eq!(MAX_RUNNING.with(|m| m.get()), 2);
+}
+
+#[test]
+async fn test_query_fetch_limit() {
+    thread_local! {
+        static RUNNING: RefCell<[u32; 2]> = const { RefCell::new([0; 2]) };
+        static MAX_RUNNING: RefCell<[u32; 2]> = const { RefCell::new([0; 2]) };
+    }
+
+    async fn fetch(kind: usize) {
+        let running = RUNNING.with(|m| {
+            let mut m = m.borrow_mut();
+            m[kind] += 1;
+            m[kind]
+        });
+        MAX_RUNNING.with(|m| {
+            let mut m = m.borrow_mut();
+            m[kind] = m[kind].max(running);
+        });
+
+        sleep(Duration::from_millis(20)).await;
+
+        RUNNING.with(|m| m.borrow_mut()[kind] -= 1);
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct SearchQuery {
+        value: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for SearchQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        const FETCH_LIMIT: Option<usize> = Some(1);
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            fetch(0).await;
+
+            Ok(Self { value: *input }.into())
+        }
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct ItemQuery {
+        value: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ItemQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            fetch(1).await;
+
+            Ok(Self { value: *input }.into())
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ItemProps {
+        id: u64,
+    }
+
+    #[function_component(Item)]
+    fn item(props: &ItemProps) -> Html {
+        let search = use_query_value::<SearchQuery>(props.id.into());
+        let item = use_query_value::<ItemQuery>(props.id.into());
+
+        match (search.result(), item.result()) {
+            (Some(Ok(m)), Some(Ok(n))) => html! { <span>{m.value}{n.value}</span> },
+            _ => Html::default(),
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide(
+                BounceConfig::new()
+                    .with_query_options::<ItemQuery>(|m| QueryOptions {
+                        fetch_limit: Some(2),
+                        ..m
+                    })
+                    .with_fetch_pool_limit(4),
+            )
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <div id="content">
+                    {for (1..=4).map(|id| html! { <Item {id} /> })}
+                </div>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(300)).await;
+
+    // All queries complete, fetches of each query type are limited separately.
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "11223344");
+    assert_eq!(MAX_RUNNING.with(|m| *m.borrow()), [1, 2]);
+}
+
+#[test]
+async fn test_mock_queries() {
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    #[error("user not found")]
+    pub struct NotFound;
+
+    #[derive(PartialEq, Eq)]
+    pub struct UserQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = u64;
+        type Error = NotFound;
+
+        async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            unreachable!("mocked queries are not run");
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct UserProps {
+        id: u64,
+    }
+
+    #[function_component(User)]
+    fn user(props: &UserProps) -> Html {
+        let user = use_query_value::<UserQuery>(props.id.into());
+        let id = format!("user-{}", props.id);
+
+        match user.result() {
+            None => html! { <div {id}>{"Loading..."}</div> },
+            Some(Ok(m)) => html! { <div {id}>{m.name.clone()}</div> },
+            Some(Err(e)) => html! { <div {id}>{e.to_string()}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_mocks = Callback::from(|_| {
+            QueryMocks::new()
+                .with_latency(Duration::from_millis(50))
+                .with_query::<UserQuery, _>(|id| match id {
+                    1 => Ok(UserQuery {
+                        name: "Jane Doe".into(),
+                    }
+                    .into()),
+                    _ => Err(NotFound),
+                })
+        });
+
+        html! {
+            <BounceRoot>
+                <MockQueries {get_mocks}>
+                    <User id={1} />
+                    <User id={2} />
+                </MockQueries>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#user-1").await;
+    assert_eq!(s, "Loading...");
+
+    sleep(Duration::from_millis(100)).await;
+    let s = get_text_content("#user-1").await;
+    assert_eq!(s, "Jane Doe");
+    let s = get_text_content("#user-2").await;
+    assert_eq!(s, "user not found");
+}
+
+#[test]
+async fn test_mock_queries_nested() {
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    #[error("user not found")]
+    pub struct NotFound;
+
+    #[derive(PartialEq, Eq)]
+    pub struct UserQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = u64;
+        type Error = NotFound;
+
+        const RETRIES: u32 = 3;
+
+        async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            unreachable!("mocked queries are not run");
+        }
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct TeamQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for TeamQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            unreachable!("mocked queries are not run");
+        }
+    }
+
+    thread_local! {
+        static MISSING_RUNS: RefCell<u32> = RefCell::default();
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct UserProps {
+        id: u64,
+    }
+
+    #[function_component(User)]
+    fn user(props: &UserProps) -> Html {
+        let user = use_query_value::<UserQuery>(props.id.into());
+        let team = use_query_value::<TeamQuery>(().into());
+        let id = format!("user-{}", props.id);
+
+        let user = match user.result() {
+            None => "Loading...".to_string(),
+            Some(Ok(m)) => m.name.clone(),
+            Some(Err(e)) => e.to_string(),
+        };
+        let team = match team.result() {
+            Some(Ok(m)) => m.name.clone(),
+            _ => "Loading...".to_string(),
+        };
+
+        html! { <div {id}>{format!("{user}@{team}")}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_outer_mocks = Callback::from(|_| {
+            QueryMocks::new()
+                .with_query::<UserQuery, _>(|_| {
+                    Ok(UserQuery {
+                        name: "Jane Doe".into(),
+                    }
+                    .into())
+                })
+                .with_query::<TeamQuery, _>(|_| {
+                    Ok(TeamQuery {
+                        name: "core".into(),
+                    }
+                    .into())
+                })
+        });
+        let get_inner_mocks = Callback::from(|_| {
+            QueryMocks::new().with_query::<UserQuery, _>(|_| {
+                MISSING_RUNS.with(|m| *m.borrow_mut() += 1);
+                Err(NotFound)
+            })
+        });
+
+        html! {
+            <BounceRoot>
+                <MockQueries get_mocks={get_outer_mocks}>
+                    <User id={1} />
+                    <MockQueries get_mocks={get_inner_mocks}>
+                        <User id={2} />
+                    </MockQueries>
+                </MockQueries>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // Both users are fetched while the inner mocks are mounted, the inner mocks override the
+    // user query and the outer mocks still apply to the team query.
+    let s = get_text_content("#user-1").await;
+    assert_eq!(s, "user not found@core");
+    let s = get_text_content("#user-2").await;
+    assert_eq!(s, "user not found@core");
+
+    // The mocked error is not retried.
+    assert_eq!(MISSING_RUNS.with(|m| *m.borrow()), 2);
+}
+
+#[test]
+async fn test_a11y_status_announcer() {
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    #[error("failed to save")]
+    pub struct SaveError;
+
+    #[derive(PartialEq, Eq)]
+    pub struct UserQuery;
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(UserQuery.into())
+        }
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct SaveUser;
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveUser {
+        type Input = ();
+        type Error = SaveError;
+
+        async fn run(
+            _states: &BounceStates,
+            _input: Rc<()>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            sleep(Duration::from_millis(50)).await;
+
+            Err(SaveError)
+        }
+    }
+
+    #[function_component(User)]
+    fn user() -> Html {
+        let user = use_query_value::<UserQuery>(().into());
+        let save_user = use_mutation::<SaveUser>();
+
+        use_a11y_status_announcer(
+            user.state().into(),
+            A11yMessages::new()
+                .loading("Loading user")
+                .success("User loaded"),
+        );
+        use_a11y_status_announcer(
+            save_user.state().into(),
+            A11yMessages::new()
+                .success("User saved")
+                .error("Failed to save user"),
+        );
+
+        let onclick = Callback::from(move |_| {
+            let save_user = save_user.clone();
+            spawn_local(async move {
+                let _ = save_user.run(()).await;
+            });
+        });
+
+        html! { <button id="save" {onclick}>{"Save"}</button> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <div id="announcer">
+                    <A11yAnnouncer />
+                </div>
+                <User />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#announcer [role=status]").await;
+    assert_eq!(s, "Loading user");
+    let s = get_text_content("#announcer [role=alert]").await;
+    assert_eq!(s, "");
+
+    sleep(Duration::from_millis(100)).await;
+    let s = get_text_content("#announcer [role=status]").await;
+    assert_eq!(s, "User loaded");
+
+    let el: web_sys::HtmlElement =
+        wasm_bindgen::JsCast::unchecked_into(document().query_selector("#save").unwrap().unwrap());
+    el.click();
+
+    // The mutation has no loading message, so the status is not replaced.
+    let s = get_text_content("#announcer [role=status]").await;
+    assert_eq!(s, "User loaded");
+
+    sleep(Duration::from_millis(100)).await;
+    let s = get_text_content("#announcer [role=alert]").await;
+    assert_eq!(s, "Failed to save user");
+}
+
+#[test]
+async fn test_query_polling() {
+    thread_local! {
+        static CLOCK: ManualClock = ManualClock::new();
+    }
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct StatusQuery {
+        runs: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for StatusQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        const POLL_INTERVAL: Option<Duration> = Some(Duration::from_secs(10));
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let runs = RUNS.fetch_add(1, Ordering::SeqCst) + 1;
+
+            Ok(StatusQuery { runs }.into())
+        }
+    }
+
+    #[function_component(Status)]
+    fn status() -> Html {
+        let status = use_query_value::<StatusQuery>(().into());
+
+        match status.result() {
+            Some(Ok(m)) => html! { <div id="runs">{m.runs}</div> },
+            _ => html! { <div id="runs">{"Loading..."}</div> },
+        }
+    }
+
+    // Polls the same input, it shares the poller of `Status`.
+    #[function_component(StatusBadge)]
+    fn status_badge() -> Html {
+        let _status = use_query_value::<StatusQuery>(().into());
+
+        Html::default()
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new().provide_rc(clock)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Status />
+                <StatusBadge />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "1");
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(5)));
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "1");
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(5)));
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "2");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(10)));
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "3");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+async fn test_query_stale_on_mount() {
+    thread_local! {
+        static CLOCK: ManualClock = ManualClock::new();
+    }
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct ProfileQuery {
+        runs: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProfileQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        const STALE_TIME: Option<Duration> = Some(Duration::from_secs(10));
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let runs = RUNS.fetch_add(1, Ordering::SeqCst) + 1;
+
+            if runs > 1 {
+                sleep(Duration::from_millis(50)).await;
+            }
+
+            Ok(ProfileQuery { runs }.into())
+        }
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct ShowMore(bool);
+
+    #[derive(Properties, PartialEq)]
+    struct ProfileProps {
+        id: &'static str,
+    }
+
+    #[function_component(Profile)]
+    fn profile(props: &ProfileProps) -> Html {
+        let profile = use_query_value::<ProfileQuery>(().into());
+
+        match profile.result() {
+            Some(Ok(m)) => html! { <div id={props.id}>{m.runs}</div> },
+            _ => html! { <div id={props.id}>{"Loading..."}</div> },
+        }
+    }
+
+    #[function_component(UpdatedAt)]
+    fn updated_at() -> Html {
+        let client = use_query_client();
+        // Re-rendered with the profile.
+        let _profile = use_query_value::<ProfileQuery>(().into());
+
+        let updated_at = client
+            .updated_at::<ProfileQuery>(())
+            .map(|m| m.as_secs().to_string())
+            .unwrap_or_default();
+
+        html! { <div id="updated-at">{updated_at}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let show_more = use_atom_value::<ShowMore>();
+
+        html! {
+            <>
+                <Profile id="profile-1" />
+                <UpdatedAt />
+                if show_more.0 {
+                    <Profile id="profile-2" />
+                    <Profile id="profile-3" />
+                }
+            </>
+        }
+    }
+
+    #[function_component(ShowMoreSetter)]
+    fn show_more_setter() -> Html {
+        let set_show_more = use_atom_setter::<ShowMore>();
+
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                sleep(Duration::from_millis(20)).await;
+
+                CLOCK.with(|m| m.advance(Duration::from_secs(20)));
+                set_show_more(ShowMore(true));
+            });
+
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new().provide_rc(clock)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <App />
+                <ShowMoreSetter />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#profile-1").await;
+    assert_eq!(s, "1");
+    let s = get_text_content("#updated-at").await;
+    assert_eq!(s, "0");
+
+    // The stale result is served while it is refreshed in the background.
+    sleep(Duration::from_millis(20)).await;
+    let s = get_text_content("#profile-2").await;
+    assert_eq!(s, "1");
+
+    // Subscribers that are mounted at the same time share a single refresh.
+    sleep(Duration::from_millis(100)).await;
+    let s = get_text_content("#profile-3").await;
+    assert_eq!(s, "2");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+    let s = get_text_content("#updated-at").await;
+    assert_eq!(s, "20");
+}
+
+#[test]
+async fn test_query_fetch_duration() {
+    thread_local! {
+        static CLOCK: ManualClock = ManualClock::new();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct ReportQuery {
+        value: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ReportQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            CLOCK.with(|m| m.advance(Duration::from_secs(*input)));
+
+            Ok(Self { value: *input }.into())
+        }
+
+        fn describe_input(input: &u64) -> String {
+            format!("{:?}", input)
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ReportProps {
+        id: u64,
+    }
+
+    #[function_component(Report)]
+    fn report(props: &ReportProps) -> Html {
+        let client = use_query_client();
+        let report = use_query_value::<ReportQuery>(props.id.into());
+
+        let duration = client
+            .fetch_duration::<ReportQuery>(props.id)
+            .map(|m| m.as_secs().to_string())
+            .unwrap_or_default();
+
+        match report.result() {
+            Some(Ok(m)) => html! { <div id={format!("report-{}", m.value)}>{duration}</div> },
+            _ => Html::default(),
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new()
+                .provide_rc(clock)
+                .provide(BounceConfig::new().with_slow_query_threshold(Duration::from_secs(2)))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Report id={1} />
+                <Report id={3} />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // The fetch of the second report exceeds the threshold and is reported as slow.
+    let s = get_text_content("#report-1").await;
+    assert_eq!(s, "1");
+    let s = get_text_content("#report-3").await;
+    assert_eq!(s, "3");
+}
+
+#[test]
+async fn test_offline_demo() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct StatsQuery {
+        visits: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for StatsQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            RUNS.fetch_add(1, Ordering::SeqCst);
+
+            Ok(StatsQuery { visits: 0 }.into())
+        }
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct FeedQuery {
+        title: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for FeedQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            RUNS.fetch_add(1, Ordering::SeqCst);
+
+            Ok(FeedQuery {
+                title: "real".into(),
+            }
+            .into())
+        }
+    }
+
+    #[future_notion(FetchGreeting)]
+    async fn fetch_greeting(name: &&'static str) -> String {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+
+        format!("Hello, {}!", name)
+    }
+
+    #[derive(PartialEq, Default, Atom)]
+    #[bounce(with_notion(Deferred<FetchGreeting>))]
+    struct Greeting(String);
+
+    impl WithNotion<Deferred<FetchGreeting>> for Greeting {
+        fn apply(self: Rc<Self>, notion: Rc<Deferred<FetchGreeting>>) -> Rc<Self> {
+            match notion.output() {
+                Some(m) => Self(m.to_string()).into(),
+                None => self,
+            }
+        }
+    }
+
+    #[function_component(Dashboard)]
+    fn dashboard() -> Html {
+        let stats = use_query_value::<StatsQuery>(().into());
+        let feed = use_query_value::<FeedQuery>(().into());
+        let greeting = use_atom_value::<Greeting>();
+        let fetch_greeting = use_future_notion_runner::<FetchGreeting>();
+
+        use_effect_with((), move |_| {
+            fetch_greeting("Jane");
+            || {}
+        });
+
+        let stats = match stats.result() {
+            Some(Ok(m)) => m.visits.to_string(),
+            _ => "Loading...".to_string(),
+        };
+        let feed = match feed.result() {
+            Some(Ok(m)) => m.title.clone(),
+            _ => "Loading...".to_string(),
+        };
+
+        html! {
+            <>
+                <div id="demo-stats">{stats}</div>
+                <div id="demo-feed">{feed}</div>
+                <div id="demo-greeting">{greeting.0.clone()}</div>
+            </>
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide(BounceConfig::new().with_offline_demo(true))
+        });
+        let get_mocks = Callback::from(|_| {
+            QueryMocks::new()
+                .with_query::<FeedQuery, _>(|_| {
+                    Ok(FeedQuery {
+                        title: "mocked".into(),
+                    }
+                    .into())
+                })
+                .with_future_notion::<FetchGreeting, _>(|name| format!("Hi, {}!", name))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <MockQueries {get_mocks}>
+                    <Dashboard />
+                </MockQueries>
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // Mocks are served and the implementations that are not mocked are never run.
+    let s = get_text_content("#demo-feed").await;
+    assert_eq!(s, "mocked");
+    let s = get_text_content("#demo-greeting").await;
+    assert_eq!(s, "Hi, Jane!");
+    let s = get_text_content("#demo-stats").await;
+    assert_eq!(s, "Loading...");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+async fn test_query_subscriptions() {
+    #[derive(PartialEq, Eq)]
+    pub struct ItemQuery {
+        value: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ItemQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            Ok(Self { value: *input }.into())
+        }
+
+        fn describe_input(input: &u64) -> String {
+            format!("item {}", input)
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ItemProps {
+        name: &'static str,
+        id: u64,
+    }
+
+    #[function_component(Item)]
+    fn item(props: &ItemProps) -> Html {
+        let item = use_query_value::<ItemQuery>(props.id.into());
+
+        match item.result() {
+            Some(Ok(m)) => html! {
+                <div id={props.name}>{format!("{} {}", m.value, item.subscriber_count())}</div>
+            },
+            _ => Html::default(),
+        }
+    }
+
+    #[function_component(SuspendingItem)]
+    fn suspending_item() -> HtmlResult {
+        let item = use_query::<ItemQuery>(2.into())?;
+        let value = item.as_ref().map(|m| m.value).unwrap_or_default();
+
+        Ok(html! { <div id="d">{value}</div> })
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Item name="a" id={1} />
+                <Item name="b" id={1} />
+                <Item name="c" id={2} />
+                // Suspending hooks are subscribed as well.
+                <Suspense>
+                    <SuspendingItem />
+                </Suspense>
+                <QuerySubscriptionsOverlay class="overlay" interval={Duration::from_millis(10)} />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // Hooks with the same input share a subscription.
+    let s = get_text_content("#a").await;
+    assert_eq!(s, "1 2");
+    let s = get_text_content("#b").await;
+    assert_eq!(s, "1 2");
+    let s = get_text_content("#c").await;
+    assert_eq!(s, "2 2");
+    let s = get_text_content("#d").await;
+    assert_eq!(s, "2");
+
+    let rows = document().query_selector_all(".overlay tbody tr").unwrap();
+    assert_eq!(rows.length(), 2);
+
+    let s = get_text_content(".overlay tbody tr:first-child td:nth-child(2)").await;
+    assert_eq!(s, "item 1");
+    let s = get_text_content(".overlay tbody tr:first-child td:nth-child(3)").await;
+    assert_eq!(s, "2");
+    let s = get_text_content(".overlay tbody tr:first-child td:nth-child(4)").await;
+    assert_eq!(s, "0s ago");
+}
+
+#[test]
+async fn test_query_abort_token() {
+    thread_local! {
+        static RUNS: RefCell<Vec<(u64, AbortToken)>> = RefCell::default();
+    }
+
+    #[derive(PartialEq, Eq)]
+    pub struct SlowQuery {
+        run: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for SlowQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            let run = RUNS.with(|m| {
+                let mut m = m.borrow_mut();
+                m.push((*input, states.abort_token()));
+                m.len()
+            });
+
+            sleep(Duration::from_millis(50)).await;
+
+            Ok(SlowQuery { run }.into())
+        }
+    }
+
+    #[function_component(Refreshed)]
+    fn refreshed() -> Html {
+        let query = use_query_value::<SlowQuery>(1.into());
+
+        {
+            let query = query.clone();
+            use_effect_with((), move |_| {
+                // The refresh supersedes the run in flight.
+                spawn_local(async move {
+                    query.refresh().await.unwrap();
+                });
+
+                || {}
+            });
+        }
+
+        match query.result() {
+            Some(Ok(m)) => html! { <div id="refreshed">{m.run}</div> },
+            _ => html! { <div id="refreshed">{"Loading..."}</div> },
+        }
+    }
+
+    #[function_component(Unmounted)]
+    fn unmounted() -> Html {
+        let _query = use_query_value::<SlowQuery>(2.into());
+
+        Html::default()
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let mounted = use_state(|| true);
+
+        {
+            let mounted = mounted.clone();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(10)).await;
+                    mounted.set(false);
+                });
+
+                || {}
+            });
+        }
+
+        html! {
+            <BounceRoot>
+                <Refreshed />
+                if *mounted {
+                    <Unmounted />
+                }
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(30)).await;
+
+    let aborted = |input: u64| {
+        RUNS.with(|m| {
+            m.borrow()
+                .iter()
+                .filter(|(i, _)| *i == input)
+                .map(|(_, token)| token.is_aborted())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // The superseded run and the run of the unmounted component are aborted.
+    assert_eq!(aborted(1), vec![true, false]);
+    assert_eq!(aborted(2), vec![true]);
+
+    sleep(Duration::from_millis(50)).await;
+
+    // The result of the refresh is shown.
+    let latest_run = RUNS.with(|m| m.borrow().iter().rposition(|(i, _)| *i == 1).unwrap() + 1);
+    let s = get_text_content("#refreshed").await;
+    assert_eq!(s, latest_run.to_string());
+}
+
+#[test]
+async fn test_query_timeout() {
+    #[derive(PartialEq, Eq)]
+    pub struct SlowQuery {
+        timed_out: bool,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for SlowQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        const TIMEOUT: Option<Duration> = Some(Duration::from_millis(20));
+
+        async fn query(states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let token = states.abort_token();
+            futures::future::select(
+                Box::pin(sleep(Duration::from_millis(500))),
+                Box::pin(token.aborted()),
+            )
+            .await;
+
+            Ok(SlowQuery {
+                timed_out: token.is_aborted(),
+            }
+            .into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let query = use_query_value::<SlowQuery>(().into());
+
+        match query.result() {
+            Some(Ok(m)) => html! { <div id="content">{m.timed_out}</div> },
+            _ => html! { <div id="content">{"Loading..."}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Loading...");
+
+    // The query returns its own result when it has timed out.
+    sleep(Duration::from_millis(40)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "true");
+}
+
+#[test]
+async fn test_mutation_abort_token() {
+    static ABORTED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct SaveMutation {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveMutation {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn run(
+            states: &BounceStates,
+            input: Rc<u64>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            let token = states.abort_token();
+            futures::future::select(
+                Box::pin(sleep(Duration::from_millis(50))),
+                Box::pin(token.aborted()),
+            )
+            .await;
+
+            if token.is_aborted() {
+                ABORTED.fetch_add(1, Ordering::SeqCst);
+            }
+
+            Ok(SaveMutation { inner: *input }.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let save = use_mutation::<SaveMutation>();
+
+        {
+            let save = save.clone();
+            use_effect_with((), move |_| {
+                for i in 1..=2 {
+                    let save = save.clone();
+                    spawn_local(async move {
+                        let _result = save.run_latest(i).await;
+                    });
+                }
+
+                || {}
+            });
+        }
+
+        match save.result() {
+            Some(Ok(m)) => html! { <div id="content">{format!("saved: {}", m.inner)}</div> },
+            _ => html! { <div id="content">{"Saving..."}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+
+    // The earlier save is superseded by the later save.
+    assert_eq!(ABORTED.load(Ordering::SeqCst), 1);
+
+    sleep(Duration::from_millis(75)).await;
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "saved: 2");
+    assert_eq!(ABORTED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+async fn test_mutation_abort_token_on_unmount() {
+    static ABORTED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct SaveMutation {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveMutation {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn run(
+            states: &BounceStates,
+            input: Rc<u64>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            let token = states.abort_token();
+            futures::future::select(
+                Box::pin(sleep(Duration::from_millis(50))),
+                Box::pin(token.aborted()),
+            )
+            .await;
+
+            if token.is_aborted() {
+                ABORTED.fetch_add(1, Ordering::SeqCst);
+            }
+
+            Ok(SaveMutation { inner: *input }.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let save = use_mutation::<SaveMutation>();
+
+        {
+            let save = save.clone();
+            use_effect_with((), move |_| {
+                for i in 1..=2 {
+                    let save = save.clone();
+                    spawn_local(async move {
+                        let _result = save.run(i).await;
+                    });
+                }
+
+                || {}
+            });
+        }
+
+        html! { <div id="content">{"Saving..."}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    let handle =
+        yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+            .render();
+
+    sleep(Duration::from_millis(10)).await;
+
+    // Runs that are not latest-only do not supersede each other.
+    assert_eq!(ABORTED.load(Ordering::SeqCst), 0);
+
+    handle.destroy();
+    sleep(Duration::from_millis(10)).await;
+
+    // Both runs are cancelled when the handle is unmounted.
+    assert_eq!(ABORTED.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+async fn test_query_value_cache_only() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct ProjectQuery {
+        inner: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            RUNS.fetch_add(1, Ordering::Relaxed);
+            sleep(Duration::from_millis(10)).await;
+
+            Ok(ProjectQuery { inner: *input }.into())
+        }
+    }
+
+    #[function_component(Preview)]
+    fn preview() -> Html {
+        let project = use_query_value_with_policy::<ProjectQuery>(1.into(), FetchPolicy::CacheOnly);
+
+        match project.cached() {
+            Ok(m) => html! { <div id="preview">{m.as_ref().unwrap().inner}</div> },
+            Err(QueryMissing) => html! { <div id="preview">{"Missing"}</div> },
+        }
+    }
+
+    #[function_component(Project)]
+    fn project() -> Html {
+        let _project = use_query_value::<ProjectQuery>(1.into());
+
+        Html::default()
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let fetching = use_state(|| false);
+
+        {
+            let fetching = fetching.setter();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(50)).await;
+                    fetching.set(true);
+                });
+
+                || {}
+            });
+        }
+
+        html! {
+            <BounceRoot>
+                <Preview />
+                if *fetching {
+                    <Project />
+                }
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(25)).await;
+
+    // The query is not run by the cache-only hook.
+    let s = get_text_content("#preview").await;
+    assert_eq!(s, "Missing");
+    assert_eq!(RUNS.load(Ordering::Relaxed), 0);
+
+    sleep(Duration::from_millis(50)).await;
+
+    // The cache-only hook is updated when the query is run by another hook.
+    let s = get_text_content("#preview").await;
+    assert_eq!(s, "1");
+    assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+}
+
+fn dispatch_pageshow(persisted: bool) {
+    js_sys::Function::new_with_args(
+        "persisted",
+        "const e = new Event('pageshow');
+        Object.defineProperty(e, 'persisted', { value: persisted });
+        window.dispatchEvent(e);",
+    )
+    .call1(&wasm_bindgen::JsValue::NULL, &persisted.into())
+    .unwrap();
+}
+
+#[test]
+async fn test_query_refresh_on_restore() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct CartQuery {
+        runs: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for CartQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        const REFRESH_ON_RESTORE: bool = true;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let runs = RUNS.fetch_add(1, Ordering::SeqCst) + 1;
+
+            Ok(CartQuery { runs }.into())
+        }
+    }
+
+    #[function_component(Cart)]
+    fn cart() -> Html {
+        let cart = use_query_value::<CartQuery>(().into());
+
+        match cart.result() {
+            Some(Ok(m)) => html! { <div id="runs">{m.runs}</div> },
+            _ => html! { <div id="runs">{"Loading..."}</div> },
+        }
+    }
+
+    #[function_component(CartCount)]
+    fn cart_count() -> Html {
+        let cart = use_query_value::<CartQuery>(().into());
+
+        match cart.result() {
+            Some(Ok(m)) => html! { <div id="count">{m.runs}</div> },
+            _ => html! { <div id="count">{"Loading..."}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Cart />
+                <CartCount />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "1");
+
+    // A page that is loaded is not restored.
+    dispatch_pageshow(false);
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "1");
+
+    // The input is refreshed once, although two hooks are mounted with it.
+    dispatch_pageshow(true);
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "2");
+    let s = get_text_content("#count").await;
+    assert_eq!(s, "2");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+async fn test_query_cache_only_not_refreshed_on_restore() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct CartQuery {
+        runs: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for CartQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        const REFRESH_ON_RESTORE: bool = true;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let runs = RUNS.fetch_add(1, Ordering::SeqCst) + 1;
+
+            Ok(CartQuery { runs }.into())
+        }
+    }
+
+    #[function_component(Cart)]
+    fn cart() -> Html {
+        let cart = use_query_value_with_policy::<CartQuery>(().into(), FetchPolicy::CacheOnly);
+
+        match cart.result() {
+            Some(Ok(m)) => html! { <div id="runs">{m.runs}</div> },
+            _ => html! { <div id="runs">{"Empty"}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Cart />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // A cache-only hook does not fetch the query when the page is restored.
+    dispatch_pageshow(true);
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "Empty");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+async fn test_query_client_fetch_query() {
+    #[derive(PartialEq, Eq, Default, Atom)]
+    struct Version {
+        inner: u64,
+    }
+
+    type VersionSetter = Rc<dyn Fn(Version)>;
+
+    thread_local! {
+        static FETCHES: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+        static CLIENT: RefCell<Option<QueryClient>> = RefCell::default();
+        static SET_VERSION: RefCell<Option<VersionSetter>> = RefCell::default();
+    }
+
+    fn client() -> QueryClient {
+        CLIENT.with(|m| m.borrow().clone()).unwrap()
+    }
+
+    fn fetches() -> u64 {
+        FETCHES.with(|m| m.get())
+    }
+
+    #[derive(PartialEq, Eq)]
+    struct ProjectQuery {
+        fetch: u64,
+        version: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            let version = states.get_atom_value::<Version>().inner;
+            let fetch = FETCHES.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            sleep(Duration::from_millis(10)).await;
+
+            Ok(ProjectQuery { fetch, version }.into())
+        }
+    }
+
+    #[function_component(Project)]
+    fn project() -> Html {
+        let project = use_query_value::<ProjectQuery>(2.into());
+        let fetch = project
+            .result()
+            .map(|m| m.as_ref().unwrap().fetch.to_string())
+            .unwrap_or_default();
+
+        html! { <div id="project-2">{fetch}</div> }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let client = use_query_client();
+        let set_version = use_atom_setter::<Version>();
+        let show_project = use_state(|| false);
+
+        CLIENT.with(|m| *m.borrow_mut() = Some(client.clone()));
+        SET_VERSION.with(|m| *m.borrow_mut() = Some(set_version));
+
+        {
+            let show_project = show_project.setter();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(100)).await;
+                    show_project.set(true);
+                });
+
+                || {}
+            });
+        }
+
+        if *show_project {
+            html! { <Project /> }
+        } else {
+            Html::default()
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+
+    // Cold: the query is fetched.
+    let result = client().fetch_query::<ProjectQuery>(1).await.unwrap();
+    assert_eq!(result.fetch, 1);
+    assert_eq!(fetches(), 1);
+
+    // Cached: the cached result is returned.
+    let result = client().fetch_query::<ProjectQuery>(1).await.unwrap();
+    assert_eq!(result.fetch, 1);
+    assert_eq!(fetches(), 1);
+
+    // Outdated: a state read by the query has changed, so the query is refreshed.
+    SET_VERSION.with(|m| m.borrow().clone()).unwrap()(Version { inner: 1 });
+    sleep(Duration::ZERO).await;
+
+    let result = client().fetch_query::<ProjectQuery>(1).await.unwrap();
+    assert_eq!(result.fetch, 2);
+    assert_eq!(result.version, 1);
+    assert_eq!(fetches(), 2);
+
+    // Concurrent with a hook: the fetch waits for the query started by the hook.
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(fetches(), 3);
+
+    let result = client().fetch_query::<ProjectQuery>(2).await.unwrap();
+    assert_eq!(result.fetch, 3);
+    assert_eq!(fetches(), 3);
+    assert_eq!(get_text_content("#project-2").await, "3");
+
+    // Concurrent fetches share a run.
+    let (first, second) = futures::future::join(
+        client().fetch_query::<ProjectQuery>(3),
+        client().fetch_query::<ProjectQuery>(3),
+    )
+    .await;
+    assert_eq!(first.unwrap().fetch, 4);
+    assert_eq!(second.unwrap().fetch, 4);
+    assert_eq!(fetches(), 4);
+}
+
+#[test]
+async fn test_query_client_prefetch_query_when_idle() {
+    thread_local! {
+        static FETCHES: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+        static CLIENT: RefCell<Option<QueryClient>> = RefCell::default();
+    }
+
+    fn client() -> QueryClient {
+        CLIENT.with(|m| m.borrow().clone()).unwrap()
+    }
+
+    #[derive(PartialEq, Eq)]
+    struct ProjectQuery {
+        fetch: u64,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for ProjectQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+            let fetch = FETCHES.with(|m| {
+                m.set(m.get() + 1);
+                m.get()
+            });
+
+            Ok(ProjectQuery { fetch }.into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let client = use_query_client();
+        CLIENT.with(|m| *m.borrow_mut() = Some(client));
+
+        Html::default()
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::ZERO).await;
+
+    client().prefetch_query_when_idle::<ProjectQuery>(1);
+    // The query is not fetched until the browser is idle.
+    assert!(client().read_cached::<ProjectQuery>(1).is_none());
+
+    // Idle tasks are run after 2 seconds at the latest.
+    sleep(Duration::from_millis(2100)).await;
+    let result = client().read_cached::<ProjectQuery>(1).unwrap().unwrap();
+    assert_eq!(result.fetch, 1);
+
+    // The cached result is not fetched again.
+    client().prefetch_query_when_idle::<ProjectQuery>(1);
+    sleep(Duration::from_millis(2100)).await;
+    assert_eq!(FETCHES.with(|m| m.get()), 1);
+}
+
+#[test]
+async fn test_result_stability_check() {
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+    static FETCHES: AtomicUsize = AtomicUsize::new(0);
+
+    // Counts the warnings of the stability check.
+    struct WarningCounter;
+
+    impl Subscriber for WarningCounter {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let metadata = event.metadata();
+            if *metadata.level() == Level::WARN
+                && metadata.module_path() == Some("bounce::query::stability")
+            {
+                WARNINGS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[derive(PartialEq, Eq, Default, Atom)]
+    pub struct Version {
+        inner: u32,
+    }
+
+    // The result contains the number of the fetch, so it is never equal to the previous result.
+    #[derive(PartialEq, Eq)]
+    pub struct StampQuery {
+        fetch: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for StampQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let _version = states.get_atom_value::<Version>();
+
+            Ok(StampQuery {
+                fetch: FETCHES.fetch_add(1, Ordering::SeqCst),
+            }
+            .into())
+        }
+    }
+
+    type VersionSetter = Rc<dyn Fn(Version)>;
+
+    thread_local! {
+        static SET_VERSION: RefCell<Option<VersionSetter>> = RefCell::default();
+        static QUERY: RefCell<Option<UseQueryValueHandle<StampQuery>>> = RefCell::default();
+    }
+
+    async fn set_version(inner: u32) {
+        SET_VERSION.with(|m| m.borrow().clone()).unwrap()(Version { inner });
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    async fn refresh() {
+        let query = QUERY.with(|m| m.borrow().clone()).unwrap();
+        query.refresh().await.unwrap();
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let set_version = use_atom_setter::<Version>();
+        let query = use_query_value::<StampQuery>(().into());
+
+        SET_VERSION.with(|m| *m.borrow_mut() = Some(set_version));
+        QUERY.with(|m| *m.borrow_mut() = Some(query));
+
+        Html::default()
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct RootProps {
+        enabled: bool,
+    }
+
+    #[function_component(Root)]
+    fn root(props: &RootProps) -> Html {
+        let get_services = {
+            let enabled = props.enabled;
+            Callback::from(move |_| {
+                Services::new().provide(BounceConfig::new().with_result_stability_check(enabled))
+            })
+        };
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    let _guard = tracing::subscriber::set_default(WarningCounter);
+
+    // The check is opt-in.
+    let handle = yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps { enabled: false },
+    )
+    .render();
+    sleep(Duration::from_millis(10)).await;
+
+    for i in 1..=4 {
+        set_version(i).await;
+    }
+    assert_eq!(WARNINGS.load(Ordering::SeqCst), 0);
+
+    handle.destroy();
+
+    let _handle = yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps { enabled: true },
+    )
+    .render();
+    sleep(Duration::from_millis(10)).await;
+
+    // Refreshes are expected to return new data and are not checked.
+    for _ in 0..4 {
+        refresh().await;
+    }
+    assert_eq!(WARNINGS.load(Ordering::SeqCst), 0);
+
+    // The query is refetched when the state it has read changes.
+    for i in 1..=2 {
+        set_version(i).await;
+    }
+    assert_eq!(WARNINGS.load(Ordering::SeqCst), 0);
+
+    set_version(3).await;
+    assert_eq!(WARNINGS.load(Ordering::SeqCst), 1);
+
+    // The warning is only logged once.
+    set_version(4).await;
+    assert_eq!(WARNINGS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+async fn test_query_schedule_refresh() {
+    thread_local! {
+        static CLOCK: ManualClock = ManualClock::new();
+        static HANDLE: RefCell<Option<UseQueryValueHandle<StatusQuery>>> = RefCell::default();
+    }
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    pub struct StatusQuery {
+        runs: usize,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for StatusQuery {
+        type Input = ();
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            let runs = RUNS.fetch_add(1, Ordering::SeqCst) + 1;
+
+            Ok(StatusQuery { runs }.into())
+        }
+    }
+
+    fn handle() -> UseQueryValueHandle<StatusQuery> {
+        HANDLE.with(|m| m.borrow().clone()).unwrap()
+    }
+
+    async fn advance(duration: Duration) {
+        CLOCK.with(|m| m.advance(duration));
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    #[function_component(Status)]
+    fn status() -> Html {
+        let status = use_query_value::<StatusQuery>(().into());
+
+        {
+            let status = status.clone();
+            use_effect(move || {
+                HANDLE.with(|m| *m.borrow_mut() = Some(status));
+            });
+        }
+
+        match status.result() {
+            Some(Ok(m)) => html! { <div id="runs">{m.runs}</div> },
+            _ => html! { <div id="runs">{"Loading..."}</div> },
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new().provide_rc(clock)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Status />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "1");
+
+    // The query is refreshed once the delay has passed.
+    handle().schedule_refresh(Duration::from_secs(10));
+    advance(Duration::from_secs(5)).await;
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    advance(Duration::from_secs(5)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "2");
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+
+    // A cancelled refresh is not run.
+    handle().schedule_refresh(Duration::from_secs(10));
+    handle().cancel_scheduled_refresh();
+    advance(Duration::from_secs(10)).await;
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+
+    // Scheduling a refresh again replaces the earlier one.
+    handle().schedule_refresh(Duration::from_secs(10));
+    advance(Duration::from_secs(5)).await;
+    handle().schedule_refresh(Duration::from_secs(10));
+    advance(Duration::from_secs(5)).await;
+    assert_eq!(RUNS.load(Ordering::SeqCst), 2);
+
+    advance(Duration::from_secs(5)).await;
+    let s = get_text_content("#runs").await;
+    assert_eq!(s, "3");
+
+    // Only the replacing refresh is run.
+    advance(Duration::from_secs(20)).await;
+    assert_eq!(RUNS.load(Ordering::SeqCst), 3);
+}
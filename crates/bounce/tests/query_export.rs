@@ -0,0 +1,214 @@
+#![cfg(feature = "query-export")]
+
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::prelude::*;
+use bounce::query::{
+    use_query_client, use_query_value, Query, QueryCacheSeed, QueryClient, QueryResult,
+};
+use bounce::{BounceRoot, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static CLIENT: RefCell<Option<QueryClient>> = RefCell::default();
+}
+
+#[test]
+async fn test_query_cache_export_import() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct UserQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            Ok(UserQuery {
+                name: format!("User {input}"),
+            }
+            .into())
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TeamQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for TeamQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            Ok(TeamQuery {
+                name: format!("Team {input}"),
+            }
+            .into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let user = use_query_value::<UserQuery>(1.into());
+        let client = use_query_client();
+        CLIENT.with(|m| *m.borrow_mut() = Some(client));
+
+        match user.result() {
+            None => html! { <div id="content">{"Loading..."}</div> },
+            Some(Ok(m)) => html! { <div id="content">{m.name.clone()}</div> },
+            Some(Err(_)) => unreachable!(),
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide(
+                QueryCacheSeed::default()
+                    .with_exported_query::<UserQuery>("user")
+                    .with_exported_query::<TeamQuery>("team"),
+            )
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "User 1");
+
+    let client = CLIENT.with(|m| m.borrow().clone().unwrap());
+    assert_eq!(
+        client.export_cache(),
+        json!({ "user": [{ "input": 1, "value": { "name": "User 1" } }], "team": [] })
+    );
+
+    client
+        .import_cache(json!({
+            "user": [{ "input": 1, "value": { "name": "Jane Doe" } }],
+            "unknown": [],
+        }))
+        .unwrap();
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Jane Doe");
+
+    assert!(client
+        .import_cache(json!({ "user": [{ "input": "1" }] }))
+        .is_err());
+
+    // The document is validated before any of it is imported.
+    assert!(client
+        .import_cache(json!({
+            "user": [{ "input": 1, "value": { "name": "John Smith" } }],
+            "team": [{ "input": 1 }],
+        }))
+        .is_err());
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Jane Doe");
+}
+
+#[test]
+async fn test_query_cache_import_in_flight() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SlowQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for SlowQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            sleep(Duration::from_millis(100)).await;
+
+            Ok(SlowQuery {
+                name: format!("Slow {input}"),
+            }
+            .into())
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let slow = use_query_value::<SlowQuery>(1.into());
+        let client = use_query_client();
+        CLIENT.with(|m| *m.borrow_mut() = Some(client));
+
+        match slow.result() {
+            None => html! { <div id="content">{"Loading..."}</div> },
+            Some(Ok(m)) => html! { <div id="content">{m.name.clone()}</div> },
+            Some(Err(_)) => unreachable!(),
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new()
+                .provide(QueryCacheSeed::default().with_exported_query::<SlowQuery>("slow"))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(20)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Loading...");
+
+    let client = CLIENT.with(|m| m.borrow().clone().unwrap());
+    client
+        .import_cache(json!({ "slow": [{ "input": 1, "value": { "name": "Imported" } }] }))
+        .unwrap();
+
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Imported");
+
+    // The run in flight does not replace the imported result when it completes.
+    sleep(Duration::from_millis(150)).await;
+    let s = get_text_content("#content").await;
+    assert_eq!(s, "Imported");
+}
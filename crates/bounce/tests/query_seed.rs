@@ -0,0 +1,93 @@
+#![cfg(feature = "query-seed")]
+
+use std::convert::Infallible;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::prelude::*;
+use bounce::query::{use_query_value, Query, QueryCacheSeed, QueryResult};
+use bounce::{BounceRoot, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_query_cache_seed() {
+    const SEED: &str = r#"{ "user": [{ "input": 1, "value": { "name": "Jane Doe" } }] }"#;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct UserQuery {
+        name: String,
+    }
+
+    #[async_trait(?Send)]
+    impl Query for UserQuery {
+        type Input = u64;
+        type Error = Infallible;
+
+        async fn query(_states: &BounceStates, input: Rc<u64>) -> QueryResult<Self> {
+            Ok(UserQuery {
+                name: format!("fetched {input}"),
+            }
+            .into())
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct UserProps {
+        id: u64,
+    }
+
+    #[function_component(User)]
+    fn user(props: &UserProps) -> Html {
+        let user = use_query_value::<UserQuery>(props.id.into());
+        let name = user
+            .result()
+            .map(|m| m.as_ref().unwrap().name.clone())
+            .unwrap_or_else(|| "loading".into());
+
+        html! { <div id={format!("user-{}", props.id)}>{name}</div> }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        let get_services = Callback::from(|_| {
+            Services::new().provide(QueryCacheSeed::new(SEED).with_query::<UserQuery>("user"))
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <User id={1} />
+                <User id={2} />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // Seeded results are available without fetching, other inputs are fetched as usual.
+    let s = get_text_content("#user-1").await;
+    assert_eq!(s, "Jane Doe");
+
+    sleep(Duration::from_millis(25)).await;
+    let s = get_text_content("#user-2").await;
+    assert_eq!(s, "fetched 2");
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "query")]
+
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::prelude::*;
+use bounce::query::{
+    use_query_client, use_query_value, use_query_view, Query, QueryClient, QueryResult, QueryView,
+};
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static RUNS: Cell<u32> = const { Cell::new(0) };
+    static DERIVES: Cell<u32> = const { Cell::new(0) };
+    static LAST_VALUE: RefCell<Weak<NamesQuery>> = RefCell::default();
+    static CLIENT: RefCell<Option<QueryClient>> = RefCell::default();
+}
+
+#[derive(Debug, PartialEq)]
+struct NamesQuery {
+    names: Vec<String>,
+}
+
+#[async_trait(?Send)]
+impl Query for NamesQuery {
+    type Input = u64;
+    type Error = Infallible;
+
+    async fn query(_states: &BounceStates, _input: Rc<u64>) -> QueryResult<Self> {
+        RUNS.with(|m| m.set(m.get() + 1));
+        sleep(Duration::from_millis(10)).await;
+
+        let value = Rc::new(NamesQuery {
+            names: vec!["b".into(), "c".into(), "a".into()],
+        });
+        LAST_VALUE.with(|m| *m.borrow_mut() = Rc::downgrade(&value));
+
+        Ok(value)
+    }
+}
+
+#[derive(PartialEq)]
+struct SortedNames(String);
+
+impl QueryView<NamesQuery> for SortedNames {
+    fn derive(_input: &u64, value: &NamesQuery) -> Self {
+        DERIVES.with(|m| m.set(m.get() + 1));
+
+        let mut names = value.names.clone();
+        names.sort();
+
+        Self(names.join(","))
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ViewProps {
+    id: &'static str,
+}
+
+#[function_component(View)]
+fn view(props: &ViewProps) -> Html {
+    let content = match use_query_view::<NamesQuery, SortedNames>(1.into()) {
+        Some(Ok(m)) => m.0.clone(),
+        Some(Err(_)) => unreachable!(),
+        None => "loading".to_string(),
+    };
+
+    html! { <div id={props.id}>{content}</div> }
+}
+
+#[function_component(Fetcher)]
+fn fetcher() -> Html {
+    let _names = use_query_value::<NamesQuery>(1.into());
+    let client = use_query_client();
+    CLIENT.with(|m| *m.borrow_mut() = Some(client));
+
+    Html::default()
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    fetch: bool,
+}
+
+#[function_component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <BounceRoot>
+            if props.fetch {
+                <Fetcher />
+            }
+            <View id="view-1" />
+            <View id="view-2" />
+        </BounceRoot>
+    }
+}
+
+#[test]
+async fn test_query_view_cache() {
+    let mut handle = yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps { fetch: true },
+    )
+    .render();
+
+    assert_eq!(get_text_content("#view-1").await, "loading");
+
+    sleep(Duration::from_millis(50)).await;
+    // The view is derived once and shared by both components.
+    assert_eq!(get_text_content("#view-1").await, "a,b,c");
+    assert_eq!(get_text_content("#view-2").await, "a,b,c");
+    assert_eq!(RUNS.with(|m| m.get()), 1);
+    assert_eq!(DERIVES.with(|m| m.get()), 1);
+
+    // The fetching component is unmounted, the result stays in the cache.
+    handle.update(RootProps { fetch: false });
+    sleep(Duration::ZERO).await;
+    assert_eq!(get_text_content("#view-1").await, "a,b,c");
+    assert!(LAST_VALUE.with(|m| m.borrow().upgrade()).is_some());
+
+    // Removing the result evicts the view and releases the query value.
+    CLIENT
+        .with(|m| m.borrow().clone())
+        .unwrap()
+        .remove::<NamesQuery>(1);
+    assert_eq!(get_text_content("#view-1").await, "loading");
+    assert_eq!(get_text_content("#view-2").await, "loading");
+    assert!(LAST_VALUE.with(|m| m.borrow().upgrade()).is_none());
+
+    // The view is derived again when the query is fetched again.
+    handle.update(RootProps { fetch: true });
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(get_text_content("#view-1").await, "a,b,c");
+    assert_eq!(get_text_content("#view-2").await, "a,b,c");
+    assert_eq!(RUNS.with(|m| m.get()), 2);
+    assert_eq!(DERIVES.with(|m| m.get()), 2);
+}
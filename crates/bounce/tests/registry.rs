@@ -0,0 +1,128 @@
+use std::any::{type_name, TypeId};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{use_registered_states, BounceRoot, StateDescriptor, StateKind};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+fn click<S: AsRef<str>>(selector: S) {
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+#[test]
+async fn test_registered_states() {
+    thread_local! {
+        static STATES: RefCell<Vec<StateDescriptor>> = const { RefCell::new(Vec::new()) };
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Count(u64);
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Name(String);
+
+    #[derive(PartialEq)]
+    struct IsEven(bool);
+
+    impl Selector for IsEven {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            Self(states.get_atom_value::<Count>().0 % 2 == 0).into()
+        }
+    }
+
+    #[function_component(CountView)]
+    fn count_view() -> Html {
+        let count = use_atom_value::<Count>();
+
+        html! { <div>{count.0}</div> }
+    }
+
+    #[function_component(IsEvenView)]
+    fn is_even_view() -> Html {
+        let is_even = use_selector_value::<IsEven>();
+
+        html! { <div>{is_even.0}</div> }
+    }
+
+    #[function_component(NameView)]
+    fn name_view() -> Html {
+        let name = use_atom_value::<Name>();
+
+        html! { <div>{name.0.clone()}</div> }
+    }
+
+    #[function_component(Inspector)]
+    fn inspector() -> Html {
+        let renders = use_state(|| 0);
+        let states = use_registered_states();
+        STATES.with(|m| *m.borrow_mut() = states);
+
+        let onclick = {
+            let renders = renders.clone();
+            Callback::from(move |_| renders.set(*renders + 1))
+        };
+
+        html! { <button id="inspect" {onclick}>{*renders}</button> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <CountView />
+                <IsEvenView />
+                <NameView />
+                <NameView />
+                <Inspector />
+            </BounceRoot>
+        }
+    }
+
+    fn find<T: 'static>() -> StateDescriptor {
+        STATES.with(|m| {
+            m.borrow()
+                .iter()
+                .find(|m| m.type_id == TypeId::of::<T>())
+                .cloned()
+                .unwrap_or_else(|| panic!("{} is not registered", type_name::<T>()))
+        })
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+    sleep(Duration::ZERO).await;
+
+    // The states are listed once all components have subscribed to them.
+    click("#inspect");
+    sleep(Duration::ZERO).await;
+
+    let count = find::<Count>();
+    assert_eq!(count.kind, StateKind::Atom);
+    assert_eq!(count.type_name, type_name::<Count>());
+    // The count is read by a component and the selector.
+    assert_eq!(count.listener_count, 2);
+
+    let is_even = find::<IsEven>();
+    assert_eq!(is_even.kind, StateKind::Selector);
+    assert_eq!(is_even.type_name, type_name::<IsEven>());
+    assert_eq!(is_even.listener_count, 1);
+
+    let name = find::<Name>();
+    assert_eq!(name.kind, StateKind::Atom);
+    assert_eq!(name.type_name, type_name::<Name>());
+    assert_eq!(name.listener_count, 2);
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "router")]
+
+use std::convert::Infallible;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use async_trait::async_trait;
+use bounce::prelude::*;
+use bounce::query::{use_route_query, FromRoute, Query, QueryResult};
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
+use yew_router::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[derive(Routable, Clone, PartialEq)]
+enum Route {
+    #[at("/users/:id")]
+    User { id: u64 },
+    #[at("/")]
+    Home,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct UserInput {
+    id: u64,
+    tab: String,
+}
+
+impl FromRoute for UserInput {
+    type Route = Route;
+
+    fn from_route(route: &Route, location: &Location) -> Option<Self> {
+        let tab = location
+            .query_str()
+            .trim_start_matches('?')
+            .strip_prefix("tab=")
+            .unwrap_or("profile")
+            .to_string();
+
+        match route {
+            Route::User { id } => Some(UserInput { id: *id, tab }),
+            Route::Home => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct UserQuery {
+    value: String,
+}
+
+#[async_trait(?Send)]
+impl Query for UserQuery {
+    type Input = UserInput;
+    type Error = Infallible;
+
+    async fn query(_states: &BounceStates, input: Rc<UserInput>) -> QueryResult<Self> {
+        Ok(UserQuery {
+            value: format!("{} {}", input.id, input.tab),
+        }
+        .into())
+    }
+}
+
+#[test]
+async fn test_route_query() {
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let user = match use_route_query::<UserQuery>() {
+            Some(m) => m,
+            None => return html! { <div id="user">{"no input"}</div> },
+        };
+
+        match user.result() {
+            None => html! { <div id="user">{"loading"}</div> },
+            Some(Ok(m)) => html! { <div id="user">{&m.value}</div> },
+            Some(Err(_)) => html! { <div id="user">{"error"}</div> },
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct RootProps {
+        history: AnyHistory,
+    }
+
+    #[function_component(Root)]
+    fn root(props: &RootProps) -> Html {
+        html! {
+            <BounceRoot>
+                <Router history={props.history.clone()}>
+                    <Comp />
+                </Router>
+            </BounceRoot>
+        }
+    }
+
+    // The route does not provide an input when the component is rendered for the first time.
+    let history = AnyHistory::from(MemoryHistory::with_entries(vec!["/"]));
+
+    yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps {
+            history: history.clone(),
+        },
+    )
+    .render();
+
+    let s = get_text_content("#user").await;
+    assert_eq!(s, "no input");
+
+    history.push("/users/1");
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#user").await;
+    assert_eq!(s, "1 profile");
+
+    // The query is run again with the input derived from the new route.
+    history.push("/users/2?tab=posts");
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#user").await;
+    assert_eq!(s, "2 posts");
+
+    // The last input is kept when the route does not provide one.
+    history.push("/");
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#user").await;
+    assert_eq!(s, "2 posts");
+}
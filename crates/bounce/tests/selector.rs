@@ -0,0 +1,228 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{with_batched_updates, BounceRoot};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_selector_no_torn_reads() {
+    /// Sets both atoms to the same value.
+    pub struct SetBoth(u32);
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(SetBoth))]
+    struct First(u32);
+
+    impl WithNotion<SetBoth> for First {
+        fn apply(self: Rc<Self>, notion: Rc<SetBoth>) -> Rc<Self> {
+            Self(notion.0).into()
+        }
+    }
+
+    #[derive(Atom, PartialEq, Default)]
+    #[bounce(with_notion(SetBoth))]
+    struct Second(u32);
+
+    impl WithNotion<SetBoth> for Second {
+        fn apply(self: Rc<Self>, notion: Rc<SetBoth>) -> Rc<Self> {
+            Self(notion.0).into()
+        }
+    }
+
+    type Actions = (Rc<dyn Fn(SetBoth)>, Rc<dyn Fn(First)>, Rc<dyn Fn(Second)>);
+
+    thread_local! {
+        static SEEN: RefCell<Vec<(u32, u32)>> = RefCell::default();
+        static ACTIONS: RefCell<Option<Actions>> = RefCell::default();
+    }
+
+    fn actions() -> Actions {
+        ACTIONS.with(|m| m.borrow().clone()).unwrap()
+    }
+
+    /// A selector that reads both atoms, and records every pair of values it has seen.
+    #[derive(PartialEq)]
+    struct Pair(u32, u32);
+
+    impl Selector for Pair {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            let first = states.get_atom_value::<First>().0;
+            let second = states.get_atom_value::<Second>().0;
+            SEEN.with(|m| m.borrow_mut().push((first, second)));
+
+            Self(first, second).into()
+        }
+    }
+
+    /// Reads the first atom before the selector is notified.
+    #[function_component(FirstView)]
+    fn first_view() -> Html {
+        let first = use_atom_value::<First>();
+
+        html! { <div id="first">{first.0}</div> }
+    }
+
+    #[function_component(PairView)]
+    fn pair_view() -> Html {
+        let pair = use_selector_value::<Pair>();
+
+        html! { <div id="pair">{format!("{} {}", pair.0, pair.1)}</div> }
+    }
+
+    #[function_component(Actor)]
+    fn actor() -> Html {
+        let set_both = use_notion_applier::<SetBoth>();
+        let set_first = use_atom_setter::<First>();
+        let set_second = use_atom_setter::<Second>();
+
+        ACTIONS.with(|m| *m.borrow_mut() = Some((set_both, set_first, set_second)));
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Actor />
+                <FirstView />
+                <PairView />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    assert_eq!(get_text_content("#pair").await, "0 0");
+
+    // Both atoms are changed by 1 notion.
+    let (set_both, set_first, set_second) = actions();
+    set_both(SetBoth(1));
+    assert_eq!(get_text_content("#pair").await, "1 1");
+
+    // Both atoms are changed by 2 setters in 1 batch.
+    with_batched_updates(|| {
+        set_first(First(2));
+        set_second(Second(2));
+    });
+    assert_eq!(get_text_content("#pair").await, "2 2");
+
+    set_both(SetBoth(3));
+    assert_eq!(get_text_content("#first").await, "3");
+    assert_eq!(get_text_content("#pair").await, "3 3");
+
+    // The selector has never seen one atom updated without the other.
+    let seen = SEEN.with(|m| m.borrow().clone());
+    assert!(!seen.is_empty());
+    assert!(
+        seen.iter().all(|(first, second)| first == second),
+        "{seen:?}"
+    );
+}
+
+#[test]
+async fn test_selector_value_or_suspend() {
+    #[derive(Atom, PartialEq, Default)]
+    struct Config {
+        endpoint: Option<String>,
+    }
+
+    #[derive(PartialEq)]
+    struct Endpoint(String);
+
+    impl TrySelector for Endpoint {
+        fn try_select(states: &BounceStates) -> SelectorResult<Self> {
+            match states.get_atom_value::<Config>().endpoint {
+                Some(ref m) => SelectorResult::Ready(Self(m.clone()).into()),
+                None => SelectorResult::NotReady,
+            }
+        }
+    }
+
+    thread_local! {
+        static SET_CONFIG: RefCell<Option<Callback<Config>>> = const { RefCell::new(None) };
+    }
+
+    #[function_component(EndpointView)]
+    fn endpoint_view() -> HtmlResult {
+        let endpoint = use_selector_value_or_suspend::<Endpoint>()?;
+
+        Ok(html! { <div id="endpoint">{&endpoint.0}</div> })
+    }
+
+    #[function_component(Loader)]
+    fn loader() -> Html {
+        let set_config = use_atom_setter::<Config>();
+        SET_CONFIG.with(|m| *m.borrow_mut() = Some(Callback::from(move |m| set_config(m))));
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let fallback = html! { <div id="endpoint">{"loading"}</div> };
+
+        html! {
+            <BounceRoot>
+                <Loader />
+                <div>
+                    <Suspense {fallback}>
+                        <EndpointView />
+                    </Suspense>
+                </div>
+            </BounceRoot>
+        }
+    }
+
+    fn set_config(endpoint: Option<&str>) {
+        let set_config = SET_CONFIG.with(|m| m.borrow().clone().unwrap());
+        set_config.emit(Config {
+            endpoint: endpoint.map(|m| m.to_string()),
+        });
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // The component is suspended while the selector is not ready.
+    let s = get_text_content("#endpoint").await;
+    assert_eq!(s, "loading");
+
+    // The component is resumed once the selector is ready.
+    set_config(Some("https://example.com"));
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#endpoint").await;
+    assert_eq!(s, "https://example.com");
+
+    // The component renders the value of the selector after it has been resumed.
+    set_config(Some("https://example.org"));
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#endpoint").await;
+    assert_eq!(s, "https://example.org");
+
+    // The component is suspended again if the selector is no longer ready.
+    set_config(None);
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#endpoint").await;
+    assert_eq!(s, "loading");
+}
@@ -0,0 +1,109 @@
+// Panics can only be caught on targets that unwind, so these tests are run natively.
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use futures::executor::block_on;
+use yew::prelude::*;
+
+#[derive(Atom, PartialEq, Default)]
+struct Divisor {
+    inner: u64,
+}
+
+#[derive(PartialEq)]
+struct Quotient {
+    inner: u64,
+}
+
+impl Selector for Quotient {
+    fn select(states: &BounceStates) -> Rc<Self> {
+        let divisor = states.get_atom_value::<Divisor>().inner;
+        assert!(divisor != 0, "the divisor is zero");
+
+        Self {
+            inner: 100 / divisor,
+        }
+        .into()
+    }
+}
+
+thread_local! {
+    static RESULTS: RefCell<Vec<Result<u64, String>>> = RefCell::default();
+}
+
+#[function_component(FallibleReader)]
+fn fallible_reader() -> Html {
+    let quotient = use_selector_value_fallible::<Quotient>();
+    RESULTS.with(|m| {
+        m.borrow_mut()
+            .push(quotient.map(|m| m.inner).map_err(|e| e.to_string()))
+    });
+
+    Html::default()
+}
+
+#[function_component(Reader)]
+fn reader() -> Html {
+    let _quotient = use_selector_value::<Quotient>();
+
+    Html::default()
+}
+
+#[function_component(Setter)]
+fn setter() -> Html {
+    let set_divisor = use_atom_setter::<Divisor>();
+    use_state(move || set_divisor(Divisor { inner: 4 }));
+
+    Html::default()
+}
+
+#[test]
+fn test_selector_poisoned_and_recovered() {
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <FallibleReader />
+                <Setter />
+                <FallibleReader />
+            </BounceRoot>
+        }
+    }
+
+    block_on(yew::LocalServerRenderer::<Root>::new().render());
+
+    // The selector is selected again after the state it has read changes.
+    let results = RESULTS.with(|m| m.take());
+    assert_eq!(results.len(), 2);
+    assert!(results[0]
+        .as_ref()
+        .unwrap_err()
+        .ends_with("has panicked: the divisor is zero"));
+    assert_eq!(results[1], Ok(25));
+}
+
+#[test]
+fn test_selector_poisoned_resumes_panic() {
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Reader />
+            </BounceRoot>
+        }
+    }
+
+    let payload = panic::catch_unwind(AssertUnwindSafe(|| {
+        block_on(yew::LocalServerRenderer::<Root>::new().render())
+    }))
+    .unwrap_err();
+
+    // The panic is resumed with the error of the selector.
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.ends_with("has panicked: the divisor is zero"));
+}
@@ -0,0 +1,126 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{BounceRoot, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_get_service() {
+    struct ApiClient {
+        base_url: &'static str,
+    }
+
+    trait TokenProvider {
+        fn token(&self) -> String;
+    }
+
+    struct StaticToken;
+
+    impl TokenProvider for StaticToken {
+        fn token(&self) -> String {
+            "secret".into()
+        }
+    }
+
+    // A service that is never provided.
+    struct Analytics;
+
+    #[derive(PartialEq)]
+    struct ServiceSummary(String);
+
+    impl Selector for ServiceSummary {
+        fn select(states: &BounceStates) -> Rc<Self> {
+            let base_url = states
+                .get_service::<ApiClient>()
+                .map(|m| m.base_url)
+                .unwrap_or("none");
+            let token = states
+                .get_service::<dyn TokenProvider>()
+                .map(|m| m.token())
+                .unwrap_or_else(|| "none".into());
+            let analytics = states.get_service::<Analytics>().is_some();
+
+            Self(format!("{base_url} {token} {analytics}")).into()
+        }
+    }
+
+    #[function_component(Summary)]
+    fn summary() -> Html {
+        let summary = use_selector_value::<ServiceSummary>();
+
+        html! { <div id="services">{summary.0.clone()}</div> }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct RootProps {
+        provided: bool,
+    }
+
+    #[function_component(Root)]
+    fn root(props: &RootProps) -> Html {
+        let provided = props.provided;
+        let get_services = Callback::from(move |_| {
+            if !provided {
+                return Services::new();
+            }
+
+            let token: Rc<dyn TokenProvider> = Rc::new(StaticToken);
+
+            Services::new()
+                .provide(ApiClient {
+                    base_url: "https://old.example.com",
+                })
+                // Providing the same type again replaces the previous service.
+                .provide(ApiClient {
+                    base_url: "https://api.example.com",
+                })
+                .provide_rc(token)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Summary />
+            </BounceRoot>
+        }
+    }
+
+    let handle = yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps { provided: true },
+    )
+    .render();
+
+    // Provided services are read back, services that are not provided are None.
+    let s = get_text_content("#services").await;
+    assert_eq!(s, "https://api.example.com secret false");
+
+    handle.destroy();
+
+    // Without services, every service is None.
+    yew::Renderer::<Root>::with_root_and_props(
+        document().query_selector("#output").unwrap().unwrap(),
+        RootProps { provided: false },
+    )
+    .render();
+
+    let s = get_text_content("#services").await;
+    assert_eq!(s, "none none false");
+}
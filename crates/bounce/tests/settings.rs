@@ -0,0 +1,274 @@
+#![cfg(feature = "settings")]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::persist::{HostStorage, PersistStorage};
+use bounce::settings::{use_setting, use_settings, Settings};
+use bounce::{BounceRoot, PersistGate, Services};
+use gloo::storage::{LocalStorage, Storage};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+async fn click<S: AsRef<str>>(selector: S) {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+#[derive(Settings, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[bounce(key = "bounce-test-settings", version = 2, migrate = migrate_settings)]
+#[serde(default)]
+struct TestSettings {
+    /// Use the dark theme.
+    dark_mode: bool,
+    /// The size of the font
+    /// in pixels.
+    font_size: u32,
+    language: Option<String>,
+}
+
+impl Default for TestSettings {
+    fn default() -> Self {
+        Self {
+            dark_mode: false,
+            font_size: 16,
+            language: None,
+        }
+    }
+}
+
+fn migrate_settings(version: u32, mut value: Value) -> Option<Value> {
+    match version {
+        // Version 1 stored the font size as a string.
+        1 => {
+            let font_size = value.get("font_size")?.as_str()?.parse::<u32>().ok()?;
+            value["font_size"] = font_size.into();
+            Some(value)
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn test_settings_fields() {
+    assert_eq!(TestSettings::KEY, "bounce-test-settings");
+    assert_eq!(TestSettings::VERSION, 2);
+
+    let fields = TestSettings::fields()
+        .into_iter()
+        .map(|m| (m.name, m.ty, m.default, m.description))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        fields,
+        vec![
+            ("dark_mode", "bool", json!(false), "Use the dark theme."),
+            (
+                "font_size",
+                "u32",
+                json!(16),
+                "The size of the font in pixels."
+            ),
+            ("language", "Option<String>", Value::Null, ""),
+        ]
+    );
+
+    assert_eq!(
+        TestSettings::migrate(1, json!({ "font_size": "20" })),
+        Some(json!({ "font_size": 20 }))
+    );
+    assert_eq!(TestSettings::migrate(3, json!({})), None);
+}
+
+#[test]
+async fn test_settings_hooks() {
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let settings = use_settings::<TestSettings>();
+        let font_size = use_setting::<TestSettings, _>(|m| &mut m.font_size);
+
+        let increase_font_size = {
+            let font_size = font_size.clone();
+            Callback::from(move |_| font_size.set(*font_size + 1))
+        };
+
+        let set_language = {
+            let settings = settings.clone();
+            Callback::from(move |_| {
+                settings.set_field("language", json!("en")).unwrap();
+                assert!(settings.set_field("unknown", json!(1)).is_err());
+                assert!(settings.set_field("font_size", json!("large")).is_err());
+            })
+        };
+
+        let reset = {
+            let settings = settings.clone();
+            Callback::from(move |_| settings.reset())
+        };
+
+        html! {
+            <div>
+                <div id="settings">
+                    {format!("{} {:?} {}", settings.font_size, settings.language, *font_size)}
+                </div>
+                <div id="language">{settings.get_field("language").unwrap().to_string()}</div>
+                <button id="increase" onclick={increase_font_size}>{"Increase"}</button>
+                <button id="set-language" onclick={set_language}>{"Set language"}</button>
+                <button id="reset" onclick={reset}>{"Reset"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    // Settings of version 1 are migrated when they are restored.
+    LocalStorage::set(
+        "bounce-test-settings",
+        json!({ "version": 1, "value": { "dark_mode": false, "font_size": "14" } }),
+    )
+    .unwrap();
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // Settings are restored asynchronously.
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#settings").await;
+    assert_eq!(s, "14 None 14");
+
+    click("#increase").await;
+    let s = get_text_content("#settings").await;
+    assert_eq!(s, "15 None 15");
+
+    click("#set-language").await;
+    let s = get_text_content("#settings").await;
+    assert_eq!(s, "15 Some(\"en\") 15");
+    let s = get_text_content("#language").await;
+    assert_eq!(s, "\"en\"");
+
+    let stored = LocalStorage::get::<Value>("bounce-test-settings").unwrap();
+    assert_eq!(
+        stored,
+        json!({
+            "version": 2,
+            "value": { "dark_mode": false, "font_size": 15, "language": "en" },
+        })
+    );
+
+    click("#reset").await;
+    let s = get_text_content("#settings").await;
+    assert_eq!(s, "16 None 16");
+}
+
+#[test]
+async fn test_settings_host_storage() {
+    thread_local! {
+        static STORE: RefCell<HashMap<String, String>> = RefCell::default();
+    }
+
+    #[derive(Settings, Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+    #[bounce(key = "host-settings")]
+    struct HostSettings {
+        font_size: u32,
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let font_size = use_setting::<HostSettings, _>(|m| &mut m.font_size);
+
+        let onclick = {
+            let font_size = font_size.clone();
+            Callback::from(move |_| font_size.set(*font_size + 1))
+        };
+
+        html! {
+            <div>
+                <div id="font-size">{*font_size}</div>
+                <button id="increase" {onclick}>{"Increase"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let storage: Rc<dyn PersistStorage> = Rc::new(HostStorage::new(
+                |key| async move {
+                    sleep(Duration::from_millis(10)).await;
+                    Ok(STORE.with(|m| m.borrow().get(&key).cloned()))
+                },
+                |key, value| async move {
+                    STORE.with(|m| m.borrow_mut().insert(key, value));
+                    Ok(())
+                },
+            ));
+
+            Services::new().provide_rc(storage)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <PersistGate loading={html! {<div id="font-size">{"Loading..."}</div>}}>
+                    <Comp />
+                </PersistGate>
+            </BounceRoot>
+        }
+    }
+
+    STORE.with(|m| {
+        m.borrow_mut().insert(
+            "host-settings".to_string(),
+            r#"{"version":1,"value":{"font_size":20}}"#.to_string(),
+        )
+    });
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // The settings are restored from the storage of the root.
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#font-size").await;
+    assert_eq!(s, "20");
+
+    click("#increase").await;
+    sleep(Duration::from_millis(10)).await;
+    let stored = STORE.with(|m| m.borrow().get("host-settings").cloned());
+    assert_eq!(
+        stored.as_deref(),
+        Some(r#"{"version":1,"value":{"font_size":21}}"#)
+    );
+}
@@ -0,0 +1,241 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::{BounceRoot, LazySubscriptions};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_lazy_subscriptions() {
+    #[derive(PartialEq, Default, Atom)]
+    struct Counter(u64);
+
+    #[function_component(Reader)]
+    fn reader() -> Html {
+        let ctr = use_atom_value::<Counter>();
+
+        html! { <div id="lazy-ctr">{ctr.0}</div> }
+    }
+
+    #[function_component(Incrementer)]
+    fn incrementer() -> Html {
+        let set_ctr = use_atom_setter::<Counter>();
+
+        use_effect_with((), move |_| {
+            set_ctr(Counter(1));
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <LazySubscriptions>
+                    <Reader />
+                </LazySubscriptions>
+                <Incrementer />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // The value that is set before the listener is attached is read when the browser is idle.
+    sleep(Duration::from_millis(100)).await;
+    let s = get_text_content("#lazy-ctr").await;
+    assert_eq!(s, "1");
+}
+
+#[test]
+async fn test_custom_eq() {
+    #[derive(Atom)]
+    #[bounce(eq = "Document::same_revision")]
+    struct Document {
+        revision: u64,
+        content: &'static str,
+    }
+
+    impl Document {
+        fn same_revision(&self, other: &Self) -> bool {
+            self.revision == other.revision
+        }
+    }
+
+    impl Default for Document {
+        fn default() -> Self {
+            Self {
+                revision: 0,
+                content: "initial",
+            }
+        }
+    }
+
+    #[function_component(Reader)]
+    fn reader() -> Html {
+        let doc = use_atom_value::<Document>();
+
+        html! { <div id="custom-eq">{doc.content}</div> }
+    }
+
+    #[function_component(Setter)]
+    fn setter() -> Html {
+        let set_doc = use_atom_setter::<Document>();
+
+        use_effect_with((), move |_| {
+            // The revision is unchanged, so the value is considered equal.
+            set_doc(Document {
+                revision: 0,
+                content: "ignored",
+            });
+
+            yew::platform::spawn_local(async move {
+                sleep(Duration::from_millis(50)).await;
+
+                set_doc(Document {
+                    revision: 1,
+                    content: "updated",
+                });
+            });
+
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Reader />
+                <Setter />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#custom-eq").await;
+    assert_eq!(s, "initial");
+
+    sleep(Duration::from_millis(100)).await;
+    let s = get_text_content("#custom-eq").await;
+    assert_eq!(s, "updated");
+}
+
+#[test]
+async fn test_dispatch_with_output() {
+    use std::cell::RefCell;
+
+    use bounce::{use_slice_dispatch_with_output, ReducibleWithOutput};
+
+    enum TodoAction {
+        Add(&'static str),
+    }
+
+    #[derive(PartialEq, Default, Clone, Slice)]
+    struct Todos {
+        next_id: u64,
+        items: Vec<(u64, &'static str)>,
+    }
+
+    impl ReducibleWithOutput for Todos {
+        type Output = u64;
+
+        fn reduce_with_output(self: Rc<Self>, action: TodoAction) -> (Rc<Self>, u64) {
+            match action {
+                TodoAction::Add(title) => {
+                    let mut next = self.clone_slice();
+                    let id = next.next_id;
+                    next.next_id += 1;
+                    next.items.push((id, title));
+
+                    (next.into(), id)
+                }
+            }
+        }
+    }
+
+    impl Reducible for Todos {
+        type Action = TodoAction;
+
+        fn reduce(self: Rc<Self>, action: TodoAction) -> Rc<Self> {
+            self.reduce_with_output(action).0
+        }
+    }
+
+    thread_local! {
+        static OUTPUTS: RefCell<Vec<u64>> = RefCell::default();
+    }
+
+    #[function_component(Reader)]
+    fn reader() -> Html {
+        let todos = use_slice_value::<Todos>();
+
+        let titles = todos
+            .items
+            .iter()
+            .map(|(id, title)| format!("{id}:{title}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        html! { <div id="todos">{titles}</div> }
+    }
+
+    #[function_component(Adder)]
+    fn adder() -> Html {
+        let todos = use_slice::<Todos>();
+        let add_todo = use_slice_dispatch_with_output::<Todos>();
+
+        use_effect_with((), move |_| {
+            let first = todos.dispatch_with_output(TodoAction::Add("first"));
+            let second = add_todo(TodoAction::Add("second"));
+            OUTPUTS.with(|m| m.borrow_mut().extend([first, second]));
+
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Reader />
+                <Adder />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(50)).await;
+
+    // The outputs are the ids of the added items and subscribers are notified.
+    assert_eq!(OUTPUTS.with(|m| m.borrow().clone()), vec![0, 1]);
+    assert_eq!(get_text_content("#todos").await, "0:first,1:second");
+}
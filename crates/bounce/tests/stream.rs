@@ -0,0 +1,201 @@
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_atom_stream_and_sink() {
+    #[derive(Atom, PartialEq, Default)]
+    struct Source(u32);
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Mirror(u32);
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let source = use_atom::<Source>();
+        let mirror = use_atom::<Mirror>();
+
+        {
+            let source = source.clone();
+            let mirror = mirror.clone();
+            use_effect_with((), move |_| {
+                // Every change of the source is sent to the mirror.
+                let mut changes = source.to_stream();
+                let mut sink = mirror.to_sink();
+
+                spawn_local(async move {
+                    while let Some(m) = changes.next().await {
+                        sink.send(Mirror(m.0 * 10)).await.unwrap();
+                    }
+                });
+
+                || {}
+            });
+        }
+
+        {
+            let source = source.clone();
+            use_effect_with((), move |_| {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(10)).await;
+                    source.set(Source(1));
+                    sleep(Duration::from_millis(10)).await;
+                    source.set(Source(2));
+                });
+
+                || {}
+            });
+        }
+
+        html! {
+            <div>
+                <div id="source">{source.0}</div>
+                <div id="mirror">{mirror.0}</div>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#mirror").await;
+    assert_eq!(s, "0");
+
+    sleep(Duration::from_millis(50)).await;
+
+    let s = get_text_content("#source").await;
+    assert_eq!(s, "2");
+    let s = get_text_content("#mirror").await;
+    assert_eq!(s, "20");
+}
+
+#[test]
+async fn test_use_stream_atom() {
+    use std::cell::{Cell, RefCell};
+
+    use futures::channel::mpsc;
+
+    #[derive(Atom, PartialEq, Default)]
+    struct Ticks(u32);
+
+    thread_local! {
+        static SENDER: RefCell<Option<mpsc::UnboundedSender<Ticks>>> = const { RefCell::new(None) };
+        static STREAMS: Cell<u32> = const { Cell::new(0) };
+        static HIDE_SECOND: RefCell<Option<Callback<()>>> = const { RefCell::new(None) };
+    }
+
+    fn send(ticks: u32) {
+        SENDER.with(|m| {
+            m.borrow()
+                .as_ref()
+                .unwrap()
+                .unbounded_send(Ticks(ticks))
+                .unwrap()
+        });
+    }
+
+    fn is_stream_dropped() -> bool {
+        SENDER.with(|m| m.borrow().as_ref().unwrap().is_closed())
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct CompProps {
+        id: &'static str,
+    }
+
+    #[function_component(Comp)]
+    fn comp(props: &CompProps) -> Html {
+        let ticks = use_stream_atom::<Ticks, _, _>(|| {
+            STREAMS.with(|m| m.set(m.get() + 1));
+
+            let (tx, rx) = mpsc::unbounded();
+            SENDER.with(|m| *m.borrow_mut() = Some(tx));
+
+            rx
+        });
+
+        html! { <div id={props.id}>{ticks.0}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let shown = use_state(|| true);
+
+        {
+            let shown = shown.clone();
+            HIDE_SECOND.with(|m| *m.borrow_mut() = Some(Callback::from(move |_| shown.set(false))));
+        }
+
+        html! {
+            <BounceRoot>
+                <Comp id="first" />
+                if *shown {
+                    <Comp id="second" />
+                }
+            </BounceRoot>
+        }
+    }
+
+    let handle =
+        yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+            .render();
+
+    let s = get_text_content("#first").await;
+    assert_eq!(s, "0");
+
+    // The stream is shared by both components.
+    assert_eq!(STREAMS.with(|m| m.get()), 1);
+
+    // Each item of the stream updates the atom.
+    send(1);
+    assert_eq!(get_text_content("#first").await, "1");
+    assert_eq!(get_text_content("#second").await, "1");
+
+    send(2);
+    assert_eq!(get_text_content("#first").await, "2");
+    assert_eq!(get_text_content("#second").await, "2");
+
+    // The stream is kept while a component is connected to the atom.
+    HIDE_SECOND.with(|m| m.borrow().clone().unwrap().emit(()));
+    sleep(Duration::from_millis(10)).await;
+    assert!(!is_stream_dropped());
+
+    send(3);
+    assert_eq!(get_text_content("#first").await, "3");
+
+    // The stream is dropped when the last component is unmounted.
+    handle.destroy();
+    sleep(Duration::from_millis(10)).await;
+    assert!(is_stream_dropped());
+    assert_eq!(STREAMS.with(|m| m.get()), 1);
+}
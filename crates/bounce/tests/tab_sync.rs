@@ -0,0 +1,137 @@
+#![cfg(feature = "tab-sync")]
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::tab_sync::{use_tab_sync, ConflictResolution, TabSynced};
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use serde::{Deserialize, Serialize};
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+pub enum TagsAction {
+    Add(&'static str),
+}
+
+#[derive(Slice, Serialize, Deserialize, PartialEq, Default, Clone)]
+struct Tags {
+    items: Vec<String>,
+}
+
+impl Reducible for Tags {
+    type Action = TagsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            TagsAction::Add(m) => {
+                let mut items = self.items.clone();
+                items.push(m.to_string());
+                Self { items }.into()
+            }
+        }
+    }
+}
+
+impl TabSynced for Tags {
+    const CHANNEL: &'static str = "bounce-test-tags";
+    const CONFLICT_RESOLUTION: ConflictResolution<Self> = ConflictResolution::Merge(merge_tags);
+}
+
+fn merge_tags(local: &Tags, remote: &Tags) -> Tags {
+    let mut items = local.items.clone();
+    items.extend(remote.items.iter().cloned());
+    items.sort();
+    items.dedup();
+
+    Tags { items }
+}
+
+#[test]
+async fn test_tab_sync_merge() {
+    #[derive(Properties, PartialEq)]
+    struct TabProps {
+        id: &'static str,
+        tag: &'static str,
+    }
+
+    #[function_component(TagList)]
+    fn tag_list(props: &TabProps) -> Html {
+        let tags = use_slice_value::<Tags>();
+
+        html! { <div id={props.id}>{tags.items.join(",")}</div> }
+    }
+
+    #[function_component(TagsSync)]
+    fn tags_sync() -> Html {
+        use_tab_sync::<Tags>();
+
+        Html::default()
+    }
+
+    #[function_component(Adder)]
+    fn adder(props: &TabProps) -> Html {
+        let dispatch = use_slice_dispatch::<Tags>();
+        let tag = props.tag;
+
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                sleep(Duration::from_millis(10)).await;
+                dispatch(TagsAction::Add(tag));
+            });
+
+            || {}
+        });
+
+        Html::default()
+    }
+
+    #[function_component(Tab)]
+    fn tab(props: &TabProps) -> Html {
+        html! {
+            <BounceRoot>
+                <TagsSync />
+                <Adder id={props.id} tag={props.tag} />
+                <TagList id={props.id} tag={props.tag} />
+            </BounceRoot>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <>
+                <Tab id="tab-1" tag="a" />
+                <Tab id="tab-2" tag="b" />
+            </>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    // Tags added in both roots at the same time are merged.
+    sleep(Duration::from_millis(100)).await;
+
+    let s = get_text_content("#tab-1").await;
+    assert_eq!(s, "a,b");
+    let s = get_text_content("#tab-2").await;
+    assert_eq!(s, "a,b");
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::testing::{wait_for_state, WaitTimedOut};
+use bounce::BounceRoot;
+use gloo::utils::document;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+
+#[test]
+async fn test_wait_for_state() {
+    #[derive(Atom, PartialEq, Default)]
+    struct Counter {
+        inner: u32,
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let ctr = use_atom::<Counter>();
+
+        {
+            let ctr = ctr.clone();
+            use_effect_with(ctr.inner, move |inner| {
+                let inner = *inner;
+                if inner < 3 {
+                    yew::platform::spawn_local(async move {
+                        sleep(Duration::from_millis(10)).await;
+                        ctr.set(Counter { inner: inner + 1 });
+                    });
+                }
+
+                || {}
+            });
+        }
+
+        html! { <div id="ctr">{ctr.inner}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let ctr = wait_for_state::<Counter>(|m| m.inner == 3, Duration::from_secs(1))
+        .await
+        .unwrap();
+    assert_eq!(ctr.inner, 3);
+
+    let timeout = Duration::from_millis(50);
+    let result = wait_for_state::<Counter>(|m| m.inner == 4, timeout).await;
+    assert_eq!(result.err(), Some(WaitTimedOut { timeout }));
+}
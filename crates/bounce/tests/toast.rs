@@ -0,0 +1,183 @@
+#![cfg(feature = "toast")]
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::toast::{use_push_toast, use_toasts, Toast};
+use bounce::{BounceRoot, Clock, ManualClock, Services};
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+thread_local! {
+    static CLOCK: ManualClock = ManualClock::new();
+}
+
+#[test]
+async fn test_toast_ttl() {
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let toasts = use_toasts();
+        let push_toast = use_push_toast();
+
+        use_effect_with((), move |_| {
+            push_toast(Toast::info("a").with_ttl(Duration::from_secs(5)));
+            push_toast(Toast::info("b").with_ttl(Duration::from_secs(10)));
+            push_toast(Toast::info("c").persistent());
+            || {}
+        });
+
+        let messages = toasts
+            .iter()
+            .map(|m| m.message.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        html! { <div id="toasts">{messages}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let get_services = Callback::from(|_| {
+            let clock: Rc<dyn Clock> = Rc::new(CLOCK.with(|m| m.clone()));
+            Services::new().provide_rc(clock)
+        });
+
+        html! {
+            <BounceRoot {get_services}>
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#toasts").await;
+    assert_eq!(s, "a,b,c");
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(5)));
+    let s = get_text_content("#toasts").await;
+    assert_eq!(s, "b,c");
+
+    CLOCK.with(|m| m.advance(Duration::from_secs(5)));
+    let s = get_text_content("#toasts").await;
+    assert_eq!(s, "c");
+
+    // Persistent toasts are only dismissed manually.
+    CLOCK.with(|m| m.advance(Duration::from_secs(60)));
+    let s = get_text_content("#toasts").await;
+    assert_eq!(s, "c");
+}
+
+#[cfg(feature = "query")]
+#[test]
+async fn test_query_and_mutation_error_toasts() {
+    use async_trait::async_trait;
+    use bounce::prelude::*;
+    use bounce::query::{
+        use_mutation, use_query_value, Mutation, MutationErrorNotion, MutationResult, ProgressSink,
+        Query, QueryResult,
+    };
+    use bounce::toast::QueryErrorToasts;
+    use yew::platform::spawn_local;
+
+    #[derive(Debug, Clone, PartialEq, thiserror::Error)]
+    #[error("{0}")]
+    struct ApiError(&'static str);
+
+    #[derive(PartialEq)]
+    struct ProfileQuery;
+
+    #[async_trait(?Send)]
+    impl Query for ProfileQuery {
+        type Input = ();
+        type Error = ApiError;
+
+        async fn query(_states: &BounceStates, _input: Rc<()>) -> QueryResult<Self> {
+            Err(ApiError("failed to load"))
+        }
+    }
+
+    #[derive(PartialEq)]
+    struct SaveMutation;
+
+    #[async_trait(?Send)]
+    impl Mutation for SaveMutation {
+        type Input = ();
+        type Error = ApiError;
+
+        async fn run(
+            _states: &BounceStates,
+            _input: Rc<()>,
+            _progress: ProgressSink,
+        ) -> MutationResult<Self> {
+            sleep(Duration::from_millis(10)).await;
+
+            Err(ApiError("failed to save"))
+        }
+    }
+
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let toasts = use_toasts();
+        let _profile = use_query_value::<ProfileQuery>(().into());
+        let save = use_mutation::<SaveMutation>();
+
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                sleep(Duration::from_millis(20)).await;
+                let _result = save.run(()).await;
+            });
+            || {}
+        });
+
+        let messages = toasts
+            .iter()
+            .map(|m| m.message.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        html! { <div id="toasts">{messages}</div> }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let mutation_to_toast = Callback::from(|error: Rc<MutationErrorNotion>| {
+            Some(Toast::error(format!("mutation: {}", error.message)).persistent())
+        });
+
+        html! {
+            <BounceRoot>
+                <QueryErrorToasts {mutation_to_toast} />
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    sleep(Duration::from_millis(10)).await;
+    let s = get_text_content("#toasts").await;
+    assert_eq!(s, "failed to load");
+
+    sleep(Duration::from_millis(50)).await;
+    let s = get_text_content("#toasts").await;
+    assert_eq!(s, "failed to load,mutation: failed to save");
+}
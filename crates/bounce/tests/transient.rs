@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+fn click<S: AsRef<str>>(selector: S) {
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+#[test]
+async fn test_transient_scope() {
+    #[derive(Atom, PartialEq, Default)]
+    struct Draft(u32);
+
+    #[function_component(Editor)]
+    fn editor() -> Html {
+        let draft = use_atom::<Draft>();
+
+        let onclick = {
+            let draft = draft.clone();
+            Callback::from(move |_| draft.set(Draft(draft.0 + 1)))
+        };
+
+        html! {
+            <div>
+                <div id="editor">{draft.0}</div>
+                <button id="edit" {onclick}>{"Edit"}</button>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        let open = use_state(|| true);
+        let draft = use_atom_value::<Draft>();
+
+        let onclick = {
+            let open = open.clone();
+            Callback::from(move |_| open.set(!*open))
+        };
+
+        html! {
+            <div>
+                <div id="outside">{draft.0}</div>
+                <button id="toggle" {onclick}>{"Toggle"}</button>
+                if *open {
+                    <TransientScope states={TransientStates::new().with_atom::<Draft>()}>
+                        <Editor />
+                    </TransientScope>
+                }
+            </div>
+        }
+    }
+
+    #[function_component(App)]
+    fn app() -> Html {
+        html! {
+            <BounceRoot>
+                <Root />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<App>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let s = get_text_content("#editor").await;
+    assert_eq!(s, "0");
+
+    click("#edit");
+    click("#edit");
+
+    let s = get_text_content("#editor").await;
+    assert_eq!(s, "2");
+    let s = get_text_content("#outside").await;
+    assert_eq!(s, "2");
+
+    // The draft is reset when the scope is unmounted.
+    click("#toggle");
+
+    let s = get_text_content("#outside").await;
+    assert_eq!(s, "0");
+
+    click("#toggle");
+
+    let s = get_text_content("#editor").await;
+    assert_eq!(s, "0");
+}
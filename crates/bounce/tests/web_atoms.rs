@@ -0,0 +1,62 @@
+#![cfg(feature = "web-atoms")]
+
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+use bounce::prelude::*;
+use bounce::web_atoms::{Breakpoint, WebAtoms, WindowSize};
+use bounce::BounceRoot;
+use gloo::timers::future::sleep;
+use gloo::utils::{document, window};
+use yew::prelude::*;
+
+async fn get_text_content<S: AsRef<str>>(selector: S) -> String {
+    sleep(Duration::ZERO).await;
+
+    document()
+        .query_selector(selector.as_ref())
+        .unwrap()
+        .unwrap()
+        .text_content()
+        .unwrap()
+}
+
+#[test]
+async fn test_web_atoms_window_size() {
+    #[function_component(Comp)]
+    fn comp() -> Html {
+        let size = use_atom_value::<WindowSize>();
+        let breakpoint = use_selector_value::<Breakpoint>();
+
+        html! {
+            <div>
+                <div id="width">{size.width}</div>
+                <div id="breakpoint">{format!("{:?}", breakpoint)}</div>
+            </div>
+        }
+    }
+
+    #[function_component(Root)]
+    fn root() -> Html {
+        html! {
+            <BounceRoot>
+                <WebAtoms />
+                <Comp />
+            </BounceRoot>
+        }
+    }
+
+    yew::Renderer::<Root>::with_root(document().query_selector("#output").unwrap().unwrap())
+        .render();
+
+    let width = window().inner_width().unwrap().as_f64().unwrap();
+
+    let s = get_text_content("#width").await;
+    assert_eq!(s, width.to_string());
+
+    let s = get_text_content("#breakpoint").await;
+    assert_eq!(s, format!("{:?}", Breakpoint::from_width(width)));
+}
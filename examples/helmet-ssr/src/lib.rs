@@ -1,4 +1,4 @@
-use bounce::helmet::{Helmet, HelmetBridge};
+use bounce::helmet::{CsrOnly, Helmet, HelmetBridge};
 use bounce::BounceRoot;
 use yew::prelude::*;
 use yew_router::prelude::*;
@@ -71,15 +71,16 @@ fn render_fn(route: Route) -> Html {
     }
 }
 
-fn format_title(s: AttrValue) -> AttrValue {
-    format!("{s} - Example").into()
-}
+// A template is applied identically by the server and the client, so the hydrated title
+// matches the server-side rendered title.
+const TITLE_TEMPLATE: &str = "%s - Example";
 
 #[function_component(App)]
 pub fn app() -> Html {
     html! {
         <BounceRoot>
-            <HelmetBridge default_title="Example" {format_title} />
+            // The client application is not rendered on the server.
+            <HelmetBridge default_title="Example" title_template={TITLE_TEMPLATE} writer={CsrOnly} />
             <Helmet>
                 <meta charset="utf-8" />
                 <meta name="description" content="default page" />
@@ -120,7 +121,7 @@ mod feat_ssr {
             <BounceRoot>
                 <HelmetBridge
                     default_title="Example"
-                    {format_title}
+                    title_template={TITLE_TEMPLATE}
                     writer={props.helmet_writer.clone()}
                 />
                 <Helmet>
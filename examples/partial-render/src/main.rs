@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+use bounce::devtools::{use_render_count, Devtools};
 use bounce::prelude::*;
 use bounce::BounceRoot;
 use log::Level;
@@ -54,15 +55,7 @@ impl Reducible for SliceC {
 fn comp_a() -> Html {
     let a = use_slice_value::<SliceA>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompA");
 
     html! {
         <div>
@@ -76,15 +69,7 @@ fn comp_a() -> Html {
 fn comp_b() -> Html {
     let b = use_slice_value::<SliceB>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompB");
 
     html! {
         <div>
@@ -98,15 +83,7 @@ fn comp_b() -> Html {
 fn comp_c() -> Html {
     let c = use_slice_value::<SliceC>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompC");
 
     html! {
         <div>
@@ -121,15 +98,7 @@ fn comp_ab() -> Html {
     let a = use_slice_value::<SliceA>();
     let b = use_slice_value::<SliceB>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompAB");
 
     html! {
         <div>
@@ -145,15 +114,7 @@ fn comp_ac() -> Html {
     let a = use_slice_value::<SliceA>();
     let c = use_slice_value::<SliceC>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompAC");
 
     html! {
         <div>
@@ -169,15 +130,7 @@ fn comp_bc() -> Html {
     let b = use_slice_value::<SliceB>();
     let c = use_slice_value::<SliceC>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompBC");
 
     html! {
         <div>
@@ -194,15 +147,7 @@ fn comp_abc() -> Html {
     let b = use_slice_value::<SliceB>();
     let c = use_slice_value::<SliceC>();
 
-    let ctr = {
-        let ctr = use_mut_ref(|| 0);
-
-        let mut ctr = ctr.borrow_mut();
-
-        *ctr += 1;
-
-        *ctr
-    };
+    let ctr = use_render_count("CompABC");
 
     html! {
         <div class={css!(r#"
@@ -241,25 +186,27 @@ fn setters() -> Html {
 fn app() -> Html {
     html! {
         <BounceRoot>
-            <div>
-                <div class={css!(r#"
-                    grid-template-columns: auto auto auto;
-                    display: grid;
-
-                    width: 600px;
-                "#)}>
-                    <CompA />
-                    <CompB />
-                    <CompC />
-
-                    <CompAB />
-                    <CompAC />
-                    <CompBC />
-
-                    <CompABC />
+            <Devtools count_renders={true}>
+                <div>
+                    <div class={css!(r#"
+                        grid-template-columns: auto auto auto;
+                        display: grid;
+
+                        width: 600px;
+                    "#)}>
+                        <CompA />
+                        <CompB />
+                        <CompC />
+
+                        <CompAB />
+                        <CompAC />
+                        <CompBC />
+
+                        <CompABC />
+                    </div>
+                    <Setters />
                 </div>
-                <Setters />
-            </div>
+            </Devtools>
         </BounceRoot>
     }
 }
@@ -489,5 +436,12 @@ mod tests {
             get_text_content_by_id("val-abc-render-ctr").await,
             "Rendered: 4 Time(s)"
         );
+
+        // The renders are also aggregated by the devtools.
+        let counts = bounce::devtools::render_counts();
+        let abc = counts.iter().find(|m| m.tag == "CompABC").unwrap();
+        assert_eq!((abc.mounts, abc.rerenders()), (1, 3));
+        let c = counts.iter().find(|m| m.tag == "CompC").unwrap();
+        assert_eq!((c.mounts, c.rerenders()), (1, 1));
     }
 }
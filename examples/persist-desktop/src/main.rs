@@ -0,0 +1,186 @@
+//! Persists a slice to disk through the commands of a Tauri host shell.
+//!
+//! When the application is run in Tauri, the notes are stored with 2 commands of the host:
+//!
+//! ```ignore
+//! #[tauri::command]
+//! fn load_state(app: tauri::AppHandle, key: String) -> Result<Option<String>, String> {
+//!     let path = app.path().app_data_dir().map_err(|e| e.to_string())?.join(key);
+//!
+//!     match std::fs::read_to_string(path) {
+//!         Ok(m) => Ok(Some(m)),
+//!         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+//!         Err(e) => Err(e.to_string()),
+//!     }
+//! }
+//!
+//! #[tauri::command]
+//! fn save_state(app: tauri::AppHandle, key: String, value: String) -> Result<(), String> {
+//!     let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+//!     std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+//!
+//!     std::fs::write(dir.join(key), value).map_err(|e| e.to_string())
+//! }
+//! ```
+//!
+//! In a browser, the notes are stored in the local storage instead.
+
+use std::rc::Rc;
+
+use bounce::persist::{
+    use_persist, HostStorage, PersistError, PersistStorage, Persisted, WebStorage,
+};
+use bounce::prelude::*;
+use bounce::{BounceRoot, PersistGate, Services};
+use log::Level;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+enum NotesAction {
+    Add(String),
+    Clear,
+}
+
+#[derive(Slice, Serialize, Deserialize, PartialEq, Default)]
+struct Notes {
+    items: Vec<String>,
+}
+
+impl Reducible for Notes {
+    type Action = NotesAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            NotesAction::Add(m) => {
+                let mut items = self.items.clone();
+                items.push(m);
+                Self { items }.into()
+            }
+            NotesAction::Clear => Self::default().into(),
+        }
+    }
+}
+
+impl Persisted for Notes {
+    const KEY: &'static str = "notes.json";
+}
+
+/// Returns the `invoke` function of Tauri, if the application is run in Tauri.
+fn tauri_invoke() -> Option<js_sys::Function> {
+    let tauri = js_sys::Reflect::get(&js_sys::global(), &"__TAURI__".into()).ok()?;
+    let core = js_sys::Reflect::get(&tauri, &"core".into()).ok()?;
+
+    js_sys::Reflect::get(&core, &"invoke".into())
+        .ok()?
+        .dyn_into()
+        .ok()
+}
+
+/// Runs a command of the host shell.
+async fn invoke(cmd: &str, args: &[(&str, &str)]) -> Result<JsValue, PersistError> {
+    let invoke = tauri_invoke().ok_or_else(|| PersistError::new("Tauri is not available"))?;
+
+    let js_args = js_sys::Object::new();
+    for (name, value) in args {
+        js_sys::Reflect::set(&js_args, &(*name).into(), &(*value).into())
+            .map_err(|e| PersistError::new(format!("{:?}", e)))?;
+    }
+
+    let promise = invoke
+        .call2(&JsValue::NULL, &cmd.into(), &js_args)
+        .map_err(|e| PersistError::new(format!("{:?}", e)))?;
+
+    JsFuture::from(js_sys::Promise::from(promise))
+        .await
+        .map_err(|e| PersistError::new(format!("{:?}", e)))
+}
+
+/// Creates the storage of the application.
+fn create_storage() -> Rc<dyn PersistStorage> {
+    if tauri_invoke().is_none() {
+        return Rc::new(WebStorage);
+    }
+
+    Rc::new(HostStorage::new(
+        |key| async move {
+            let value = invoke("load_state", &[("key", &key)]).await?;
+
+            Ok(value.as_string())
+        },
+        |key, value| async move {
+            invoke("save_state", &[("key", &key), ("value", &value)]).await?;
+
+            Ok(())
+        },
+    ))
+}
+
+#[function_component(NotesPersist)]
+fn notes_persist() -> Html {
+    use_persist::<Notes>();
+
+    Html::default()
+}
+
+#[function_component(NoteList)]
+fn note_list() -> Html {
+    let notes = use_slice_value::<Notes>();
+
+    html! {
+        <ul id="notes">
+            {for notes.items.iter().map(|m| html! { <li>{m}</li> })}
+        </ul>
+    }
+}
+
+#[function_component(NoteEditor)]
+fn note_editor() -> Html {
+    let dispatch = use_slice_dispatch::<Notes>();
+    let input_ref = use_node_ref();
+
+    let on_add = {
+        let dispatch = dispatch.clone();
+        let input_ref = input_ref.clone();
+
+        Callback::from(move |_| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                dispatch(NotesAction::Add(input.value()));
+                input.set_value("");
+            }
+        })
+    };
+
+    let on_clear = Callback::from(move |_| dispatch(NotesAction::Clear));
+
+    html! {
+        <div>
+            <input id="input" type="text" ref={input_ref} />
+            <button id="add" onclick={on_add}>{"Add"}</button>
+            <button id="clear" onclick={on_clear}>{"Clear"}</button>
+        </div>
+    }
+}
+
+#[function_component(App)]
+fn app() -> Html {
+    let get_services = Callback::from(|_| Services::new().provide_rc(create_storage()));
+
+    html! {
+        <BounceRoot {get_services}>
+            <NotesPersist />
+            <PersistGate loading={html! {<div>{"Loading notes..."}</div>}}>
+                <NoteList />
+                <NoteEditor />
+            </PersistGate>
+            <div>{"Notes are saved to disk by the host shell, or to the local storage in a browser."}</div>
+        </BounceRoot>
+    }
+}
+
+fn main() {
+    console_log::init_with_level(Level::Trace).expect("Failed to initialise Log!");
+    yew::Renderer::<App>::new().render();
+}
@@ -1,4 +1,3 @@
-use std::fmt;
 use std::rc::Rc;
 
 use bounce::*;
@@ -9,17 +8,11 @@ use yew::prelude::*;
 use yew::InputEvent;
 
 #[derive(PartialEq, Atom, Eq)]
-#[bounce(observed)]
+#[bounce(transparent, observed)]
 struct Username {
     inner: String,
 }
 
-impl From<String> for Username {
-    fn from(s: String) -> Self {
-        Self { inner: s }
-    }
-}
-
 impl Default for Username {
     fn default() -> Self {
         Self {
@@ -28,12 +21,6 @@ impl Default for Username {
     }
 }
 
-impl fmt::Display for Username {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.inner)
-    }
-}
-
 impl Observed for Username {
     fn changed(self: Rc<Self>) {
         LocalStorage::set("username", &self.inner).expect("failed to set username.");
@@ -3,8 +3,8 @@ use std::rc::Rc;
 use async_trait::async_trait;
 use bounce::prelude::*;
 use bounce::query::{
-    use_mutation, use_query, use_query_value, Mutation, MutationResult, MutationState, Query,
-    QueryResult, QueryState, QueryValueState,
+    use_mutation, use_query, use_query_value, Mutation, MutationResult, MutationState,
+    ProgressSink, Query, QueryResult, QueryState, QueryValueState,
 };
 use bounce::BounceRoot;
 use log::Level;
@@ -46,7 +46,11 @@ impl Mutation for EchoMutation {
     type Input = EchoInput;
     type Error = Infallible;
 
-    async fn run(_states: &BounceStates, input: Rc<Self::Input>) -> MutationResult<Self> {
+    async fn run(
+        _states: &BounceStates,
+        input: Rc<Self::Input>,
+        _progress: ProgressSink,
+    ) -> MutationResult<Self> {
         // We manually delay this for testing.
         #[cfg(test)]
         yew::platform::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -232,6 +236,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bounce::testing::{wait_for_query, QueryStatus};
     use gloo::timers::future::sleep;
     use gloo::utils::document;
     use std::time::Duration;
@@ -277,27 +282,19 @@ mod tests {
             "Loading UUID, Please wait..."
         );
 
-        let mut found = false;
-        for _i in 0..1000 {
-            sleep(Duration::from_millis(100)).await;
-
-            if get_text_content_by_id("query-content-0")
-                .await
-                .starts_with("Random UUID: ")
-            {
-                // ensure only 1 request is sent.
-                assert_eq!(
-                    get_text_content_by_id("query-content-0").await,
-                    get_text_content_by_id("query-content-1").await
-                );
-
-                found = true;
+        wait_for_query::<UuidQuery>((), QueryStatus::Completed, Duration::from_secs(100))
+            .await
+            .expect("request didn't finish in time!");
 
-                break;
-            }
-        }
+        assert!(get_text_content_by_id("query-content-0")
+            .await
+            .starts_with("Random UUID: "));
 
-        assert!(found, "request didn't finish in time!");
+        // ensure only 1 request is sent.
+        assert_eq!(
+            get_text_content_by_id("query-content-0").await,
+            get_text_content_by_id("query-content-1").await
+        );
 
         let uuid_found = get_text_content_by_id("query-content-0").await;
         click_by_id("query-refresh").await;
@@ -313,30 +310,22 @@ mod tests {
             get_text_content_by_id("query-content-1").await
         );
 
-        let mut found = false;
-        for _i in 0..1000 {
-            sleep(Duration::from_millis(100)).await;
-
-            if get_text_content_by_id("query-content-0")
-                .await
-                .starts_with("Random UUID: ")
-            {
-                // assert uuid changed.
-                assert_ne!(uuid_found, get_text_content_by_id("query-content-0").await);
-
-                // ensure only 1 request is sent.
-                assert_eq!(
-                    get_text_content_by_id("query-content-0").await,
-                    get_text_content_by_id("query-content-1").await
-                );
+        wait_for_query::<UuidQuery>((), QueryStatus::Completed, Duration::from_secs(100))
+            .await
+            .expect("request didn't finish in time!");
 
-                found = true;
+        assert!(get_text_content_by_id("query-content-0")
+            .await
+            .starts_with("Random UUID: "));
 
-                break;
-            }
-        }
+        // assert uuid changed.
+        assert_ne!(uuid_found, get_text_content_by_id("query-content-0").await);
 
-        assert!(found, "request didn't finish in time!");
+        // ensure only 1 request is sent.
+        assert_eq!(
+            get_text_content_by_id("query-content-0").await,
+            get_text_content_by_id("query-content-1").await
+        );
 
         document()
             .query_selector("#mut-input")
@@ -24,20 +24,15 @@ async fn fetch_uuid(_input: &()) -> String {
     uuid_resp.uuid
 }
 
-#[derive(PartialEq, Atom, Eq)]
+#[derive(PartialEq, Atom, Eq, Default)]
 #[bounce(with_notion(Deferred<FetchUuid>))]
 enum UuidState {
+    #[default]
     NotStarted,
     Pending,
     Complete(String),
 }
 
-impl Default for UuidState {
-    fn default() -> UuidState {
-        Self::NotStarted
-    }
-}
-
 impl WithNotion<Deferred<FetchUuid>> for UuidState {
     fn apply(self: Rc<Self>, notion: Rc<Deferred<FetchUuid>>) -> Rc<Self> {
         match notion.output() {
@@ -1,5 +1,3 @@
-use std::fmt;
-
 use bounce::*;
 use log::Level;
 use web_sys::HtmlInputElement;
@@ -7,16 +5,11 @@ use yew::prelude::*;
 use yew::InputEvent;
 
 #[derive(PartialEq, Atom, Eq)]
+#[bounce(transparent)]
 struct Username {
     inner: String,
 }
 
-impl From<String> for Username {
-    fn from(s: String) -> Self {
-        Self { inner: s }
-    }
-}
-
 impl Default for Username {
     fn default() -> Self {
         Self {
@@ -25,12 +18,6 @@ impl Default for Username {
     }
 }
 
-impl fmt::Display for Username {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.inner)
-    }
-}
-
 #[function_component(Reader)]
 fn reader() -> Html {
     let username = use_atom_value::<Username>();
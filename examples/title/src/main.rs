@@ -13,28 +13,19 @@ use log::Level;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Artifact)]
+#[bounce(apply = apply_title)]
 pub struct Title {
     value: String,
 }
 
-/// A component to apply the title when it changes.
-#[function_component(TitleApplier)]
-pub fn title_applier() -> Html {
-    let titles = use_artifacts::<Title>();
-
+fn apply_title(titles: &[Rc<Title>]) {
     let title = titles
         .last()
-        .map(|m| m.value.to_owned())
-        .unwrap_or_else(|| "unknown title".into());
-
-    use_effect_with(title, |m| {
-        document().set_title(m);
-
-        || {}
-    });
+        .map(|m| m.value.as_str())
+        .unwrap_or("unknown title");
 
-    Html::default()
+    document().set_title(title);
 }
 
 #[derive(Routable, PartialEq, Clone, Eq)]
@@ -51,7 +42,7 @@ pub enum Route {
 fn a() -> Html {
     html! {
         <>
-            <Artifact<Title> value={Rc::new(Title { value: "Page A - Example".into() })} />
+            {Title { value: "Page A - Example".into() }.into_artifact()}
             <div>{"This is page A."}</div>
         </>
     }
@@ -68,7 +59,7 @@ fn b() -> Html {
 fn home() -> Html {
     html! {
         <>
-            <Artifact<Title> value={Rc::new(Title { value: "Home Page - Example".into() })} />
+            {Title { value: "Home Page - Example".into() }.into_artifact()}
             <div>{"This is home page."}</div>
         </>
     }
@@ -97,8 +88,8 @@ fn render_fn(route: Route) -> Html {
 fn app() -> Html {
     html! {
         <BounceRoot>
-            <TitleApplier />
-            <Artifact<Title> value={Rc::new(Title { value: "Example".into() })} />
+            <ArtifactApplier<Title> />
+            {Title { value: "Example".into() }.into_artifact()}
             <BrowserRouter>
                 <Switch<Route> render={render_fn} />
                 <Links />